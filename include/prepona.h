@@ -0,0 +1,72 @@
+/*
+ * C API of prepona, available when the crate is built with the `ffi` feature.
+ *
+ * Every function returns a `PreponaStatus`, except the ones that can not fail.
+ * Graphs are created by `prepona_graph_new` and must be freed by `prepona_graph_free`.
+ * Vertices can not be removed, so ids of the vertices are 0..vertex_count and
+ * algorithms write their results to arrays indexed by id of the vertices.
+ */
+#ifndef PREPONA_H
+#define PREPONA_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Distance written for vertices that are not reachable from the source vertex. */
+#define PREPONA_UNREACHABLE UINT64_MAX
+
+typedef enum PreponaStatus {
+    PREPONA_OK = 0,
+    PREPONA_NULL_POINTER = 1,
+    PREPONA_BUFFER_TOO_SMALL = 2,
+    PREPONA_VERTEX_NOT_FOUND = 3,
+    PREPONA_LOOP = 4,
+    PREPONA_MULTI_EDGE = 5,
+    PREPONA_WEIGHT_OVERFLOW = 6,
+    PREPONA_OTHER = 7,
+} PreponaStatus;
+
+typedef struct PreponaGraph PreponaGraph;
+
+PreponaGraph *prepona_graph_new(bool directed);
+
+void prepona_graph_free(PreponaGraph *graph);
+
+PreponaStatus prepona_graph_add_vertex(PreponaGraph *graph, size_t *out_vertex_id);
+
+/* `out_edge_id` can be NULL. */
+PreponaStatus prepona_graph_add_edge(PreponaGraph *graph, size_t src_id, size_t dst_id, uint64_t weight,
+                                     size_t *out_edge_id);
+
+size_t prepona_graph_vertex_count(const PreponaGraph *graph);
+
+size_t prepona_graph_edge_count(const PreponaGraph *graph);
+
+/* `distances_len` must be at least the number of vertices. */
+PreponaStatus prepona_dijkstra(const PreponaGraph *graph, size_t src_id, uint64_t *out_distances,
+                               size_t distances_len);
+
+/* On PREPONA_BUFFER_TOO_SMALL the required capacity is written to `out_path_len`. */
+PreponaStatus prepona_shortest_path(const PreponaGraph *graph, size_t src_id, size_t dst_id, size_t *out_path,
+                                    size_t path_capacity, size_t *out_path_len, uint64_t *out_distance);
+
+/* `labels_len` must be at least the number of vertices. `out_component_count` can be NULL. */
+PreponaStatus prepona_connected_components(const PreponaGraph *graph, size_t *out_labels, size_t labels_len,
+                                           size_t *out_component_count);
+
+/*
+ * `status` is a value of `PreponaStatus`. Values that are not one of its variants get "unknown status".
+ * Returned string is statically allocated and must not be freed.
+ */
+const char *prepona_status_message(uint32_t status);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* PREPONA_H */
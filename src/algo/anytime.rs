@@ -0,0 +1,191 @@
+use std::time::{Duration, Instant};
+
+// Number of iterations between two checks of the clock, because reading the clock costs more than an iteration of most algorithms.
+const ITERATIONS_PER_CLOCK_CHECK: usize = 64;
+
+/// Limits the time and the number of iterations that an anytime algorithm may use.
+///
+/// Anytime algorithms, like [`MaxClique`](crate::algo::MaxClique), [`VertexColoring`](crate::algo::VertexColoring) and [`Tsp`](crate::algo::Tsp),
+/// search for an optimal solution but can be stopped at any time, returning the best solution found so far.
+/// What an iteration is depends on the algorithm, for example visiting a node of the search tree in branch and bound algorithms.
+/// If both limits are set, the algorithm stops when either of them is reached.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Budget {
+    time_limit: Option<Duration>,
+    iteration_limit: Option<usize>,
+}
+
+impl Budget {
+    /// # Returns
+    /// Budget without any limit, so the algorithm runs until it finds an optimal solution.
+    pub fn init() -> Self {
+        Budget {
+            time_limit: None,
+            iteration_limit: None,
+        }
+    }
+
+    /// # Arguments
+    /// `time_limit`: Maximum time the algorithm may run for. The clock is checked every few iterations, so it may run a little longer.
+    /// Standard library has no clock on `wasm32-unknown-unknown`, so use an iteration limit on that target.
+    ///
+    /// # Returns
+    /// The budget that is exhausted once `time_limit` has passed.
+    pub fn with_time_limit(mut self, time_limit: Duration) -> Self {
+        self.time_limit = Some(time_limit);
+
+        self
+    }
+
+    /// # Arguments
+    /// `iteration_limit`: Maximum number of iterations the algorithm may use.
+    /// Unlike the time limit, it gives the same result on every run.
+    ///
+    /// # Returns
+    /// The budget that is exhausted once `iteration_limit` iterations are used.
+    pub fn with_iteration_limit(mut self, iteration_limit: usize) -> Self {
+        self.iteration_limit = Some(iteration_limit);
+
+        self
+    }
+
+    pub(crate) fn start(&self) -> BudgetClock {
+        BudgetClock {
+            deadline: self
+                .time_limit
+                .map(|time_limit| Instant::now() + time_limit),
+            iteration_limit: self.iteration_limit,
+            iteration_count: 0,
+            is_exhausted: false,
+        }
+    }
+}
+
+// Tracks how much of a budget is used by a single execution of an algorithm.
+pub(crate) struct BudgetClock {
+    deadline: Option<Instant>,
+    iteration_limit: Option<usize>,
+    iteration_count: usize,
+    is_exhausted: bool,
+}
+
+impl BudgetClock {
+    // Uses one iteration of the budget. Returns `false` if budget is exhausted, and keeps returning `false` afterwards.
+    pub fn tick(&mut self) -> bool {
+        if self.is_exhausted {
+            return false;
+        }
+
+        if self.iteration_limit == Some(self.iteration_count) {
+            self.is_exhausted = true;
+        } else if self.iteration_count % ITERATIONS_PER_CLOCK_CHECK == 0 {
+            self.is_exhausted = self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline);
+        }
+
+        self.iteration_count += 1;
+
+        !self.is_exhausted
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.is_exhausted
+    }
+}
+
+/// Best solution that an anytime algorithm found within its [`Budget`](crate::algo::Budget), and a bound on the value of the optimal solution.
+///
+/// For maximization problems the bound is an upper bound, and for minimization problems it's a lower bound.
+/// So the optimal value is between `value` and `bound`, and the solution is known to be optimal when they are equal.
+#[derive(Debug, Clone)]
+pub struct AnytimeSolution<S, V> {
+    solution: S,
+    value: V,
+    bound: V,
+}
+
+impl<S, V: Copy + PartialEq> AnytimeSolution<S, V> {
+    pub(crate) fn init(solution: S, value: V, bound: V) -> Self {
+        AnytimeSolution {
+            solution,
+            value,
+            bound,
+        }
+    }
+
+    /// # Returns
+    /// Best solution found.
+    pub fn solution(&self) -> &S {
+        &self.solution
+    }
+
+    /// # Returns
+    /// Value of the best solution found, like size of a clique or number of colors.
+    pub fn value(&self) -> V {
+        self.value
+    }
+
+    /// # Returns
+    /// Bound on the value of the optimal solution. Value of no solution is better than the bound.
+    pub fn bound(&self) -> V {
+        self.bound
+    }
+
+    /// # Returns
+    /// `true` if the solution is proven to be optimal, `false` otherwise.
+    pub fn is_optimal(&self) -> bool {
+        self.value == self.bound
+    }
+
+    /// # Returns
+    /// Best solution found.
+    pub fn into_solution(self) -> S {
+        self.solution
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlimited_budget() {
+        let mut clock = Budget::init().start();
+
+        assert!((0..1000).all(|_| clock.tick()));
+        assert!(!clock.is_exhausted());
+    }
+
+    #[test]
+    fn iteration_limit() {
+        // Given:
+        let mut clock = Budget::init().with_iteration_limit(3).start();
+
+        // When:
+        let ticks: Vec<bool> = (0..5).map(|_| clock.tick()).collect();
+
+        // Then:
+        assert_eq!(ticks, vec![true, true, true, false, false]);
+        assert!(clock.is_exhausted());
+    }
+
+    #[test]
+    fn time_limit() {
+        let mut clock = Budget::init()
+            .with_time_limit(Duration::from_millis(1))
+            .start();
+        std::thread::sleep(Duration::from_millis(5));
+
+        assert!(!clock.tick());
+    }
+
+    #[test]
+    fn optimal_solution() {
+        let solution = AnytimeSolution::init(vec![1, 2], 2, 2);
+
+        assert!(solution.is_optimal());
+        assert_eq!(solution.into_solution(), vec![1, 2]);
+        assert!(!AnytimeSolution::init((), 2, 3).is_optimal());
+    }
+}
@@ -0,0 +1,481 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use anyhow::Result;
+
+use crate::graph::{Edge, Error, ListGraph, UndirectedEdge};
+use crate::provide::{Edges, Graph, IdMap, Neighbors, Vertices};
+use crate::storage::List;
+
+/// What a vertex of a [`BlockCutTree`](crate::algo::BlockCutTree) stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockCutNode {
+    /// Index of a block in [`blocks`](crate::algo::BlockCutTree::blocks).
+    Block(usize),
+
+    /// Id of a cut vertex of the original graph.
+    Cut(usize),
+}
+
+/// Block-cut tree of an undirected graph.
+///
+/// Blocks are the biconnected components of the graph: maximal sets of vertices that stay connected after removing any one of them.
+/// Bridges form blocks with two vertices and isolated vertices form blocks with one vertex, so each vertex is in at least one block.
+/// Cut vertices(articulation points) are the vertices that are in more than one block.
+///
+/// The tree has one vertex for each block and one vertex for each cut vertex, and an edge between a block and each cut vertex in it.
+/// It's a forest with one tree for each connected component of the graph.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::BlockCutTree;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b  ---  d  ---  e
+/// //      |     /
+/// //      |   /
+/// //      | /
+/// //      c
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// let e = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, a, 1.into());
+/// graph.add_edge_unchecked(b, d, 1.into());
+/// graph.add_edge_unchecked(d, e, 1.into());
+///
+/// let block_cut_tree = BlockCutTree::init(&graph);
+///
+/// assert_eq!(block_cut_tree.blocks().len(), 3);
+/// assert_eq!(block_cut_tree.cut_vertices(), vec![b, d]);
+/// assert!(block_cut_tree.separates(a, e, d).unwrap());
+/// assert!(!block_cut_tree.separates(a, b, c).unwrap());
+/// ```
+pub struct BlockCutTree {
+    tree: ListGraph<usize, UndirectedEdge>,
+    blocks: Vec<Vec<usize>>,
+
+    // Indices of blocks that contain each vertex.
+    blocks_of: HashMap<usize, Vec<usize>>,
+
+    // Vertex of the tree for each block, and for each cut vertex.
+    block_nodes: Vec<usize>,
+    cut_nodes: HashMap<usize, usize>,
+
+    kind_of: HashMap<usize, BlockCutNode>,
+}
+
+// State of the depth first search that finds the blocks.
+struct BlockFinder {
+    id_map: IdMap,
+    depth_of: Vec<Option<usize>>,
+    low_of: Vec<usize>,
+    stack: Vec<usize>,
+    blocks: Vec<Vec<usize>>,
+}
+
+impl BlockFinder {
+    fn visit<W, E, G>(
+        &mut self,
+        graph: &G,
+        virt_id: usize,
+        parent_edge_id: Option<usize>,
+        depth: usize,
+    ) where
+        E: Edge<W>,
+        G: Edges<W, E> + Vertices,
+    {
+        self.depth_of[virt_id] = Some(depth);
+        self.low_of[virt_id] = depth;
+        self.stack.push(virt_id);
+
+        for (n_id, edge) in graph.edges_from_unchecked(self.id_map.real_id_of(virt_id)) {
+            let n_virt_id = self.id_map.virt_id_of(n_id);
+
+            // Loops do not affect connectivity, and only the edge to the parent itself is skipped so parallel edges count as a back edge.
+            if n_virt_id == virt_id || Some(edge.get_id()) == parent_edge_id {
+                continue;
+            }
+
+            match self.depth_of[n_virt_id] {
+                Some(n_depth) => self.low_of[virt_id] = self.low_of[virt_id].min(n_depth),
+                None => {
+                    self.visit(graph, n_virt_id, Some(edge.get_id()), depth + 1);
+                    self.low_of[virt_id] = self.low_of[virt_id].min(self.low_of[n_virt_id]);
+
+                    // Subtree of the neighbor can not reach above this vertex, so it forms a block with this vertex.
+                    if self.low_of[n_virt_id] >= depth {
+                        let mut block = vec![self.id_map.real_id_of(virt_id)];
+                        while let Some(b_virt_id) = self.stack.pop() {
+                            block.push(self.id_map.real_id_of(b_virt_id));
+
+                            if b_virt_id == n_virt_id {
+                                break;
+                            }
+                        }
+                        block.sort_unstable();
+
+                        self.blocks.push(block);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl BlockCutTree {
+    /// # Arguments
+    /// `graph`: Graph to build the block-cut tree of.
+    ///
+    /// # Returns
+    /// Block-cut tree of the graph.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|)
+    pub fn init<W, E, G>(graph: &G) -> Self
+    where
+        E: Edge<W>,
+        G: Edges<W, E> + Neighbors + Vertices + Graph<W, E, UndirectedEdge>,
+    {
+        let vertex_count = graph.vertex_count();
+
+        let mut finder = BlockFinder {
+            id_map: graph.continuous_id_map(),
+            depth_of: vec![None; vertex_count],
+            low_of: vec![0; vertex_count],
+            stack: vec![],
+            blocks: vec![],
+        };
+
+        for virt_id in 0..vertex_count {
+            if finder.depth_of[virt_id].is_none() {
+                let blocks_count = finder.blocks.len();
+                finder.visit(graph, virt_id, None, 0);
+
+                // Root is left on the stack, and it forms a block on its own when it has no neighbors.
+                finder.stack.pop();
+                if finder.blocks.len() == blocks_count {
+                    finder.blocks.push(vec![finder.id_map.real_id_of(virt_id)]);
+                }
+            }
+        }
+
+        let blocks = finder.blocks;
+
+        let mut blocks_of: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (index, block) in blocks.iter().enumerate() {
+            for vertex_id in block {
+                blocks_of.entry(*vertex_id).or_default().push(index);
+            }
+        }
+
+        let mut tree = ListGraph::init(List::init());
+        let mut kind_of = HashMap::new();
+
+        let block_nodes = (0..blocks.len())
+            .map(|index| {
+                let node_id = tree.add_vertex();
+                kind_of.insert(node_id, BlockCutNode::Block(index));
+                node_id
+            })
+            .collect::<Vec<usize>>();
+
+        let mut cut_vertices = blocks_of
+            .iter()
+            .filter(|(_, indices)| indices.len() > 1)
+            .map(|(vertex_id, _)| *vertex_id)
+            .collect::<Vec<usize>>();
+        cut_vertices.sort_unstable();
+
+        let mut cut_nodes = HashMap::new();
+        for vertex_id in cut_vertices {
+            let node_id = tree.add_vertex();
+            kind_of.insert(node_id, BlockCutNode::Cut(vertex_id));
+            cut_nodes.insert(vertex_id, node_id);
+
+            for index in &blocks_of[&vertex_id] {
+                tree.add_edge_unchecked(node_id, block_nodes[*index], 1.into());
+            }
+        }
+
+        BlockCutTree {
+            tree,
+            blocks,
+            blocks_of,
+            block_nodes,
+            cut_nodes,
+            kind_of,
+        }
+    }
+
+    /// # Returns
+    /// The block-cut tree as a graph. Use [`kind_of`](crate::algo::BlockCutTree::kind_of) to find what each of its vertices stands for.
+    pub fn tree(&self) -> &ListGraph<usize, UndirectedEdge> {
+        &self.tree
+    }
+
+    /// # Returns
+    /// Ids of the vertices of each block(sorted).
+    pub fn blocks(&self) -> &Vec<Vec<usize>> {
+        &self.blocks
+    }
+
+    /// # Returns
+    /// Ids of the cut vertices(sorted).
+    pub fn cut_vertices(&self) -> Vec<usize> {
+        let mut cut_vertices = self.cut_nodes.keys().copied().collect::<Vec<usize>>();
+        cut_vertices.sort_unstable();
+
+        cut_vertices
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of a vertex of the original graph.
+    ///
+    /// # Returns
+    /// `true` if removing the vertex disconnects its connected component, `false` otherwise.
+    pub fn is_cut_vertex(&self, vertex_id: usize) -> bool {
+        self.cut_nodes.contains_key(&vertex_id)
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of a vertex of the original graph.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing indices of the blocks that contain the vertex.
+    /// * `Err`: [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if vertex does not exist.
+    pub fn blocks_of(&self, vertex_id: usize) -> Result<&Vec<usize>> {
+        match self.blocks_of.get(&vertex_id) {
+            Some(indices) => Ok(indices),
+            None => Err(Error::new_vnf(vertex_id))?,
+        }
+    }
+
+    /// # Arguments
+    /// `block_index`: Index of the block.
+    ///
+    /// # Returns
+    /// Id of the vertex of the tree that stands for the block.
+    ///
+    /// # Panics
+    /// If `block_index` is not less than the number of blocks.
+    pub fn node_of_block(&self, block_index: usize) -> usize {
+        self.block_nodes[block_index]
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of a vertex of the original graph.
+    ///
+    /// # Returns
+    /// * `Some`: Containing id of the vertex of the tree that stands for the cut vertex.
+    /// * `None`: If vertex is not a cut vertex.
+    pub fn node_of_cut_vertex(&self, vertex_id: usize) -> Option<usize> {
+        self.cut_nodes.get(&vertex_id).copied()
+    }
+
+    /// # Arguments
+    /// `node_id`: Id of a vertex of the tree.
+    ///
+    /// # Returns
+    /// * `Some`: Containing what the vertex of the tree stands for.
+    /// * `None`: If vertex does not exist in the tree.
+    pub fn kind_of(&self, node_id: usize) -> Option<BlockCutNode> {
+        self.kind_of.get(&node_id).copied()
+    }
+
+    // Vertex of the tree that a vertex of the original graph belongs to.
+    fn node_of(&self, vertex_id: usize) -> Result<usize> {
+        match self.node_of_cut_vertex(vertex_id) {
+            Some(node_id) => Ok(node_id),
+            None => Ok(self.block_nodes[self.blocks_of(vertex_id)?[0]]),
+        }
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the first vertex.
+    /// * `dst_id`: Id of the second vertex.
+    /// * `vertex_id`: Id of the vertex to check.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing `true` if every path between `src_id` and `dst_id` passes through `vertex_id`, `false` otherwise.
+    ///   It's `true` if `vertex_id` is one of the end points, or if there is no path between the end points at all.
+    /// * `Err`: [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if any of the vertices does not exist.
+    ///
+    /// # Complexity
+    /// O(number of blocks + number of cut vertices)
+    pub fn separates(&self, src_id: usize, dst_id: usize, vertex_id: usize) -> Result<bool> {
+        let src_node = self.node_of(src_id)?;
+        let dst_node = self.node_of(dst_id)?;
+        self.blocks_of(vertex_id)?;
+
+        if vertex_id == src_id || vertex_id == dst_id {
+            return Ok(true);
+        }
+
+        let removed_node = match self.node_of_cut_vertex(vertex_id) {
+            Some(node_id) => node_id,
+            None => return Ok(!self.is_connected(src_node, dst_node, None)),
+        };
+
+        Ok(!self.is_connected(src_node, dst_node, Some(removed_node)))
+    }
+
+    // Whether `src_node` can reach `dst_node` in the tree without passing through `removed_node`.
+    fn is_connected(&self, src_node: usize, dst_node: usize, removed_node: Option<usize>) -> bool {
+        let mut is_visited = HashSet::new();
+        let mut queue = VecDeque::new();
+
+        is_visited.insert(src_node);
+        queue.push_back(src_node);
+
+        while let Some(node_id) = queue.pop_front() {
+            if node_id == dst_node {
+                return true;
+            }
+
+            for n_id in self.tree.neighbors_unchecked(node_id) {
+                if Some(n_id) != removed_node && is_visited.insert(n_id) {
+                    queue.push_back(n_id);
+                }
+            }
+        }
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::tree::is_forest;
+    use crate::graph::MatGraph;
+    use crate::storage::Mat;
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let block_cut_tree = BlockCutTree::init(&graph);
+
+        assert!(block_cut_tree.blocks().is_empty());
+        assert_eq!(block_cut_tree.tree().vertex_count(), 0);
+    }
+
+    #[test]
+    fn isolated_vertices_and_bridges() {
+        // Given: Graph
+        //
+        //      a       b  ---  c  ---  d
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+
+        // When:
+        let block_cut_tree = BlockCutTree::init(&graph);
+
+        // Then:
+        let mut blocks = block_cut_tree.blocks().clone();
+        blocks.sort();
+        assert_eq!(blocks, vec![vec![a], vec![b, c], vec![c, d]]);
+        assert_eq!(block_cut_tree.cut_vertices(), vec![c]);
+        assert!(block_cut_tree.is_cut_vertex(c));
+        assert!(!block_cut_tree.is_cut_vertex(b));
+        assert_eq!(block_cut_tree.blocks_of(c).unwrap().len(), 2);
+        assert!(block_cut_tree.blocks_of(d + 1).is_err());
+
+        // And: Every path between b and d passes through c, and there is no path between a and b.
+        assert!(block_cut_tree.separates(b, d, c).unwrap());
+        assert!(block_cut_tree.separates(a, b, c).unwrap());
+        assert!(!block_cut_tree.separates(c, d, b).unwrap());
+    }
+
+    #[test]
+    fn blocks_and_cut_vertices() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  e  ---  f
+        //      |       |       |     /
+        //      d  ---  c       |   /
+        //              |       | /
+        //              h       g  ---  i
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        let g = graph.add_vertex();
+        let h = graph.add_vertex();
+        let i = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+        graph.add_edge_unchecked(d, a, 1.into());
+        graph.add_edge_unchecked(b, e, 1.into());
+        graph.add_edge_unchecked(e, f, 1.into());
+        graph.add_edge_unchecked(f, g, 1.into());
+        graph.add_edge_unchecked(g, e, 1.into());
+        graph.add_edge_unchecked(c, h, 1.into());
+        graph.add_edge_unchecked(g, i, 1.into());
+
+        // When:
+        let block_cut_tree = BlockCutTree::init(&graph);
+
+        // Then:
+        let mut blocks = block_cut_tree.blocks().clone();
+        blocks.sort();
+        assert_eq!(
+            blocks,
+            vec![
+                vec![a, b, c, d],
+                vec![b, e],
+                vec![c, h],
+                vec![e, f, g],
+                vec![g, i]
+            ]
+        );
+        assert_eq!(block_cut_tree.cut_vertices(), vec![b, c, e, g]);
+
+        // And: Tree has a vertex for each block and each cut vertex, and it's a tree.
+        let tree = block_cut_tree.tree();
+        assert_eq!(tree.vertex_count(), 9);
+        assert_eq!(tree.edges_count(), 8);
+        assert!(is_forest(tree));
+
+        let b_node = block_cut_tree.node_of_cut_vertex(b).unwrap();
+        assert_eq!(block_cut_tree.kind_of(b_node), Some(BlockCutNode::Cut(b)));
+        assert_eq!(tree.neighbors_unchecked(b_node).len(), 2);
+        for n_id in tree.neighbors_unchecked(b_node) {
+            match block_cut_tree.kind_of(n_id) {
+                Some(BlockCutNode::Block(index)) => {
+                    assert!(block_cut_tree.blocks()[index].contains(&b))
+                }
+                _ => panic!("Cut vertex must be connected to blocks"),
+            }
+        }
+
+        // And: Queries.
+        assert!(block_cut_tree.separates(a, i, b).unwrap());
+        assert!(block_cut_tree.separates(a, i, e).unwrap());
+        assert!(block_cut_tree.separates(h, f, c).unwrap());
+        assert!(!block_cut_tree.separates(a, c, d).unwrap());
+        assert!(!block_cut_tree.separates(e, f, g).unwrap());
+        assert!(!block_cut_tree.separates(a, h, g).unwrap());
+        assert!(block_cut_tree.separates(a, b, b).unwrap());
+    }
+}
@@ -0,0 +1,438 @@
+use std::collections::HashMap;
+
+use crate::graph::{Edge, EdgeDir};
+use crate::provide::{Graph, IdMap, Neighbors, Vertices};
+
+// Code of a discrete coloring and the coloring itself.
+type Leaf = (Vec<(usize, usize)>, Vec<usize>);
+
+/// Structure of a graph that does not depend on ids of its vertices. Isomorphic graphs have equal codes, so codes can be hashed and compared to deduplicate graphs.
+///
+/// Weights of the edges are not part of the code.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CanonicalCode {
+    vertex_count: usize,
+    is_directed: bool,
+    edges: Vec<(usize, usize)>,
+}
+
+impl CanonicalCode {
+    /// # Returns
+    /// Number of vertices of the graph.
+    pub fn vertex_count(&self) -> usize {
+        self.vertex_count
+    }
+
+    /// # Returns
+    /// `true` if graph is directed, `false` otherwise.
+    pub fn is_directed(&self) -> bool {
+        self.is_directed
+    }
+
+    /// # Returns
+    /// Edges of the graph in the format of: (`src_label`, `dst_label`), in ascending order. For undirected edges `src_label` <= `dst_label`.
+    pub fn edges(&self) -> &Vec<(usize, usize)> {
+        &self.edges
+    }
+}
+
+/// Result of a [`CanonicalForm`](crate::algo::CanonicalForm).
+#[derive(Debug)]
+pub struct CanonicalLabeling {
+    label_of: HashMap<usize, usize>,
+    code: CanonicalCode,
+}
+
+impl CanonicalLabeling {
+    /// # Returns
+    /// Canonical label of each vertex, keyed by id of the vertex. Labels are in range of 0 to |V| - 1.
+    pub fn labels(&self) -> &HashMap<usize, usize> {
+        &self.label_of
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing canonical label of the vertex.
+    /// * `None`: If vertex does not exist.
+    pub fn label_of(&self, vertex_id: usize) -> Option<usize> {
+        self.label_of.get(&vertex_id).copied()
+    }
+
+    /// # Returns
+    /// Code of the graph after relabeling its vertices with their canonical labels.
+    pub fn code(&self) -> &CanonicalCode {
+        &self.code
+    }
+
+    /// # Returns
+    /// Code of the graph, consuming the labeling.
+    pub fn into_code(self) -> CanonicalCode {
+        self.code
+    }
+}
+
+/// Computes a canonical labeling of a graph: labels that are the same for all graphs that are isomorphic to each other.
+///
+/// Vertices are first colored by color refinement, which splits vertices with the same color by the colors of their neighbors until colors do not change.
+/// If some vertices still share a color, each of them is given a distinct color in turn and refinement is repeated. Among all the discrete colorings
+/// found this way, the one that relabels the graph to the smallest [`CanonicalCode`](crate::algo::CanonicalCode) is chosen.
+///
+/// The search is exact but explores every branch, so it can take exponential time on highly symmetric graphs. It's meant for small graphs such as motifs.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::CanonicalForm;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Two paths with vertices added in different orders.
+/// //
+/// //      a  ---  b  ---  c           y  ---  x  ---  z
+/// //
+/// let mut graph1 = MatGraph::init(Mat::<usize>::init());
+/// let a = graph1.add_vertex();
+/// let b = graph1.add_vertex();
+/// let c = graph1.add_vertex();
+/// graph1.add_edge_unchecked(a, b, 1.into());
+/// graph1.add_edge_unchecked(b, c, 1.into());
+///
+/// let mut graph2 = MatGraph::init(Mat::<usize>::init());
+/// let x = graph2.add_vertex();
+/// let y = graph2.add_vertex();
+/// let z = graph2.add_vertex();
+/// graph2.add_edge_unchecked(y, x, 1.into());
+/// graph2.add_edge_unchecked(x, z, 1.into());
+///
+/// let labeling1 = CanonicalForm::init(&graph1).execute(&graph1);
+/// let labeling2 = CanonicalForm::init(&graph2).execute(&graph2);
+///
+/// assert_eq!(labeling1.code(), labeling2.code());
+/// assert_eq!(labeling1.label_of(b), labeling2.label_of(x));
+/// ```
+pub struct CanonicalForm {
+    id_map: IdMap,
+
+    // Vertices that each vertex has an edge to, using virtual ids.
+    out_neighbors: Vec<Vec<usize>>,
+
+    // Vertices that have an edge to each vertex, using virtual ids. Empty for undirected graphs.
+    in_neighbors: Vec<Vec<usize>>,
+
+    is_directed: bool,
+}
+
+impl CanonicalForm {
+    pub fn init<G, W, E, Ty>(graph: &G) -> Self
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Graph<W, E, Ty> + Vertices + Neighbors,
+    {
+        let id_map = graph.continuous_id_map();
+        let vertex_count = graph.vertex_count();
+
+        let mut out_neighbors = vec![vec![]; vertex_count];
+        let mut in_neighbors = vec![vec![]; vertex_count];
+        for (virt_id, neighbors) in out_neighbors.iter_mut().enumerate() {
+            for n_id in graph.neighbors_unchecked(id_map.real_id_of(virt_id)) {
+                let n_virt_id = id_map.virt_id_of(n_id);
+
+                neighbors.push(n_virt_id);
+                if Ty::is_directed() {
+                    in_neighbors[n_virt_id].push(virt_id);
+                }
+            }
+        }
+
+        CanonicalForm {
+            id_map,
+            out_neighbors,
+            in_neighbors,
+            is_directed: Ty::is_directed(),
+        }
+    }
+
+    // Splits colors by the colors of neighbors until number of colors does not change. Returned colors are in range of 0 to number of colors - 1.
+    fn refine(&self, mut colors: Vec<usize>) -> Vec<usize> {
+        let mut color_count = {
+            let mut distinct_colors = colors.clone();
+            distinct_colors.sort_unstable();
+            distinct_colors.dedup();
+            distinct_colors.len()
+        };
+
+        loop {
+            let colors_of = |neighbors: &Vec<usize>| {
+                let mut neighbor_colors = neighbors
+                    .iter()
+                    .map(|n_id| colors[*n_id])
+                    .collect::<Vec<usize>>();
+                neighbor_colors.sort_unstable();
+                neighbor_colors
+            };
+
+            let signatures = (0..colors.len())
+                .map(|virt_id| {
+                    (
+                        colors[virt_id],
+                        colors_of(&self.out_neighbors[virt_id]),
+                        colors_of(&self.in_neighbors[virt_id]),
+                    )
+                })
+                .collect::<Vec<(usize, Vec<usize>, Vec<usize>)>>();
+
+            let mut distinct_signatures = signatures.clone();
+            distinct_signatures.sort();
+            distinct_signatures.dedup();
+
+            colors = signatures
+                .iter()
+                .map(|signature| distinct_signatures.binary_search(signature).unwrap())
+                .collect();
+
+            if distinct_signatures.len() == color_count {
+                return colors;
+            }
+            color_count = distinct_signatures.len();
+        }
+    }
+
+    // Edges of the graph after relabeling each vertex with its color.
+    fn code_of(&self, colors: &[usize]) -> Vec<(usize, usize)> {
+        let mut edges = vec![];
+        for (virt_id, neighbors) in self.out_neighbors.iter().enumerate() {
+            for n_id in neighbors {
+                let (src_label, dst_label) = (colors[virt_id], colors[*n_id]);
+
+                if self.is_directed || src_label <= dst_label {
+                    edges.push((src_label, dst_label));
+                }
+            }
+        }
+        edges.sort_unstable();
+
+        edges
+    }
+
+    fn search(&self, colors: Vec<usize>, best: &mut Option<Leaf>) {
+        let colors = self.refine(colors);
+
+        let mut cell_sizes = vec![0; colors.len()];
+        for color in &colors {
+            cell_sizes[*color] += 1;
+        }
+
+        match cell_sizes.iter().position(|size| *size > 1) {
+            None => {
+                let code = self.code_of(&colors);
+
+                let is_better = match best {
+                    Some((best_code, _)) => code < *best_code,
+                    None => true,
+                };
+                if is_better {
+                    *best = Some((code, colors));
+                }
+            }
+            Some(target_color) => {
+                for virt_id in (0..colors.len()).filter(|virt_id| colors[*virt_id] == target_color)
+                {
+                    // Doubling keeps the order of colors, and the individualized vertex comes before the rest of its cell.
+                    let individualized_colors = colors
+                        .iter()
+                        .enumerate()
+                        .map(|(other_id, color)| {
+                            if *color == target_color && other_id != virt_id {
+                                2 * color + 1
+                            } else {
+                                2 * color
+                            }
+                        })
+                        .collect();
+
+                    self.search(individualized_colors, best);
+                }
+            }
+        }
+    }
+
+    /// # Arguments
+    /// `graph`: Graph to label.
+    ///
+    /// # Returns
+    /// Canonical label of each vertex and the canonical code of the graph.
+    ///
+    /// # Complexity
+    /// O(|V|! * |V| * |E| * log(|E|)) in the worst case, but much less for graphs whose vertices are mostly told apart by refinement.
+    pub fn execute<G: Vertices + Neighbors>(&self, _: &G) -> CanonicalLabeling {
+        let vertex_count = self.out_neighbors.len();
+
+        let mut best = None;
+        self.search(vec![0; vertex_count], &mut best);
+
+        let (edges, colors) = best.unwrap();
+
+        CanonicalLabeling {
+            label_of: colors
+                .into_iter()
+                .enumerate()
+                .map(|(virt_id, label)| (self.id_map.real_id_of(virt_id), label))
+                .collect(),
+            code: CanonicalCode {
+                vertex_count,
+                is_directed: self.is_directed,
+                edges,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{MatGraph, UndirectedEdge};
+    use crate::storage::{DiMat, Mat};
+    use std::collections::HashSet;
+
+    fn code_of<G, W, E, Ty>(graph: &G) -> CanonicalCode
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Graph<W, E, Ty> + Vertices + Neighbors,
+    {
+        CanonicalForm::init(graph).execute(graph).into_code()
+    }
+
+    // Undirected graph with `vertex_count` vertices and the given edges between the i-th added vertices.
+    fn graph_of(vertex_count: usize, edges: &[(usize, usize)]) -> MatGraph<usize, UndirectedEdge> {
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let ids = (0..vertex_count)
+            .map(|_| graph.add_vertex())
+            .collect::<Vec<usize>>();
+        for (src, dst) in edges {
+            graph.add_edge_unchecked(ids[*src], ids[*dst], 1.into());
+        }
+
+        graph
+    }
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let labeling = CanonicalForm::init(&graph).execute(&graph);
+
+        assert!(labeling.labels().is_empty());
+        assert_eq!(labeling.code().vertex_count(), 0);
+        assert!(labeling.code().edges().is_empty());
+    }
+
+    #[test]
+    fn labels_are_a_permutation() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c  ---  d
+        //              |
+        //              e
+        //
+        let graph = graph_of(5, &[(0, 1), (1, 2), (2, 3), (1, 4)]);
+
+        // When:
+        let labeling = CanonicalForm::init(&graph).execute(&graph);
+
+        // Then:
+        let labels = labeling
+            .labels()
+            .values()
+            .copied()
+            .collect::<HashSet<usize>>();
+        assert_eq!(labels, (0..5).collect());
+        assert_eq!(labeling.code().edges().len(), 4);
+    }
+
+    #[test]
+    fn isomorphic_graphs() {
+        // Given: Two cycles of length 6 with different vertex orders, and a regular graph made of two triangles.
+        //
+        //      0 --- 1 --- 2           0 --- 2 --- 4           0 --- 1     3 --- 4
+        //      |           |           |           |            \   /       \   /
+        //      5 --- 4 --- 3           5 --- 3 --- 1              2           5
+        //
+        let cycle1 = graph_of(6, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 0)]);
+        let cycle2 = graph_of(6, &[(0, 2), (2, 4), (4, 1), (1, 3), (3, 5), (5, 0)]);
+        let triangles = graph_of(6, &[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)]);
+
+        // Then: All vertices have degree two, but only the cycles are isomorphic.
+        assert_eq!(code_of(&cycle1), code_of(&cycle2));
+        assert_ne!(code_of(&cycle1), code_of(&triangles));
+    }
+
+    #[test]
+    fn all_relabelings_of_a_graph() {
+        // Given: Graph
+        //
+        //      0  ---  1  ---  2
+        //      |     /
+        //      |   /
+        //      | /
+        //      3  ---  4
+        //
+        let edges = [(0, 1), (1, 2), (0, 3), (1, 3), (3, 4)];
+        let expected_code = code_of(&graph_of(5, &edges));
+
+        // When: Relabeling vertices by every rotation and reflection of their ids.
+        for shift in 0..5 {
+            for reflect in [false, true].iter() {
+                let relabel = |id: usize| {
+                    let id = if *reflect { 4 - id } else { id };
+                    (id + shift) % 5
+                };
+                let relabeled_edges = edges
+                    .iter()
+                    .map(|(src, dst)| (relabel(*src), relabel(*dst)))
+                    .collect::<Vec<(usize, usize)>>();
+
+                // Then:
+                assert_eq!(code_of(&graph_of(5, &relabeled_edges)), expected_code);
+            }
+        }
+    }
+
+    #[test]
+    fn directed_graphs() {
+        // Given: Directed path, its reverse, and a directed cycle.
+        //
+        //      a  -->  b  -->  c       c  -->  b  -->  a       a  -->  b  -->  c  --> a
+        //
+        let mut path = MatGraph::init(DiMat::<usize>::init());
+        let a = path.add_vertex();
+        let b = path.add_vertex();
+        let c = path.add_vertex();
+        path.add_edge_unchecked(a, b, 1.into());
+        path.add_edge_unchecked(b, c, 1.into());
+
+        let mut reversed_path = MatGraph::init(DiMat::<usize>::init());
+        let a = reversed_path.add_vertex();
+        let b = reversed_path.add_vertex();
+        let c = reversed_path.add_vertex();
+        reversed_path.add_edge_unchecked(c, b, 1.into());
+        reversed_path.add_edge_unchecked(b, a, 1.into());
+
+        let mut cycle = MatGraph::init(DiMat::<usize>::init());
+        let a = cycle.add_vertex();
+        let b = cycle.add_vertex();
+        let c = cycle.add_vertex();
+        cycle.add_edge_unchecked(a, b, 1.into());
+        cycle.add_edge_unchecked(b, c, 1.into());
+        cycle.add_edge_unchecked(c, a, 1.into());
+
+        // Then:
+        assert_eq!(code_of(&path), code_of(&reversed_path));
+        assert_ne!(code_of(&path), code_of(&cycle));
+        assert!(code_of(&path).is_directed());
+        assert_ne!(code_of(&path), code_of(&graph_of(3, &[(0, 1), (1, 2)])));
+    }
+}
@@ -1,22 +1,30 @@
-use crate::algo::{Dfs, DfsListener};
+use crate::algo::{Control, Dfs, DfsListener};
 use crate::graph::{Edge, UndirectedEdge};
 use crate::provide;
 
+/// Finds connected components of an undirected graph.
+///
+/// For directed graphs use [`WeaklyConnectedComponents`](crate::algo::WeaklyConnectedComponents) to ignore direction of edges,
+/// or [`TarjanSCC`](crate::algo::TarjanSCC) to find strongly connected components.
 pub struct ConnectedComponents {
     current_component: Vec<usize>,
     ccs: Vec<Vec<usize>>,
 }
 
 impl DfsListener for ConnectedComponents {
-    fn on_white(&mut self, dfs: &Dfs<Self>, virt_id: usize) {
+    fn on_white(&mut self, dfs: &Dfs<Self>, virt_id: usize) -> Control {
         let real_id = dfs.get_id_map().real_id_of(virt_id);
 
         self.current_component.push(real_id);
+
+        Control::Continue
     }
 
-    fn on_finish(&mut self, _: &Dfs<Self>) {
+    fn on_finish(&mut self, _: &Dfs<Self>) -> Control {
         self.ccs.push(self.current_component.clone());
         self.current_component.clear();
+
+        Control::Continue
     }
 }
 
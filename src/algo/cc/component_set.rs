@@ -0,0 +1,245 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::graph::{subgraph::Subgraph, Edge, EdgeDir};
+use crate::provide;
+
+/// Partition of vertices of a graph into components.
+///
+/// It can be built from the output of algorithms like [`ConnectedComponents`](crate::algo::ConnectedComponents),
+/// [`WeaklyConnectedComponents`](crate::algo::WeaklyConnectedComponents) or [`TarjanSCC`](crate::algo::TarjanSCC)
+/// to answer queries about which component each vertex belongs to in O(1).
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::{ComponentSet, ConnectedComponents};
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b       c
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+///
+/// let ccs = ComponentSet::from(ConnectedComponents::init(&graph).execute(&graph));
+///
+/// assert_eq!(ccs.len(), 2);
+/// assert!(ccs.are_connected(a, b));
+/// assert!(!ccs.are_connected(a, c));
+/// ```
+pub struct ComponentSet {
+    components: Vec<Vec<usize>>,
+    component_of: HashMap<usize, usize>,
+}
+
+impl ComponentSet {
+    /// # Arguments
+    /// `components`: Each component containing id of the vertices in it.
+    ///
+    /// # Returns
+    /// Initialized component set.
+    ///
+    /// # Complexity
+    /// O(|V|)
+    pub fn init(components: Vec<Vec<usize>>) -> Self {
+        let component_of = components
+            .iter()
+            .enumerate()
+            .flat_map(|(index, component)| {
+                component.iter().map(move |vertex_id| (*vertex_id, index))
+            })
+            .collect();
+
+        ComponentSet {
+            components,
+            component_of,
+        }
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing index of the component that contains vertex with id: `vertex_id`.
+    /// * `None`: If vertex is not in any of the components.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn component_of(&self, vertex_id: usize) -> Option<usize> {
+        self.component_of.get(&vertex_id).copied()
+    }
+
+    /// # Arguments
+    /// `index`: Index of the component.
+    ///
+    /// # Returns
+    /// * `Some`: Containing id of the vertices in the component.
+    /// * `None`: If there is no component with the given index.
+    pub fn component(&self, index: usize) -> Option<&Vec<usize>> {
+        self.components.get(index)
+    }
+
+    /// # Returns
+    /// All components.
+    pub fn components(&self) -> &Vec<Vec<usize>> {
+        &self.components
+    }
+
+    /// # Returns
+    /// Number of components.
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    /// # Returns
+    /// `true` if there is no component, `false` otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `true`: If both vertices are in the same component.
+    /// * `false`: Otherwise.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn are_connected(&self, src_id: usize, dst_id: usize) -> bool {
+        match (self.component_of(src_id), self.component_of(dst_id)) {
+            (Some(src_index), Some(dst_index)) => src_index == dst_index,
+            _ => false,
+        }
+    }
+
+    /// # Arguments
+    /// * `graph`: Graph that components are computed from.
+    /// * `index`: Index of the component.
+    ///
+    /// # Returns
+    /// * `Some`: Containing subgraph induced by vertices of the component.
+    /// * `None`: If there is no component with the given index.
+    ///
+    /// # Complexity
+    /// O(|E|)
+    pub fn subgraph_of<'a, W, E, Dir, G>(
+        &self,
+        graph: &'a G,
+        index: usize,
+    ) -> Option<Subgraph<'a, W, E, Dir, G>>
+    where
+        E: Edge<W>,
+        Dir: EdgeDir,
+        G: provide::Graph<W, E, Dir> + provide::Edges<W, E> + provide::Neighbors,
+    {
+        let vertices = self
+            .component(index)?
+            .iter()
+            .copied()
+            .collect::<HashSet<usize>>();
+
+        let edges = graph
+            .edges()
+            .into_iter()
+            .filter(|(src_id, dst_id, _)| vertices.contains(src_id) && vertices.contains(dst_id))
+            .map(|(src_id, dst_id, edge)| (src_id, dst_id, edge.get_id()))
+            .collect();
+
+        Some(Subgraph::init(graph, edges, vertices))
+    }
+}
+
+impl From<Vec<Vec<usize>>> for ComponentSet {
+    fn from(components: Vec<Vec<usize>>) -> Self {
+        ComponentSet::init(components)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::{ConnectedComponents, WeaklyConnectedComponents};
+    use crate::graph::MatGraph;
+    use crate::provide::*;
+    use crate::storage::{DiMat, Mat};
+
+    #[test]
+    fn empty_set() {
+        let ccs = ComponentSet::init(vec![]);
+
+        assert!(ccs.is_empty());
+        assert_eq!(ccs.component_of(0), None);
+        assert!(!ccs.are_connected(0, 0));
+    }
+
+    #[test]
+    fn undirected_graph() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c       d  ---  e
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        let de = graph.add_edge_unchecked(d, e, 1.into());
+
+        // When: Building component set from connected components.
+        let ccs = ComponentSet::from(ConnectedComponents::init(&graph).execute(&graph));
+
+        // Then:
+        assert_eq!(ccs.len(), 2);
+        assert!(ccs.are_connected(a, c));
+        assert!(!ccs.are_connected(c, d));
+        assert_eq!(ccs.component_of(d), ccs.component_of(e));
+        assert_eq!(ccs.component_of(d + 10), None);
+
+        let de_subgraph = ccs
+            .subgraph_of(&graph, ccs.component_of(d).unwrap())
+            .unwrap();
+        assert_eq!(de_subgraph.vertex_count(), 2);
+        assert_eq!(de_subgraph.edges_count(), 1);
+        assert!(de_subgraph.edge(de).is_ok());
+        assert!(ccs.subgraph_of(&graph, ccs.len()).is_none());
+    }
+
+    #[test]
+    fn directed_graph() {
+        // Given: Graph
+        //
+        //      a  -->  b  <--  c       d
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(c, b, 1.into());
+
+        // When: Building component set from weakly connected components.
+        let wccs = ComponentSet::from(WeaklyConnectedComponents::init(&graph).execute(&graph));
+
+        // Then:
+        assert_eq!(wccs.len(), 2);
+        assert!(wccs.are_connected(a, c));
+        assert!(!wccs.are_connected(a, d));
+
+        let abc_subgraph = wccs
+            .subgraph_of(&graph, wccs.component_of(a).unwrap())
+            .unwrap();
+        assert_eq!(abc_subgraph.vertex_count(), 3);
+        assert_eq!(abc_subgraph.edges_count(), 2);
+    }
+}
@@ -0,0 +1,372 @@
+use crate::graph::{Edge, EdgeDir};
+use crate::provide::{self, IdMap};
+
+/// Answers connectivity questions about a graph, whether it's directed or undirected.
+///
+/// [`ConnectedComponents`](crate::algo::ConnectedComponents) only accepts undirected graphs,
+/// and [`TarjanSCC`](crate::algo::TarjanSCC) and [`WeaklyConnectedComponents`](crate::algo::WeaklyConnectedComponents) only accept directed ones.
+/// This struct accepts both and picks the right notion of connectivity based on direction of the graph:
+/// * In an undirected graph connected, weakly connected and strongly connected all mean the same thing.
+/// * In a directed graph connected means strongly connected: every vertex can reach every other vertex.
+///
+/// A graph with no vertices is considered connected.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::Connectivity;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  -->  b  -->  c
+/// //
+/// let mut graph = MatGraph::init(DiMat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+///
+/// let connectivity = Connectivity::init(&graph);
+///
+/// assert!(connectivity.is_weakly_connected());
+/// assert!(!connectivity.is_strongly_connected());
+/// assert_eq!(connectivity.connected_components(), vec![vec![a], vec![b], vec![c]]);
+///
+/// // When: Adding an edge from c to a.
+/// graph.add_edge_unchecked(c, a, 1.into());
+///
+/// assert!(Connectivity::init(&graph).is_connected());
+/// ```
+pub struct Connectivity {
+    id_map: IdMap,
+    is_directed: bool,
+    out_neighbors_of: Vec<Vec<usize>>,
+    in_neighbors_of: Vec<Vec<usize>>,
+}
+
+impl Connectivity {
+    /// # Arguments
+    /// `graph`: Graph to answer connectivity questions about.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|)
+    pub fn init<W, E, Ty, G>(graph: &G) -> Self
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: provide::Graph<W, E, Ty> + provide::Vertices + provide::Neighbors,
+    {
+        let id_map = graph.continuous_id_map();
+        let vertex_count = graph.vertex_count();
+
+        let mut out_neighbors_of = vec![vec![]; vertex_count];
+        let mut in_neighbors_of = vec![vec![]; vertex_count];
+        for (virt_id, out_neighbors) in out_neighbors_of.iter_mut().enumerate() {
+            for n_id in graph.neighbors_unchecked(id_map.real_id_of(virt_id)) {
+                let n_virt_id = id_map.virt_id_of(n_id);
+
+                out_neighbors.push(n_virt_id);
+                in_neighbors_of[n_virt_id].push(virt_id);
+            }
+        }
+
+        Connectivity {
+            id_map,
+            is_directed: Ty::is_directed(),
+            out_neighbors_of,
+            in_neighbors_of,
+        }
+    }
+
+    /// # Returns
+    /// `true` if every vertex can reach every other vertex, `false` otherwise.
+    /// For directed graphs it's the same as [`is_strongly_connected`](crate::algo::Connectivity::is_strongly_connected).
+    pub fn is_connected(&self) -> bool {
+        self.is_strongly_connected()
+    }
+
+    /// # Returns
+    /// `true` if every vertex can reach every other vertex when direction of edges is ignored, `false` otherwise.
+    pub fn is_weakly_connected(&self) -> bool {
+        self.vertex_count() == 0
+            || self
+                .reach_from(0, true, true)
+                .iter()
+                .all(|is_reached| *is_reached)
+    }
+
+    /// # Returns
+    /// `true` if every vertex can reach every other vertex using edges in their direction, `false` otherwise.
+    pub fn is_strongly_connected(&self) -> bool {
+        if self.vertex_count() == 0 {
+            return true;
+        }
+
+        // Every vertex is reachable from vertex 0, and vertex 0 is reachable from every vertex.
+        let is_reached = |reached: Vec<bool>| reached.iter().all(|is_reached| *is_reached);
+
+        is_reached(self.reach_from(0, true, false))
+            && (!self.is_directed || is_reached(self.reach_from(0, false, true)))
+    }
+
+    /// # Returns
+    /// Connected components of the graph, which are strongly connected components for directed graphs.
+    /// Each component contains the id of vertices in it sorted in ascending order, and components are sorted by their first vertex.
+    pub fn connected_components(&self) -> Vec<Vec<usize>> {
+        if self.is_directed {
+            self.strongly_connected_components()
+        } else {
+            self.weakly_connected_components()
+        }
+    }
+
+    /// # Returns
+    /// Weakly connected components of the graph, which are the connected components for undirected graphs.
+    /// Each component contains the id of vertices in it sorted in ascending order, and components are sorted by their first vertex.
+    pub fn weakly_connected_components(&self) -> Vec<Vec<usize>> {
+        let mut component_of = vec![None; self.vertex_count()];
+        let mut component_count = 0;
+
+        for virt_id in 0..self.vertex_count() {
+            if component_of[virt_id].is_some() {
+                continue;
+            }
+
+            for (reached_id, is_reached) in
+                self.reach_from(virt_id, true, true).into_iter().enumerate()
+            {
+                if is_reached {
+                    component_of[reached_id] = Some(component_count);
+                }
+            }
+            component_count += 1;
+        }
+
+        self.collect_components(component_of, component_count)
+    }
+
+    /// # Returns
+    /// Strongly connected components of the graph, which are the connected components for undirected graphs.
+    /// Each component contains the id of vertices in it sorted in ascending order, and components are sorted by their first vertex.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        if !self.is_directed {
+            return self.weakly_connected_components();
+        }
+
+        let vertex_count = self.vertex_count();
+
+        // Kosaraju: Find finish order of vertices using edges in their direction.
+        let mut is_visited = vec![false; vertex_count];
+        let mut finish_order = vec![];
+        for start_id in 0..vertex_count {
+            if is_visited[start_id] {
+                continue;
+            }
+
+            is_visited[start_id] = true;
+            let mut stack = vec![(start_id, 0)];
+
+            while let Some((virt_id, next_index)) = stack.pop() {
+                match self.out_neighbors_of[virt_id].get(next_index) {
+                    Some(n_virt_id) => {
+                        stack.push((virt_id, next_index + 1));
+
+                        if !is_visited[*n_virt_id] {
+                            is_visited[*n_virt_id] = true;
+                            stack.push((*n_virt_id, 0));
+                        }
+                    }
+                    None => finish_order.push(virt_id),
+                }
+            }
+        }
+
+        // Then visit vertices in reverse finish order using edges in reverse direction. Each search finds one component.
+        let mut component_of = vec![None; vertex_count];
+        let mut component_count = 0;
+        for start_id in finish_order.into_iter().rev() {
+            if component_of[start_id].is_some() {
+                continue;
+            }
+
+            component_of[start_id] = Some(component_count);
+            let mut stack = vec![start_id];
+
+            while let Some(virt_id) = stack.pop() {
+                for n_virt_id in &self.in_neighbors_of[virt_id] {
+                    if component_of[*n_virt_id].is_none() {
+                        component_of[*n_virt_id] = Some(component_count);
+                        stack.push(*n_virt_id);
+                    }
+                }
+            }
+            component_count += 1;
+        }
+
+        self.collect_components(component_of, component_count)
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.out_neighbors_of.len()
+    }
+
+    // Vertices reachable from `start_id`, following outgoing edges, incoming edges or both.
+    fn reach_from(&self, start_id: usize, use_out_edges: bool, use_in_edges: bool) -> Vec<bool> {
+        let mut is_reached = vec![false; self.vertex_count()];
+        is_reached[start_id] = true;
+
+        let mut stack = vec![start_id];
+        while let Some(virt_id) = stack.pop() {
+            let out_neighbors = self.out_neighbors_of[virt_id]
+                .iter()
+                .filter(|_| use_out_edges);
+            let in_neighbors = self.in_neighbors_of[virt_id]
+                .iter()
+                .filter(|_| use_in_edges);
+
+            for n_virt_id in out_neighbors.chain(in_neighbors) {
+                if !is_reached[*n_virt_id] {
+                    is_reached[*n_virt_id] = true;
+                    stack.push(*n_virt_id);
+                }
+            }
+        }
+
+        is_reached
+    }
+
+    fn collect_components(
+        &self,
+        component_of: Vec<Option<usize>>,
+        component_count: usize,
+    ) -> Vec<Vec<usize>> {
+        let mut components = vec![vec![]; component_count];
+        for (virt_id, component) in component_of.into_iter().enumerate() {
+            components[component.unwrap()].push(self.id_map.real_id_of(virt_id));
+        }
+
+        for component in components.iter_mut() {
+            component.sort_unstable();
+        }
+        components.sort_unstable_by_key(|component| component[0]);
+
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::*;
+    use crate::storage::{DiMat, Mat};
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let connectivity = Connectivity::init(&graph);
+
+        assert!(connectivity.is_connected());
+        assert!(connectivity.is_weakly_connected());
+        assert!(connectivity.is_strongly_connected());
+        assert!(connectivity.connected_components().is_empty());
+    }
+
+    #[test]
+    fn undirected_graph() {
+        // Given: Graph
+        //
+        //      a  ---  b       c  ---  d
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+
+        // When:
+        let connectivity = Connectivity::init(&graph);
+
+        // Then:
+        assert!(!connectivity.is_connected());
+        assert!(!connectivity.is_weakly_connected());
+        assert!(!connectivity.is_strongly_connected());
+        assert_eq!(
+            connectivity.connected_components(),
+            vec![vec![a, b], vec![c, d]]
+        );
+        assert_eq!(
+            connectivity.strongly_connected_components(),
+            vec![vec![a, b], vec![c, d]]
+        );
+
+        // When: Connecting the components.
+        graph.add_edge_unchecked(b, c, 1.into());
+        let connectivity = Connectivity::init(&graph);
+
+        // Then: All notions of connectivity agree.
+        assert!(connectivity.is_connected());
+        assert!(connectivity.is_weakly_connected());
+        assert!(connectivity.is_strongly_connected());
+        assert_eq!(connectivity.connected_components(), vec![vec![a, b, c, d]]);
+    }
+
+    #[test]
+    fn directed_graph() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  d  <--  e
+        //      ^     /         |       ^
+        //      |   /           v       |
+        //      | v             f  -----'
+        //      c
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, a, 1.into());
+        graph.add_edge_unchecked(b, d, 1.into());
+        graph.add_edge_unchecked(d, f, 1.into());
+        graph.add_edge_unchecked(f, e, 1.into());
+        graph.add_edge_unchecked(e, d, 1.into());
+
+        // When:
+        let connectivity = Connectivity::init(&graph);
+
+        // Then:
+        assert!(!connectivity.is_connected());
+        assert!(connectivity.is_weakly_connected());
+        assert!(!connectivity.is_strongly_connected());
+        assert_eq!(
+            connectivity.connected_components(),
+            vec![vec![a, b, c], vec![d, e, f]]
+        );
+        assert_eq!(
+            connectivity.weakly_connected_components(),
+            vec![vec![a, b, c, d, e, f]]
+        );
+
+        // When: Adding an edge back from the second cycle to the first one.
+        graph.add_edge_unchecked(e, a, 1.into());
+        let connectivity = Connectivity::init(&graph);
+
+        // Then:
+        assert!(connectivity.is_connected());
+        assert_eq!(
+            connectivity.connected_components(),
+            vec![vec![a, b, c, d, e, f]]
+        );
+    }
+}
@@ -0,0 +1,383 @@
+use std::collections::HashMap;
+
+use crate::graph::{Edge, UndirectedEdge};
+use crate::provide;
+
+/// Keeps track of connected components of an undirected graph while the graph is being mutated.
+///
+/// As opposed to [`ConnectedComponents`](crate::algo::ConnectedComponents) which computes the components from scratch,
+/// `DynamicConnectivity` must be notified about each mutation of the graph and updates the components accordingly.
+/// Insertions are handled by a union-find with union by rank. Removing the last edge between two vertices may split a component,
+/// so in that case union-find is rebuilt from the edges that are still present.
+///
+/// ## Note
+/// * |V|: Means number of vertices that are tracked.
+/// * |E|: Means number of edges that are tracked.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::DynamicConnectivity;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b       c
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let ab = graph.add_edge_unchecked(a, b, 1.into());
+///
+/// let mut dc = DynamicConnectivity::init(&graph);
+/// assert!(dc.are_connected(a, b));
+/// assert!(!dc.are_connected(a, c));
+///
+/// // Connect b and c.
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// dc.add_edge(b, c);
+/// assert!(dc.are_connected(a, c));
+///
+/// // Disconnect a and b.
+/// graph.remove_edge_unchecked(a, b, ab);
+/// dc.remove_edge(a, b);
+/// assert!(!dc.are_connected(a, c));
+/// assert_eq!(dc.component_of(b), dc.component_of(c));
+/// ```
+pub struct DynamicConnectivity {
+    parent: HashMap<usize, usize>,
+    rank: HashMap<usize, usize>,
+
+    // Number of edges between each pair of vertices, keyed by (min_id, max_id).
+    edges: HashMap<(usize, usize), usize>,
+
+    component_count: usize,
+}
+
+impl DynamicConnectivity {
+    /// Initializes the structure with the vertices and edges that are currently in the `graph`.
+    ///
+    /// # Arguments
+    /// `graph`: Graph to track connected components of.
+    ///
+    /// # Returns
+    /// Initialized structure.
+    ///
+    /// # Complexity
+    /// O(|V| + |E| * log(|V|))
+    pub fn init<G, W, E: Edge<W>>(graph: &G) -> Self
+    where
+        G: provide::Graph<W, E, UndirectedEdge> + provide::Vertices + provide::Edges<W, E>,
+    {
+        let mut dc = DynamicConnectivity {
+            parent: HashMap::new(),
+            rank: HashMap::new(),
+            edges: HashMap::new(),
+            component_count: 0,
+        };
+
+        for vertex_id in graph.vertices() {
+            dc.add_vertex(vertex_id);
+        }
+
+        for (src_id, dst_id, _) in graph.edges() {
+            dc.add_edge(src_id, dst_id);
+        }
+
+        dc
+    }
+
+    fn key_of(src_id: usize, dst_id: usize) -> (usize, usize) {
+        (src_id.min(dst_id), src_id.max(dst_id))
+    }
+
+    fn find(&self, mut vertex_id: usize) -> usize {
+        while self.parent[&vertex_id] != vertex_id {
+            vertex_id = self.parent[&vertex_id];
+        }
+
+        vertex_id
+    }
+
+    fn union(&mut self, src_id: usize, dst_id: usize) {
+        let src_root = self.find(src_id);
+        let dst_root = self.find(dst_id);
+
+        if src_root == dst_root {
+            return;
+        }
+
+        let src_rank = self.rank[&src_root];
+        let dst_rank = self.rank[&dst_root];
+
+        if src_rank < dst_rank {
+            self.parent.insert(src_root, dst_root);
+        } else {
+            self.parent.insert(dst_root, src_root);
+
+            if src_rank == dst_rank {
+                self.rank.insert(src_root, src_rank + 1);
+            }
+        }
+
+        self.component_count -= 1;
+    }
+
+    // Recomputes the components from the edges that are tracked.
+    fn rebuild(&mut self) {
+        for (vertex_id, parent_id) in self.parent.iter_mut() {
+            *parent_id = *vertex_id;
+        }
+        for rank in self.rank.values_mut() {
+            *rank = 0;
+        }
+        self.component_count = self.parent.len();
+
+        let pairs = self.edges.keys().copied().collect::<Vec<(usize, usize)>>();
+        for (src_id, dst_id) in pairs {
+            self.union(src_id, dst_id);
+        }
+    }
+
+    /// Starts tracking vertex with id: `vertex_id` as a new component.
+    ///
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex that is added to the graph.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn add_vertex(&mut self, vertex_id: usize) {
+        if !self.parent.contains_key(&vertex_id) {
+            self.parent.insert(vertex_id, vertex_id);
+            self.rank.insert(vertex_id, 0);
+
+            self.component_count += 1;
+        }
+    }
+
+    /// Stops tracking vertex with id: `vertex_id` and all of its edges.
+    ///
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex that is removed from the graph.
+    ///
+    /// # Complexity
+    /// O(|V| + |E| * log(|V|))
+    pub fn remove_vertex(&mut self, vertex_id: usize) {
+        if self.parent.remove(&vertex_id).is_some() {
+            self.rank.remove(&vertex_id);
+
+            self.edges
+                .retain(|(src_id, dst_id), _| *src_id != vertex_id && *dst_id != vertex_id);
+
+            self.rebuild();
+        }
+    }
+
+    /// Merges components of the two vertices.
+    ///
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Panics
+    /// If either of the vertices is not tracked.
+    ///
+    /// # Complexity
+    /// O(log(|V|))
+    pub fn add_edge(&mut self, src_id: usize, dst_id: usize) {
+        *self.edges.entry(Self::key_of(src_id, dst_id)).or_insert(0) += 1;
+
+        self.union(src_id, dst_id);
+    }
+
+    /// Removes an edge between the two vertices.
+    /// If it was the last edge between them, components are recomputed.
+    ///
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Complexity
+    /// * O(1): If there are other edges between source and destination.
+    /// * O(|V| + |E| * log(|V|)): Otherwise.
+    pub fn remove_edge(&mut self, src_id: usize, dst_id: usize) {
+        let key = Self::key_of(src_id, dst_id);
+
+        if let Some(count) = self.edges.get_mut(&key) {
+            *count -= 1;
+
+            if *count == 0 {
+                self.edges.remove(&key);
+
+                self.rebuild();
+            }
+        }
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `true`: If there is a path between source and destination.
+    /// * `false`: Otherwise.
+    ///
+    /// # Panics
+    /// If either of the vertices is not tracked.
+    ///
+    /// # Complexity
+    /// O(log(|V|))
+    pub fn are_connected(&self, src_id: usize, dst_id: usize) -> bool {
+        self.find(src_id) == self.find(dst_id)
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Id of the vertex that represents the component containing `vertex_id`.
+    /// Note that representative of a component may change after the graph is mutated.
+    ///
+    /// # Panics
+    /// If vertex is not tracked.
+    ///
+    /// # Complexity
+    /// O(log(|V|))
+    pub fn component_of(&self, vertex_id: usize) -> usize {
+        self.find(vertex_id)
+    }
+
+    /// # Returns
+    /// Number of connected components.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn component_count(&self) -> usize {
+        self.component_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::ConnectedComponents;
+    use crate::graph::MatGraph;
+    use crate::provide::*;
+    use crate::storage::Mat;
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let dc = DynamicConnectivity::init(&graph);
+
+        assert_eq!(dc.component_count(), 0);
+    }
+
+    #[test]
+    fn graph_with_no_edge() {
+        //      a       b       c
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+
+        let dc = DynamicConnectivity::init(&graph);
+
+        assert_eq!(dc.component_count(), 3);
+        assert!(!dc.are_connected(a, b));
+        assert!(!dc.are_connected(b, c));
+        assert!(dc.are_connected(a, a));
+    }
+
+    #[test]
+    fn adding_edges() {
+        //      a  ---  b  ---  d               g
+        //      |      /
+        //      c ___/              e  --- f
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        let g = graph.add_vertex();
+        let mut dc = DynamicConnectivity::init(&graph);
+
+        for (src_id, dst_id) in vec![(a, b), (a, c), (c, b), (b, d), (e, f)] {
+            graph.add_edge_unchecked(src_id, dst_id, 1.into());
+            dc.add_edge(src_id, dst_id);
+        }
+
+        assert_eq!(dc.component_count(), 3);
+        assert_eq!(
+            dc.component_count(),
+            ConnectedComponents::init(&graph).execute(&graph).len()
+        );
+        assert!(vec![b, c, d].iter().all(|v_id| dc.are_connected(a, *v_id)));
+        assert!(dc.are_connected(e, f));
+        assert!(!dc.are_connected(a, e));
+        assert!(!dc.are_connected(f, g));
+        assert_eq!(dc.component_of(c), dc.component_of(d));
+    }
+
+    #[test]
+    fn removing_edges() {
+        //      a  ---  b  ---  c  ---  d
+        //      |       |
+        //      '-------'
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let mut dc = DynamicConnectivity::init(&graph);
+        dc.add_edge(a, b);
+        dc.add_edge(b, a);
+        dc.add_edge(b, c);
+        dc.add_edge(c, d);
+        assert_eq!(dc.component_count(), 1);
+
+        // Removing one of the two edges between a and b keeps them connected.
+        dc.remove_edge(a, b);
+        assert!(dc.are_connected(a, b));
+        assert_eq!(dc.component_count(), 1);
+
+        // Removing b --- c splits the graph.
+        dc.remove_edge(c, b);
+        assert_eq!(dc.component_count(), 2);
+        assert!(dc.are_connected(a, b));
+        assert!(dc.are_connected(c, d));
+        assert!(!dc.are_connected(a, d));
+    }
+
+    #[test]
+    fn adding_and_removing_vertices() {
+        //      a  ---  b  ---  c
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        let mut dc = DynamicConnectivity::init(&graph);
+        assert_eq!(dc.component_count(), 1);
+
+        // Removing b splits a and c.
+        dc.remove_vertex(b);
+        assert_eq!(dc.component_count(), 2);
+        assert!(!dc.are_connected(a, c));
+
+        // Adding d and connecting it to both a and c merges them again.
+        let d = b + 10;
+        dc.add_vertex(d);
+        assert_eq!(dc.component_count(), 3);
+        dc.add_edge(a, d);
+        dc.add_edge(d, c);
+        assert_eq!(dc.component_count(), 1);
+        assert!(dc.are_connected(a, c));
+    }
+}
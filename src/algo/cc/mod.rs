@@ -1,5 +1,13 @@
 mod tarjan;
 mod cc;
+mod component_set;
+mod connectivity;
+mod dynamic;
+mod wcc;
 
 pub use tarjan::TarjanSCC;
-pub use cc::ConnectedComponents;
\ No newline at end of file
+pub use cc::ConnectedComponents;
+pub use component_set::ComponentSet;
+pub use connectivity::Connectivity;
+pub use dynamic::DynamicConnectivity;
+pub use wcc::WeaklyConnectedComponents;
\ No newline at end of file
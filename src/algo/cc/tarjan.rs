@@ -27,7 +27,7 @@ impl TarjanSCC {
             low_link_of: vec![Magnitude::PosInfinite; vertex_count],
             index: 0,
             scc: vec![],
-            id_map: graph.continuos_id_map(),
+            id_map: graph.continuous_id_map(),
         }
     }
 
@@ -0,0 +1,173 @@
+use crate::graph::{DirectedEdge, Edge};
+use crate::provide::{self, IdMap};
+
+/// Finds weakly connected components of a directed graph.
+///
+/// Two vertices are in the same weakly connected component if there is a path between them when direction of edges is ignored.
+/// For undirected graphs use [`ConnectedComponents`](crate::algo::ConnectedComponents) instead.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::WeaklyConnectedComponents;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  -->  b  <--  c       d
+/// //
+/// let mut graph = MatGraph::init(DiMat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(c, b, 1.into());
+///
+/// let wccs = WeaklyConnectedComponents::init(&graph).execute(&graph);
+///
+/// assert_eq!(wccs, vec![vec![a, b, c], vec![d]]);
+/// ```
+pub struct WeaklyConnectedComponents {
+    id_map: IdMap,
+    adjacents_of: Vec<Vec<usize>>,
+}
+
+impl WeaklyConnectedComponents {
+    pub fn init<G, W, E: Edge<W>>(graph: &G) -> Self
+    where
+        G: provide::Graph<W, E, DirectedEdge> + provide::Vertices + provide::Edges<W, E>,
+    {
+        let id_map = graph.continuous_id_map();
+
+        // Treat each edge as undirected by storing it in adjacency of both of its end points.
+        let mut adjacents_of = vec![vec![]; graph.vertex_count()];
+        for (src_id, dst_id, _) in graph.edges() {
+            let src_virt_id = id_map.virt_id_of(src_id);
+            let dst_virt_id = id_map.virt_id_of(dst_id);
+
+            adjacents_of[src_virt_id].push(dst_virt_id);
+            adjacents_of[dst_virt_id].push(src_virt_id);
+        }
+
+        WeaklyConnectedComponents {
+            id_map,
+            adjacents_of,
+        }
+    }
+
+    /// # Returns
+    /// Weakly connected components of the graph. Each component contains the id of vertices in it, sorted in ascending order.
+    pub fn execute<G, W, E: Edge<W>>(self, _: &G) -> Vec<Vec<usize>>
+    where
+        G: provide::Graph<W, E, DirectedEdge> + provide::Vertices + provide::Edges<W, E>,
+    {
+        let vertex_count = self.adjacents_of.len();
+
+        let mut visited = vec![false; vertex_count];
+        let mut wccs = vec![];
+
+        for start_id in 0..vertex_count {
+            if visited[start_id] {
+                continue;
+            }
+
+            let mut wcc = vec![];
+            let mut stack = vec![start_id];
+            visited[start_id] = true;
+
+            while let Some(virt_id) = stack.pop() {
+                wcc.push(self.id_map.real_id_of(virt_id));
+
+                for adjacent_id in &self.adjacents_of[virt_id] {
+                    if !visited[*adjacent_id] {
+                        visited[*adjacent_id] = true;
+                        stack.push(*adjacent_id);
+                    }
+                }
+            }
+
+            wcc.sort_unstable();
+            wccs.push(wcc);
+        }
+
+        wccs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ListGraph, MatGraph};
+    use crate::provide::*;
+    use crate::storage::{DiList, DiMat};
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(DiMat::<usize>::init());
+
+        let wccs = WeaklyConnectedComponents::init(&graph).execute(&graph);
+
+        assert_eq!(wccs.len(), 0);
+    }
+
+    #[test]
+    fn graph_with_no_edge() {
+        //      a       b       c
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+
+        let wccs = WeaklyConnectedComponents::init(&graph).execute(&graph);
+
+        assert_eq!(wccs, vec![vec![a], vec![b], vec![c]]);
+    }
+
+    #[test]
+    fn not_strongly_connected_graph() {
+        //      a  -->  b  <--  c       d  <--  e
+        //              ^                       |
+        //              |                       v
+        //              f                       g
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        let g = graph.add_vertex();
+
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(c, b, 1.into());
+        graph.add_edge_unchecked(f, b, 1.into());
+        graph.add_edge_unchecked(e, d, 1.into());
+        graph.add_edge_unchecked(e, g, 1.into());
+
+        let wccs = WeaklyConnectedComponents::init(&graph).execute(&graph);
+
+        assert_eq!(wccs.len(), 2);
+        assert_eq!(wccs[0], vec![a, b, c, f]);
+        assert_eq!(wccs[1], vec![d, e, g]);
+    }
+
+    #[test]
+    fn graph_with_removed_vertex() {
+        //      a  -->  b  -->  c
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // Removing b separates a and c.
+        graph.remove_vertex_unchecked(b);
+
+        let wccs = WeaklyConnectedComponents::init(&graph).execute(&graph);
+
+        assert_eq!(wccs, vec![vec![a], vec![c]]);
+    }
+}
@@ -0,0 +1,403 @@
+use std::collections::{HashMap, VecDeque};
+
+use anyhow::Result;
+
+use crate::algo::ExecutionControl;
+use crate::graph::Edge;
+use crate::provide::{Direction, Edges, IdMap, Vertices};
+
+/// Computes vertex and edge betweenness centrality using Brandes' algorithm, ignoring weights of the edges.
+///
+/// Betweenness of a vertex is the sum, over all pairs of other vertices s and t, of the fraction of shortest paths from s to t that pass through the vertex.
+/// Betweenness of an edge is defined the same way, but pairs include the end points of the edge.
+/// In undirected graphs each pair is counted once, in directed graphs each ordered pair is counted once.
+///
+/// It only needs the provider traits, so any subgraph like [`Subgraph`](crate::graph::subgraph::Subgraph)
+/// or [`MultiRootSubgraph`](crate::graph::subgraph::MultiRootSubgraph) can be used as input,
+/// in which case only vertices and edges of the subgraph are considered.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::centrality::Betweenness;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b  ---  c
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let ab = graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+///
+/// let (vertex_betweenness, edge_betweenness) = Betweenness::init(&graph).execute(&graph);
+///
+/// assert_eq!(vertex_betweenness[&a], 0.0);
+/// assert_eq!(vertex_betweenness[&b], 1.0);
+/// assert_eq!(edge_betweenness[&ab], 2.0);
+/// ```
+pub struct Betweenness {
+    id_map: IdMap,
+    is_directed: bool,
+
+    // Edges from each vertex in the format of: (virtual id of the destination, edge id).
+    edges_of: Vec<Vec<(usize, usize)>>,
+}
+
+impl Betweenness {
+    pub fn init<W, E, G>(graph: &G) -> Self
+    where
+        E: Edge<W>,
+        G: Edges<W, E> + Vertices + Direction,
+    {
+        let id_map = graph.continuous_id_map();
+
+        let edges_of = (0..graph.vertex_count())
+            .map(|virt_id| {
+                graph
+                    .edges_from_unchecked(id_map.real_id_of(virt_id))
+                    .into_iter()
+                    .map(|(dst_id, edge)| (id_map.virt_id_of(dst_id), edge.get_id()))
+                    // Loops are never part of a shortest path.
+                    .filter(|(dst_virt_id, _)| *dst_virt_id != virt_id)
+                    .collect()
+            })
+            .collect();
+
+        Betweenness {
+            id_map,
+            is_directed: graph.is_directed(),
+            edges_of,
+        }
+    }
+
+    /// # Returns
+    /// * Betweenness of each vertex, mapped from its id.
+    /// * Betweenness of each edge, mapped from its id.
+    ///
+    /// # Complexity
+    /// O(|V|.|E|)
+    pub fn execute<W, E, G>(self, graph: &G) -> (HashMap<usize, f64>, HashMap<usize, f64>)
+    where
+        E: Edge<W>,
+        G: Edges<W, E> + Vertices + Direction,
+    {
+        self.execute_with_control(graph, &mut ExecutionControl::init())
+            .unwrap()
+    }
+
+    /// Same as [`execute`](crate::algo::centrality::Betweenness::execute), but can be cancelled and reports its progress.
+    /// Each step is a search from one of the vertices, so there are |V| steps.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph that the algorithm is initialized with.
+    /// * `control`: Control to report progress to and check for cancellation after each step.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing betweenness of each vertex and each edge, mapped from their ids.
+    /// * `Err`: [`Cancelled`](crate::graph::ErrorKind::Cancelled) if `control` is cancelled before all steps are completed.
+    ///
+    /// # Complexity
+    /// O(|V|.|E|)
+    pub fn execute_with_control<W, E, G>(
+        self,
+        _: &G,
+        control: &mut ExecutionControl,
+    ) -> Result<(HashMap<usize, f64>, HashMap<usize, f64>)>
+    where
+        E: Edge<W>,
+        G: Edges<W, E> + Vertices + Direction,
+    {
+        let vertex_count = self.edges_of.len();
+
+        let mut vertex_betweenness = vec![0.0; vertex_count];
+        let mut edge_betweenness: HashMap<usize, f64> = self
+            .edges_of
+            .iter()
+            .flatten()
+            .map(|(_, edge_id)| (*edge_id, 0.0))
+            .collect();
+
+        for src_id in 0..vertex_count {
+            // Breadth first search from source, counting shortest paths to each vertex.
+            let mut distance_of = vec![None; vertex_count];
+            let mut path_count_of = vec![0.0; vertex_count];
+            let mut predecessors_of = vec![vec![]; vertex_count];
+            let mut order = vec![];
+
+            distance_of[src_id] = Some(0);
+            path_count_of[src_id] = 1.0;
+
+            let mut queue = VecDeque::new();
+            queue.push_back(src_id);
+
+            while let Some(virt_id) = queue.pop_front() {
+                order.push(virt_id);
+                let distance = distance_of[virt_id].unwrap();
+
+                for (dst_id, edge_id) in &self.edges_of[virt_id] {
+                    if distance_of[*dst_id].is_none() {
+                        distance_of[*dst_id] = Some(distance + 1);
+                        queue.push_back(*dst_id);
+                    }
+
+                    if distance_of[*dst_id] == Some(distance + 1) {
+                        path_count_of[*dst_id] += path_count_of[virt_id];
+                        predecessors_of[*dst_id].push((virt_id, *edge_id));
+                    }
+                }
+            }
+
+            // Accumulate dependencies of source on each vertex, farthest vertices first.
+            let mut dependency_of = vec![0.0; vertex_count];
+            for virt_id in order.into_iter().rev() {
+                for (pred_id, edge_id) in &predecessors_of[virt_id] {
+                    let credit = path_count_of[*pred_id] / path_count_of[virt_id]
+                        * (1.0 + dependency_of[virt_id]);
+
+                    *edge_betweenness.get_mut(edge_id).unwrap() += credit;
+                    dependency_of[*pred_id] += credit;
+                }
+
+                if virt_id != src_id {
+                    vertex_betweenness[virt_id] += dependency_of[virt_id];
+                }
+            }
+
+            control.checkpoint(src_id + 1, vertex_count)?;
+        }
+
+        // Each pair of an undirected graph is visited from both of its end points.
+        if !self.is_directed {
+            vertex_betweenness
+                .iter_mut()
+                .for_each(|value| *value /= 2.0);
+            edge_betweenness
+                .values_mut()
+                .for_each(|value| *value /= 2.0);
+        }
+
+        let vertex_betweenness = vertex_betweenness
+            .into_iter()
+            .enumerate()
+            .map(|(virt_id, value)| (self.id_map.real_id_of(virt_id), value))
+            .collect();
+
+        Ok((vertex_betweenness, edge_betweenness))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::algo::{ego_network, CancellationToken};
+    use crate::graph::subgraph::{MultiRootSubgraph, Subgraph};
+    use crate::graph::MatGraph;
+    use crate::graph::{Error, ErrorKind};
+    use crate::provide::*;
+    use crate::storage::{DiMat, Mat};
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let (vertex_betweenness, edge_betweenness) = Betweenness::init(&graph).execute(&graph);
+
+        assert!(vertex_betweenness.is_empty());
+        assert!(edge_betweenness.is_empty());
+    }
+
+    #[test]
+    fn undirected_graph() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  d  ---  e
+        //      |       |
+        //      '-- c --'
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+        let ac = graph.add_edge_unchecked(a, c, 1.into());
+        let bc = graph.add_edge_unchecked(b, c, 1.into());
+        let bd = graph.add_edge_unchecked(b, d, 1.into());
+        let de = graph.add_edge_unchecked(d, e, 1.into());
+
+        // When:
+        let (vertex_betweenness, edge_betweenness) = Betweenness::init(&graph).execute(&graph);
+
+        // Then: b is on every path between {a, c} and {d, e}, and d is on every path between e and others.
+        assert_eq!(vertex_betweenness[&a], 0.0);
+        assert_eq!(vertex_betweenness[&b], 4.0);
+        assert_eq!(vertex_betweenness[&c], 0.0);
+        assert_eq!(vertex_betweenness[&d], 3.0);
+        assert_eq!(vertex_betweenness[&e], 0.0);
+
+        assert_eq!(edge_betweenness[&ab], 3.0);
+        assert_eq!(edge_betweenness[&ac], 1.0);
+        assert_eq!(edge_betweenness[&bc], 3.0);
+        assert_eq!(edge_betweenness[&bd], 6.0);
+        assert_eq!(edge_betweenness[&de], 4.0);
+    }
+
+    #[test]
+    fn directed_graph_with_equal_paths() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  d
+        //      |               ^
+        //      '-----> c ------'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(a, c, 1.into());
+        let bd = graph.add_edge_unchecked(b, d, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+
+        // When:
+        let (vertex_betweenness, edge_betweenness) = Betweenness::init(&graph).execute(&graph);
+
+        // Then: Half of the shortest paths from a to d pass through b.
+        assert_eq!(vertex_betweenness[&a], 0.0);
+        assert_eq!(vertex_betweenness[&b], 0.5);
+        assert_eq!(vertex_betweenness[&c], 0.5);
+        assert_eq!(vertex_betweenness[&d], 0.0);
+
+        assert_eq!(edge_betweenness[&ab], 1.5);
+        assert_eq!(edge_betweenness[&bd], 1.5);
+    }
+
+    #[test]
+    fn subgraph() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c
+        //      |               |
+        //      '------ d ------'
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+        let bc = graph.add_edge_unchecked(b, c, 1.into());
+        let cd = graph.add_edge_unchecked(c, d, 1.into());
+        graph.add_edge_unchecked(d, a, 1.into());
+
+        // And: Subgraph without vertex d.
+        let subgraph = Subgraph::init(
+            &graph,
+            vec![(a, b, ab), (b, c, bc)],
+            [a, b, c].iter().copied().collect::<HashSet<usize>>(),
+        );
+
+        // When:
+        let (vertex_betweenness, edge_betweenness) =
+            Betweenness::init(&subgraph).execute(&subgraph);
+
+        // Then: Only vertices and edges of the subgraph are considered.
+        assert_eq!(vertex_betweenness.len(), 3);
+        assert_eq!(vertex_betweenness[&b], 1.0);
+        assert_eq!(edge_betweenness.len(), 2);
+        assert_eq!(edge_betweenness[&ab], 2.0);
+        assert!(!edge_betweenness.contains_key(&cd));
+
+        // And: On the whole graph there is another shortest path from a to c through d.
+        let (vertex_betweenness, _) = Betweenness::init(&graph).execute(&graph);
+        assert_eq!(vertex_betweenness[&b], 0.5);
+    }
+
+    #[test]
+    fn cancelled_by_progress_callback() {
+        // Given: Path of four vertices.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let ids: Vec<usize> = (0..4).map(|_| graph.add_vertex()).collect();
+        for window in ids.windows(2) {
+            graph.add_edge_unchecked(window[0], window[1], 1.into());
+        }
+
+        // And: Control that is cancelled once half of the sources are processed.
+        let token = CancellationToken::init();
+        let canceller = token.clone();
+        let mut reported = vec![];
+        let mut control = ExecutionControl::init()
+            .with_token(token)
+            .on_progress(|progress| {
+                reported.push((progress.completed(), progress.total()));
+                if progress.fraction() >= 0.5 {
+                    canceller.cancel();
+                }
+            });
+
+        // When:
+        let result = Betweenness::init(&graph).execute_with_control(&graph, &mut control);
+
+        // Then:
+        let error = result.unwrap_err().downcast::<Error>().unwrap();
+        assert!(matches!(error.kind(), ErrorKind::Cancelled));
+        drop(control);
+        assert_eq!(reported, vec![(1, 4), (2, 4)]);
+    }
+
+    #[test]
+    fn reports_progress_of_each_source() {
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        let mut completed = vec![];
+        let mut control =
+            ExecutionControl::init().on_progress(|progress| completed.push(progress.completed()));
+
+        let (vertex_betweenness, _) = Betweenness::init(&graph)
+            .execute_with_control(&graph, &mut control)
+            .unwrap();
+
+        assert_eq!(vertex_betweenness.len(), 2);
+        drop(control);
+        assert_eq!(completed, vec![1, 2]);
+    }
+
+    #[test]
+    fn multi_root_subgraph() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c  ---  d
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        let cd = graph.add_edge_unchecked(c, d, 1.into());
+
+        // And: Ego network of b.
+        let ego: MultiRootSubgraph<_, _, _, _> = ego_network(&graph, b, 1).unwrap();
+
+        // When:
+        let (vertex_betweenness, edge_betweenness) = Betweenness::init(&ego).execute(&ego);
+
+        // Then:
+        assert_eq!(vertex_betweenness.len(), 3);
+        assert_eq!(vertex_betweenness[&b], 1.0);
+        assert_eq!(vertex_betweenness[&c], 0.0);
+        assert!(!edge_betweenness.contains_key(&cd));
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+
+use crate::algo::centrality::in_neighbors;
+use crate::graph::Error;
+use crate::provide::{IdMap, Neighbors, Vertices};
+
+/// Computes eigenvector centrality of vertices using power iteration.
+///
+/// Eigenvector centrality of a vertex is proportional to the sum of centralities of the vertices that have an edge to it.
+/// So centralities form the eigenvector of the largest eigenvalue of the transposed adjacency matrix.
+/// Each iteration also keeps the previous centralities, so iterations converge on bipartite graphs too.
+///
+/// Weights of the edges are ignored, and in undirected graphs each edge can be followed in both directions.
+/// Centralities are normalized so their Euclidean norm is one.
+/// Eigenvector centrality is only meaningful for strongly connected graphs. On other graphs iterations may converge slowly or not at all.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::centrality::Eigenvector;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b  ---  c
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+///
+/// let centralities = Eigenvector::init(&graph).execute(&graph).unwrap();
+///
+/// // Centralities are 1/2, 1/√2 and 1/2.
+/// assert!((centralities[&a] - 0.5).abs() < 1e-6);
+/// assert!((centralities[&b] - 0.5_f64.sqrt()).abs() < 1e-6);
+/// ```
+pub struct Eigenvector {
+    id_map: IdMap,
+    in_neighbors_of: Vec<Vec<usize>>,
+
+    tolerance: f64,
+    max_iterations: usize,
+}
+
+impl Eigenvector {
+    /// # Arguments
+    /// `graph`: Graph to compute centrality of its vertices.
+    ///
+    /// # Returns
+    /// Initialized eigenvector centrality with tolerance of 1e-10 and at most 1000 iterations.
+    pub fn init<G: Neighbors + Vertices>(graph: &G) -> Self {
+        let (id_map, in_neighbors_of) = in_neighbors(graph);
+
+        Eigenvector {
+            id_map,
+            in_neighbors_of,
+
+            tolerance: 1e-10,
+            max_iterations: 1000,
+        }
+    }
+
+    /// # Arguments
+    /// `tolerance`: Iterations stop when sum of the changes of centralities in an iteration is less than `tolerance`.
+    ///
+    /// # Returns
+    /// Eigenvector centrality with the specified tolerance.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+
+        self
+    }
+
+    /// # Arguments
+    /// `max_iterations`: Maximum number of iterations before giving up.
+    ///
+    /// # Returns
+    /// Eigenvector centrality with the specified maximum number of iterations.
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+
+        self
+    }
+
+    /// # Returns
+    /// * `Ok`: Containing centrality of each vertex, mapped from its id.
+    /// * `Err`: [`NotConverged`](crate::graph::ErrorKind::NotConverged) if centralities did not converge within the maximum number of iterations.
+    ///
+    /// # Complexity
+    /// O(`max_iterations` * (|V| + |E|))
+    pub fn execute<G: Neighbors + Vertices>(&self, _: &G) -> Result<HashMap<usize, f64>> {
+        let vertex_count = self.in_neighbors_of.len();
+
+        let mut centralities = vec![1.0 / (vertex_count as f64).sqrt(); vertex_count];
+        for _ in 0..self.max_iterations {
+            let next_centralities = self.iterate(&centralities);
+
+            let change = centralities
+                .iter()
+                .zip(&next_centralities)
+                .map(|(centrality, next_centrality)| (centrality - next_centrality).abs())
+                .sum::<f64>();
+
+            centralities = next_centralities;
+
+            if change < self.tolerance {
+                return Ok(centralities
+                    .into_iter()
+                    .enumerate()
+                    .map(|(virt_id, centrality)| (self.id_map.real_id_of(virt_id), centrality))
+                    .collect());
+            }
+        }
+
+        Err(Error::new_ncv(self.max_iterations))?
+    }
+
+    fn iterate(&self, centralities: &[f64]) -> Vec<f64> {
+        let mut next_centralities = self
+            .in_neighbors_of
+            .iter()
+            .enumerate()
+            .map(|(virt_id, in_neighbors)| {
+                centralities[virt_id]
+                    + in_neighbors
+                        .iter()
+                        .map(|src_id| centralities[*src_id])
+                        .sum::<f64>()
+            })
+            .collect::<Vec<f64>>();
+
+        let norm = next_centralities.iter().map(|c| c * c).sum::<f64>().sqrt();
+        next_centralities
+            .iter_mut()
+            .for_each(|centrality| *centrality /= norm);
+
+        next_centralities
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ErrorKind, MatGraph};
+    use crate::provide::*;
+    use crate::storage::{DiMat, Mat};
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let centralities = Eigenvector::init(&graph).execute(&graph).unwrap();
+
+        assert!(centralities.is_empty());
+    }
+
+    #[test]
+    fn star() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c
+        //              |
+        //              d
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(b, d, 1.into());
+
+        // When:
+        let centralities = Eigenvector::init(&graph).execute(&graph).unwrap();
+
+        // Then: Largest eigenvalue is √3, so centrality of b is √3 times centrality of each leaf.
+        let leaf = 1.0 / 6.0_f64.sqrt();
+        assert!((centralities[&b] - 3.0_f64.sqrt() * leaf).abs() < 1e-6);
+        assert!((centralities[&a] - leaf).abs() < 1e-6);
+        assert!((centralities[&c] - leaf).abs() < 1e-6);
+        assert!((centralities[&d] - leaf).abs() < 1e-6);
+    }
+
+    #[test]
+    fn directed_cycle() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c
+        //      ^               |
+        //      '---------------'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, a, 1.into());
+
+        // When:
+        let centralities = Eigenvector::init(&graph).execute(&graph).unwrap();
+
+        // Then:
+        for vertex_id in graph.vertices() {
+            assert!((centralities[&vertex_id] - 1.0 / 3.0_f64.sqrt()).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn not_converged() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // When: Centralities slowly move towards c.
+        let result = Eigenvector::init(&graph)
+            .with_max_iterations(10)
+            .execute(&graph);
+
+        // Then:
+        let error = result.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            ErrorKind::NotConverged
+        ));
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::algo::centrality::in_neighbors;
+use crate::provide::{IdMap, Neighbors, Vertices};
+
+/// Computes harmonic centrality of vertices, ignoring weights of the edges.
+///
+/// Harmonic centrality of a vertex is the sum of the inverse distances from all other vertices to it.
+/// Vertices that can not reach the vertex are at infinite distance, so they contribute zero.
+///
+/// Closeness centrality, which is the inverse of the sum of distances, is ill-defined when some vertices are unreachable:
+/// the sum becomes infinite and centrality of every vertex in a disconnected graph becomes zero.
+/// Harmonic centrality ranks vertices almost the same as closeness centrality on connected graphs, and it also ranks them meaningfully on disconnected graphs.
+/// So it should be preferred unless the graph is known to be (strongly) connected.
+///
+/// Dividing each centrality by |V| - 1 normalizes it to range of 0 to 1.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::centrality::Harmonic;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b  ---  c       d
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+///
+/// let centralities = Harmonic::init(&graph).execute(&graph);
+///
+/// assert_eq!(centralities[&a], 1.5);
+/// assert_eq!(centralities[&b], 2.0);
+/// assert_eq!(centralities[&d], 0.0);
+/// ```
+pub struct Harmonic {
+    id_map: IdMap,
+    in_neighbors_of: Vec<Vec<usize>>,
+}
+
+impl Harmonic {
+    /// # Arguments
+    /// `graph`: Graph to compute centrality of its vertices.
+    ///
+    /// # Returns
+    /// Initialized harmonic centrality.
+    pub fn init<G: Neighbors + Vertices>(graph: &G) -> Self {
+        let (id_map, in_neighbors_of) = in_neighbors(graph);
+
+        Harmonic {
+            id_map,
+            in_neighbors_of,
+        }
+    }
+
+    /// # Returns
+    /// Centrality of each vertex, mapped from its id.
+    ///
+    /// # Complexity
+    /// O(|V|.(|V| + |E|))
+    pub fn execute<G: Neighbors + Vertices>(&self, _: &G) -> HashMap<usize, f64> {
+        let vertex_count = self.in_neighbors_of.len();
+
+        (0..vertex_count)
+            .map(|dst_id| {
+                // Breadth first search on reversed edges, to find distance from every vertex to the destination.
+                let mut distance_of = vec![None; vertex_count];
+                distance_of[dst_id] = Some(0);
+
+                let mut queue = VecDeque::new();
+                queue.push_back(dst_id);
+
+                let mut centrality = 0.0;
+                while let Some(virt_id) = queue.pop_front() {
+                    let distance = distance_of[virt_id].unwrap() + 1;
+
+                    for src_id in &self.in_neighbors_of[virt_id] {
+                        if distance_of[*src_id].is_none() {
+                            distance_of[*src_id] = Some(distance);
+                            centrality += 1.0 / distance as f64;
+                            queue.push_back(*src_id);
+                        }
+                    }
+                }
+
+                (self.id_map.real_id_of(dst_id), centrality)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::*;
+    use crate::storage::{DiMat, Mat};
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let centralities = Harmonic::init(&graph).execute(&graph);
+
+        assert!(centralities.is_empty());
+    }
+
+    #[test]
+    fn disconnected_graph() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c       d  ---  e
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(d, e, 1.into());
+
+        // When:
+        let centralities = Harmonic::init(&graph).execute(&graph);
+
+        // Then: Only vertices of the same component contribute.
+        assert_eq!(centralities[&a], 1.5);
+        assert_eq!(centralities[&b], 2.0);
+        assert_eq!(centralities[&c], 1.5);
+        assert_eq!(centralities[&d], 1.0);
+        assert_eq!(centralities[&e], 1.0);
+    }
+
+    #[test]
+    fn directed_graph() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c
+        //      |               ^
+        //      '---------------'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(a, c, 1.into());
+
+        // When:
+        let centralities = Harmonic::init(&graph).execute(&graph);
+
+        // Then: Distances are measured from other vertices to each vertex.
+        assert_eq!(centralities[&a], 0.0);
+        assert_eq!(centralities[&b], 1.0);
+        assert_eq!(centralities[&c], 2.0);
+    }
+}
@@ -0,0 +1,265 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+
+use crate::algo::centrality::in_neighbors;
+use crate::graph::Error;
+use crate::provide::{IdMap, Neighbors, Vertices};
+
+/// Computes Katz centrality of vertices using power iteration.
+///
+/// Katz centrality of a vertex is `alpha` times the sum of centralities of the vertices that have an edge to it, plus `beta`.
+/// So walks of length k from any vertex contribute to centrality of their destination, attenuated by `alpha` to the power of k.
+/// Iterations converge only if `alpha` is less than the inverse of the largest eigenvalue of the adjacency matrix.
+///
+/// Weights of the edges are ignored, and in undirected graphs each edge can be followed in both directions.
+/// Centralities are normalized so their Euclidean norm is one.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::centrality::Katz;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  -->  b  <--  c
+/// //
+/// let mut graph = MatGraph::init(DiMat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(c, b, 1.into());
+///
+/// let centralities = Katz::init(&graph).with_alpha(0.5).execute(&graph).unwrap();
+///
+/// // Centrality of b is 0.5 * (1 + 1) + 1 = 2 before normalization.
+/// assert!((centralities[&b] / centralities[&a] - 2.0).abs() < 1e-9);
+/// assert_eq!(centralities[&a], centralities[&c]);
+/// ```
+pub struct Katz {
+    id_map: IdMap,
+    in_neighbors_of: Vec<Vec<usize>>,
+
+    alpha: f64,
+    beta: f64,
+    tolerance: f64,
+    max_iterations: usize,
+}
+
+impl Katz {
+    /// # Arguments
+    /// `graph`: Graph to compute centrality of its vertices.
+    ///
+    /// # Returns
+    /// Initialized Katz centrality with `alpha` of 0.1, `beta` of 1, tolerance of 1e-10 and at most 1000 iterations.
+    pub fn init<G: Neighbors + Vertices>(graph: &G) -> Self {
+        let (id_map, in_neighbors_of) = in_neighbors(graph);
+
+        Katz {
+            id_map,
+            in_neighbors_of,
+
+            alpha: 0.1,
+            beta: 1.0,
+            tolerance: 1e-10,
+            max_iterations: 1000,
+        }
+    }
+
+    /// # Arguments
+    /// `alpha`: Attenuation factor of walks.
+    ///
+    /// # Returns
+    /// Katz centrality with the specified attenuation factor.
+    pub fn with_alpha(mut self, alpha: f64) -> Self {
+        self.alpha = alpha;
+
+        self
+    }
+
+    /// # Arguments
+    /// `beta`: Centrality that each vertex gets regardless of its edges.
+    ///
+    /// # Returns
+    /// Katz centrality with the specified `beta`.
+    pub fn with_beta(mut self, beta: f64) -> Self {
+        self.beta = beta;
+
+        self
+    }
+
+    /// # Arguments
+    /// `tolerance`: Iterations stop when sum of the changes of centralities in an iteration is less than `tolerance`.
+    ///
+    /// # Returns
+    /// Katz centrality with the specified tolerance.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+
+        self
+    }
+
+    /// # Arguments
+    /// `max_iterations`: Maximum number of iterations before giving up.
+    ///
+    /// # Returns
+    /// Katz centrality with the specified maximum number of iterations.
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+
+        self
+    }
+
+    /// # Returns
+    /// * `Ok`: Containing centrality of each vertex, mapped from its id.
+    /// * `Err`: [`NotConverged`](crate::graph::ErrorKind::NotConverged) if centralities did not converge within the maximum number of iterations.
+    ///
+    /// # Complexity
+    /// O(`max_iterations` * (|V| + |E|))
+    pub fn execute<G: Neighbors + Vertices>(&self, _: &G) -> Result<HashMap<usize, f64>> {
+        let vertex_count = self.in_neighbors_of.len();
+
+        let mut centralities = vec![0.0; vertex_count];
+        for _ in 0..self.max_iterations {
+            let next_centralities = self
+                .in_neighbors_of
+                .iter()
+                .map(|in_neighbors| {
+                    self.alpha
+                        * in_neighbors
+                            .iter()
+                            .map(|src_id| centralities[*src_id])
+                            .sum::<f64>()
+                        + self.beta
+                })
+                .collect::<Vec<f64>>();
+
+            let change = centralities
+                .iter()
+                .zip(&next_centralities)
+                .map(|(centrality, next_centrality)| (centrality - next_centrality).abs())
+                .sum::<f64>();
+
+            centralities = next_centralities;
+
+            if change < self.tolerance {
+                let norm = centralities.iter().map(|c| c * c).sum::<f64>().sqrt();
+
+                return Ok(centralities
+                    .into_iter()
+                    .enumerate()
+                    .map(|(virt_id, centrality)| {
+                        let centrality = if norm > 0.0 {
+                            centrality / norm
+                        } else {
+                            centrality
+                        };
+
+                        (self.id_map.real_id_of(virt_id), centrality)
+                    })
+                    .collect());
+            }
+        }
+
+        Err(Error::new_ncv(self.max_iterations))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ErrorKind, MatGraph};
+    use crate::provide::*;
+    use crate::storage::{DiMat, Mat};
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let centralities = Katz::init(&graph).execute(&graph).unwrap();
+
+        assert!(centralities.is_empty());
+    }
+
+    #[test]
+    fn directed_path() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // When:
+        let centralities = Katz::init(&graph)
+            .with_alpha(0.5)
+            .with_beta(2.0)
+            .execute(&graph)
+            .unwrap();
+
+        // Then: Before normalization centralities are 2, 3 and 3.5.
+        let norm = (4.0_f64 + 9.0 + 12.25).sqrt();
+        assert!((centralities[&a] - 2.0 / norm).abs() < 1e-9);
+        assert!((centralities[&b] - 3.0 / norm).abs() < 1e-9);
+        assert!((centralities[&c] - 3.5 / norm).abs() < 1e-9);
+    }
+
+    #[test]
+    fn star() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c
+        //              |
+        //              d
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(b, d, 1.into());
+
+        // When:
+        let centralities = Katz::init(&graph).execute(&graph).unwrap();
+
+        // Then:
+        assert!(centralities[&b] > centralities[&a]);
+        assert!((centralities[&a] - centralities[&c]).abs() < 1e-9);
+        assert!((centralities[&a] - centralities[&d]).abs() < 1e-9);
+        assert!((centralities.values().map(|c| c * c).sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn divergence() {
+        // Given: Graph
+        //
+        //      a  ---  b
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+
+        // When: Alpha is larger than the inverse of the largest eigenvalue, which is 1.
+        let result = Katz::init(&graph)
+            .with_alpha(1.5)
+            .with_max_iterations(50)
+            .execute(&graph);
+
+        // Then:
+        let error = result.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            ErrorKind::NotConverged
+        ));
+    }
+}
@@ -0,0 +1,27 @@
+mod betweenness;
+mod eigenvector;
+mod harmonic;
+mod katz;
+mod page_rank;
+
+pub use betweenness::Betweenness;
+pub use eigenvector::Eigenvector;
+pub use harmonic::Harmonic;
+pub use katz::Katz;
+pub use page_rank::PageRank;
+
+use crate::provide::{IdMap, Neighbors, Vertices};
+
+// Returns the continuous id map of the graph, and virtual id of the vertices that have an edge to each vertex.
+fn in_neighbors<G: Neighbors + Vertices>(graph: &G) -> (IdMap, Vec<Vec<usize>>) {
+    let id_map = graph.continuous_id_map();
+
+    let mut in_neighbors_of = vec![vec![]; graph.vertex_count()];
+    for src_id in 0..graph.vertex_count() {
+        for dst_id in graph.neighbors_unchecked(id_map.real_id_of(src_id)) {
+            in_neighbors_of[id_map.virt_id_of(dst_id)].push(src_id);
+        }
+    }
+
+    (id_map, in_neighbors_of)
+}
@@ -0,0 +1,448 @@
+use std::collections::{HashMap, VecDeque};
+
+use anyhow::Result;
+
+use crate::graph::Error;
+use crate::provide::{IdMap, Neighbors, Vertices};
+
+/// Computes PageRank of vertices, optionally personalized to a set of seed vertices.
+///
+/// A random surfer follows a random edge from its current vertex with probability of `damping`, and teleports otherwise.
+/// PageRank of a vertex is the probability of the surfer being at that vertex in the long run.
+/// In classic PageRank the surfer teleports to a uniformly random vertex. In personalized PageRank it teleports according to a personalization vector,
+/// so the ranks measure how related each vertex is to the seed vertices. Vertices without outgoing edges teleport the surfer the same way.
+///
+/// Weights of the edges are ignored, and in undirected graphs each edge can be followed in both directions.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::centrality::PageRank;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  -->  b  -->  c  -->  d
+/// //      ^               |
+/// //      '---------------'
+/// //
+/// let mut graph = MatGraph::init(DiMat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, a, 1.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+///
+/// let page_rank = PageRank::init(&graph);
+///
+/// let ranks = page_rank.execute(&graph);
+/// assert!((ranks.values().sum::<f64>() - 1.0).abs() < 1e-9);
+///
+/// // Relatedness of vertices to a decreases along the path.
+/// let related = page_rank.execute_with_seeds(&graph, &[a]).unwrap();
+/// assert!(related[&a] > related[&b]);
+/// assert!(related[&b] > related[&c]);
+/// assert!(related[&c] > related[&d]);
+/// ```
+pub struct PageRank {
+    id_map: IdMap,
+    neighbors_of: Vec<Vec<usize>>,
+
+    damping: f64,
+    tolerance: f64,
+    max_iterations: usize,
+}
+
+impl PageRank {
+    /// # Arguments
+    /// `graph`: Graph to compute ranks of its vertices.
+    ///
+    /// # Returns
+    /// Initialized PageRank with damping factor of 0.85, tolerance of 1e-10 and at most 100 iterations.
+    pub fn init<G: Neighbors + Vertices>(graph: &G) -> Self {
+        let id_map = graph.continuous_id_map();
+
+        let neighbors_of = (0..graph.vertex_count())
+            .map(|virt_id| {
+                graph
+                    .neighbors_unchecked(id_map.real_id_of(virt_id))
+                    .into_iter()
+                    .map(|dst_id| id_map.virt_id_of(dst_id))
+                    .collect()
+            })
+            .collect();
+
+        PageRank {
+            id_map,
+            neighbors_of,
+
+            damping: 0.85,
+            tolerance: 1e-10,
+            max_iterations: 100,
+        }
+    }
+
+    /// # Arguments
+    /// `damping`: Probability of following an edge instead of teleporting. It's clamped to range of 0 to 1.
+    ///
+    /// # Returns
+    /// PageRank with the specified damping factor.
+    pub fn with_damping(mut self, damping: f64) -> Self {
+        self.damping = damping.clamp(0.0, 1.0);
+
+        self
+    }
+
+    /// # Arguments
+    /// `tolerance`: Iterations stop when sum of the changes of ranks in an iteration is less than `tolerance`.
+    ///
+    /// # Returns
+    /// PageRank with the specified tolerance.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+
+        self
+    }
+
+    /// # Arguments
+    /// `max_iterations`: Maximum number of iterations, even if ranks have not converged.
+    ///
+    /// # Returns
+    /// PageRank with the specified maximum number of iterations.
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+
+        self
+    }
+
+    /// # Returns
+    /// Rank of each vertex, mapped from its id. Ranks sum up to one.
+    ///
+    /// # Complexity
+    /// O(`max_iterations` * (|V| + |E|))
+    pub fn execute<G: Neighbors + Vertices>(&self, _: &G) -> HashMap<usize, f64> {
+        let vertex_count = self.neighbors_of.len();
+
+        self.power_iteration(vec![1.0 / vertex_count as f64; vertex_count])
+    }
+
+    /// # Arguments
+    /// * `graph`: Graph to compute ranks of its vertices.
+    /// * `personalization`: Probability of teleporting to each vertex, mapped from its id. Vertices that are not in the map are never teleported to.
+    ///   Values must be non-negative, and they get normalized to sum up to one.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing rank of each vertex, mapped from its id. Ranks sum up to one.
+    /// * `Err`: [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if a vertex in `personalization` does not exist.
+    ///   If all values of `personalization` are zero, it's the same as [`execute`](crate::algo::centrality::PageRank::execute).
+    ///
+    /// # Complexity
+    /// O(`max_iterations` * (|V| + |E|))
+    pub fn execute_personalized<G: Neighbors + Vertices>(
+        &self,
+        graph: &G,
+        personalization: &HashMap<usize, f64>,
+    ) -> Result<HashMap<usize, f64>> {
+        let total = personalization.values().sum::<f64>();
+        if total <= 0.0 {
+            return Ok(self.execute(graph));
+        }
+
+        let mut teleport = vec![0.0; self.neighbors_of.len()];
+        for (vertex_id, value) in personalization {
+            match self.id_map.get_virt_id(*vertex_id) {
+                Some(virt_id) => teleport[virt_id] = value / total,
+                None => Err(Error::new_vnf(*vertex_id))?,
+            }
+        }
+
+        Ok(self.power_iteration(teleport))
+    }
+
+    /// # Arguments
+    /// * `graph`: Graph to compute ranks of its vertices.
+    /// * `seeds`: Id of the vertices to teleport to, with equal probability.
+    ///
+    /// # Returns
+    /// Checkout [`execute_personalized`](crate::algo::centrality::PageRank::execute_personalized).
+    pub fn execute_with_seeds<G: Neighbors + Vertices>(
+        &self,
+        graph: &G,
+        seeds: &[usize],
+    ) -> Result<HashMap<usize, f64>> {
+        let personalization = seeds.iter().map(|seed| (*seed, 1.0)).collect();
+
+        self.execute_personalized(graph, &personalization)
+    }
+
+    /// Approximates personalized PageRank with a single seed, by pushing probability mass from the seed to its neighborhood.
+    /// Only vertices near the seed are visited. So it's much faster than [`execute_with_seeds`](crate::algo::centrality::PageRank::execute_with_seeds)
+    /// when only the most related vertices to the seed are needed.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to compute ranks of its vertices.
+    /// * `seed`: Id of the vertex to teleport to.
+    /// * `epsilon`: Error of rank of each vertex is less than `epsilon` times the number of its outgoing edges.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing approximated rank of the visited vertices, mapped from their ids. Ranks of other vertices are zero.
+    /// * `Err`: [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if seed does not exist.
+    ///
+    /// # Complexity
+    /// O(1 / (`epsilon` * (1 - `damping`))), regardless of the size of the graph.
+    pub fn approximate<G: Neighbors + Vertices>(
+        &self,
+        _: &G,
+        seed: usize,
+        epsilon: f64,
+    ) -> Result<HashMap<usize, f64>> {
+        let seed = match self.id_map.get_virt_id(seed) {
+            Some(virt_id) => virt_id,
+            None => Err(Error::new_vnf(seed))?,
+        };
+
+        let mut rank_of = HashMap::new();
+        let mut residual_of = HashMap::new();
+        residual_of.insert(seed, 1.0);
+
+        // Only vertices with enough residual are pushed. Each vertex counts as having at least one edge, because dangling vertices push to the seed.
+        let threshold = |virt_id: usize| epsilon * self.neighbors_of[virt_id].len().max(1) as f64;
+
+        let mut queue = VecDeque::new();
+        queue.push_back(seed);
+
+        while let Some(virt_id) = queue.pop_front() {
+            let residual = residual_of.get(&virt_id).copied().unwrap_or(0.0);
+            if residual < threshold(virt_id) {
+                continue;
+            }
+
+            residual_of.insert(virt_id, 0.0);
+            *rank_of.entry(virt_id).or_insert(0.0) += (1.0 - self.damping) * residual;
+
+            let neighbors = &self.neighbors_of[virt_id];
+            let (targets, share) = if neighbors.is_empty() {
+                (std::slice::from_ref(&seed), self.damping * residual)
+            } else {
+                (
+                    neighbors.as_slice(),
+                    self.damping * residual / neighbors.len() as f64,
+                )
+            };
+
+            for dst_id in targets {
+                let dst_residual = residual_of.entry(*dst_id).or_insert(0.0);
+                let was_below = *dst_residual < threshold(*dst_id);
+                *dst_residual += share;
+
+                if was_below && *dst_residual >= threshold(*dst_id) {
+                    queue.push_back(*dst_id);
+                }
+            }
+        }
+
+        Ok(rank_of
+            .into_iter()
+            .map(|(virt_id, rank)| (self.id_map.real_id_of(virt_id), rank))
+            .collect())
+    }
+
+    fn power_iteration(&self, teleport: Vec<f64>) -> HashMap<usize, f64> {
+        let vertex_count = self.neighbors_of.len();
+
+        let mut ranks = teleport.clone();
+        for _ in 0..self.max_iterations {
+            let dangling_rank = (0..vertex_count)
+                .filter(|virt_id| self.neighbors_of[*virt_id].is_empty())
+                .map(|virt_id| ranks[virt_id])
+                .sum::<f64>();
+
+            // Rank that is teleported, either by choice or from dangling vertices.
+            let teleported_rank = (1.0 - self.damping) + self.damping * dangling_rank;
+            let mut next_ranks = teleport
+                .iter()
+                .map(|probability| teleported_rank * probability)
+                .collect::<Vec<f64>>();
+
+            for (src_id, neighbors) in self.neighbors_of.iter().enumerate() {
+                let share = self.damping * ranks[src_id] / neighbors.len() as f64;
+
+                for dst_id in neighbors {
+                    next_ranks[*dst_id] += share;
+                }
+            }
+
+            let change = ranks
+                .iter()
+                .zip(&next_ranks)
+                .map(|(rank, next_rank)| (rank - next_rank).abs())
+                .sum::<f64>();
+
+            ranks = next_ranks;
+
+            if change < self.tolerance {
+                break;
+            }
+        }
+
+        ranks
+            .into_iter()
+            .enumerate()
+            .map(|(virt_id, rank)| (self.id_map.real_id_of(virt_id), rank))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::*;
+    use crate::storage::{DiMat, Mat};
+
+    fn assert_close(lhs: f64, rhs: f64, tolerance: f64) {
+        assert!(
+            (lhs - rhs).abs() < tolerance,
+            "{} is not close to {}",
+            lhs,
+            rhs
+        );
+    }
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let ranks = PageRank::init(&graph).execute(&graph);
+
+        assert!(ranks.is_empty());
+    }
+
+    #[test]
+    fn symmetric_graph() {
+        // Given: Graph
+        //
+        //      a  ---  b
+        //      |       |
+        //      d  ---  c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+        graph.add_edge_unchecked(d, a, 1.into());
+
+        // When:
+        let ranks = PageRank::init(&graph).execute(&graph);
+
+        // Then:
+        for vertex_id in graph.vertices() {
+            assert_close(ranks[&vertex_id], 0.25, 1e-9);
+        }
+    }
+
+    #[test]
+    fn dangling_vertex() {
+        // Given: Graph
+        //
+        //      a  -->  b
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+
+        // When:
+        let ranks = PageRank::init(&graph).with_damping(0.5).execute(&graph);
+
+        // Then: Rank of b is teleported uniformly. So rank(a) = 1/4 + rank(b)/4 and rank(b) = 1/4 + rank(b)/4 + rank(a)/2.
+        assert_close(ranks[&a], 0.4, 1e-9);
+        assert_close(ranks[&b], 0.6, 1e-9);
+    }
+
+    #[test]
+    fn personalized() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c  ---  d  ---  e
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let vertices: Vec<usize> = (0..5).map(|_| graph.add_vertex()).collect();
+        for window in vertices.windows(2) {
+            graph.add_edge_unchecked(window[0], window[1], 1.into());
+        }
+        let page_rank = PageRank::init(&graph);
+
+        // When: Seeding from a.
+        let ranks = page_rank
+            .execute_with_seeds(&graph, &[vertices[0]])
+            .unwrap();
+
+        // Then: Ranks decrease by distance from a.
+        assert_close(ranks.values().sum(), 1.0, 1e-9);
+        for window in vertices[1..].windows(2) {
+            assert!(ranks[&window[0]] > ranks[&window[1]]);
+        }
+
+        // When: Seeding from both ends.
+        let ranks = page_rank
+            .execute_with_seeds(&graph, &[vertices[0], vertices[4]])
+            .unwrap();
+
+        // Then: Ranks are symmetric.
+        assert_close(ranks[&vertices[0]], ranks[&vertices[4]], 1e-9);
+        assert_close(ranks[&vertices[1]], ranks[&vertices[3]], 1e-9);
+
+        // And: Seeds must exist.
+        assert!(page_rank.execute_with_seeds(&graph, &[10]).is_err());
+        assert!(page_rank.approximate(&graph, 10, 1e-4).is_err());
+    }
+
+    #[test]
+    fn push_approximation() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c  -->  d
+        //      ^       |
+        //      '-------'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, a, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+        let page_rank = PageRank::init(&graph);
+
+        // When:
+        let exact = page_rank.execute_with_seeds(&graph, &[b]).unwrap();
+        let approximated = page_rank.approximate(&graph, b, 1e-6).unwrap();
+
+        // Then:
+        for vertex_id in graph.vertices() {
+            assert_close(
+                approximated.get(&vertex_id).copied().unwrap_or(0.0),
+                exact[&vertex_id],
+                1e-4,
+            );
+        }
+
+        // When: Approximating with a large epsilon.
+        let approximated = page_rank.approximate(&graph, b, 0.5).unwrap();
+
+        // Then: Only the seed is visited.
+        assert_eq!(approximated.len(), 1);
+        assert_close(approximated[&b], 0.15, 1e-9);
+    }
+}
@@ -0,0 +1,454 @@
+use std::collections::{HashMap, VecDeque};
+
+use anyhow::Result;
+
+use crate::graph::{Edge, Error, UndirectedEdge};
+use crate::provide;
+
+/// Colors edges of an undirected graph so that edges sharing an end point have different colors.
+///
+/// If graph is bipartite, edges are colored with Δ colors which is optimal(König's theorem): each edge gets a color that is free at one of its end points,
+/// after swapping two colors along an alternating path if needed.
+/// Otherwise Misra & Gries algorithm is used, which follows the proof of Vizing's theorem and colors edges with at most Δ + 1 colors.
+/// Both bounds only hold for simple graphs, so graphs with loops or multiple edges between two vertices are rejected.
+///
+/// ## Note
+/// * Δ: Means maximum degree of the vertices.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::EdgeColoring;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b
+/// //      |     /
+/// //      |   /
+/// //      | /
+/// //      c
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let ab = graph.add_edge_unchecked(a, b, 1.into());
+/// let bc = graph.add_edge_unchecked(b, c, 1.into());
+/// let ca = graph.add_edge_unchecked(c, a, 1.into());
+///
+/// let colors = EdgeColoring::init(&graph).unwrap().execute(&graph);
+///
+/// assert_ne!(colors[&ab], colors[&bc]);
+/// assert_ne!(colors[&bc], colors[&ca]);
+/// assert_ne!(colors[&ca], colors[&ab]);
+/// ```
+pub struct EdgeColoring {
+    // Neighbors of each vertex, in the format of: (neighbor_id, edge_id), using virtual ids.
+    adjacents_of: Vec<Vec<(usize, usize)>>,
+
+    // Maps each color to the neighbor that is connected to each vertex by an edge with that color.
+    neighbor_by_color: Vec<HashMap<usize, usize>>,
+
+    // Color of each colored edge, keyed by its end points(smaller id first).
+    color_of: HashMap<(usize, usize), usize>,
+}
+
+impl EdgeColoring {
+    /// # Arguments
+    /// `graph`: Graph to color its edges.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing the initialized algorithm.
+    /// * `Err`:
+    ///     * [`Loop`](crate::graph::ErrorKind::Loop) if graph has an edge from a vertex to itself.
+    ///     * [`MultiEdge`](crate::graph::ErrorKind::MultiEdge) if graph has more than one edge between two vertices.
+    pub fn init<G, W, E: Edge<W>>(graph: &G) -> Result<Self>
+    where
+        G: provide::Graph<W, E, UndirectedEdge> + provide::Vertices + provide::Edges<W, E>,
+    {
+        let id_map = graph.continuous_id_map();
+        let vertex_count = graph.vertex_count();
+
+        let mut adjacents_of = vec![vec![]; vertex_count];
+        let mut edge_id_of = HashMap::new();
+        for (src_id, dst_id, edge) in graph.edges() {
+            if src_id == dst_id {
+                Err(Error::new_l(src_id))?
+            } else if *edge_id_of
+                .entry(Self::key_of(src_id, dst_id))
+                .or_insert(edge.get_id())
+                != edge.get_id()
+            {
+                Err(Error::new_me(src_id, dst_id))?
+            }
+
+            let src_virt_id = id_map.virt_id_of(src_id);
+            let dst_virt_id = id_map.virt_id_of(dst_id);
+
+            adjacents_of[src_virt_id].push((dst_virt_id, edge.get_id()));
+            adjacents_of[dst_virt_id].push((src_virt_id, edge.get_id()));
+        }
+
+        Ok(EdgeColoring {
+            adjacents_of,
+            neighbor_by_color: vec![HashMap::new(); vertex_count],
+            color_of: HashMap::new(),
+        })
+    }
+
+    fn key_of(src_id: usize, dst_id: usize) -> (usize, usize) {
+        (src_id.min(dst_id), src_id.max(dst_id))
+    }
+
+    fn is_free(&self, virt_id: usize, color: usize) -> bool {
+        !self.neighbor_by_color[virt_id].contains_key(&color)
+    }
+
+    // Smallest color that is free at vertex with virtual id: `virt_id`.
+    fn free_color_of(&self, virt_id: usize) -> usize {
+        (0..).find(|color| self.is_free(virt_id, *color)).unwrap()
+    }
+
+    fn set_color(&mut self, src_id: usize, dst_id: usize, color: usize) {
+        self.neighbor_by_color[src_id].insert(color, dst_id);
+        self.neighbor_by_color[dst_id].insert(color, src_id);
+        self.color_of.insert(Self::key_of(src_id, dst_id), color);
+    }
+
+    fn remove_color(&mut self, src_id: usize, dst_id: usize) {
+        if let Some(color) = self.color_of.remove(&Self::key_of(src_id, dst_id)) {
+            self.neighbor_by_color[src_id].remove(&color);
+            self.neighbor_by_color[dst_id].remove(&color);
+        }
+    }
+
+    // Swaps colors `c1` and `c2` on the path that starts from vertex with virtual id: `virt_id` and alternates between edges with color `c1` and `c2`.
+    fn invert_path(&mut self, virt_id: usize, c1: usize, c2: usize) {
+        let mut path = vec![];
+
+        let mut current_id = virt_id;
+        let mut color = c1;
+        while let Some(next_id) = self.neighbor_by_color[current_id].get(&color).copied() {
+            path.push((current_id, next_id, color));
+
+            current_id = next_id;
+            color = if color == c1 { c2 } else { c1 };
+        }
+
+        for (src_id, dst_id, _) in &path {
+            self.remove_color(*src_id, *dst_id);
+        }
+        for (src_id, dst_id, color) in path {
+            self.set_color(src_id, dst_id, if color == c1 { c2 } else { c1 });
+        }
+    }
+
+    // Tries to color vertices with two colors, so that each edge connects vertices with different colors.
+    fn is_bipartite(&self) -> bool {
+        let mut side_of = vec![None; self.adjacents_of.len()];
+
+        for start_id in 0..self.adjacents_of.len() {
+            if side_of[start_id].is_some() {
+                continue;
+            }
+
+            side_of[start_id] = Some(false);
+            let mut queue = VecDeque::new();
+            queue.push_back(start_id);
+
+            while let Some(virt_id) = queue.pop_front() {
+                let side = side_of[virt_id].unwrap();
+
+                for (n_id, _) in &self.adjacents_of[virt_id] {
+                    match side_of[*n_id] {
+                        None => {
+                            side_of[*n_id] = Some(!side);
+                            queue.push_back(*n_id);
+                        }
+                        Some(n_side) if n_side == side => return false,
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        true
+    }
+
+    fn color_bipartite_edge(&mut self, src_id: usize, dst_id: usize) {
+        let src_color = self.free_color_of(src_id);
+        let dst_color = self.free_color_of(dst_id);
+
+        // Path from destination that alternates between `src_color` and `dst_color` never reaches source in a bipartite graph.
+        // So after swapping colors on it, `src_color` is free at both end points.
+        if !self.is_free(dst_id, src_color) {
+            self.invert_path(dst_id, src_color, dst_color);
+        }
+
+        self.set_color(src_id, dst_id, src_color);
+    }
+
+    fn color_edge(&mut self, src_id: usize, dst_id: usize) {
+        // Maximal fan of source: each next vertex is connected to source by an edge whose color is free at the previous vertex.
+        let mut fan = vec![dst_id];
+        loop {
+            let last_id = *fan.last().unwrap();
+
+            let next_id = self.adjacents_of[src_id].iter().find_map(|(n_id, _)| {
+                let color = self.color_of.get(&Self::key_of(src_id, *n_id))?;
+
+                if !fan.contains(n_id) && self.is_free(last_id, *color) {
+                    Some(*n_id)
+                } else {
+                    None
+                }
+            });
+
+            match next_id {
+                Some(next_id) => fan.push(next_id),
+                None => break,
+            }
+        }
+
+        let c = self.free_color_of(src_id);
+        let d = self.free_color_of(*fan.last().unwrap());
+
+        // After this, d is free at source.
+        self.invert_path(src_id, d, c);
+
+        // Find a prefix of the fan that is still a fan, and ends at a vertex where d is free.
+        let mut end = 0;
+        for index in 0..fan.len() {
+            if index > 0 {
+                let color = self.color_of.get(&Self::key_of(src_id, fan[index]));
+
+                if !color.is_some_and(|color| self.is_free(fan[index - 1], *color)) {
+                    break;
+                }
+            }
+
+            if self.is_free(fan[index], d) {
+                end = index;
+                break;
+            }
+        }
+
+        // Rotate the fan prefix: each edge takes the color of the next edge, and the last edge gets color d.
+        for index in 0..end {
+            let color = self.color_of[&Self::key_of(src_id, fan[index + 1])];
+
+            self.remove_color(src_id, fan[index + 1]);
+            self.set_color(src_id, fan[index], color);
+        }
+        self.set_color(src_id, fan[end], d);
+    }
+
+    /// # Returns
+    /// Color of each edge, keyed by id of the edge. Colors are in range of 0 to Δ - 1 if graph is bipartite, and 0 to Δ otherwise.
+    ///
+    /// # Complexity
+    /// * Bipartite graphs: O(|E| * |V|)
+    /// * Other graphs: O(|E| * |V| * Δ)
+    pub fn execute<G, W, E: Edge<W>>(mut self, _: &G) -> HashMap<usize, usize>
+    where
+        G: provide::Graph<W, E, UndirectedEdge> + provide::Vertices + provide::Edges<W, E>,
+    {
+        let is_bipartite = self.is_bipartite();
+
+        let mut edge_ids = HashMap::new();
+        for src_id in 0..self.adjacents_of.len() {
+            for (dst_id, edge_id) in self.adjacents_of[src_id].clone() {
+                if src_id < dst_id {
+                    edge_ids.insert(Self::key_of(src_id, dst_id), edge_id);
+
+                    if is_bipartite {
+                        self.color_bipartite_edge(src_id, dst_id);
+                    } else {
+                        self.color_edge(src_id, dst_id);
+                    }
+                }
+            }
+        }
+
+        self.color_of
+            .into_iter()
+            .map(|(key, color)| (edge_ids[&key], color))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ErrorKind, MatGraph, SimpleGraph};
+    use crate::provide::*;
+    use crate::storage::{List, Mat};
+    use std::collections::HashSet;
+
+    // Checks that edges sharing an end point have different colors, and returns number of used colors.
+    fn assert_proper<G: Edges<usize, E>, E: Edge<usize>>(
+        graph: &G,
+        colors: &HashMap<usize, usize>,
+    ) -> usize {
+        let edges = graph.edges();
+        assert_eq!(colors.len(), edges.len());
+
+        for (src_id, dst_id, edge) in &edges {
+            for (other_src_id, other_dst_id, other_edge) in &edges {
+                let is_adjacent = src_id == other_src_id
+                    || src_id == other_dst_id
+                    || dst_id == other_src_id
+                    || dst_id == other_dst_id;
+
+                if edge.get_id() != other_edge.get_id() && is_adjacent {
+                    assert_ne!(colors[&edge.get_id()], colors[&other_edge.get_id()]);
+                }
+            }
+        }
+
+        colors.values().collect::<HashSet<_>>().len()
+    }
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let colors = EdgeColoring::init(&graph).unwrap().execute(&graph);
+
+        assert!(colors.is_empty());
+    }
+
+    #[test]
+    fn odd_cycle() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c
+        //      |               |
+        //      e  -----------  d
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let ids = (0..5).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        for i in 0..5 {
+            graph.add_edge_unchecked(ids[i], ids[(i + 1) % 5], 1.into());
+        }
+
+        // When: Coloring edges.
+        let colors = EdgeColoring::init(&graph).unwrap().execute(&graph);
+
+        // Then: Odd cycle needs Δ + 1 colors.
+        assert_eq!(assert_proper(&graph, &colors), 3);
+    }
+
+    #[test]
+    fn complete_graphs() {
+        for vertex_count in 2..8 {
+            // Given: Complete graph.
+            let mut graph = MatGraph::init(Mat::<usize>::init());
+            let ids = (0..vertex_count)
+                .map(|_| graph.add_vertex())
+                .collect::<Vec<usize>>();
+            for i in 0..vertex_count {
+                for j in i + 1..vertex_count {
+                    graph.add_edge_unchecked(ids[i], ids[j], 1.into());
+                }
+            }
+
+            // When: Coloring edges.
+            let colors = EdgeColoring::init(&graph).unwrap().execute(&graph);
+
+            // Then: At most Δ + 1 colors are used.
+            assert!(assert_proper(&graph, &colors) <= vertex_count);
+        }
+    }
+
+    #[test]
+    fn petersen_graph() {
+        // Given: Petersen graph which has Δ = 3 but needs 4 colors.
+        let mut graph = SimpleGraph::init(List::<usize>::init());
+        let ids = (0..10).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        for i in 0..5 {
+            graph.add_edge_unchecked(ids[i], ids[(i + 1) % 5], 1.into());
+            graph.add_edge_unchecked(ids[i], ids[i + 5], 1.into());
+            graph.add_edge_unchecked(ids[i + 5], ids[(i + 2) % 5 + 5], 1.into());
+        }
+
+        // When: Coloring edges.
+        let colors = EdgeColoring::init(&graph).unwrap().execute(&graph);
+
+        // Then:
+        assert_eq!(assert_proper(&graph, &colors), 4);
+    }
+
+    #[test]
+    fn bipartite_graphs() {
+        // Given: Complete bipartite graph K(3, 4).
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let left_ids = (0..3).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        let right_ids = (0..4).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        for left_id in &left_ids {
+            for right_id in &right_ids {
+                graph.add_edge_unchecked(*left_id, *right_id, 1.into());
+            }
+        }
+
+        // When: Coloring edges.
+        let colors = EdgeColoring::init(&graph).unwrap().execute(&graph);
+
+        // Then: Exactly Δ colors are used.
+        assert_eq!(assert_proper(&graph, &colors), 4);
+
+        // Given: 5 x 5 grid.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let ids = (0..25).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        for row in 0..5 {
+            for col in 0..5 {
+                if col < 4 {
+                    graph.add_edge_unchecked(ids[row * 5 + col], ids[row * 5 + col + 1], 1.into());
+                }
+                if row < 4 {
+                    graph.add_edge_unchecked(
+                        ids[row * 5 + col],
+                        ids[(row + 1) * 5 + col],
+                        1.into(),
+                    );
+                }
+            }
+        }
+
+        // When: Coloring edges.
+        let colors = EdgeColoring::init(&graph).unwrap().execute(&graph);
+
+        // Then:
+        assert_eq!(assert_proper(&graph, &colors), 4);
+    }
+
+    #[test]
+    fn loops_and_multi_edges() {
+        // Given: Graph with a loop, and graph with two edges between the same vertices.
+        let mut loop_graph = SimpleGraph::init(List::<usize>::init());
+        let a = loop_graph.add_vertex();
+        loop_graph.add_edge_unchecked(a, a, 1.into());
+
+        let mut multi_graph = SimpleGraph::init(List::<usize>::init());
+        let a = multi_graph.add_vertex();
+        let b = multi_graph.add_vertex();
+        multi_graph.add_edge_unchecked(a, b, 1.into());
+        multi_graph.add_edge_unchecked(b, a, 1.into());
+
+        // When:
+        let loop_error = EdgeColoring::init(&loop_graph).err().unwrap();
+        let multi_error = EdgeColoring::init(&multi_graph).err().unwrap();
+
+        // Then:
+        assert_eq!(
+            loop_error.downcast_ref::<Error>().unwrap().kind(),
+            &ErrorKind::Loop
+        );
+        assert_eq!(
+            multi_error.downcast_ref::<Error>().unwrap().kind(),
+            &ErrorKind::MultiEdge
+        );
+    }
+}
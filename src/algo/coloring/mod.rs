@@ -0,0 +1,5 @@
+mod edge_coloring;
+mod vertex_coloring;
+
+pub use edge_coloring::EdgeColoring;
+pub use vertex_coloring::VertexColoring;
@@ -0,0 +1,427 @@
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+
+use crate::algo::anytime::BudgetClock;
+use crate::algo::{AnytimeSolution, Budget};
+use crate::graph::{Edge, EdgeDir};
+use crate::provide::{Graph, IdMap, Neighbors, Vertices};
+
+/// Colors vertices of a graph with the minimum number of colors, so that adjacent vertices have different colors.
+///
+/// In directed graphs, direction of the edges is ignored, and loops are ignored.
+/// First the graph is colored greedily using DSatur(Brélaz), which colors the vertex with the most distinct colors among its neighbors first.
+/// Then a branch and bound search with the same order of vertices looks for colorings with fewer colors.
+/// The search stops early if the number of colors reaches the size of a clique, because vertices of a clique need different colors.
+///
+/// Running time is exponential in the worst case. Use [`execute_within`](crate::algo::VertexColoring::execute_within) to get the best coloring found within a [`Budget`](crate::algo::Budget).
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::{Budget, VertexColoring};
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b
+/// //      |       |
+/// //      d  ---  c
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+/// graph.add_edge_unchecked(d, a, 1.into());
+///
+/// let coloring = VertexColoring::init(&graph).execute_within(&graph, Budget::init().with_iteration_limit(100));
+/// let colors = coloring.solution();
+///
+/// assert_eq!(coloring.value(), 2);
+/// assert!(coloring.is_optimal());
+/// assert_eq!(colors[&a], colors[&c]);
+/// assert_ne!(colors[&a], colors[&b]);
+/// ```
+pub struct VertexColoring {
+    id_map: IdMap,
+
+    // Neighbors of each vertex ignoring direction of the edges and loops, using virtual ids.
+    adjacents_of: Vec<HashSet<usize>>,
+}
+
+impl VertexColoring {
+    pub fn init<W, E, Ty, G>(graph: &G) -> Self
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Neighbors + Vertices + Graph<W, E, Ty>,
+    {
+        let id_map = graph.continuous_id_map();
+        let vertex_count = graph.vertex_count();
+
+        let mut adjacents_of = vec![HashSet::new(); vertex_count];
+        for virt_id in 0..vertex_count {
+            for n_id in graph.neighbors_unchecked(id_map.real_id_of(virt_id)) {
+                let n_virt_id = id_map.virt_id_of(n_id);
+
+                if n_virt_id != virt_id {
+                    adjacents_of[virt_id].insert(n_virt_id);
+                    adjacents_of[n_virt_id].insert(virt_id);
+                }
+            }
+        }
+
+        VertexColoring {
+            id_map,
+            adjacents_of,
+        }
+    }
+
+    fn neighbor_colors_of(&self, virt_id: usize, colors: &[Option<usize>]) -> HashSet<usize> {
+        self.adjacents_of[virt_id]
+            .iter()
+            .filter_map(|n_virt_id| colors[*n_virt_id])
+            .collect()
+    }
+
+    // Uncolored vertex with the most distinct colors among its neighbors. Ties are broken by degree, and then by id.
+    fn most_saturated(&self, colors: &[Option<usize>]) -> usize {
+        (0..colors.len())
+            .filter(|virt_id| colors[*virt_id].is_none())
+            .max_by_key(|virt_id| {
+                (
+                    self.neighbor_colors_of(*virt_id, colors).len(),
+                    self.adjacents_of[*virt_id].len(),
+                    Reverse(*virt_id),
+                )
+            })
+            .unwrap()
+    }
+
+    // Colors each vertex with the smallest color that none of its neighbors has, in the order of DSatur.
+    fn greedy_coloring(&self) -> Vec<usize> {
+        let mut colors = vec![None; self.adjacents_of.len()];
+
+        for _ in 0..colors.len() {
+            let virt_id = self.most_saturated(&colors);
+            let neighbor_colors = self.neighbor_colors_of(virt_id, &colors);
+
+            colors[virt_id] = (0..).find(|color| !neighbor_colors.contains(color));
+        }
+
+        colors.into_iter().map(|color| color.unwrap()).collect()
+    }
+
+    // Size of a clique that is found by adding vertices in decreasing order of their degree.
+    fn greedy_clique_size(&self) -> usize {
+        let mut order = (0..self.adjacents_of.len()).collect::<Vec<usize>>();
+        order.sort_by_key(|virt_id| Reverse(self.adjacents_of[*virt_id].len()));
+
+        let mut clique = vec![];
+        for virt_id in order {
+            if clique
+                .iter()
+                .all(|member| self.adjacents_of[virt_id].contains(member))
+            {
+                clique.push(virt_id);
+            }
+        }
+
+        clique.len()
+    }
+
+    // `best` is the coloring with the least colors found so far, and the number of its colors.
+    fn branch(
+        &self,
+        colors: &mut [Option<usize>],
+        colored_count: usize,
+        used_count: usize,
+        best: &mut (Vec<usize>, usize),
+        lower_bound: usize,
+        clock: &mut BudgetClock,
+    ) {
+        // Best coloring may have improved since this branch was started.
+        if used_count >= best.1 {
+            return;
+        }
+
+        if colored_count == colors.len() {
+            *best = (
+                colors.iter().map(|color| color.unwrap()).collect(),
+                used_count,
+            );
+            return;
+        }
+
+        if !clock.tick() {
+            return;
+        }
+
+        let virt_id = self.most_saturated(colors);
+        let neighbor_colors = self.neighbor_colors_of(virt_id, colors);
+
+        // Only colors that keep the coloring better than the best one are tried, and at most one new color.
+        for color in 0..=used_count {
+            if color + 1 >= best.1 {
+                break;
+            }
+
+            if neighbor_colors.contains(&color) {
+                continue;
+            }
+
+            colors[virt_id] = Some(color);
+            self.branch(
+                colors,
+                colored_count + 1,
+                used_count.max(color + 1),
+                best,
+                lower_bound,
+                clock,
+            );
+            colors[virt_id] = None;
+
+            if best.1 <= lower_bound || clock.is_exhausted() {
+                return;
+            }
+        }
+    }
+
+    /// # Arguments
+    /// `graph`: Graph to color its vertices.
+    ///
+    /// # Returns
+    /// Color of each vertex, mapped from its id. Colors start from zero and are continuous.
+    ///
+    /// # Complexity
+    /// O(k^|V| * |V|.|E|) in the worst case, where k is the number of colors of the greedy coloring.
+    pub fn execute<W, E, Ty, G>(self, graph: &G) -> HashMap<usize, usize>
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Neighbors + Vertices + Graph<W, E, Ty>,
+    {
+        self.execute_within(graph, Budget::init()).into_solution()
+    }
+
+    /// Each node of the search tree uses one iteration of `budget`.
+    /// The greedy coloring is computed before the search starts, so a coloring is returned even if `budget` has no iteration.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to color its vertices.
+    /// * `budget`: Limits the time and iterations of the search.
+    ///
+    /// # Returns
+    /// Color of each vertex in the coloring with the least colors found, mapped from its id, and the number of colors.
+    /// Bound of the solution is a lower bound on the number of colors of any coloring.
+    ///
+    /// # Complexity
+    /// O(k^|V| * |V|.|E|) in the worst case, where k is the number of colors of the greedy coloring.
+    pub fn execute_within<W, E, Ty, G>(
+        self,
+        _: &G,
+        budget: Budget,
+    ) -> AnytimeSolution<HashMap<usize, usize>, usize>
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Neighbors + Vertices + Graph<W, E, Ty>,
+    {
+        let greedy_colors = self.greedy_coloring();
+        let greedy_count = greedy_colors.iter().max().map_or(0, |color| color + 1);
+        let mut best = (greedy_colors, greedy_count);
+        let lower_bound = self.greedy_clique_size();
+
+        let mut clock = budget.start();
+        if greedy_count > lower_bound {
+            let mut colors = vec![None; best.0.len()];
+            self.branch(&mut colors, 0, 0, &mut best, lower_bound, &mut clock);
+        }
+
+        let (colors, color_count) = best;
+
+        // If the search is complete, no coloring with fewer colors exists.
+        let bound = if clock.is_exhausted() {
+            lower_bound
+        } else {
+            color_count
+        };
+
+        let colors = colors
+            .into_iter()
+            .enumerate()
+            .map(|(virt_id, color)| (self.id_map.real_id_of(virt_id), color))
+            .collect();
+
+        AnytimeSolution::init(colors, color_count, bound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ListGraph, MatGraph, UndirectedEdge};
+    use crate::storage::{DiList, Mat};
+    use crate::util::seeded;
+
+    fn graph_of(
+        vertex_count: usize,
+        edges: &[(usize, usize)],
+    ) -> (MatGraph<usize, UndirectedEdge>, Vec<usize>) {
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let ids = (0..vertex_count)
+            .map(|_| graph.add_vertex())
+            .collect::<Vec<usize>>();
+        for (src, dst) in edges {
+            graph.add_edge_unchecked(ids[*src], ids[*dst], 1.into());
+        }
+
+        (graph, ids)
+    }
+
+    // Minimum number of colors by trying to color vertices with one color, two colors and so on.
+    fn brute_force(vertex_count: usize, edges: &[(usize, usize)]) -> usize {
+        fn can_color(
+            colors: &mut Vec<usize>,
+            edges: &[(usize, usize)],
+            k: usize,
+            n: usize,
+        ) -> bool {
+            let index = colors.len();
+            if index == n {
+                return true;
+            }
+
+            for color in 0..k {
+                let is_valid = edges.iter().all(|(src, dst)| {
+                    let is_conflict_with_dst =
+                        *src == index && *dst < index && colors[*dst] == color;
+                    let is_conflict_with_src =
+                        *dst == index && *src < index && colors[*src] == color;
+
+                    !is_conflict_with_dst && !is_conflict_with_src
+                });
+                if is_valid {
+                    colors.push(color);
+                    if can_color(colors, edges, k, n) {
+                        return true;
+                    }
+                    colors.pop();
+                }
+            }
+
+            false
+        }
+
+        (0..=vertex_count)
+            .find(|k| can_color(&mut vec![], edges, *k, vertex_count))
+            .unwrap()
+    }
+
+    fn assert_valid(colors: &HashMap<usize, usize>, ids: &[usize], edges: &[(usize, usize)]) {
+        assert_eq!(colors.len(), ids.len());
+        for (src, dst) in edges {
+            assert_ne!(colors[&ids[*src]], colors[&ids[*dst]]);
+        }
+    }
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let coloring = VertexColoring::init(&graph).execute_within(&graph, Budget::init());
+
+        assert!(coloring.solution().is_empty());
+        assert_eq!(coloring.value(), 0);
+        assert!(coloring.is_optimal());
+    }
+
+    #[test]
+    fn odd_cycle() {
+        // Given: Cycle with five vertices.
+        let edges = [(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)];
+        let (graph, ids) = graph_of(5, &edges);
+
+        // When:
+        let coloring = VertexColoring::init(&graph).execute_within(&graph, Budget::init());
+
+        // Then:
+        assert_eq!(coloring.value(), 3);
+        assert!(coloring.is_optimal());
+        assert_valid(coloring.solution(), &ids, &edges);
+    }
+
+    #[test]
+    fn directed_graph_with_loop() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c
+        //      ^  |
+        //      '--'
+        //
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, a, 1.into());
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // When:
+        let colors = VertexColoring::init(&graph).execute(&graph);
+
+        // Then:
+        assert_eq!(colors[&a], colors[&c]);
+        assert_ne!(colors[&a], colors[&b]);
+    }
+
+    #[test]
+    fn matches_brute_force_on_random_graphs() {
+        let mut rng = seeded(11);
+        for _ in 0..10 {
+            // Given:
+            let mut edges = vec![];
+            for src in 0..9 {
+                for dst in src + 1..9 {
+                    if rng(10) < 4 {
+                        edges.push((src, dst));
+                    }
+                }
+            }
+            let (graph, ids) = graph_of(9, &edges);
+
+            // When:
+            let coloring = VertexColoring::init(&graph).execute_within(&graph, Budget::init());
+
+            // Then:
+            assert!(coloring.is_optimal());
+            assert_eq!(coloring.value(), brute_force(9, &edges));
+            assert_valid(coloring.solution(), &ids, &edges);
+        }
+    }
+
+    #[test]
+    fn greedy_coloring_without_budget() {
+        // Given: Petersen graph, with an outer cycle, an inner pentagram and spokes between them.
+        let mut edges = vec![];
+        for i in 0..5 {
+            edges.push((i, (i + 1) % 5));
+            edges.push((5 + i, 5 + (i + 2) % 5));
+            edges.push((i, 5 + i));
+        }
+        let (graph, ids) = graph_of(10, &edges);
+
+        // When: Search has no iteration.
+        let coloring = VertexColoring::init(&graph)
+            .execute_within(&graph, Budget::init().with_iteration_limit(0));
+
+        // Then: Greedy coloring is valid, and the bound is the size of the largest clique.
+        assert_valid(coloring.solution(), &ids, &edges);
+        assert!(coloring.value() >= 3);
+        assert_eq!(coloring.bound(), 2);
+    }
+}
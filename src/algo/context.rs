@@ -0,0 +1,183 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::provide::{IdMap, Vertices};
+
+/// Keeps buffers and the id map of a graph between executions of algorithms, so running many algorithms back to back does not allocate them each time.
+///
+/// [`Bfs`](crate::algo::Bfs), [`Dfs`](crate::algo::Dfs) and [`Dijkstra`](crate::algo::Dijkstra) take their buffers from the context when initialized with `init_in`,
+/// and give them back when they are released. Other algorithms still allocate their own buffers. Buffers of any type can be pooled, so custom algorithms can use a context the same way
+/// using [`take_buffer`](crate::algo::AlgoContext::take_buffer) and [`put_buffer`](crate::algo::AlgoContext::put_buffer).
+///
+/// The id map is computed the first time it's needed and reused afterwards.
+/// So a context must only be used with one graph, and [`invalidate`](crate::algo::AlgoContext::invalidate) must be called after vertices of the graph change.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::{AlgoContext, Bfs};
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+///
+/// let mut context = AlgoContext::init();
+/// for start_id in graph.vertices() {
+///     let mut bfs = Bfs::init_in(&graph, vec![start_id], &mut context);
+///     assert_eq!(bfs.execute(&graph).len(), 3);
+///
+///     bfs.release(&mut context);
+/// }
+/// ```
+pub struct AlgoContext {
+    id_map: Option<Arc<IdMap>>,
+
+    // Released buffers of each element type: Maps `TypeId` of `T` to a `Vec<Vec<T>>`.
+    buffers: HashMap<TypeId, Box<dyn Any>>,
+}
+
+impl AlgoContext {
+    /// # Returns
+    /// A context with no cached id map or buffer.
+    pub fn init() -> Self {
+        AlgoContext {
+            id_map: None,
+            buffers: HashMap::new(),
+        }
+    }
+
+    /// # Arguments
+    /// `graph`: Graph that the context is used with.
+    ///
+    /// # Returns
+    /// Continuous id map of the graph, shared with the context so it's not copied. It's only computed on the first call, or the first call after `invalidate`.
+    pub fn id_map<G: Vertices>(&mut self, graph: &G) -> Arc<IdMap> {
+        Arc::clone(
+            self.id_map
+                .get_or_insert_with(|| Arc::new(graph.continuous_id_map())),
+        )
+    }
+
+    /// Drops the cached id map, so it's computed again the next time it's needed. Must be called after vertices of the graph are added or removed.
+    /// Buffers are kept, because they are resized when they are taken.
+    pub fn invalidate(&mut self) {
+        self.id_map = None;
+    }
+
+    /// # Arguments
+    /// * `len`: Length of the buffer.
+    /// * `value`: Value of all elements of the buffer.
+    ///
+    /// # Returns
+    /// A buffer that was previously put in the context, or a new one if there is none. Either way, it has `len` elements equal to `value`.
+    pub fn take_buffer<T: Clone + Any>(&mut self, len: usize, value: T) -> Vec<T> {
+        let mut buffer = self
+            .pool_of::<T>()
+            .and_then(|pool| pool.pop())
+            .unwrap_or_default();
+
+        buffer.clear();
+        buffer.resize(len, value);
+
+        buffer
+    }
+
+    /// Keeps `buffer`, so its memory is reused by a later call to `take_buffer`.
+    ///
+    /// # Arguments
+    /// `buffer`: Buffer that is no longer needed.
+    pub fn put_buffer<T: Any>(&mut self, buffer: Vec<T>) {
+        self.buffers
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(Vec::<Vec<T>>::new()))
+            .downcast_mut::<Vec<Vec<T>>>()
+            .unwrap()
+            .push(buffer);
+    }
+
+    /// # Returns
+    /// Number of buffers with elements of type `T` that are kept for reuse.
+    pub fn buffer_count<T: Any>(&self) -> usize {
+        self.buffers
+            .get(&TypeId::of::<T>())
+            .and_then(|pool| pool.downcast_ref::<Vec<Vec<T>>>())
+            .map_or(0, |pool| pool.len())
+    }
+
+    fn pool_of<T: Any>(&mut self) -> Option<&mut Vec<Vec<T>>> {
+        self.buffers
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|pool| pool.downcast_mut::<Vec<Vec<T>>>())
+    }
+}
+
+impl Default for AlgoContext {
+    fn default() -> Self {
+        AlgoContext::init()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::Graph;
+    use crate::storage::Mat;
+
+    #[test]
+    fn buffers_are_reused() {
+        // Given: Context with a released buffer.
+        let mut context = AlgoContext::init();
+        let mut buffer = context.take_buffer(100, 0_usize);
+        buffer[3] = 5;
+        let pointer = buffer.as_ptr();
+        context.put_buffer(buffer);
+
+        // When: Taking a smaller buffer of the same type.
+        let buffer = context.take_buffer(50, 1_usize);
+
+        // Then: Same memory is used and all elements are reset.
+        assert_eq!(buffer.as_ptr(), pointer);
+        assert_eq!(buffer, vec![1; 50]);
+        assert_eq!(context.buffer_count::<usize>(), 0);
+    }
+
+    #[test]
+    fn buffers_of_different_types() {
+        let mut context = AlgoContext::init();
+        context.put_buffer(vec![true]);
+        context.put_buffer(vec![1_u8, 2]);
+        context.put_buffer(vec![false]);
+
+        assert_eq!(context.buffer_count::<bool>(), 2);
+        assert_eq!(context.buffer_count::<u8>(), 1);
+        assert_eq!(context.buffer_count::<u16>(), 0);
+        assert_eq!(context.take_buffer(2, 0_u16), vec![0, 0]);
+    }
+
+    #[test]
+    fn id_map_is_cached_until_invalidated() {
+        // Given: Graph with two vertices.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        graph.add_vertex();
+        let b = graph.add_vertex();
+        let mut context = AlgoContext::init();
+        let id_map = context.id_map(&graph);
+        assert_eq!(id_map.len(), 2);
+
+        // When: Adding a vertex.
+        let c = graph.add_vertex();
+
+        // Then: Cached id map is used until the context is invalidated.
+        assert!(Arc::ptr_eq(&context.id_map(&graph), &id_map));
+        context.invalidate();
+        assert_eq!(context.id_map(&graph).virt_id_of(c), 2);
+        assert_eq!(context.id_map(&graph).virt_id_of(b), 1);
+    }
+}
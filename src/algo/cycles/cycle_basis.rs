@@ -0,0 +1,435 @@
+use std::any::Any;
+
+use anyhow::Result;
+use magnitude::Magnitude;
+use num_traits::{Unsigned, Zero};
+
+use crate::graph::{Edge, Error, UndirectedEdge};
+use crate::provide::{Edges, Graph, IdMap, Vertices};
+
+// Edges of a cycle in the order they appear in the cycle, in the format of: (src_id, dst_id, edge_id).
+type Cycle = Vec<(usize, usize, usize)>;
+
+// Parent of each vertex in a shortest path tree, in the format of: (parent_virt_id, edge_index).
+type Tree = Vec<Option<(usize, usize)>>;
+
+// Candidate cycle closed by an edge, in the format of: (weight, root, edge_index).
+type Candidate<W> = (Magnitude<W>, usize, usize);
+
+/// Finds a minimum cycle basis of an undirected weighted graph, using Horton's algorithm.
+///
+/// A cycle basis is a set of cycles that any cycle(or union of edge disjoint cycles) of the graph can be built from, by taking the symmetric difference of some of them.
+/// Minimum cycle basis is a cycle basis with minimum total weight. It has |E| - |V| + C cycles, where C is the number of connected components.
+///
+/// Candidates are cycles formed by an edge x --- y and the shortest paths from a root to x and y, for each root and each edge.
+/// Candidates are processed in increasing order of their weights, and each one that is independent of the already selected cycles is added to the basis.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::MinimumCycleBasis;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  --(1)--  b
+/// //      |         / |
+/// //     (1)     (1) (5)
+/// //      |     /     |
+/// //      c  --(1)--  d
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(a, c, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(b, d, 5.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+///
+/// let (cycles, weight) = MinimumCycleBasis::init(&graph).execute(&graph).unwrap();
+///
+/// assert_eq!(cycles.len(), 2);
+/// assert_eq!(weight, 10.into());
+/// ```
+pub struct MinimumCycleBasis {
+    id_map: IdMap,
+}
+
+impl MinimumCycleBasis {
+    pub fn init<W, E, G>(graph: &G) -> Self
+    where
+        E: Edge<W>,
+        G: Edges<W, E> + Vertices + Graph<W, E, UndirectedEdge>,
+    {
+        MinimumCycleBasis {
+            id_map: graph.continuous_id_map(),
+        }
+    }
+
+    // Shortest path tree rooted at `root`, along with distance of each vertex from the root.
+    fn shortest_path_tree<W: Copy + Ord + Zero + Any + Unsigned>(
+        root: usize,
+        adjacents_of: &[Vec<(usize, usize)>],
+        weights: &[W],
+    ) -> (Vec<Magnitude<W>>, Tree) {
+        let vertex_count = adjacents_of.len();
+
+        let mut dist = vec![Magnitude::PosInfinite; vertex_count];
+        let mut parent = vec![None; vertex_count];
+        let mut is_visited = vec![false; vertex_count];
+
+        dist[root] = W::zero().into();
+
+        while let Some(virt_id) = (0..vertex_count)
+            .filter(|virt_id| !is_visited[*virt_id] && dist[*virt_id].is_finite())
+            .min_by(|v1, v2| dist[*v1].cmp(&dist[*v2]))
+        {
+            is_visited[virt_id] = true;
+
+            for (n_virt_id, edge_index) in &adjacents_of[virt_id] {
+                let alt = dist[virt_id] + weights[*edge_index].into();
+                if alt < dist[*n_virt_id] {
+                    dist[*n_virt_id] = alt;
+                    parent[*n_virt_id] = Some((virt_id, *edge_index));
+                }
+            }
+        }
+
+        (dist, parent)
+    }
+
+    // Vertices and edges on the path from `virt_id` up to the root of the `tree`, in the format of: (virt_id, edge_index).
+    fn path_to_root(tree: &Tree, mut virt_id: usize) -> Vec<(usize, Option<usize>)> {
+        let mut path = vec![];
+        while let Some((p_virt_id, edge_index)) = tree[virt_id] {
+            path.push((virt_id, Some(edge_index)));
+            virt_id = p_virt_id;
+        }
+        path.push((virt_id, None));
+
+        path
+    }
+
+    /// # Arguments
+    /// `graph`: Graph to find its minimum cycle basis.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing cycles of the minimum cycle basis and their total weight. Each cycle is a list of edges in the order they appear in the cycle,
+    ///   in the format of: (`src_id`, `dst_id`, `edge_id`).
+    /// * `Err`: [`InvalidWeight`](crate::graph::ErrorKind::InvalidWeight) if weight of an edge is infinite.
+    ///
+    /// # Complexity
+    /// O(|V| * |E| * (log(|V| * |E|) + |E|^2 / 64) + |V|^3)
+    pub fn execute<W, E, G>(self, graph: &G) -> Result<(Vec<Cycle>, Magnitude<W>)>
+    where
+        W: Copy + Ord + Zero + Any + Unsigned,
+        E: Edge<W>,
+        G: Edges<W, E> + Vertices + Graph<W, E, UndirectedEdge>,
+    {
+        let vertex_count = graph.vertex_count();
+
+        // Edges in the format of: (src_virt_id, dst_virt_id, edge_id), and their weights.
+        let mut edges = vec![];
+        let mut weights = vec![];
+        let mut adjacents_of = vec![vec![]; vertex_count];
+        for (src_id, dst_id, edge) in graph.edges() {
+            if !edge.get_weight().is_finite() {
+                Err(Error::new_iw(edge.get_id()))?
+            }
+
+            let src_virt_id = self.id_map.virt_id_of(src_id);
+            let dst_virt_id = self.id_map.virt_id_of(dst_id);
+
+            adjacents_of[src_virt_id].push((dst_virt_id, edges.len()));
+            if src_virt_id != dst_virt_id {
+                adjacents_of[dst_virt_id].push((src_virt_id, edges.len()));
+            }
+
+            edges.push((src_virt_id, dst_virt_id, edge.get_id()));
+            weights.push(edge.get_weight().unwrap());
+        }
+
+        // Dimension of the cycle space: |E| - |V| + C. Each edge that does not connect two trees of the forest adds one dimension.
+        let mut component_of = (0..vertex_count).collect::<Vec<usize>>();
+        fn find(component_of: &mut [usize], virt_id: usize) -> usize {
+            if component_of[virt_id] != virt_id {
+                component_of[virt_id] = find(component_of, component_of[virt_id]);
+            }
+            component_of[virt_id]
+        }
+        let mut dimension = 0;
+        for (src_virt_id, dst_virt_id, _) in &edges {
+            let src_component = find(&mut component_of, *src_virt_id);
+            let dst_component = find(&mut component_of, *dst_virt_id);
+
+            if src_component == dst_component {
+                dimension += 1;
+            } else {
+                component_of[src_component] = dst_component;
+            }
+        }
+
+        let trees = (0..vertex_count)
+            .map(|root| Self::shortest_path_tree(root, &adjacents_of, &weights))
+            .collect::<Vec<(Vec<Magnitude<W>>, Tree)>>();
+
+        // Candidate cycles that are simple: paths from root to end points of the edge only share the root, and the edge is not on them.
+        let mut candidates: Vec<Candidate<W>> = vec![];
+        for (root, (dist, tree)) in trees.iter().enumerate() {
+            for (edge_index, (src_virt_id, dst_virt_id, _)) in edges.iter().enumerate() {
+                if !dist[*src_virt_id].is_finite() {
+                    continue;
+                }
+
+                let src_path = Self::path_to_root(tree, *src_virt_id);
+                let dst_path = Self::path_to_root(tree, *dst_virt_id);
+
+                let shares_vertex = src_path.iter().any(|(virt_id, _)| {
+                    *virt_id != root && dst_path.iter().any(|(other_id, _)| other_id == virt_id)
+                });
+                let contains_edge = src_path
+                    .iter()
+                    .chain(dst_path.iter())
+                    .any(|(_, index)| *index == Some(edge_index));
+
+                if !shares_vertex && !contains_edge {
+                    let weight =
+                        dist[*src_virt_id] + dist[*dst_virt_id] + weights[edge_index].into();
+                    candidates.push((weight, root, edge_index));
+                }
+            }
+        }
+        candidates.sort_by_key(|(weight, _, _)| *weight);
+
+        // Selected cycles as vectors over edges, reduced so that each one has a distinct pivot edge that is not in the other selected vectors before it.
+        let words = edges.len().div_ceil(64);
+        let mut basis: Vec<(usize, Vec<u64>)> = vec![];
+        let mut cycles = vec![];
+        let mut selected_weights = vec![];
+
+        for (weight, root, edge_index) in candidates {
+            if basis.len() == dimension {
+                break;
+            }
+
+            let tree = &trees[root].1;
+            let (src_virt_id, dst_virt_id, edge_id) = edges[edge_index];
+
+            let src_path = Self::path_to_root(tree, src_virt_id);
+            let dst_path = Self::path_to_root(tree, dst_virt_id);
+
+            let mut vector = vec![0u64; words];
+            for index in src_path
+                .iter()
+                .chain(dst_path.iter())
+                .filter_map(|(_, index)| *index)
+                .chain(std::iter::once(edge_index))
+            {
+                vector[index / 64] ^= 1 << (index % 64);
+            }
+
+            for (pivot, row) in &basis {
+                if vector[pivot / 64] & (1 << (pivot % 64)) != 0 {
+                    vector.iter_mut().zip(row).for_each(|(a, b)| *a ^= b);
+                }
+            }
+
+            let pivot = vector
+                .iter()
+                .enumerate()
+                .find(|(_, word)| **word != 0)
+                .map(|(index, word)| index * 64 + word.trailing_zeros() as usize);
+
+            if let Some(pivot) = pivot {
+                basis.push((pivot, vector));
+                selected_weights.push(weight);
+
+                // Root --> src --> dst --> root.
+                let mut cycle = src_path
+                    .windows(2)
+                    .rev()
+                    .map(|pair| (pair[1].0, pair[0].0, edges[pair[0].1.unwrap()].2))
+                    .collect::<Vec<(usize, usize, usize)>>();
+                cycle.push((src_virt_id, dst_virt_id, edge_id));
+                cycle.extend(
+                    dst_path
+                        .windows(2)
+                        .map(|pair| (pair[0].0, pair[1].0, edges[pair[0].1.unwrap()].2)),
+                );
+
+                cycles.push(
+                    cycle
+                        .into_iter()
+                        .map(|(src_virt_id, dst_virt_id, edge_id)| {
+                            (
+                                self.id_map.real_id_of(src_virt_id),
+                                self.id_map.real_id_of(dst_virt_id),
+                                edge_id,
+                            )
+                        })
+                        .collect(),
+                );
+            }
+        }
+
+        let total_weight = selected_weights
+            .into_iter()
+            .fold(W::zero().into(), |sum, weight| sum + weight);
+
+        Ok((cycles, total_weight))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ListGraph, MatGraph};
+    use crate::storage::{List, Mat};
+
+    // Asserts that `cycle` is a closed walk that does not repeat any vertex.
+    fn assert_is_cycle(cycle: &[(usize, usize, usize)]) {
+        for (index, (_, dst_id, _)) in cycle.iter().enumerate() {
+            assert_eq!(*dst_id, cycle[(index + 1) % cycle.len()].0);
+        }
+
+        let mut vertices = cycle
+            .iter()
+            .map(|(src_id, _, _)| *src_id)
+            .collect::<Vec<usize>>();
+        vertices.sort_unstable();
+        vertices.dedup();
+        assert_eq!(vertices.len(), cycle.len());
+    }
+
+    #[test]
+    fn forest() {
+        // Given: Graph
+        //
+        //      a  ---  b       c  ---  d
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+
+        // When:
+        let (cycles, weight) = MinimumCycleBasis::init(&graph).execute(&graph).unwrap();
+
+        // Then:
+        assert!(cycles.is_empty());
+        assert_eq!(weight, 0.into());
+    }
+
+    #[test]
+    fn theta_graph() {
+        // Given: Three paths between a and b with weights 1, 2 and 10.
+        //
+        //      a  --------(1)--------  b
+        //      |  \                  / |
+        //      |  (1)              (1) |
+        //      |     \            /    |
+        //     (4)     '---  c  ---'   (3)
+        //      |                       |
+        //      d  --------(3)--------  e
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(a, c, 1.into());
+        graph.add_edge_unchecked(c, b, 1.into());
+        graph.add_edge_unchecked(a, d, 4.into());
+        graph.add_edge_unchecked(d, e, 3.into());
+        graph.add_edge_unchecked(e, b, 3.into());
+
+        // When:
+        let (cycles, weight) = MinimumCycleBasis::init(&graph).execute(&graph).unwrap();
+
+        // Then: Cycles with weights 3 and 11 are selected, not the one with weight 12.
+        assert_eq!(cycles.len(), 2);
+        assert_eq!(weight, 14.into());
+        for cycle in &cycles {
+            assert_is_cycle(cycle);
+        }
+    }
+
+    #[test]
+    fn complete_graph() {
+        // Given: Complete graph with 4 vertices.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let ids = (0..4).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        for i in 0..4 {
+            for j in i + 1..4 {
+                graph.add_edge_unchecked(ids[i], ids[j], 1.into());
+            }
+        }
+
+        // When:
+        let (cycles, weight) = MinimumCycleBasis::init(&graph).execute(&graph).unwrap();
+
+        // Then: Basis consists of three triangles.
+        assert_eq!(cycles.len(), 3);
+        assert_eq!(weight, 9.into());
+        for cycle in &cycles {
+            assert_eq!(cycle.len(), 3);
+            assert_is_cycle(cycle);
+        }
+    }
+
+    #[test]
+    fn cube_graph() {
+        // Given: Cube graph, where vertices are 3 bit numbers and each edge connects numbers that differ in one bit.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let ids = (0..8).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        for i in 0..8 {
+            for bit in [1, 2, 4] {
+                if i & bit == 0 {
+                    graph.add_edge_unchecked(ids[i], ids[i | bit], 1.into());
+                }
+            }
+        }
+
+        // When:
+        let (cycles, weight) = MinimumCycleBasis::init(&graph).execute(&graph).unwrap();
+
+        // Then: Basis consists of five of the faces.
+        assert_eq!(cycles.len(), 5);
+        assert_eq!(weight, 20.into());
+    }
+
+    #[test]
+    fn loops_and_multi_edges() {
+        // Given: Graph
+        //
+        //      a  ==(1, 2)==  b  --(1)--  c  (loop on c with weight 3)
+        //
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(a, b, 2.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, c, 3.into());
+
+        // When:
+        let (cycles, weight) = MinimumCycleBasis::init(&graph).execute(&graph).unwrap();
+
+        // Then: Basis consists of the two edges between a and b, and the loop.
+        assert_eq!(cycles.len(), 2);
+        assert_eq!(weight, 6.into());
+        for cycle in &cycles {
+            assert_is_cycle(cycle);
+        }
+    }
+}
@@ -0,0 +1,367 @@
+use std::collections::VecDeque;
+
+use crate::graph::{Edge, EdgeDir};
+use crate::provide::{Edges, Graph, IdMap, Neighbors, Vertices};
+
+/// Finds the shortest cycle(in number of edges) of a graph. Length of the shortest cycle is called the girth of the graph.
+///
+/// A breadth first search is started from each vertex. In directed graphs, shortest cycle through the root of the search is closed by an edge back to the root.
+/// In undirected graphs, each edge that is not in the search tree closes a cycle, and the shortest of them over all roots is the shortest cycle of the graph.
+/// Loops are cycles with length one, and in undirected graphs, two edges between the same vertices form a cycle with length two.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::Girth;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b  ---  c
+/// //      |               |
+/// //      e  -----------  d
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// let e = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+/// graph.add_edge_unchecked(d, e, 1.into());
+/// graph.add_edge_unchecked(e, a, 1.into());
+///
+/// assert_eq!(Girth::init(&graph).execute(&graph), Some(5));
+/// assert_eq!(Girth::init(&graph).shortest_cycle(&graph).unwrap().len(), 5);
+/// ```
+pub struct Girth {
+    id_map: IdMap,
+}
+
+impl Girth {
+    pub fn init<W, E, Ty, G>(graph: &G) -> Self
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Neighbors + Vertices + Graph<W, E, Ty>,
+    {
+        Girth {
+            id_map: graph.continuous_id_map(),
+        }
+    }
+
+    /// # Arguments
+    /// `graph`: Graph to compute its girth.
+    ///
+    /// # Returns
+    /// * `Some`: Containing number of edges of the shortest cycle.
+    /// * `None`: If graph is acyclic.
+    ///
+    /// # Complexity
+    /// O(|V| * (|V| + |E|))
+    pub fn execute<W, E, Ty, G>(self, graph: &G) -> Option<usize>
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Neighbors + Vertices + Graph<W, E, Ty>,
+    {
+        self.shortest_cycle(graph).map(|cycle| cycle.len())
+    }
+
+    /// # Arguments
+    /// `graph`: Graph to find its shortest cycle.
+    ///
+    /// # Returns
+    /// * `Some`: Containing edges of the shortest cycle in the order they appear in the cycle, in the format of: (`src_id`, `dst_id`, `edge_id`).
+    /// * `None`: If graph is acyclic.
+    ///
+    /// # Complexity
+    /// O(|V| * (|V| + |E|))
+    pub fn shortest_cycle<W, E, Ty, G>(self, graph: &G) -> Option<Vec<(usize, usize, usize)>>
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Neighbors + Vertices + Graph<W, E, Ty>,
+    {
+        let vertex_count = graph.vertex_count();
+
+        // Length of the shortest cycle so far, with its root and the edge that closes it.
+        let mut shortest: Option<(usize, usize, usize, usize, usize)> = None;
+        let mut shortest_parents = vec![];
+
+        for root in 0..vertex_count {
+            let mut dist = vec![usize::MAX; vertex_count];
+            let mut parent: Vec<Option<(usize, usize)>> = vec![None; vertex_count];
+            let mut queue = VecDeque::new();
+
+            dist[root] = 0;
+            queue.push_back(root);
+
+            let mut found = false;
+            while let Some(virt_id) = queue.pop_front() {
+                // Cycles found from now on are not shorter than the shortest one.
+                let min_length = if Ty::is_directed() {
+                    dist[virt_id] + 1
+                } else {
+                    2 * dist[virt_id] + 1
+                };
+                if shortest.is_some_and(|(length, ..)| min_length >= length) {
+                    break;
+                }
+
+                for (n_id, edge) in graph.edges_from_unchecked(self.id_map.real_id_of(virt_id)) {
+                    let n_virt_id = self.id_map.virt_id_of(n_id);
+                    let edge_id = edge.get_id();
+
+                    let length = if Ty::is_directed() {
+                        if n_virt_id != root {
+                            None
+                        } else {
+                            Some(dist[virt_id] + 1)
+                        }
+                    } else if parent[virt_id].is_some_and(|(_, p_edge_id)| p_edge_id == edge_id)
+                        || parent[n_virt_id].is_some_and(|(_, p_edge_id)| p_edge_id == edge_id)
+                        || dist[n_virt_id] == usize::MAX
+                    {
+                        None
+                    } else {
+                        Some(dist[virt_id] + dist[n_virt_id] + 1)
+                    };
+
+                    if let Some(length) = length {
+                        if shortest.is_none_or(|(shortest_length, ..)| length < shortest_length) {
+                            shortest = Some((length, root, virt_id, n_virt_id, edge_id));
+                            found = true;
+                        }
+                    }
+
+                    if dist[n_virt_id] == usize::MAX {
+                        dist[n_virt_id] = dist[virt_id] + 1;
+                        parent[n_virt_id] = Some((virt_id, edge_id));
+                        queue.push_back(n_virt_id);
+                    }
+                }
+            }
+
+            if found {
+                shortest_parents = parent;
+            }
+        }
+
+        let (_, root, virt_id, n_virt_id, edge_id) = shortest?;
+
+        // Edges on the path from root to `virt_id`, in the format of: (src_virt_id, dst_virt_id, edge_id).
+        let path_to = |mut virt_id: usize| {
+            let mut path = vec![];
+            while virt_id != root {
+                let (p_virt_id, p_edge_id) = shortest_parents[virt_id].unwrap();
+                path.push((p_virt_id, virt_id, p_edge_id));
+                virt_id = p_virt_id;
+            }
+            path.reverse();
+
+            path
+        };
+
+        let mut cycle = path_to(virt_id);
+        cycle.push((virt_id, n_virt_id, edge_id));
+        if !Ty::is_directed() {
+            cycle.extend(
+                path_to(n_virt_id)
+                    .into_iter()
+                    .rev()
+                    .map(|(src_virt_id, dst_virt_id, edge_id)| (dst_virt_id, src_virt_id, edge_id)),
+            );
+        }
+
+        Some(
+            cycle
+                .into_iter()
+                .map(|(src_virt_id, dst_virt_id, edge_id)| {
+                    (
+                        self.id_map.real_id_of(src_virt_id),
+                        self.id_map.real_id_of(dst_virt_id),
+                        edge_id,
+                    )
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ListGraph;
+    use crate::graph::MatGraph;
+    use crate::storage::{DiList, DiMat, List, Mat};
+
+    // Asserts that `cycle` is a closed walk that does not repeat any vertex.
+    fn assert_is_cycle(cycle: &[(usize, usize, usize)]) {
+        for (index, (_, dst_id, _)) in cycle.iter().enumerate() {
+            assert_eq!(*dst_id, cycle[(index + 1) % cycle.len()].0);
+        }
+
+        let mut vertices = cycle
+            .iter()
+            .map(|(src_id, _, _)| *src_id)
+            .collect::<Vec<usize>>();
+        vertices.sort_unstable();
+        vertices.dedup();
+        assert_eq!(vertices.len(), cycle.len());
+    }
+
+    #[test]
+    fn acyclic_graphs() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c
+        //              |
+        //              d
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(b, d, 1.into());
+
+        // Then:
+        assert_eq!(Girth::init(&graph).execute(&graph), None);
+
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c
+        //      |               ^
+        //      '---------------'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(a, c, 1.into());
+
+        // Then:
+        assert_eq!(Girth::init(&graph).execute(&graph), None);
+    }
+
+    #[test]
+    fn undirected_graph() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c  ---  d
+        //      |       |               |
+        //      f  ---  e  -----------  g
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        let g = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+        graph.add_edge_unchecked(d, g, 1.into());
+        graph.add_edge_unchecked(g, e, 1.into());
+        graph.add_edge_unchecked(e, f, 1.into());
+        graph.add_edge_unchecked(f, a, 1.into());
+        graph.add_edge_unchecked(b, e, 1.into());
+
+        // When:
+        let cycle = Girth::init(&graph).shortest_cycle(&graph).unwrap();
+
+        // Then: Shortest cycle is a --- b --- e --- f.
+        assert_eq!(cycle.len(), 4);
+        assert_is_cycle(&cycle);
+        let mut vertices = cycle
+            .iter()
+            .map(|(src_id, _, _)| *src_id)
+            .collect::<Vec<usize>>();
+        vertices.sort_unstable();
+        assert_eq!(vertices, vec![a, b, e, f]);
+    }
+
+    #[test]
+    fn directed_graph() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c  -->  d
+        //      ^       ^       |       |
+        //      |       '-------|-------'
+        //      '---------------'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+        graph.add_edge_unchecked(d, b, 1.into());
+        graph.add_edge_unchecked(c, a, 1.into());
+
+        // When:
+        let cycle = Girth::init(&graph).shortest_cycle(&graph).unwrap();
+
+        // Then: Both a --> b --> c --> a and b --> c --> d --> b are shortest.
+        assert_eq!(cycle.len(), 3);
+        assert_is_cycle(&cycle);
+        assert!(cycle
+            .iter()
+            .any(|(src_id, dst_id, _)| *src_id == b && *dst_id == c));
+    }
+
+    #[test]
+    fn loops_and_multi_edges() {
+        // Given: Graph
+        //
+        //      a  ===  b  ---  c
+        //
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // Then: Two edges between a and b form a cycle.
+        let cycle = Girth::init(&graph).shortest_cycle(&graph).unwrap();
+        assert_eq!(cycle.len(), 2);
+        assert_is_cycle(&cycle);
+
+        // Given: Graph with a loop on c.
+        graph.add_edge_unchecked(c, c, 1.into());
+
+        // Then:
+        assert_eq!(Girth::init(&graph).shortest_cycle(&graph).unwrap()[0].0, c);
+
+        // Given: Directed graph
+        //
+        //      a  -->  b  --> c
+        //      ^       |
+        //      '-------'
+        //
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, a, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // Then:
+        assert_eq!(Girth::init(&graph).execute(&graph), Some(2));
+    }
+}
@@ -0,0 +1,5 @@
+mod cycle_basis;
+mod girth;
+
+pub use cycle_basis::MinimumCycleBasis;
+pub use girth::Girth;
@@ -0,0 +1,424 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+
+use crate::algo::TopologicalSort;
+use crate::graph::{DirectedEdge, Edge, Error};
+use crate::provide;
+
+/// Maintains a topological order of a directed acyclic graph under edge insertions, using the algorithm of Pearce and Kelly.
+///
+/// Instead of sorting the whole graph after each insertion, only vertices between the source and destination of the
+/// new edge in the current order get visited and reordered. Insertions that would create a cycle are rejected.
+///
+/// ## Note
+/// * |V|: Means number of vertices that are tracked.
+/// * |δ|: Means number of vertices whose position in the order lies between the destination and source of the inserted edge.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::DynamicTopologicalSort;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  -->  b       c
+/// //
+/// let mut graph = MatGraph::init(DiMat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+///
+/// let mut dts = DynamicTopologicalSort::init(&graph).unwrap();
+///
+/// // c must come before a.
+/// assert!(dts.add_edge(c, a).is_ok());
+/// assert!(dts.position_of(c) < dts.position_of(a));
+///
+/// // b --> c creates a cycle.
+/// assert!(dts.add_edge(b, c).is_err());
+/// ```
+pub struct DynamicTopologicalSort {
+    order: Vec<usize>,
+    position_of: HashMap<usize, usize>,
+
+    out_neighbors: HashMap<usize, Vec<usize>>,
+    in_neighbors: HashMap<usize, Vec<usize>>,
+}
+
+impl DynamicTopologicalSort {
+    /// Initializes the structure with the vertices and edges that are currently in the `graph`.
+    ///
+    /// # Arguments
+    /// `graph`: Graph to maintain topological order of.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing the initialized structure.
+    /// * `Err`: [`Cycle`](crate::graph::ErrorKind::Cycle) if graph contains a cycle.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|)
+    pub fn init<W, E: Edge<W>, G>(graph: &G) -> Result<Self>
+    where
+        G: provide::Graph<W, E, DirectedEdge> + provide::Vertices + provide::Neighbors,
+    {
+        let order = TopologicalSort::init().execute(graph);
+
+        let position_of = order
+            .iter()
+            .enumerate()
+            .map(|(position, vertex_id)| (*vertex_id, position))
+            .collect::<HashMap<usize, usize>>();
+
+        let mut out_neighbors = HashMap::new();
+        let mut in_neighbors = HashMap::new();
+        for vertex_id in &order {
+            out_neighbors.insert(*vertex_id, vec![]);
+            in_neighbors.insert(*vertex_id, vec![]);
+        }
+
+        for src_id in &order {
+            for dst_id in graph.neighbors_unchecked(*src_id) {
+                if position_of[src_id] >= position_of[&dst_id] {
+                    Err(Error::new_c(*src_id, dst_id))?
+                }
+
+                out_neighbors.get_mut(src_id).unwrap().push(dst_id);
+                in_neighbors.get_mut(&dst_id).unwrap().push(*src_id);
+            }
+        }
+
+        Ok(DynamicTopologicalSort {
+            order,
+            position_of,
+            out_neighbors,
+            in_neighbors,
+        })
+    }
+
+    /// Adds vertex with id: `vertex_id` to the end of the order.
+    ///
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex that is added to the graph.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn add_vertex(&mut self, vertex_id: usize) {
+        if !self.position_of.contains_key(&vertex_id) {
+            self.position_of.insert(vertex_id, self.order.len());
+            self.order.push(vertex_id);
+
+            self.out_neighbors.insert(vertex_id, vec![]);
+            self.in_neighbors.insert(vertex_id, vec![]);
+        }
+    }
+
+    /// Removes vertex with id: `vertex_id` and all of its edges.
+    ///
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex that is removed from the graph.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|)
+    pub fn remove_vertex(&mut self, vertex_id: usize) {
+        if let Some(position) = self.position_of.remove(&vertex_id) {
+            self.order.remove(position);
+            for (position, vertex_id) in self.order.iter().enumerate().skip(position) {
+                self.position_of.insert(*vertex_id, position);
+            }
+
+            for dst_id in self.out_neighbors.remove(&vertex_id).unwrap() {
+                if let Some(in_neighbors) = self.in_neighbors.get_mut(&dst_id) {
+                    in_neighbors.retain(|src_id| *src_id != vertex_id);
+                }
+            }
+            for src_id in self.in_neighbors.remove(&vertex_id).unwrap() {
+                if let Some(out_neighbors) = self.out_neighbors.get_mut(&src_id) {
+                    out_neighbors.retain(|dst_id| *dst_id != vertex_id);
+                }
+            }
+        }
+    }
+
+    /// Adds an edge from `src_id` to `dst_id` and updates the order if needed.
+    ///
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: If edge is added and the order is updated.
+    /// * `Err`: [`Cycle`](crate::graph::ErrorKind::Cycle) if adding the edge creates a cycle. In this case nothing changes.
+    ///
+    /// # Panics
+    /// If either of the vertices is not tracked.
+    ///
+    /// # Complexity
+    /// O(|δ| * log(|δ|) + number of edges of vertices in δ)
+    pub fn add_edge(&mut self, src_id: usize, dst_id: usize) -> Result<()> {
+        let lower_bound = self.position_of[&dst_id];
+        let upper_bound = self.position_of[&src_id];
+
+        if lower_bound <= upper_bound {
+            // Source already comes before destination, unless they are the same vertex.
+            if src_id == dst_id {
+                Err(Error::new_c(src_id, dst_id))?
+            }
+
+            let mut forward = vec![];
+            if !self.visit_forward(dst_id, src_id, upper_bound, &mut forward) {
+                Err(Error::new_c(src_id, dst_id))?
+            }
+
+            let mut backward = vec![];
+            self.visit_backward(src_id, lower_bound, &mut backward);
+
+            self.reorder(forward, backward);
+        }
+
+        self.out_neighbors.get_mut(&src_id).unwrap().push(dst_id);
+        self.in_neighbors.get_mut(&dst_id).unwrap().push(src_id);
+
+        Ok(())
+    }
+
+    /// Removes an edge from `src_id` to `dst_id`. Removing an edge never invalidates the order.
+    ///
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Complexity
+    /// O(out degree of source + in degree of destination)
+    pub fn remove_edge(&mut self, src_id: usize, dst_id: usize) {
+        if let Some(out_neighbors) = self.out_neighbors.get_mut(&src_id) {
+            if let Some(index) = out_neighbors.iter().position(|id| *id == dst_id) {
+                out_neighbors.swap_remove(index);
+            }
+        }
+
+        if let Some(in_neighbors) = self.in_neighbors.get_mut(&dst_id) {
+            if let Some(index) = in_neighbors.iter().position(|id| *id == src_id) {
+                in_neighbors.swap_remove(index);
+            }
+        }
+    }
+
+    // Collects vertices reachable from `start_id` that are positioned before or at `upper_bound`.
+    // Returns false if `target_id` is reachable, which means the new edge closes a cycle.
+    fn visit_forward(
+        &self,
+        start_id: usize,
+        target_id: usize,
+        upper_bound: usize,
+        visited: &mut Vec<usize>,
+    ) -> bool {
+        let mut seen = HashSet::new();
+        let mut stack = vec![start_id];
+        seen.insert(start_id);
+
+        while let Some(vertex_id) = stack.pop() {
+            visited.push(vertex_id);
+
+            for dst_id in &self.out_neighbors[&vertex_id] {
+                if *dst_id == target_id {
+                    return false;
+                }
+
+                if self.position_of[dst_id] < upper_bound && seen.insert(*dst_id) {
+                    stack.push(*dst_id);
+                }
+            }
+        }
+
+        true
+    }
+
+    // Collects vertices that can reach `start_id` and are positioned after `lower_bound`.
+    fn visit_backward(&self, start_id: usize, lower_bound: usize, visited: &mut Vec<usize>) {
+        let mut seen = HashSet::new();
+        let mut stack = vec![start_id];
+        seen.insert(start_id);
+
+        while let Some(vertex_id) = stack.pop() {
+            visited.push(vertex_id);
+
+            for src_id in &self.in_neighbors[&vertex_id] {
+                if self.position_of[src_id] > lower_bound && seen.insert(*src_id) {
+                    stack.push(*src_id);
+                }
+            }
+        }
+    }
+
+    // Moves vertices in `backward` before vertices in `forward`, using the positions that they already occupy.
+    fn reorder(&mut self, mut forward: Vec<usize>, mut backward: Vec<usize>) {
+        forward.sort_unstable_by_key(|vertex_id| self.position_of[vertex_id]);
+        backward.sort_unstable_by_key(|vertex_id| self.position_of[vertex_id]);
+
+        let mut positions = forward
+            .iter()
+            .chain(backward.iter())
+            .map(|vertex_id| self.position_of[vertex_id])
+            .collect::<Vec<usize>>();
+        positions.sort_unstable();
+
+        for (vertex_id, position) in backward.into_iter().chain(forward).zip(positions) {
+            self.order[position] = vertex_id;
+            self.position_of.insert(vertex_id, position);
+        }
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Position of the vertex in the topological order.
+    ///
+    /// # Panics
+    /// If vertex is not tracked.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn position_of(&self, vertex_id: usize) -> usize {
+        self.position_of[&vertex_id]
+    }
+
+    /// # Returns
+    /// Id of vertices in topological order.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn order(&self) -> &Vec<usize> {
+        &self.order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::*;
+    use crate::storage::DiMat;
+
+    fn assert_valid_order(dts: &DynamicTopologicalSort, edges: &[(usize, usize)]) {
+        for (src_id, dst_id) in edges {
+            assert!(dts.position_of(*src_id) < dts.position_of(*dst_id));
+        }
+    }
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(DiMat::<usize>::init());
+
+        let dts = DynamicTopologicalSort::init(&graph).unwrap();
+
+        assert!(dts.order().is_empty());
+    }
+
+    #[test]
+    fn graph_with_cycle() {
+        //      a  -->  b  -->  c
+        //      ^               |
+        //      '---------------'
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, a, 1.into());
+
+        assert!(DynamicTopologicalSort::init(&graph).is_err());
+    }
+
+    #[test]
+    fn adding_edges_in_reverse_order() {
+        //      a   b   c   d   e
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let vertices = (0..5).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        let mut dts = DynamicTopologicalSort::init(&graph).unwrap();
+
+        // Chain e --> d --> c --> b --> a, which is against the initial order.
+        let mut edges = vec![];
+        for window in vertices.windows(2).rev() {
+            let (src_id, dst_id) = (window[1], window[0]);
+            assert!(dts.add_edge(src_id, dst_id).is_ok());
+            edges.push((src_id, dst_id));
+
+            assert_valid_order(&dts, &edges);
+        }
+
+        let mut expected = vertices.clone();
+        expected.reverse();
+        assert_eq!(*dts.order(), expected);
+    }
+
+    #[test]
+    fn rejecting_cycles() {
+        //      a  -->  b  -->  c  -->  d
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let mut dts = DynamicTopologicalSort::init(&graph).unwrap();
+        let edges = vec![(c, d), (b, c), (a, b)];
+        for (src_id, dst_id) in &edges {
+            dts.add_edge(*src_id, *dst_id).unwrap();
+        }
+        let order = dts.order().clone();
+
+        assert!(dts.add_edge(d, a).is_err());
+        assert!(dts.add_edge(c, b).is_err());
+        assert!(dts.add_edge(a, a).is_err());
+
+        // Order must not change after rejected insertions.
+        assert_eq!(*dts.order(), order);
+        assert_valid_order(&dts, &edges);
+
+        // After removing b --> c, c --> b is allowed.
+        dts.remove_edge(b, c);
+        assert!(dts.add_edge(c, b).is_ok());
+        assert_valid_order(&dts, &[(c, d), (a, b), (c, b)]);
+    }
+
+    #[test]
+    fn trivial_graph() {
+        //      a  -->  b       d  -->  e
+        //              |
+        //              v
+        //              c
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(d, e, 1.into());
+        let mut dts = DynamicTopologicalSort::init(&graph).unwrap();
+        let mut edges = vec![(a, b), (b, c), (d, e)];
+        assert_valid_order(&dts, &edges);
+
+        // Connecting the two chains in both possible ways.
+        dts.add_edge(c, d).unwrap();
+        edges.push((c, d));
+        assert_valid_order(&dts, &edges);
+
+        assert!(dts.add_edge(e, a).is_err());
+
+        // Removing a vertex and adding a new one.
+        dts.remove_vertex(c);
+        assert_eq!(dts.order().len(), 4);
+        let f = e + 1;
+        dts.add_vertex(f);
+        dts.add_edge(f, a).unwrap();
+        assert!(dts.add_edge(e, f).is_ok());
+        assert_valid_order(&dts, &[(a, b), (d, e), (f, a), (e, f)]);
+    }
+}
@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use num_traits::Zero;
+
+use crate::graph::{Edge, EdgeDir, Error, TemporalGraph};
+use crate::provide::{Edges, Graph, Neighbors, Vertices};
+use crate::util::IndexedHeap;
+
+/// Earliest arrival times and time-respecting paths from a source vertex, computed by [`EarliestArrival`](crate::algo::EarliestArrival).
+#[derive(Debug)]
+pub struct TemporalPaths<T> {
+    src_id: usize,
+    arrival_of: HashMap<usize, T>,
+    prev_of: HashMap<usize, (usize, usize)>,
+}
+
+impl<T: Copy> TemporalPaths<T> {
+    pub(crate) fn init(
+        src_id: usize,
+        arrival_of: HashMap<usize, T>,
+        prev_of: HashMap<usize, (usize, usize)>,
+    ) -> Self {
+        TemporalPaths {
+            src_id,
+            arrival_of,
+            prev_of,
+        }
+    }
+
+    /// # Returns
+    /// Id of the source vertex.
+    pub fn src_id(&self) -> usize {
+        self.src_id
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing the earliest time that vertex can be reached at.
+    /// * `None`: If vertex can not be reached.
+    pub fn arrival_of(&self, vertex_id: usize) -> Option<T> {
+        self.arrival_of.get(&vertex_id).copied()
+    }
+
+    /// # Returns
+    /// Earliest arrival time of each reachable vertex, mapped from its id.
+    pub fn arrivals(&self) -> &HashMap<usize, T> {
+        &self.arrival_of
+    }
+
+    /// # Arguments
+    /// `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing the edges of a path that reaches the destination at its earliest arrival time, in the format of: (`src_id`, `dst_id`, `edge_id`).
+    /// * `None`: If destination can not be reached.
+    pub fn path_to(&self, dst_id: usize) -> Option<Vec<(usize, usize, usize)>> {
+        if !self.arrival_of.contains_key(&dst_id) {
+            return None;
+        }
+
+        let mut path = vec![];
+        let mut vertex_id = dst_id;
+        while let Some((prev_id, edge_id)) = self.prev_of.get(&vertex_id) {
+            path.push((*prev_id, vertex_id, *edge_id));
+            vertex_id = *prev_id;
+        }
+        path.reverse();
+
+        Some(path)
+    }
+}
+
+/// Finds the earliest time that each vertex of a [`TemporalGraph`](crate::graph::TemporalGraph) can be reached at, using time-respecting paths.
+///
+/// A time-respecting path starts from the source at the start time, and waits at each vertex as long as needed.
+/// It can traverse an edge at any moment in the interval of the edge, and traversing takes `traversal_time`.
+/// So an edge with interval [s, e] can be taken from a vertex reached at time t, if t <= e. Its destination is reached at max(t, s) + `traversal_time`.
+/// Edges without an interval can be traversed at any time.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::EarliestArrival;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::{Interval, MatGraph, TemporalGraph};
+///
+/// // Given: Graph
+/// //
+/// //      a  --[3, 4]-->  b  --[1, 2]-->  c
+/// //      |                               ^
+/// //      '------------[6, 9]-------------'
+/// //
+/// let mut graph = TemporalGraph::init(MatGraph::init(DiMat::<usize>::init()));
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_temporal_edge(a, b, 1.into(), Interval::init(3, 4)).unwrap();
+/// graph.add_temporal_edge(b, c, 1.into(), Interval::init(1, 2)).unwrap();
+/// graph.add_temporal_edge(a, c, 1.into(), Interval::init(6, 9)).unwrap();
+///
+/// let paths = EarliestArrival::init(&graph).execute(&graph, a, 0).unwrap();
+///
+/// // b is reached after b --> c is gone, so c must be reached directly.
+/// assert_eq!(paths.arrival_of(b), Some(3));
+/// assert_eq!(paths.arrival_of(c), Some(6));
+/// assert_eq!(paths.path_to(c).unwrap().len(), 1);
+/// ```
+pub struct EarliestArrival<T> {
+    traversal_time: T,
+}
+
+impl<T: Copy + Ord + Zero> EarliestArrival<T> {
+    /// # Arguments
+    /// `graph`: Temporal graph to find paths in.
+    ///
+    /// # Returns
+    /// Initialized earliest arrival search, in which traversing an edge takes no time.
+    pub fn init<W, E, Dir, G>(_: &TemporalGraph<T, W, E, Dir, G>) -> Self
+    where
+        E: Edge<W>,
+        Dir: EdgeDir,
+        G: Graph<W, E, Dir> + Vertices + Neighbors + Edges<W, E>,
+    {
+        EarliestArrival {
+            traversal_time: T::zero(),
+        }
+    }
+
+    /// # Arguments
+    /// `traversal_time`: Time it takes to traverse each edge.
+    ///
+    /// # Returns
+    /// Earliest arrival search with the specified traversal time.
+    pub fn with_traversal_time(mut self, traversal_time: T) -> Self {
+        self.traversal_time = traversal_time;
+
+        self
+    }
+
+    /// # Arguments
+    /// * `graph`: Temporal graph to find paths in.
+    /// * `src_id`: Id of the source vertex.
+    /// * `start_time`: Moment the search starts at the source vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing the earliest arrival time of, and a path to, each vertex that can be reached.
+    /// * `Err`: [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if source vertex does not exist.
+    ///
+    /// # Complexity
+    /// O((|V| + |E|).log(|V|))
+    pub fn execute<W, E, Dir, G>(
+        &self,
+        graph: &TemporalGraph<T, W, E, Dir, G>,
+        src_id: usize,
+        start_time: T,
+    ) -> Result<TemporalPaths<T>>
+    where
+        E: Edge<W>,
+        Dir: EdgeDir,
+        G: Graph<W, E, Dir> + Vertices + Neighbors + Edges<W, E>,
+    {
+        if !graph.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        }
+
+        let mut arrival_of = HashMap::new();
+        let mut prev_of = HashMap::new();
+        arrival_of.insert(src_id, start_time);
+
+        let id_map = graph.continuous_id_map();
+
+        let mut heap = IndexedHeap::with_capacity(graph.vertex_count());
+        heap.push(id_map.virt_id_of(src_id), start_time);
+
+        while let Some((virt_id, arrival)) = heap.pop() {
+            let vertex_id = id_map.real_id_of(virt_id);
+
+            for (dst_id, edge) in graph.edges_from_unchecked(vertex_id) {
+                let departure = match graph.interval_of(edge.get_id()) {
+                    Some(interval) if interval.end() < arrival => continue,
+                    Some(interval) => arrival.max(interval.start()),
+                    None => arrival,
+                };
+                let dst_arrival = departure + self.traversal_time;
+
+                let is_earlier = match arrival_of.get(&dst_id) {
+                    Some(current) => dst_arrival < *current,
+                    None => true,
+                };
+
+                if is_earlier {
+                    arrival_of.insert(dst_id, dst_arrival);
+                    prev_of.insert(dst_id, (vertex_id, edge.get_id()));
+                    heap.push_or_decrease(id_map.virt_id_of(dst_id), dst_arrival);
+                }
+            }
+        }
+
+        Ok(TemporalPaths::init(src_id, arrival_of, prev_of))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ErrorKind, Interval, MatGraph};
+    use crate::storage::{DiMat, Mat};
+
+    #[test]
+    fn unknown_source() {
+        let graph = TemporalGraph::init(MatGraph::init(Mat::<usize>::init()));
+
+        let result = EarliestArrival::init(&graph).execute(&graph, 0, 0);
+
+        let error = result.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            ErrorKind::VertexNotFound
+        ));
+    }
+
+    #[test]
+    fn waiting_at_vertices() {
+        // Given: Graph
+        //
+        //      a  --[2, 2]-->  b  --[5, 7]-->  c  --[4, 4]-->  d
+        //
+        let mut graph = TemporalGraph::init(MatGraph::init(DiMat::<usize>::init()));
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let ab = graph
+            .add_temporal_edge(a, b, 1.into(), Interval::instant(2))
+            .unwrap();
+        let bc = graph
+            .add_temporal_edge(b, c, 1.into(), Interval::init(5, 7))
+            .unwrap();
+        graph
+            .add_temporal_edge(c, d, 1.into(), Interval::instant(4))
+            .unwrap();
+
+        // When:
+        let paths = EarliestArrival::init(&graph).execute(&graph, a, 0).unwrap();
+        let late_paths = EarliestArrival::init(&graph).execute(&graph, a, 3).unwrap();
+
+        // Then: Path waits at b until b --> c becomes valid, and c --> d is gone by then.
+        assert_eq!(paths.arrival_of(a), Some(0));
+        assert_eq!(paths.arrival_of(b), Some(2));
+        assert_eq!(paths.arrival_of(c), Some(5));
+        assert_eq!(paths.arrival_of(d), None);
+        assert_eq!(paths.path_to(c), Some(vec![(a, b, ab), (b, c, bc)]));
+        assert_eq!(paths.path_to(d), None);
+        assert_eq!(late_paths.arrivals().len(), 1);
+    }
+
+    #[test]
+    fn traversal_time() {
+        // Given: Graph
+        //
+        //      a  --[0, 9]--  b  --[0, 3]--  c
+        //       \                           /
+        //        '---------- always -------'
+        //
+        let mut graph = TemporalGraph::init(MatGraph::init(Mat::<usize>::init()));
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph
+            .add_temporal_edge(a, b, 1.into(), Interval::init(0, 9))
+            .unwrap();
+        graph
+            .add_temporal_edge(b, c, 1.into(), Interval::init(0, 3))
+            .unwrap();
+        let ac = graph.add_edge_unchecked(a, c, 1.into());
+
+        // When:
+        let paths = EarliestArrival::init(&graph)
+            .with_traversal_time(2)
+            .execute(&graph, b, 1)
+            .unwrap();
+
+        // Then: Undirected edges can be traversed in both directions.
+        assert_eq!(paths.arrival_of(a), Some(3));
+        assert_eq!(paths.arrival_of(c), Some(3));
+
+        // When: c --- b is gone by the time a is reached.
+        let paths = EarliestArrival::init(&graph)
+            .with_traversal_time(2)
+            .execute(&graph, c, 2)
+            .unwrap();
+
+        // Then:
+        assert_eq!(paths.arrival_of(b), Some(4));
+        assert_eq!(paths.arrival_of(a), Some(4));
+        assert_eq!(paths.path_to(a), Some(vec![(c, a, ac)]));
+    }
+}
@@ -0,0 +1,380 @@
+use std::collections::HashSet;
+
+use crate::algo::metrics::adjacency;
+use crate::provide::{IdMap, Neighbors, Vertices};
+
+/// Heuristic used by [`EliminationOrdering`](crate::algo::EliminationOrdering) to order the vertices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EliminationHeuristic {
+    /// Numbers vertices one by one, each time picking the vertex with the most numbered neighbors, and eliminates them in reverse order.
+    /// Produces an ordering without fill-in edges if graph is chordal.
+    MaximumCardinalitySearch,
+
+    /// Each time eliminates the vertex with the fewest neighbors.
+    MinDegree,
+
+    /// Each time eliminates the vertex whose elimination adds the fewest fill-in edges.
+    MinFill,
+}
+
+/// Result of an [`EliminationOrdering`](crate::algo::EliminationOrdering).
+#[derive(Debug)]
+pub struct EliminationOrder {
+    order: Vec<usize>,
+    fill_edges: Vec<(usize, usize)>,
+    width: usize,
+}
+
+impl EliminationOrder {
+    /// # Returns
+    /// Id of the vertices in the order they are eliminated.
+    pub fn order(&self) -> &Vec<usize> {
+        &self.order
+    }
+
+    /// # Returns
+    /// Edges that are added by eliminating the vertices in order, in the format of: (`src_id`, `dst_id`). Adding them to the graph makes it chordal.
+    pub fn fill_edges(&self) -> &Vec<(usize, usize)> {
+        &self.fill_edges
+    }
+
+    /// # Returns
+    /// Largest number of neighbors that a vertex has when it's eliminated, which is an upper bound for treewidth of the graph.
+    pub fn treewidth_bound(&self) -> usize {
+        self.width
+    }
+}
+
+/// Finds an order to eliminate vertices of a graph, like the order needed to build a junction tree.
+///
+/// Eliminating a vertex connects all of its remaining neighbors to each other and removes it from the graph. Good orderings add few edges,
+/// and keep the number of neighbors of the eliminated vertices small. Finding the best ordering is NP-hard, so one of the [`EliminationHeuristic`](crate::algo::EliminationHeuristic)s is used.
+///
+/// Direction of the edges and loops are ignored.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::{EliminationHeuristic, EliminationOrdering};
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b
+/// //      |       |
+/// //      d  ---  c
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+/// graph.add_edge_unchecked(d, a, 1.into());
+///
+/// let order = EliminationOrdering::init(&graph).execute(&graph, EliminationHeuristic::MinFill);
+///
+/// // Cycle of length 4 needs one chord to become chordal, and its treewidth is 2.
+/// assert_eq!(order.order().len(), 4);
+/// assert_eq!(order.fill_edges().len(), 1);
+/// assert_eq!(order.treewidth_bound(), 2);
+/// ```
+pub struct EliminationOrdering {
+    id_map: IdMap,
+
+    // Distinct vertices adjacent to each vertex, using virtual ids.
+    adjacent_to: Vec<HashSet<usize>>,
+}
+
+impl EliminationOrdering {
+    pub fn init<G: Neighbors + Vertices>(graph: &G) -> Self {
+        let (id_map, adjacent_to) = adjacency(graph);
+
+        EliminationOrdering {
+            id_map,
+            adjacent_to,
+        }
+    }
+
+    // Reverse of the order in which maximum cardinality search numbers the vertices.
+    fn maximum_cardinality_search(&self) -> Vec<usize> {
+        let vertex_count = self.adjacent_to.len();
+
+        let mut is_numbered = vec![false; vertex_count];
+        let mut numbered_neighbors = vec![0; vertex_count];
+        let mut order = Vec::with_capacity(vertex_count);
+        for _ in 0..vertex_count {
+            let virt_id = (0..vertex_count)
+                .filter(|virt_id| !is_numbered[*virt_id])
+                .max_by_key(|virt_id| (numbered_neighbors[*virt_id], vertex_count - *virt_id))
+                .unwrap();
+
+            is_numbered[virt_id] = true;
+            for n_id in &self.adjacent_to[virt_id] {
+                numbered_neighbors[*n_id] += 1;
+            }
+            order.push(virt_id);
+        }
+        order.reverse();
+
+        order
+    }
+
+    // Number of edges that eliminating `virt_id` adds between its neighbors.
+    fn fill_in_of(adjacent_to: &[HashSet<usize>], virt_id: usize) -> usize {
+        let neighbors = adjacent_to[virt_id].iter().collect::<Vec<&usize>>();
+
+        let mut fill_in = 0;
+        for (index, src_id) in neighbors.iter().enumerate() {
+            for dst_id in &neighbors[index + 1..] {
+                if !adjacent_to[**src_id].contains(dst_id) {
+                    fill_in += 1;
+                }
+            }
+        }
+
+        fill_in
+    }
+
+    // Each time eliminates the remaining vertex with the smallest key. Ties are broken by the number of neighbors, then by id.
+    fn greedy<F>(&self, key_of: F) -> Vec<usize>
+    where
+        F: Fn(&[HashSet<usize>], usize) -> usize,
+    {
+        let vertex_count = self.adjacent_to.len();
+
+        let mut adjacent_to = self.adjacent_to.clone();
+        let mut is_eliminated = vec![false; vertex_count];
+        let mut order = Vec::with_capacity(vertex_count);
+        for _ in 0..vertex_count {
+            let virt_id = (0..vertex_count)
+                .filter(|virt_id| !is_eliminated[*virt_id])
+                .min_by_key(|virt_id| {
+                    (
+                        key_of(&adjacent_to, *virt_id),
+                        adjacent_to[*virt_id].len(),
+                        *virt_id,
+                    )
+                })
+                .unwrap();
+
+            Self::eliminate(&mut adjacent_to, virt_id);
+            is_eliminated[virt_id] = true;
+            order.push(virt_id);
+        }
+
+        order
+    }
+
+    // Connects neighbors of `virt_id` to each other and removes it. Returns the edges that are added.
+    pub(crate) fn eliminate(
+        adjacent_to: &mut [HashSet<usize>],
+        virt_id: usize,
+    ) -> Vec<(usize, usize)> {
+        let neighbors = std::mem::take(&mut adjacent_to[virt_id])
+            .into_iter()
+            .collect::<Vec<usize>>();
+
+        let mut fill_edges = vec![];
+        for (index, src_id) in neighbors.iter().enumerate() {
+            adjacent_to[*src_id].remove(&virt_id);
+
+            for dst_id in &neighbors[index + 1..] {
+                if adjacent_to[*src_id].insert(*dst_id) {
+                    adjacent_to[*dst_id].insert(*src_id);
+                    fill_edges.push((*src_id, *dst_id));
+                }
+            }
+        }
+
+        fill_edges
+    }
+
+    /// # Arguments
+    /// * `graph`: Graph to order vertices of.
+    /// * `heuristic`: Heuristic to use.
+    ///
+    /// # Returns
+    /// The elimination order, the fill-in edges it adds and the treewidth upper bound it gives.
+    ///
+    /// # Complexity
+    /// * [`MaximumCardinalitySearch`](crate::algo::EliminationHeuristic::MaximumCardinalitySearch): O(|V|^2 + |V| * Δ^2), where Δ is the largest number of neighbors of a vertex after fill-in.
+    /// * [`MinDegree`](crate::algo::EliminationHeuristic::MinDegree): O(|V|^2 + |V| * Δ^2)
+    /// * [`MinFill`](crate::algo::EliminationHeuristic::MinFill): O(|V|^2 * Δ^2)
+    pub fn execute<G: Neighbors + Vertices>(
+        &self,
+        _: &G,
+        heuristic: EliminationHeuristic,
+    ) -> EliminationOrder {
+        let order = match heuristic {
+            EliminationHeuristic::MaximumCardinalitySearch => self.maximum_cardinality_search(),
+            EliminationHeuristic::MinDegree => {
+                self.greedy(|adjacent_to, virt_id| adjacent_to[virt_id].len())
+            }
+            EliminationHeuristic::MinFill => self.greedy(Self::fill_in_of),
+        };
+
+        let mut adjacent_to = self.adjacent_to.clone();
+        let mut fill_edges = vec![];
+        let mut width = 0;
+        for virt_id in &order {
+            width = width.max(adjacent_to[*virt_id].len());
+
+            fill_edges.extend(Self::eliminate(&mut adjacent_to, *virt_id));
+        }
+
+        EliminationOrder {
+            order: order
+                .into_iter()
+                .map(|virt_id| self.id_map.real_id_of(virt_id))
+                .collect(),
+            fill_edges: fill_edges
+                .into_iter()
+                .map(|(src_id, dst_id)| {
+                    (
+                        self.id_map.real_id_of(src_id),
+                        self.id_map.real_id_of(dst_id),
+                    )
+                })
+                .collect(),
+            width,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::*;
+    use crate::storage::{DiMat, Mat};
+
+    const HEURISTICS: [EliminationHeuristic; 3] = [
+        EliminationHeuristic::MaximumCardinalitySearch,
+        EliminationHeuristic::MinDegree,
+        EliminationHeuristic::MinFill,
+    ];
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        for heuristic in HEURISTICS.iter() {
+            let order = EliminationOrdering::init(&graph).execute(&graph, *heuristic);
+
+            assert!(order.order().is_empty());
+            assert!(order.fill_edges().is_empty());
+            assert_eq!(order.treewidth_bound(), 0);
+        }
+    }
+
+    #[test]
+    fn tree() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c
+        //              |
+        //              d  ---  e
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(b, d, 1.into());
+        graph.add_edge_unchecked(d, e, 1.into());
+
+        for heuristic in HEURISTICS.iter() {
+            // When:
+            let order = EliminationOrdering::init(&graph).execute(&graph, *heuristic);
+
+            // Then: Trees are chordal and have treewidth 1.
+            assert_eq!(order.order().len(), 5);
+            assert!(order.fill_edges().is_empty());
+            assert_eq!(order.treewidth_bound(), 1);
+        }
+    }
+
+    #[test]
+    fn chordal_graph() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  e
+        //      |     / |     /
+        //      |   /   |   /
+        //      | /     | /
+        //      c  ---  d
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(a, c, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(b, d, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+        graph.add_edge_unchecked(b, e, 1.into());
+        graph.add_edge_unchecked(d, e, 1.into());
+
+        // When:
+        let order = EliminationOrdering::init(&graph)
+            .execute(&graph, EliminationHeuristic::MaximumCardinalitySearch);
+
+        // Then: Maximum cardinality search finds a perfect elimination ordering.
+        assert!(order.fill_edges().is_empty());
+        assert_eq!(order.treewidth_bound(), 2);
+    }
+
+    #[test]
+    fn grid() {
+        // Given: Directed graph
+        //
+        //      a  -->  b  -->  c
+        //      |       |       |
+        //      v       v       v
+        //      d  -->  e  -->  f
+        //      |       |       |
+        //      v       v       v
+        //      g  -->  h  -->  i
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let ids = (0..9).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        for row in 0..3 {
+            for column in 0..3 {
+                let id = ids[row * 3 + column];
+                if column < 2 {
+                    graph.add_edge_unchecked(id, id + 1, 1.into());
+                }
+                if row < 2 {
+                    graph.add_edge_unchecked(id, id + 3, 1.into());
+                }
+            }
+        }
+
+        for heuristic in HEURISTICS.iter() {
+            // When:
+            let order = EliminationOrdering::init(&graph).execute(&graph, *heuristic);
+
+            // Then: Treewidth of the 3x3 grid is 3, and every vertex is eliminated once.
+            let mut eliminated = order.order().clone();
+            eliminated.sort_unstable();
+            assert_eq!(eliminated, ids);
+            assert!(order.treewidth_bound() >= 3);
+            assert!(!order.fill_edges().is_empty());
+        }
+
+        // Then: Min fill finds an optimal ordering for the grid.
+        let order =
+            EliminationOrdering::init(&graph).execute(&graph, EliminationHeuristic::MinFill);
+        assert_eq!(order.treewidth_bound(), 3);
+    }
+}
@@ -26,7 +26,7 @@ where
     G: Graph<W, E, Ty> + Vertices + Edges<W, E>,
 {
     pub fn init(graph: &G) -> Self {
-        let id_map = graph.continuos_id_map();
+        let id_map = graph.continuous_id_map();
 
         let vertex_count = graph.vertex_count();
 
@@ -0,0 +1,240 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::graph::Error;
+
+type ProgressCallback<'a> = Box<dyn FnMut(&Progress) + 'a>;
+
+/// Flag that is shared between an algorithm and whoever wants to stop it.
+///
+/// Clones of a token share the same flag, so a clone can be moved to another thread, like the UI thread or a request handler, and cancel the algorithm from there.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    is_cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// # Returns
+    /// A token that is not cancelled.
+    pub fn init() -> Self {
+        CancellationToken {
+            is_cancelled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Asks algorithms that use this token, or any of its clones, to stop.
+    pub fn cancel(&self) {
+        self.is_cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// # Returns
+    /// `true` if `cancel` is called on this token or any of its clones, `false` otherwise.
+    pub fn is_cancelled(&self) -> bool {
+        self.is_cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// Progress of a long running algorithm, which is reported each time a step of the algorithm is completed.
+#[derive(Debug, Clone, Copy)]
+pub struct Progress {
+    completed: usize,
+    total: usize,
+}
+
+impl Progress {
+    /// # Returns
+    /// Number of steps completed so far.
+    pub fn completed(&self) -> usize {
+        self.completed
+    }
+
+    /// # Returns
+    /// Number of steps of the algorithm. What a step is depends on the algorithm, for example one source vertex in [`Betweenness`](crate::algo::centrality::Betweenness).
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// # Returns
+    /// Fraction of the steps that are completed, between 0 and 1. It's 1 for algorithms with no step.
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.completed as f64 / self.total as f64
+        }
+    }
+}
+
+/// Lets callers of long running algorithms, like [`Betweenness`](crate::algo::centrality::Betweenness) and [`BlockedFloydWarshall`](crate::algo::BlockedFloydWarshall),
+/// cancel them and display their progress.
+///
+/// Algorithms that support it have an `execute_with_control` function, which checks the cancellation token and reports progress after each step.
+/// If the token is cancelled, the algorithm stops at the end of the current step and returns an error of kind [`Cancelled`](crate::graph::ErrorKind::Cancelled).
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::{CancellationToken, ExecutionControl};
+/// use prepona::algo::centrality::Betweenness;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+///
+/// // Cancel after two of the three source vertices are processed.
+/// let token = CancellationToken::init();
+/// let canceller = token.clone();
+/// let mut control = ExecutionControl::init()
+///     .with_token(token)
+///     .on_progress(|progress| {
+///         if progress.completed() == 2 {
+///             canceller.cancel();
+///         }
+///     });
+///
+/// let result = Betweenness::init(&graph).execute_with_control(&graph, &mut control);
+///
+/// assert!(result.is_err());
+/// ```
+pub struct ExecutionControl<'a> {
+    token: Option<CancellationToken>,
+
+    on_progress: Option<ProgressCallback<'a>>,
+}
+
+impl<'a> ExecutionControl<'a> {
+    /// # Returns
+    /// Control that never cancels and does not report progress.
+    pub fn init() -> Self {
+        ExecutionControl {
+            token: None,
+
+            on_progress: None,
+        }
+    }
+
+    /// # Arguments
+    /// `token`: Token that cancels the algorithm when it's cancelled.
+    ///
+    /// # Returns
+    /// The control that stops the algorithm once `token` is cancelled.
+    pub fn with_token(mut self, token: CancellationToken) -> Self {
+        self.token = Some(token);
+
+        self
+    }
+
+    /// # Arguments
+    /// `on_progress`: Gets called after each step of the algorithm.
+    ///
+    /// # Returns
+    /// The control that reports progress of the algorithm to `on_progress`.
+    pub fn on_progress<F: FnMut(&Progress) + 'a>(mut self, on_progress: F) -> Self {
+        self.on_progress = Some(Box::new(on_progress));
+
+        self
+    }
+
+    /// # Returns
+    /// `true` if the token of this control is cancelled, `false` otherwise.
+    pub fn is_cancelled(&self) -> bool {
+        self.token
+            .as_ref()
+            .is_some_and(|token| token.is_cancelled())
+    }
+
+    /// Reports progress and then checks the cancellation token.
+    /// Algorithms call it after each step, and custom algorithms can do the same.
+    ///
+    /// # Arguments
+    /// * `completed`: Number of steps completed so far.
+    /// * `total`: Number of steps of the algorithm.
+    ///
+    /// # Returns
+    /// * `Ok`: If the algorithm should continue.
+    /// * `Err`: If the token is cancelled.
+    pub fn checkpoint(&mut self, completed: usize, total: usize) -> Result<()> {
+        if let Some(on_progress) = self.on_progress.as_mut() {
+            on_progress(&Progress { completed, total });
+        }
+
+        if self.is_cancelled() {
+            Err(Error::new_cnl(completed, total))?
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for ExecutionControl<'_> {
+    fn default() -> Self {
+        ExecutionControl::init()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ErrorKind;
+
+    #[test]
+    fn default_control_never_cancels() {
+        let mut control = ExecutionControl::init();
+
+        assert!(!control.is_cancelled());
+        assert!(control.checkpoint(1, 2).is_ok());
+    }
+
+    #[test]
+    fn progress_is_reported_before_cancelling() {
+        // Given: Control with a cancelled token.
+        let token = CancellationToken::init();
+        let mut reported = vec![];
+        let mut control = ExecutionControl::init()
+            .with_token(token.clone())
+            .on_progress(|progress| reported.push(progress.completed()));
+        assert!(control.checkpoint(1, 3).is_ok());
+        token.cancel();
+
+        // When:
+        let result = control.checkpoint(2, 3);
+
+        // Then:
+        let error = result.unwrap_err().downcast::<Error>().unwrap();
+        assert!(matches!(error.kind(), ErrorKind::Cancelled));
+        drop(control);
+        assert_eq!(reported, vec![1, 2]);
+    }
+
+    #[test]
+    fn token_is_shared_between_threads() {
+        let token = CancellationToken::init();
+        let clone = token.clone();
+
+        std::thread::spawn(move || clone.cancel()).join().unwrap();
+
+        assert!(token.is_cancelled());
+    }
+
+    #[test]
+    fn fraction() {
+        let quarter = Progress {
+            completed: 1,
+            total: 4,
+        };
+        let empty = Progress {
+            completed: 0,
+            total: 0,
+        };
+
+        assert_eq!(quarter.fraction(), 0.25);
+        assert_eq!(empty.fraction(), 1.0);
+    }
+}
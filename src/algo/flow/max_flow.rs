@@ -0,0 +1,520 @@
+use std::collections::{HashMap, VecDeque};
+
+use anyhow::Result;
+
+use crate::graph::{Edge, EdgeDir, Error, FlowEdge};
+use crate::provide::{Edges, Graph, Vertices};
+
+// Arc of the split network. Reverse of the arc at index `i` is at index `i ^ 1`.
+struct Arc {
+    dst: usize,
+    capacity: usize,
+    flow: usize,
+}
+
+/// Result of a [`MaxFlow`](crate::algo::MaxFlow) computation, mapped back to vertices and edges of the original graph.
+pub struct FlowSolution {
+    value: usize,
+    edge_flows: Vec<(usize, usize, usize, usize)>,
+    vertex_flows: HashMap<usize, usize>,
+}
+
+impl FlowSolution {
+    /// # Returns
+    /// Value of the maximum flow from source to sink.
+    pub fn value(&self) -> usize {
+        self.value
+    }
+
+    /// # Returns
+    /// Edges that carry flow in the format of: (`src_id`, `dst_id`, `edge_id`, `flow`). \
+    /// For undirected edges, `src_id` and `dst_id` show the direction that flow goes through the edge.
+    pub fn edge_flows(&self) -> &Vec<(usize, usize, usize, usize)> {
+        &self.edge_flows
+    }
+
+    /// # Arguments
+    /// `edge_id`: Id of the edge.
+    ///
+    /// # Returns
+    /// Flow that goes through the edge. 0 if edge carries no flow or it does not exist.
+    pub fn flow_of_edge(&self, edge_id: usize) -> usize {
+        self.edge_flows
+            .iter()
+            .find(|(_, _, e_id, _)| *e_id == edge_id)
+            .map_or(0, |(_, _, _, flow)| *flow)
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Flow that passes through the vertex. For source and sink it's equal to value of the flow.
+    pub fn flow_through(&self, vertex_id: usize) -> usize {
+        self.vertex_flows.get(&vertex_id).copied().unwrap_or(0)
+    }
+}
+
+/// Finds maximum flow from a source to a sink, where both edges and vertices can have capacities.
+///
+/// Capacity of each edge is the capacity of its [`FlowEdge`](crate::graph::FlowEdge), and undirected edges can carry flow in either direction.
+/// Vertices have unlimited capacity unless one is set using [`set_vertex_capacity`](crate::algo::MaxFlow::set_vertex_capacity).
+///
+/// Each vertex v is split into v<sub>in</sub> and v<sub>out</sub>, connected by an arc with capacity of the vertex.
+/// Edges going into v end at v<sub>in</sub> and edges going out of v start from v<sub>out</sub>. So flow through v is limited by its capacity.
+/// Then maximum flow of the split network is computed using Edmonds-Karp algorithm.
+///
+/// ## Note
+/// * F: Means value of the maximum flow.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::MaxFlow;
+/// use prepona::storage::DiFlowMat;
+/// use prepona::graph::{FlowEdge, SimpleGraph};
+///
+/// // Given: Graph with capacities of the edges
+/// //
+/// //           .--(3)-->  b  --(3)--.
+/// //           |                    v
+/// //           a  --(2)-->  c  --(2)-->  d
+/// //
+/// let mut graph = SimpleGraph::init(DiFlowMat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, FlowEdge::init_with(1.into(), 3, 0));
+/// graph.add_edge_unchecked(b, d, FlowEdge::init_with(1.into(), 3, 0));
+/// graph.add_edge_unchecked(a, c, FlowEdge::init_with(1.into(), 2, 0));
+/// graph.add_edge_unchecked(c, d, FlowEdge::init_with(1.into(), 2, 0));
+///
+/// // When: Only 1 unit of flow can pass through b.
+/// let mut max_flow = MaxFlow::init(&graph);
+/// max_flow.set_vertex_capacity(b, 1);
+/// let solution = max_flow.execute(&graph, a, d).unwrap();
+///
+/// // Then:
+/// assert_eq!(solution.value(), 3);
+/// assert_eq!(solution.flow_through(b), 1);
+/// assert_eq!(solution.flow_through(c), 2);
+/// ```
+pub struct MaxFlow {
+    vertex_capacity: HashMap<usize, usize>,
+    arcs: Vec<Arc>,
+    arcs_of: Vec<Vec<usize>>,
+}
+
+impl MaxFlow {
+    pub fn init<W, Ty, G>(_: &G) -> Self
+    where
+        Ty: EdgeDir,
+        G: Edges<W, FlowEdge<W>> + Vertices + Graph<W, FlowEdge<W>, Ty>,
+    {
+        MaxFlow {
+            vertex_capacity: HashMap::new(),
+            arcs: vec![],
+            arcs_of: vec![],
+        }
+    }
+
+    /// # Arguments
+    /// * `vertex_id`: Id of the vertex.
+    /// * `capacity`: Maximum flow that can pass through the vertex.
+    pub fn set_vertex_capacity(&mut self, vertex_id: usize, capacity: usize) {
+        self.vertex_capacity.insert(vertex_id, capacity);
+    }
+
+    // Adds arc from `src` to `dst` and its reverse arc with capacity of 0, and returns index of the arc.
+    fn add_arc(&mut self, src: usize, dst: usize, capacity: usize) -> usize {
+        let index = self.arcs.len();
+
+        self.arcs.push(Arc {
+            dst,
+            capacity,
+            flow: 0,
+        });
+        self.arcs.push(Arc {
+            dst: src,
+            capacity: 0,
+            flow: 0,
+        });
+
+        self.arcs_of[src].push(index);
+        self.arcs_of[dst].push(index + 1);
+
+        index
+    }
+
+    fn residual_of(&self, index: usize) -> usize {
+        let arc = &self.arcs[index];
+
+        if index & 1 == 0 {
+            arc.capacity - arc.flow
+        } else {
+            // Reverse arcs can cancel the flow of their forward arc.
+            self.arcs[index ^ 1].flow
+        }
+    }
+
+    fn push_flow(&mut self, index: usize, flow: usize) {
+        if index & 1 == 0 {
+            self.arcs[index].flow += flow;
+        } else {
+            self.arcs[index ^ 1].flow -= flow;
+        }
+    }
+
+    // Finds shortest augmenting path from `source` to `sink` and returns index of its arcs.
+    fn augmenting_path(&self, source: usize, sink: usize) -> Option<Vec<usize>> {
+        let mut parent_arc = vec![None; self.arcs_of.len()];
+        let mut visited = vec![false; self.arcs_of.len()];
+        let mut queue = VecDeque::new();
+
+        visited[source] = true;
+        queue.push_back(source);
+
+        while let Some(node) = queue.pop_front() {
+            if node == sink {
+                break;
+            }
+
+            for index in &self.arcs_of[node] {
+                let dst = self.arcs[*index].dst;
+
+                if !visited[dst] && self.residual_of(*index) > 0 {
+                    visited[dst] = true;
+                    parent_arc[dst] = Some(*index);
+                    queue.push_back(dst);
+                }
+            }
+        }
+
+        if !visited[sink] {
+            return None;
+        }
+
+        let mut path = vec![];
+        let mut node = sink;
+        while let Some(index) = parent_arc[node] {
+            path.push(index);
+            node = self.arcs[index ^ 1].dst;
+        }
+
+        Some(path)
+    }
+
+    /// # Arguments
+    /// * `graph`: Graph to find maximum flow in.
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the sink vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing the maximum flow. Value of the flow is 0 if source and sink are the same vertex.
+    /// * `Err`:
+    ///   * [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if source, sink or a vertex that has a capacity does not exist.
+    ///   * [`WeightOverflow`](crate::graph::ErrorKind::WeightOverflow) if value of the flow does not fit in `usize`.
+    ///
+    /// # Complexity
+    /// O(min(|V| * |E|<sup>2</sup>, F * |E|))
+    pub fn execute<W, Ty, G>(
+        mut self,
+        graph: &G,
+        src_id: usize,
+        dst_id: usize,
+    ) -> Result<FlowSolution>
+    where
+        Ty: EdgeDir,
+        G: Edges<W, FlowEdge<W>> + Vertices + Graph<W, FlowEdge<W>, Ty>,
+    {
+        for vertex_id in [src_id, dst_id].iter().chain(self.vertex_capacity.keys()) {
+            if !graph.contains_vertex(*vertex_id) {
+                Err(Error::new_vnf(*vertex_id))?
+            }
+        }
+
+        let id_map = graph.continuous_id_map();
+        let vertex_count = graph.vertex_count();
+
+        // Vertex with virtual id: v is split into v_in = 2v and v_out = 2v + 1.
+        self.arcs_of = vec![vec![]; vertex_count * 2];
+        let mut vertex_arcs = vec![];
+        for virt_id in 0..vertex_count {
+            let capacity = self
+                .vertex_capacity
+                .get(&id_map.real_id_of(virt_id))
+                .copied()
+                .unwrap_or(usize::MAX);
+
+            vertex_arcs.push(self.add_arc(2 * virt_id, 2 * virt_id + 1, capacity));
+        }
+
+        let mut edge_arcs = vec![];
+        for (src_id, dst_id, edge) in graph.edges() {
+            let src_virt_id = id_map.virt_id_of(src_id);
+            let dst_virt_id = id_map.virt_id_of(dst_id);
+            let capacity = edge.get_capacity();
+
+            let forward = self.add_arc(2 * src_virt_id + 1, 2 * dst_virt_id, capacity);
+            let backward = if Ty::is_directed() {
+                None
+            } else {
+                Some(self.add_arc(2 * dst_virt_id + 1, 2 * src_virt_id, capacity))
+            };
+
+            edge_arcs.push((src_id, dst_id, edge.get_id(), forward, backward));
+        }
+
+        let mut value = 0;
+        if src_id != dst_id {
+            let source = 2 * id_map.virt_id_of(src_id);
+            let sink = 2 * id_map.virt_id_of(dst_id) + 1;
+
+            while let Some(path) = self.augmenting_path(source, sink) {
+                let flow = path
+                    .iter()
+                    .map(|index| self.residual_of(*index))
+                    .min()
+                    .unwrap();
+
+                for index in path {
+                    self.push_flow(index, flow);
+                }
+
+                value = value.checked_add(flow).ok_or_else(Error::new_wo)?;
+            }
+        }
+
+        let edge_flows = edge_arcs
+            .into_iter()
+            .filter_map(|(src_id, dst_id, edge_id, forward, backward)| {
+                let forward_flow = self.arcs[forward].flow;
+                let backward_flow = backward.map_or(0, |index| self.arcs[index].flow);
+
+                // Flows in opposite directions of an undirected edge cancel each other.
+                if forward_flow > backward_flow {
+                    Some((src_id, dst_id, edge_id, forward_flow - backward_flow))
+                } else if backward_flow > forward_flow {
+                    Some((dst_id, src_id, edge_id, backward_flow - forward_flow))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        let vertex_flows = vertex_arcs
+            .into_iter()
+            .enumerate()
+            .filter(|(_, index)| self.arcs[*index].flow > 0)
+            .map(|(virt_id, index)| (id_map.real_id_of(virt_id), self.arcs[index].flow))
+            .collect();
+
+        Ok(FlowSolution {
+            value,
+            edge_flows,
+            vertex_flows,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::SimpleGraph;
+    use crate::storage::{DiFlowMat, FlowMat};
+
+    #[test]
+    fn unknown_vertex() {
+        let mut graph = SimpleGraph::init(DiFlowMat::<usize>::init());
+        let a = graph.add_vertex();
+
+        let mut max_flow = MaxFlow::init(&graph);
+        max_flow.set_vertex_capacity(a + 1, 1);
+
+        assert!(max_flow.execute(&graph, a, a).is_err());
+        assert!(MaxFlow::init(&graph).execute(&graph, a, a + 1).is_err());
+    }
+
+    #[test]
+    fn same_source_and_sink() {
+        let mut graph = SimpleGraph::init(DiFlowMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, FlowEdge::init_with(1.into(), 1, 0));
+
+        let solution = MaxFlow::init(&graph).execute(&graph, a, a).unwrap();
+
+        assert_eq!(solution.value(), 0);
+        assert!(solution.edge_flows().is_empty());
+    }
+
+    #[test]
+    fn vertices_added_after_init() {
+        // Given: Graph with a single vertex when algorithm is initialized.
+        let mut graph = SimpleGraph::init(DiFlowMat::<usize>::init());
+        let a = graph.add_vertex();
+        let max_flow = MaxFlow::init(&graph);
+
+        // When: Adding vertices and edges before executing.
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, FlowEdge::init_with(1.into(), 3, 0));
+        graph.add_edge_unchecked(b, c, FlowEdge::init_with(1.into(), 2, 0));
+        let solution = max_flow.execute(&graph, a, c).unwrap();
+
+        // Then:
+        assert_eq!(solution.value(), 2);
+        assert_eq!(solution.flow_through(b), 2);
+    }
+
+    #[test]
+    fn directed_graph_without_vertex_capacities() {
+        // Given: Graph with capacities of the edges
+        //
+        //      a  --(10)-->  b  --(4)-->  d
+        //      |             |            ^
+        //     (5)           (8)           |
+        //      v             v            |
+        //      c  ----(9)--> e  ---(7)----'
+        //
+        let mut graph = SimpleGraph::init(DiFlowMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, FlowEdge::init_with(1.into(), 10, 0));
+        graph.add_edge_unchecked(a, c, FlowEdge::init_with(1.into(), 5, 0));
+        let bd = graph.add_edge_unchecked(b, d, FlowEdge::init_with(1.into(), 4, 0));
+        graph.add_edge_unchecked(b, e, FlowEdge::init_with(1.into(), 8, 0));
+        graph.add_edge_unchecked(c, e, FlowEdge::init_with(1.into(), 9, 0));
+        let ed = graph.add_edge_unchecked(e, d, FlowEdge::init_with(1.into(), 7, 0));
+
+        // When: Finding maximum flow from a to d.
+        let solution = MaxFlow::init(&graph).execute(&graph, a, d).unwrap();
+
+        // Then:
+        assert_eq!(solution.value(), 11);
+        assert_eq!(solution.flow_of_edge(bd), 4);
+        assert_eq!(solution.flow_of_edge(ed), 7);
+        assert!(solution.flow_of_edge(ab) >= 4);
+        assert_eq!(solution.flow_through(a), 11);
+        assert_eq!(solution.flow_through(d), 11);
+        assert_eq!(solution.flow_through(e), 7);
+
+        // When: Finding maximum flow from d to a.
+        let solution = MaxFlow::init(&graph).execute(&graph, d, a).unwrap();
+
+        // Then:
+        assert_eq!(solution.value(), 0);
+    }
+
+    #[test]
+    fn directed_graph_with_vertex_capacities() {
+        // Given: Graph with capacities of the edges
+        //
+        //      a  --(5)-->  b  --(5)-->  e
+        //      |            |            ^ ^
+        //     (5)          (5)           | |
+        //      |            v            | |
+        //      .-------->  c  ---(5)-----' |
+        //                   |              |
+        //                  (5)             |
+        //                   v              |
+        //                   d  ----(5)-----'
+        //
+        let mut graph = SimpleGraph::init(DiFlowMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, FlowEdge::init_with(1.into(), 5, 0));
+        graph.add_edge_unchecked(a, c, FlowEdge::init_with(1.into(), 5, 0));
+        graph.add_edge_unchecked(b, c, FlowEdge::init_with(1.into(), 5, 0));
+        graph.add_edge_unchecked(b, e, FlowEdge::init_with(1.into(), 5, 0));
+        graph.add_edge_unchecked(c, e, FlowEdge::init_with(1.into(), 5, 0));
+        let cd = graph.add_edge_unchecked(c, d, FlowEdge::init_with(1.into(), 5, 0));
+        graph.add_edge_unchecked(d, e, FlowEdge::init_with(1.into(), 5, 0));
+
+        // When: b can pass 2 units of flow and c can pass 3 units.
+        let mut max_flow = MaxFlow::init(&graph);
+        max_flow.set_vertex_capacity(b, 2);
+        max_flow.set_vertex_capacity(c, 3);
+        let solution = max_flow.execute(&graph, a, e).unwrap();
+
+        // Then:
+        assert_eq!(solution.value(), 5);
+        assert_eq!(solution.flow_through(b), 2);
+        assert_eq!(solution.flow_through(c), 3);
+        assert!(solution.flow_of_edge(cd) <= 3);
+
+        // And: Flow is conserved at each vertex other than source and sink.
+        for vertex_id in [b, c, d] {
+            let flow_in: usize = solution
+                .edge_flows()
+                .iter()
+                .filter(|(_, dst_id, _, _)| *dst_id == vertex_id)
+                .map(|(_, _, _, flow)| flow)
+                .sum();
+            let flow_out: usize = solution
+                .edge_flows()
+                .iter()
+                .filter(|(src_id, _, _, _)| *src_id == vertex_id)
+                .map(|(_, _, _, flow)| flow)
+                .sum();
+
+            assert_eq!(flow_in, flow_out);
+            assert_eq!(flow_in, solution.flow_through(vertex_id));
+        }
+
+        // When: Sink can only receive 1 unit of flow.
+        let mut max_flow = MaxFlow::init(&graph);
+        max_flow.set_vertex_capacity(e, 1);
+        let solution = max_flow.execute(&graph, a, e).unwrap();
+
+        // Then:
+        assert_eq!(solution.value(), 1);
+    }
+
+    #[test]
+    fn undirected_graph() {
+        // Given: Graph with capacities of the edges
+        //
+        //      a  --(3)--  b  --(1)--  d
+        //      |           |           |
+        //     (1)         (2)         (3)
+        //      |           |           |
+        //      c  --(2)----'           |
+        //      |                       |
+        //      '-----------------------'
+        //
+        let mut graph = SimpleGraph::init(FlowMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, FlowEdge::init_with(1.into(), 3, 0));
+        graph.add_edge_unchecked(b, d, FlowEdge::init_with(1.into(), 1, 0));
+        graph.add_edge_unchecked(a, c, FlowEdge::init_with(1.into(), 1, 0));
+        let bc = graph.add_edge_unchecked(c, b, FlowEdge::init_with(1.into(), 2, 0));
+        graph.add_edge_unchecked(c, d, FlowEdge::init_with(1.into(), 3, 0));
+
+        // When: Finding maximum flow from a to d.
+        let solution = MaxFlow::init(&graph).execute(&graph, a, d).unwrap();
+
+        // Then: Flow goes from b to c, against the order edge is added.
+        assert_eq!(solution.value(), 4);
+        assert!(solution.edge_flows().contains(&(b, c, bc, 2)));
+
+        // When: Only 2 units of flow can pass through b.
+        let mut max_flow = MaxFlow::init(&graph);
+        max_flow.set_vertex_capacity(b, 2);
+        let solution = max_flow.execute(&graph, a, d).unwrap();
+
+        // Then:
+        assert_eq!(solution.value(), 3);
+        assert_eq!(solution.flow_through(b), 2);
+    }
+}
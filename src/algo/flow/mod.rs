@@ -0,0 +1,3 @@
+mod max_flow;
+
+pub use max_flow::{FlowSolution, MaxFlow};
@@ -23,7 +23,7 @@ impl HasCycle {
             is_visited: vec![false; graph.vertex_count()],
             is_finished: vec![false; graph.vertex_count()],
             edge_stack: vec![],
-            id_map: graph.continuos_id_map(),
+            id_map: graph.continuous_id_map(),
         }
     }
 
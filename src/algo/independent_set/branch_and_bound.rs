@@ -0,0 +1,300 @@
+use std::collections::HashSet;
+use std::ops::Add;
+
+use num_traits::Zero;
+
+use crate::graph::{Edge, EdgeDir};
+use crate::provide::{Graph, IdMap, Neighbors, Vertices};
+
+/// Finds a maximum weight independent set of a general graph using branch and bound.
+///
+/// An independent set is a set of vertices where no two of them are connected by an edge. In directed graphs, direction of the edges is ignored.
+/// At each step, the heaviest remaining vertex is either added to the set(removing its neighbors) or discarded.
+/// A branch is pruned when the weight of the current set plus the weight of all remaining vertices can not beat the best set found so far.
+/// Vertices with a loop and vertices with non positive weight are never selected.
+///
+/// Running time is exponential in the worst case, so it's meant for small graphs. For forests use [`TreeIndependentSet`](crate::algo::TreeIndependentSet).
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::BranchAndBoundIndependentSet;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a(2)  ---  b(3)
+/// //       |          |
+/// //      d(3)  ---  c(2)
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+/// graph.add_edge_unchecked(d, a, 1.into());
+///
+/// let weights = [2, 3, 2, 3];
+/// let (set, weight) =
+///     BranchAndBoundIndependentSet::init(&graph).execute(&graph, |vertex_id| weights[vertex_id]);
+///
+/// assert_eq!(set, vec![b, d]);
+/// assert_eq!(weight, 6);
+/// ```
+pub struct BranchAndBoundIndependentSet {
+    id_map: IdMap,
+
+    // Neighbors of each vertex ignoring direction of the edges, using virtual ids.
+    adjacents_of: Vec<HashSet<usize>>,
+}
+
+impl BranchAndBoundIndependentSet {
+    pub fn init<W, E, Ty, G>(graph: &G) -> Self
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Neighbors + Vertices + Graph<W, E, Ty>,
+    {
+        let id_map = graph.continuous_id_map();
+        let vertex_count = graph.vertex_count();
+
+        let mut adjacents_of = vec![HashSet::new(); vertex_count];
+        for virt_id in 0..vertex_count {
+            for n_id in graph.neighbors_unchecked(id_map.real_id_of(virt_id)) {
+                let n_virt_id = id_map.virt_id_of(n_id);
+
+                adjacents_of[virt_id].insert(n_virt_id);
+                adjacents_of[n_virt_id].insert(virt_id);
+            }
+        }
+
+        BranchAndBoundIndependentSet {
+            id_map,
+            adjacents_of,
+        }
+    }
+
+    // `candidates` are sorted by weight in descending order.
+    fn branch<V>(
+        &self,
+        candidates: &[usize],
+        weights: &[V],
+        set: &mut Vec<usize>,
+        weight: V,
+        best: &mut (Vec<usize>, V),
+    ) where
+        V: Copy + Ord + Zero + Add<Output = V>,
+    {
+        if weight > best.1 {
+            *best = (set.clone(), weight);
+        }
+
+        let bound = candidates
+            .iter()
+            .fold(weight, |sum, virt_id| sum + weights[*virt_id]);
+        if candidates.is_empty() || bound <= best.1 {
+            return;
+        }
+
+        let virt_id = candidates[0];
+
+        // Include the vertex.
+        let remaining = candidates[1..]
+            .iter()
+            .copied()
+            .filter(|c_virt_id| !self.adjacents_of[virt_id].contains(c_virt_id))
+            .collect::<Vec<usize>>();
+        set.push(virt_id);
+        self.branch(&remaining, weights, set, weight + weights[virt_id], best);
+        set.pop();
+
+        // Exclude the vertex.
+        self.branch(&candidates[1..], weights, set, weight, best);
+    }
+
+    /// # Arguments
+    /// * `graph`: Graph to find its maximum weight independent set.
+    /// * `weight_of`: Returns weight of the vertex with the given id.
+    ///
+    /// # Returns
+    /// Ids of the vertices in the set(sorted), and total weight of the set.
+    ///
+    /// # Complexity
+    /// O(2^|V| * |V|) in the worst case.
+    pub fn execute<W, E, Ty, G, V, F>(self, graph: &G, weight_of: F) -> (Vec<usize>, V)
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Neighbors + Vertices + Graph<W, E, Ty>,
+        V: Copy + Ord + Zero + Add<Output = V>,
+        F: Fn(usize) -> V,
+    {
+        let vertex_count = graph.vertex_count();
+
+        let weights = (0..vertex_count)
+            .map(|virt_id| weight_of(self.id_map.real_id_of(virt_id)))
+            .collect::<Vec<V>>();
+
+        let mut candidates = (0..vertex_count)
+            .filter(|virt_id| {
+                weights[*virt_id] > V::zero() && !self.adjacents_of[*virt_id].contains(virt_id)
+            })
+            .collect::<Vec<usize>>();
+        candidates.sort_by(|v1, v2| weights[*v2].cmp(&weights[*v1]));
+
+        let mut best = (vec![], V::zero());
+        self.branch(&candidates, &weights, &mut vec![], V::zero(), &mut best);
+
+        let (set, weight) = best;
+        let mut set = set
+            .into_iter()
+            .map(|virt_id| self.id_map.real_id_of(virt_id))
+            .collect::<Vec<usize>>();
+        set.sort_unstable();
+
+        (set, weight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::TreeIndependentSet;
+    use crate::graph::{ListGraph, MatGraph};
+    use crate::storage::{DiList, DiMat, List, Mat};
+
+    // Maximum weight of an independent set by checking all subsets of vertices.
+    fn brute_force(vertex_count: usize, edges: &[(usize, usize)], weights: &[isize]) -> isize {
+        (0..1usize << vertex_count)
+            .filter(|subset| {
+                edges
+                    .iter()
+                    .all(|(src, dst)| subset & (1 << src) == 0 || subset & (1 << dst) == 0)
+            })
+            .map(|subset| {
+                (0..vertex_count)
+                    .filter(|index| subset & (1 << index) != 0)
+                    .map(|index| weights[index])
+                    .sum()
+            })
+            .max()
+            .unwrap()
+    }
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let (set, weight) = BranchAndBoundIndependentSet::init(&graph).execute(&graph, |_| 1);
+
+        assert!(set.is_empty());
+        assert_eq!(weight, 0);
+    }
+
+    #[test]
+    fn complete_graph() {
+        // Given: Complete graph with 5 vertices.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let ids = (0..5).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        for i in 0..5 {
+            for j in i + 1..5 {
+                graph.add_edge_unchecked(ids[i], ids[j], 1.into());
+            }
+        }
+
+        // When:
+        let (set, weight) =
+            BranchAndBoundIndependentSet::init(&graph).execute(&graph, |vertex_id| vertex_id + 1);
+
+        // Then: Only the heaviest vertex is selected.
+        assert_eq!(set, vec![ids[4]]);
+        assert_eq!(weight, 5);
+    }
+
+    #[test]
+    fn directed_graph_with_loop() {
+        // Given: Graph
+        //
+        //      a(5)  -->  b(2)  -->  c(2)
+        //      ^  |
+        //      '--'
+        //
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, a, 1.into());
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        let weights = [5, 2, 2];
+
+        // When:
+        let (set, weight) = BranchAndBoundIndependentSet::init(&graph)
+            .execute(&graph, |vertex_id| weights[vertex_id]);
+
+        // Then: a can not be selected because it's adjacent to itself.
+        assert_eq!(weight, 2);
+        assert!(!set.contains(&a));
+    }
+
+    #[test]
+    fn petersen_graph() {
+        // Given: Petersen graph, with an outer cycle, an inner pentagram and spokes between them.
+        let mut edges = vec![];
+        for i in 0..5 {
+            edges.push((i, (i + 1) % 5));
+            edges.push((5 + i, 5 + (i + 2) % 5));
+            edges.push((i, 5 + i));
+        }
+        let weights: [isize; 10] = [4, 7, 1, 3, 6, 5, 2, 8, -3, 4];
+
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let ids = (0..10).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        for (src, dst) in &edges {
+            graph.add_edge_unchecked(ids[*src], ids[*dst], 1.into());
+        }
+
+        // When:
+        let (set, weight) = BranchAndBoundIndependentSet::init(&graph)
+            .execute(&graph, |vertex_id| weights[vertex_id]);
+
+        // Then:
+        assert_eq!(weight, brute_force(10, &edges, &weights));
+        assert_eq!(set.iter().map(|id| weights[*id]).sum::<isize>(), weight);
+        for (src, dst) in &edges {
+            assert!(!(set.contains(src) && set.contains(dst)));
+        }
+    }
+
+    #[test]
+    fn same_as_tree_solver() {
+        // Given: Tree
+        //
+        //      a  ---  b  ---  c  ---  d
+        //      |       |
+        //      e       f  ---  g
+        //
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let ids = (0..7).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        for (src, dst) in [(0, 1), (1, 2), (2, 3), (0, 4), (1, 5), (5, 6)] {
+            graph.add_edge_unchecked(ids[src], ids[dst], 1.into());
+        }
+        let weights = [3, 6, 2, 4, 2, 1, 5];
+
+        // When:
+        let (set, weight) = BranchAndBoundIndependentSet::init(&graph)
+            .execute(&graph, |vertex_id| weights[vertex_id]);
+        let (tree_set, tree_weight) = TreeIndependentSet::init(&graph)
+            .execute(&graph, |vertex_id| weights[vertex_id])
+            .unwrap();
+
+        // Then:
+        assert_eq!(weight, tree_weight);
+        assert_eq!(set, tree_set);
+    }
+}
@@ -0,0 +1,5 @@
+mod branch_and_bound;
+mod tree_independent_set;
+
+pub use branch_and_bound::BranchAndBoundIndependentSet;
+pub use tree_independent_set::TreeIndependentSet;
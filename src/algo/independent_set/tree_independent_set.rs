@@ -0,0 +1,278 @@
+use std::ops::Add;
+
+use anyhow::Result;
+use num_traits::Zero;
+
+use crate::graph::{Edge, Error, ErrorKind, UndirectedEdge};
+use crate::provide::{Edges, Graph, IdMap, Neighbors, Vertices};
+
+/// Finds a maximum weight independent set of a forest using dynamic programming.
+///
+/// An independent set is a set of vertices where no two of them are connected by an edge.
+/// For each vertex v, two values are computed bottom up: best weight of the subtree of v when v is in the set, and when it's not.
+/// Vertices with negative weight are never selected.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::TreeIndependentSet;
+/// use prepona::storage::List;
+/// use prepona::graph::ListGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a(1)  ---  b(5)  ---  c(1)
+/// //                  |
+/// //                 d(2)  ---  e(4)
+/// //
+/// let mut graph = ListGraph::init(List::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// let e = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(b, d, 1.into());
+/// graph.add_edge_unchecked(d, e, 1.into());
+///
+/// let weights = [1, 5, 1, 2, 4];
+/// let (set, weight) = TreeIndependentSet::init(&graph)
+///     .execute(&graph, |vertex_id| weights[vertex_id])
+///     .unwrap();
+///
+/// assert_eq!(set, vec![b, e]);
+/// assert_eq!(weight, 9);
+/// ```
+pub struct TreeIndependentSet {
+    id_map: IdMap,
+    is_visited: Vec<bool>,
+}
+
+impl TreeIndependentSet {
+    pub fn init<W, E, G>(graph: &G) -> Self
+    where
+        E: Edge<W>,
+        G: Edges<W, E> + Neighbors + Vertices + Graph<W, E, UndirectedEdge>,
+    {
+        TreeIndependentSet {
+            id_map: graph.continuous_id_map(),
+            is_visited: vec![false; graph.vertex_count()],
+        }
+    }
+
+    /// # Arguments
+    /// * `graph`: Forest to find its maximum weight independent set.
+    /// * `weight_of`: Returns weight of the vertex with the given id.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing ids of the vertices in the set(sorted), and total weight of the set.
+    /// * `Err`: [`Cycle`](crate::graph::ErrorKind::Cycle) if graph is not a forest. Loops and multiple edges between two vertices are also cycles.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|)
+    pub fn execute<W, E, G, V, F>(mut self, graph: &G, weight_of: F) -> Result<(Vec<usize>, V)>
+    where
+        E: Edge<W>,
+        G: Edges<W, E> + Neighbors + Vertices + Graph<W, E, UndirectedEdge>,
+        V: Copy + Ord + Zero + Add<Output = V>,
+        F: Fn(usize) -> V,
+    {
+        let vertex_count = graph.vertex_count();
+
+        // Best weight of subtree of each vertex when the vertex is in the set, and when it's not.
+        let mut with = vec![V::zero(); vertex_count];
+        let mut without = vec![V::zero(); vertex_count];
+        let mut children_of = vec![vec![]; vertex_count];
+        let mut roots = vec![];
+
+        for root in 0..vertex_count {
+            if self.is_visited[root] {
+                continue;
+            }
+            roots.push(root);
+
+            // Vertices in the order they are visited, in the format of: (virt_id, parent edge id).
+            let mut order = vec![];
+            let mut stack = vec![(root, None)];
+            self.is_visited[root] = true;
+
+            while let Some((virt_id, parent_edge_id)) = stack.pop() {
+                order.push(virt_id);
+
+                let real_id = self.id_map.real_id_of(virt_id);
+                for (n_id, edge) in graph.edges_from_unchecked(real_id) {
+                    if Some(edge.get_id()) == parent_edge_id {
+                        continue;
+                    }
+
+                    let n_virt_id = self.id_map.virt_id_of(n_id);
+                    if self.is_visited[n_virt_id] {
+                        Err(Error::new(
+                            ErrorKind::Cycle,
+                            format!(
+                                "Graph is not a forest: edge with id: {} closes a cycle",
+                                edge.get_id()
+                            ),
+                        ))?
+                    }
+
+                    self.is_visited[n_virt_id] = true;
+                    children_of[virt_id].push(n_virt_id);
+                    stack.push((n_virt_id, Some(edge.get_id())));
+                }
+            }
+
+            // Children are visited after their parents, so the reverse order visits children first.
+            for virt_id in order.into_iter().rev() {
+                with[virt_id] = children_of[virt_id].iter().fold(
+                    weight_of(self.id_map.real_id_of(virt_id)),
+                    |sum, c_virt_id| sum + without[*c_virt_id],
+                );
+                without[virt_id] = children_of[virt_id]
+                    .iter()
+                    .fold(V::zero(), |sum, c_virt_id| {
+                        sum + with[*c_virt_id].max(without[*c_virt_id])
+                    });
+            }
+        }
+
+        // Top down: a vertex is selected if it's better to do so and its parent is not selected.
+        let mut set = vec![];
+        let mut weight = V::zero();
+        let mut stack = roots
+            .into_iter()
+            .map(|root| (root, false))
+            .collect::<Vec<(usize, bool)>>();
+
+        while let Some((virt_id, is_parent_selected)) = stack.pop() {
+            let is_selected = !is_parent_selected && with[virt_id] > without[virt_id];
+
+            if is_selected {
+                set.push(self.id_map.real_id_of(virt_id));
+                weight = weight + weight_of(self.id_map.real_id_of(virt_id));
+            }
+
+            stack.extend(
+                children_of[virt_id]
+                    .iter()
+                    .map(|c_virt_id| (*c_virt_id, is_selected)),
+            );
+        }
+        set.sort_unstable();
+
+        Ok((set, weight))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ListGraph, MatGraph};
+    use crate::storage::{List, Mat};
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let (set, weight) = TreeIndependentSet::init(&graph)
+            .execute(&graph, |_| 1)
+            .unwrap();
+
+        assert!(set.is_empty());
+        assert_eq!(weight, 0);
+    }
+
+    #[test]
+    fn not_a_forest() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c
+        //      |               |
+        //      '---------------'
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, a, 1.into());
+
+        // Then:
+        assert!(TreeIndependentSet::init(&graph)
+            .execute(&graph, |_| 1)
+            .is_err());
+
+        // Given: Graph
+        //
+        //      a  ===  b
+        //
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(a, b, 1.into());
+
+        // Then:
+        assert!(TreeIndependentSet::init(&graph)
+            .execute(&graph, |_| 1)
+            .is_err());
+    }
+
+    #[test]
+    fn forest() {
+        // Given: Graph
+        //
+        //      a(3)  ---  b(4)  ---  c(3)      f(-1)  ---  g(2)
+        //                  |
+        //                 d(1)  ---  e(1)      h(-2)
+        //
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        let g = graph.add_vertex();
+        graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(b, d, 1.into());
+        graph.add_edge_unchecked(d, e, 1.into());
+        graph.add_edge_unchecked(f, g, 1.into());
+
+        let weights: [isize; 8] = [3, 4, 3, 1, 1, -1, 2, -2];
+
+        // When:
+        let (set, weight) = TreeIndependentSet::init(&graph)
+            .execute(&graph, |vertex_id| weights[vertex_id])
+            .unwrap();
+
+        // Then: Taking both leaves of b is better than b, and vertices with negative weight are skipped.
+        assert_eq!(weight, 3 + 3 + 1 + 2);
+        assert!(set.contains(&a) && set.contains(&c) && set.contains(&g));
+        assert!(set.contains(&d) ^ set.contains(&e));
+        assert!(!set.contains(&b) && !set.contains(&f));
+    }
+
+    #[test]
+    fn path() {
+        // Given: Path with 10 vertices, where weight of vertex i is i.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let ids = (0..10).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        for i in 0..9 {
+            graph.add_edge_unchecked(ids[i], ids[i + 1], 1.into());
+        }
+
+        // When:
+        let (set, weight) = TreeIndependentSet::init(&graph)
+            .execute(&graph, |vertex_id| vertex_id)
+            .unwrap();
+
+        // Then: Odd vertices are selected.
+        assert_eq!(set, vec![1, 3, 5, 7, 9]);
+        assert_eq!(weight, 25);
+    }
+}
@@ -0,0 +1,470 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use anyhow::Result;
+
+use crate::graph::{DirectedEdge, Edge, Error};
+use crate::provide::{Graph, IdMap, Neighbors, Vertices};
+
+/// Method used by [`Layering`](crate::algo::Layering) to assign layers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayeringMethod {
+    /// Puts each vertex one layer below its lowest predecessor. Uses the fewest possible layers, but layers can be arbitrarily wide.
+    LongestPath,
+
+    /// Coffman–Graham layering that puts at most the given number of vertices in each layer. Transitive edges are ignored,
+    /// and number of layers is at most (2 - 2 / width) times the fewest possible.
+    CoffmanGraham(usize),
+}
+
+/// Result of a [`Layering`](crate::algo::Layering).
+#[derive(Debug)]
+pub struct Layers {
+    layer_of: HashMap<usize, usize>,
+    layers: Vec<Vec<usize>>,
+}
+
+impl Layers {
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing index of the layer of the vertex.
+    /// * `None`: If vertex does not exist.
+    pub fn layer_of(&self, vertex_id: usize) -> Option<usize> {
+        self.layer_of.get(&vertex_id).copied()
+    }
+
+    /// # Returns
+    /// Vertices of each layer, starting from the layer of the sources.
+    pub fn layers(&self) -> &Vec<Vec<usize>> {
+        &self.layers
+    }
+
+    /// # Returns
+    /// Number of layers.
+    pub fn layer_count(&self) -> usize {
+        self.layers.len()
+    }
+}
+
+/// Assigns a layer to each vertex of a directed acyclic graph so that every edge goes from a layer to a later layer.
+///
+/// This is the layer assignment step of Sugiyama style layered drawings, and also schedules a pipeline of dependent tasks into rounds.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::{Layering, LayeringMethod};
+/// use prepona::storage::DiMat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  -->  b  -->  c
+/// //      |               ^
+/// //      '-----> d  -----'
+/// //
+/// let mut graph = MatGraph::init(DiMat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(a, d, 1.into());
+/// graph.add_edge_unchecked(d, c, 1.into());
+///
+/// let layers = Layering::init(&graph).execute(&graph, LayeringMethod::LongestPath).unwrap();
+/// assert_eq!(layers.layer_count(), 3);
+/// assert_eq!(layers.layer_of(b), layers.layer_of(d));
+///
+/// // At most one vertex in each layer.
+/// let layers = Layering::init(&graph).execute(&graph, LayeringMethod::CoffmanGraham(1)).unwrap();
+/// assert_eq!(layers.layer_count(), 4);
+/// ```
+pub struct Layering {
+    id_map: IdMap,
+
+    // Vertices that each vertex has an edge to, using virtual ids.
+    successors: Vec<HashSet<usize>>,
+
+    // Vertices that have an edge to each vertex, using virtual ids.
+    predecessors: Vec<HashSet<usize>>,
+}
+
+impl Layering {
+    pub fn init<W, E, G>(graph: &G) -> Self
+    where
+        E: Edge<W>,
+        G: Graph<W, E, DirectedEdge> + Vertices + Neighbors,
+    {
+        let id_map = graph.continuous_id_map();
+        let vertex_count = graph.vertex_count();
+
+        let mut successors = vec![HashSet::new(); vertex_count];
+        let mut predecessors = vec![HashSet::new(); vertex_count];
+        for (virt_id, vertex_successors) in successors.iter_mut().enumerate() {
+            for n_id in graph.neighbors_unchecked(id_map.real_id_of(virt_id)) {
+                let n_virt_id = id_map.virt_id_of(n_id);
+
+                vertex_successors.insert(n_virt_id);
+                predecessors[n_virt_id].insert(virt_id);
+            }
+        }
+
+        Layering {
+            id_map,
+            successors,
+            predecessors,
+        }
+    }
+
+    // Vertices in topological order, or an edge of a cycle.
+    fn topological_order(&self) -> Result<Vec<usize>> {
+        let vertex_count = self.successors.len();
+
+        let mut in_degree = self
+            .predecessors
+            .iter()
+            .map(|predecessors| predecessors.len())
+            .collect::<Vec<usize>>();
+        let mut queue = (0..vertex_count)
+            .filter(|virt_id| in_degree[*virt_id] == 0)
+            .collect::<VecDeque<usize>>();
+
+        let mut order = Vec::with_capacity(vertex_count);
+        while let Some(virt_id) = queue.pop_front() {
+            order.push(virt_id);
+
+            for s_id in &self.successors[virt_id] {
+                in_degree[*s_id] -= 1;
+                if in_degree[*s_id] == 0 {
+                    queue.push_back(*s_id);
+                }
+            }
+        }
+
+        // Every remaining vertex has a remaining predecessor, so there is a cycle.
+        if let Some(virt_id) = (0..vertex_count).find(|virt_id| in_degree[*virt_id] != 0) {
+            let p_id = self.predecessors[virt_id]
+                .iter()
+                .find(|p_id| in_degree[**p_id] != 0)
+                .unwrap();
+
+            Err(Error::new_c(
+                self.id_map.real_id_of(*p_id),
+                self.id_map.real_id_of(virt_id),
+            ))?
+        }
+
+        Ok(order)
+    }
+
+    fn longest_path(&self, order: &[usize]) -> Vec<usize> {
+        let mut layer_of = vec![0; order.len()];
+        for virt_id in order {
+            for s_id in &self.successors[*virt_id] {
+                layer_of[*s_id] = layer_of[*s_id].max(layer_of[*virt_id] + 1);
+            }
+        }
+
+        layer_of
+    }
+
+    // Successors of each vertex, without the ones that are also reachable through another successor.
+    fn transitive_reduction(&self, order: &[usize]) -> Vec<HashSet<usize>> {
+        let mut reachable_from = vec![HashSet::new(); order.len()];
+        for virt_id in order.iter().rev() {
+            let mut reachable = HashSet::new();
+            for s_id in &self.successors[*virt_id] {
+                reachable.insert(*s_id);
+                reachable.extend(reachable_from[*s_id].iter().copied());
+            }
+            reachable_from[*virt_id] = reachable;
+        }
+
+        self.successors
+            .iter()
+            .map(|successors| {
+                successors
+                    .iter()
+                    .filter(|s_id| {
+                        successors
+                            .iter()
+                            .all(|other_id| !reachable_from[*other_id].contains(s_id))
+                    })
+                    .copied()
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn coffman_graham(&self, order: &[usize], width: usize) -> Vec<usize> {
+        if width == 0 {
+            panic!("Width of the layers must be at least one");
+        }
+
+        let vertex_count = order.len();
+        let successors = self.transitive_reduction(order);
+        let mut predecessors = vec![vec![]; vertex_count];
+        for (virt_id, vertex_successors) in successors.iter().enumerate() {
+            for s_id in vertex_successors {
+                predecessors[*s_id].push(virt_id);
+            }
+        }
+
+        // Each time labels the vertex, whose predecessors are all labeled, with the smallest decreasing sequence of labels of predecessors.
+        let mut label_of: Vec<Option<usize>> = vec![None; vertex_count];
+        for label in 0..vertex_count {
+            let virt_id = (0..vertex_count)
+                .filter(|virt_id| label_of[*virt_id].is_none())
+                .filter_map(|virt_id| {
+                    let mut labels = predecessors[virt_id]
+                        .iter()
+                        .map(|p_id| label_of[*p_id])
+                        .collect::<Option<Vec<usize>>>()?;
+                    labels.sort_unstable_by(|lhs, rhs| rhs.cmp(lhs));
+
+                    Some((labels, virt_id))
+                })
+                .min()
+                .unwrap()
+                .1;
+
+            label_of[virt_id] = Some(label);
+        }
+
+        // Fills layers from the sinks upward, each time placing the vertex with the largest label whose successors are all placed.
+        let mut level_of: Vec<Option<usize>> = vec![None; vertex_count];
+        let mut level = 0;
+        let mut level_size = 0;
+        for _ in 0..vertex_count {
+            let virt_id = (0..vertex_count)
+                .filter(|virt_id| level_of[*virt_id].is_none())
+                .filter(|virt_id| {
+                    successors[*virt_id]
+                        .iter()
+                        .all(|s_id| level_of[*s_id].is_some())
+                })
+                .max_by_key(|virt_id| label_of[*virt_id])
+                .unwrap();
+
+            let has_successor_in_level = successors[virt_id]
+                .iter()
+                .any(|s_id| level_of[*s_id] == Some(level));
+            if level_size == width || has_successor_in_level {
+                level += 1;
+                level_size = 0;
+            }
+
+            level_of[virt_id] = Some(level);
+            level_size += 1;
+        }
+
+        level_of
+            .into_iter()
+            .map(|vertex_level| level - vertex_level.unwrap())
+            .collect()
+    }
+
+    /// # Arguments
+    /// * `graph`: Graph to assign layers to.
+    /// * `method`: Method used to assign the layers.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing layer of each vertex. Sources of the graph are in the first layer, except when [`CoffmanGraham`](crate::algo::LayeringMethod::CoffmanGraham) has to move them down.
+    /// * `Err`: [`Cycle`](crate::graph::ErrorKind::Cycle) if graph has a cycle.
+    ///
+    /// # Panics
+    /// If width of [`CoffmanGraham`](crate::algo::LayeringMethod::CoffmanGraham) is zero.
+    ///
+    /// # Complexity
+    /// * [`LongestPath`](crate::algo::LayeringMethod::LongestPath): O(|V| + |E|)
+    /// * [`CoffmanGraham`](crate::algo::LayeringMethod::CoffmanGraham): O(|V|^2 * log(|V|) + |V| * |E|)
+    pub fn execute<G: Vertices + Neighbors>(
+        &self,
+        _: &G,
+        method: LayeringMethod,
+    ) -> Result<Layers> {
+        let order = self.topological_order()?;
+
+        let virt_layer_of = match method {
+            LayeringMethod::LongestPath => self.longest_path(&order),
+            LayeringMethod::CoffmanGraham(width) => self.coffman_graham(&order, width),
+        };
+
+        let layer_count = match virt_layer_of.iter().max() {
+            Some(max_layer) => max_layer + 1,
+            None => 0,
+        };
+        let mut layers = vec![vec![]; layer_count];
+        let mut layer_of = HashMap::new();
+        for (virt_id, layer) in virt_layer_of.into_iter().enumerate() {
+            let real_id = self.id_map.real_id_of(virt_id);
+
+            layers[layer].push(real_id);
+            layer_of.insert(real_id, layer);
+        }
+
+        Ok(Layers { layer_of, layers })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ErrorKind, MatGraph};
+    use crate::provide::*;
+    use crate::storage::DiMat;
+
+    fn assert_edges_go_down<G>(graph: &G, layers: &Layers)
+    where
+        G: Edges<usize, crate::graph::DefaultEdge<usize>>,
+    {
+        for (src_id, dst_id, _) in graph.edges() {
+            assert!(layers.layer_of(src_id).unwrap() < layers.layer_of(dst_id).unwrap());
+        }
+    }
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(DiMat::<usize>::init());
+
+        let layers = Layering::init(&graph)
+            .execute(&graph, LayeringMethod::LongestPath)
+            .unwrap();
+
+        assert_eq!(layers.layer_count(), 0);
+    }
+
+    #[test]
+    fn cyclic_graph() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c
+        //              ^       |
+        //              '-------'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, b, 1.into());
+
+        // When:
+        let result = Layering::init(&graph).execute(&graph, LayeringMethod::LongestPath);
+
+        // Then:
+        assert!(matches!(
+            result.unwrap_err().downcast_ref::<Error>().unwrap().kind(),
+            ErrorKind::Cycle
+        ));
+    }
+
+    #[test]
+    fn longest_path() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c  -->  d
+        //      |                       ^
+        //      '-------->  e  ---------'
+        //                  ^
+        //      f  ---------'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+        graph.add_edge_unchecked(a, e, 1.into());
+        graph.add_edge_unchecked(e, d, 1.into());
+        graph.add_edge_unchecked(f, e, 1.into());
+
+        // When:
+        let layers = Layering::init(&graph)
+            .execute(&graph, LayeringMethod::LongestPath)
+            .unwrap();
+
+        // Then:
+        assert_edges_go_down(&graph, &layers);
+        assert_eq!(layers.layer_count(), 4);
+        assert_eq!(layers.layer_of(a), Some(0));
+        assert_eq!(layers.layer_of(f), Some(0));
+        assert_eq!(layers.layer_of(e), Some(1));
+        assert_eq!(layers.layer_of(d), Some(3));
+        assert_eq!(layers.layers()[0].len(), 2);
+    }
+
+    #[test]
+    fn coffman_graham() {
+        // Given: Graph with six sources that all point to one sink.
+        //
+        //      a   b   c   d   e   f
+        //       \  |   |   |   |  /
+        //        '-'---'-+-'---'-'
+        //                v
+        //                g
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let sources = (0..6).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        let g = graph.add_vertex();
+        for source_id in &sources {
+            graph.add_edge_unchecked(*source_id, g, 1.into());
+        }
+
+        for width in 1..=6 {
+            // When:
+            let layers = Layering::init(&graph)
+                .execute(&graph, LayeringMethod::CoffmanGraham(width))
+                .unwrap();
+
+            // Then: Sources are split into layers of at most `width` vertices, above the sink.
+            assert_edges_go_down(&graph, &layers);
+            assert!(layers.layers().iter().all(|layer| layer.len() <= width));
+            assert_eq!(layers.layer_count(), 6_usize.div_ceil(width) + 1);
+            assert_eq!(layers.layer_of(g), Some(layers.layer_count() - 1));
+        }
+    }
+
+    #[test]
+    fn coffman_graham_ignores_transitive_edges() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c
+        //      |               ^
+        //      '---------------'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(a, c, 1.into());
+
+        // When:
+        let layers = Layering::init(&graph)
+            .execute(&graph, LayeringMethod::CoffmanGraham(2))
+            .unwrap();
+
+        // Then:
+        assert_edges_go_down(&graph, &layers);
+        assert_eq!(layers.layer_count(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_width() {
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        graph.add_vertex();
+
+        let _ = Layering::init(&graph).execute(&graph, LayeringMethod::CoffmanGraham(0));
+    }
+}
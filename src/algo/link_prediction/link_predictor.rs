@@ -0,0 +1,287 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+
+use crate::algo::link_prediction::rank;
+use crate::algo::metrics::adjacency;
+use crate::graph::Error;
+use crate::provide::{IdMap, Neighbors, Vertices};
+
+/// Score used by [`LinkPredictor`](crate::algo::link_prediction::LinkPredictor) to measure how likely two vertices are to become adjacent.
+///
+/// N(v) is the set of vertices adjacent to v.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkScore {
+    /// |N(u) ∩ N(v)|
+    CommonNeighbors,
+
+    /// |N(u) ∩ N(v)| / |N(u) ∪ N(v)|: Zero if both vertices are isolated.
+    Jaccard,
+
+    /// Sum of 1 / log(|N(w)|) for every w in N(u) ∩ N(v): Common neighbors with fewer neighbors weigh more.
+    AdamicAdar,
+
+    /// |N(u)| * |N(v)|
+    PreferentialAttachment,
+}
+
+/// Predicts missing or future edges of a graph from the neighborhoods of its vertices.
+///
+/// Directions and weights of the edges are ignored, parallel edges are counted once and loops are ignored.
+/// For [`SimRank`](crate::algo::link_prediction::SimRank) scores, which are based on the whole structure of the graph, use [`SimRank`](crate::algo::link_prediction::SimRank) instead.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::link_prediction::{LinkPredictor, LinkScore};
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b  ---  c
+/// //      |       |
+/// //      d  ---  e
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// let e = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(a, d, 1.into());
+/// graph.add_edge_unchecked(b, e, 1.into());
+/// graph.add_edge_unchecked(d, e, 1.into());
+///
+/// let predictor = LinkPredictor::init(&graph);
+/// let predictions = predictor
+///     .predict(LinkScore::CommonNeighbors, &predictor.candidates())
+///     .unwrap();
+///
+/// // a and e have two common neighbors: b and d.
+/// assert_eq!(predictions[0], (a, e, 2.0));
+/// ```
+pub struct LinkPredictor {
+    id_map: IdMap,
+    adjacent_to: Vec<HashSet<usize>>,
+}
+
+impl LinkPredictor {
+    /// # Arguments
+    /// `graph`: Graph to predict its edges.
+    ///
+    /// # Returns
+    /// Initialized link predictor.
+    pub fn init<G: Neighbors + Vertices>(graph: &G) -> Self {
+        let (id_map, adjacent_to) = adjacency(graph);
+
+        LinkPredictor {
+            id_map,
+            adjacent_to,
+        }
+    }
+
+    /// # Returns
+    /// Every pair of distinct vertices that are not adjacent, with smaller id first.
+    ///
+    /// # Complexity
+    /// O(|V|<sup>2</sup>)
+    pub fn candidates(&self) -> Vec<(usize, usize)> {
+        let mut candidates = vec![];
+
+        for src_id in 0..self.adjacent_to.len() {
+            for dst_id in src_id + 1..self.adjacent_to.len() {
+                if !self.adjacent_to[src_id].contains(&dst_id) {
+                    let src_id = self.id_map.real_id_of(src_id);
+                    let dst_id = self.id_map.real_id_of(dst_id);
+
+                    candidates.push((src_id.min(dst_id), src_id.max(dst_id)));
+                }
+            }
+        }
+
+        candidates
+    }
+
+    /// # Arguments
+    /// * `score`: Score to compute.
+    /// * `src_id`: Id of the first vertex.
+    /// * `dst_id`: Id of the second vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing the score of the pair.
+    /// * `Err`: [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if either of the vertices does not exist.
+    ///
+    /// # Complexity
+    /// O(min(|N(u)|, |N(v)|))
+    pub fn score(&self, score: LinkScore, src_id: usize, dst_id: usize) -> Result<f64> {
+        let src_id = self.virt_id_of(src_id)?;
+        let dst_id = self.virt_id_of(dst_id)?;
+
+        Ok(self.score_unchecked(score, src_id, dst_id))
+    }
+
+    /// # Arguments
+    /// * `score`: Score to compute.
+    /// * `candidates`: Pairs of vertices to score.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing each candidate with its score, sorted by score in descending order. Candidates with equal scores keep their order.
+    /// * `Err`: [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if a vertex of a candidate does not exist.
+    pub fn predict(
+        &self,
+        score: LinkScore,
+        candidates: &[(usize, usize)],
+    ) -> Result<Vec<(usize, usize, f64)>> {
+        let predictions = candidates
+            .iter()
+            .map(|(src_id, dst_id)| {
+                self.score(score, *src_id, *dst_id)
+                    .map(|score| (*src_id, *dst_id, score))
+            })
+            .collect::<Result<Vec<(usize, usize, f64)>>>()?;
+
+        Ok(rank(predictions))
+    }
+
+    fn virt_id_of(&self, real_id: usize) -> Result<usize> {
+        match self.id_map.get_virt_id(real_id) {
+            Some(virt_id) => Ok(virt_id),
+            None => Err(Error::new_vnf(real_id))?,
+        }
+    }
+
+    fn score_unchecked(&self, score: LinkScore, src_id: usize, dst_id: usize) -> f64 {
+        let src_neighbors = &self.adjacent_to[src_id];
+        let dst_neighbors = &self.adjacent_to[dst_id];
+
+        let common_neighbors = || src_neighbors.intersection(dst_neighbors);
+
+        match score {
+            LinkScore::CommonNeighbors => common_neighbors().count() as f64,
+            LinkScore::Jaccard => {
+                let common_count = common_neighbors().count();
+                let union_count = src_neighbors.len() + dst_neighbors.len() - common_count;
+
+                if union_count == 0 {
+                    0.0
+                } else {
+                    common_count as f64 / union_count as f64
+                }
+            }
+            LinkScore::AdamicAdar => common_neighbors()
+                .map(|virt_id| 1.0 / (self.adjacent_to[*virt_id].len() as f64).ln())
+                .filter(|score| score.is_finite())
+                .sum(),
+            LinkScore::PreferentialAttachment => (src_neighbors.len() * dst_neighbors.len()) as f64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ErrorKind, MatGraph};
+    use crate::provide::*;
+    use crate::storage::{DiMat, Mat};
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let predictor = LinkPredictor::init(&graph);
+
+        assert!(predictor.candidates().is_empty());
+        assert!(predictor
+            .predict(LinkScore::Jaccard, &[])
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn scores() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c
+        //      |       |       |
+        //      d  ---  e  ---  f
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(a, d, 1.into());
+        graph.add_edge_unchecked(b, e, 1.into());
+        graph.add_edge_unchecked(c, f, 1.into());
+        graph.add_edge_unchecked(d, e, 1.into());
+        graph.add_edge_unchecked(e, f, 1.into());
+
+        // When:
+        let predictor = LinkPredictor::init(&graph);
+
+        // Then: b and d have two common neighbors a and e, which have 2 and 3 neighbors.
+        let score = |kind| predictor.score(kind, b, d).unwrap();
+        assert_eq!(score(LinkScore::CommonNeighbors), 2.0);
+        assert_eq!(score(LinkScore::Jaccard), 2.0 / 3.0);
+        assert!(
+            (score(LinkScore::AdamicAdar) - (1.0 / 2.0_f64.ln() + 1.0 / 3.0_f64.ln())).abs() < 1e-9
+        );
+        assert_eq!(score(LinkScore::PreferentialAttachment), 6.0);
+        assert_eq!(
+            predictor.score(LinkScore::CommonNeighbors, a, f).unwrap(),
+            0.0
+        );
+        assert_eq!(predictor.candidates().len(), 8);
+    }
+
+    #[test]
+    fn ranked_predictions() {
+        // Given: Graph
+        //
+        //      a  <--  b  -->  c
+        //              |
+        //              v
+        //              d
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(b, a, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(b, d, 1.into());
+
+        // When:
+        let predictions = LinkPredictor::init(&graph)
+            .predict(LinkScore::AdamicAdar, &[(a, b), (a, c), (c, d)])
+            .unwrap();
+
+        // Then: Directions are ignored, and a and b have no common neighbor.
+        let expected = 1.0 / 3.0_f64.ln();
+        assert_eq!(predictions[0], (a, c, expected));
+        assert_eq!(predictions[1], (c, d, expected));
+        assert_eq!(predictions[2], (a, b, 0.0));
+    }
+
+    #[test]
+    fn vertex_not_found() {
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+
+        let result = LinkPredictor::init(&graph).predict(LinkScore::Jaccard, &[(a, a + 1)]);
+
+        let error = result.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            ErrorKind::VertexNotFound
+        ));
+    }
+}
@@ -0,0 +1,16 @@
+mod link_predictor;
+mod sim_rank;
+
+pub use link_predictor::{LinkPredictor, LinkScore};
+pub use sim_rank::SimRank;
+
+use std::cmp::Ordering;
+
+// Sorts predictions by their score in descending order. Predictions with equal scores keep their order.
+fn rank(mut predictions: Vec<(usize, usize, f64)>) -> Vec<(usize, usize, f64)> {
+    predictions.sort_by(|(_, _, lhs_score), (_, _, rhs_score)| {
+        rhs_score.partial_cmp(lhs_score).unwrap_or(Ordering::Equal)
+    });
+
+    predictions
+}
@@ -0,0 +1,326 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+
+use crate::algo::link_prediction::rank;
+use crate::graph::Error;
+use crate::provide::{IdMap, Neighbors, Vertices};
+
+/// Computes SimRank similarity of every pair of vertices iteratively.
+///
+/// Two vertices are similar if the vertices that have an edge to them are similar.
+/// So similarity of two distinct vertices is `decay` times the average similarity of pairs of their in-neighbors, and every vertex is completely similar to itself.
+/// Vertices without in-neighbors are not similar to any other vertex.
+///
+/// Weights of the edges are ignored, parallel edges are counted once, and in undirected graphs each edge can be followed in both directions.
+/// Iterations always converge, and error of the similarities after k iterations is at most `decay` to the power of k + 1.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::link_prediction::SimRank;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      b  <--  a  -->  c
+/// //
+/// let mut graph = MatGraph::init(DiMat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(a, c, 1.into());
+///
+/// let sim_rank = SimRank::init(&graph).with_decay(0.5);
+/// let predictions = sim_rank.predict(&graph, &[(a, b), (b, c)]).unwrap();
+///
+/// // b and c are both pointed to by a.
+/// assert_eq!(predictions, vec![(b, c, 0.5), (a, b, 0.0)]);
+/// ```
+pub struct SimRank {
+    id_map: IdMap,
+    in_neighbors_of: Vec<Vec<usize>>,
+
+    decay: f64,
+    tolerance: f64,
+    max_iterations: usize,
+}
+
+impl SimRank {
+    /// # Arguments
+    /// `graph`: Graph to compute similarity of its vertices.
+    ///
+    /// # Returns
+    /// Initialized SimRank with decay of 0.8, tolerance of 1e-6 and at most 100 iterations.
+    pub fn init<G: Neighbors + Vertices>(graph: &G) -> Self {
+        let id_map = graph.continuous_id_map();
+
+        let mut in_neighbors_of = vec![HashSet::new(); graph.vertex_count()];
+        for src_id in 0..graph.vertex_count() {
+            for dst_id in graph.neighbors_unchecked(id_map.real_id_of(src_id)) {
+                in_neighbors_of[id_map.virt_id_of(dst_id)].insert(src_id);
+            }
+        }
+
+        SimRank {
+            id_map,
+            in_neighbors_of: in_neighbors_of
+                .into_iter()
+                .map(|in_neighbors| in_neighbors.into_iter().collect())
+                .collect(),
+
+            decay: 0.8,
+            tolerance: 1e-6,
+            max_iterations: 100,
+        }
+    }
+
+    /// # Arguments
+    /// `decay`: Factor in range of 0 to 1, by which similarity of in-neighbors is attenuated.
+    ///
+    /// # Returns
+    /// SimRank with the specified decay.
+    pub fn with_decay(mut self, decay: f64) -> Self {
+        self.decay = decay.clamp(0.0, 1.0);
+
+        self
+    }
+
+    /// # Arguments
+    /// `tolerance`: Iterations stop when largest change of similarities in an iteration is less than `tolerance`.
+    ///
+    /// # Returns
+    /// SimRank with the specified tolerance.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+
+        self
+    }
+
+    /// # Arguments
+    /// `max_iterations`: Maximum number of iterations before giving up.
+    ///
+    /// # Returns
+    /// SimRank with the specified maximum number of iterations.
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+
+        self
+    }
+
+    /// # Returns
+    /// * `Ok`: Containing similarity of every ordered pair of vertices, mapped from their ids.
+    /// * `Err`: [`NotConverged`](crate::graph::ErrorKind::NotConverged) if similarities did not converge within the maximum number of iterations.
+    ///
+    /// # Complexity
+    /// O(`max_iterations` * |E|<sup>2</sup>)
+    pub fn execute<G: Neighbors + Vertices>(&self, _: &G) -> Result<HashMap<(usize, usize), f64>> {
+        let similarities = self.similarities()?;
+
+        let mut similarity_of = HashMap::new();
+        for (src_id, similarities) in similarities.into_iter().enumerate() {
+            for (dst_id, similarity) in similarities.into_iter().enumerate() {
+                similarity_of.insert(
+                    (
+                        self.id_map.real_id_of(src_id),
+                        self.id_map.real_id_of(dst_id),
+                    ),
+                    similarity,
+                );
+            }
+        }
+
+        Ok(similarity_of)
+    }
+
+    /// # Arguments
+    /// * `graph`: Graph to predict its edges.
+    /// * `candidates`: Pairs of vertices to score.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing each candidate with its similarity, sorted by similarity in descending order. Candidates with equal similarities keep their order.
+    /// * `Err`:
+    ///     * [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if a vertex of a candidate does not exist.
+    ///     * [`NotConverged`](crate::graph::ErrorKind::NotConverged) if similarities did not converge within the maximum number of iterations.
+    pub fn predict<G: Neighbors + Vertices>(
+        &self,
+        _: &G,
+        candidates: &[(usize, usize)],
+    ) -> Result<Vec<(usize, usize, f64)>> {
+        let virt_candidates = candidates
+            .iter()
+            .map(|(src_id, dst_id)| Ok((self.virt_id_of(*src_id)?, self.virt_id_of(*dst_id)?)))
+            .collect::<Result<Vec<(usize, usize)>>>()?;
+
+        let similarities = self.similarities()?;
+
+        Ok(rank(
+            candidates
+                .iter()
+                .zip(virt_candidates)
+                .map(|((src_id, dst_id), (virt_src_id, virt_dst_id))| {
+                    (*src_id, *dst_id, similarities[virt_src_id][virt_dst_id])
+                })
+                .collect(),
+        ))
+    }
+
+    fn virt_id_of(&self, real_id: usize) -> Result<usize> {
+        match self.id_map.get_virt_id(real_id) {
+            Some(virt_id) => Ok(virt_id),
+            None => Err(Error::new_vnf(real_id))?,
+        }
+    }
+
+    fn similarities(&self) -> Result<Vec<Vec<f64>>> {
+        let vertex_count = self.in_neighbors_of.len();
+
+        let mut similarities = vec![vec![0.0; vertex_count]; vertex_count];
+        (0..vertex_count).for_each(|virt_id| similarities[virt_id][virt_id] = 1.0);
+
+        for _ in 0..self.max_iterations {
+            let mut next_similarities = vec![vec![0.0; vertex_count]; vertex_count];
+            let mut change: f64 = 0.0;
+
+            for src_id in 0..vertex_count {
+                next_similarities[src_id][src_id] = 1.0;
+
+                for dst_id in src_id + 1..vertex_count {
+                    let src_in_neighbors = &self.in_neighbors_of[src_id];
+                    let dst_in_neighbors = &self.in_neighbors_of[dst_id];
+                    if src_in_neighbors.is_empty() || dst_in_neighbors.is_empty() {
+                        continue;
+                    }
+
+                    let sum = src_in_neighbors
+                        .iter()
+                        .map(|i| {
+                            dst_in_neighbors
+                                .iter()
+                                .map(|j| similarities[*i][*j])
+                                .sum::<f64>()
+                        })
+                        .sum::<f64>();
+                    let similarity =
+                        self.decay * sum / (src_in_neighbors.len() * dst_in_neighbors.len()) as f64;
+
+                    change = change.max((similarity - similarities[src_id][dst_id]).abs());
+                    next_similarities[src_id][dst_id] = similarity;
+                    next_similarities[dst_id][src_id] = similarity;
+                }
+            }
+
+            similarities = next_similarities;
+
+            if change < self.tolerance {
+                return Ok(similarities);
+            }
+        }
+
+        Err(Error::new_ncv(self.max_iterations))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ErrorKind, MatGraph};
+    use crate::provide::*;
+    use crate::storage::{DiMat, Mat};
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let similarities = SimRank::init(&graph).execute(&graph).unwrap();
+
+        assert!(similarities.is_empty());
+    }
+
+    #[test]
+    fn undirected_path() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // When:
+        let similarities = SimRank::init(&graph)
+            .with_tolerance(1e-12)
+            .with_max_iterations(1000)
+            .execute(&graph)
+            .unwrap();
+
+        // Then: s(a, b) is zero, because neighbors of a and b are never at the same distance.
+        // So s(a, c) = 0.8 * s(b, b) and s(b, b) = 1.
+        assert_eq!(similarities[&(a, a)], 1.0);
+        assert!((similarities[&(a, c)] - 0.8).abs() < 1e-9);
+        assert_eq!(similarities[&(a, c)], similarities[&(c, a)]);
+        assert!(similarities[&(a, b)].abs() < 1e-9);
+    }
+
+    #[test]
+    fn directed_graph() {
+        // Given: Graph
+        //
+        //      a  -->  c  -->  e
+        //      |
+        //      v
+        //      d  -->  f
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        graph.add_edge_unchecked(a, c, 1.into());
+        graph.add_edge_unchecked(a, d, 1.into());
+        graph.add_edge_unchecked(c, e, 1.into());
+        graph.add_edge_unchecked(d, f, 1.into());
+
+        // When:
+        let predictions = SimRank::init(&graph)
+            .predict(&graph, &[(a, c), (e, f), (c, d)])
+            .unwrap();
+
+        // Then: s(c, d) = 0.8 and s(e, f) = 0.8 * s(c, d).
+        assert_eq!(predictions[0].0, c);
+        assert!((predictions[0].2 - 0.8).abs() < 1e-9);
+        assert_eq!(predictions[1].0, e);
+        assert!((predictions[1].2 - 0.64).abs() < 1e-9);
+        assert_eq!(predictions[2], (a, c, 0.0));
+    }
+
+    #[test]
+    fn not_converged() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // When:
+        let result = SimRank::init(&graph).with_max_iterations(1).execute(&graph);
+
+        // Then:
+        let error = result.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            ErrorKind::NotConverged
+        ));
+    }
+}
@@ -0,0 +1,409 @@
+use std::any::Any;
+use std::ops::Sub;
+
+use anyhow::Result;
+use magnitude::Magnitude;
+use num_traits::Zero;
+
+use crate::graph::{Edge, Error, ErrorKind};
+use crate::provide::{Edges, Vertices};
+
+// Edges of a matching in the format of: (left_id, right_id, edge_id).
+type Matching = Vec<(usize, usize, usize)>;
+
+/// Solves the assignment problem: matches each vertex of the left side to a distinct vertex of the right side, so that total weight of the matching is minimum.
+///
+/// There must be an edge from each vertex of the left side to each vertex of the right side(complete bipartite graph), so an [`AdjMatrix`](crate::storage::AdjMatrix)
+/// is a natural storage for it. If there are multiple edges between two vertices, the one with the minimum weight is used.
+///
+/// Left side can be smaller than the right side, in which case some vertices of the right side remain unmatched.
+///
+/// ## Note
+/// * L: Means number of vertices of the left side.
+/// * R: Means number of vertices of the right side.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::Hungarian;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Cost of assigning each worker to each job
+/// //
+/// //               job1   job2
+/// //      worker1    4      1
+/// //      worker2    2      3
+/// //
+/// let mut graph = MatGraph::init(DiMat::<usize>::init());
+/// let worker1 = graph.add_vertex();
+/// let worker2 = graph.add_vertex();
+/// let job1 = graph.add_vertex();
+/// let job2 = graph.add_vertex();
+/// graph.add_edge_unchecked(worker1, job1, 4.into());
+/// graph.add_edge_unchecked(worker1, job2, 1.into());
+/// graph.add_edge_unchecked(worker2, job1, 2.into());
+/// graph.add_edge_unchecked(worker2, job2, 3.into());
+///
+/// let (assignment, cost) = Hungarian::init(&graph)
+///     .execute(&graph, &[worker1, worker2], &[job1, job2])
+///     .unwrap();
+///
+/// assert_eq!(cost, 3.into());
+/// assert!(assignment.iter().any(|(src_id, dst_id, _)| *src_id == worker1 && *dst_id == job2));
+/// assert!(assignment.iter().any(|(src_id, dst_id, _)| *src_id == worker2 && *dst_id == job1));
+/// ```
+pub struct Hungarian<W> {
+    // Potential of each row(left vertex), and negated potential of each column(right vertex).
+    // Keeping column potentials negated lets both of them only grow, so reduced costs never need negative values.
+    row_potential: Vec<W>,
+    col_potential: Vec<W>,
+
+    // Row matched to each column and the previous column on the alternating path to each column. Index 0 is a dummy column.
+    row_of: Vec<usize>,
+    prev_col: Vec<usize>,
+}
+
+impl<W: Copy + Ord + Zero + Any + Sub<Output = W>> Hungarian<W> {
+    pub fn init<E, G>(_: &G) -> Self
+    where
+        E: Edge<W>,
+        G: Edges<W, E> + Vertices,
+    {
+        Hungarian {
+            row_potential: vec![],
+            col_potential: vec![],
+            row_of: vec![],
+            prev_col: vec![],
+        }
+    }
+
+    /// # Arguments
+    /// * `graph`: Graph containing the edges from the left side to the right side.
+    /// * `left_ids`: Id of the vertices of the left side.
+    /// * `right_ids`: Id of the vertices of the right side.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing:
+    ///     * Edges of the matching in the format of: (`left_id`, `right_id`, `edge_id`).
+    ///     * Total weight of the matching.
+    /// * `Err`:
+    ///     * [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if a vertex does not exist.
+    ///     * [`EdgeNotFound`](crate::graph::ErrorKind::EdgeNotFound) if there is no edge from a vertex of the left side to a vertex of the right side,
+    ///       or left side has more vertices than the right side.
+    ///     * [`InvalidWeight`](crate::graph::ErrorKind::InvalidWeight) if weight of an edge is infinite.
+    ///
+    /// # Complexity
+    /// O(L<sup>2</sup> * R)
+    pub fn execute<E, G>(
+        mut self,
+        graph: &G,
+        left_ids: &[usize],
+        right_ids: &[usize],
+    ) -> Result<(Matching, Magnitude<W>)>
+    where
+        E: Edge<W>,
+        G: Edges<W, E> + Vertices,
+    {
+        if left_ids.len() > right_ids.len() {
+            Err(Error::new(
+                ErrorKind::EdgeNotFound,
+                format!(
+                    "Can not match {} vertices to only {} vertices",
+                    left_ids.len(),
+                    right_ids.len()
+                ),
+            ))?
+        }
+
+        // Cheapest edge from each left vertex to each right vertex, in the format of: (weight, edge_id).
+        let mut costs = vec![];
+        for left_id in left_ids {
+            let mut row = vec![];
+
+            for right_id in right_ids {
+                let edge = graph
+                    .edges_between(*left_id, *right_id)?
+                    .into_iter()
+                    .min_by_key(|edge| *edge.get_weight())
+                    .ok_or_else(|| {
+                        Error::new(
+                            ErrorKind::EdgeNotFound,
+                            format!("There is no edge from {} to {}", left_id, right_id),
+                        )
+                    })?;
+
+                if !edge.get_weight().is_finite() {
+                    Err(Error::new_iw(edge.get_id()))?
+                }
+
+                row.push((edge.get_weight().unwrap(), edge.get_id()));
+            }
+
+            costs.push(row);
+        }
+
+        let row_count = left_ids.len();
+        let col_count = right_ids.len();
+
+        self.row_potential = vec![W::zero(); row_count + 1];
+        self.col_potential = vec![W::zero(); col_count + 1];
+        self.row_of = vec![0; col_count + 1];
+        self.prev_col = vec![0; col_count + 1];
+
+        // Rows and columns are 1-indexed so that 0 can represent "no row" and the dummy column.
+        for row in 1..=row_count {
+            self.row_of[0] = row;
+            let mut col = 0;
+
+            let mut min_reduced_cost = vec![Magnitude::PosInfinite; col_count + 1];
+            let mut is_used = vec![false; col_count + 1];
+
+            // Grow the alternating tree until it reaches an unmatched column.
+            while self.row_of[col] != 0 {
+                is_used[col] = true;
+                let current_row = self.row_of[col];
+
+                let mut delta = Magnitude::PosInfinite;
+                let mut next_col = 0;
+
+                for c in 1..=col_count {
+                    if is_used[c] {
+                        continue;
+                    }
+
+                    let reduced_cost = Magnitude::from(
+                        costs[current_row - 1][c - 1].0 + self.col_potential[c]
+                            - self.row_potential[current_row],
+                    );
+                    if reduced_cost < min_reduced_cost[c] {
+                        min_reduced_cost[c] = reduced_cost;
+                        self.prev_col[c] = col;
+                    }
+
+                    if min_reduced_cost[c] < delta {
+                        delta = min_reduced_cost[c];
+                        next_col = c;
+                    }
+                }
+
+                let delta = delta.unwrap();
+                for c in 0..=col_count {
+                    if is_used[c] {
+                        self.row_potential[self.row_of[c]] =
+                            self.row_potential[self.row_of[c]] + delta;
+                        self.col_potential[c] = self.col_potential[c] + delta;
+                    } else if min_reduced_cost[c].is_finite() {
+                        min_reduced_cost[c] = Magnitude::from(min_reduced_cost[c].unwrap() - delta);
+                    }
+                }
+
+                col = next_col;
+            }
+
+            // Flip the alternating path that ends at the unmatched column.
+            while col != 0 {
+                let prev_col = self.prev_col[col];
+                self.row_of[col] = self.row_of[prev_col];
+                col = prev_col;
+            }
+        }
+
+        let matched_cols = (1..=col_count).filter(|col| self.row_of[*col] != 0);
+
+        let total_weight = matched_cols
+            .clone()
+            .fold(Magnitude::from(W::zero()), |total_weight, col| {
+                total_weight + costs[self.row_of[col] - 1][col - 1].0.into()
+            });
+
+        let assignment = matched_cols
+            .map(|col| {
+                let row = self.row_of[col];
+                (
+                    left_ids[row - 1],
+                    right_ids[col - 1],
+                    costs[row - 1][col - 1].1,
+                )
+            })
+            .collect();
+
+        Ok((assignment, total_weight))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::*;
+    use crate::storage::{DiMat, Mat};
+
+    // Adds vertices for each row and column of `costs` and an edge for each entry.
+    fn graph_of(
+        costs: &[&[usize]],
+    ) -> (
+        MatGraph<usize, crate::graph::DirectedEdge>,
+        Vec<usize>,
+        Vec<usize>,
+    ) {
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+
+        let left_ids = (0..costs.len())
+            .map(|_| graph.add_vertex())
+            .collect::<Vec<usize>>();
+        let right_ids = (0..costs[0].len())
+            .map(|_| graph.add_vertex())
+            .collect::<Vec<usize>>();
+
+        for (i, row) in costs.iter().enumerate() {
+            for (j, cost) in row.iter().enumerate() {
+                graph.add_edge_unchecked(left_ids[i], right_ids[j], (*cost).into());
+            }
+        }
+
+        (graph, left_ids, right_ids)
+    }
+
+    // Finds cost of the cheapest assignment by trying all of them.
+    fn brute_force(costs: &[&[usize]], row: usize, used: &mut Vec<bool>) -> usize {
+        if row == costs.len() {
+            return 0;
+        }
+
+        let mut min_cost = usize::MAX;
+        for col in 0..costs[row].len() {
+            if !used[col] {
+                used[col] = true;
+                min_cost = min_cost.min(costs[row][col] + brute_force(costs, row + 1, used));
+                used[col] = false;
+            }
+        }
+
+        min_cost
+    }
+
+    #[test]
+    fn empty_left_side() {
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+
+        let (assignment, cost) = Hungarian::init(&graph).execute(&graph, &[], &[a]).unwrap();
+
+        assert!(assignment.is_empty());
+        assert_eq!(cost, 0.into());
+    }
+
+    #[test]
+    fn square_matrix() {
+        // Given: Cost matrix
+        let costs: &[&[usize]] = &[&[9, 2, 7, 8], &[6, 4, 3, 7], &[5, 8, 1, 8], &[7, 6, 9, 4]];
+        let (graph, left_ids, right_ids) = graph_of(costs);
+
+        // When: Performing Hungarian algorithm.
+        let (assignment, cost) = Hungarian::init(&graph)
+            .execute(&graph, &left_ids, &right_ids)
+            .unwrap();
+
+        // Then:
+        assert_eq!(cost, 13.into());
+        assert_eq!(brute_force(costs, 0, &mut vec![false; 4]), 13);
+        assert_eq!(assignment.len(), 4);
+        for (left_id, right_id, edge_id) in assignment {
+            assert!(graph
+                .edge_between(left_id, right_id, edge_id)
+                .unwrap()
+                .is_some());
+        }
+    }
+
+    #[test]
+    fn rectangular_matrices() {
+        let matrices: [&[&[usize]]; 3] = [
+            &[&[3, 1, 4, 1], &[5, 9, 2, 6]],
+            &[&[10, 10, 1], &[1, 10, 10], &[10, 1, 10]],
+            &[&[0, 0, 0], &[7, 3, 5], &[2, 8, 1]],
+        ];
+
+        for costs in matrices.iter() {
+            // Given: Cost matrix
+            let (graph, left_ids, right_ids) = graph_of(costs);
+
+            // When: Performing Hungarian algorithm.
+            let (assignment, cost) = Hungarian::init(&graph)
+                .execute(&graph, &left_ids, &right_ids)
+                .unwrap();
+
+            // Then: Each left vertex is matched to a distinct right vertex with minimum total cost.
+            let expected = brute_force(costs, 0, &mut vec![false; right_ids.len()]);
+            assert_eq!(cost, expected.into());
+            assert_eq!(assignment.len(), left_ids.len());
+            for left_id in &left_ids {
+                assert_eq!(
+                    assignment
+                        .iter()
+                        .filter(|(src_id, _, _)| src_id == left_id)
+                        .count(),
+                    1
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn incomplete_bipartite_graph() {
+        // Given: Graph
+        //
+        //      a  --(1)--  c
+        //      b           d
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, c, 1.into());
+
+        // Then:
+        assert!(Hungarian::init(&graph)
+            .execute(&graph, &[a, b], &[c, d])
+            .is_err());
+        assert!(Hungarian::init(&graph)
+            .execute(&graph, &[a, b], &[c])
+            .is_err());
+        assert!(Hungarian::init(&graph)
+            .execute(&graph, &[a], &[d + 1])
+            .is_err());
+    }
+
+    #[test]
+    fn undirected_graph() {
+        // Given: Graph
+        //
+        //      a  --(5)--  c
+        //      |           |
+        //     (2)         (3)
+        //      |           |
+        //      d  --(9)--  b
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, c, 5.into());
+        let ad = graph.add_edge_unchecked(a, d, 2.into());
+        let bc = graph.add_edge_unchecked(b, c, 3.into());
+        graph.add_edge_unchecked(b, d, 9.into());
+
+        // When: Performing Hungarian algorithm.
+        let (assignment, cost) = Hungarian::init(&graph)
+            .execute(&graph, &[a, b], &[c, d])
+            .unwrap();
+
+        // Then:
+        assert_eq!(cost, 5.into());
+        assert!(assignment.contains(&(a, d, ad)));
+        assert!(assignment.contains(&(b, c, bc)));
+    }
+}
@@ -0,0 +1,3 @@
+mod hungarian;
+
+pub use hungarian::Hungarian;
@@ -0,0 +1,357 @@
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+use crate::algo::anytime::BudgetClock;
+use crate::algo::{AnytimeSolution, Budget};
+use crate::graph::{Edge, EdgeDir};
+use crate::provide::{Graph, IdMap, Neighbors, Vertices};
+
+/// Finds a maximum clique of a graph using branch and bound.
+///
+/// A clique is a set of vertices where every two of them are connected by an edge. In directed graphs, direction of the edges is ignored, and loops are ignored.
+/// At each step the candidates are colored greedily, and since vertices of a clique have different colors, the number of colors bounds the size of any clique among them.
+/// Candidates are tried in decreasing order of their color, and a branch is pruned when it can not beat the largest clique found so far(Tomita & Seki).
+///
+/// Running time is exponential in the worst case. Use [`execute_within`](crate::algo::MaxClique::execute_within) to get the largest clique found within a [`Budget`](crate::algo::Budget).
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::{Budget, MaxClique};
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b  ---  d
+/// //      |     /
+/// //      |   /
+/// //      | /
+/// //      c
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, a, 1.into());
+/// graph.add_edge_unchecked(b, d, 1.into());
+///
+/// let clique = MaxClique::init(&graph).execute_within(&graph, Budget::init().with_iteration_limit(100));
+///
+/// assert_eq!(clique.solution(), &vec![a, b, c]);
+/// assert!(clique.is_optimal());
+/// ```
+pub struct MaxClique {
+    id_map: IdMap,
+
+    // Neighbors of each vertex ignoring direction of the edges and loops, using virtual ids.
+    adjacents_of: Vec<HashSet<usize>>,
+}
+
+impl MaxClique {
+    pub fn init<W, E, Ty, G>(graph: &G) -> Self
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Neighbors + Vertices + Graph<W, E, Ty>,
+    {
+        let id_map = graph.continuous_id_map();
+        let vertex_count = graph.vertex_count();
+
+        let mut adjacents_of = vec![HashSet::new(); vertex_count];
+        for virt_id in 0..vertex_count {
+            for n_id in graph.neighbors_unchecked(id_map.real_id_of(virt_id)) {
+                let n_virt_id = id_map.virt_id_of(n_id);
+
+                if n_virt_id != virt_id {
+                    adjacents_of[virt_id].insert(n_virt_id);
+                    adjacents_of[n_virt_id].insert(virt_id);
+                }
+            }
+        }
+
+        MaxClique {
+            id_map,
+            adjacents_of,
+        }
+    }
+
+    // Colors `candidates` greedily, in their order.
+    // Returns candidates sorted by their color, and color of each of them. Colors start from one.
+    fn color_sort(&self, candidates: &[usize]) -> (Vec<usize>, Vec<usize>) {
+        let mut classes: Vec<Vec<usize>> = vec![];
+        for &virt_id in candidates {
+            let class = classes.iter_mut().find(|class| {
+                class
+                    .iter()
+                    .all(|member| !self.adjacents_of[virt_id].contains(member))
+            });
+
+            match class {
+                Some(class) => class.push(virt_id),
+                None => classes.push(vec![virt_id]),
+            }
+        }
+
+        let mut order = Vec::with_capacity(candidates.len());
+        let mut colors = Vec::with_capacity(candidates.len());
+        for (color, class) in classes.into_iter().enumerate() {
+            colors.extend(std::iter::repeat(color + 1).take(class.len()));
+            order.extend(class);
+        }
+
+        (order, colors)
+    }
+
+    // `candidates` are the vertices that are adjacent to all vertices of `clique`.
+    // If budget gets exhausted, bounds of the branches that are not explored are added to `unexplored_bound`.
+    fn expand(
+        &self,
+        candidates: &[usize],
+        clique: &mut Vec<usize>,
+        best: &mut Vec<usize>,
+        clock: &mut BudgetClock,
+        unexplored_bound: &mut usize,
+    ) {
+        let (order, colors) = self.color_sort(candidates);
+
+        for index in (0..order.len()).rev() {
+            // Vertices up to `index` are colored with at most `colors[index]` colors.
+            let bound = clique.len() + colors[index];
+            if bound <= best.len() {
+                return;
+            }
+
+            if !clock.tick() {
+                *unexplored_bound = (*unexplored_bound).max(bound);
+                return;
+            }
+
+            let virt_id = order[index];
+            let remaining = order[..index]
+                .iter()
+                .copied()
+                .filter(|c_virt_id| self.adjacents_of[virt_id].contains(c_virt_id))
+                .collect::<Vec<usize>>();
+
+            clique.push(virt_id);
+            if remaining.is_empty() {
+                if clique.len() > best.len() {
+                    *best = clique.clone();
+                }
+            } else {
+                self.expand(&remaining, clique, best, clock, unexplored_bound);
+            }
+            clique.pop();
+        }
+    }
+
+    /// # Arguments
+    /// `graph`: Graph to find its maximum clique.
+    ///
+    /// # Returns
+    /// Ids of the vertices in the clique(sorted).
+    ///
+    /// # Complexity
+    /// O(2^|V| * |V|<sup>2</sup>) in the worst case.
+    pub fn execute<W, E, Ty, G>(self, graph: &G) -> Vec<usize>
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Neighbors + Vertices + Graph<W, E, Ty>,
+    {
+        self.execute_within(graph, Budget::init()).into_solution()
+    }
+
+    /// Each node of the search tree uses one iteration of `budget`.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to find its maximum clique.
+    /// * `budget`: Limits the time and iterations of the search.
+    ///
+    /// # Returns
+    /// Ids of the vertices in the largest clique found(sorted), and its size.
+    /// Bound of the solution is an upper bound on the size of the maximum clique.
+    ///
+    /// # Complexity
+    /// O(2^|V| * |V|<sup>2</sup>) in the worst case.
+    pub fn execute_within<W, E, Ty, G>(
+        self,
+        graph: &G,
+        budget: Budget,
+    ) -> AnytimeSolution<Vec<usize>, usize>
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Neighbors + Vertices + Graph<W, E, Ty>,
+    {
+        // Coloring vertices in decreasing order of their degree uses fewer colors, which gives tighter bounds.
+        let mut candidates = (0..graph.vertex_count()).collect::<Vec<usize>>();
+        candidates.sort_by_key(|virt_id| Reverse(self.adjacents_of[*virt_id].len()));
+
+        let mut clock = budget.start();
+        let mut best = vec![];
+        let mut unexplored_bound = 0;
+        self.expand(
+            &candidates,
+            &mut vec![],
+            &mut best,
+            &mut clock,
+            &mut unexplored_bound,
+        );
+
+        let size = best.len();
+        let mut clique = best
+            .into_iter()
+            .map(|virt_id| self.id_map.real_id_of(virt_id))
+            .collect::<Vec<usize>>();
+        clique.sort_unstable();
+
+        AnytimeSolution::init(clique, size, size.max(unexplored_bound))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ListGraph, MatGraph, UndirectedEdge};
+    use crate::provide::Edges;
+    use crate::storage::{DiList, Mat};
+    use crate::util::seeded;
+
+    // Size of the maximum clique by checking all subsets of vertices.
+    fn brute_force(vertex_count: usize, edges: &[(usize, usize)]) -> usize {
+        let is_adjacent = |src: usize, dst: usize| {
+            edges
+                .iter()
+                .any(|edge| *edge == (src, dst) || *edge == (dst, src))
+        };
+
+        (0..1usize << vertex_count)
+            .filter(|subset| {
+                (0..vertex_count).all(|src| {
+                    (src + 1..vertex_count).all(|dst| {
+                        subset & (1 << src) == 0
+                            || subset & (1 << dst) == 0
+                            || is_adjacent(src, dst)
+                    })
+                })
+            })
+            .map(|subset| subset.count_ones() as usize)
+            .max()
+            .unwrap()
+    }
+
+    fn random_graph(seed: u64) -> (MatGraph<usize, UndirectedEdge>, Vec<(usize, usize)>) {
+        let mut rng = seeded(seed);
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let ids = (0..14).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+
+        let mut edges = vec![];
+        for src in 0..14 {
+            for dst in src + 1..14 {
+                if rng(10) < 6 {
+                    graph.add_edge_unchecked(ids[src], ids[dst], 1.into());
+                    edges.push((src, dst));
+                }
+            }
+        }
+
+        (graph, edges)
+    }
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let clique = MaxClique::init(&graph).execute_within(&graph, Budget::init());
+
+        assert!(clique.solution().is_empty());
+        assert!(clique.is_optimal());
+    }
+
+    #[test]
+    fn directed_graph_with_loop() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c
+        //      ^       |
+        //      '-------'
+        //
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, a, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, c, 1.into());
+
+        // When:
+        let clique = MaxClique::init(&graph).execute(&graph);
+
+        // Then: Loop does not make c adjacent to itself, and direction is ignored.
+        assert_eq!(clique.len(), 2);
+        assert!(clique.contains(&b));
+    }
+
+    #[test]
+    fn matches_brute_force_on_random_graphs() {
+        for seed in 0..5 {
+            // Given:
+            let (graph, edges) = random_graph(seed);
+
+            // When:
+            let clique = MaxClique::init(&graph).execute_within(&graph, Budget::init());
+
+            // Then:
+            assert!(clique.is_optimal());
+            assert_eq!(clique.value(), brute_force(14, &edges));
+            for src in clique.solution() {
+                for dst in clique.solution() {
+                    assert!(src == dst || graph.has_any_edge_unchecked(*src, *dst));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn stops_when_budget_is_exhausted() {
+        for seed in 0..5 {
+            // Given:
+            let (graph, edges) = random_graph(seed);
+            let optimal = brute_force(14, &edges);
+
+            // When: Search is stopped after a few nodes.
+            let clique = MaxClique::init(&graph)
+                .execute_within(&graph, Budget::init().with_iteration_limit(5));
+
+            // Then: Optimal size is between the size of the clique found and the bound.
+            assert!(clique.value() <= optimal);
+            assert!(optimal <= clique.bound());
+            assert_eq!(clique.solution().len(), clique.value());
+        }
+    }
+
+    #[test]
+    fn no_iteration() {
+        // Given: Triangle.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let ids = (0..3).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        graph.add_edge_unchecked(ids[0], ids[1], 1.into());
+        graph.add_edge_unchecked(ids[1], ids[2], 1.into());
+        graph.add_edge_unchecked(ids[2], ids[0], 1.into());
+
+        // When:
+        let clique =
+            MaxClique::init(&graph).execute_within(&graph, Budget::init().with_iteration_limit(0));
+
+        // Then: Bound comes from coloring the whole graph.
+        assert!(clique.solution().is_empty());
+        assert_eq!(clique.bound(), 3);
+        assert!(!clique.is_optimal());
+    }
+}
@@ -0,0 +1,281 @@
+use crate::algo::metrics::adjacency;
+use crate::provide::{Neighbors, Vertices};
+
+/// Partition of the vertices of a graph into a densely connected core and a sparse periphery.
+pub struct CorePeriphery {
+    core: Vec<usize>,
+    periphery: Vec<usize>,
+    correlation: f64,
+}
+
+impl CorePeriphery {
+    /// # Returns
+    /// Id of the core vertices, sorted by their degree in descending order.
+    pub fn core(&self) -> &Vec<usize> {
+        &self.core
+    }
+
+    /// # Returns
+    /// Id of the periphery vertices, sorted by their degree in descending order.
+    pub fn periphery(&self) -> &Vec<usize> {
+        &self.periphery
+    }
+
+    /// # Returns
+    /// Correlation of the graph with the ideal core–periphery pattern, in range of -1 to 1.
+    pub fn correlation(&self) -> f64 {
+        self.correlation
+    }
+}
+
+/// Fits a discrete core–periphery model to the graph.
+///
+/// In the ideal pattern of Borgatti and Everett, every two core vertices are adjacent and no two periphery vertices are adjacent.
+/// Edges between core and periphery are not constrained, so pairs of a core and a periphery vertex are left out of the fit.
+/// Quality of a partition is the correlation between the adjacency of the remaining pairs and the ideal pattern.
+///
+/// Instead of searching all partitions, vertices are sorted by their degree and the core is chosen among the prefixes of this order.
+/// So the fit is exact when core vertices are the ones with the highest degree, which is the case for most graphs with a core–periphery structure.
+/// On ties, the smallest core is chosen.
+///
+/// Directions and weights of the edges are ignored, parallel edges are counted once and loops are ignored.
+///
+/// # Arguments
+/// `graph`: Graph to fit the model to.
+///
+/// # Returns
+/// * `Some`: Containing the best partition found.
+/// * `None`: If correlation is not defined for any partition, which happens when graph has less than four vertices, or it is empty or complete.
+///
+/// # Complexity
+/// O(|V|.log(|V|) + |E|)
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::metrics::core_periphery;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      d  ---  a  ---  b  ---  e
+/// //               \     /
+/// //                \   /
+/// //                  c  ---  f
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// let e = graph.add_vertex();
+/// let f = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, a, 1.into());
+/// graph.add_edge_unchecked(a, d, 1.into());
+/// graph.add_edge_unchecked(b, e, 1.into());
+/// graph.add_edge_unchecked(c, f, 1.into());
+///
+/// let partition = core_periphery(&graph).unwrap();
+///
+/// assert_eq!(partition.core(), &vec![a, b, c]);
+/// assert_eq!(partition.periphery(), &vec![d, e, f]);
+/// assert_eq!(partition.correlation(), 1.0);
+/// ```
+pub fn core_periphery<G: Neighbors + Vertices>(graph: &G) -> Option<CorePeriphery> {
+    let (id_map, adjacent_to) = adjacency(graph);
+    let vertex_count = adjacent_to.len();
+
+    let mut order = (0..vertex_count).collect::<Vec<usize>>();
+    order.sort_by_key(|virt_id| std::cmp::Reverse(adjacent_to[*virt_id].len()));
+
+    // Number of edges among core vertices and among periphery vertices.
+    let mut core_edge_count = 0;
+    let mut periphery_edge_count = adjacent_to
+        .iter()
+        .map(|adjacents| adjacents.len())
+        .sum::<usize>()
+        / 2;
+
+    let mut is_core = vec![false; vertex_count];
+    let mut best: Option<(usize, f64)> = None;
+    for (index, virt_id) in order.iter().enumerate() {
+        let core_adjacent_count = adjacent_to[*virt_id]
+            .iter()
+            .filter(|dst_id| is_core[**dst_id])
+            .count();
+
+        core_edge_count += core_adjacent_count;
+        periphery_edge_count -= adjacent_to[*virt_id].len() - core_adjacent_count;
+        is_core[*virt_id] = true;
+
+        let core_size = index + 1;
+        let periphery_size = vertex_count - core_size;
+        if let Some(correlation) = correlation(
+            core_edge_count,
+            core_size * (core_size.saturating_sub(1)) / 2,
+            periphery_edge_count,
+            periphery_size * (periphery_size.saturating_sub(1)) / 2,
+        ) {
+            match best {
+                Some((_, best_correlation)) if best_correlation >= correlation => {}
+                _ => best = Some((core_size, correlation)),
+            }
+        }
+    }
+
+    best.map(|(core_size, correlation)| {
+        let real_ids = order
+            .into_iter()
+            .map(|virt_id| id_map.real_id_of(virt_id))
+            .collect::<Vec<usize>>();
+
+        CorePeriphery {
+            core: real_ids[..core_size].to_vec(),
+            periphery: real_ids[core_size..].to_vec(),
+            correlation,
+        }
+    })
+}
+
+// Returns the phi coefficient between adjacency of the pairs and the ideal pattern, in which core pairs are adjacent and periphery pairs are not.
+fn correlation(
+    core_edge_count: usize,
+    core_pair_count: usize,
+    periphery_edge_count: usize,
+    periphery_pair_count: usize,
+) -> Option<f64> {
+    let edge_count = core_edge_count + periphery_edge_count;
+    let non_edge_count = core_pair_count + periphery_pair_count - edge_count;
+
+    let denominator = (edge_count as f64
+        * non_edge_count as f64
+        * core_pair_count as f64
+        * periphery_pair_count as f64)
+        .sqrt();
+
+    if denominator == 0.0 {
+        None
+    } else {
+        let core_non_edge_count = core_pair_count - core_edge_count;
+        let periphery_non_edge_count = periphery_pair_count - periphery_edge_count;
+
+        Some(
+            (core_edge_count as f64 * periphery_non_edge_count as f64
+                - core_non_edge_count as f64 * periphery_edge_count as f64)
+                / denominator,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::*;
+    use crate::storage::{DiMat, Mat};
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        assert!(core_periphery(&graph).is_none());
+    }
+
+    #[test]
+    fn complete_graph() {
+        // Given: Graph
+        //
+        //      a  ---  b
+        //      |  \ /  |
+        //      |  / \  |
+        //      d  ---  c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let vertices = (0..4).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        for i in 0..4 {
+            for j in i + 1..4 {
+                graph.add_edge_unchecked(vertices[i], vertices[j], 1.into());
+            }
+        }
+
+        // When:
+        let partition = core_periphery(&graph);
+
+        // Then: There is no non-adjacent pair, so correlation is not defined.
+        assert!(partition.is_none());
+    }
+
+    #[test]
+    fn imperfect_core() {
+        // Given: Graph
+        //
+        //      e  ---  a  ---  b  ---  f
+        //              |       |
+        //              |       |
+        //      h  ---  d  ---  c  ---  g
+        //              |
+        //              i
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let leaves = (0..5).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+        graph.add_edge_unchecked(d, a, 1.into());
+        graph.add_edge_unchecked(a, leaves[0], 1.into());
+        graph.add_edge_unchecked(b, leaves[1], 1.into());
+        graph.add_edge_unchecked(c, leaves[2], 1.into());
+        graph.add_edge_unchecked(d, leaves[3], 1.into());
+        graph.add_edge_unchecked(d, leaves[4], 1.into());
+
+        // When:
+        let partition = core_periphery(&graph).unwrap();
+
+        // Then: Core has 4 of 6 possible edges and periphery has none of 10 possible edges.
+        let mut core = partition.core().clone();
+        core.sort_unstable();
+        assert_eq!(core, vec![a, b, c, d]);
+        assert_eq!(partition.periphery().len(), 5);
+        assert!(
+            (partition.correlation() - 40.0 / (4.0 * 12.0 * 6.0 * 10.0_f64).sqrt()).abs() < 1e-9
+        );
+    }
+
+    #[test]
+    fn directed_graph() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c  -->  a
+        //      |       |       |
+        //      v       v       v
+        //      d       e       f
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, a, 1.into());
+        graph.add_edge_unchecked(a, d, 1.into());
+        graph.add_edge_unchecked(b, e, 1.into());
+        graph.add_edge_unchecked(c, f, 1.into());
+
+        // When:
+        let partition = core_periphery(&graph).unwrap();
+
+        // Then: Directions are ignored.
+        assert_eq!(partition.core(), &vec![a, b, c]);
+        assert_eq!(partition.correlation(), 1.0);
+    }
+}
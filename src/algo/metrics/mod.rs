@@ -0,0 +1,28 @@
+mod core_periphery;
+mod rich_club;
+
+pub use core_periphery::{core_periphery, CorePeriphery};
+pub use rich_club::rich_club;
+
+use std::collections::HashSet;
+
+use crate::provide::{IdMap, Neighbors, Vertices};
+
+// Returns the continuous id map of the graph, and virtual id of the distinct vertices adjacent to each vertex.
+// Loops are ignored. In directed graphs, both successors and predecessors of a vertex are adjacent to it.
+pub(crate) fn adjacency<G: Neighbors + Vertices>(graph: &G) -> (IdMap, Vec<HashSet<usize>>) {
+    let id_map = graph.continuous_id_map();
+
+    let mut adjacent_to = vec![HashSet::new(); graph.vertex_count()];
+    for src_id in 0..graph.vertex_count() {
+        for dst_id in graph.neighbors_unchecked(id_map.real_id_of(src_id)) {
+            let dst_id = id_map.virt_id_of(dst_id);
+            if src_id != dst_id {
+                adjacent_to[src_id].insert(dst_id);
+                adjacent_to[dst_id].insert(src_id);
+            }
+        }
+    }
+
+    (id_map, adjacent_to)
+}
@@ -0,0 +1,140 @@
+use crate::algo::metrics::adjacency;
+use crate::provide::{Neighbors, Vertices};
+
+/// Computes the rich-club coefficient of the graph for degree `k`.
+///
+/// Rich-club coefficient is the density of the subgraph induced by vertices with degree more than `k`.
+/// So it is 2 * E<sub>k</sub> / (N<sub>k</sub> * (N<sub>k</sub> - 1)), where N<sub>k</sub> is the number of vertices with degree more than `k` and E<sub>k</sub> is the number of edges between them.
+/// A coefficient that grows towards one as `k` grows indicates that hubs of the graph are densely connected to each other.
+///
+/// Directions and weights of the edges are ignored, parallel edges are counted once and loops are ignored.
+///
+/// # Arguments
+/// * `graph`: Graph to compute its rich-club coefficient.
+/// * `k`: Vertices with degree more than `k` are members of the rich club.
+///
+/// # Returns
+/// * `Some`: Containing the rich-club coefficient.
+/// * `None`: If less than two vertices have degree more than `k`.
+///
+/// # Complexity
+/// O(|V| + |E|)
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::metrics::rich_club;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b  ---  c  ---  d
+/// //      |               |
+/// //      '---------------'
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+/// graph.add_edge_unchecked(a, c, 1.into());
+///
+/// // a, b and c have degree more than one, and they form a triangle.
+/// assert_eq!(rich_club(&graph, 1), Some(1.0));
+/// assert_eq!(rich_club(&graph, 3), None);
+/// ```
+pub fn rich_club<G: Neighbors + Vertices>(graph: &G, k: usize) -> Option<f64> {
+    let (_, adjacent_to) = adjacency(graph);
+
+    let is_rich = adjacent_to
+        .iter()
+        .map(|adjacents| adjacents.len() > k)
+        .collect::<Vec<bool>>();
+
+    let rich_count = is_rich.iter().filter(|is_rich| **is_rich).count();
+    if rich_count < 2 {
+        return None;
+    }
+
+    // Each edge between two rich vertices is counted from both of its end points.
+    let twice_edge_count = adjacent_to
+        .iter()
+        .enumerate()
+        .filter(|(virt_id, _)| is_rich[*virt_id])
+        .map(|(_, adjacents)| adjacents.iter().filter(|dst_id| is_rich[**dst_id]).count())
+        .sum::<usize>();
+
+    Some(twice_edge_count as f64 / (rich_count * (rich_count - 1)) as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::*;
+    use crate::storage::{DiMat, Mat};
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        assert_eq!(rich_club(&graph, 0), None);
+    }
+
+    #[test]
+    fn clique_with_leaves() {
+        // Given: Graph
+        //
+        //      e  ---  a  ---  b  ---  f
+        //              |  \ /  |
+        //              |  / \  |
+        //      h  ---  d  ---  c  ---  g
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let core = (0..4).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        for i in 0..4 {
+            for j in i + 1..4 {
+                graph.add_edge_unchecked(core[i], core[j], 1.into());
+            }
+
+            let leaf = graph.add_vertex();
+            graph.add_edge_unchecked(core[i], leaf, 1.into());
+        }
+
+        // When:
+        let coefficients = (0..5)
+            .map(|k| rich_club(&graph, k))
+            .collect::<Vec<Option<f64>>>();
+
+        // Then: 10 of 28 possible edges exist among all vertices, and core vertices form a clique.
+        assert_eq!(coefficients[0], Some(20.0 / 56.0));
+        assert_eq!(coefficients[1], Some(1.0));
+        assert_eq!(coefficients[3], Some(1.0));
+        assert_eq!(coefficients[4], None);
+    }
+
+    #[test]
+    fn directed_graph() {
+        // Given: Graph
+        //
+        //      a  <->  b  -->  c
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, a, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // When:
+        let coefficient = rich_club(&graph, 0);
+
+        // Then: Directions are ignored and a <-> b counts as a single edge.
+        assert_eq!(coefficient, Some(2.0 / 3.0));
+    }
+}
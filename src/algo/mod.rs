@@ -1,19 +1,84 @@
+mod anytime;
+mod block_cut_tree;
+mod canonical_form;
 mod cc;
+mod context;
+pub mod centrality;
+mod coloring;
+mod cycles;
+mod dynamic_topological_sort;
+mod earliest_arrival;
+mod execution_control;
+mod elimination_ordering;
+mod flow;
 mod has_cycle;
+mod independent_set;
+mod layering;
+pub mod link_prediction;
+mod matching;
+mod max_clique;
+pub mod metrics;
+mod motifs;
 mod mst;
+pub mod ops;
+mod reachability;
+mod routing;
 mod shortest_path;
+pub mod similarity;
+mod spanner;
+mod sparsify;
+mod spectral;
 mod topological_sort;
 mod traversal;
+pub mod tree;
+mod tree_decomposition;
+mod tsp;
 mod vertex_edge_cut;
+pub mod walk;
 mod eulerian;
 
-pub use cc::{ConnectedComponents, TarjanSCC};
+pub use anytime::{AnytimeSolution, Budget};
+pub use block_cut_tree::{BlockCutNode, BlockCutTree};
+pub use canonical_form::{CanonicalCode, CanonicalForm, CanonicalLabeling};
+pub use context::AlgoContext;
+pub use cc::{ComponentSet, ConnectedComponents, Connectivity, DynamicConnectivity, TarjanSCC, WeaklyConnectedComponents};
+pub use coloring::{EdgeColoring, VertexColoring};
+pub use cycles::{Girth, MinimumCycleBasis};
+pub use dynamic_topological_sort::DynamicTopologicalSort;
+pub use earliest_arrival::{EarliestArrival, TemporalPaths};
+pub use execution_control::{CancellationToken, ExecutionControl, Progress};
+pub use elimination_ordering::{EliminationHeuristic, EliminationOrder, EliminationOrdering};
+pub use flow::{FlowSolution, MaxFlow};
 pub use has_cycle::HasCycle;
-pub use mst::Kruskal;
+pub use independent_set::{BranchAndBoundIndependentSet, TreeIndependentSet};
+pub use layering::{Layering, LayeringMethod, Layers};
+pub use matching::Hungarian;
+pub use max_clique::MaxClique;
+pub use motifs::{MotifCounter, MotifSize};
+pub use mst::{BottleneckSpanningTree, Kruskal};
+pub use ops::ego_network;
+pub use reachability::Reachability;
+pub use routing::{ContractionHierarchy, TurnRestrictedDijkstra};
+pub use shortest_path::AStar;
 pub use shortest_path::BellmanFord;
+pub use shortest_path::BlockedFloydWarshall;
+pub use shortest_path::{Bottleneck, BottleneckPath};
+pub use shortest_path::ConstrainedShortestPath;
+pub use shortest_path::Dial;
 pub use shortest_path::Dijkstra;
+pub use shortest_path::DistanceMatrix;
 pub use shortest_path::FloydWarshall;
+pub use shortest_path::PathTracer;
+pub use shortest_path::TimeDependentDijkstra;
+pub use shortest_path::UnweightedBfs;
+pub use shortest_path::WidestPath;
+pub use shortest_path::ZeroOneBfs;
+pub use spanner::GreedySpanner;
+pub use sparsify::{Sparsifier, SparsifyMethod};
+pub use spectral::{SpectralKind, SpectralMatrix};
 pub use topological_sort::TopologicalSort;
-pub use traversal::{Bfs, Color, Dfs, DfsListener};
+pub use traversal::{Bfs, BfsListener, Color, Control, Dfs, DfsListener, IddfsIter};
+pub use tree_decomposition::{Decomposition, TreeDecomposition};
+pub use tsp::Tsp;
 pub use vertex_edge_cut::VertexEdgeCut;
 pub use eulerian::Eulerian;
\ No newline at end of file
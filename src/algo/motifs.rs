@@ -0,0 +1,407 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::graph::{Edge, EdgeDir};
+use crate::provide::{Graph, Neighbors, Vertices};
+
+/// Number of vertices of the motifs to count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MotifSize {
+    Three,
+    Four,
+}
+
+impl MotifSize {
+    fn vertex_count(&self) -> usize {
+        match self {
+            MotifSize::Three => 3,
+            MotifSize::Four => 4,
+        }
+    }
+}
+
+/// Counts occurrences of connected subgraph patterns(motifs) with three or four vertices.
+///
+/// Each connected set of vertices with the given size is enumerated exactly once using the ESU algorithm, and the subgraph induced by it
+/// is counted under the canonical id of its pattern. In directed graphs, a set of vertices is connected if it's weakly connected.
+///
+/// Canonical id of a pattern with k vertices is computed by encoding each edge (i, j) as bit i * k + j of a number and taking the minimum
+/// of this number over all orderings of the vertices. So isomorphic subgraphs get the same id. Use [`canonical_id`](crate::algo::MotifCounter::canonical_id)
+/// to find the id of a pattern.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::{MotifCounter, MotifSize};
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b  ---  d
+/// //      |     /
+/// //      |   /
+/// //      | /
+/// //      c
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, a, 1.into());
+/// graph.add_edge_unchecked(b, d, 1.into());
+///
+/// let counts = MotifCounter::init(&graph).execute(&graph, MotifSize::Three);
+///
+/// let triangle = MotifCounter::canonical_id(MotifSize::Three, &[(0, 1), (1, 2), (2, 0)], false);
+/// let path = MotifCounter::canonical_id(MotifSize::Three, &[(0, 1), (1, 2)], false);
+/// assert_eq!(counts[&triangle], 1);
+/// assert_eq!(counts[&path], 2);
+/// ```
+pub struct MotifCounter {
+    // Neighbors of each vertex ignoring direction of the edges, using virtual ids.
+    adjacents_of: Vec<HashSet<usize>>,
+
+    // Vertices that each vertex has an edge to, using virtual ids.
+    out_neighbors: Vec<HashSet<usize>>,
+
+    is_directed: bool,
+}
+
+impl MotifCounter {
+    pub fn init<G, W, E, Ty>(graph: &G) -> Self
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Graph<W, E, Ty> + Vertices + Neighbors,
+    {
+        let id_map = graph.continuous_id_map();
+        let vertex_count = graph.vertex_count();
+
+        let mut adjacents_of = vec![HashSet::new(); vertex_count];
+        let mut out_neighbors = vec![HashSet::new(); vertex_count];
+        for virt_id in 0..vertex_count {
+            for n_id in graph.neighbors_unchecked(id_map.real_id_of(virt_id)) {
+                let n_virt_id = id_map.virt_id_of(n_id);
+
+                out_neighbors[virt_id].insert(n_virt_id);
+                adjacents_of[virt_id].insert(n_virt_id);
+                adjacents_of[n_virt_id].insert(virt_id);
+            }
+        }
+
+        MotifCounter {
+            adjacents_of,
+            out_neighbors,
+            is_directed: Ty::is_directed(),
+        }
+    }
+
+    // All orderings of 0..`k`.
+    fn permutations_of(k: usize) -> Vec<Vec<usize>> {
+        if k == 0 {
+            return vec![vec![]];
+        }
+
+        let mut permutations = vec![];
+        for permutation in Self::permutations_of(k - 1) {
+            for index in 0..k {
+                let mut permutation = permutation.clone();
+                permutation.insert(index, k - 1);
+                permutations.push(permutation);
+            }
+        }
+
+        permutations
+    }
+
+    // Minimum code of the pattern over all orderings of its vertices.
+    fn min_code(k: usize, edges: &[(usize, usize)], permutations: &[Vec<usize>]) -> usize {
+        permutations
+            .iter()
+            .map(|permutation| {
+                edges.iter().fold(0, |code, (src, dst)| {
+                    code | 1 << (permutation[*src] * k + permutation[*dst])
+                })
+            })
+            .min()
+            .unwrap()
+    }
+
+    /// # Arguments
+    /// * `size`: Number of vertices of the pattern.
+    /// * `edges`: Edges of the pattern in the format of: (`src`, `dst`), where vertices are numbered from 0 to size - 1.
+    /// * `is_directed`: Whether pattern is directed. For undirected patterns, order of end points of the edges does not matter.
+    ///
+    /// # Returns
+    /// Canonical id of the pattern, which is the same for all isomorphic patterns.
+    ///
+    /// # Panics
+    /// If an end point of an edge is not less than `size`.
+    pub fn canonical_id(size: MotifSize, edges: &[(usize, usize)], is_directed: bool) -> usize {
+        let k = size.vertex_count();
+
+        let mut all_edges = edges.to_vec();
+        if !is_directed {
+            all_edges.extend(edges.iter().map(|(src, dst)| (*dst, *src)));
+        }
+
+        if let Some((src, dst)) = all_edges.iter().find(|(src, dst)| *src >= k || *dst >= k) {
+            panic!(
+                "Edge ({}, {}) does not fit in a pattern with {} vertices",
+                src, dst, k
+            );
+        }
+
+        Self::min_code(k, &all_edges, &Self::permutations_of(k))
+    }
+
+    // Extends `subgraph` with vertices of `extension`, only using vertices with virtual id greater than `root`.
+    fn extend(
+        &self,
+        k: usize,
+        subgraph: &mut Vec<usize>,
+        mut extension: Vec<usize>,
+        root: usize,
+        on_found: &mut dyn FnMut(&[usize]),
+    ) {
+        if subgraph.len() == k {
+            on_found(subgraph);
+            return;
+        }
+
+        while let Some(w) = extension.pop() {
+            // Vertices that are adjacent to w but not to the current subgraph, so each subgraph is found only once.
+            let mut new_extension = extension.clone();
+            for n_id in &self.adjacents_of[w] {
+                let is_exclusive = !subgraph.contains(n_id)
+                    && subgraph
+                        .iter()
+                        .all(|virt_id| !self.adjacents_of[*virt_id].contains(n_id));
+
+                if *n_id > root && is_exclusive && !new_extension.contains(n_id) {
+                    new_extension.push(*n_id);
+                }
+            }
+
+            subgraph.push(w);
+            self.extend(k, subgraph, new_extension, root, on_found);
+            subgraph.pop();
+        }
+    }
+
+    /// # Arguments
+    /// * `graph`: Graph to count motifs in.
+    /// * `size`: Number of vertices of the motifs.
+    ///
+    /// # Returns
+    /// Number of occurrences of each motif that appears in the graph at least once, keyed by its canonical id.
+    ///
+    /// # Complexity
+    /// O(|S| * k!), where |S| is the number of connected sets of vertices with size k.
+    pub fn execute<G, W, E, Ty>(self, _: &G, size: MotifSize) -> HashMap<usize, usize>
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Graph<W, E, Ty> + Vertices + Neighbors,
+    {
+        let k = size.vertex_count();
+        let permutations = Self::permutations_of(k);
+
+        let mut counts = HashMap::new();
+        let mut on_found = |vertices: &[usize]| {
+            let mut edges = vec![];
+            for (i, src_id) in vertices.iter().enumerate() {
+                for (j, dst_id) in vertices.iter().enumerate() {
+                    if self.out_neighbors[*src_id].contains(dst_id) {
+                        edges.push((i, j));
+
+                        if !self.is_directed {
+                            edges.push((j, i));
+                        }
+                    }
+                }
+            }
+
+            *counts
+                .entry(Self::min_code(k, &edges, &permutations))
+                .or_insert(0) += 1;
+        };
+
+        for root in 0..self.adjacents_of.len() {
+            let extension = self.adjacents_of[root]
+                .iter()
+                .copied()
+                .filter(|n_id| *n_id > root)
+                .collect();
+
+            self.extend(k, &mut vec![root], extension, root, &mut on_found);
+        }
+
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::*;
+    use crate::storage::{DiMat, Mat};
+
+    #[test]
+    fn canonical_id_of_isomorphic_patterns() {
+        // Path a --- b --- c is the same as b --- a --- c.
+        assert_eq!(
+            MotifCounter::canonical_id(MotifSize::Three, &[(0, 1), (1, 2)], false),
+            MotifCounter::canonical_id(MotifSize::Three, &[(1, 0), (0, 2)], false)
+        );
+
+        // Directed cycle and feed forward loop are different.
+        assert_ne!(
+            MotifCounter::canonical_id(MotifSize::Three, &[(0, 1), (1, 2), (2, 0)], true),
+            MotifCounter::canonical_id(MotifSize::Three, &[(0, 1), (1, 2), (0, 2)], true)
+        );
+
+        // Star and path with four vertices are different.
+        assert_ne!(
+            MotifCounter::canonical_id(MotifSize::Four, &[(0, 1), (0, 2), (0, 3)], false),
+            MotifCounter::canonical_id(MotifSize::Four, &[(0, 1), (1, 2), (2, 3)], false)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn canonical_id_of_invalid_pattern() {
+        MotifCounter::canonical_id(MotifSize::Three, &[(0, 3)], false);
+    }
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let counts = MotifCounter::init(&graph).execute(&graph, MotifSize::Three);
+
+        assert!(counts.is_empty());
+    }
+
+    #[test]
+    fn complete_graph() {
+        // Given: Complete graph with 5 vertices.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let ids = (0..5).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        for i in 0..5 {
+            for j in i + 1..5 {
+                graph.add_edge_unchecked(ids[i], ids[j], 1.into());
+            }
+        }
+
+        // When: Counting motifs.
+        let three_counts = MotifCounter::init(&graph).execute(&graph, MotifSize::Three);
+        let four_counts = MotifCounter::init(&graph).execute(&graph, MotifSize::Four);
+
+        // Then: Each set of vertices induces a complete graph.
+        let triangle =
+            MotifCounter::canonical_id(MotifSize::Three, &[(0, 1), (1, 2), (2, 0)], false);
+        let k4 = MotifCounter::canonical_id(
+            MotifSize::Four,
+            &[(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)],
+            false,
+        );
+        assert_eq!(three_counts.len(), 1);
+        assert_eq!(three_counts[&triangle], 10);
+        assert_eq!(four_counts.len(), 1);
+        assert_eq!(four_counts[&k4], 5);
+    }
+
+    #[test]
+    fn undirected_graph() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c
+        //      |       |
+        //      d  -----'       e  ---  f
+        //      |
+        //      g
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        let g = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(a, d, 1.into());
+        graph.add_edge_unchecked(b, d, 1.into());
+        graph.add_edge_unchecked(d, g, 1.into());
+        graph.add_edge_unchecked(e, f, 1.into());
+
+        // When: Counting motifs with three vertices.
+        let counts = MotifCounter::init(&graph).execute(&graph, MotifSize::Three);
+
+        // Then:
+        let triangle =
+            MotifCounter::canonical_id(MotifSize::Three, &[(0, 1), (1, 2), (2, 0)], false);
+        let path = MotifCounter::canonical_id(MotifSize::Three, &[(0, 1), (1, 2)], false);
+        assert_eq!(counts[&triangle], 1);
+        // a-b-c, d-b-c, b-d-g, a-d-g
+        assert_eq!(counts[&path], 4);
+        assert_eq!(counts.len(), 2);
+
+        // When: Counting motifs with four vertices.
+        let counts = MotifCounter::init(&graph).execute(&graph, MotifSize::Four);
+
+        // Then:
+        let tailed_triangle =
+            MotifCounter::canonical_id(MotifSize::Four, &[(0, 1), (1, 2), (2, 0), (0, 3)], false);
+        let path = MotifCounter::canonical_id(MotifSize::Four, &[(0, 1), (1, 2), (2, 3)], false);
+        // {a, b, d, c} and {a, b, d, g}
+        assert_eq!(counts[&tailed_triangle], 2);
+        // {c, b, d, g}
+        assert_eq!(counts[&path], 1);
+        assert_eq!(counts.values().sum::<usize>(), 3);
+    }
+
+    #[test]
+    fn directed_graph() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c  -->  d
+        //      |               ^       |
+        //      '---------------'       |
+        //              ^               |
+        //              '---------------'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(a, c, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+        graph.add_edge_unchecked(d, b, 1.into());
+
+        // When: Counting motifs with three vertices.
+        let counts = MotifCounter::init(&graph).execute(&graph, MotifSize::Three);
+
+        // Then:
+        let feed_forward =
+            MotifCounter::canonical_id(MotifSize::Three, &[(0, 1), (1, 2), (0, 2)], true);
+        let cycle = MotifCounter::canonical_id(MotifSize::Three, &[(0, 1), (1, 2), (2, 0)], true);
+        let chain = MotifCounter::canonical_id(MotifSize::Three, &[(0, 1), (1, 2)], true);
+        // {a, b, c}
+        assert_eq!(counts[&feed_forward], 1);
+        // {b, c, d}
+        assert_eq!(counts[&cycle], 1);
+        // {a, c, d} and {a, b, d}: a --> c --> d and a --> b <-- d
+        assert_eq!(counts[&chain], 1);
+        assert_eq!(counts.values().sum::<usize>(), 4);
+    }
+}
@@ -0,0 +1,169 @@
+use magnitude::Magnitude;
+
+use super::Kruskal;
+use crate::algo::Bottleneck;
+use crate::graph::{subgraph::SpanningTreeSubgraph, Edge, UndirectedEdge};
+use crate::provide::{self, Edges};
+
+/// Finds a bottleneck spanning tree(or forest) of an undirected graph: a spanning tree whose heaviest edge is as light as possible(minimum bottleneck spanning tree),
+/// or whose lightest edge is as heavy as possible(maximum bottleneck spanning tree).
+///
+/// Every minimum spanning tree is a minimum bottleneck spanning tree, and every maximum spanning tree is a maximum bottleneck spanning tree.
+/// Moreover, path between every two vertices in these trees is a bottleneck path between them, just like the ones found by [`BottleneckPath`](crate::algo::BottleneckPath).
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::{Bottleneck, BottleneckSpanningTree};
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  --(1)--  b
+/// //      |           |
+/// //     (4)         (3)
+/// //      |           |
+/// //      c  --(2)--  d
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, d, 3.into());
+/// graph.add_edge_unchecked(a, c, 4.into());
+/// graph.add_edge_unchecked(c, d, 2.into());
+///
+/// let (tree, bottleneck) = BottleneckSpanningTree::init(&graph, Bottleneck::MinimizeMaximum).execute(&graph);
+/// assert_eq!(tree.edges_count(), 3);
+/// assert_eq!(bottleneck, Some(3.into()));
+///
+/// let (tree, bottleneck) = BottleneckSpanningTree::init(&graph, Bottleneck::MaximizeMinimum).execute(&graph);
+/// assert_eq!(tree.edges_count(), 3);
+/// assert_eq!(bottleneck, Some(2.into()));
+/// ```
+pub struct BottleneckSpanningTree {
+    kruskal: Kruskal,
+    bottleneck: Bottleneck,
+}
+
+impl BottleneckSpanningTree {
+    /// # Arguments
+    /// * `graph`: Graph to find its bottleneck spanning tree.
+    /// * `bottleneck`: `MinimizeMaximum` for minimum bottleneck spanning tree, or `MaximizeMinimum` for maximum bottleneck spanning tree.
+    ///
+    /// # Returns
+    /// Initialized algorithm.
+    pub fn init<G, W: Ord, E: Edge<W>>(graph: &G, bottleneck: Bottleneck) -> Self
+    where
+        G: provide::Vertices + provide::Edges<W, E> + provide::Graph<W, E, UndirectedEdge>,
+    {
+        BottleneckSpanningTree {
+            kruskal: Kruskal::init(graph),
+            bottleneck,
+        }
+    }
+
+    /// # Arguments
+    /// `graph`: Graph to find its bottleneck spanning tree.
+    ///
+    /// # Returns
+    /// * Subgraph containing edges of the spanning forest.
+    /// * Weight of the heaviest(or the lightest) edge of the forest, or `None` if it has no edges.
+    ///
+    /// # Complexity
+    /// O(|E| * log(|E|) + |V|^2)
+    pub fn execute<'a, G, W: Copy + Ord, E: Edge<W>>(
+        self,
+        graph: &'a G,
+    ) -> (
+        SpanningTreeSubgraph<'a, W, E, UndirectedEdge, G>,
+        Option<Magnitude<W>>,
+    )
+    where
+        G: provide::Edges<W, E>
+            + provide::Neighbors
+            + provide::Vertices
+            + provide::Graph<W, E, UndirectedEdge>,
+    {
+        let mut edges = graph.edges();
+
+        match self.bottleneck {
+            Bottleneck::MinimizeMaximum => {
+                edges.sort_by(|(_, _, e1), (_, _, e2)| e1.get_weight().cmp(e2.get_weight()))
+            }
+            Bottleneck::MaximizeMinimum => {
+                edges.sort_by(|(_, _, e1), (_, _, e2)| e2.get_weight().cmp(e1.get_weight()))
+            }
+        }
+
+        let tree = self.kruskal.spanning_tree(graph, edges);
+
+        let weights = tree
+            .edges()
+            .into_iter()
+            .map(|(_, _, edge)| *edge.get_weight());
+        let bottleneck = match self.bottleneck {
+            Bottleneck::MinimizeMaximum => weights.max(),
+            Bottleneck::MaximizeMinimum => weights.min(),
+        };
+
+        (tree, bottleneck)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::*;
+    use crate::storage::Mat;
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let (tree, bottleneck) =
+            BottleneckSpanningTree::init(&graph, Bottleneck::MinimizeMaximum).execute(&graph);
+
+        assert_eq!(tree.edges_count(), 0);
+        assert!(bottleneck.is_none());
+    }
+
+    #[test]
+    fn forest() {
+        // Given: Graph
+        //
+        //      a  --(5)--  b  --(2)--  c       d  --(7)--  e
+        //      |                       |
+        //      '----------(4)----------'
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let ids: Vec<usize> = (0..5).map(|_| graph.add_vertex()).collect();
+        let ab = graph.add_edge_unchecked(ids[0], ids[1], 5.into());
+        let bc = graph.add_edge_unchecked(ids[1], ids[2], 2.into());
+        let ac = graph.add_edge_unchecked(ids[0], ids[2], 4.into());
+        let de = graph.add_edge_unchecked(ids[3], ids[4], 7.into());
+
+        // When:
+        let (minimum_tree, minimum_bottleneck) =
+            BottleneckSpanningTree::init(&graph, Bottleneck::MinimizeMaximum).execute(&graph);
+        let (maximum_tree, maximum_bottleneck) =
+            BottleneckSpanningTree::init(&graph, Bottleneck::MaximizeMinimum).execute(&graph);
+
+        // Then: Each tree of the forest spans one component.
+        assert_eq!(minimum_tree.edges_count(), 3);
+        assert!(minimum_tree.contains_edge(bc));
+        assert!(minimum_tree.contains_edge(ac));
+        assert!(minimum_tree.contains_edge(de));
+        assert_eq!(minimum_bottleneck, Some(7.into()));
+
+        assert_eq!(maximum_tree.edges_count(), 3);
+        assert!(maximum_tree.contains_edge(ab));
+        assert!(maximum_tree.contains_edge(ac));
+        assert!(maximum_tree.contains_edge(de));
+        assert_eq!(maximum_bottleneck, Some(4.into()));
+    }
+}
@@ -2,7 +2,9 @@ use std::cell::RefCell;
 use std::collections::HashSet;
 use std::rc::Rc;
 
-use crate::graph::{subgraph::Subgraph, Edge, UndirectedEdge};
+use magnitude::Magnitude;
+
+use crate::graph::{subgraph::SpanningTreeSubgraph, Edge, UndirectedEdge};
 use crate::provide;
 
 pub struct Kruskal {
@@ -27,23 +29,74 @@ impl Kruskal {
     }
 
     pub fn execute<'a, G, W: Ord + std::fmt::Debug, E: Edge<W>>(
-        mut self,
+        self,
         graph: &'a G,
-    ) -> Subgraph<W, E, UndirectedEdge, G>
+    ) -> SpanningTreeSubgraph<W, E, UndirectedEdge, G>
     where
         G: provide::Edges<W, E>
             + provide::Neighbors
             + provide::Vertices
             + provide::Graph<W, E, UndirectedEdge>,
     {
-        let mut mst = Vec::<(usize, usize, usize)>::new();
-
-        let id_map = graph.continuos_id_map();
-
         let mut edges = graph.edges();
 
         edges.sort_by(|(_, _, e1), (_, _, e2)| e1.get_weight().cmp(e2.get_weight()));
 
+        self.spanning_tree(graph, edges)
+    }
+
+    /// Same as `execute` but cost of each edge is computed by `cost_of` instead of using weight of the edge.
+    /// So costs can be derived from the stored weights(or anything else) without mutating or copying the graph.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to find its minimum spanning forest.
+    /// * `cost_of`: Computes cost of an edge from its source id, destination id and the edge itself.
+    ///   Edges with infinite cost are never used.
+    ///
+    /// # Returns
+    /// Subgraph containing edges of the spanning forest with minimum total cost.
+    pub fn execute_with_cost<'a, G, W, W2, E, F>(
+        self,
+        graph: &'a G,
+        cost_of: F,
+    ) -> SpanningTreeSubgraph<W, E, UndirectedEdge, G>
+    where
+        W2: Ord,
+        E: Edge<W>,
+        F: Fn(usize, usize, &E) -> Magnitude<W2>,
+        G: provide::Edges<W, E>
+            + provide::Neighbors
+            + provide::Vertices
+            + provide::Graph<W, E, UndirectedEdge>,
+    {
+        let mut edges = graph
+            .edges()
+            .into_iter()
+            .map(|(src_id, dst_id, edge)| (cost_of(src_id, dst_id, edge), (src_id, dst_id, edge)))
+            .filter(|(cost, _)| cost.is_finite())
+            .collect::<Vec<_>>();
+
+        edges.sort_by(|(cost1, _), (cost2, _)| cost1.cmp(cost2));
+
+        self.spanning_tree(graph, edges.into_iter().map(|(_, edge)| edge).collect())
+    }
+
+    // Adds edges in the given order, skipping the ones that connect two vertices of the same tree.
+    pub(super) fn spanning_tree<'a, G, W, E: Edge<W>>(
+        mut self,
+        graph: &'a G,
+        edges: Vec<(usize, usize, &E)>,
+    ) -> SpanningTreeSubgraph<'a, W, E, UndirectedEdge, G>
+    where
+        G: provide::Edges<W, E>
+            + provide::Neighbors
+            + provide::Vertices
+            + provide::Graph<W, E, UndirectedEdge>,
+    {
+        let mut mst = Vec::<(usize, usize, usize)>::new();
+
+        let id_map = graph.continuous_id_map();
+
         for (v_real_id, u_real_id, edge) in edges {
             let v_virt_id = id_map.virt_id_of(v_real_id);
             let u_virt_id = id_map.virt_id_of(u_real_id);
@@ -73,7 +126,7 @@ impl Kruskal {
             .flat_map(|(src_id, dst_id, _)| vec![*src_id, *dst_id])
             .collect::<HashSet<usize>>();
 
-        Subgraph::init(graph, mst, vertices)
+        SpanningTreeSubgraph::init(graph, mst, vertices)
     }
 }
 
@@ -137,8 +190,59 @@ mod tests {
 
         assert_eq!(mst.vertex_count(), 6);
         assert_eq!(mst.edges_count(), 5);
+        assert_eq!(mst.total_weight(), 8.into());
         assert!(vec![ab, af, bd, dc, ec]
             .into_iter()
             .all(|edge_id| mst.edge(edge_id).is_ok()))
     }
+
+    #[test]
+    fn graph_with_cost_function() {
+        //  Given: Graph
+        //                5
+        //      f ----------------.
+        //      |                 |
+        //    3 |  1     1     4  |
+        //      a --- b --- d --- e
+        //    3 |   5 |   2 |   1 |
+        //      |     |     |     |
+        //      c ----'-----'-----'
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+
+        graph.add_edge_unchecked(a, b, 1.into());
+        let ac = graph.add_edge_unchecked(a, c, 3.into());
+        let af = graph.add_edge_unchecked(a, f, 3.into());
+
+        let bc = graph.add_edge_unchecked(b, c, 5.into());
+        graph.add_edge_unchecked(b, d, 1.into());
+
+        let dc = graph.add_edge_unchecked(d, c, 2.into());
+        let de = graph.add_edge_unchecked(d, e, 4.into());
+
+        graph.add_edge_unchecked(e, c, 1.into());
+        let ef = graph.add_edge_unchecked(e, f, 5.into());
+
+        // When: Heavier edges are cheaper, and edge between e and f can not be used.
+        let mst = Kruskal::init(&graph).execute_with_cost(&graph, |_, _, edge| {
+            if edge.get_id() == ef {
+                Magnitude::PosInfinite
+            } else {
+                (10 - edge.get_weight().unwrap()).into()
+            }
+        });
+
+        // Then:
+        assert_eq!(mst.vertex_count(), 6);
+        assert_eq!(mst.edges_count(), 5);
+        assert_eq!(mst.total_weight(), 17.into());
+        assert!(vec![ac, af, bc, dc, de]
+            .into_iter()
+            .all(|edge_id| mst.edge(edge_id).is_ok()))
+    }
 }
@@ -1,3 +1,5 @@
+mod bottleneck;
 mod kruskal;
 
+pub use bottleneck::BottleneckSpanningTree;
 pub use kruskal::Kruskal;
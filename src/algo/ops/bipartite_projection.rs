@@ -0,0 +1,274 @@
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Add;
+
+use anyhow::Result;
+use magnitude::Magnitude;
+use num_traits::One;
+
+use crate::graph::{DefaultEdge, Edge, EdgeDir, Error, ListGraph, UndirectedEdge};
+use crate::provide::{Edges, Graph, Vertices};
+use crate::storage::List;
+
+/// Determines weight of the edges of a [`bipartite_projection`](crate::algo::ops::bipartite_projection).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectionWeight {
+    /// Number of neighbors that the two end points share.
+    SharedNeighbors,
+
+    /// Sum of weights of the edges that connect the two end points to their shared neighbors.
+    SumOfWeights,
+}
+
+/// Projects a bipartite graph onto one of its sides.
+///
+/// Projection has a vertex for each vertex of `side`, and two of them are connected if they have at least one neighbor in common.
+/// Direction of the edges is ignored, so projection is always undirected.
+///
+/// # Arguments
+/// * `graph`: Bipartite graph to project.
+/// * `side`: Id of the vertices to project onto. No two of them can be connected.
+/// * `weight`: How weight of each edge of the projection is computed.
+///
+/// # Returns
+/// * `Ok`: Containing the projection and id of each vertex of `side` in the projection, mapped from its id in `graph`.
+/// * `Err`:
+///     * [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if a vertex of `side` does not exist.
+///     * [`InvalidPartition`](crate::graph::ErrorKind::InvalidPartition) if a vertex of `side` is connected to another vertex of `side` or to itself.
+///
+/// # Complexity
+/// O(|V| + |E| + sum of squared degrees of the vertices that are not in `side`)
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::ops::{bipartite_projection, ProjectionWeight};
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph of people and the clubs they are members of.
+/// //
+/// //      alice         bob         carol
+/// //      |    \       /   \       /
+/// //      |     chess       tennis
+/// //      |
+/// //      golf
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let alice = graph.add_vertex();
+/// let bob = graph.add_vertex();
+/// let carol = graph.add_vertex();
+/// let chess = graph.add_vertex();
+/// let tennis = graph.add_vertex();
+/// let golf = graph.add_vertex();
+/// graph.add_edge_unchecked(alice, chess, 1.into());
+/// graph.add_edge_unchecked(alice, golf, 1.into());
+/// graph.add_edge_unchecked(bob, chess, 1.into());
+/// graph.add_edge_unchecked(bob, tennis, 1.into());
+/// graph.add_edge_unchecked(carol, tennis, 1.into());
+///
+/// // When: Projecting onto the people.
+/// let (projection, id_map) =
+///     bipartite_projection(&graph, &[alice, bob, carol], ProjectionWeight::SharedNeighbors).unwrap();
+///
+/// // Then: Alice and bob are both in chess club, bob and carol are both in tennis club.
+/// assert_eq!(projection.vertex_count(), 3);
+/// assert_eq!(projection.edges_count(), 2);
+/// assert!(projection.has_any_edge_unchecked(id_map[&alice], id_map[&bob]));
+/// assert!(!projection.has_any_edge_unchecked(id_map[&alice], id_map[&carol]));
+/// ```
+pub fn bipartite_projection<W, E, Ty, G>(
+    graph: &G,
+    side: &[usize],
+    weight: ProjectionWeight,
+) -> Result<(ListGraph<W, UndirectedEdge>, HashMap<usize, usize>)>
+where
+    W: Copy + Any + One + Add<Output = W>,
+    E: Edge<W>,
+    Ty: EdgeDir,
+    G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+{
+    let mut projection = ListGraph::init(List::init());
+
+    let mut id_map = HashMap::new();
+    for vertex_id in side {
+        if !graph.contains_vertex(*vertex_id) {
+            Err(Error::new_vnf(*vertex_id))?
+        }
+
+        if !id_map.contains_key(vertex_id) {
+            id_map.insert(*vertex_id, projection.add_vertex());
+        }
+    }
+
+    // Maps each vertex of the other side to the vertices of `side` it is connected to, and total weight of the edges between them.
+    let mut members_of: HashMap<usize, BTreeMap<usize, Magnitude<W>>> = HashMap::new();
+    for (src_id, dst_id, edge) in graph.edges() {
+        let (member_id, other_id) =
+            match (id_map.contains_key(&src_id), id_map.contains_key(&dst_id)) {
+                (true, true) => Err(Error::new_ip(src_id))?,
+                (true, false) => (src_id, dst_id),
+                (false, true) => (dst_id, src_id),
+                (false, false) => continue,
+            };
+
+        let members = members_of.entry(other_id).or_default();
+        match members.get_mut(&member_id) {
+            Some(total_weight) => *total_weight += *edge.get_weight(),
+            None => {
+                members.insert(member_id, *edge.get_weight());
+            }
+        }
+    }
+
+    // Ordered so that edges of the projection are added in a deterministic order.
+    let mut weight_between = BTreeMap::new();
+    for members in members_of.values() {
+        let members = members.iter().collect::<Vec<(&usize, &Magnitude<W>)>>();
+
+        for (index, (src_id, src_weight)) in members.iter().enumerate() {
+            for (dst_id, dst_weight) in &members[index + 1..] {
+                let pair_weight = match weight {
+                    ProjectionWeight::SharedNeighbors => W::one().into(),
+                    ProjectionWeight::SumOfWeights => **src_weight + **dst_weight,
+                };
+
+                match weight_between.get_mut(&(**src_id, **dst_id)) {
+                    Some(total_weight) => *total_weight += pair_weight,
+                    None => {
+                        weight_between.insert((**src_id, **dst_id), pair_weight);
+                    }
+                }
+            }
+        }
+    }
+
+    for ((src_id, dst_id), weight) in weight_between {
+        projection.add_edge_unchecked(id_map[&src_id], id_map[&dst_id], DefaultEdge::init(weight));
+    }
+
+    Ok((projection, id_map))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ErrorKind, MatGraph};
+    use crate::provide::Neighbors;
+    use crate::storage::{DiList, Mat};
+
+    fn weight_between(
+        projection: &ListGraph<usize, UndirectedEdge>,
+        src_id: usize,
+        dst_id: usize,
+    ) -> usize {
+        projection.edges_between_unchecked(src_id, dst_id)[0]
+            .get_weight()
+            .unwrap()
+    }
+
+    #[test]
+    fn invalid_side() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // When: Projecting onto a missing vertex, and onto two connected vertices.
+        let missing_result =
+            bipartite_projection(&graph, &[a, c + 1], ProjectionWeight::SharedNeighbors);
+        let connected_result =
+            bipartite_projection(&graph, &[a, b], ProjectionWeight::SharedNeighbors);
+
+        // Then:
+        assert!(matches!(
+            missing_result
+                .unwrap_err()
+                .downcast_ref::<Error>()
+                .unwrap()
+                .kind(),
+            ErrorKind::VertexNotFound
+        ));
+        assert!(matches!(
+            connected_result
+                .unwrap_err()
+                .downcast_ref::<Error>()
+                .unwrap()
+                .kind(),
+            ErrorKind::InvalidPartition
+        ));
+    }
+
+    #[test]
+    fn shared_neighbors() {
+        // Given: Graph
+        //
+        //      a       b       c       d
+        //      | \   / | \   /
+        //      |   x   |   x
+        //      | /   \ | /   \
+        //      e       f       g
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        let g = graph.add_vertex();
+        graph.add_edge_unchecked(a, e, 1.into());
+        graph.add_edge_unchecked(a, f, 1.into());
+        graph.add_edge_unchecked(b, e, 1.into());
+        graph.add_edge_unchecked(b, f, 1.into());
+        graph.add_edge_unchecked(b, g, 1.into());
+        graph.add_edge_unchecked(c, f, 1.into());
+
+        // When:
+        let (projection, id_map) =
+            bipartite_projection(&graph, &[a, b, c, d], ProjectionWeight::SharedNeighbors).unwrap();
+
+        // Then:
+        assert_eq!(projection.vertex_count(), 4);
+        assert_eq!(projection.edges_count(), 3);
+        assert_eq!(weight_between(&projection, id_map[&a], id_map[&b]), 2);
+        assert_eq!(weight_between(&projection, id_map[&a], id_map[&c]), 1);
+        assert_eq!(weight_between(&projection, id_map[&b], id_map[&c]), 1);
+        assert!(projection.neighbors_unchecked(id_map[&d]).is_empty());
+    }
+
+    #[test]
+    fn sum_of_weights_in_directed_graph() {
+        // Given: Graph
+        //
+        //      a  --(1)-->  c  <--(2)--  b
+        //      |                         |
+        //     (3)                       (4)
+        //      |                         |
+        //      '--------->  d  <---------'
+        //
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, c, 1.into());
+        graph.add_edge_unchecked(b, c, 2.into());
+        graph.add_edge_unchecked(a, d, 3.into());
+        graph.add_edge_unchecked(d, b, 4.into());
+
+        // When:
+        let (projection, id_map) =
+            bipartite_projection(&graph, &[a, b], ProjectionWeight::SumOfWeights).unwrap();
+
+        // Then: Directions are ignored, so a and b share both c and d.
+        assert_eq!(projection.edges_count(), 1);
+        assert_eq!(weight_between(&projection, id_map[&a], id_map[&b]), 10);
+    }
+}
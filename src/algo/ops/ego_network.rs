@@ -0,0 +1,172 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+
+use crate::graph::subgraph::MultiRootSubgraph;
+use crate::graph::{Edge, EdgeDir, Error};
+use crate::provide::{Edges, Graph, Neighbors, Vertices};
+
+/// Extracts the ego network of a vertex: subgraph induced by all vertices that are at most `radius` hops away from the center.
+///
+/// In directed graphs, hops follow direction of the edges. So the ego network contains vertices that are reachable from the center within `radius` hops.
+///
+/// # Arguments
+/// * `graph`: Graph to extract the ego network from.
+/// * `center_id`: Id of the center vertex.
+/// * `radius`: Maximum number of hops from the center.
+///
+/// # Returns
+/// * `Ok`: Containing the ego network, rooted at the center vertex. It contains every edge of the graph that connects two of its vertices.
+/// * `Err`: [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if center vertex does not exist.
+///
+/// # Complexity
+/// O(|V| + |E|)
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::ego_network;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b  ---  c  ---  d
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+///
+/// let ego = ego_network(&graph, b, 1).unwrap();
+///
+/// assert_eq!(ego.roots(), &vec![b]);
+/// assert_eq!(ego.vertex_count(), 3);
+/// assert!(!ego.contains_vertex(d));
+/// ```
+pub fn ego_network<'a, W, E, Ty, G>(
+    graph: &'a G,
+    center_id: usize,
+    radius: usize,
+) -> Result<MultiRootSubgraph<'a, W, E, Ty, G>>
+where
+    E: Edge<W>,
+    Ty: EdgeDir,
+    G: Edges<W, E> + Neighbors + Vertices + Graph<W, E, Ty>,
+{
+    if !graph.contains_vertex(center_id) {
+        Err(Error::new_vnf(center_id))?
+    }
+
+    let vertices = graph
+        .neighbors_within_unchecked(center_id, radius)
+        .into_keys()
+        .collect::<HashSet<usize>>();
+
+    let edges = graph
+        .edges()
+        .into_iter()
+        .filter(|(src_id, dst_id, _)| vertices.contains(src_id) && vertices.contains(dst_id))
+        .map(|(src_id, dst_id, edge)| (src_id, dst_id, edge.get_id()))
+        .collect();
+
+    Ok(MultiRootSubgraph::init(
+        graph,
+        edges,
+        vertices,
+        vec![center_id],
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::storage::{DiMat, Mat};
+
+    #[test]
+    fn unknown_center() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        assert!(ego_network(&graph, 0, 1).is_err());
+    }
+
+    #[test]
+    fn undirected_graph() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c  ---  d
+        //      |       |
+        //      e  -----'       f
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+        graph.add_edge_unchecked(a, e, 1.into());
+        let be = graph.add_edge_unchecked(b, e, 1.into());
+
+        // When: Extracting ego network of a with radius 0.
+        let ego = ego_network(&graph, a, 0).unwrap();
+
+        // Then:
+        assert_eq!(ego.vertex_count(), 1);
+        assert_eq!(ego.edges_count(), 0);
+
+        // When: Extracting ego network of a with radius 1.
+        let ego = ego_network(&graph, a, 1).unwrap();
+
+        // Then: Edge between b and e is included because both of them are in the ego network.
+        assert_eq!(ego.vertex_count(), 3);
+        assert_eq!(ego.edges_count(), 3);
+        assert!(ego.edge(be).is_ok());
+        assert!(ego.is_root(a));
+
+        // When: Extracting ego network of a with a radius larger than the graph.
+        let ego = ego_network(&graph, a, 10).unwrap();
+
+        // Then: Only vertices reachable from a are included.
+        assert_eq!(ego.vertex_count(), 5);
+        assert!(!ego.contains_vertex(f));
+    }
+
+    #[test]
+    fn directed_graph() {
+        // Given: Graph
+        //
+        //      e  -->  a  -->  b  -->  c  -->  d
+        //              ^       |
+        //              '-------'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+        graph.add_edge_unchecked(b, a, 1.into());
+        graph.add_edge_unchecked(e, a, 1.into());
+
+        // When: Extracting ego network of b with radius 1.
+        let ego = ego_network(&graph, b, 1).unwrap();
+
+        // Then: e is not included because it's not reachable from b.
+        assert_eq!(ego.vertex_count(), 3);
+        assert_eq!(ego.edges_count(), 3);
+        assert!(!ego.contains_vertex(e));
+        assert!(!ego.contains_vertex(d));
+    }
+}
@@ -0,0 +1,13 @@
+mod bipartite_projection;
+mod ego_network;
+mod quotient;
+mod relabel;
+mod rewire;
+mod sample;
+
+pub use bipartite_projection::{bipartite_projection, ProjectionWeight};
+pub use ego_network::ego_network;
+pub use quotient::quotient;
+pub use relabel::relabel;
+pub use rewire::rewire;
+pub use sample::{sample_edges, sample_edges_weighted, sample_vertices, sample_vertices_weighted};
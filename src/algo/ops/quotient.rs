@@ -0,0 +1,278 @@
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::Result;
+use magnitude::Magnitude;
+
+use crate::graph::{DefaultEdge, Edge, EdgeDir, Error, ListGraph};
+use crate::provide::{Edges, Graph, Vertices};
+use crate::storage::List;
+
+/// Merges each block of a partition of vertices into a super vertex.
+///
+/// There is an edge from one super vertex to another if there is at least one edge from a vertex of the first block to a vertex of the second block.
+/// Weights of all edges between two blocks are combined into one weight using the `reducer`. Edges inside a block are dropped.
+/// Quotient graph has the same direction as the original graph.
+///
+/// # Arguments
+/// * `graph`: Graph to coarsen.
+/// * `partition`: Blocks of the partition, like the components of a [`ComponentSet`](crate::algo::ComponentSet). Each vertex must be in exactly one block.
+/// * `reducer`: Combines weights of two edges between the same blocks.
+///
+/// # Returns
+/// * `Ok`: Containing the quotient graph and id of the super vertex of each block.
+/// * `Err`:
+///     * [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if a vertex of the partition does not exist.
+///     * [`InvalidPartition`](crate::graph::ErrorKind::InvalidPartition) if a vertex is in none or more than one of the blocks.
+///
+/// # Complexity
+/// O(|V| + |E| * log(|E|))
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::ops::quotient;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  --(1)--  b  --(2)--  c
+/// //      |                       |
+/// //      '-----------(3)---------'
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 2.into());
+/// graph.add_edge_unchecked(a, c, 3.into());
+///
+/// // When: Merging a and b, and summing weights of the edges.
+/// let (quotient_graph, super_ids) = quotient(&graph, &[vec![a, b], vec![c]], |w1, w2| w1 + w2).unwrap();
+///
+/// // Then:
+/// assert_eq!(quotient_graph.vertex_count(), 2);
+/// assert_eq!(quotient_graph.edges_count(), 1);
+/// assert_eq!(*quotient_graph.edges_between(super_ids[0], super_ids[1]).unwrap()[0].get_weight(), 5.into());
+/// ```
+pub fn quotient<W, E, Ty, G, F>(
+    graph: &G,
+    partition: &[Vec<usize>],
+    reducer: F,
+) -> Result<(ListGraph<W, Ty>, Vec<usize>)>
+where
+    W: Copy + Any,
+    E: Edge<W>,
+    Ty: EdgeDir,
+    G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+    F: Fn(Magnitude<W>, Magnitude<W>) -> Magnitude<W>,
+{
+    let mut block_of = HashMap::new();
+    for (index, block) in partition.iter().enumerate() {
+        for vertex_id in block {
+            if !graph.contains_vertex(*vertex_id) {
+                Err(Error::new_vnf(*vertex_id))?
+            }
+
+            if block_of.insert(*vertex_id, index).is_some() {
+                Err(Error::new_ip(*vertex_id))?
+            }
+        }
+    }
+
+    if let Some(vertex_id) = graph
+        .vertices()
+        .into_iter()
+        .find(|vertex_id| !block_of.contains_key(vertex_id))
+    {
+        Err(Error::new_ip(vertex_id))?
+    }
+
+    // Combined weight of edges between each pair of blocks. Ordered so that edges of the quotient graph are added in a deterministic order.
+    let mut weight_between = BTreeMap::new();
+    for (src_id, dst_id, edge) in graph.edges() {
+        let src_block = block_of[&src_id];
+        let dst_block = block_of[&dst_id];
+
+        if src_block == dst_block {
+            continue;
+        }
+
+        let key = if Ty::is_directed() {
+            (src_block, dst_block)
+        } else {
+            (src_block.min(dst_block), src_block.max(dst_block))
+        };
+
+        let weight = match weight_between.remove(&key) {
+            Some(weight) => reducer(weight, *edge.get_weight()),
+            None => *edge.get_weight(),
+        };
+        weight_between.insert(key, weight);
+    }
+
+    let mut quotient_graph = ListGraph::init(List::init());
+
+    let super_ids = partition
+        .iter()
+        .map(|_| quotient_graph.add_vertex())
+        .collect::<Vec<usize>>();
+
+    for ((src_block, dst_block), weight) in weight_between {
+        quotient_graph.add_edge_unchecked(
+            super_ids[src_block],
+            super_ids[dst_block],
+            DefaultEdge::init(weight),
+        );
+    }
+
+    Ok((quotient_graph, super_ids))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::ConnectedComponents;
+    use crate::graph::MatGraph;
+    use crate::storage::{DiMat, Mat};
+
+    #[test]
+    fn invalid_partition() {
+        // Given: Graph
+        //
+        //      a  ---  b       c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+
+        let sum = |w1: Magnitude<usize>, w2: Magnitude<usize>| w1 + w2;
+
+        // Then: c is in no block.
+        assert!(quotient(&graph, &[vec![a, b]], sum).is_err());
+
+        // Then: a is in two blocks.
+        assert!(quotient(&graph, &[vec![a, b], vec![a, c]], sum).is_err());
+
+        // Then: Vertex does not exist.
+        assert!(quotient(&graph, &[vec![a, b, c, c + 1]], sum).is_err());
+    }
+
+    #[test]
+    fn undirected_graph() {
+        // Given: Graph
+        //
+        //      a  --(1)--  b  --(4)--  d  --(2)--  e
+        //      |           |           |
+        //     (2)         (3)         (5)
+        //      |           |           |
+        //      c  ---------'           f
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(a, c, 2.into());
+        graph.add_edge_unchecked(b, c, 3.into());
+        graph.add_edge_unchecked(b, d, 4.into());
+        graph.add_edge_unchecked(d, e, 2.into());
+        graph.add_edge_unchecked(d, f, 5.into());
+
+        // When: Merging {a, b}, {c, d} and {e, f}, and keeping the maximum weight.
+        let (quotient_graph, super_ids) =
+            quotient(&graph, &[vec![a, b], vec![c, d], vec![e, f]], std::cmp::max).unwrap();
+
+        // Then:
+        assert_eq!(quotient_graph.vertex_count(), 3);
+        assert_eq!(quotient_graph.edges_count(), 2);
+        assert_eq!(
+            *quotient_graph
+                .edges_between(super_ids[1], super_ids[0])
+                .unwrap()[0]
+                .get_weight(),
+            4.into()
+        );
+        assert_eq!(
+            *quotient_graph
+                .edges_between(super_ids[2], super_ids[1])
+                .unwrap()[0]
+                .get_weight(),
+            5.into()
+        );
+        assert!(!quotient_graph.has_any_edge_unchecked(super_ids[0], super_ids[2]));
+    }
+
+    #[test]
+    fn directed_graph() {
+        // Given: Graph
+        //
+        //      a  --(1)-->  b  --(2)-->  c
+        //      ^            |            |
+        //     (4)          (3)          (1)
+        //      |            v            |
+        //      '----------  d  <---------'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 2.into());
+        graph.add_edge_unchecked(b, d, 3.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+        graph.add_edge_unchecked(d, a, 4.into());
+
+        // When: Merging {a, b} and {c, d}, and summing weights of the edges.
+        let (quotient_graph, super_ids) =
+            quotient(&graph, &[vec![a, b], vec![c, d]], |w1, w2| w1 + w2).unwrap();
+
+        // Then: Edges in each direction are kept apart.
+        assert_eq!(quotient_graph.edges_count(), 2);
+        assert_eq!(
+            *quotient_graph
+                .edges_between(super_ids[0], super_ids[1])
+                .unwrap()[0]
+                .get_weight(),
+            5.into()
+        );
+        assert_eq!(
+            *quotient_graph
+                .edges_between(super_ids[1], super_ids[0])
+                .unwrap()[0]
+                .get_weight(),
+            4.into()
+        );
+    }
+
+    #[test]
+    fn partition_from_components() {
+        // Given: Graph
+        //
+        //      a  ---  b       c  ---  d
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+
+        // When: Merging each connected component.
+        let components = ConnectedComponents::init(&graph).execute(&graph);
+        let (quotient_graph, _) = quotient(&graph, &components, |w1, w2| w1 + w2).unwrap();
+
+        // Then: Components are not connected to each other.
+        assert_eq!(quotient_graph.vertex_count(), 2);
+        assert_eq!(quotient_graph.edges_count(), 0);
+    }
+}
@@ -0,0 +1,226 @@
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+
+use crate::graph::{DefaultEdge, Edge, EdgeDir, Error, ListGraph};
+use crate::provide::{Edges, Graph, Vertices};
+use crate::storage::List;
+
+/// Copies a graph so that each vertex gets the id it is mapped to.
+///
+/// Ids of the new graph do not have to be contiguous. Edges keep their weights and directions, but not their ids.
+///
+/// # Arguments
+/// * `graph`: Graph to relabel.
+/// * `mapping`: New id of each vertex of the graph, keyed by its current id. No two vertices can be mapped to the same id.
+///
+/// # Returns
+/// * `Ok`: Containing the relabeled graph.
+/// * `Err`:
+///     * [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if a vertex of the mapping does not exist.
+///     * [`InvalidMapping`](crate::graph::ErrorKind::InvalidMapping) if a vertex is not mapped, or it is mapped to the same id as another vertex.
+///
+/// # Complexity
+/// O(|V| + |E| + largest new id)
+///
+/// # Examples
+/// ```
+/// use std::collections::HashMap;
+///
+/// use prepona::prelude::*;
+/// use prepona::algo::ops::relabel;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  --(1)--  b  --(2)--  c
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 2.into());
+///
+/// // When: Reversing the ids.
+/// let mapping: HashMap<usize, usize> = vec![(a, c), (b, b), (c, a)].into_iter().collect();
+/// let relabeled = relabel(&graph, &mapping).unwrap();
+///
+/// // Then:
+/// assert_eq!(*relabeled.edges_between(c, b).unwrap()[0].get_weight(), 1.into());
+/// assert_eq!(*relabeled.edges_between(b, a).unwrap()[0].get_weight(), 2.into());
+/// ```
+pub fn relabel<W, E, Ty, G>(graph: &G, mapping: &HashMap<usize, usize>) -> Result<ListGraph<W, Ty>>
+where
+    W: Copy + Any,
+    E: Edge<W>,
+    Ty: EdgeDir,
+    G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+{
+    if let Some(vertex_id) = mapping
+        .keys()
+        .find(|vertex_id| !graph.contains_vertex(**vertex_id))
+    {
+        Err(Error::new_vnf(*vertex_id))?
+    }
+
+    let mut new_ids = HashSet::new();
+    for vertex_id in graph.vertices() {
+        match mapping.get(&vertex_id) {
+            Some(new_id) if new_ids.insert(*new_id) => {}
+            _ => Err(Error::new_im(vertex_id))?,
+        }
+    }
+
+    // A new storage allocates ids from 0 upwards, so vertices are added up to the largest new id and the ones that are not mapped to are removed.
+    let mut relabeled = ListGraph::init(List::init());
+    if let Some(max_id) = new_ids.iter().max() {
+        for _ in 0..=*max_id {
+            relabeled.add_vertex();
+        }
+
+        for vertex_id in 0..*max_id {
+            if !new_ids.contains(&vertex_id) {
+                relabeled.remove_vertex_unchecked(vertex_id);
+            }
+        }
+    }
+
+    for (src_id, dst_id, edge) in graph.edges() {
+        relabeled.add_edge_unchecked(
+            mapping[&src_id],
+            mapping[&dst_id],
+            DefaultEdge::init(*edge.get_weight()),
+        );
+    }
+
+    Ok(relabeled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ErrorKind, MatGraph};
+    use crate::storage::{DiMat, Mat};
+
+    fn mapping_of(pairs: &[(usize, usize)]) -> HashMap<usize, usize> {
+        pairs.iter().copied().collect()
+    }
+
+    #[test]
+    fn invalid_mapping() {
+        // Given: Graph
+        //
+        //      a  ---  b       c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+
+        let is_invalid_mapping = |mapping: HashMap<usize, usize>| {
+            matches!(
+                relabel(&graph, &mapping)
+                    .unwrap_err()
+                    .downcast_ref::<Error>()
+                    .unwrap()
+                    .kind(),
+                ErrorKind::InvalidMapping
+            )
+        };
+
+        // Then: c is not mapped.
+        assert!(is_invalid_mapping(mapping_of(&[(a, 0), (b, 1)])));
+
+        // Then: a and c are mapped to the same id.
+        assert!(is_invalid_mapping(mapping_of(&[(a, 0), (b, 1), (c, 0)])));
+
+        // Then: Vertex does not exist.
+        let missing_result = relabel(&graph, &mapping_of(&[(a, 0), (b, 1), (c, 2), (c + 1, 3)]));
+        assert!(matches!(
+            missing_result
+                .unwrap_err()
+                .downcast_ref::<Error>()
+                .unwrap()
+                .kind(),
+            ErrorKind::VertexNotFound
+        ));
+    }
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let relabeled = relabel(&graph, &HashMap::new()).unwrap();
+
+        assert_eq!(relabeled.vertex_count(), 0);
+    }
+
+    #[test]
+    fn contiguous_ids() {
+        // Given: Graph with ids 1, 3 and 4, after removing the other vertices.
+        //
+        //      b  --(1)-->  d  --(2)-->  e
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        graph.add_edge_unchecked(b, d, 1.into());
+        graph.add_edge_unchecked(d, e, 2.into());
+        graph.remove_vertex_unchecked(a);
+        graph.remove_vertex_unchecked(c);
+
+        // When: Making ids contiguous.
+        let relabeled = relabel(&graph, &mapping_of(&[(b, 0), (d, 1), (e, 2)])).unwrap();
+
+        // Then:
+        assert_eq!(relabeled.vertices(), vec![0, 1, 2]);
+        assert_eq!(relabeled.edges_count(), 2);
+        assert_eq!(
+            *relabeled.edges_between(0, 1).unwrap()[0].get_weight(),
+            1.into()
+        );
+        assert_eq!(
+            *relabeled.edges_between(1, 2).unwrap()[0].get_weight(),
+            2.into()
+        );
+        assert!(!relabeled.has_any_edge_unchecked(1, 0));
+    }
+
+    #[test]
+    fn sparse_ids() {
+        // Given: Graph
+        //
+        //      a  --(1)--  b  --(2)--  c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 2.into());
+
+        // When:
+        let relabeled = relabel(&graph, &mapping_of(&[(a, 7), (b, 2), (c, 4)])).unwrap();
+
+        // Then:
+        let mut vertices = relabeled.vertices();
+        vertices.sort_unstable();
+        assert_eq!(vertices, vec![2, 4, 7]);
+        assert_eq!(relabeled.edges_count(), 2);
+        assert_eq!(
+            *relabeled.edges_between(2, 7).unwrap()[0].get_weight(),
+            1.into()
+        );
+        assert_eq!(
+            *relabeled.edges_between(4, 2).unwrap()[0].get_weight(),
+            2.into()
+        );
+    }
+}
@@ -0,0 +1,243 @@
+use crate::graph::{Edge, EdgeDir};
+use crate::provide::{Edges, Graph, Vertices};
+
+// Maximum number of attempts for each requested swap. Attempts fail when they would create a loop or a multi edge,
+// so graphs where few swaps are possible(like complete graphs) would loop forever without it.
+const MAX_ATTEMPTS_PER_SWAP: usize = 100;
+
+/// Randomizes the graph while preserving degree of each vertex, using double edge swaps.
+///
+/// Each swap picks two random edges a --- b and c --- d and replaces them with a --- d and c --- b.
+/// In directed graphs it replaces a --> b and c --> d with a --> d and c --> b, so both in degree and out degree of each vertex are preserved.
+/// Swaps that would create a loop or a multi edge are rejected. Each edge keeps its value(weight, ...) but gets a new id.
+///
+/// It can be used to generate null models, in order to test significance of properties like motif counts or assortativity.
+///
+/// # Arguments
+/// * `graph`: Graph to rewire.
+/// * `n_swaps`: Number of swaps to perform.
+/// * `rng`: Source of randomness: when called with `n`, returns a uniformly random number in range of 0 to `n` - 1.
+///   Use [`seeded`](crate::util::seeded) for reproducible results, or `util::from_rng` with the `rand` feature.
+///
+/// # Returns
+/// Number of swaps that are performed. It's less than `n_swaps` if too many attempts are rejected.
+///
+/// # Complexity
+/// O(`n_swaps` * cost of adding and removing edges)
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::ops::rewire;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b       c  ---  d
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+///
+/// // Rng that alternates between 1 and 0, so the two edges get swapped.
+/// let mut next = 0;
+/// let swaps = rewire(&mut graph, 1, &mut |n| {
+///     next = (next + 1) % 2;
+///     next % n
+/// });
+///
+/// assert_eq!(swaps, 1);
+/// assert_eq!(graph.edges_count(), 2);
+/// assert!(!graph.has_any_edge_unchecked(a, b));
+/// ```
+pub fn rewire<W, E, Ty, G, R>(graph: &mut G, n_swaps: usize, rng: &mut R) -> usize
+where
+    E: Edge<W>,
+    Ty: EdgeDir,
+    G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+    R: FnMut(usize) -> usize,
+{
+    let mut edges = graph
+        .edges()
+        .into_iter()
+        .map(|(src_id, dst_id, edge)| (src_id, dst_id, edge.get_id()))
+        .collect::<Vec<(usize, usize, usize)>>();
+
+    if edges.len() < 2 {
+        return 0;
+    }
+
+    let mut swaps = 0;
+    let mut attempts = 0;
+    while swaps < n_swaps && attempts < n_swaps * MAX_ATTEMPTS_PER_SWAP {
+        attempts += 1;
+
+        let first = rng(edges.len());
+        let second = rng(edges.len());
+        if first == second {
+            continue;
+        }
+
+        let (a, b, ab) = edges[first];
+        let (mut c, mut d, cd) = edges[second];
+
+        // Undirected edge c --- d can be swapped either as c --- d or d --- c.
+        if !Ty::is_directed() && rng(2) == 1 {
+            std::mem::swap(&mut c, &mut d);
+        }
+
+        let creates_loop = a == d || c == b;
+        if creates_loop || graph.has_any_edge_unchecked(a, d) || graph.has_any_edge_unchecked(c, b)
+        {
+            continue;
+        }
+
+        let ab_edge = graph.remove_edge_unchecked(a, b, ab).unwrap();
+        let cd_edge = graph.remove_edge_unchecked(c, d, cd).unwrap();
+
+        let ad = graph.add_edge_unchecked(a, d, ab_edge);
+        let cb = graph.add_edge_unchecked(c, b, cd_edge);
+
+        edges[first] = (a, d, ad);
+        edges[second] = (c, b, cb);
+
+        swaps += 1;
+    }
+
+    swaps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::storage::{DiMat, Mat};
+    use crate::util::seeded;
+    use std::collections::HashMap;
+
+    // Returns (out degree, in degree) of each vertex.
+    fn degrees_of<G: Edges<usize, E> + Vertices, E: Edge<usize>>(
+        graph: &G,
+    ) -> HashMap<usize, (usize, usize)> {
+        let mut degrees = graph
+            .vertices()
+            .into_iter()
+            .map(|vertex_id| (vertex_id, (0, 0)))
+            .collect::<HashMap<usize, (usize, usize)>>();
+
+        for (src_id, dst_id, _) in graph.edges() {
+            degrees.get_mut(&src_id).unwrap().0 += 1;
+            degrees.get_mut(&dst_id).unwrap().1 += 1;
+        }
+
+        degrees
+    }
+
+    #[test]
+    fn too_few_edges() {
+        // Given: Graph
+        //
+        //      a  ---  b       c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+
+        // Then:
+        assert_eq!(rewire(&mut graph, 10, &mut seeded(1)), 0);
+    }
+
+    #[test]
+    fn no_swap_is_possible() {
+        // Given: Graph
+        //
+        //      a  ---  b
+        //      |     /
+        //      |   /
+        //      | /
+        //      c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, a, 1.into());
+
+        // When: Rewiring triangle, where each swap creates a loop or a multi edge.
+        let swaps = rewire(&mut graph, 5, &mut seeded(2));
+
+        // Then:
+        assert_eq!(swaps, 0);
+        assert_eq!(graph.edges_count(), 3);
+    }
+
+    #[test]
+    fn undirected_graph() {
+        // Given: Ring of 10 vertices with a chord from each vertex to the vertex two steps ahead.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let ids = (0..10).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        for i in 0..10 {
+            graph.add_edge_unchecked(ids[i], ids[(i + 1) % 10], 1.into());
+            graph.add_edge_unchecked(ids[i], ids[(i + 2) % 10], 2.into());
+        }
+        let degrees = degrees_of(&graph);
+        let degree_sums = degrees
+            .iter()
+            .map(|(id, (out_degree, in_degree))| (*id, out_degree + in_degree))
+            .collect::<HashMap<usize, usize>>();
+
+        // When: Rewiring the graph.
+        let swaps = rewire(&mut graph, 30, &mut seeded(3));
+
+        // Then: Degree of each vertex and weights of the edges are preserved.
+        assert_eq!(swaps, 30);
+        assert_eq!(graph.edges_count(), 20);
+        for (id, (out_degree, in_degree)) in degrees_of(&graph) {
+            assert_eq!(out_degree + in_degree, degree_sums[&id]);
+        }
+        let weight_sum = graph
+            .edges()
+            .into_iter()
+            .map(|(_, _, edge)| edge.get_weight().unwrap())
+            .sum::<usize>();
+        assert_eq!(weight_sum, 30);
+
+        // And: Graph is still simple.
+        for (src_id, dst_id, _) in graph.edges() {
+            assert_ne!(src_id, dst_id);
+            assert_eq!(graph.edges_between_unchecked(src_id, dst_id).len(), 1);
+        }
+    }
+
+    #[test]
+    fn directed_graph() {
+        // Given: Directed ring of 8 vertices with an edge from each vertex to the vertex three steps ahead.
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let ids = (0..8).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        for i in 0..8 {
+            graph.add_edge_unchecked(ids[i], ids[(i + 1) % 8], 1.into());
+            graph.add_edge_unchecked(ids[i], ids[(i + 3) % 8], 1.into());
+        }
+        let degrees = degrees_of(&graph);
+
+        // When: Rewiring the graph.
+        let swaps = rewire(&mut graph, 20, &mut seeded(4));
+
+        // Then: Both in degree and out degree of each vertex are preserved.
+        assert!(swaps > 0);
+        assert_eq!(degrees_of(&graph), degrees);
+        for (src_id, dst_id, _) in graph.edges() {
+            assert_ne!(src_id, dst_id);
+            assert_eq!(graph.edges_between_unchecked(src_id, dst_id).len(), 1);
+        }
+    }
+}
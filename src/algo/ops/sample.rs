@@ -0,0 +1,320 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use magnitude::Magnitude;
+use num_traits::ToPrimitive;
+
+use crate::graph::{Edge, OrdFloat};
+use crate::provide::{Edges, Vertices};
+use crate::util::unit;
+
+/// Samples `k` distinct vertices uniformly at random, using reservoir sampling.
+///
+/// # Arguments
+/// * `graph`: Graph to sample its vertices.
+/// * `k`: Number of vertices to sample. If the graph has at most `k` vertices, all of them are returned.
+/// * `rng`: Source of randomness: when called with `n`, returns a uniformly random number in range of 0 to `n` - 1.
+///   Use [`seeded`](crate::util::seeded) for reproducible results, or `util::from_rng` with the `rand` feature.
+///
+/// # Returns
+/// Id of the sampled vertices, in no particular order.
+///
+/// # Complexity
+/// O(|V|)
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::ops::sample_vertices;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+/// use prepona::util::seeded;
+///
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let vertices: Vec<usize> = (0..10).map(|_| graph.add_vertex()).collect();
+///
+/// let sample = sample_vertices(&graph, 3, &mut seeded(1));
+///
+/// assert_eq!(sample.len(), 3);
+/// assert!(sample.iter().all(|vertex_id| vertices.contains(vertex_id)));
+/// ```
+pub fn sample_vertices<G, R>(graph: &G, k: usize, rng: &mut R) -> Vec<usize>
+where
+    G: Vertices,
+    R: FnMut(usize) -> usize,
+{
+    reservoir(graph.vertices().into_iter(), k, rng)
+}
+
+/// Samples `k` distinct vertices, where probability of sampling each vertex is proportional to its weight.
+///
+/// Uses weighted reservoir sampling(Efraimidis & Spirakis): each vertex gets a random key that grows with its weight, and vertices with the `k` largest keys are sampled.
+/// So it matches drawing vertices one by one without replacement, each time with probability proportional to the weight.
+///
+/// # Arguments
+/// * `graph`: Graph to sample its vertices.
+/// * `k`: Number of vertices to sample.
+/// * `weight_of`: Weight of a vertex, given its id. For example its degree.
+///   Vertices with weight that is not positive or not finite are never sampled.
+/// * `rng`: Source of randomness: when called with `n`, returns a uniformly random number in range of 0 to `n` - 1.
+///
+/// # Returns
+/// Id of the sampled vertices, in no particular order. It has less than `k` vertices if less than `k` vertices have a positive weight.
+///
+/// # Complexity
+/// O(|V| * log(k))
+pub fn sample_vertices_weighted<G, F, R>(
+    graph: &G,
+    k: usize,
+    weight_of: F,
+    rng: &mut R,
+) -> Vec<usize>
+where
+    G: Vertices,
+    F: Fn(usize) -> f64,
+    R: FnMut(usize) -> usize,
+{
+    let vertices = graph
+        .vertices()
+        .into_iter()
+        .map(|vertex_id| (vertex_id, weight_of(vertex_id)));
+
+    weighted_reservoir(vertices, k, rng)
+}
+
+/// Samples `k` distinct edges uniformly at random, using reservoir sampling.
+///
+/// # Arguments
+/// * `graph`: Graph to sample its edges.
+/// * `k`: Number of edges to sample. If the graph has at most `k` edges, all of them are returned.
+/// * `rng`: Source of randomness: when called with `n`, returns a uniformly random number in range of 0 to `n` - 1.
+///
+/// # Returns
+/// Sampled edges in the format of: (`src_id`, `dst_id`, `edge`), in no particular order.
+///
+/// # Complexity
+/// O(|E|)
+pub fn sample_edges<'a, W, E, G, R>(
+    graph: &'a G,
+    k: usize,
+    rng: &mut R,
+) -> Vec<(usize, usize, &'a E)>
+where
+    E: Edge<W>,
+    G: Edges<W, E>,
+    R: FnMut(usize) -> usize,
+{
+    reservoir(graph.edges().into_iter(), k, rng)
+}
+
+/// Samples `k` distinct edges, where probability of sampling each edge is proportional to its weight.
+///
+/// Works like [`sample_vertices_weighted`](crate::algo::ops::sample_vertices_weighted), using weights of the edges.
+///
+/// # Arguments
+/// * `graph`: Graph to sample its edges.
+/// * `k`: Number of edges to sample.
+/// * `rng`: Source of randomness: when called with `n`, returns a uniformly random number in range of 0 to `n` - 1.
+///
+/// # Returns
+/// Sampled edges in the format of: (`src_id`, `dst_id`, `edge`), in no particular order.
+/// Edges with weight that is not positive or is infinite are never sampled, so it has less than `k` edges if less than `k` edges have a positive finite weight.
+///
+/// # Complexity
+/// O(|E| * log(k))
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::ops::sample_edges_weighted;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+/// use prepona::util::seeded;
+///
+/// // Given: Graph
+/// //
+/// //      a  --(0)--  b  --(5)--  c
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 0.into());
+/// let bc = graph.add_edge_unchecked(b, c, 5.into());
+///
+/// let sample = sample_edges_weighted(&graph, 2, &mut seeded(3));
+///
+/// // Edge with weight of zero is never sampled.
+/// assert_eq!(sample.len(), 1);
+/// assert_eq!(sample[0].2.get_id(), bc);
+/// ```
+pub fn sample_edges_weighted<'a, W, E, G, R>(
+    graph: &'a G,
+    k: usize,
+    rng: &mut R,
+) -> Vec<(usize, usize, &'a E)>
+where
+    W: ToPrimitive,
+    E: Edge<W>,
+    G: Edges<W, E>,
+    R: FnMut(usize) -> usize,
+{
+    let edges = graph.edges().into_iter().map(|(src_id, dst_id, edge)| {
+        let weight = match edge.get_weight() {
+            Magnitude::Finite(weight) => weight.to_f64().unwrap_or(0.0),
+            _ => 0.0,
+        };
+
+        ((src_id, dst_id, edge), weight)
+    });
+
+    weighted_reservoir(edges, k, rng)
+}
+
+// Keeps the first `k` items, then replaces a random one of them with the i-th item with probability of k/i(Algorithm R).
+fn reservoir<T, I, R>(items: I, k: usize, rng: &mut R) -> Vec<T>
+where
+    I: Iterator<Item = T>,
+    R: FnMut(usize) -> usize,
+{
+    let mut sample = Vec::with_capacity(k);
+
+    for (index, item) in items.enumerate() {
+        if index < k {
+            sample.push(item);
+        } else {
+            let slot = rng(index + 1);
+            if slot < k {
+                sample[slot] = item;
+            }
+        }
+    }
+
+    sample
+}
+
+// Keeps the `k` items with the largest key of ln(u) / weight, where u is uniform in (0, 1]. It's the same as u^(1 / weight) but does not underflow.
+fn weighted_reservoir<T, I, R>(items: I, k: usize, rng: &mut R) -> Vec<T>
+where
+    I: Iterator<Item = (T, f64)>,
+    R: FnMut(usize) -> usize,
+{
+    if k == 0 {
+        return vec![];
+    }
+
+    let mut sample = Vec::with_capacity(k);
+
+    // Key and index of each item in `sample`, with the smallest key on top.
+    let mut keys: BinaryHeap<Reverse<(OrdFloat<f64>, usize)>> = BinaryHeap::with_capacity(k);
+
+    for (item, weight) in items {
+        if weight <= 0.0 || !weight.is_finite() {
+            continue;
+        }
+
+        // Keys of tiny weights can underflow to negative infinity. They are the least likely to be kept anyway.
+        let key = OrdFloat::new(((1.0 - unit(rng)).ln() / weight).max(f64::MIN)).unwrap();
+
+        if sample.len() < k {
+            keys.push(Reverse((key, sample.len())));
+            sample.push(item);
+        } else if let Some(Reverse((min_key, slot))) = keys.peek().copied() {
+            if key > min_key {
+                keys.pop();
+                keys.push(Reverse((key, slot)));
+                sample[slot] = item;
+            }
+        }
+    }
+
+    sample
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::Graph;
+    use crate::storage::Mat;
+    use crate::util::seeded;
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        assert!(sample_vertices(&graph, 3, &mut seeded(0)).is_empty());
+        assert!(sample_edges(&graph, 3, &mut seeded(0)).is_empty());
+        assert!(sample_vertices_weighted(&graph, 3, |_| 1.0, &mut seeded(0)).is_empty());
+    }
+
+    #[test]
+    fn small_graph_is_sampled_entirely() {
+        // Given: Triangle.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let ids: Vec<usize> = (0..3).map(|_| graph.add_vertex()).collect();
+        graph.add_edge_unchecked(ids[0], ids[1], 1.into());
+        graph.add_edge_unchecked(ids[1], ids[2], 2.into());
+        graph.add_edge_unchecked(ids[2], ids[0], 3.into());
+
+        // When:
+        let mut vertices = sample_vertices(&graph, 5, &mut seeded(0));
+        let edges = sample_edges(&graph, 5, &mut seeded(0));
+        let weighted_edges = sample_edges_weighted(&graph, 3, &mut seeded(0));
+
+        // Then:
+        vertices.sort_unstable();
+        assert_eq!(vertices, ids);
+        assert_eq!(edges.len(), 3);
+        assert_eq!(weighted_edges.len(), 3);
+        assert!(sample_vertices(&graph, 0, &mut seeded(0)).is_empty());
+        assert!(sample_edges_weighted(&graph, 0, &mut seeded(0)).is_empty());
+    }
+
+    #[test]
+    fn uniform_samples_are_distinct_and_balanced() {
+        // Given:
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let ids: Vec<usize> = (0..10).map(|_| graph.add_vertex()).collect();
+        let mut counts = vec![0; 10];
+        let mut rng = seeded(5);
+
+        // When:
+        for _ in 0..2000 {
+            let mut sample = sample_vertices(&graph, 3, &mut rng);
+            sample.sort_unstable();
+            sample.dedup();
+            assert_eq!(sample.len(), 3);
+
+            for vertex_id in sample {
+                counts[ids.iter().position(|id| *id == vertex_id).unwrap()] += 1;
+            }
+        }
+
+        // Then: Each vertex is sampled with probability of 0.3.
+        assert!(counts.iter().all(|count| (500..700).contains(count)));
+    }
+
+    #[test]
+    fn weighted_samples_prefer_heavy_vertices() {
+        // Given: Vertices with weights of 0, 1 and 9.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let ids: Vec<usize> = (0..3).map(|_| graph.add_vertex()).collect();
+        let weight_of =
+            |vertex_id: usize| [0.0, 1.0, 9.0][ids.iter().position(|id| *id == vertex_id).unwrap()];
+        let mut counts = vec![0; 3];
+        let mut rng = seeded(8);
+
+        // When: Sampling one vertex many times.
+        for _ in 0..2000 {
+            for vertex_id in sample_vertices_weighted(&graph, 1, weight_of, &mut rng) {
+                counts[ids.iter().position(|id| *id == vertex_id).unwrap()] += 1;
+            }
+        }
+
+        // Then: Vertices are sampled with probabilities of 0, 0.1 and 0.9.
+        assert_eq!(counts[0], 0);
+        assert!((120..280).contains(&counts[1]));
+        assert_eq!(counts[1] + counts[2], 2000);
+    }
+}
@@ -0,0 +1,396 @@
+use std::collections::VecDeque;
+
+use anyhow::Result;
+
+use crate::graph::Error;
+use crate::provide::{self, IdMap};
+
+/// Answers reachability queries about vertices of a graph.
+///
+/// Neighbors of vertices are copied when `Reachability` is initialized, so queries reflect the graph at that moment
+/// and it must be reinitialized after the graph is mutated.
+///
+/// Without an index, each query performs a search that takes O(|V| + |E|).
+/// For repeated queries on a directed acyclic graph, [`build_index`](Reachability::build_index) can be used to label each vertex
+/// with intervals of positions of its descendants in a reverse topological order, so [`is_reachable`](Reachability::is_reachable) takes O(log(|V|)).
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::Reachability;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  -->  b  -->  c       d
+/// //
+/// let mut graph = MatGraph::init(DiMat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+///
+/// let mut reachability = Reachability::init(&graph);
+/// assert_eq!(reachability.reachable_from(a), vec![b, c]);
+/// assert_eq!(reachability.ancestors(c), vec![a, b]);
+///
+/// reachability.build_index().unwrap();
+/// assert!(reachability.is_reachable(a, c));
+/// assert!(!reachability.is_reachable(c, a));
+/// assert!(!reachability.is_reachable(a, d));
+/// ```
+pub struct Reachability {
+    id_map: IdMap,
+    out_neighbors: Vec<Vec<usize>>,
+    in_neighbors: Vec<Vec<usize>>,
+
+    index: Option<Index>,
+}
+
+// Position of each vertex in a reverse topological order, and intervals of positions of vertices reachable from it.
+type Index = (Vec<usize>, Vec<Vec<(usize, usize)>>);
+
+impl Reachability {
+    /// # Arguments
+    /// `graph`: Graph to answer reachability queries about.
+    ///
+    /// # Returns
+    /// Initialized structure without an index.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|)
+    pub fn init<G>(graph: &G) -> Self
+    where
+        G: provide::Vertices + provide::Neighbors,
+    {
+        let id_map = graph.continuous_id_map();
+        let vertex_count = graph.vertex_count();
+
+        let mut out_neighbors = vec![vec![]; vertex_count];
+        let mut in_neighbors = vec![vec![]; vertex_count];
+        for virt_id in 0..vertex_count {
+            let real_id = id_map.real_id_of(virt_id);
+
+            for n_real_id in graph.neighbors_unchecked(real_id) {
+                let n_virt_id = id_map.virt_id_of(n_real_id);
+
+                out_neighbors[virt_id].push(n_virt_id);
+                in_neighbors[n_virt_id].push(virt_id);
+            }
+        }
+
+        Reachability {
+            id_map,
+            out_neighbors,
+            in_neighbors,
+            index: None,
+        }
+    }
+
+    /// Builds an index to answer [`is_reachable`](Reachability::is_reachable) queries without searching the graph.
+    ///
+    /// # Returns
+    /// * `Ok`: If index is built.
+    /// * `Err`: [`Cycle`](crate::graph::ErrorKind::Cycle) if graph contains a cycle. Note that each undirected edge forms a cycle.
+    ///
+    /// # Complexity
+    /// O(|V| * |E|) in the worst case, but close to O(|V| + |E|) for graphs that are close to a forest.
+    pub fn build_index(&mut self) -> Result<()> {
+        let vertex_count = self.out_neighbors.len();
+
+        // Kahn's algorithm to find a topological order.
+        let mut in_degrees = self
+            .in_neighbors
+            .iter()
+            .map(|in_neighbors| in_neighbors.len())
+            .collect::<Vec<usize>>();
+        let mut queue = (0..vertex_count)
+            .filter(|virt_id| in_degrees[*virt_id] == 0)
+            .collect::<VecDeque<usize>>();
+        let mut order = vec![];
+        while let Some(virt_id) = queue.pop_front() {
+            order.push(virt_id);
+
+            for n_virt_id in &self.out_neighbors[virt_id] {
+                in_degrees[*n_virt_id] -= 1;
+
+                if in_degrees[*n_virt_id] == 0 {
+                    queue.push_back(*n_virt_id);
+                }
+            }
+        }
+
+        if order.len() != vertex_count {
+            // Any vertex that is not in the order has an incoming edge from another vertex that is not in the order.
+            let dst_id = (0..vertex_count)
+                .find(|virt_id| in_degrees[*virt_id] != 0)
+                .unwrap();
+            let src_id = *self.in_neighbors[dst_id]
+                .iter()
+                .find(|virt_id| in_degrees[**virt_id] != 0)
+                .unwrap();
+
+            Err(Error::new_c(
+                self.id_map.real_id_of(src_id),
+                self.id_map.real_id_of(dst_id),
+            ))?
+        }
+
+        // Vertices are labeled in reverse topological order so each vertex is labeled after all of its descendants.
+        let mut position_of = vec![0; vertex_count];
+        let mut intervals_of = vec![vec![]; vertex_count];
+        for (position, virt_id) in order.into_iter().rev().enumerate() {
+            position_of[virt_id] = position;
+
+            let mut intervals = self.out_neighbors[virt_id]
+                .iter()
+                .flat_map(|n_virt_id| intervals_of[*n_virt_id].iter().copied())
+                .chain(std::iter::once((position, position)))
+                .collect::<Vec<(usize, usize)>>();
+            intervals.sort_unstable();
+
+            let mut merged: Vec<(usize, usize)> = vec![];
+            for (start, end) in intervals {
+                match merged.last_mut() {
+                    Some(last) if start <= last.1 + 1 => last.1 = last.1.max(end),
+                    _ => merged.push((start, end)),
+                }
+            }
+
+            intervals_of[virt_id] = merged;
+        }
+
+        self.index = Some((position_of, intervals_of));
+
+        Ok(())
+    }
+
+    /// # Returns
+    /// `true` if index is built, `false` otherwise.
+    pub fn is_indexed(&self) -> bool {
+        self.index.is_some()
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `true`: If there is a path from source to destination. Each vertex is reachable from itself.
+    /// * `false`: Otherwise.
+    ///
+    /// # Panics
+    /// If either of the vertices is not in the graph.
+    ///
+    /// # Complexity
+    /// * O(log(|V|)): If index is built.
+    /// * O(|V| + |E|): Otherwise.
+    pub fn is_reachable(&self, src_id: usize, dst_id: usize) -> bool {
+        let src_virt_id = self.id_map.virt_id_of(src_id);
+        let dst_virt_id = self.id_map.virt_id_of(dst_id);
+
+        if let Some((position_of, intervals_of)) = &self.index {
+            let position = position_of[dst_virt_id];
+            let intervals = &intervals_of[src_virt_id];
+
+            // Index of the first interval that ends at or after position.
+            let index = intervals.partition_point(|(_, end)| *end < position);
+
+            index < intervals.len() && intervals[index].0 <= position
+        } else {
+            src_virt_id == dst_virt_id
+                || Self::search(&self.out_neighbors, src_virt_id).contains(&dst_virt_id)
+        }
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Id of the vertices that are reachable from vertex with id: `vertex_id`(its descendants), sorted in ascending order.
+    /// The vertex itself is not included.
+    ///
+    /// # Panics
+    /// If vertex is not in the graph.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|)
+    pub fn reachable_from(&self, vertex_id: usize) -> Vec<usize> {
+        self.real_ids_of(Self::search(
+            &self.out_neighbors,
+            self.id_map.virt_id_of(vertex_id),
+        ))
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Id of the vertices that vertex with id: `vertex_id` is reachable from(its ancestors), sorted in ascending order.
+    /// The vertex itself is not included.
+    ///
+    /// # Panics
+    /// If vertex is not in the graph.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|)
+    pub fn ancestors(&self, vertex_id: usize) -> Vec<usize> {
+        self.real_ids_of(Self::search(
+            &self.in_neighbors,
+            self.id_map.virt_id_of(vertex_id),
+        ))
+    }
+
+    // Returns virtual id of the vertices reachable from `start_id` through `neighbors`, excluding `start_id` itself.
+    fn search(neighbors: &[Vec<usize>], start_id: usize) -> Vec<usize> {
+        let mut is_visited = vec![false; neighbors.len()];
+        is_visited[start_id] = true;
+
+        let mut queue = VecDeque::new();
+        queue.push_back(start_id);
+
+        let mut visited = vec![];
+        while let Some(virt_id) = queue.pop_front() {
+            for n_virt_id in &neighbors[virt_id] {
+                if !is_visited[*n_virt_id] {
+                    is_visited[*n_virt_id] = true;
+                    visited.push(*n_virt_id);
+                    queue.push_back(*n_virt_id);
+                }
+            }
+        }
+
+        visited
+    }
+
+    fn real_ids_of(&self, virt_ids: Vec<usize>) -> Vec<usize> {
+        let mut real_ids = virt_ids
+            .into_iter()
+            .map(|virt_id| self.id_map.real_id_of(virt_id))
+            .collect::<Vec<usize>>();
+        real_ids.sort_unstable();
+
+        real_ids
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::*;
+    use crate::storage::{DiMat, Mat};
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(DiMat::<usize>::init());
+
+        let mut reachability = Reachability::init(&graph);
+
+        assert!(reachability.build_index().is_ok());
+        assert!(reachability.is_indexed());
+    }
+
+    #[test]
+    fn directed_acyclic_graph() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c  -->  d
+        //      |               ^
+        //      v               |
+        //      e  -->  f  -----'       g
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        let g = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+        graph.add_edge_unchecked(a, e, 1.into());
+        graph.add_edge_unchecked(e, f, 1.into());
+        graph.add_edge_unchecked(f, c, 1.into());
+
+        // When: Initializing reachability with and without index.
+        let reachability = Reachability::init(&graph);
+        let mut indexed_reachability = Reachability::init(&graph);
+        indexed_reachability.build_index().unwrap();
+
+        // Then:
+        assert_eq!(reachability.reachable_from(a), vec![b, c, d, e, f]);
+        assert_eq!(reachability.reachable_from(f), vec![c, d]);
+        assert!(reachability.reachable_from(g).is_empty());
+        assert_eq!(reachability.ancestors(c), vec![a, b, e, f]);
+        assert!(reachability.ancestors(a).is_empty());
+
+        let vertices = vec![a, b, c, d, e, f, g];
+        for src_id in &vertices {
+            for dst_id in &vertices {
+                let expected =
+                    src_id == dst_id || reachability.reachable_from(*src_id).contains(dst_id);
+
+                assert_eq!(reachability.is_reachable(*src_id, *dst_id), expected);
+                assert_eq!(
+                    indexed_reachability.is_reachable(*src_id, *dst_id),
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn directed_graph_with_cycle() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c
+        //      ^               |
+        //      '---------------'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, a, 1.into());
+
+        // When: Initializing reachability.
+        let mut reachability = Reachability::init(&graph);
+
+        // Then:
+        assert!(reachability.build_index().is_err());
+        assert!(!reachability.is_indexed());
+        assert_eq!(reachability.reachable_from(a), vec![b, c]);
+        assert_eq!(reachability.ancestors(a), vec![b, c]);
+        assert!(reachability.is_reachable(c, b));
+    }
+
+    #[test]
+    fn undirected_graph() {
+        // Given: Graph
+        //
+        //      a  ---  b       c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+
+        // When: Initializing reachability.
+        let mut reachability = Reachability::init(&graph);
+
+        // Then:
+        assert!(reachability.build_index().is_err());
+        assert_eq!(reachability.reachable_from(b), vec![a]);
+        assert!(reachability.is_reachable(b, a));
+        assert!(!reachability.is_reachable(a, c));
+    }
+}
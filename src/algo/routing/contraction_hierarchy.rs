@@ -0,0 +1,686 @@
+use std::any::Any;
+use std::collections::HashMap;
+
+use anyhow::Result;
+use magnitude::Magnitude;
+use num_traits::{CheckedAdd, Unsigned, Zero};
+
+use crate::algo::shortest_path::checked_add;
+use crate::graph::{Edge, EdgeDir, Error};
+use crate::provide::{Edges, Graph, IdMap, Vertices};
+use crate::util::IndexedHeap;
+
+// Maximum number of vertices that a witness search settles before giving up.
+// Giving up early only adds shortcuts that are not necessary, it never makes queries incorrect.
+const WITNESS_SEARCH_LIMIT: usize = 128;
+
+// What an arc of the hierarchy represents.
+#[derive(Clone, Copy)]
+enum Via {
+    // An edge of the graph with the given id.
+    Edge(usize),
+
+    // A shortcut that passes through the given(virtual) vertex.
+    Vertex(usize),
+}
+
+// Arcs from or to each vertex that is not contracted yet, mapped to their weight.
+type Arcs<W> = Vec<HashMap<usize, Magnitude<W>>>;
+
+// Shortcuts in the format of: (src_id, dst_id, weight). Weight is `None` if it does not fit in `W`.
+type Shortcuts<W> = Vec<(usize, usize, Option<Magnitude<W>>)>;
+
+// Edges of a path in the format of: (src_id, dst_id, edge_id).
+type EdgePath = Vec<(usize, usize, usize)>;
+
+/// Preprocesses a graph so point to point shortest path queries can be answered much faster than running [`Dijkstra`](crate::algo::Dijkstra) for each of them.
+///
+/// Vertices are contracted one by one, in the order of their importance. Contracting a vertex removes it and adds a shortcut between each pair of its neighbors
+/// whose shortest path passes through it. Each vertex gets a rank based on when it is contracted, and queries only relax arcs that go toward vertices with higher rank,
+/// from both source and destination.
+///
+/// The hierarchy is a snapshot of the graph, so it must be rebuilt after the graph is mutated.
+/// Weights must be non-negative, and edges with infinite weight are ignored.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::ContractionHierarchy;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  --(1)--  b  --(2)--  c
+/// //      |                       |
+/// //      '-----------(5)---------'
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 2.into());
+/// graph.add_edge_unchecked(a, c, 5.into());
+///
+/// let ch = ContractionHierarchy::init(&graph).unwrap();
+///
+/// assert_eq!(ch.distance(a, c).unwrap(), 3.into());
+/// assert_eq!(ch.path(a, c).unwrap().unwrap(), vec![a, b, c]);
+/// ```
+pub struct ContractionHierarchy<W> {
+    id_map: IdMap,
+
+    // Arcs from each vertex to vertices with higher rank.
+    up_arcs: Vec<Vec<(usize, Magnitude<W>)>>,
+
+    // Arcs to each vertex from vertices with higher rank, stored at the vertex with lower rank.
+    down_arcs: Vec<Vec<(usize, Magnitude<W>)>>,
+
+    arc_via: HashMap<(usize, usize), Via>,
+    shortcut_count: usize,
+}
+
+impl<W: Copy + Ord + Zero + Any + Unsigned + CheckedAdd> ContractionHierarchy<W> {
+    /// Builds the hierarchy.
+    ///
+    /// # Arguments
+    /// `graph`: Graph to build the hierarchy for.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing the hierarchy that answers shortest path queries about the graph.
+    /// * `Err`: [`WeightOverflow`](crate::graph::ErrorKind::WeightOverflow) if weight of a shortcut that is needed does not fit in `W`.
+    ///
+    /// # Complexity
+    /// Depends heavily on structure of the graph. It's close to O((|V| + |E|) * log(|V|)) for sparse graphs like road networks.
+    pub fn init<E, Ty, G>(graph: &G) -> Result<Self>
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+    {
+        let id_map = graph.continuous_id_map();
+        let vertex_count = graph.vertex_count();
+
+        let mut ch = ContractionHierarchy {
+            id_map,
+            up_arcs: vec![vec![]; vertex_count],
+            down_arcs: vec![vec![]; vertex_count],
+            arc_via: HashMap::new(),
+            shortcut_count: 0,
+        };
+
+        let mut out_arcs = vec![HashMap::new(); vertex_count];
+        let mut in_arcs = vec![HashMap::new(); vertex_count];
+        for (src_id, dst_id, edge) in graph.as_directed_edges() {
+            let weight = *edge.get_weight();
+
+            if src_id != dst_id && weight.is_finite() {
+                let src_virt_id = ch.id_map.virt_id_of(src_id);
+                let dst_virt_id = ch.id_map.virt_id_of(dst_id);
+
+                ch.add_arc(
+                    &mut out_arcs,
+                    &mut in_arcs,
+                    (src_virt_id, dst_virt_id),
+                    weight,
+                    Via::Edge(edge.get_id()),
+                );
+            }
+        }
+
+        let mut contracted_neighbors = vec![0; vertex_count];
+        let mut witness_queue = IndexedHeap::with_capacity(vertex_count);
+
+        // Importance of vertices is updated lazily: when a vertex is popped, its importance is recomputed and if it's
+        // no longer the least important vertex, it's pushed back.
+        let mut queue = IndexedHeap::with_capacity(vertex_count);
+        for virt_id in 0..vertex_count {
+            let shortcuts = Self::shortcuts_of(&out_arcs, &in_arcs, &mut witness_queue, virt_id)?;
+            queue.push(
+                virt_id,
+                Self::importance_of(&out_arcs, &in_arcs, &shortcuts, virt_id, 0),
+            );
+        }
+
+        while let Some((virt_id, _)) = queue.pop() {
+            let shortcuts = Self::shortcuts_of(&out_arcs, &in_arcs, &mut witness_queue, virt_id)?;
+            let importance = Self::importance_of(
+                &out_arcs,
+                &in_arcs,
+                &shortcuts,
+                virt_id,
+                contracted_neighbors[virt_id],
+            );
+
+            if let Some((_, next_importance)) = queue.peek() {
+                if importance > *next_importance {
+                    queue.push(virt_id, importance);
+                    continue;
+                }
+            }
+
+            // All remaining neighbors will be contracted later, so they get a higher rank.
+            ch.up_arcs[virt_id] = out_arcs[virt_id].iter().map(|(id, w)| (*id, *w)).collect();
+            ch.down_arcs[virt_id] = in_arcs[virt_id].iter().map(|(id, w)| (*id, *w)).collect();
+
+            for (src_id, dst_id, weight) in shortcuts {
+                let weight = weight.ok_or_else(Error::new_wo)?;
+
+                if ch.add_arc(
+                    &mut out_arcs,
+                    &mut in_arcs,
+                    (src_id, dst_id),
+                    weight,
+                    Via::Vertex(virt_id),
+                ) {
+                    ch.shortcut_count += 1;
+                }
+            }
+
+            for dst_id in out_arcs[virt_id].keys() {
+                in_arcs[*dst_id].remove(&virt_id);
+                contracted_neighbors[*dst_id] += 1;
+            }
+            for src_id in in_arcs[virt_id].keys() {
+                out_arcs[*src_id].remove(&virt_id);
+                contracted_neighbors[*src_id] += 1;
+            }
+            out_arcs[virt_id].clear();
+            in_arcs[virt_id].clear();
+        }
+
+        Ok(ch)
+    }
+
+    // Adds arc from `src_id` to `dst_id` if there is no cheaper arc between them already, and returns whether it's added.
+    fn add_arc(
+        &mut self,
+        out_arcs: &mut Arcs<W>,
+        in_arcs: &mut Arcs<W>,
+        (src_id, dst_id): (usize, usize),
+        weight: Magnitude<W>,
+        via: Via,
+    ) -> bool {
+        let is_cheaper = out_arcs[src_id]
+            .get(&dst_id)
+            .is_none_or(|current_weight| weight < *current_weight);
+
+        if is_cheaper {
+            out_arcs[src_id].insert(dst_id, weight);
+            in_arcs[dst_id].insert(src_id, weight);
+            self.arc_via.insert((src_id, dst_id), via);
+        }
+
+        is_cheaper
+    }
+
+    // Shortcuts that are needed if vertex with virtual id: `virt_id` gets contracted.
+    // `witness_queue` must be empty, and is left empty.
+    fn shortcuts_of(
+        out_arcs: &Arcs<W>,
+        in_arcs: &Arcs<W>,
+        witness_queue: &mut IndexedHeap<Magnitude<W>>,
+        virt_id: usize,
+    ) -> Result<Shortcuts<W>> {
+        let mut shortcuts = vec![];
+
+        for (src_id, in_weight) in &in_arcs[virt_id] {
+            // Weight of each shortcut from `src_id`, where `None` means it does not fit in `W`.
+            let mut weights = vec![];
+            for (dst_id, out_weight) in &out_arcs[virt_id] {
+                weights.push((*dst_id, checked_add(*in_weight, *out_weight)?));
+            }
+
+            // A weight that does not fit in `W` is larger than every other weight.
+            let max_weight = weights
+                .iter()
+                .map(|(_, weight)| weight.unwrap_or(Magnitude::PosInfinite))
+                .max();
+
+            let max_weight = match max_weight {
+                Some(max_weight) => max_weight,
+                None => continue,
+            };
+
+            let witness_dist =
+                Self::witness_search(out_arcs, witness_queue, *src_id, virt_id, max_weight)?;
+
+            for (dst_id, weight) in weights {
+                let has_witness = witness_dist.get(&dst_id).is_some_and(|witness_weight| {
+                    weight.is_none_or(|weight| *witness_weight <= weight)
+                });
+
+                if dst_id != *src_id && !has_witness {
+                    shortcuts.push((*src_id, dst_id, weight));
+                }
+            }
+        }
+
+        Ok(shortcuts)
+    }
+
+    // Dijkstra from `src_id` that does not pass through `excluded_id` and stops at `max_weight`.
+    // `queue` must be empty, and is left empty.
+    fn witness_search(
+        out_arcs: &Arcs<W>,
+        queue: &mut IndexedHeap<Magnitude<W>>,
+        src_id: usize,
+        excluded_id: usize,
+        max_weight: Magnitude<W>,
+    ) -> Result<HashMap<usize, Magnitude<W>>> {
+        let mut dist = HashMap::new();
+        let mut settled_count = 0;
+
+        dist.insert(src_id, W::zero().into());
+        queue.push(src_id, W::zero().into());
+
+        while let Some((virt_id, weight)) = queue.pop() {
+            if weight > max_weight || settled_count >= WITNESS_SEARCH_LIMIT {
+                break;
+            }
+            settled_count += 1;
+
+            for (n_id, arc_weight) in &out_arcs[virt_id] {
+                if *n_id == excluded_id {
+                    continue;
+                }
+
+                // Paths whose weight does not fit in `W` can not be witnesses.
+                let alt = match checked_add(weight, *arc_weight)? {
+                    Some(alt) => alt,
+                    None => continue,
+                };
+
+                if dist.get(n_id).is_none_or(|n_weight| alt < *n_weight) {
+                    dist.insert(*n_id, alt);
+                    queue.push_or_decrease(*n_id, alt);
+                }
+            }
+        }
+        queue.clear();
+
+        Ok(dist)
+    }
+
+    // Vertices whose contraction adds fewer arcs than it removes, and are far from other contracted vertices, are less important.
+    fn importance_of(
+        out_arcs: &Arcs<W>,
+        in_arcs: &Arcs<W>,
+        shortcuts: &[(usize, usize, Option<Magnitude<W>>)],
+        virt_id: usize,
+        contracted_neighbors: usize,
+    ) -> isize {
+        let removed_arc_count = out_arcs[virt_id].len() + in_arcs[virt_id].len();
+
+        shortcuts.len() as isize - removed_arc_count as isize + contracted_neighbors as isize
+    }
+
+    /// # Returns
+    /// Number of shortcuts that are added while building the hierarchy.
+    pub fn shortcut_count(&self) -> usize {
+        self.shortcut_count
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing weight of the shortest path from source to destination. `PosInfinite` if destination is not reachable from source.
+    /// * `Err`: [`WeightOverflow`](crate::graph::ErrorKind::WeightOverflow) if destination is reachable but weight of the shortest path does not fit in `W`.
+    ///
+    /// # Panics
+    /// If either of the vertices is not in the graph.
+    pub fn distance(&self, src_id: usize, dst_id: usize) -> Result<Magnitude<W>> {
+        Ok(self
+            .query(src_id, dst_id)?
+            .map_or(Magnitude::PosInfinite, |(distance, _)| distance))
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing
+    ///   * `Some`: Containing id of the vertices on the shortest path from source to destination, including both of them.
+    ///   * `None`: If destination is not reachable from source.
+    /// * `Err`: [`WeightOverflow`](crate::graph::ErrorKind::WeightOverflow) if destination is reachable but weight of the shortest path does not fit in `W`.
+    ///
+    /// # Panics
+    /// If either of the vertices is not in the graph.
+    pub fn path(&self, src_id: usize, dst_id: usize) -> Result<Option<Vec<usize>>> {
+        Ok(self.edge_path(src_id, dst_id)?.map(|edge_path| {
+            std::iter::once(src_id)
+                .chain(edge_path.into_iter().map(|(_, dst_id, _)| dst_id))
+                .collect()
+        }))
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing
+    ///   * `Some`: Containing edges of the shortest path from source to destination in the format of: (`src_id`, `dst_id`, `edge_id`).
+    ///   * `None`: If destination is not reachable from source.
+    /// * `Err`: [`WeightOverflow`](crate::graph::ErrorKind::WeightOverflow) if destination is reachable but weight of the shortest path does not fit in `W`.
+    ///
+    /// # Panics
+    /// If either of the vertices is not in the graph.
+    pub fn edge_path(
+        &self,
+        src_id: usize,
+        dst_id: usize,
+    ) -> Result<Option<Vec<(usize, usize, usize)>>> {
+        Ok(self.query(src_id, dst_id)?.map(|(_, edge_path)| edge_path))
+    }
+
+    // Bidirectional Dijkstra that only relaxes arcs toward vertices with higher rank.
+    fn query(&self, src_id: usize, dst_id: usize) -> Result<Option<(Magnitude<W>, EdgePath)>> {
+        let src_virt_id = self.id_map.virt_id_of(src_id);
+        let dst_virt_id = self.id_map.virt_id_of(dst_id);
+
+        // Index 0 is the search from source over up arcs and index 1 is the search from destination over down arcs.
+        let arcs = [&self.up_arcs, &self.down_arcs];
+        let mut dist = [HashMap::new(), HashMap::new()];
+        let mut parent = [HashMap::new(), HashMap::new()];
+        let mut queues = [IndexedHeap::init(), IndexedHeap::init()];
+
+        for (direction, start_id) in [src_virt_id, dst_virt_id].iter().enumerate() {
+            dist[direction].insert(*start_id, Magnitude::from(W::zero()));
+            queues[direction].push(*start_id, Magnitude::from(W::zero()));
+        }
+
+        let mut best: Option<(Magnitude<W>, usize)> = None;
+
+        // Whether a path is found whose weight does not fit in `W`.
+        let mut overflowed = false;
+
+        loop {
+            // Each search stops when it can no longer find a path shorter than the best one found so far.
+            let direction = (0..2)
+                .filter(|direction| match (queues[*direction].peek(), best) {
+                    (None, _) => false,
+                    (Some((_, weight)), Some((best_weight, _))) => *weight < best_weight,
+                    (Some(_), None) => true,
+                })
+                .min_by_key(|direction| queues[*direction].peek().map(|(_, weight)| *weight));
+
+            let direction = match direction {
+                Some(direction) => direction,
+                None => break,
+            };
+
+            let (virt_id, weight) = queues[direction].pop().unwrap();
+
+            if let Some(other_weight) = dist[1 - direction].get(&virt_id) {
+                match checked_add(weight, *other_weight)? {
+                    Some(total_weight) => {
+                        if best.is_none_or(|(best_weight, _)| total_weight < best_weight) {
+                            best = Some((total_weight, virt_id));
+                        }
+                    }
+                    None => overflowed = true,
+                }
+            }
+
+            for (n_id, arc_weight) in &arcs[direction][virt_id] {
+                let alt = match checked_add(weight, *arc_weight)? {
+                    Some(alt) => alt,
+                    None => {
+                        overflowed = true;
+                        continue;
+                    }
+                };
+
+                if dist[direction]
+                    .get(n_id)
+                    .is_none_or(|n_weight| alt < *n_weight)
+                {
+                    dist[direction].insert(*n_id, alt);
+                    parent[direction].insert(*n_id, virt_id);
+                    queues[direction].push_or_decrease(*n_id, alt);
+                }
+            }
+        }
+
+        // Paths whose weight does not fit in `W` are longer than every other path, so they only matter if there is no other path.
+        let (distance, meeting_id) = match best {
+            Some(best) => best,
+            None if overflowed => Err(Error::new_wo())?,
+            None => return Ok(None),
+        };
+
+        // Arcs from source to the meeting vertex, followed by arcs from the meeting vertex to destination.
+        let mut arcs_on_path = vec![];
+        let mut virt_id = meeting_id;
+        while let Some(parent_id) = parent[0].get(&virt_id) {
+            arcs_on_path.push((*parent_id, virt_id));
+            virt_id = *parent_id;
+        }
+        arcs_on_path.reverse();
+
+        let mut virt_id = meeting_id;
+        while let Some(child_id) = parent[1].get(&virt_id) {
+            arcs_on_path.push((virt_id, *child_id));
+            virt_id = *child_id;
+        }
+
+        let mut edge_path = vec![];
+        for arc in arcs_on_path {
+            self.unpack(arc, &mut edge_path);
+        }
+
+        Ok(Some((distance, edge_path)))
+    }
+
+    // Replaces shortcuts with the edges they represent and appends them to `edge_path`.
+    fn unpack(&self, arc: (usize, usize), edge_path: &mut Vec<(usize, usize, usize)>) {
+        let mut stack = vec![arc];
+
+        while let Some((src_id, dst_id)) = stack.pop() {
+            match self.arc_via[&(src_id, dst_id)] {
+                Via::Edge(edge_id) => edge_path.push((
+                    self.id_map.real_id_of(src_id),
+                    self.id_map.real_id_of(dst_id),
+                    edge_id,
+                )),
+                Via::Vertex(mid_id) => {
+                    // Second half is pushed first so the first half is unpacked first.
+                    stack.push((mid_id, dst_id));
+                    stack.push((src_id, mid_id));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::{Dijkstra, PathTracer};
+    use crate::graph::MatGraph;
+    use crate::provide::*;
+    use crate::storage::{DiMat, Mat};
+
+    // Checks distance and path of each pair of vertices against Dijkstra.
+    fn assert_matches_dijkstra<Ty, G>(graph: &G)
+    where
+        Ty: EdgeDir,
+        G: Edges<usize, crate::graph::DefaultEdge<usize>>
+            + Neighbors
+            + Vertices
+            + Graph<usize, crate::graph::DefaultEdge<usize>, Ty>,
+    {
+        let ch = ContractionHierarchy::init(graph).unwrap();
+
+        for src_id in graph.vertices() {
+            let sp_subgraph = Dijkstra::init(graph).execute(graph, src_id);
+
+            for dst_id in graph.vertices() {
+                let expected = sp_subgraph.distance_to(dst_id).unwrap();
+
+                assert_eq!(ch.distance(src_id, dst_id).unwrap(), expected);
+
+                match ch.edge_path(src_id, dst_id).unwrap() {
+                    Some(edge_path) => {
+                        let tracer = PathTracer::from_edges(&edge_path);
+
+                        assert_eq!(tracer.total_weight(graph, dst_id).unwrap(), expected);
+                        assert_eq!(ch.path(src_id, dst_id).unwrap().unwrap()[0], src_id);
+                    }
+                    None => assert!(expected.is_pos_infinite()),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let ch = ContractionHierarchy::init(&graph).unwrap();
+
+        assert_eq!(ch.shortcut_count(), 0);
+    }
+
+    #[test]
+    fn path_graph() {
+        // Given: Graph
+        //
+        //      a  --(1)--  b  --(2)--  c  --(3)--  d
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 2.into());
+        graph.add_edge_unchecked(c, d, 3.into());
+
+        // When: Building contraction hierarchy.
+        let ch = ContractionHierarchy::init(&graph).unwrap();
+
+        // Then:
+        assert_eq!(ch.distance(a, d).unwrap(), 6.into());
+        assert_eq!(ch.distance(d, a).unwrap(), 6.into());
+        assert_eq!(ch.distance(b, b).unwrap(), 0.into());
+        assert_eq!(ch.path(a, d).unwrap().unwrap(), vec![a, b, c, d]);
+        assert_eq!(ch.path(d, b).unwrap().unwrap(), vec![d, c, b]);
+        assert_eq!(ch.path(c, c).unwrap().unwrap(), vec![c]);
+    }
+
+    #[test]
+    fn undirected_grid() {
+        // Given: 4 x 4 grid with different weights.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let ids = (0..16).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        for row in 0..4 {
+            for col in 0..4 {
+                let id = ids[row * 4 + col];
+
+                if col < 3 {
+                    graph.add_edge_unchecked(
+                        id,
+                        ids[row * 4 + col + 1],
+                        ((row + col) % 3 + 1).into(),
+                    );
+                }
+                if row < 3 {
+                    graph.add_edge_unchecked(
+                        id,
+                        ids[(row + 1) * 4 + col],
+                        ((row * col) % 4 + 1).into(),
+                    );
+                }
+            }
+        }
+
+        // Then:
+        assert_matches_dijkstra(&graph);
+    }
+
+    #[test]
+    fn directed_graph() {
+        // Given: Graph
+        //
+        //      a  --(1)-->  b  --(1)-->  c  --(1)-->  d
+        //      ^            |                         |
+        //     (1)          (5)                       (1)
+        //      |            v                         v
+        //      g  <--(1)--  f  <--(1)-----------------e       h
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        let g = graph.add_vertex();
+        graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+        graph.add_edge_unchecked(d, e, 1.into());
+        graph.add_edge_unchecked(e, f, 1.into());
+        graph.add_edge_unchecked(b, f, 5.into());
+        graph.add_edge_unchecked(f, g, 1.into());
+        graph.add_edge_unchecked(g, a, 1.into());
+
+        // Then:
+        assert_matches_dijkstra(&graph);
+    }
+
+    #[test]
+    fn graph_with_removed_vertex() {
+        // Given: Graph
+        //
+        //      a  --(1)--  b  --(1)--  c
+        //      |                       |
+        //      '-----------(4)---------'
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(a, c, 4.into());
+
+        // When: Removing b.
+        graph.remove_vertex_unchecked(b);
+
+        // Then:
+        assert_matches_dijkstra(&graph);
+    }
+
+    #[test]
+    fn weight_overflow() {
+        // Given: Graph
+        //
+        //      a  --(200)--  b  --(100)--  c
+        //
+        let mut graph = MatGraph::init(Mat::<u8>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 200.into());
+        graph.add_edge_unchecked(b, c, 100.into());
+
+        // When: Distance from a to c does not fit in u8.
+        let error = ContractionHierarchy::init(&graph)
+            .and_then(|ch| ch.distance(a, c))
+            .unwrap_err();
+
+        // Then:
+        assert_eq!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            &crate::graph::ErrorKind::WeightOverflow
+        );
+    }
+}
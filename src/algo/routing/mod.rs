@@ -0,0 +1,5 @@
+mod contraction_hierarchy;
+mod turn_restricted;
+
+pub use contraction_hierarchy::ContractionHierarchy;
+pub use turn_restricted::TurnRestrictedDijkstra;
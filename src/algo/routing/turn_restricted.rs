@@ -0,0 +1,382 @@
+use std::any::Any;
+
+use anyhow::Result;
+use magnitude::Magnitude;
+use num_traits::{CheckedAdd, Unsigned, Zero};
+
+use crate::algo::shortest_path::checked_add;
+use crate::graph::{Edge, EdgeDir, Error};
+use crate::provide::{Edges, Graph, IdMap, Vertices};
+use crate::util::IndexedHeap;
+
+// Edges of a path in the format of: (src_id, dst_id, edge_id).
+type EdgePath = Vec<(usize, usize, usize)>;
+
+/// Finds shortest paths in graphs where cost of a path also depends on each pair of consecutive edges, like turn penalties and forbidden turns of road networks.
+///
+/// It's Dijkstra over arcs instead of vertices(edge based routing): an arc is a traversal of an edge in one direction, and the search settles arcs,
+/// so the same vertex can be passed more than once with different incoming arcs. So it's the same as running Dijkstra on the line graph,
+/// without building the line graph.
+///
+/// Like [`ContractionHierarchy`](crate::algo::ContractionHierarchy), it's a snapshot of the graph and must be rebuilt after the graph is mutated.
+/// Weights must be non-negative, and edges with infinite weight are ignored.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::TurnRestrictedDijkstra;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+/// use magnitude::Magnitude;
+///
+/// // Given: Graph
+/// //
+/// //      a  --(1)--  b  --(1)--  c
+/// //                  |
+/// //                 (1)
+/// //                  |
+/// //                  d
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// let ab = graph.add_edge_unchecked(a, b, 1.into());
+/// let bc = graph.add_edge_unchecked(b, c, 1.into());
+/// let bd = graph.add_edge_unchecked(b, d, 1.into());
+///
+/// // When: Turning from a -> b into b -> d is forbidden.
+/// let routing = TurnRestrictedDijkstra::init(&graph);
+/// let (edge_path, cost) = routing
+///     .execute(a, d, |_, from_edge_id, to_edge_id| {
+///         if (from_edge_id, to_edge_id) == (ab, bd) {
+///             Magnitude::PosInfinite
+///         } else {
+///             0.into()
+///         }
+///     })
+///     .unwrap()
+///     .unwrap();
+///
+/// // Then: Path goes to c and turns back.
+/// assert_eq!(edge_path, vec![(a, b, ab), (b, c, bc), (c, b, bc), (b, d, bd)]);
+/// assert_eq!(cost, 4.into());
+/// ```
+pub struct TurnRestrictedDijkstra<W> {
+    id_map: IdMap,
+
+    // Arcs in the format of: (src virtual id, dst virtual id, edge id, weight).
+    arcs: Vec<(usize, usize, usize, Magnitude<W>)>,
+
+    // Index of the arcs that start from each vertex.
+    out_arcs_of: Vec<Vec<usize>>,
+}
+
+impl<W: Copy + Ord + Zero + Any + Unsigned + CheckedAdd> TurnRestrictedDijkstra<W> {
+    /// # Arguments
+    /// `graph`: Graph to search for paths in.
+    ///
+    /// # Returns
+    /// Initialized algorithm, holding arcs of the graph.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|)
+    pub fn init<E, Ty, G>(graph: &G) -> Self
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+    {
+        let id_map = graph.continuous_id_map();
+
+        let mut arcs = vec![];
+        let mut out_arcs_of = vec![vec![]; graph.vertex_count()];
+        for (src_id, dst_id, edge) in graph.as_directed_edges() {
+            let weight = *edge.get_weight();
+
+            if weight.is_finite() {
+                let src_virt_id = id_map.virt_id_of(src_id);
+
+                out_arcs_of[src_virt_id].push(arcs.len());
+                arcs.push((
+                    src_virt_id,
+                    id_map.virt_id_of(dst_id),
+                    edge.get_id(),
+                    weight,
+                ));
+            }
+        }
+
+        TurnRestrictedDijkstra {
+            id_map,
+            arcs,
+            out_arcs_of,
+        }
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    /// * `turn_cost_of`: Cost of passing a vertex from one edge into another, given id of the vertex, id of the incoming edge and id of the outgoing edge.
+    ///   `PosInfinite` forbids the turn. For undirected graphs, a U-turn goes out of a vertex through the edge it came in.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing
+    ///   * `Some`: Containing edges of the shortest path in the format of: (`src_id`, `dst_id`, `edge_id`), and its cost including the turn costs.
+    ///     Path has no edges if source and destination are the same.
+    ///   * `None`: If destination is not reachable from source without forbidden turns.
+    /// * `Err`:
+    ///   * [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if `src_id` or `dst_id` is not in the graph.
+    ///   * [`WeightOverflow`](crate::graph::ErrorKind::WeightOverflow) if cost of the shortest path does not fit in `W`.
+    ///
+    /// # Complexity
+    /// O(T * log(|E|)), where T is the number of pairs of consecutive arcs(turns) in the graph.
+    pub fn execute<F>(
+        &self,
+        src_id: usize,
+        dst_id: usize,
+        turn_cost_of: F,
+    ) -> Result<Option<(EdgePath, Magnitude<W>)>>
+    where
+        F: Fn(usize, usize, usize) -> Magnitude<W>,
+    {
+        for vertex_id in [src_id, dst_id] {
+            if self.id_map.get_virt_id(vertex_id).is_none() {
+                Err(Error::new_vnf(vertex_id))?
+            }
+        }
+
+        let src_virt_id = self.id_map.virt_id_of(src_id);
+        let dst_virt_id = self.id_map.virt_id_of(dst_id);
+
+        if src_virt_id == dst_virt_id {
+            return Ok(Some((vec![], W::zero().into())));
+        }
+
+        // Cost of the shortest path that ends with each arc, and the arc before it.
+        let mut dist = vec![Magnitude::PosInfinite; self.arcs.len()];
+        let mut prev = vec![None; self.arcs.len()];
+        let mut heap = IndexedHeap::with_capacity(self.arcs.len());
+        let mut overflowed = false;
+
+        for arc_index in &self.out_arcs_of[src_virt_id] {
+            let weight = self.arcs[*arc_index].3;
+
+            if weight < dist[*arc_index] {
+                dist[*arc_index] = weight;
+                heap.push_or_decrease(*arc_index, weight);
+            }
+        }
+
+        while let Some((arc_index, cost)) = heap.pop() {
+            let (_, via_virt_id, from_edge_id, _) = self.arcs[arc_index];
+
+            if via_virt_id == dst_virt_id {
+                return Ok(Some((self.edge_path_of(&prev, arc_index), cost)));
+            }
+
+            let via_id = self.id_map.real_id_of(via_virt_id);
+            for n_arc_index in &self.out_arcs_of[via_virt_id] {
+                let (_, _, to_edge_id, weight) = self.arcs[*n_arc_index];
+
+                let turn_cost = turn_cost_of(via_id, from_edge_id, to_edge_id);
+                if !turn_cost.is_finite() {
+                    continue;
+                }
+
+                let alt = match checked_add(cost, turn_cost)? {
+                    Some(cost) => checked_add(cost, weight)?,
+                    None => None,
+                };
+                let alt = match alt {
+                    Some(alt) => alt,
+                    None => {
+                        overflowed = true;
+                        continue;
+                    }
+                };
+
+                if alt < dist[*n_arc_index] {
+                    dist[*n_arc_index] = alt;
+                    prev[*n_arc_index] = Some(arc_index);
+                    heap.push_or_decrease(*n_arc_index, alt);
+                }
+            }
+        }
+
+        if overflowed {
+            Err(Error::new_wo())?
+        }
+
+        Ok(None)
+    }
+
+    // Follows the arcs before the arc at `arc_index` back to the source.
+    fn edge_path_of(&self, prev: &[Option<usize>], arc_index: usize) -> EdgePath {
+        let mut edge_path = vec![];
+
+        let mut current = Some(arc_index);
+        while let Some(arc_index) = current {
+            let (src_virt_id, dst_virt_id, edge_id, _) = self.arcs[arc_index];
+            edge_path.push((
+                self.id_map.real_id_of(src_virt_id),
+                self.id_map.real_id_of(dst_virt_id),
+                edge_id,
+            ));
+
+            current = prev[arc_index];
+        }
+        edge_path.reverse();
+
+        edge_path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ErrorKind, MatGraph};
+    use crate::storage::{DiMat, Mat};
+
+    #[test]
+    fn same_source_and_destination() {
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+
+        let (edge_path, cost) = TurnRestrictedDijkstra::init(&graph)
+            .execute(a, a, |_, _, _| 0.into())
+            .unwrap()
+            .unwrap();
+
+        assert!(edge_path.is_empty());
+        assert_eq!(cost, 0.into());
+    }
+
+    #[test]
+    fn turn_penalties() {
+        // Given: Graph
+        //
+        //      a  --(1)-->  b  --(1)-->  c
+        //                   |            |
+        //                  (1)          (1)
+        //                   v            v
+        //                   d  --(1)-->  e  --(1)-->  f
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let ids: Vec<usize> = (0..6).map(|_| graph.add_vertex()).collect();
+        let (a, b, c, d, e, f) = (ids[0], ids[1], ids[2], ids[3], ids[4], ids[5]);
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+        let bc = graph.add_edge_unchecked(b, c, 1.into());
+        let bd = graph.add_edge_unchecked(b, d, 1.into());
+        let ce = graph.add_edge_unchecked(c, e, 1.into());
+        let de = graph.add_edge_unchecked(d, e, 1.into());
+        let ef = graph.add_edge_unchecked(e, f, 1.into());
+
+        // Going straight is free, and every turn costs 2.
+        let straight = [(ab, bc), (bd, de), (de, ef)];
+        let turn_cost_of = |_, from_edge_id, to_edge_id| {
+            if straight.contains(&(from_edge_id, to_edge_id)) {
+                0.into()
+            } else {
+                2.into()
+            }
+        };
+
+        // When:
+        let routing = TurnRestrictedDijkstra::init(&graph);
+        let (edge_path, cost) = routing.execute(a, f, turn_cost_of).unwrap().unwrap();
+
+        // Then: Both paths have 4 edges, but a -> b -> d -> e -> f has one turn instead of two.
+        assert_eq!(
+            edge_path,
+            vec![(a, b, ab), (b, d, bd), (d, e, de), (e, f, ef)]
+        );
+        assert_eq!(cost, 6.into());
+    }
+
+    #[test]
+    fn forbidden_turns() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c
+        //              |
+        //              d
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        let bd = graph.add_edge_unchecked(b, d, 1.into());
+
+        // When: Turning from a -> b into b -> d and U-turns are forbidden.
+        let routing = TurnRestrictedDijkstra::init(&graph);
+        let path = routing.execute(a, d, |_, from_edge_id, to_edge_id| {
+            if from_edge_id == to_edge_id || (from_edge_id, to_edge_id) == (ab, bd) {
+                Magnitude::PosInfinite
+            } else {
+                0.into()
+            }
+        });
+        let reverse_path = routing.execute(d, a, |_, from_edge_id, to_edge_id| {
+            if from_edge_id == to_edge_id || (from_edge_id, to_edge_id) == (ab, bd) {
+                Magnitude::PosInfinite
+            } else {
+                0.into()
+            }
+        });
+
+        // Then: Restriction only applies to the given direction.
+        assert!(path.unwrap().is_none());
+        assert_eq!(reverse_path.unwrap().unwrap().1, 2.into());
+    }
+
+    #[test]
+    fn vertex_not_found() {
+        let graph = MatGraph::init(DiMat::<usize>::init());
+
+        let error = TurnRestrictedDijkstra::init(&graph)
+            .execute(0, 1, |_, _, _| 0.into())
+            .unwrap_err();
+
+        assert_eq!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            &ErrorKind::VertexNotFound
+        );
+    }
+
+    #[test]
+    fn weight_overflow() {
+        // Given: Graph
+        //
+        //      a  --(200)-->  b  --(50)-->  c
+        //
+        let mut graph = MatGraph::init(DiMat::<u8>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 200.into());
+        graph.add_edge_unchecked(b, c, 50.into());
+
+        // When: Turning at b costs 10, so cost of the path does not fit in u8.
+        let routing = TurnRestrictedDijkstra::init(&graph);
+        let error = routing.execute(a, c, |_, _, _| 10.into()).unwrap_err();
+
+        // Then:
+        assert_eq!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            &ErrorKind::WeightOverflow
+        );
+        assert_eq!(
+            routing
+                .execute(a, b, |_, _, _| 10.into())
+                .unwrap()
+                .unwrap()
+                .1,
+            200.into()
+        );
+    }
+}
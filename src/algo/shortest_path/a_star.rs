@@ -0,0 +1,338 @@
+use anyhow::Result;
+use magnitude::Magnitude;
+use num_traits::{CheckedAdd, Unsigned, Zero};
+use std::any::Any;
+
+use super::checked::checked_add;
+use crate::graph::{Edge, EdgeDir, Error};
+use crate::provide::{Edges, Graph, IdMap, Vertices};
+use crate::util::IndexedHeap;
+
+/// Finds shortest path between two vertices, guided by a heuristic.
+///
+/// Like Dijkstra, but vertices are visited in increasing order of their distance from the source plus the estimated distance to the destination.
+/// So with a good heuristic, like euclidean distance in road networks, far fewer vertices are visited.
+/// Heuristic must never overestimate distance to the destination(be admissible), otherwise the path found may not be the shortest one.
+/// A vertex is visited again if a shorter path to it is found, so the heuristic does not need to be consistent.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::AStar;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  --(1)-->  b  --(1)-->  c
+/// //      |                         ^
+/// //      '-----------(3)-----------'
+/// //
+/// let mut graph = MatGraph::init(DiMat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(a, c, 3.into());
+///
+/// // Number of edges to c is a lower bound for the distance to c, because every weight is at least one.
+/// let hops_to_c = |vertex_id| if vertex_id == c { 0 } else { 1 };
+///
+/// let (path, distance) = AStar::init(&graph).execute(&graph, a, c, hops_to_c).unwrap().unwrap();
+///
+/// assert_eq!(path, vec![a, b, c]);
+/// assert_eq!(distance, 2.into());
+/// ```
+pub struct AStar<W> {
+    id_map: IdMap,
+    dist: Vec<Magnitude<W>>,
+    prev: Vec<Option<usize>>,
+}
+
+impl<W: Copy + Ord + Zero + Any + Unsigned + CheckedAdd> AStar<W> {
+    pub fn init<E, Ty, G>(graph: &G) -> Self
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+    {
+        let vertex_count = graph.vertex_count();
+
+        AStar {
+            id_map: graph.continuous_id_map(),
+            dist: vec![Magnitude::PosInfinite; vertex_count],
+            prev: vec![None; vertex_count],
+        }
+    }
+
+    /// # Arguments
+    /// * `graph`: Graph to search for the shortest path in.
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    /// * `heuristic`: Estimates distance from a vertex to the destination, given id of the vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing
+    ///   * `Some`: Ids of the vertices on the shortest path from `src_id` to `dst_id`(including both), and length of the path.
+    ///   * `None`: If `dst_id` is not reachable from `src_id`.
+    /// * `Err`:
+    ///   * [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if `src_id` or `dst_id` is not in the graph.
+    ///   * [`WeightOverflow`](crate::graph::ErrorKind::WeightOverflow) if length of the shortest path does not fit in `W`.
+    ///
+    /// # Complexity
+    /// O((|V| + |E|)log(|V|)) with a consistent heuristic.
+    pub fn execute<E, Ty, G, H>(
+        mut self,
+        graph: &G,
+        src_id: usize,
+        dst_id: usize,
+        heuristic: H,
+    ) -> Result<Option<(Vec<usize>, Magnitude<W>)>>
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+        H: Fn(usize) -> W,
+    {
+        for vertex_id in [src_id, dst_id] {
+            if !graph.contains_vertex(vertex_id) {
+                Err(Error::new_vnf(vertex_id))?
+            }
+        }
+
+        let src_virt_id = self.id_map.virt_id_of(src_id);
+        let dst_virt_id = self.id_map.virt_id_of(dst_id);
+        let mut overflowed = false;
+
+        self.dist[src_virt_id] = W::zero().into();
+
+        let mut heap = IndexedHeap::with_capacity(self.dist.len());
+        heap.push(src_virt_id, self.estimate(src_virt_id, &heuristic)?);
+
+        while let Some((virt_id, _)) = heap.pop() {
+            if virt_id == dst_virt_id {
+                break;
+            }
+
+            let real_id = self.id_map.real_id_of(virt_id);
+
+            for (n_id, edge) in graph.edges_from_unchecked(real_id) {
+                let n_virt_id = self.id_map.virt_id_of(n_id);
+
+                let alt = match checked_add(self.dist[virt_id], *edge.get_weight())? {
+                    Some(alt) => alt,
+                    None => {
+                        overflowed = true;
+                        continue;
+                    }
+                };
+                if alt < self.dist[n_virt_id] {
+                    self.dist[n_virt_id] = alt;
+                    self.prev[n_virt_id] = Some(virt_id);
+                    heap.push_or_decrease(n_virt_id, self.estimate(n_virt_id, &heuristic)?);
+                }
+            }
+        }
+
+        if self.dist[dst_virt_id].is_pos_infinite() {
+            return if overflowed {
+                Err(Error::new_wo())?
+            } else {
+                Ok(None)
+            };
+        }
+
+        let mut path = vec![dst_id];
+        let mut virt_id = dst_virt_id;
+        while let Some(prev_virt_id) = self.prev[virt_id] {
+            path.push(self.id_map.real_id_of(prev_virt_id));
+            virt_id = prev_virt_id;
+        }
+        path.reverse();
+
+        Ok(Some((path, self.dist[dst_virt_id])))
+    }
+
+    // Distance of the vertex from the source plus its estimated distance to the destination.
+    // Estimations that do not fit in `W` are infinite, so such vertices are visited last.
+    fn estimate<H>(&self, virt_id: usize, heuristic: &H) -> Result<Magnitude<W>>
+    where
+        H: Fn(usize) -> W,
+    {
+        let h = heuristic(self.id_map.real_id_of(virt_id));
+
+        Ok(checked_add(self.dist[virt_id], h.into())?.unwrap_or(Magnitude::PosInfinite))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::Dijkstra;
+    use crate::graph::{ErrorKind, MatGraph, UndirectedEdge};
+    use crate::storage::{DiMat, Mat};
+
+    // Grid of `size` x `size` vertices where each vertex is connected to its right and bottom neighbors.
+    // Returns the graph and id of each vertex, indexed by its (row, column).
+    fn grid(size: usize) -> (MatGraph<usize, UndirectedEdge>, Vec<Vec<usize>>) {
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let ids: Vec<Vec<usize>> = (0..size)
+            .map(|_| (0..size).map(|_| graph.add_vertex()).collect())
+            .collect();
+
+        for row in 0..size {
+            for column in 0..size {
+                let weight = 1 + (row * 7 + column * 3) % 4;
+                if column + 1 < size {
+                    graph.add_edge_unchecked(ids[row][column], ids[row][column + 1], weight.into());
+                }
+                if row + 1 < size {
+                    graph.add_edge_unchecked(ids[row][column], ids[row + 1][column], weight.into());
+                }
+            }
+        }
+
+        (graph, ids)
+    }
+
+    #[test]
+    fn same_source_and_destination() {
+        // Given:
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+
+        // When:
+        let result = AStar::init(&graph).execute(&graph, a, a, |_| 0).unwrap();
+
+        // Then:
+        assert_eq!(result, Some((vec![a], 0.into())));
+    }
+
+    #[test]
+    fn unreachable_destination() {
+        // Given: Graph
+        //
+        //      a  <--  b
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(b, a, 1.into());
+
+        // When:
+        let result = AStar::init(&graph).execute(&graph, a, b, |_| 0).unwrap();
+
+        // Then:
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn missing_vertex() {
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+
+        let error = AStar::init(&graph)
+            .execute(&graph, a, a + 1, |_| 0)
+            .unwrap_err();
+
+        assert_eq!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            &ErrorKind::VertexNotFound
+        );
+    }
+
+    #[test]
+    fn overflowing_path() {
+        // Given: Graph
+        //
+        //      a  --(MAX)-->  b  --(1)-->  c
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, usize::MAX.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // When:
+        let error = AStar::init(&graph)
+            .execute(&graph, a, c, |_| 0)
+            .unwrap_err();
+
+        // Then:
+        assert_eq!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            &ErrorKind::WeightOverflow
+        );
+    }
+
+    #[test]
+    fn matches_dijkstra_on_grid() {
+        // Given:
+        let size = 6;
+        let (graph, ids) = grid(size);
+        let src_id = ids[0][0];
+        let distances = Dijkstra::init(&graph).execute(&graph, src_id);
+
+        for row in 0..size {
+            for column in 0..size {
+                // Manhattan distance is admissible because every weight is at least one.
+                let dst_id = ids[row][column];
+                let manhattan = |vertex_id: usize| {
+                    let (v_row, v_column) = (vertex_id / size, vertex_id % size);
+
+                    (v_row as isize - row as isize).unsigned_abs()
+                        + (v_column as isize - column as isize).unsigned_abs()
+                };
+
+                // When:
+                let (path, distance) = AStar::init(&graph)
+                    .execute(&graph, src_id, dst_id, manhattan)
+                    .unwrap()
+                    .unwrap();
+
+                // Then:
+                assert_eq!(Some(distance), distances.distance_to(dst_id));
+                assert_eq!(path.first(), Some(&src_id));
+                assert_eq!(path.last(), Some(&dst_id));
+                let length = path.windows(2).fold(Magnitude::Finite(0), |length, pair| {
+                    length + *graph.edges_between_unchecked(pair[0], pair[1])[0].get_weight()
+                });
+                assert_eq!(length, distance);
+            }
+        }
+    }
+
+    #[test]
+    fn inadmissible_heuristic_still_finds_a_path() {
+        // Given: Graph
+        //
+        //      a  --(1)-->  b  --(1)-->  c
+        //      |                         ^
+        //      '-----------(3)-----------'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(a, c, 3.into());
+
+        // When: Heuristic overestimates distance of b.
+        let (path, distance) = AStar::init(&graph)
+            .execute(
+                &graph,
+                a,
+                c,
+                |vertex_id| if vertex_id == b { 10 } else { 0 },
+            )
+            .unwrap()
+            .unwrap();
+
+        // Then: Path through b is not found.
+        assert_eq!(path, vec![a, c]);
+        assert_eq!(distance, 3.into());
+    }
+}
@@ -1,17 +1,22 @@
 use magnitude::Magnitude;
-use num_traits::Zero;
+use num_traits::{CheckedAdd, Zero};
 use std::{any::Any, collections::HashSet};
 use std::collections::HashMap;
 
-use crate::{graph::{subgraph::ShortestPathSubgraph, Edge, EdgeDir}, prelude::Neighbors};
+use super::checked::checked_add;
+use crate::{graph::{subgraph::ShortestPathSubgraph, Edge, EdgeDir, Error}, prelude::Neighbors};
+use crate::algo::PathTracer;
 use crate::provide::{Edges, Graph, Vertices};
 
+// Distance of each vertex from source, and edges of the shortest path tree.
+type ShortestPathTree<W> = (HashMap<usize, Magnitude<W>>, Vec<(usize, usize, usize)>);
+
 pub struct BellmanFord<W> {
     distance: Vec<Magnitude<W>>,
     prev: Vec<Magnitude<usize>>,
 }
 
-impl<W: Copy + Any + Zero + Ord> BellmanFord<W> {
+impl<W: Copy + Any + Zero + Ord + CheckedAdd> BellmanFord<W> {
     pub fn init<E, Ty, G>(graph: &G) -> Self
     where
         E: Edge<W>,
@@ -27,7 +32,7 @@ impl<W: Copy + Any + Zero + Ord> BellmanFord<W> {
     }
 
     pub fn execute<E, Ty, G>(
-        mut self,
+        self,
         graph: &G,
         src_id: usize,
     ) -> Result<ShortestPathSubgraph<W, E, Ty, G>, String>
@@ -35,25 +40,112 @@ impl<W: Copy + Any + Zero + Ord> BellmanFord<W> {
         E: Edge<W>,
         Ty: EdgeDir,
         G: Vertices + Edges<W, E> + Neighbors + Graph<W, E, Ty>,
+    {
+        let (distance_map, sp_edges) =
+            self.shortest_path_tree(graph, src_id, |_, _, edge: &E| *edge.get_weight())?;
+
+        let vertices = graph
+            .as_directed_edges()
+            .iter()
+            .flat_map(|(src_id, dst_id, _)| vec![*src_id, *dst_id])
+            .chain(std::iter::once(src_id))
+            .collect::<HashSet<usize>>();
+
+        Ok(ShortestPathSubgraph::init(
+            graph,
+            sp_edges,
+            vertices,
+            distance_map,
+        ))
+    }
+
+    /// Same as `execute` but cost of each edge is computed by `cost_of` instead of using weight of the edge.
+    /// So costs can be derived from the stored weights(or anything else) without mutating or copying the graph.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to search for shortest paths in.
+    /// * `src_id`: Id of the source vertex.
+    /// * `cost_of`: Computes cost of an edge from its source id, destination id and the edge itself.
+    ///   Edges with infinite cost are never used.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing:
+    ///     * Distance from the source vertex to each vertex, which is infinite for vertices that are not reachable.
+    ///     * Tracer to reconstruct the shortest path from the source vertex to each reachable vertex.
+    /// * `Err`: If there is a cycle with negative cost, or distance of a vertex does not fit in `W2`.
+    pub fn execute_with_cost<W2, E, G, F>(
+        self,
+        graph: &G,
+        src_id: usize,
+        cost_of: F,
+    ) -> Result<(HashMap<usize, Magnitude<W2>>, PathTracer), String>
+    where
+        W2: Copy + Any + Zero + Ord + CheckedAdd,
+        E: Edge<W>,
+        G: Vertices + Edges<W, E>,
+        F: Fn(usize, usize, &E) -> Magnitude<W2>,
+    {
+        let vertex_count = self.distance.len();
+
+        let bellman_ford = BellmanFord::<W2> {
+            distance: vec![Magnitude::PosInfinite; vertex_count],
+            prev: vec![Magnitude::PosInfinite; vertex_count],
+        };
+
+        let (distance_map, sp_edges) = bellman_ford.shortest_path_tree(graph, src_id, cost_of)?;
+
+        Ok((distance_map, PathTracer::from_edges(&sp_edges)))
+    }
+
+    fn shortest_path_tree<W0, E, G, F>(
+        mut self,
+        graph: &G,
+        src_id: usize,
+        cost_of: F,
+    ) -> Result<ShortestPathTree<W>, String>
+    where
+        E: Edge<W0>,
+        G: Vertices + Edges<W0, E>,
+        F: Fn(usize, usize, &E) -> Magnitude<W>,
     {
         let mut sp_edges = vec![];
+        let mut overflowed = vec![];
 
         let vertex_count = graph.vertex_count();
 
-        let id_map = graph.continuos_id_map();
+        let id_map = graph.continuous_id_map();
 
         let src_virt_id = id_map.virt_id_of(src_id);
 
         self.distance[src_virt_id] = W::zero().into();
 
-        let edges = graph.as_directed_edges();
+        let edges = graph
+            .as_directed_edges()
+            .into_iter()
+            .map(|(u_real_id, v_real_id, edge)| {
+                (
+                    u_real_id,
+                    v_real_id,
+                    edge,
+                    cost_of(u_real_id, v_real_id, edge),
+                )
+            })
+            .collect::<Vec<_>>();
 
         for _ in 0..vertex_count - 1 {
-            for (u_real_id, v_real_id, edge) in &edges {
+            for (u_real_id, v_real_id, edge, cost) in &edges {
                 let u_virt_id = id_map.virt_id_of(*u_real_id);
                 let v_virt_id = id_map.virt_id_of(*v_real_id);
 
-                let alt = self.distance[u_virt_id] + *edge.get_weight();
+                let alt = match checked_add(self.distance[u_virt_id], *cost)
+                    .map_err(|err| err.to_string())?
+                {
+                    Some(alt) => alt,
+                    None => {
+                        overflowed.push(v_virt_id);
+                        continue;
+                    }
+                };
                 if alt < self.distance[v_virt_id] {
                     self.distance[v_virt_id] = alt;
                     self.prev[v_virt_id] = u_virt_id.into();
@@ -64,34 +156,31 @@ impl<W: Copy + Any + Zero + Ord> BellmanFord<W> {
             }
         }
 
-        for (u_real_id, v_real_id, edge) in &edges {
-            let u_virt_id = id_map.real_id_of(*u_real_id);
-            let v_virt_id = id_map.real_id_of(*v_real_id);
+        for (u_real_id, v_real_id, _, cost) in &edges {
+            let u_virt_id = id_map.virt_id_of(*u_real_id);
+            let v_virt_id = id_map.virt_id_of(*v_real_id);
 
-            let alt = self.distance[u_virt_id] + *edge.get_weight();
-            if alt < self.distance[v_virt_id] {
+            let alt =
+                checked_add(self.distance[u_virt_id], *cost).map_err(|err| err.to_string())?;
+            if matches!(alt, Some(alt) if alt < self.distance[v_virt_id]) {
                 return Err("Cycle detected".to_string());
             }
         }
 
+        if overflowed
+            .into_iter()
+            .any(|virt_id| self.distance[virt_id].is_pos_infinite())
+        {
+            return Err(Error::new_wo().to_string());
+        }
+
         let mut distance_map = HashMap::new();
         for virt_id in 0..graph.vertex_count() {
             let real_id = id_map.real_id_of(virt_id);
             distance_map.insert(real_id, self.distance[virt_id]);
         }
 
-        let vertices = edges
-            .iter()
-            .flat_map(|(src_id, dst_id, _)| vec![*src_id, *dst_id])
-            .chain(std::iter::once(src_id))
-            .collect::<HashSet<usize>>();
-
-        Ok(ShortestPathSubgraph::init(
-            graph,
-            sp_edges,
-            vertices,
-            distance_map,
-        ))
+        Ok((distance_map, sp_edges))
     }
 }
 
@@ -295,4 +384,66 @@ mod tests {
 
         assert!(shortest_paths.is_err());
     }
+
+    #[test]
+    fn directed_graph_with_cost_function() {
+        // Given: Graph
+        //          1       2
+        //      a ----> b ----> c
+        //      |               ^
+        //      '---------------'
+        //              5
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 2.into());
+        graph.add_edge_unchecked(a, c, 5.into());
+
+        // When: Cost of each edge is its weight minus 3, which can be negative.
+        let (distances, tracer) = BellmanFord::init(&graph)
+            .execute_with_cost(&graph, a, |_, _, edge| {
+                (edge.get_weight().unwrap() as isize - 3).into()
+            })
+            .unwrap();
+
+        // Then:
+        assert_eq!(distances[&a], 0.into());
+        assert_eq!(distances[&b], (-2).into());
+        assert_eq!(distances[&c], (-3).into());
+        assert_eq!(tracer.vertex_path(c).unwrap(), vec![a, b, c]);
+
+        // When: Costs form a negative cycle.
+        graph.add_edge_unchecked(c, a, 1.into());
+        let shortest_paths =
+            BellmanFord::init(&graph).execute_with_cost(&graph, a, |_, _, edge| {
+                (edge.get_weight().unwrap() as isize - 3).into()
+            });
+
+        // Then:
+        assert!(shortest_paths.is_err());
+    }
+
+    #[test]
+    fn distance_overflow() {
+        // Given: Graph
+        //          100      100
+        //      a  ----> b  ----> c
+        //
+        let mut graph = MatGraph::init(DiMat::<i8>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 100.into());
+        graph.add_edge_unchecked(b, c, 100.into());
+
+        // When: Distance from a to c does not fit in i8.
+        let shortest_paths = BellmanFord::init(&graph).execute(&graph, a);
+
+        // Then:
+        assert!(shortest_paths.is_err());
+        assert!(BellmanFord::init(&graph).execute(&graph, b).is_ok());
+    }
 }
@@ -0,0 +1,526 @@
+use anyhow::Result;
+use num_traits::Float;
+
+use super::distance_matrix::{relax_row, DistanceMatrix};
+use crate::algo::ExecutionControl;
+use crate::graph::{Edge, Error};
+use crate::provide::{Edges, Vertices};
+
+/// Computes the shortest distance between every pair of vertices of a graph with floating point weights.
+///
+/// Unlike [`FloydWarshall`](crate::algo::FloydWarshall), distances are stored in a dense [`DistanceMatrix`](crate::algo::DistanceMatrix)
+/// and the matrix is processed in square blocks. For each block of intermediate vertices, the rows and columns of the block are computed first.
+/// Then the rest of the matrix is updated by the min-plus product of those rows and columns, one block at a time, so the data being used stays in cache.
+/// All updates are done over contiguous parts of the rows so the compiler can vectorize them.
+/// With the `parallel` feature, large and dense graphs are processed on multiple threads.
+///
+/// Edges may have negative weights, as long as there is no negative cycle.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::MatGraph;
+/// use prepona::algo::BlockedFloydWarshall;
+///
+/// // Given: Graph
+/// //          2.0       -1.0
+/// //      a  -----> b  -----> c
+/// //      ^                   |
+/// //      '-------------------'
+/// //              4.0
+/// let mut graph = MatGraph::init(DiMat::<f64>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 2.0.into());
+/// graph.add_edge_unchecked(b, c, (-1.0).into());
+/// graph.add_edge_unchecked(c, a, 4.0.into());
+///
+/// let distances = BlockedFloydWarshall::init().execute(&graph).unwrap();
+///
+/// assert_eq!(distances.distance(a, c), Some(1.0));
+/// assert_eq!(distances.distance(b, a), Some(3.0));
+/// assert_eq!(distances.distance(c, b), Some(6.0));
+/// ```
+pub struct BlockedFloydWarshall {
+    block_size: usize,
+
+    // Number of threads to use, or `None` to choose it based on the graph.
+    #[cfg(feature = "parallel")]
+    thread_count: Option<usize>,
+}
+
+// Graphs with fewer vertices are processed on a single thread, because spawning threads for each block costs more than it saves.
+#[cfg(feature = "parallel")]
+const PARALLEL_MIN_VERTEX_COUNT: usize = 512;
+
+// Graphs in which fewer pairs of vertices are adjacent are processed on a single thread,
+// because algorithms like Dijkstra from every vertex are a better choice for sparse graphs anyway.
+#[cfg(feature = "parallel")]
+const PARALLEL_MIN_DENSITY: f64 = 0.1;
+
+impl BlockedFloydWarshall {
+    /// # Returns
+    /// Initialized algorithm that uses blocks of 64 vertices.
+    /// If the `parallel` feature is enabled, the number of threads is chosen automatically. Checkout `with_thread_count`.
+    pub fn init() -> Self {
+        BlockedFloydWarshall {
+            block_size: 64,
+
+            #[cfg(feature = "parallel")]
+            thread_count: None,
+        }
+    }
+
+    /// # Arguments
+    /// `block_size`: Number of vertices in each block. Smaller blocks use less cache but update shorter parts of each row at a time.
+    ///
+    /// # Panics
+    /// If `block_size` is zero.
+    pub fn with_block_size(mut self, block_size: usize) -> Self {
+        assert!(block_size > 0, "Block size must be positive");
+
+        self.block_size = block_size;
+
+        self
+    }
+
+    /// By default, graphs with at least 512 vertices in which at least 10% of the pairs of vertices are adjacent use all available cores,
+    /// and other graphs use a single thread. Using this function overrides that choice.
+    ///
+    /// For each block, rows that are not in the block are split between the threads. Results do not depend on the number of threads.
+    ///
+    /// # Arguments
+    /// `thread_count`: Number of threads to use. Using one thread runs the algorithm on the calling thread.
+    ///
+    /// # Panics
+    /// If `thread_count` is zero.
+    #[cfg(feature = "parallel")]
+    pub fn with_thread_count(mut self, thread_count: usize) -> Self {
+        assert!(thread_count > 0, "Thread count must be positive");
+
+        self.thread_count = Some(thread_count);
+
+        self
+    }
+
+    /// # Arguments
+    /// `graph`: Graph to compute distances of.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing distance between every pair of vertices, which is infinity if there is no path between them.
+    /// * `Err`: If graph contains a negative cycle.
+    ///
+    /// # Complexity
+    /// O(|V|<sup>3</sup>)
+    pub fn execute<F, E, G>(&self, graph: &G) -> Result<DistanceMatrix<F>>
+    where
+        F: Float + Send + Sync,
+        E: Edge<F>,
+        G: Edges<F, E> + Vertices,
+    {
+        self.execute_with_control(graph, &mut ExecutionControl::init())
+    }
+
+    /// Same as [`execute`](crate::algo::BlockedFloydWarshall::execute), but can be cancelled and reports its progress.
+    /// Each step is one block of intermediate vertices, so there are |V| / block size steps, rounded up.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to compute distances of.
+    /// * `control`: Control to report progress to and check for cancellation after each step.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing distance between every pair of vertices, which is infinity if there is no path between them.
+    /// * `Err`:
+    ///     * [`Cancelled`](crate::graph::ErrorKind::Cancelled) if `control` is cancelled before all steps are completed.
+    ///     * [`NegativeCycle`](crate::graph::ErrorKind::NegativeCycle) if graph contains a negative cycle.
+    ///
+    /// # Complexity
+    /// O(|V|<sup>3</sup>)
+    pub fn execute_with_control<F, E, G>(
+        &self,
+        graph: &G,
+        control: &mut ExecutionControl,
+    ) -> Result<DistanceMatrix<F>>
+    where
+        F: Float + Send + Sync,
+        E: Edge<F>,
+        G: Edges<F, E> + Vertices,
+    {
+        let mut distances = DistanceMatrix::from_graph(graph);
+
+        let size = distances.size();
+        let thread_count = self.thread_count_for(&distances);
+        let entries = distances.entries_mut();
+
+        let mut pivot_row = vec![F::zero(); size];
+        let mut panel = Vec::with_capacity(self.block_size * size);
+
+        let block_count = size.div_ceil(self.block_size);
+        for (block_index, k_start) in (0..size).step_by(self.block_size).enumerate() {
+            let k_end = (k_start + self.block_size).min(size);
+
+            // Rows of the block: Regular Floyd-Warshall restricted to the block.
+            for k in k_start..k_end {
+                pivot_row.copy_from_slice(&entries[k * size..(k + 1) * size]);
+
+                for i in k_start..k_end {
+                    let via = entries[i * size + k];
+                    relax_row(&mut entries[i * size..(i + 1) * size], via, &pivot_row);
+                }
+            }
+
+            // Rows of the block are final from now on, so they are copied to be read while other rows are updated.
+            panel.clear();
+            panel.extend_from_slice(&entries[k_start * size..k_end * size]);
+
+            // Other rows only depend on themselves and the rows of the block, so they can be updated independently.
+            let (before, rest) = entries.split_at_mut(k_start * size);
+            let after = &mut rest[(k_end - k_start) * size..];
+            let mut other_rows: Vec<&mut [F]> = before
+                .chunks_exact_mut(size)
+                .chain(after.chunks_exact_mut(size))
+                .collect();
+
+            if thread_count <= 1 || other_rows.len() < 2 {
+                self.update_rows(&mut other_rows, &panel, k_start, k_end);
+            } else {
+                #[cfg(feature = "parallel")]
+                {
+                    let chunk_size = other_rows.len().div_ceil(thread_count);
+                    let panel = &panel;
+
+                    std::thread::scope(|scope| {
+                        for rows in other_rows.chunks_mut(chunk_size) {
+                            scope.spawn(move || self.update_rows(rows, panel, k_start, k_end));
+                        }
+                    });
+                }
+            }
+
+            control.checkpoint(block_index + 1, block_count)?;
+        }
+
+        // A vertex is on a negative cycle iff its distance to itself is negative.
+        let negative_id = (0..size).find(|virt_id| entries[virt_id * size + virt_id] < F::zero());
+        if let Some(virt_id) = negative_id {
+            Err(Error::new_nc(distances.id_map().real_id_of(virt_id)))?
+        }
+
+        Ok(distances)
+    }
+
+    // Relaxes `rows` using vertices from `k_start` to `k_end` as intermediate vertices. `panel` contains final rows of these vertices.
+    fn update_rows<F: Float>(
+        &self,
+        rows: &mut [&mut [F]],
+        panel: &[F],
+        k_start: usize,
+        k_end: usize,
+    ) {
+        let size = panel.len() / (k_end - k_start);
+        let panel_row = |k: usize, j_start: usize, j_end: usize| {
+            let offset = (k - k_start) * size;
+
+            &panel[offset + j_start..offset + j_end]
+        };
+
+        // Columns of the block: Distances to the vertices of the block must be final before they are used to relax other columns.
+        for row in rows.iter_mut() {
+            for k in k_start..k_end {
+                let via = row[k];
+                relax_row(&mut row[k_start..k_end], via, panel_row(k, k_start, k_end));
+            }
+        }
+
+        // Rest of the rows: Min-plus product of the columns and rows of the block, one block of columns at a time.
+        for j_start in (0..size).step_by(self.block_size) {
+            if j_start == k_start {
+                continue;
+            }
+            let j_end = (j_start + self.block_size).min(size);
+
+            for row in rows.iter_mut() {
+                for k in k_start..k_end {
+                    let via = row[k];
+                    relax_row(&mut row[j_start..j_end], via, panel_row(k, j_start, j_end));
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    fn thread_count_for<F: Float>(&self, distances: &DistanceMatrix<F>) -> usize {
+        if let Some(thread_count) = self.thread_count {
+            return thread_count;
+        }
+
+        let size = distances.size();
+        if size < PARALLEL_MIN_VERTEX_COUNT {
+            return 1;
+        }
+
+        let adjacent_count = distances
+            .entries()
+            .iter()
+            .filter(|distance| **distance != F::infinity())
+            .count()
+            - size;
+        if (adjacent_count as f64) < PARALLEL_MIN_DENSITY * (size * (size - 1)) as f64 {
+            return 1;
+        }
+
+        std::thread::available_parallelism()
+            .map(|thread_count| thread_count.get())
+            .unwrap_or(1)
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    fn thread_count_for<F: Float>(&self, _: &DistanceMatrix<F>) -> usize {
+        1
+    }
+}
+
+impl Default for BlockedFloydWarshall {
+    fn default() -> Self {
+        BlockedFloydWarshall::init()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::{CancellationToken, FloydWarshall};
+    use crate::graph::{ErrorKind, MatGraph};
+    use crate::provide::*;
+    use crate::storage::{DiMat, Mat};
+    use crate::util::seeded;
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(DiMat::<f64>::init());
+
+        let distances = BlockedFloydWarshall::init().execute(&graph).unwrap();
+
+        assert_eq!(distances.size(), 0);
+    }
+
+    #[test]
+    fn undirected_graph() {
+        // Given: Graph
+        //          6       5
+        //      a  ---  b  ---  c
+        //    1 |       |       | 5
+        //      |  2 /`````\ 2  |
+        //      |````       ````|
+        //      d  -----------  e
+        //              1
+        let mut graph = MatGraph::init(Mat::<f32>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 6.0.into());
+        graph.add_edge_unchecked(a, d, 1.0.into());
+        graph.add_edge_unchecked(b, d, 2.0.into());
+        graph.add_edge_unchecked(b, c, 5.0.into());
+        graph.add_edge_unchecked(b, e, 2.0.into());
+        graph.add_edge_unchecked(c, e, 5.0.into());
+        graph.add_edge_unchecked(d, e, 1.0.into());
+
+        // When: Using blocks of two vertices, so the last block is smaller than others.
+        let distances = BlockedFloydWarshall::init()
+            .with_block_size(2)
+            .execute(&graph)
+            .unwrap();
+
+        // Then:
+        assert_eq!(distances.distance(a, b), Some(3.0));
+        assert_eq!(distances.distance(a, c), Some(7.0));
+        assert_eq!(distances.distance(a, e), Some(2.0));
+        assert_eq!(distances.distance(c, d), Some(6.0));
+        assert_eq!(distances.distance(e, e), Some(0.0));
+    }
+
+    #[test]
+    fn matches_floyd_warshall_on_random_graph() {
+        // Given: Random simple directed graph with 70 vertices, some of them removed, and integral weights.
+        let mut rng = seeded(7);
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let vertices: Vec<usize> = (0..70).map(|_| graph.add_vertex()).collect();
+        for vertex_id in vertices.iter().step_by(9) {
+            graph.remove_vertex_unchecked(*vertex_id);
+        }
+        let vertices = graph.vertices();
+        for _ in 0..400 {
+            let src_id = vertices[rng(vertices.len())];
+            let dst_id = vertices[rng(vertices.len())];
+            let weight = rng(20) + 1;
+            if src_id != dst_id && !graph.has_any_edge_unchecked(src_id, dst_id) {
+                graph.add_edge_unchecked(src_id, dst_id, weight.into());
+            }
+        }
+        let float_graph = graph.map_weights(DiMat::<f64>::init(), |weight| {
+            (weight.unwrap() as f64).into()
+        });
+
+        // When: Computing distances with blocks of various sizes.
+        let expected = FloydWarshall::init().execute(&graph).unwrap();
+        for block_size in [1, 5, 16, 64, 100].iter() {
+            let distances = BlockedFloydWarshall::init()
+                .with_block_size(*block_size)
+                .execute(&float_graph)
+                .unwrap();
+
+            // Then:
+            for ((src_id, dst_id), distance) in &expected {
+                let blocked_distance = distances.distance(*src_id, *dst_id).unwrap();
+                match distance {
+                    magnitude::Magnitude::Finite(distance) => {
+                        assert_eq!(blocked_distance, *distance as f64)
+                    }
+                    _ => assert_eq!(blocked_distance, f64::INFINITY),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn negative_cycle() {
+        // Given: Graph
+        //          1.0
+        //      a  -----> b
+        //      ^         |
+        //      '---------'
+        //          -2.0
+        let mut graph = MatGraph::init(DiMat::<f64>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.0.into());
+        graph.add_edge_unchecked(b, a, (-2.0).into());
+
+        // When: Computing distances.
+        let result = BlockedFloydWarshall::init().execute(&graph);
+
+        // Then:
+        assert!(matches!(
+            result.unwrap_err().downcast_ref::<Error>().unwrap().kind(),
+            ErrorKind::NegativeCycle
+        ));
+    }
+
+    #[test]
+    fn cancelled_after_first_block() {
+        // Given: Graph with five vertices, which is three blocks of two vertices.
+        let mut graph = MatGraph::init(DiMat::<f64>::init());
+        for _ in 0..5 {
+            graph.add_vertex();
+        }
+
+        // And: Control that is already cancelled.
+        let token = CancellationToken::init();
+        token.cancel();
+        let mut reported = vec![];
+        let mut control = ExecutionControl::init()
+            .with_token(token)
+            .on_progress(|progress| reported.push((progress.completed(), progress.total())));
+
+        // When:
+        let result = BlockedFloydWarshall::init()
+            .with_block_size(2)
+            .execute_with_control(&graph, &mut control);
+
+        // Then: Algorithm stops after reporting the first block.
+        assert!(matches!(
+            result.unwrap_err().downcast_ref::<Error>().unwrap().kind(),
+            ErrorKind::Cancelled
+        ));
+        drop(control);
+        assert_eq!(reported, vec![(1, 3)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_block_size() {
+        BlockedFloydWarshall::init().with_block_size(0);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_matches_sequential() {
+        // Given: Random directed graph with 150 vertices and real weights.
+        let mut rng = seeded(11);
+        let mut graph = MatGraph::init(DiMat::<f64>::init());
+        let vertices: Vec<usize> = (0..150).map(|_| graph.add_vertex()).collect();
+        for _ in 0..2000 {
+            let src_id = vertices[rng(vertices.len())];
+            let dst_id = vertices[rng(vertices.len())];
+            graph.add_edge_unchecked(src_id, dst_id, (rng(1000) as f64 / 7.0).into());
+        }
+
+        // When: Computing distances on one and on multiple threads.
+        let sequential = BlockedFloydWarshall::init()
+            .with_block_size(16)
+            .with_thread_count(1)
+            .execute(&graph)
+            .unwrap();
+        let parallel = BlockedFloydWarshall::init()
+            .with_block_size(16)
+            .with_thread_count(4)
+            .execute(&graph)
+            .unwrap();
+
+        // Then:
+        for src_id in &vertices {
+            for dst_id in &vertices {
+                assert_eq!(
+                    parallel.distance(*src_id, *dst_id),
+                    sequential.distance(*src_id, *dst_id)
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn thread_count_is_chosen_by_size_and_density() {
+        // Given: Sparse cycle and dense complete graph with 512 vertices.
+        let mut sparse = MatGraph::init(DiMat::<f32>::init());
+        let mut dense = MatGraph::init(DiMat::<f32>::init());
+        let vertices: Vec<usize> = (0..512)
+            .map(|_| {
+                dense.add_vertex();
+                sparse.add_vertex()
+            })
+            .collect();
+        for (index, src_id) in vertices.iter().enumerate() {
+            sparse.add_edge_unchecked(*src_id, vertices[(index + 1) % 512], 1.0.into());
+            for dst_id in vertices.iter().filter(|dst_id| *dst_id != src_id) {
+                dense.add_edge_unchecked(*src_id, *dst_id, 1.0.into());
+            }
+        }
+
+        // When: Choosing number of threads automatically.
+        let algo = BlockedFloydWarshall::init();
+        let small = DistanceMatrix::from_graph(&MatGraph::init(DiMat::<f32>::init()));
+
+        // Then:
+        let available = std::thread::available_parallelism().map_or(1, |count| count.get());
+        assert_eq!(algo.thread_count_for(&small), 1);
+        assert_eq!(
+            algo.thread_count_for(&DistanceMatrix::from_graph(&sparse)),
+            1
+        );
+        assert_eq!(
+            algo.thread_count_for(&DistanceMatrix::from_graph(&dense)),
+            available
+        );
+        assert_eq!(
+            algo.with_thread_count(3)
+                .thread_count_for(&DistanceMatrix::from_graph(&sparse)),
+            3
+        );
+    }
+}
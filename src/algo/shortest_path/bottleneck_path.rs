@@ -0,0 +1,427 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+use magnitude::Magnitude;
+
+use crate::algo::PathTracer;
+use crate::graph::{subgraph::ShortestPathSubgraph, Edge, EdgeDir, Error};
+use crate::provide::{Edges, Graph, IdMap, Neighbors, Vertices};
+use crate::util::IndexedHeap;
+
+// Bottleneck value of each vertex, and edges of the bottleneck path tree.
+type BottleneckTree<W> = (HashMap<usize, Magnitude<W>>, Vec<(usize, usize, usize)>);
+
+/// Objective of a bottleneck path, whose value is the weight of a single edge of the path instead of sum of the weights.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bottleneck {
+    /// Path whose heaviest edge is as light as possible(minimax path). For example the route whose most congested link is the least congested.
+    MinimizeMaximum,
+
+    /// Path whose lightest edge is as heavy as possible(maximin or widest path). For example the route with the most bandwidth.
+    MaximizeMinimum,
+}
+
+impl Bottleneck {
+    // Value of a path without edges, which is better than value of any edge.
+    fn of_empty_path<W>(&self) -> Magnitude<W> {
+        match self {
+            Bottleneck::MinimizeMaximum => Magnitude::NegInfinite,
+            Bottleneck::MaximizeMinimum => Magnitude::PosInfinite,
+        }
+    }
+
+    // Value of a vertex that is not reachable, which is worse than value of any path.
+    fn of_unreachable<W>(&self) -> Magnitude<W> {
+        match self {
+            Bottleneck::MinimizeMaximum => Magnitude::PosInfinite,
+            Bottleneck::MaximizeMinimum => Magnitude::NegInfinite,
+        }
+    }
+
+    // Value of a path with value of `path_value`, after appending an edge with weight of `weight`.
+    fn extend<W: Ord>(&self, path_value: Magnitude<W>, weight: Magnitude<W>) -> Magnitude<W> {
+        match self {
+            Bottleneck::MinimizeMaximum => path_value.max(weight),
+            Bottleneck::MaximizeMinimum => path_value.min(weight),
+        }
+    }
+
+    // Orders values so that the better value is greater.
+    fn compare<W: Ord>(&self, value: &Magnitude<W>, other: &Magnitude<W>) -> Ordering {
+        match self {
+            Bottleneck::MinimizeMaximum => other.cmp(value),
+            Bottleneck::MaximizeMinimum => value.cmp(other),
+        }
+    }
+}
+
+// Key of a vertex in the heap, so that vertex with the best value is the smallest and is popped first.
+struct Candidate<W> {
+    value: Magnitude<W>,
+    bottleneck: Bottleneck,
+}
+
+impl<W: Ord> Ord for Candidate<W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bottleneck.compare(&other.value, &self.value)
+    }
+}
+
+impl<W: Ord> PartialOrd for Candidate<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: Ord> PartialEq for Candidate<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<W: Ord> Eq for Candidate<W> {}
+
+/// Finds bottleneck paths from a source vertex to every other vertex, where value of a path is its heaviest or its lightest edge(based on [`Bottleneck`](crate::algo::Bottleneck)) instead of sum of its weights.
+///
+/// It's a variant of Dijkstra that combines weights with max(or min) instead of sum, so weights can be negative and can not overflow.
+/// Path between two vertices in a minimum(or maximum) spanning tree is also a bottleneck path,
+/// so paths of a [`BottleneckSpanningTree`](crate::algo::BottleneckSpanningTree) are bottleneck paths between all pairs of vertices at once.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::{Bottleneck, BottleneckPath};
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  --(1)--  b  --(9)--  d
+/// //      |                       |
+/// //     (4)                     (5)
+/// //      |                       |
+/// //      '---------  c  ---------'
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, d, 9.into());
+/// graph.add_edge_unchecked(a, c, 4.into());
+/// graph.add_edge_unchecked(c, d, 5.into());
+///
+/// let minimax = BottleneckPath::init(&graph, Bottleneck::MinimizeMaximum).execute(&graph, a).unwrap();
+/// let maximin = BottleneckPath::init(&graph, Bottleneck::MaximizeMinimum).execute(&graph, a).unwrap();
+///
+/// // Heaviest edge of a -> c -> d is 5, which is less than 9.
+/// assert_eq!(minimax.distance_to(d).unwrap(), 5.into());
+/// assert_eq!(minimax.path_to(d).unwrap(), vec![a, c, d]);
+///
+/// // Lightest edge of a -> c -> d is 4, which is more than 1.
+/// assert_eq!(maximin.distance_to(d).unwrap(), 4.into());
+/// ```
+pub struct BottleneckPath {
+    id_map: IdMap,
+    bottleneck: Bottleneck,
+}
+
+impl BottleneckPath {
+    /// # Arguments
+    /// * `graph`: Graph to search for bottleneck paths in.
+    /// * `bottleneck`: Objective of the paths.
+    ///
+    /// # Returns
+    /// Initialized algorithm.
+    pub fn init<W, E, Ty, G>(graph: &G, bottleneck: Bottleneck) -> Self
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Graph<W, E, Ty> + Vertices,
+    {
+        BottleneckPath {
+            id_map: graph.continuous_id_map(),
+            bottleneck,
+        }
+    }
+
+    /// # Arguments
+    /// * `graph`: Graph to search for bottleneck paths in.
+    /// * `src_id`: Id of the source vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing subgraph of the bottleneck paths rooted at the source vertex.
+    ///   Its distances are the values of the paths: `NegInfinite`(for `MinimizeMaximum`) or `PosInfinite`(for `MaximizeMinimum`) for the source vertex itself,
+    ///   and the opposite infinity for vertices that are not reachable.
+    /// * `Err`: [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if source vertex is not in the graph.
+    ///
+    /// # Complexity
+    /// O((|V| + |E|) * log(|V|))
+    pub fn execute<'a, W, E, Ty, G>(
+        self,
+        graph: &'a G,
+        src_id: usize,
+    ) -> Result<ShortestPathSubgraph<'a, W, E, Ty, G>>
+    where
+        W: Copy + Ord,
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Graph<W, E, Ty> + Edges<W, E> + Neighbors + Vertices,
+    {
+        let (value_map, edges) =
+            self.bottleneck_tree(graph, src_id, |_, _, edge: &E| *edge.get_weight())?;
+
+        let vertices = edges
+            .iter()
+            .flat_map(|(src_id, dst_id, _)| vec![*src_id, *dst_id])
+            .chain(std::iter::once(src_id))
+            .collect::<HashSet<usize>>();
+
+        Ok(ShortestPathSubgraph::init(
+            graph, edges, vertices, value_map,
+        ))
+    }
+
+    /// Same as `execute` but weight of each edge is computed by `weight_of` instead of using weight of the edge.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to search for bottleneck paths in.
+    /// * `src_id`: Id of the source vertex.
+    /// * `weight_of`: Computes weight of an edge from its source id, destination id and the edge itself.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing
+    ///   * Value of the bottleneck path from the source vertex to each vertex.
+    ///   * Tracer to reconstruct the bottleneck path from the source vertex to each reachable vertex.
+    /// * `Err`: [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if source vertex is not in the graph.
+    pub fn execute_with_cost<W, W2, E, G, F>(
+        self,
+        graph: &G,
+        src_id: usize,
+        weight_of: F,
+    ) -> Result<(HashMap<usize, Magnitude<W2>>, PathTracer)>
+    where
+        W2: Copy + Ord,
+        E: Edge<W>,
+        G: Edges<W, E> + Vertices,
+        F: Fn(usize, usize, &E) -> Magnitude<W2>,
+    {
+        let (value_map, edges) = self.bottleneck_tree(graph, src_id, weight_of)?;
+
+        Ok((value_map, PathTracer::from_edges(&edges)))
+    }
+
+    fn bottleneck_tree<W, W2, E, G, F>(
+        &self,
+        graph: &G,
+        src_id: usize,
+        weight_of: F,
+    ) -> Result<BottleneckTree<W2>>
+    where
+        W2: Copy + Ord,
+        E: Edge<W>,
+        G: Edges<W, E> + Vertices,
+        F: Fn(usize, usize, &E) -> Magnitude<W2>,
+    {
+        if !graph.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        }
+
+        let vertex_count = self.id_map.len();
+        let mut value_of = vec![self.bottleneck.of_unreachable(); vertex_count];
+        let mut parent_of = vec![None; vertex_count];
+
+        let src_virt_id = self.id_map.virt_id_of(src_id);
+        value_of[src_virt_id] = self.bottleneck.of_empty_path();
+
+        let mut heap = IndexedHeap::with_capacity(vertex_count);
+        heap.push(
+            src_virt_id,
+            Candidate {
+                value: value_of[src_virt_id],
+                bottleneck: self.bottleneck,
+            },
+        );
+
+        // Value of a popped vertex is final, so paths through other vertices can not be better than it.
+        while let Some((virt_id, _)) = heap.pop() {
+            let real_id = self.id_map.real_id_of(virt_id);
+            for (n_id, edge) in graph.edges_from_unchecked(real_id) {
+                let n_virt_id = self.id_map.virt_id_of(n_id);
+
+                let alt = self
+                    .bottleneck
+                    .extend(value_of[virt_id], weight_of(real_id, n_id, edge));
+
+                if self.bottleneck.compare(&alt, &value_of[n_virt_id]) == Ordering::Greater {
+                    value_of[n_virt_id] = alt;
+                    parent_of[n_virt_id] = Some((real_id, edge.get_id()));
+                    heap.push_or_decrease(
+                        n_virt_id,
+                        Candidate {
+                            value: alt,
+                            bottleneck: self.bottleneck,
+                        },
+                    );
+                }
+            }
+        }
+
+        let edges = parent_of
+            .iter()
+            .enumerate()
+            .filter_map(|(virt_id, parent)| {
+                parent.map(|(p_id, edge_id)| (p_id, self.id_map.real_id_of(virt_id), edge_id))
+            })
+            .collect();
+
+        let value_map = value_of
+            .into_iter()
+            .enumerate()
+            .map(|(virt_id, value)| (self.id_map.real_id_of(virt_id), value))
+            .collect();
+
+        Ok((value_map, edges))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ErrorKind, MatGraph};
+    use crate::storage::{DiMat, Mat};
+
+    #[test]
+    fn one_vertex_graph() {
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+
+        let minimax = BottleneckPath::init(&graph, Bottleneck::MinimizeMaximum)
+            .execute(&graph, a)
+            .unwrap();
+        let maximin = BottleneckPath::init(&graph, Bottleneck::MaximizeMinimum)
+            .execute(&graph, a)
+            .unwrap();
+
+        assert!(minimax.distance_to(a).unwrap().is_neg_infinite());
+        assert!(maximin.distance_to(a).unwrap().is_pos_infinite());
+        assert_eq!(minimax.vertex_count(), 1);
+        assert_eq!(minimax.edges_count(), 0);
+    }
+
+    #[test]
+    fn undirected_graph() {
+        // Given: Graph
+        //
+        //      a  --(3)--  b  --(7)--  c
+        //      |           |           |
+        //     (8)         (2)         (1)
+        //      |           |           |
+        //      d  --(6)--  e  --(4)--  f
+        //
+        let mut graph = MatGraph::init(Mat::<isize>::init());
+        let ids: Vec<usize> = (0..6).map(|_| graph.add_vertex()).collect();
+        let (a, b, c, d, e, f) = (ids[0], ids[1], ids[2], ids[3], ids[4], ids[5]);
+        graph.add_edge_unchecked(a, b, 3.into());
+        graph.add_edge_unchecked(b, c, 7.into());
+        graph.add_edge_unchecked(a, d, 8.into());
+        graph.add_edge_unchecked(b, e, 2.into());
+        graph.add_edge_unchecked(c, f, 1.into());
+        graph.add_edge_unchecked(d, e, 6.into());
+        graph.add_edge_unchecked(e, f, 4.into());
+
+        // When:
+        let minimax = BottleneckPath::init(&graph, Bottleneck::MinimizeMaximum)
+            .execute(&graph, a)
+            .unwrap();
+        let maximin = BottleneckPath::init(&graph, Bottleneck::MaximizeMinimum)
+            .execute(&graph, a)
+            .unwrap();
+
+        // Then:
+        assert_eq!(minimax.distance_to(c).unwrap(), 4.into());
+        assert_eq!(minimax.path_to(c).unwrap(), vec![a, b, e, f, c]);
+        assert_eq!(minimax.distance_to(d).unwrap(), 6.into());
+
+        assert_eq!(maximin.distance_to(c).unwrap(), 3.into());
+        assert_eq!(maximin.path_to(c).unwrap(), vec![a, b, c]);
+        assert_eq!(maximin.distance_to(f).unwrap(), 4.into());
+        assert_eq!(maximin.path_to(f).unwrap(), vec![a, d, e, f]);
+        assert_eq!(maximin.distance_to(e).unwrap(), 6.into());
+        assert_eq!(maximin.path_to(e).unwrap(), vec![a, d, e]);
+    }
+
+    #[test]
+    fn directed_graph_with_unreachable_vertex() {
+        // Given: Graph
+        //
+        //      a  --(-5)-->  b  --(-1)-->  c       d
+        //      |                           ^
+        //      '------------(-3)-----------'
+        //
+        let mut graph = MatGraph::init(DiMat::<isize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, (-5).into());
+        graph.add_edge_unchecked(b, c, (-1).into());
+        graph.add_edge_unchecked(a, c, (-3).into());
+
+        // When:
+        let minimax = BottleneckPath::init(&graph, Bottleneck::MinimizeMaximum)
+            .execute(&graph, a)
+            .unwrap();
+
+        // Then: Weights can be negative.
+        assert_eq!(minimax.distance_to(c).unwrap(), (-3).into());
+        assert_eq!(minimax.path_to(c).unwrap(), vec![a, c]);
+        assert!(minimax.distance_to(d).unwrap().is_pos_infinite());
+        assert!(minimax.path_to(d).is_none());
+    }
+
+    #[test]
+    fn custom_weights() {
+        // Given: Graph
+        //
+        //      a  --(1)--  b  --(2)--  c
+        //      |                       |
+        //      '----------(3)----------'
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 2.into());
+        let ac = graph.add_edge_unchecked(a, c, 3.into());
+
+        // When: Negating the weights, so minimax of negated weights is maximin of the weights.
+        let (value_map, tracer) = BottleneckPath::init(&graph, Bottleneck::MinimizeMaximum)
+            .execute_with_cost(&graph, a, |_, _, edge| {
+                Magnitude::Finite(-(edge.get_weight().unwrap() as isize))
+            })
+            .unwrap();
+
+        // Then:
+        assert_eq!(value_map[&c], (-3).into());
+        assert_eq!(tracer.edge_path(c).unwrap(), vec![(a, c, ac)]);
+    }
+
+    #[test]
+    fn vertex_not_found() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let error = BottleneckPath::init(&graph, Bottleneck::MaximizeMinimum)
+            .execute(&graph, 0)
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            &ErrorKind::VertexNotFound
+        );
+    }
+}
@@ -0,0 +1,23 @@
+use anyhow::Result;
+use magnitude::Magnitude;
+use num_traits::{CheckedAdd, Zero};
+
+use crate::graph::Error;
+
+// Same as `lhs + rhs`, except when both are finite and their sum does not fit in `W`:
+// * If the sum is too large it returns `None`. Such sum is larger than every finite distance, so it never relaxes a reachable vertex.
+//   Caller must report an error only if the vertex remains unreachable, because its real distance does not fit in `W`.
+// * If the sum is too small it returns `WeightOverflow` error, because such sum is smaller than every distance.
+pub(crate) fn checked_add<W>(lhs: Magnitude<W>, rhs: Magnitude<W>) -> Result<Option<Magnitude<W>>>
+where
+    W: Copy + Zero + Ord + CheckedAdd,
+{
+    match (lhs, rhs) {
+        (Magnitude::Finite(lhs), Magnitude::Finite(rhs)) => match lhs.checked_add(&rhs) {
+            Some(sum) => Ok(Some(sum.into())),
+            None if rhs < W::zero() => Err(Error::new_wo())?,
+            None => Ok(None),
+        },
+        _ => Ok(Some(lhs + rhs)),
+    }
+}
@@ -0,0 +1,410 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use anyhow::Result;
+use magnitude::Magnitude;
+use num_traits::{CheckedAdd, Unsigned, Zero};
+
+use super::checked::checked_add;
+use crate::graph::{Edge, EdgeDir, Error};
+use crate::provide::{Edges, Graph, IdMap, Vertices};
+
+// A partial path from the source vertex, which ends at vertex with virtual id: `virt_id`.
+struct Label<W> {
+    virt_id: usize,
+    cost: Magnitude<W>,
+    consumption: Vec<f64>,
+
+    // Index of the label that this label extends.
+    parent: Option<usize>,
+
+    // A label is dominated if there is a path to the same vertex that is not more costly and does not consume more of any resource.
+    is_dominated: bool,
+}
+
+// Ids of the vertices on a path, its cost and the amount of each resource it consumes.
+type ConstrainedPath<W> = (Vec<usize>, Magnitude<W>, Vec<f64>);
+
+// Checks if a path with `cost` and `consumption` dominates a path with `other_cost` and `other_consumption`.
+fn dominates<W: Ord>(
+    cost: &Magnitude<W>,
+    consumption: &[f64],
+    other_cost: &Magnitude<W>,
+    other_consumption: &[f64],
+) -> bool {
+    cost <= other_cost
+        && consumption
+            .iter()
+            .zip(other_consumption)
+            .all(|(used, other_used)| used <= other_used)
+}
+
+/// Finds the least costly path between two vertices, among paths that do not consume more than a limit of each resource(resource constrained shortest path).
+///
+/// Cost of each edge is its weight, and each edge also consumes some amount of each resource, like time, fuel or tolls.
+/// The problem is NP-hard, so the algorithm keeps a set of labels(partial paths) for each vertex and extends them in increasing order of cost.
+/// A label is discarded if it exceeds a limit, or if another label at the same vertex is not more costly and does not consume more of any resource.
+/// So it's fast when few paths are Pareto optimal, but can take exponential time in the worst case.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::ConstrainedShortestPath;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph with cost of the edges
+/// //
+/// //      a  --(1)-->  b  --(1)-->  d
+/// //      |                         ^
+/// //      '--(3)-->  c  --(3)-------'
+/// //
+/// let mut graph = MatGraph::init(DiMat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, d, 1.into());
+/// graph.add_edge_unchecked(a, c, 3.into());
+/// graph.add_edge_unchecked(c, d, 3.into());
+///
+/// // Edges through b take 10 hours, and edges through c take 2 hours.
+/// let hours_of = |src_id: usize, dst_id: usize, _: &_| vec![if src_id == b || dst_id == b { 10.0 } else { 2.0 }];
+///
+/// // When: Path must take at most 5 hours.
+/// let (path, cost, consumption) = ConstrainedShortestPath::init(&graph, vec![5.0])
+///     .execute(&graph, a, d, hours_of)
+///     .unwrap()
+///     .unwrap();
+///
+/// // Then: The cheaper path through b is too slow.
+/// assert_eq!(path, vec![a, c, d]);
+/// assert_eq!(cost, 6.into());
+/// assert_eq!(consumption, vec![4.0]);
+/// ```
+pub struct ConstrainedShortestPath {
+    id_map: IdMap,
+    limits: Vec<f64>,
+}
+
+impl ConstrainedShortestPath {
+    /// # Arguments
+    /// * `graph`: Graph to search for the path in.
+    /// * `limits`: Maximum amount of each resource that the path can consume.
+    ///
+    /// # Returns
+    /// Initialized algorithm.
+    pub fn init<W, E, Ty, G>(graph: &G, limits: Vec<f64>) -> Self
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+    {
+        ConstrainedShortestPath {
+            id_map: graph.continuous_id_map(),
+            limits,
+        }
+    }
+
+    /// # Arguments
+    /// * `graph`: Graph to search for the path in.
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    /// * `consumption_of`: Amount of each resource that an edge consumes, given its source id, destination id and the edge itself.
+    ///   Amounts must be in the same order as the limits.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing
+    ///   * `Some`: Ids of the vertices on the path from `src_id` to `dst_id`(including both), its cost and the amount of each resource it consumes.
+    ///   * `None`: If there is no path from `src_id` to `dst_id` within the limits.
+    /// * `Err`:
+    ///   * [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if `src_id` or `dst_id` is not in the graph.
+    ///   * [`InvalidWeight`](crate::graph::ErrorKind::InvalidWeight) if an edge consumes a negative amount of a resource.
+    ///   * [`WeightOverflow`](crate::graph::ErrorKind::WeightOverflow) if cost of the only paths within the limits does not fit in `W`.
+    ///
+    /// # Panics
+    /// If `consumption_of` returns a different number of amounts than the number of limits.
+    pub fn execute<W, E, Ty, G, F>(
+        &self,
+        graph: &G,
+        src_id: usize,
+        dst_id: usize,
+        consumption_of: F,
+    ) -> Result<Option<ConstrainedPath<W>>>
+    where
+        W: Copy + Ord + Zero + Unsigned + CheckedAdd,
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+        F: Fn(usize, usize, &E) -> Vec<f64>,
+    {
+        if !graph.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else if !graph.contains_vertex(dst_id) {
+            Err(Error::new_vnf(dst_id))?
+        }
+
+        let dst_virt_id = self.id_map.virt_id_of(dst_id);
+
+        let mut labels = vec![Label {
+            virt_id: self.id_map.virt_id_of(src_id),
+            cost: W::zero().into(),
+            consumption: vec![0.0; self.limits.len()],
+            parent: None,
+            is_dominated: false,
+        }];
+        let mut labels_of = vec![vec![]; self.id_map.len()];
+        labels_of[labels[0].virt_id].push(0);
+
+        let mut heap = BinaryHeap::new();
+        heap.push(Reverse((labels[0].cost, 0)));
+
+        let mut overflowed = false;
+
+        while let Some(Reverse((cost, index))) = heap.pop() {
+            if labels[index].is_dominated {
+                continue;
+            }
+
+            let virt_id = labels[index].virt_id;
+            if virt_id == dst_virt_id {
+                return Ok(Some(self.path_of(&labels, index)));
+            }
+
+            let real_id = self.id_map.real_id_of(virt_id);
+            for (n_id, edge) in graph.edges_from_unchecked(real_id) {
+                let n_cost = match checked_add(cost, *edge.get_weight())? {
+                    Some(n_cost) if n_cost.is_finite() => n_cost,
+                    Some(_) => continue,
+                    None => {
+                        overflowed = true;
+                        continue;
+                    }
+                };
+
+                let used = consumption_of(real_id, n_id, edge);
+                assert_eq!(
+                    used.len(),
+                    self.limits.len(),
+                    "Number of consumed resources must be the same as the number of limits"
+                );
+                if used.iter().any(|amount| amount.is_nan() || *amount < 0.0) {
+                    Err(Error::new_iw(edge.get_id()))?
+                }
+
+                let n_consumption = labels[index]
+                    .consumption
+                    .iter()
+                    .zip(used)
+                    .map(|(consumed, amount)| consumed + amount)
+                    .collect::<Vec<f64>>();
+                if n_consumption
+                    .iter()
+                    .zip(&self.limits)
+                    .any(|(consumed, limit)| consumed > limit)
+                {
+                    continue;
+                }
+
+                let n_virt_id = self.id_map.virt_id_of(n_id);
+                if labels_of[n_virt_id].iter().any(|other: &usize| {
+                    let other = &labels[*other];
+                    dominates(&other.cost, &other.consumption, &n_cost, &n_consumption)
+                }) {
+                    continue;
+                }
+
+                labels_of[n_virt_id].retain(|other| {
+                    let is_dominated = dominates(
+                        &n_cost,
+                        &n_consumption,
+                        &labels[*other].cost,
+                        &labels[*other].consumption,
+                    );
+                    if is_dominated {
+                        labels[*other].is_dominated = true;
+                    }
+
+                    !is_dominated
+                });
+
+                labels_of[n_virt_id].push(labels.len());
+                heap.push(Reverse((n_cost, labels.len())));
+                labels.push(Label {
+                    virt_id: n_virt_id,
+                    cost: n_cost,
+                    consumption: n_consumption,
+                    parent: Some(index),
+                    is_dominated: false,
+                });
+            }
+        }
+
+        if overflowed {
+            Err(Error::new_wo())?
+        } else {
+            Ok(None)
+        }
+    }
+
+    // Reconstructs the path that ends with label at `index`.
+    fn path_of<W: Copy>(&self, labels: &[Label<W>], index: usize) -> ConstrainedPath<W> {
+        let mut path = vec![];
+        let mut current = Some(index);
+        while let Some(index) = current {
+            path.push(self.id_map.real_id_of(labels[index].virt_id));
+            current = labels[index].parent;
+        }
+        path.reverse();
+
+        (path, labels[index].cost, labels[index].consumption.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DefaultEdge, ErrorKind, MatGraph};
+    use crate::storage::{DiMat, Mat};
+
+    #[test]
+    fn same_source_and_destination() {
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+
+        let (path, cost, consumption) = ConstrainedShortestPath::init(&graph, vec![1.0])
+            .execute(&graph, a, a, |_, _, _| vec![1.0])
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(path, vec![a]);
+        assert_eq!(cost, 0.into());
+        assert_eq!(consumption, vec![0.0]);
+    }
+
+    #[test]
+    fn labels_that_are_not_dominated_are_kept() {
+        // Given: Graph with (cost, resource) of the edges
+        //
+        //      a  --(1, 5)-->  b  --(1, 2)-->  d
+        //      |               ^
+        //   (1, 1)             |
+        //      |               |
+        //      '-->  c  --(2, 1)
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let resources = vec![
+            (graph.add_edge_unchecked(a, b, 1.into()), 5.0),
+            (graph.add_edge_unchecked(b, d, 1.into()), 2.0),
+            (graph.add_edge_unchecked(a, c, 1.into()), 1.0),
+            (graph.add_edge_unchecked(c, b, 2.into()), 1.0),
+        ];
+        let consumption_of = |_, _, edge: &DefaultEdge<usize>| {
+            vec![
+                resources
+                    .iter()
+                    .find(|(edge_id, _)| *edge_id == edge.get_id())
+                    .unwrap()
+                    .1,
+            ]
+        };
+
+        // When:
+        let unlimited = ConstrainedShortestPath::init(&graph, vec![f64::INFINITY])
+            .execute(&graph, a, d, consumption_of)
+            .unwrap()
+            .unwrap();
+        let limited = ConstrainedShortestPath::init(&graph, vec![5.0])
+            .execute(&graph, a, d, consumption_of)
+            .unwrap()
+            .unwrap();
+        let too_limited = ConstrainedShortestPath::init(&graph, vec![3.0])
+            .execute(&graph, a, d, consumption_of)
+            .unwrap();
+
+        // Then: Both paths to b are needed, because the cheaper one consumes more.
+        assert_eq!(unlimited, (vec![a, b, d], 2.into(), vec![7.0]));
+        assert_eq!(limited, (vec![a, c, b, d], 4.into(), vec![4.0]));
+        assert!(too_limited.is_none());
+    }
+
+    #[test]
+    fn multiple_resources() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  d
+        //      |               |
+        //      '-----  c  -----'
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, d, 1.into());
+        graph.add_edge_unchecked(a, c, 2.into());
+        graph.add_edge_unchecked(c, d, 2.into());
+
+        // Edges of b consume the first resource, and edges of c consume the second one.
+        let consumption_of = |src_id: usize, dst_id: usize, _: &DefaultEdge<usize>| {
+            if src_id == b || dst_id == b {
+                vec![1.0, 0.0]
+            } else {
+                vec![0.0, 1.0]
+            }
+        };
+
+        // When:
+        let first_limited = ConstrainedShortestPath::init(&graph, vec![1.0, 2.0])
+            .execute(&graph, a, d, consumption_of)
+            .unwrap()
+            .unwrap();
+        let both_limited = ConstrainedShortestPath::init(&graph, vec![1.0, 1.0])
+            .execute(&graph, a, d, consumption_of)
+            .unwrap();
+
+        // Then:
+        assert_eq!(first_limited, (vec![a, c, d], 4.into(), vec![0.0, 2.0]));
+        assert!(both_limited.is_none());
+    }
+
+    #[test]
+    fn negative_consumption() {
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+
+        let error = ConstrainedShortestPath::init(&graph, vec![1.0])
+            .execute(&graph, a, b, |_, _, _| vec![-1.0])
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            &ErrorKind::InvalidWeight
+        );
+    }
+
+    #[test]
+    fn vertex_not_found() {
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+
+        let error = ConstrainedShortestPath::init(&graph, vec![])
+            .execute(&graph, a, a + 1, |_, _, _| vec![])
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            &ErrorKind::VertexNotFound
+        );
+    }
+}
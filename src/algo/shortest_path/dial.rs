@@ -0,0 +1,321 @@
+use anyhow::Result;
+use magnitude::Magnitude;
+use num_traits::{CheckedAdd, ToPrimitive, Unsigned, Zero};
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+
+use super::checked::checked_add;
+use crate::graph::{subgraph::ShortestPathSubgraph, Edge, EdgeDir, Error};
+use crate::provide::{Edges, Graph, Neighbors, Vertices};
+
+/// Dial's algorithm: Dijkstra's algorithm with a bucket queue, for graphs with small non-negative integer weights.
+///
+/// Vertices are put in buckets based on their tentative distance. Because no tentative distance is more than the maximum weight(C)
+/// ahead of the distance that is being processed, C + 1 buckets that are reused in a circular fashion are enough.
+///
+/// ## Note
+/// * C: Means maximum weight of the edges.
+/// * D: Means maximum distance of a reachable vertex from the source vertex, which is at most C * (|V| - 1).
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::Dial;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  --(2)--  b  --(3)--  c
+/// //      |                       |
+/// //      '-----------(7)---------'
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 2.into());
+/// graph.add_edge_unchecked(b, c, 3.into());
+/// graph.add_edge_unchecked(a, c, 7.into());
+///
+/// let sp_subgraph = Dial::init(&graph).execute(&graph, a).unwrap();
+///
+/// assert_eq!(sp_subgraph.distance_to(c).unwrap(), 5.into());
+/// assert_eq!(sp_subgraph.path_to(c).unwrap(), vec![a, b, c]);
+/// ```
+pub struct Dial<W> {
+    buckets: Vec<Vec<usize>>,
+    dist: Vec<Magnitude<W>>,
+    prev: Vec<Option<(usize, usize)>>,
+}
+
+impl<W: Copy + Ord + Zero + Any + Unsigned + ToPrimitive + CheckedAdd> Dial<W> {
+    pub fn init<E, Ty, G>(graph: &G) -> Self
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+    {
+        let vertex_count = graph.vertex_count();
+
+        Dial {
+            buckets: vec![],
+            dist: vec![Magnitude::PosInfinite; vertex_count],
+            prev: vec![None; vertex_count],
+        }
+    }
+
+    fn index_of(distance: Magnitude<W>) -> usize {
+        distance.unwrap().to_usize().unwrap()
+    }
+
+    /// # Arguments
+    /// * `graph`: Graph to search for shortest paths in.
+    /// * `src_id`: Id of the source vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing the shortest path subgraph rooted at the source vertex.
+    /// * `Err`:
+    ///   * [`InvalidWeight`](crate::graph::ErrorKind::InvalidWeight) if weight of an edge is infinite or does not fit in `usize`.
+    ///   * [`WeightOverflow`](crate::graph::ErrorKind::WeightOverflow) if distance of a vertex does not fit in `W`.
+    ///
+    /// # Complexity
+    /// O(|E| + D + C)
+    pub fn execute<E, Ty, G>(
+        mut self,
+        graph: &G,
+        src_id: usize,
+    ) -> Result<ShortestPathSubgraph<W, E, Ty, G>>
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Neighbors + Vertices + Graph<W, E, Ty>,
+    {
+        let mut max_weight = 0;
+        for (_, _, edge) in graph.edges() {
+            let weight = if edge.get_weight().is_finite() {
+                edge.get_weight().unwrap().to_usize()
+            } else {
+                None
+            };
+
+            match weight {
+                Some(weight) => max_weight = max_weight.max(weight),
+                None => Err(Error::new_iw(edge.get_id()))?,
+            }
+        }
+
+        let bucket_count = max_weight + 1;
+        self.buckets = vec![vec![]; bucket_count];
+
+        let id_map = graph.continuous_id_map();
+        let mut overflowed = vec![];
+
+        let src_virt_id = id_map.virt_id_of(src_id);
+        self.dist[src_virt_id] = W::zero().into();
+        self.buckets[0].push(src_virt_id);
+
+        let mut queued_count = 1;
+        let mut distance = 0;
+        while queued_count != 0 {
+            while let Some(virt_id) = self.buckets[distance % bucket_count].pop() {
+                queued_count -= 1;
+
+                // Vertex is pushed again with a smaller distance after this entry was added.
+                if Self::index_of(self.dist[virt_id]) != distance {
+                    continue;
+                }
+
+                let real_id = id_map.real_id_of(virt_id);
+
+                for (n_id, edge) in graph.edges_from_unchecked(real_id) {
+                    let n_virt_id = id_map.virt_id_of(n_id);
+
+                    let alt = match checked_add(self.dist[virt_id], *edge.get_weight())? {
+                        Some(alt) => alt,
+                        None => {
+                            overflowed.push(n_virt_id);
+                            continue;
+                        }
+                    };
+                    if alt < self.dist[n_virt_id] {
+                        self.dist[n_virt_id] = alt;
+                        self.prev[n_virt_id] = Some((real_id, edge.get_id()));
+
+                        self.buckets[Self::index_of(alt) % bucket_count].push(n_virt_id);
+                        queued_count += 1;
+                    }
+                }
+            }
+
+            distance += 1;
+        }
+
+        if overflowed
+            .into_iter()
+            .any(|virt_id| self.dist[virt_id].is_pos_infinite())
+        {
+            Err(Error::new_wo())?
+        }
+
+        let mut edges = vec![];
+        let mut distance_map = HashMap::new();
+        for virt_id in 0..graph.vertex_count() {
+            let real_id = id_map.real_id_of(virt_id);
+            distance_map.insert(real_id, self.dist[virt_id]);
+
+            if let Some((parent_id, edge_id)) = self.prev[virt_id] {
+                edges.push((parent_id, real_id, edge_id));
+            }
+        }
+
+        let vertices = edges
+            .iter()
+            .flat_map(|(src_id, dst_id, _)| vec![*src_id, *dst_id])
+            .chain(std::iter::once(src_id))
+            .collect::<HashSet<usize>>();
+
+        Ok(ShortestPathSubgraph::init(
+            graph,
+            edges,
+            vertices,
+            distance_map,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::Dijkstra;
+    use crate::graph::{DefaultEdge, ErrorKind, MatGraph};
+    use crate::storage::{DiMat, Mat};
+
+    #[test]
+    fn one_vertex_graph() {
+        // Given: Graph
+        //
+        //      a
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+
+        // When: Performing Dial algorithm.
+        let sp_subgraph = Dial::init(&graph).execute(&graph, a).unwrap();
+
+        // Then:
+        assert_eq!(sp_subgraph.vertex_count(), 1);
+        assert_eq!(sp_subgraph.distance_to(a).unwrap(), 0.into());
+    }
+
+    #[test]
+    fn infinite_weight() {
+        // Given: Graph
+        //
+        //      a  --(inf)--  b
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, DefaultEdge::init(Magnitude::PosInfinite));
+
+        // When: Performing Dial algorithm.
+        let result = Dial::init(&graph).execute(&graph, a);
+
+        // Then:
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn trivial_undirected_graph() {
+        // Given: Graph
+        //
+        //      a  --(6)--  b  --(5)--  c
+        //      |        /  |           |
+        //     (1)   (2)   (2)         (5)
+        //      |   /       |           |
+        //      d  --(1)--  e  ---------'
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 6.into());
+        graph.add_edge_unchecked(a, d, 1.into());
+        graph.add_edge_unchecked(b, d, 2.into());
+        graph.add_edge_unchecked(b, c, 5.into());
+        graph.add_edge_unchecked(b, e, 2.into());
+        graph.add_edge_unchecked(c, e, 5.into());
+        graph.add_edge_unchecked(d, e, 1.into());
+
+        // When: Performing Dial algorithm.
+        let sp_subgraph = Dial::init(&graph).execute(&graph, a).unwrap();
+
+        // Then: Distances match the ones found by Dijkstra.
+        let dijkstra_subgraph = Dijkstra::init(&graph).execute(&graph, a);
+        for vertex_id in [a, b, c, d, e] {
+            assert_eq!(
+                sp_subgraph.distance_to(vertex_id),
+                dijkstra_subgraph.distance_to(vertex_id)
+            );
+        }
+        assert_eq!(sp_subgraph.edges_count(), 4);
+        assert_eq!(sp_subgraph.path_to(b).unwrap(), vec![a, d, b]);
+    }
+
+    #[test]
+    fn directed_graph_with_zero_weights() {
+        // Given: Graph
+        //
+        //      a  --(4)-->  b  --(0)-->  c  --(3)-->  d       e
+        //      |                         ^
+        //      '-----------(4)-----------'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 4.into());
+        graph.add_edge_unchecked(b, c, 0.into());
+        graph.add_edge_unchecked(c, d, 3.into());
+        graph.add_edge_unchecked(a, c, 4.into());
+
+        // When: Performing Dial algorithm.
+        let sp_subgraph = Dial::init(&graph).execute(&graph, a).unwrap();
+
+        // Then:
+        assert_eq!(sp_subgraph.distance_to(b).unwrap(), 4.into());
+        assert_eq!(sp_subgraph.distance_to(c).unwrap(), 4.into());
+        assert_eq!(sp_subgraph.distance_to(d).unwrap(), 7.into());
+        assert!(sp_subgraph.distance_to(e).unwrap().is_pos_infinite());
+        assert_eq!(sp_subgraph.vertex_count(), 4);
+    }
+
+    #[test]
+    fn distance_overflow() {
+        // Given: Graph
+        //          200     100
+        //      a  -----  b  -----  c
+        //
+        let mut graph = MatGraph::init(Mat::<u8>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 200.into());
+        graph.add_edge_unchecked(b, c, 100.into());
+
+        // When:
+        let result = Dial::init(&graph).execute(&graph, a);
+
+        // Then:
+        let error = result.err().unwrap();
+        assert!(matches!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            ErrorKind::WeightOverflow
+        ));
+    }
+}
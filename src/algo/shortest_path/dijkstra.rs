@@ -1,21 +1,30 @@
+use anyhow::Result;
 use magnitude::Magnitude;
-use num_traits::{Unsigned, Zero};
+use num_traits::{CheckedAdd, Unsigned, Zero};
 use std::{any::Any, collections::HashSet};
 use std::collections::HashMap;
+use std::sync::Arc;
 
-use crate::provide::{Edges, Graph, Vertices};
+use super::checked::checked_add;
+use crate::algo::{AlgoContext, PathTracer};
+use crate::provide::{Edges, Graph, IdMap, Vertices};
+use crate::util::IndexedHeap;
 use crate::{
-    graph::{subgraph::ShortestPathSubgraph, Edge, EdgeDir},
+    graph::{subgraph::ShortestPathSubgraph, Edge, EdgeDir, Error},
     prelude::Neighbors,
 };
 
+// Distance of each vertex from source, and edges of the shortest path tree.
+type ShortestPathTree<W> = (HashMap<usize, Magnitude<W>>, Vec<(usize, usize, usize)>);
+
 pub struct Dijkstra<W> {
     visited: Vec<bool>,
     dist: Vec<Magnitude<W>>,
     prev: Vec<Magnitude<usize>>,
+    id_map: Arc<IdMap>,
 }
 
-impl<W: Copy + Ord + Zero + Any + Unsigned> Dijkstra<W> {
+impl<W: Copy + Ord + Zero + Any + Unsigned + CheckedAdd> Dijkstra<W> {
     pub fn init<E, Ty, G>(graph: &G) -> Self
     where
         E: Edge<W>,
@@ -28,37 +37,189 @@ impl<W: Copy + Ord + Zero + Any + Unsigned> Dijkstra<W> {
             visited: vec![false; vertex_count],
             dist: vec![Magnitude::PosInfinite; vertex_count],
             prev: vec![Magnitude::PosInfinite; vertex_count],
+            id_map: Arc::new(graph.continuous_id_map()),
         }
     }
 
-    fn next_id(&self) -> Option<usize> {
-        self.dist
-            .iter()
-            .enumerate()
-            .filter(|(virt_id, dist)| dist.is_finite() && self.visited[*virt_id] == false)
-            .min_by(|(_, dist1), (_, dist2)| dist1.cmp(dist2))
-            .and_then(|(v_id, _)| Some(v_id))
+    /// Same as `init`, but takes the id map and buffers from `context` instead of allocating them.
+    /// Use `release` after executing to give the buffers back.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to search for shortest paths in.
+    /// * `context`: Context that is used with `graph`.
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::algo::{AlgoContext, Dijkstra};
+    /// use prepona::storage::Mat;
+    /// use prepona::graph::MatGraph;
+    ///
+    /// let mut graph = MatGraph::init(Mat::<usize>::init());
+    /// let a = graph.add_vertex();
+    /// let b = graph.add_vertex();
+    /// let c = graph.add_vertex();
+    /// graph.add_edge_unchecked(a, b, 1.into());
+    /// graph.add_edge_unchecked(b, c, 2.into());
+    ///
+    /// let mut context = AlgoContext::init();
+    /// for src_id in graph.vertices() {
+    ///     let mut dijkstra = Dijkstra::init_in(&graph, &mut context);
+    ///     let sp_subgraph = dijkstra.execute(&graph, src_id);
+    ///     assert_eq!(sp_subgraph.distance_to(src_id).unwrap(), 0.into());
+    ///
+    ///     dijkstra.release(&mut context);
+    /// }
+    /// ```
+    pub fn init_in<E, Ty, G>(graph: &G, context: &mut AlgoContext) -> Self
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+    {
+        let id_map = context.id_map(graph);
+        let vertex_count = id_map.len();
+
+        Dijkstra {
+            visited: context.take_buffer(vertex_count, false),
+            dist: context.take_buffer(vertex_count, Magnitude::PosInfinite),
+            prev: context.take_buffer(vertex_count, Magnitude::PosInfinite),
+            id_map,
+        }
     }
 
-    pub fn execute<E, Ty, G>(
-        mut self,
-        graph: &G,
+    /// Gives buffers of the algorithm back to `context`, so they can be reused by the next algorithm.
+    ///
+    /// # Arguments
+    /// `context`: Context to keep the buffers.
+    pub fn release(self, context: &mut AlgoContext) {
+        context.put_buffer(self.visited);
+        context.put_buffer(self.dist);
+        context.put_buffer(self.prev);
+    }
+
+    /// # Panics
+    /// If distance of a vertex does not fit in `W`. Use `execute_checked` to get an error instead.
+    pub fn execute<'a, E, Ty, G>(
+        &mut self,
+        graph: &'a G,
         src_id: usize,
-    ) -> ShortestPathSubgraph<W, E, Ty, G>
+    ) -> ShortestPathSubgraph<'a, W, E, Ty, G>
     where
         E: Edge<W>,
         Ty: EdgeDir,
         G: Edges<W, E> + Neighbors + Vertices + Graph<W, E, Ty>,
+    {
+        self.execute_checked(graph, src_id).unwrap()
+    }
+
+    /// Same as `execute` but reports overflow of distances instead of panicking.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to search for shortest paths in.
+    /// * `src_id`: Id of the source vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing the shortest path subgraph rooted at the source vertex.
+    /// * `Err`: [`WeightOverflow`](crate::graph::ErrorKind::WeightOverflow) if distance of a vertex does not fit in `W`.
+    pub fn execute_checked<'a, E, Ty, G>(
+        &mut self,
+        graph: &'a G,
+        src_id: usize,
+    ) -> Result<ShortestPathSubgraph<'a, W, E, Ty, G>>
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Neighbors + Vertices + Graph<W, E, Ty>,
+    {
+        let (distance_map, edges) =
+            self.shortest_path_tree(graph, src_id, |_, _, edge: &E| *edge.get_weight())?;
+
+        let vertices = edges
+            .iter()
+            .flat_map(|(src_id, dst_id, _)| vec![*src_id, *dst_id])
+            .chain(std::iter::once(src_id))
+            .collect::<HashSet<usize>>();
+
+        Ok(ShortestPathSubgraph::init(
+            graph,
+            edges,
+            vertices,
+            distance_map,
+        ))
+    }
+
+    /// Same as `execute` but cost of each edge is computed by `cost_of` instead of using weight of the edge.
+    /// So costs can be derived from the stored weights(or anything else) without mutating or copying the graph.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to search for shortest paths in.
+    /// * `src_id`: Id of the source vertex.
+    /// * `cost_of`: Computes cost of an edge from its source id, destination id and the edge itself.
+    ///   Edges with infinite cost are never used.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing
+    ///   * Distance from the source vertex to each vertex, which is infinite for vertices that are not reachable.
+    ///   * Tracer to reconstruct the shortest path from the source vertex to each reachable vertex.
+    /// * `Err`: [`WeightOverflow`](crate::graph::ErrorKind::WeightOverflow) if distance of a vertex does not fit in `W2`.
+    pub fn execute_with_cost<W2, E, G, F>(
+        &self,
+        graph: &G,
+        src_id: usize,
+        cost_of: F,
+    ) -> Result<(HashMap<usize, Magnitude<W2>>, PathTracer)>
+    where
+        W2: Copy + Ord + Zero + Any + Unsigned + CheckedAdd,
+        E: Edge<W>,
+        G: Edges<W, E> + Vertices,
+        F: Fn(usize, usize, &E) -> Magnitude<W2>,
+    {
+        let vertex_count = self.visited.len();
+
+        let mut dijkstra = Dijkstra::<W2> {
+            visited: vec![false; vertex_count],
+            dist: vec![Magnitude::PosInfinite; vertex_count],
+            prev: vec![Magnitude::PosInfinite; vertex_count],
+            id_map: Arc::clone(&self.id_map),
+        };
+
+        let (distance_map, edges) = dijkstra.shortest_path_tree(graph, src_id, cost_of)?;
+
+        Ok((distance_map, PathTracer::from_edges(&edges)))
+    }
+
+    fn shortest_path_tree<W0, E, G, F>(
+        &mut self,
+        graph: &G,
+        src_id: usize,
+        cost_of: F,
+    ) -> Result<ShortestPathTree<W>>
+    where
+        E: Edge<W0>,
+        G: Edges<W0, E> + Vertices,
+        F: Fn(usize, usize, &E) -> Magnitude<W>,
     {
         let mut edges = vec![];
+        let mut overflowed = vec![];
+
+        // Buffers still hold the result of the previous execution, if there is one.
+        for virt_id in 0..self.visited.len() {
+            self.visited[virt_id] = false;
+            self.dist[virt_id] = Magnitude::PosInfinite;
+            self.prev[virt_id] = Magnitude::PosInfinite;
+        }
 
-        let id_map = graph.continuos_id_map();
+        let id_map = Arc::clone(&self.id_map);
 
         let src_virt_id = id_map.virt_id_of(src_id);
 
         self.dist[src_virt_id] = W::zero().into();
 
-        while let Some(virt_id) = self.next_id() {
+        let mut heap = IndexedHeap::with_capacity(self.visited.len());
+        heap.push(src_virt_id, self.dist[src_virt_id]);
+
+        while let Some((virt_id, _)) = heap.pop() {
             self.visited[virt_id] = true;
 
             let real_id = id_map.real_id_of(virt_id);
@@ -66,10 +227,17 @@ impl<W: Copy + Ord + Zero + Any + Unsigned> Dijkstra<W> {
             for (n_id, edge) in graph.edges_from_unchecked(real_id) {
                 let n_virt_id = id_map.virt_id_of(n_id);
 
-                let alt = self.dist[virt_id] + *edge.get_weight();
+                let alt = match checked_add(self.dist[virt_id], cost_of(real_id, n_id, edge))? {
+                    Some(alt) => alt,
+                    None => {
+                        overflowed.push(n_virt_id);
+                        continue;
+                    }
+                };
                 if alt < self.dist[n_virt_id] {
                     self.dist[n_virt_id] = alt;
                     self.prev[n_virt_id] = virt_id.into();
+                    heap.push_or_decrease(n_virt_id, alt);
 
                     edges.retain(|(_, dst_id, _)| *dst_id != n_id); // remove edge to neighbor
                     edges.push((real_id, n_id, edge.get_id())); // add new edge
@@ -77,26 +245,27 @@ impl<W: Copy + Ord + Zero + Any + Unsigned> Dijkstra<W> {
             }
         }
 
+        if overflowed
+            .into_iter()
+            .any(|virt_id| self.dist[virt_id].is_pos_infinite())
+        {
+            Err(Error::new_wo())?
+        }
+
         let mut distance_map = HashMap::new();
         for virt_id in 0..graph.vertex_count() {
             let real_id = id_map.real_id_of(virt_id);
             distance_map.insert(real_id, self.dist[virt_id]);
         }
 
-        let vertices = edges
-            .iter()
-            .flat_map(|(src_id, dst_id, _)| vec![*src_id, *dst_id])
-            .chain(std::iter::once(src_id))
-            .collect::<HashSet<usize>>();
-
-        ShortestPathSubgraph::init(graph, edges, vertices, distance_map)
+        Ok((distance_map, edges))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::graph::MatGraph;
+    use crate::graph::{ErrorKind, MatGraph};
     use crate::storage::{DiMat, Mat};
 
     #[test]
@@ -177,6 +346,9 @@ mod tests {
         assert_eq!(sp_subgraph.distance_to(c).unwrap(), 7.into());
         assert_eq!(sp_subgraph.distance_to(d).unwrap(), 1.into());
         assert_eq!(sp_subgraph.distance_to(e).unwrap(), 2.into());
+        assert_eq!(sp_subgraph.path_to(a).unwrap(), vec![a]);
+        assert_eq!(sp_subgraph.path_to(b).unwrap(), vec![a, d, b]);
+        assert_eq!(sp_subgraph.path_to(c).unwrap(), vec![a, d, e, c]);
     }
 
     #[test]
@@ -222,5 +394,157 @@ mod tests {
         assert_eq!(sp_subgraph.distance_to(c).unwrap(), 3.into());
         assert_eq!(sp_subgraph.distance_to(d).unwrap(), 1.into());
         assert_eq!(sp_subgraph.distance_to(e).unwrap(), 2.into());
+        assert_eq!(sp_subgraph.path_to(b).unwrap(), vec![a, d, e, c, b]);
+        assert_eq!(sp_subgraph.path_to(d).unwrap(), vec![a, d]);
+    }
+
+    #[test]
+    fn directed_graph_with_cost_function() {
+        // Given: Graph
+        //          6       1
+        //      a  -->  b  <--  c ---
+        //    1 |       |           |
+        //      |  2 /`````\ 2      |
+        //      |````       ````|   |
+        //      v               v   | 1
+        //      d  ---------->  e --'
+        //              1
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+
+        let ab = graph.add_edge_unchecked(a, b, 6.into());
+        graph.add_edge_unchecked(a, d, 1.into());
+        graph.add_edge_unchecked(b, d, 2.into());
+        graph.add_edge_unchecked(b, e, 2.into());
+        graph.add_edge_unchecked(c, b, 1.into());
+        graph.add_edge_unchecked(e, c, 1.into());
+        graph.add_edge_unchecked(d, e, 1.into());
+
+        // When: Each edge costs ten times its weight, and edge from e to c can not be used.
+        let (distances, tracer) = Dijkstra::init(&graph)
+            .execute_with_cost(&graph, a, |src_id, dst_id, edge| {
+                if src_id == e && dst_id == c {
+                    Magnitude::PosInfinite
+                } else {
+                    (edge.get_weight().unwrap() as u64 * 10).into()
+                }
+            })
+            .unwrap();
+
+        // Then:
+        assert_eq!(distances[&a], 0.into());
+        assert_eq!(distances[&b], 60.into());
+        assert_eq!(distances[&d], 10.into());
+        assert_eq!(distances[&e], 20.into());
+        assert!(distances[&c].is_pos_infinite());
+        assert_eq!(tracer.edge_path(b).unwrap(), vec![(a, b, ab)]);
+        assert_eq!(tracer.vertex_path(e).unwrap(), vec![a, d, e]);
+
+        // And: Stored weights are not changed.
+        assert_eq!(
+            Dijkstra::init(&graph).execute(&graph, a).distance_to(c),
+            Some(3.into())
+        );
+    }
+
+    #[test]
+    fn distance_overflow() {
+        // Given: Graph
+        //          200     100
+        //      a  -----  b  -----  c
+        //
+        let mut graph = MatGraph::init(Mat::<u8>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 200.into());
+        graph.add_edge_unchecked(b, c, 100.into());
+
+        // When: Distance from a to c does not fit in u8.
+        let result = Dijkstra::init(&graph).execute_checked(&graph, a);
+
+        // Then:
+        let error = result.err().unwrap();
+        assert!(matches!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            ErrorKind::WeightOverflow
+        ));
+
+        // And: Distances that fit are still computed from b.
+        let sp_subgraph = Dijkstra::init(&graph).execute_checked(&graph, b).unwrap();
+        assert_eq!(sp_subgraph.distance_to(a), Some(200.into()));
+        assert_eq!(sp_subgraph.distance_to(c), Some(100.into()));
+    }
+
+    #[test]
+    fn overflowing_path_is_not_shortest() {
+        // Given: Graph
+        //          200     100
+        //      a  -----  b  -----  c
+        //      |                   |
+        //      '------  d  --------'
+        //          201      10
+        //
+        let mut graph = MatGraph::init(Mat::<u8>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 200.into());
+        graph.add_edge_unchecked(b, c, 100.into());
+        graph.add_edge_unchecked(a, d, 201.into());
+        graph.add_edge_unchecked(d, c, 10.into());
+
+        // When: Path from a to c through b overflows, but path through d fits in u8.
+        let sp_subgraph = Dijkstra::init(&graph).execute_checked(&graph, a).unwrap();
+
+        // Then:
+        assert_eq!(sp_subgraph.distance_to(c), Some(211.into()));
+        assert_eq!(sp_subgraph.path_to(c).unwrap(), vec![a, d, c]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn distance_overflow_panics() {
+        let mut graph = MatGraph::init(Mat::<u8>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 200.into());
+        graph.add_edge_unchecked(b, c, 100.into());
+
+        Dijkstra::init(&graph).execute(&graph, a);
+    }
+
+    #[test]
+    fn reuses_buffers_of_context() {
+        // Given: Graph
+        //          1       2
+        //      a  -->  b  -->  c
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 2.into());
+        let mut context = AlgoContext::init();
+
+        // When: Executing from a and then from b using the same buffers.
+        let mut dijkstra = Dijkstra::init_in(&graph, &mut context);
+        let from_a = dijkstra.execute(&graph, a);
+        let from_b = dijkstra.execute(&graph, b);
+        dijkstra.release(&mut context);
+
+        // Then: Second execution is not affected by the first one, and buffers are kept in the context.
+        assert_eq!(from_a.distance_to(c), Some(3.into()));
+        assert_eq!(from_b.distance_to(c), Some(2.into()));
+        assert_eq!(from_b.distance_to(a), Some(Magnitude::PosInfinite));
+        assert_eq!(context.buffer_count::<bool>(), 1);
+        assert_eq!(context.buffer_count::<Magnitude<usize>>(), 2);
     }
 }
@@ -0,0 +1,296 @@
+use std::collections::HashMap;
+
+use magnitude::Magnitude;
+use num_traits::Float;
+
+use crate::graph::Edge;
+use crate::provide::{Edges, IdMap, Vertices};
+
+// Number of entries that are relaxed together in the inner loop. Processing a fixed number of independent entries without
+// any branch lets the compiler turn the loop into SIMD instructions for both `f32` and `f64`.
+const LANES: usize = 8;
+
+/// Dense matrix of distances between every pair of vertices of a graph, stored row by row in a single vector.
+///
+/// Weights are floating point numbers so that missing edges can be represented by infinity and the min-plus(tropical) product
+/// can be computed over contiguous rows, which the compiler vectorizes.
+/// It's used by [`BlockedFloydWarshall`](crate::algo::BlockedFloydWarshall) to compute all-pairs shortest paths.
+///
+/// ## Generic Parameters
+/// * `F`: **F**loating point type of the distances: `f32` or `f64`.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::MatGraph;
+/// use prepona::algo::DistanceMatrix;
+///
+/// // Given: Graph
+/// //          1.5       2.0
+/// //      a  -----> b  -----> c
+/// //
+/// let mut graph = MatGraph::init(DiMat::<f64>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.5.into());
+/// graph.add_edge_unchecked(b, c, 2.0.into());
+///
+/// // Squaring the weight matrix gives the shortest paths with at most two edges.
+/// let weights = DistanceMatrix::from_graph(&graph);
+/// let two_hops = weights.min_plus(&weights);
+///
+/// assert_eq!(weights.distance(a, c), Some(f64::INFINITY));
+/// assert_eq!(two_hops.distance(a, c), Some(3.5));
+/// assert_eq!(two_hops.distance(c, a), Some(f64::INFINITY));
+/// ```
+#[derive(Debug, Clone)]
+pub struct DistanceMatrix<F> {
+    size: usize,
+    entries: Vec<F>,
+
+    id_map: IdMap,
+}
+
+impl<F: Float> DistanceMatrix<F> {
+    /// # Arguments
+    /// `graph`: Graph to build the matrix from.
+    ///
+    /// # Returns
+    /// Weight matrix of `graph`: Distance of each vertex to itself is zero, distance between two adjacent vertices is the smallest weight of the edges between them,
+    /// and other distances are infinity. Edges with negative infinite weight are stored as negative infinity and edges with positive infinite weight are ignored.
+    ///
+    /// # Complexity
+    /// O(|V|<sup>2</sup> + |E|)
+    pub fn from_graph<E, G>(graph: &G) -> Self
+    where
+        E: Edge<F>,
+        G: Edges<F, E> + Vertices,
+    {
+        let id_map = graph.continuous_id_map();
+        let size = id_map.len();
+
+        let mut entries = vec![F::infinity(); size * size];
+        for virt_id in 0..size {
+            entries[virt_id * size + virt_id] = F::zero();
+        }
+
+        for (src_virt_id, src_real_id) in
+            (0..size).map(|virt_id| (virt_id, id_map.real_id_of(virt_id)))
+        {
+            for (dst_real_id, edge) in graph.edges_from_unchecked(src_real_id) {
+                let weight = match edge.get_weight() {
+                    Magnitude::Finite(weight) => *weight,
+                    Magnitude::NegInfinite => F::neg_infinity(),
+                    Magnitude::PosInfinite => continue,
+                };
+
+                let entry = &mut entries[src_virt_id * size + id_map.virt_id_of(dst_real_id)];
+                if weight < *entry {
+                    *entry = weight;
+                }
+            }
+        }
+
+        DistanceMatrix {
+            size,
+            entries,
+            id_map,
+        }
+    }
+
+    /// Computes the min-plus(tropical) product of the two matrices.
+    /// Entry (`i`, `j`) of the result is the minimum of `self[i][k] + other[k][j]` over all `k`.
+    /// So if both matrices contain distances, the result contains the distances of paths that first follow `self` and then `other`.
+    ///
+    /// # Arguments
+    /// `other`: Right hand side of the product.
+    ///
+    /// # Returns
+    /// The product which uses the id map of `self`.
+    ///
+    /// # Complexity
+    /// O(|V|<sup>3</sup>)
+    ///
+    /// # Panics
+    /// If the matrices are not of the same size.
+    pub fn min_plus(&self, other: &DistanceMatrix<F>) -> DistanceMatrix<F> {
+        assert_eq!(
+            self.size, other.size,
+            "Can not multiply matrices of different sizes"
+        );
+
+        let size = self.size;
+        let mut entries = vec![F::infinity(); size * size];
+
+        for (row, lhs_row) in entries
+            .chunks_exact_mut(size.max(1))
+            .zip(self.entries.chunks_exact(size.max(1)))
+        {
+            for (k, lhs) in lhs_row.iter().enumerate() {
+                relax_row(row, *lhs, &other.entries[k * size..(k + 1) * size]);
+            }
+        }
+
+        DistanceMatrix {
+            size,
+            entries,
+            id_map: self.id_map.clone(),
+        }
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing the distance from source to destination, which is infinity if there is no path between them.
+    /// * `None`: If either vertex was not in the graph.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn distance(&self, src_id: usize, dst_id: usize) -> Option<F> {
+        let src_virt_id = self.id_map.get_virt_id(src_id)?;
+        let dst_virt_id = self.id_map.get_virt_id(dst_id)?;
+
+        Some(self.entries[src_virt_id * self.size + dst_virt_id])
+    }
+
+    /// # Returns
+    /// Number of vertices in the matrix.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// # Returns
+    /// Map between ids of vertices and their row(and column) in the matrix.
+    pub fn id_map(&self) -> &IdMap {
+        &self.id_map
+    }
+
+    /// # Returns
+    /// Distances in the same format as [`FloydWarshall`](crate::algo::FloydWarshall): (`src_id`, `dst_id`) -> distance, where infinity is converted to `PosInfinite`.
+    pub fn into_distance_map(self) -> HashMap<(usize, usize), Magnitude<F>> {
+        let mut distance_map = HashMap::with_capacity(self.entries.len());
+
+        for (index, distance) in self.entries.into_iter().enumerate() {
+            let src_id = self.id_map.real_id_of(index / self.size);
+            let dst_id = self.id_map.real_id_of(index % self.size);
+
+            let distance = if distance == F::infinity() {
+                Magnitude::PosInfinite
+            } else if distance == F::neg_infinity() {
+                Magnitude::NegInfinite
+            } else {
+                distance.into()
+            };
+
+            distance_map.insert((src_id, dst_id), distance);
+        }
+
+        distance_map
+    }
+
+    pub(crate) fn entries(&self) -> &[F] {
+        &self.entries
+    }
+
+    pub(crate) fn entries_mut(&mut self) -> &mut Vec<F> {
+        &mut self.entries
+    }
+}
+
+// Relaxes each entry of `row` with the path that goes through `via` and then uses the corresponding entry of `via_row`:
+// row[j] = min(row[j], via + via_row[j])
+pub(crate) fn relax_row<F: Float>(row: &mut [F], via: F, via_row: &[F]) {
+    // Nothing can be relaxed through an unreachable vertex.
+    if via == F::infinity() {
+        return;
+    }
+
+    let mut row_chunks = row.chunks_exact_mut(LANES);
+    let mut via_chunks = via_row.chunks_exact(LANES);
+    for (row_chunk, via_chunk) in (&mut row_chunks).zip(&mut via_chunks) {
+        for lane in 0..LANES {
+            let alt = via + via_chunk[lane];
+            if alt < row_chunk[lane] {
+                row_chunk[lane] = alt;
+            }
+        }
+    }
+
+    for (entry, via_entry) in row_chunks
+        .into_remainder()
+        .iter_mut()
+        .zip(via_chunks.remainder())
+    {
+        let alt = via + *via_entry;
+        if alt < *entry {
+            *entry = alt;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DefaultEdge, MatGraph};
+    use crate::provide::*;
+    use crate::storage::{DiMat, Mat};
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(DiMat::<f64>::init());
+
+        let weights = DistanceMatrix::from_graph(&graph);
+        let product = weights.min_plus(&weights);
+
+        assert_eq!(product.size(), 0);
+        assert!(product.into_distance_map().is_empty());
+    }
+
+    #[test]
+    fn min_plus_of_undirected_graph() {
+        // Given: Path of 10 vertices with removed first vertex, so rows are longer than a chunk and ids are not continuous.
+        let mut graph = MatGraph::init(Mat::<f32>::init());
+        let vertices: Vec<usize> = (0..11).map(|_| graph.add_vertex()).collect();
+        graph.remove_vertex_unchecked(vertices[0]);
+        for window in vertices[1..].windows(2) {
+            graph.add_edge_unchecked(window[0], window[1], 1.0.into());
+        }
+
+        // When: Computing paths with at most 1, 2 and 4 edges.
+        let one = DistanceMatrix::from_graph(&graph);
+        let two = one.min_plus(&one);
+        let four = two.min_plus(&two);
+
+        // Then:
+        let (first, last) = (vertices[1], vertices[10]);
+        assert_eq!(one.distance(first, first), Some(0.0));
+        assert_eq!(two.distance(first, vertices[3]), Some(2.0));
+        assert_eq!(two.distance(vertices[3], first), Some(2.0));
+        assert_eq!(four.distance(first, vertices[5]), Some(4.0));
+        assert_eq!(four.distance(first, last), Some(f32::INFINITY));
+        assert_eq!(four.distance(vertices[0], last), None);
+    }
+
+    #[test]
+    fn parallel_edges_and_infinite_weights() {
+        // Given: Graph with two edges from a to b and an infinite edge from b to a.
+        let mut graph = MatGraph::init(DiMat::<f64>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 3.0.into());
+        graph.add_edge_unchecked(a, b, (-1.0).into());
+        graph.add_edge_unchecked(b, a, DefaultEdge::init(Magnitude::PosInfinite));
+
+        // When: Building the weight matrix.
+        let distance_map = DistanceMatrix::from_graph(&graph).into_distance_map();
+
+        // Then:
+        assert_eq!(distance_map[&(a, b)], (-1.0).into());
+        assert!(distance_map[&(b, a)].is_pos_infinite());
+        assert_eq!(distance_map[&(b, b)], 0.0.into());
+    }
+}
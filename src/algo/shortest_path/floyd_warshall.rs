@@ -1,9 +1,10 @@
 use magnitude::Magnitude;
-use num_traits::Zero;
+use num_traits::{CheckedAdd, Zero};
 use std::any::Any;
 use std::collections::HashMap;
 
-use crate::graph::Edge;
+use super::checked::checked_add;
+use crate::graph::{Edge, Error};
 use crate::provide;
 
 pub struct FloydWarshall {}
@@ -13,7 +14,7 @@ impl FloydWarshall {
         FloydWarshall {}
     }
 
-    pub fn execute<G, W: Copy + Zero + Any + Ord + std::fmt::Debug, E: Edge<W>>(
+    pub fn execute<G, W: Copy + Zero + Any + Ord + CheckedAdd + std::fmt::Debug, E: Edge<W>>(
         self,
         graph: &G,
     ) -> Result<HashMap<(usize, usize), Magnitude<W>>, String>
@@ -23,9 +24,10 @@ impl FloydWarshall {
         let vertices = graph.vertices();
         let vertex_count = vertices.len();
 
-        let id_map = graph.continuos_id_map();
+        let id_map = graph.continuous_id_map();
 
         let mut dist = vec![vec![Magnitude::PosInfinite; vertex_count]; vertex_count];
+        let mut overflowed = vec![];
 
         for &u_real_id in &vertices {
             let u_virt_id = id_map.virt_id_of(u_real_id);
@@ -47,10 +49,18 @@ impl FloydWarshall {
                 for &j in &vertices {
                     let j_virt_id = id_map.virt_id_of(j);
 
-                    if (dist[i_virt_id][k] + dist[k][j_virt_id]).is_finite()
-                        && dist[i_virt_id][j_virt_id] > dist[i_virt_id][k] + dist[k][j_virt_id]
+                    let alt = match checked_add(dist[i_virt_id][k], dist[k][j_virt_id])
+                        .map_err(|err| err.to_string())?
                     {
-                        dist[i_virt_id][j_virt_id] = dist[i_virt_id][k] + dist[k][j_virt_id]
+                        Some(alt) => alt,
+                        None => {
+                            overflowed.push((i_virt_id, j_virt_id));
+                            continue;
+                        }
+                    };
+
+                    if alt.is_finite() && dist[i_virt_id][j_virt_id] > alt {
+                        dist[i_virt_id][j_virt_id] = alt
                     }
                 }
 
@@ -64,6 +74,13 @@ impl FloydWarshall {
             }
         }
 
+        if overflowed
+            .into_iter()
+            .any(|(i, j)| dist[i][j].is_pos_infinite())
+        {
+            return Err(Error::new_wo().to_string());
+        }
+
         let mut distance_map = HashMap::new();
         for i in 0..vertex_count {
             let i_real_id = id_map.real_id_of(i);
@@ -316,4 +333,24 @@ mod tests {
 
         assert!(shortest_paths.is_err());
     }
+
+    #[test]
+    fn distance_overflow() {
+        // Given: Graph
+        //          200     100
+        //      a  -----  b  -----  c
+        //
+        let mut graph = MatGraph::init(Mat::<u8>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 200.into());
+        graph.add_edge_unchecked(b, c, 100.into());
+
+        // When: Distance between a and c does not fit in u8.
+        let distance_map = FloydWarshall::init().execute(&graph);
+
+        // Then:
+        assert!(distance_map.is_err());
+    }
 }
@@ -1,7 +1,32 @@
+mod a_star;
 mod bellman_ford;
+mod blocked_floyd_warshall;
+mod bottleneck_path;
+mod checked;
+mod constrained_shortest_path;
+mod dial;
 mod dijkstra;
+mod distance_matrix;
 mod floyd_warshall;
+mod path_tracer;
+mod time_dependent_dijkstra;
+mod unweighted_bfs;
+mod widest_path;
+mod zero_one_bfs;
 
+pub use a_star::AStar;
 pub use bellman_ford::BellmanFord;
+pub use blocked_floyd_warshall::BlockedFloydWarshall;
+pub use bottleneck_path::{Bottleneck, BottleneckPath};
+pub use constrained_shortest_path::ConstrainedShortestPath;
+pub use dial::Dial;
 pub use dijkstra::Dijkstra;
+pub use distance_matrix::DistanceMatrix;
 pub use floyd_warshall::FloydWarshall;
+pub use path_tracer::PathTracer;
+pub use time_dependent_dijkstra::TimeDependentDijkstra;
+pub use unweighted_bfs::UnweightedBfs;
+pub use widest_path::WidestPath;
+pub use zero_one_bfs::ZeroOneBfs;
+
+pub(crate) use checked::checked_add;
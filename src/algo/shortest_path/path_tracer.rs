@@ -0,0 +1,242 @@
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+use magnitude::Magnitude;
+use num_traits::{CheckedAdd, Zero};
+
+use super::checked::checked_add;
+use crate::graph::{Edge, Error};
+use crate::provide;
+
+/// Reconstructs paths from a predecessor map, like the one built by shortest path algorithms.
+///
+/// Predecessor map maps each vertex to its parent and the id of the edge from the parent to the vertex.
+/// Vertices that are not in the map are roots, so path to each vertex starts from a root.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::PathTracer;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  --(2)-->  b  --(3)-->  c
+/// //
+/// let mut graph = MatGraph::init(DiMat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let ab = graph.add_edge_unchecked(a, b, 2.into());
+/// let bc = graph.add_edge_unchecked(b, c, 3.into());
+///
+/// let tracer = PathTracer::from_edges(&[(a, b, ab), (b, c, bc)]);
+///
+/// assert_eq!(tracer.vertex_path(c).unwrap(), vec![a, b, c]);
+/// assert_eq!(tracer.edge_path(c).unwrap(), vec![(a, b, ab), (b, c, bc)]);
+/// assert_eq!(tracer.total_weight(&graph, c).unwrap(), 5.into());
+/// ```
+pub struct PathTracer {
+    parent_of: HashMap<usize, (usize, usize)>,
+}
+
+impl PathTracer {
+    /// # Arguments
+    /// `parent_of`: Maps id of each vertex to (`parent_id`, `edge_id`), where `edge_id` is the id of the edge from parent to the vertex.
+    ///
+    /// # Returns
+    /// Initialized tracer.
+    pub fn init(parent_of: HashMap<usize, (usize, usize)>) -> Self {
+        PathTracer { parent_of }
+    }
+
+    /// # Arguments
+    /// `edges`: Edges of a tree in the format of: (`parent_id`, `child_id`, `edge_id`), like edges of a [`ShortestPathSubgraph`](crate::graph::subgraph::ShortestPathSubgraph).
+    ///
+    /// # Returns
+    /// Initialized tracer.
+    pub fn from_edges(edges: &[(usize, usize, usize)]) -> Self {
+        PathTracer::init(
+            edges
+                .iter()
+                .map(|(src_id, dst_id, edge_id)| (*dst_id, (*src_id, *edge_id)))
+                .collect(),
+        )
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing (`parent_id`, `edge_id`).
+    /// * `None`: If vertex has no parent.
+    pub fn parent_of(&self, vertex_id: usize) -> Option<(usize, usize)> {
+        self.parent_of.get(&vertex_id).copied()
+    }
+
+    /// # Arguments
+    /// `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing edges of the path from the root to destination in the format of: (`src_id`, `dst_id`, `edge_id`).
+    ///   Path is empty if destination has no parent.
+    /// * `Err`: [`Cycle`](crate::graph::ErrorKind::Cycle) if following the parents never reaches a root.
+    ///
+    /// # Complexity
+    /// O(length of the path)
+    pub fn edge_path(&self, dst_id: usize) -> Result<Vec<(usize, usize, usize)>> {
+        let mut path = vec![];
+        let mut visited = HashSet::new();
+
+        let mut vertex_id = dst_id;
+        while let Some((parent_id, edge_id)) = self.parent_of(vertex_id) {
+            if !visited.insert(vertex_id) {
+                Err(Error::new_c(parent_id, vertex_id))?
+            }
+
+            path.push((parent_id, vertex_id, edge_id));
+            vertex_id = parent_id;
+        }
+        path.reverse();
+
+        Ok(path)
+    }
+
+    /// # Arguments
+    /// `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing id of the vertices on the path from the root to destination, including both of them.
+    /// * `Err`: [`Cycle`](crate::graph::ErrorKind::Cycle) if following the parents never reaches a root.
+    ///
+    /// # Complexity
+    /// O(length of the path)
+    pub fn vertex_path(&self, dst_id: usize) -> Result<Vec<usize>> {
+        let edge_path = self.edge_path(dst_id)?;
+
+        let root_id = edge_path.first().map_or(dst_id, |(src_id, _, _)| *src_id);
+
+        Ok(std::iter::once(root_id)
+            .chain(edge_path.into_iter().map(|(_, dst_id, _)| dst_id))
+            .collect())
+    }
+
+    /// Checks that each edge of the path to destination exists in the `graph`.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph that the predecessor map is built from.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: If path is valid.
+    /// * `Err`:
+    ///     * [`Cycle`](crate::graph::ErrorKind::Cycle) if following the parents never reaches a root.
+    ///     * [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if a vertex of the path does not exist in the graph.
+    ///     * [`EdgeNotFound`](crate::graph::ErrorKind::EdgeNotFound) if an edge of the path does not exist between its end points.
+    pub fn validate<W, E, G>(&self, graph: &G, dst_id: usize) -> Result<()>
+    where
+        E: Edge<W>,
+        G: provide::Edges<W, E>,
+    {
+        self.edges_of(graph, dst_id).map(|_| ())
+    }
+
+    /// # Arguments
+    /// * `graph`: Graph that the predecessor map is built from.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing sum of the weights of the edges on the path to destination.
+    /// * `Err`:
+    ///     * If path is not valid. Checkout [`validate`](PathTracer::validate).
+    ///     * [`WeightOverflow`](crate::graph::ErrorKind::WeightOverflow) if the sum does not fit in `W`.
+    pub fn total_weight<W, E, G>(&self, graph: &G, dst_id: usize) -> Result<Magnitude<W>>
+    where
+        W: Copy + Zero + Ord + Any + CheckedAdd,
+        E: Edge<W>,
+        G: provide::Edges<W, E>,
+    {
+        let mut total_weight = W::zero().into();
+        for edge in self.edges_of(graph, dst_id)? {
+            total_weight =
+                checked_add(total_weight, *edge.get_weight())?.ok_or_else(Error::new_wo)?;
+        }
+
+        Ok(total_weight)
+    }
+
+    fn edges_of<'a, W, E, G>(&self, graph: &'a G, dst_id: usize) -> Result<Vec<&'a E>>
+    where
+        E: Edge<W>,
+        G: provide::Edges<W, E>,
+    {
+        self.edge_path(dst_id)?
+            .into_iter()
+            .map(|(src_id, dst_id, edge_id)| {
+                graph
+                    .edge_between(src_id, dst_id, edge_id)?
+                    .ok_or_else(|| Error::new_enf(edge_id).into())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::*;
+    use crate::storage::Mat;
+
+    #[test]
+    fn root() {
+        let tracer = PathTracer::init(HashMap::new());
+
+        assert!(tracer.edge_path(0).unwrap().is_empty());
+        assert_eq!(tracer.vertex_path(0).unwrap(), vec![0]);
+    }
+
+    #[test]
+    fn cycle_in_predecessor_map() {
+        let tracer = PathTracer::from_edges(&[(0, 1, 0), (1, 2, 1), (2, 1, 2)]);
+
+        assert!(tracer.vertex_path(2).is_err());
+        assert!(tracer.edge_path(1).is_err());
+    }
+
+    #[test]
+    fn path_in_undirected_graph() {
+        // Given: Graph
+        //
+        //      a  --(1)--  b  --(4)--  c       d
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+        let bc = graph.add_edge_unchecked(b, c, 4.into());
+
+        // When: Tracing path from c back to a.
+        let tracer = PathTracer::from_edges(&[(b, c, bc), (a, b, ab)]);
+
+        // Then:
+        assert_eq!(tracer.parent_of(c), Some((b, bc)));
+        assert_eq!(tracer.parent_of(a), None);
+        assert_eq!(tracer.vertex_path(c).unwrap(), vec![a, b, c]);
+        assert!(tracer.validate(&graph, c).is_ok());
+        assert_eq!(tracer.total_weight(&graph, c).unwrap(), 5.into());
+        assert_eq!(tracer.total_weight(&graph, a).unwrap(), 0.into());
+
+        // When: Predecessor map contains edges that are not in the graph.
+        let invalid_tracer = PathTracer::from_edges(&[(a, b, ab), (b, d, bc)]);
+
+        // Then:
+        assert!(invalid_tracer.validate(&graph, b).is_ok());
+        assert!(invalid_tracer.validate(&graph, d).is_err());
+        assert!(invalid_tracer.total_weight(&graph, d).is_err());
+    }
+}
@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use magnitude::Magnitude;
+use num_traits::{CheckedAdd, Zero};
+
+use super::checked::checked_add;
+use crate::algo::TemporalPaths;
+use crate::graph::{Edge, EdgeDir, Error};
+use crate::provide::{Edges, Graph, Vertices};
+use crate::util::IndexedHeap;
+
+/// Finds the earliest time that each vertex can be reached at, when time it takes to traverse an edge depends on the moment the edge is entered.
+/// For example travel times that are longer in rush hours, or waiting for the next departure of a transit line.
+///
+/// It's Dijkstra over arrival times: each edge is entered as soon as its source is reached, and arrives at its destination after the travel time of that moment.
+/// So travel times must be FIFO: entering an edge later must never reach its destination earlier. Otherwise waiting at a vertex could help, and arrivals may not be the earliest.
+/// Waiting for a departure can be modeled in the travel time itself, like the time until the next departure plus the duration of the ride.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::TimeDependentDijkstra;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::MatGraph;
+/// use magnitude::Magnitude;
+///
+/// // Given: Graph
+/// //
+/// //      a  -------->  b
+/// //      |             ^
+/// //      '-->  c  -----'
+/// //
+/// let mut graph = MatGraph::init(DiMat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let ab = graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(a, c, 1.into());
+/// graph.add_edge_unchecked(c, b, 1.into());
+///
+/// // Highway from a to b takes 10 minutes before minute 5 and 2 minutes after it. Other roads take 3 minutes.
+/// let travel_time_of = |_: usize, _: usize, edge: &DefaultEdge<usize>, time: usize| {
+///     if edge.get_id() == ab && time < 5 {
+///         Magnitude::Finite(10)
+///     } else if edge.get_id() == ab {
+///         Magnitude::Finite(2)
+///     } else {
+///         Magnitude::Finite(3)
+///     }
+/// };
+/// let dijkstra = TimeDependentDijkstra::init(&graph, travel_time_of);
+///
+/// // Leaving at minute 0, the side road is faster. Leaving at minute 5, the highway is faster.
+/// assert_eq!(dijkstra.execute(&graph, a, 0).unwrap().arrival_of(b), Some(6));
+/// assert_eq!(dijkstra.execute(&graph, a, 5).unwrap().arrival_of(b), Some(7));
+/// ```
+pub struct TimeDependentDijkstra<F> {
+    travel_time_of: F,
+}
+
+impl<F> TimeDependentDijkstra<F> {
+    /// # Arguments
+    /// * `graph`: Graph to find paths in.
+    /// * `travel_time_of`: Time it takes to traverse an edge, given its source id, destination id, the edge itself and the moment it's entered.
+    ///   `PosInfinite` means the edge can not be entered at that moment.
+    ///
+    /// # Returns
+    /// Initialized algorithm.
+    pub fn init<W, E, Ty, G>(_: &G, travel_time_of: F) -> Self
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+    {
+        TimeDependentDijkstra { travel_time_of }
+    }
+
+    /// # Arguments
+    /// * `graph`: Graph to find paths in.
+    /// * `src_id`: Id of the source vertex.
+    /// * `start_time`: Moment the search starts at the source vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing the earliest arrival time of, and a path to, each vertex that can be reached.
+    /// * `Err`:
+    ///   * [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if source vertex does not exist.
+    ///   * [`InvalidWeight`](crate::graph::ErrorKind::InvalidWeight) if travel time of an edge is negative.
+    ///   * [`WeightOverflow`](crate::graph::ErrorKind::WeightOverflow) if arrival time of a vertex that can only be reached that late does not fit in `T`.
+    ///
+    /// # Complexity
+    /// O((|V| + |E|).log(|V|)) calls to `travel_time_of`.
+    pub fn execute<T, W, E, Ty, G>(
+        &self,
+        graph: &G,
+        src_id: usize,
+        start_time: T,
+    ) -> Result<TemporalPaths<T>>
+    where
+        T: Copy + Ord + Zero + CheckedAdd,
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+        F: Fn(usize, usize, &E, T) -> Magnitude<T>,
+    {
+        if !graph.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        }
+
+        let mut arrival_of = HashMap::new();
+        let mut prev_of = HashMap::new();
+        let mut overflowed = vec![];
+        arrival_of.insert(src_id, start_time);
+
+        let id_map = graph.continuous_id_map();
+
+        let mut heap = IndexedHeap::with_capacity(graph.vertex_count());
+        heap.push(id_map.virt_id_of(src_id), start_time);
+
+        while let Some((virt_id, arrival)) = heap.pop() {
+            let vertex_id = id_map.real_id_of(virt_id);
+
+            for (dst_id, edge) in graph.edges_from_unchecked(vertex_id) {
+                let travel_time = (self.travel_time_of)(vertex_id, dst_id, edge, arrival);
+                if travel_time.is_pos_infinite() {
+                    continue;
+                } else if travel_time < T::zero().into() {
+                    Err(Error::new_iw(edge.get_id()))?
+                }
+
+                let dst_arrival = match checked_add(Magnitude::Finite(arrival), travel_time)? {
+                    Some(Magnitude::Finite(dst_arrival)) => dst_arrival,
+                    _ => {
+                        overflowed.push(dst_id);
+                        continue;
+                    }
+                };
+
+                let is_earlier = match arrival_of.get(&dst_id) {
+                    Some(current) => dst_arrival < *current,
+                    None => true,
+                };
+
+                if is_earlier {
+                    arrival_of.insert(dst_id, dst_arrival);
+                    prev_of.insert(dst_id, (vertex_id, edge.get_id()));
+                    heap.push_or_decrease(id_map.virt_id_of(dst_id), dst_arrival);
+                }
+            }
+        }
+
+        if overflowed
+            .into_iter()
+            .any(|vertex_id| !arrival_of.contains_key(&vertex_id))
+        {
+            Err(Error::new_wo())?
+        }
+
+        Ok(TemporalPaths::init(src_id, arrival_of, prev_of))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DefaultEdge, ErrorKind, MatGraph};
+    use crate::storage::{DiMat, Mat};
+
+    #[test]
+    fn unknown_source() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let error = TimeDependentDijkstra::init(
+            &graph,
+            |_: usize, _: usize, _: &DefaultEdge<usize>, _: usize| Magnitude::Finite(1usize),
+        )
+        .execute(&graph, 0, 0)
+        .unwrap_err();
+
+        assert_eq!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            &ErrorKind::VertexNotFound
+        );
+    }
+
+    #[test]
+    fn scheduled_departures() {
+        // Given: Graph
+        //
+        //      a  --->  b  --->  c
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+        let bc = graph.add_edge_unchecked(b, c, 1.into());
+
+        // Trains leave a every 10 minutes and b every 15 minutes, and each ride takes 4 minutes.
+        let travel_time_of = |_: usize, _: usize, edge: &DefaultEdge<usize>, time: usize| {
+            let period = if edge.get_id() == ab { 10 } else { 15 };
+            let wait = (period - time % period) % period;
+
+            Magnitude::Finite(wait + 4)
+        };
+
+        // When:
+        let paths = TimeDependentDijkstra::init(&graph, travel_time_of)
+            .execute(&graph, a, 3)
+            .unwrap();
+
+        // Then: Train from a leaves at 10 and reaches b at 14, and train from b leaves at 15 and reaches c at 19.
+        assert_eq!(paths.arrival_of(a), Some(3));
+        assert_eq!(paths.arrival_of(b), Some(14));
+        assert_eq!(paths.arrival_of(c), Some(19));
+        assert_eq!(paths.path_to(c).unwrap(), vec![(a, b, ab), (b, c, bc)]);
+    }
+
+    #[test]
+    fn closed_edges() {
+        // Given: Graph
+        //
+        //      a  ---  b
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+
+        // Edge is closed after minute 10.
+        let dijkstra = TimeDependentDijkstra::init(
+            &graph,
+            |_: usize, _: usize, _: &DefaultEdge<usize>, time: usize| {
+                if time <= 10 {
+                    Magnitude::Finite(1)
+                } else {
+                    Magnitude::PosInfinite
+                }
+            },
+        );
+
+        // When:
+        let early = dijkstra.execute(&graph, a, 10).unwrap();
+        let late = dijkstra.execute(&graph, a, 11).unwrap();
+
+        // Then:
+        assert_eq!(early.arrival_of(b), Some(11));
+        assert_eq!(late.arrival_of(b), None);
+        assert!(late.path_to(b).is_none());
+    }
+
+    #[test]
+    fn negative_travel_time() {
+        let mut graph = MatGraph::init(DiMat::<isize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+
+        let error = TimeDependentDijkstra::init(
+            &graph,
+            |_: usize, _: usize, _: &DefaultEdge<isize>, _: isize| Magnitude::Finite(-1isize),
+        )
+        .execute(&graph, a, 0)
+        .unwrap_err();
+
+        assert_eq!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            &ErrorKind::InvalidWeight
+        );
+    }
+
+    #[test]
+    fn overflow() {
+        let mut graph = MatGraph::init(DiMat::<u8>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+
+        let error = TimeDependentDijkstra::init(
+            &graph,
+            |_: usize, _: usize, _: &DefaultEdge<u8>, _: u8| Magnitude::Finite(100u8),
+        )
+        .execute(&graph, a, 200)
+        .unwrap_err();
+
+        assert_eq!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            &ErrorKind::WeightOverflow
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+
+use super::PathTracer;
+use crate::graph::Edge;
+use crate::provide::{Edges, Vertices};
+
+/// Finds shortest paths from a source vertex to all other vertices, ignoring weight of the edges.
+///
+/// Length of a path is the number of edges in it(hop distance). When weights are uniform, it finds the same paths as
+/// [`Dijkstra`](crate::algo::Dijkstra) in O(|V| + |E|).
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::UnweightedBfs;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  -->  b  -->  c       d
+/// //      |               ^
+/// //      '---------------'
+/// //
+/// let mut graph = MatGraph::init(DiMat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(a, c, 5.into());
+///
+/// let (distances, tracer) = UnweightedBfs::init(&graph).execute(&graph, a);
+///
+/// assert_eq!(distances[&c], 1);
+/// assert_eq!(tracer.vertex_path(c).unwrap(), vec![a, c]);
+/// assert!(!distances.contains_key(&d));
+/// ```
+pub struct UnweightedBfs {
+    queue: VecDeque<usize>,
+    distances: HashMap<usize, usize>,
+    parent_of: HashMap<usize, (usize, usize)>,
+}
+
+impl UnweightedBfs {
+    pub fn init<W, E, G>(_: &G) -> Self
+    where
+        E: Edge<W>,
+        G: Edges<W, E> + Vertices,
+    {
+        UnweightedBfs {
+            queue: VecDeque::new(),
+            distances: HashMap::new(),
+            parent_of: HashMap::new(),
+        }
+    }
+
+    /// # Arguments
+    /// * `graph`: Graph to search for shortest paths in.
+    /// * `src_id`: Id of the source vertex.
+    ///
+    /// # Returns
+    /// * Distance(number of edges) from the source vertex to each vertex that is reachable from it, including the source vertex itself.
+    /// * Tracer to reconstruct the shortest path from the source vertex to each reachable vertex.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|)
+    pub fn execute<W, E, G>(
+        mut self,
+        graph: &G,
+        src_id: usize,
+    ) -> (HashMap<usize, usize>, PathTracer)
+    where
+        E: Edge<W>,
+        G: Edges<W, E> + Vertices,
+    {
+        self.distances.insert(src_id, 0);
+        self.queue.push_back(src_id);
+
+        while let Some(vertex_id) = self.queue.pop_front() {
+            let distance = self.distances[&vertex_id] + 1;
+
+            for (n_id, edge) in graph.edges_from_unchecked(vertex_id) {
+                if let Entry::Vacant(entry) = self.distances.entry(n_id) {
+                    entry.insert(distance);
+                    self.parent_of.insert(n_id, (vertex_id, edge.get_id()));
+                    self.queue.push_back(n_id);
+                }
+            }
+        }
+
+        (self.distances, PathTracer::init(self.parent_of))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::Dijkstra;
+    use crate::graph::{ListGraph, MatGraph};
+    use crate::provide::*;
+    use crate::storage::{DiList, Mat};
+
+    #[test]
+    fn one_vertex_graph() {
+        // Given: Graph
+        //
+        //      a
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+
+        // When: Performing UnweightedBfs algorithm.
+        let (distances, tracer) = UnweightedBfs::init(&graph).execute(&graph, a);
+
+        // Then:
+        assert_eq!(distances.len(), 1);
+        assert_eq!(distances[&a], 0);
+        assert_eq!(tracer.vertex_path(a).unwrap(), vec![a]);
+    }
+
+    #[test]
+    fn trivial_undirected_graph() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c  ---  d
+        //      |                       |
+        //      e  ---------------------'
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+        graph.add_edge_unchecked(a, e, 1.into());
+        let ed = graph.add_edge_unchecked(e, d, 1.into());
+
+        // When: Performing UnweightedBfs algorithm.
+        let (distances, tracer) = UnweightedBfs::init(&graph).execute(&graph, a);
+
+        // Then: Distances match the ones found by Dijkstra because all weights are the same.
+        let sp_subgraph = Dijkstra::init(&graph).execute(&graph, a);
+        for vertex_id in [a, b, c, d, e] {
+            assert_eq!(
+                sp_subgraph.distance_to(vertex_id).unwrap(),
+                distances[&vertex_id].into()
+            );
+        }
+        assert_eq!(tracer.parent_of(d), Some((e, ed)));
+        assert_eq!(tracer.vertex_path(d).unwrap(), vec![a, e, d]);
+        assert!(tracer.validate(&graph, c).is_ok());
+    }
+
+    #[test]
+    fn directed_graph_ignores_weights() {
+        // Given: Graph
+        //
+        //      a  --(1)-->  b  --(1)-->  c  --(1)-->  d
+        //      |                                      ^
+        //      '------------------(9)-----------------'
+        //
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+        graph.add_edge_unchecked(a, d, 9.into());
+
+        // When: Performing UnweightedBfs algorithm from b.
+        let (distances, tracer) = UnweightedBfs::init(&graph).execute(&graph, b);
+
+        // Then:
+        assert_eq!(distances.len(), 3);
+        assert!(!distances.contains_key(&a));
+        assert_eq!(distances[&d], 2);
+
+        // When: Performing UnweightedBfs algorithm from a.
+        let (distances, tracer_from_a) = UnweightedBfs::init(&graph).execute(&graph, a);
+
+        // Then: Edge with larger weight is used because it has fewer hops.
+        assert_eq!(distances[&d], 1);
+        assert_eq!(tracer_from_a.vertex_path(d).unwrap(), vec![a, d]);
+        assert_eq!(tracer_from_a.total_weight(&graph, d).unwrap(), 9.into());
+        assert_eq!(tracer.vertex_path(d).unwrap(), vec![b, c, d]);
+    }
+}
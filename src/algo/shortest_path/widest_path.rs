@@ -0,0 +1,256 @@
+use anyhow::Result;
+use magnitude::Magnitude;
+
+use super::{Bottleneck, BottleneckPath};
+use crate::graph::{EdgeDir, Error, FlowEdge};
+use crate::provide::{Edges, Graph, Vertices};
+
+/// Finds the widest(maximum capacity) path between two vertices: the path whose edge with the least capacity has the most capacity among all paths.
+/// So it's the path that can carry the most flow on its own.
+///
+/// It uses [`BottleneckPath`](crate::algo::BottleneckPath) with capacities of the edges, instead of their weights.
+/// Edges with no capacity left are never used.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::WidestPath;
+/// use prepona::storage::DiFlowMat;
+/// use prepona::graph::{FlowEdge, SimpleGraph};
+///
+/// // Given: Graph with capacities of the edges
+/// //
+/// //           .--(3)-->  b  --(5)--.
+/// //           |                    v
+/// //           a  --(4)-->  c  --(4)-->  d
+/// //
+/// let mut graph = SimpleGraph::init(DiFlowMat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, FlowEdge::init_with(1.into(), 3, 0));
+/// graph.add_edge_unchecked(b, d, FlowEdge::init_with(1.into(), 5, 0));
+/// graph.add_edge_unchecked(a, c, FlowEdge::init_with(1.into(), 4, 0));
+/// graph.add_edge_unchecked(c, d, FlowEdge::init_with(1.into(), 4, 0));
+///
+/// let (path, width) = WidestPath::init(&graph).execute(&graph, a, d).unwrap().unwrap();
+///
+/// assert_eq!(path, vec![a, c, d]);
+/// assert_eq!(width, 4.into());
+/// ```
+pub struct WidestPath {
+    bottleneck_path: BottleneckPath,
+    is_residual: bool,
+}
+
+impl WidestPath {
+    /// # Arguments
+    /// `graph`: Graph to search for widest paths in.
+    ///
+    /// # Returns
+    /// Initialized algorithm that uses capacity of the edges, and not their residual capacity.
+    pub fn init<W, Ty, G>(graph: &G) -> Self
+    where
+        Ty: EdgeDir,
+        G: Edges<W, FlowEdge<W>> + Vertices + Graph<W, FlowEdge<W>, Ty>,
+    {
+        WidestPath {
+            bottleneck_path: BottleneckPath::init(graph, Bottleneck::MaximizeMinimum),
+            is_residual: false,
+        }
+    }
+
+    /// # Arguments
+    /// `is_residual`: If true, capacity of each edge is its capacity minus its flow.
+    /// So the widest path is the augmenting path that can increase the current flow the most.
+    ///
+    /// # Returns
+    /// The algorithm that uses the specified capacities.
+    pub fn with_residual(mut self, is_residual: bool) -> Self {
+        self.is_residual = is_residual;
+
+        self
+    }
+
+    /// # Arguments
+    /// * `graph`: Graph to search for the widest path in.
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing
+    ///   * `Some`: Containing id of the vertices on the widest path, including both source and destination, and the capacity of the path.
+    ///     Capacity is `PosInfinite` if source and destination are the same.
+    ///   * `None`: If destination is not reachable from source.
+    /// * `Err`: [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if either of the vertices is not in the graph.
+    ///
+    /// # Complexity
+    /// O((|V| + |E|) * log(|V|))
+    pub fn execute<W, Ty, G>(
+        self,
+        graph: &G,
+        src_id: usize,
+        dst_id: usize,
+    ) -> Result<Option<(Vec<usize>, Magnitude<usize>)>>
+    where
+        Ty: EdgeDir,
+        G: Edges<W, FlowEdge<W>> + Vertices + Graph<W, FlowEdge<W>, Ty>,
+    {
+        if !graph.contains_vertex(dst_id) {
+            Err(Error::new_vnf(dst_id))?
+        }
+
+        let is_residual = self.is_residual;
+        let (width_map, tracer) =
+            self.bottleneck_path
+                .execute_with_cost(graph, src_id, |_, _, edge: &FlowEdge<W>| {
+                    let capacity = if is_residual {
+                        (edge.get_capacity() as isize - edge.get_flow()).max(0) as usize
+                    } else {
+                        edge.get_capacity()
+                    };
+
+                    if capacity == 0 {
+                        Magnitude::NegInfinite
+                    } else {
+                        Magnitude::Finite(capacity)
+                    }
+                })?;
+
+        let width = width_map[&dst_id];
+        if width.is_neg_infinite() {
+            Ok(None)
+        } else {
+            Ok(Some((tracer.vertex_path(dst_id)?, width)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ErrorKind, SimpleGraph};
+    use crate::storage::{DiFlowMat, FlowMat};
+
+    #[test]
+    fn same_source_and_destination() {
+        let mut graph = SimpleGraph::init(DiFlowMat::<usize>::init());
+        let a = graph.add_vertex();
+
+        let (path, width) = WidestPath::init(&graph)
+            .execute(&graph, a, a)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(path, vec![a]);
+        assert!(width.is_pos_infinite());
+    }
+
+    #[test]
+    fn undirected_graph() {
+        // Given: Graph with capacities of the edges
+        //
+        //      a  --(10)--  b  --(1)--  c
+        //      |                        |
+        //     (2)                      (9)
+        //      |                        |
+        //      d  --------(3)---------  e
+        //
+        let mut graph = SimpleGraph::init(FlowMat::<usize>::init());
+        let ids: Vec<usize> = (0..5).map(|_| graph.add_vertex()).collect();
+        let (a, b, c, d, e) = (ids[0], ids[1], ids[2], ids[3], ids[4]);
+        graph.add_edge_unchecked(a, b, FlowEdge::init_with(1.into(), 10, 0));
+        graph.add_edge_unchecked(b, c, FlowEdge::init_with(1.into(), 1, 0));
+        graph.add_edge_unchecked(a, d, FlowEdge::init_with(1.into(), 2, 0));
+        graph.add_edge_unchecked(c, e, FlowEdge::init_with(1.into(), 9, 0));
+        graph.add_edge_unchecked(d, e, FlowEdge::init_with(1.into(), 3, 0));
+
+        // When:
+        let (path, width) = WidestPath::init(&graph)
+            .execute(&graph, c, a)
+            .unwrap()
+            .unwrap();
+
+        // Then:
+        assert_eq!(path, vec![c, e, d, a]);
+        assert_eq!(width, 2.into());
+    }
+
+    #[test]
+    fn residual_capacities() {
+        // Given: Graph with flow/capacity of the edges
+        //
+        //           .--(3/4)-->  b  --(3/4)--.
+        //           |                        v
+        //           a  --(0/2)-->  c  --(0/2)-->  d  --(0/5)-->  e
+        //
+        let mut graph = SimpleGraph::init(DiFlowMat::<usize>::init());
+        let ids: Vec<usize> = (0..5).map(|_| graph.add_vertex()).collect();
+        let (a, b, c, d, e) = (ids[0], ids[1], ids[2], ids[3], ids[4]);
+        graph.add_edge_unchecked(a, b, FlowEdge::init_with(1.into(), 4, 3));
+        graph.add_edge_unchecked(b, d, FlowEdge::init_with(1.into(), 4, 3));
+        graph.add_edge_unchecked(a, c, FlowEdge::init_with(1.into(), 2, 0));
+        graph.add_edge_unchecked(c, d, FlowEdge::init_with(1.into(), 2, 0));
+        graph.add_edge_unchecked(d, e, FlowEdge::init_with(1.into(), 5, 0));
+
+        // When:
+        let widest = WidestPath::init(&graph)
+            .execute(&graph, a, e)
+            .unwrap()
+            .unwrap();
+        let widest_residual = WidestPath::init(&graph)
+            .with_residual(true)
+            .execute(&graph, a, e)
+            .unwrap()
+            .unwrap();
+
+        // Then:
+        assert_eq!(widest, (vec![a, b, d, e], 4.into()));
+        assert_eq!(widest_residual, (vec![a, c, d, e], 2.into()));
+    }
+
+    #[test]
+    fn unreachable_destination() {
+        // Given: Graph
+        //
+        //      a  --(0)-->  b  <--(4)--  c
+        //
+        let mut graph = SimpleGraph::init(DiFlowMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, FlowEdge::init_with(1.into(), 0, 0));
+        graph.add_edge_unchecked(c, b, FlowEdge::init_with(1.into(), 4, 0));
+
+        // When:
+        let widest_path = WidestPath::init(&graph);
+
+        // Then: Edge without capacity is never used.
+        assert!(widest_path.execute(&graph, a, b).unwrap().is_none());
+    }
+
+    #[test]
+    fn vertex_not_found() {
+        let mut graph = SimpleGraph::init(DiFlowMat::<usize>::init());
+        let a = graph.add_vertex();
+
+        let src_error = WidestPath::init(&graph)
+            .execute(&graph, a + 1, a)
+            .err()
+            .unwrap();
+        let dst_error = WidestPath::init(&graph)
+            .execute(&graph, a, a + 1)
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            src_error.downcast_ref::<Error>().unwrap().kind(),
+            &ErrorKind::VertexNotFound
+        );
+        assert_eq!(
+            dst_error.downcast_ref::<Error>().unwrap().kind(),
+            &ErrorKind::VertexNotFound
+        );
+    }
+}
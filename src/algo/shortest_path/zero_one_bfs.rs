@@ -0,0 +1,275 @@
+use anyhow::Result;
+use magnitude::Magnitude;
+use num_traits::{CheckedAdd, One, Zero};
+use std::any::Any;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use super::checked::checked_add;
+use crate::graph::{subgraph::ShortestPathSubgraph, Edge, EdgeDir, Error};
+use crate::provide::{Edges, Graph, Neighbors, Vertices};
+
+/// Finds shortest paths from a source vertex in graphs whose edges have weight of either zero or one.
+///
+/// Uses a deque instead of a priority queue: vertices reached through an edge with weight zero are pushed to the front
+/// and vertices reached through an edge with weight one are pushed to the back. So it runs in O(|V| + |E|).
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::ZeroOneBfs;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  --(1)-->  b  --(0)-->  c
+/// //      |                         ^
+/// //      '-----------(1)-----------'
+/// //
+/// let mut graph = MatGraph::init(DiMat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 0.into());
+/// graph.add_edge_unchecked(a, c, 1.into());
+///
+/// let sp_subgraph = ZeroOneBfs::init(&graph).execute(&graph, a).unwrap();
+///
+/// assert_eq!(sp_subgraph.distance_to(c).unwrap(), 1.into());
+/// ```
+pub struct ZeroOneBfs<W> {
+    deque: VecDeque<usize>,
+    dist: Vec<Magnitude<W>>,
+    prev: Vec<Option<(usize, usize)>>,
+}
+
+impl<W: Copy + Ord + Zero + One + Any + CheckedAdd> ZeroOneBfs<W> {
+    pub fn init<E, Ty, G>(graph: &G) -> Self
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+    {
+        let vertex_count = graph.vertex_count();
+
+        ZeroOneBfs {
+            deque: VecDeque::new(),
+            dist: vec![Magnitude::PosInfinite; vertex_count],
+            prev: vec![None; vertex_count],
+        }
+    }
+
+    /// # Arguments
+    /// * `graph`: Graph to search for shortest paths in.
+    /// * `src_id`: Id of the source vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing the shortest path subgraph rooted at the source vertex.
+    /// * `Err`:
+    ///   * [`InvalidWeight`](crate::graph::ErrorKind::InvalidWeight) if weight of an edge is neither zero nor one.
+    ///   * [`WeightOverflow`](crate::graph::ErrorKind::WeightOverflow) if distance of a vertex does not fit in `W`.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|)
+    pub fn execute<E, Ty, G>(
+        mut self,
+        graph: &G,
+        src_id: usize,
+    ) -> Result<ShortestPathSubgraph<W, E, Ty, G>>
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Neighbors + Vertices + Graph<W, E, Ty>,
+    {
+        let zero = Magnitude::from(W::zero());
+        let one = Magnitude::from(W::one());
+
+        if let Some((_, _, edge)) = graph
+            .edges()
+            .into_iter()
+            .find(|(_, _, edge)| *edge.get_weight() != zero && *edge.get_weight() != one)
+        {
+            Err(Error::new_iw(edge.get_id()))?
+        }
+
+        let id_map = graph.continuous_id_map();
+        let mut overflowed = vec![];
+
+        let src_virt_id = id_map.virt_id_of(src_id);
+        self.dist[src_virt_id] = zero;
+        self.deque.push_back(src_virt_id);
+
+        while let Some(virt_id) = self.deque.pop_front() {
+            let real_id = id_map.real_id_of(virt_id);
+
+            for (n_id, edge) in graph.edges_from_unchecked(real_id) {
+                let n_virt_id = id_map.virt_id_of(n_id);
+
+                let alt = match checked_add(self.dist[virt_id], *edge.get_weight())? {
+                    Some(alt) => alt,
+                    None => {
+                        overflowed.push(n_virt_id);
+                        continue;
+                    }
+                };
+                if alt < self.dist[n_virt_id] {
+                    self.dist[n_virt_id] = alt;
+                    self.prev[n_virt_id] = Some((real_id, edge.get_id()));
+
+                    // A vertex may be pushed more than once. Its distance is final the first time it's popped,
+                    // so relaxing its neighbors again in later pops doesn't change anything.
+                    if *edge.get_weight() == zero {
+                        self.deque.push_front(n_virt_id);
+                    } else {
+                        self.deque.push_back(n_virt_id);
+                    }
+                }
+            }
+        }
+
+        if overflowed
+            .into_iter()
+            .any(|virt_id| self.dist[virt_id].is_pos_infinite())
+        {
+            Err(Error::new_wo())?
+        }
+
+        let mut edges = vec![];
+        let mut distance_map = HashMap::new();
+        for virt_id in 0..graph.vertex_count() {
+            let real_id = id_map.real_id_of(virt_id);
+            distance_map.insert(real_id, self.dist[virt_id]);
+
+            if let Some((parent_id, edge_id)) = self.prev[virt_id] {
+                edges.push((parent_id, real_id, edge_id));
+            }
+        }
+
+        let vertices = edges
+            .iter()
+            .flat_map(|(src_id, dst_id, _)| vec![*src_id, *dst_id])
+            .chain(std::iter::once(src_id))
+            .collect::<HashSet<usize>>();
+
+        Ok(ShortestPathSubgraph::init(
+            graph,
+            edges,
+            vertices,
+            distance_map,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::Dijkstra;
+    use crate::graph::MatGraph;
+    use crate::storage::{DiMat, Mat};
+
+    #[test]
+    fn one_vertex_graph() {
+        // Given: Graph
+        //
+        //      a
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+
+        // When: Performing ZeroOneBfs algorithm.
+        let sp_subgraph = ZeroOneBfs::init(&graph).execute(&graph, a).unwrap();
+
+        // Then:
+        assert_eq!(sp_subgraph.vertex_count(), 1);
+        assert_eq!(sp_subgraph.edges_count(), 0);
+        assert_eq!(sp_subgraph.distance_to(a).unwrap(), 0.into());
+    }
+
+    #[test]
+    fn invalid_weight() {
+        // Given: Graph
+        //
+        //      a  --(1)--  b  --(2)--  c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 2.into());
+
+        // When: Performing ZeroOneBfs algorithm.
+        let result = ZeroOneBfs::init(&graph).execute(&graph, a);
+
+        // Then:
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn undirected_graph() {
+        // Given: Graph
+        //
+        //      a  --(1)--  b  --(1)--  c
+        //      |                       |
+        //     (0)                     (0)
+        //      |                       |
+        //      d  --(1)--  e  --(0)--  f
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(a, d, 0.into());
+        graph.add_edge_unchecked(c, f, 0.into());
+        graph.add_edge_unchecked(d, e, 1.into());
+        graph.add_edge_unchecked(e, f, 0.into());
+
+        // When: Performing ZeroOneBfs algorithm.
+        let sp_subgraph = ZeroOneBfs::init(&graph).execute(&graph, a).unwrap();
+
+        // Then: Distances match the ones found by Dijkstra.
+        let dijkstra_subgraph = Dijkstra::init(&graph).execute(&graph, a);
+        for vertex_id in [a, b, c, d, e, f] {
+            assert_eq!(
+                sp_subgraph.distance_to(vertex_id),
+                dijkstra_subgraph.distance_to(vertex_id)
+            );
+        }
+        assert_eq!(sp_subgraph.distance_to(c).unwrap(), 1.into());
+        assert_eq!(sp_subgraph.path_to(c).unwrap(), vec![a, d, e, f, c]);
+        assert_eq!(sp_subgraph.edges_count(), 5);
+    }
+
+    #[test]
+    fn directed_graph() {
+        // Given: Graph
+        //
+        //      a  --(0)-->  b  --(1)-->  c       d
+        //      ^                         |
+        //      '-----------(0)-----------'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 0.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, a, 0.into());
+
+        // When: Performing ZeroOneBfs algorithm from c.
+        let sp_subgraph = ZeroOneBfs::init(&graph).execute(&graph, c).unwrap();
+
+        // Then:
+        assert_eq!(sp_subgraph.distance_to(a).unwrap(), 0.into());
+        assert_eq!(sp_subgraph.distance_to(b).unwrap(), 0.into());
+        assert!(sp_subgraph.distance_to(d).unwrap().is_pos_infinite());
+        assert_eq!(sp_subgraph.vertex_count(), 3);
+    }
+}
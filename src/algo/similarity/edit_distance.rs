@@ -0,0 +1,440 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::algo::similarity::edge_counts;
+use crate::graph::Edge;
+use crate::provide::{Direction, Edges, Vertices};
+
+/// Computes the exact edit distance between two graphs, using A* search over the edit operations.
+///
+/// Edit distance is the minimum number of vertex insertions, vertex deletions, edge insertions and edge deletions needed to make `lhs` isomorphic to `rhs`.
+/// Ids and weights are ignored, so only the structure of the graphs is compared.
+/// Parallel edges are counted separately.
+/// If one of the graphs is directed and the other is not, each undirected edge is considered as two edges in opposite directions.
+///
+/// Search maps vertices of `lhs` one by one to unmapped vertices of `rhs`, or deletes them.
+/// Cost of a partial mapping is bounded from below by the difference between the number of vertices left in each graph,
+/// plus the difference between the number of edges left in each graph. This bound prunes most mappings, but the search is still exponential.
+/// So it is only practical for small graphs, with up to a dozen vertices.
+///
+/// # Arguments
+/// * `lhs`: First graph.
+/// * `rhs`: Second graph.
+///
+/// # Returns
+/// Edit distance between the graphs.
+///
+/// # Complexity
+/// O((|V<sub>rhs</sub>| + 1)<sup>|V<sub>lhs</sub>|</sup>.|V|<sup>2</sup>) in the worst case.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::similarity::edit_distance;
+/// use prepona::storage::{List, Mat};
+/// use prepona::graph::{ListGraph, MatGraph};
+///
+/// // Given: Graphs
+/// //
+/// //      a  ---  b  ---  c       d  ---  e
+/// //                              |       |
+/// //                              f  ---  g
+/// //
+/// let mut lhs = MatGraph::init(Mat::<usize>::init());
+/// let a = lhs.add_vertex();
+/// let b = lhs.add_vertex();
+/// let c = lhs.add_vertex();
+/// lhs.add_edge_unchecked(a, b, 1.into());
+/// lhs.add_edge_unchecked(b, c, 1.into());
+///
+/// let mut rhs = ListGraph::init(List::<usize>::init());
+/// let d = rhs.add_vertex();
+/// let e = rhs.add_vertex();
+/// let f = rhs.add_vertex();
+/// let g = rhs.add_vertex();
+/// rhs.add_edge_unchecked(d, e, 1.into());
+/// rhs.add_edge_unchecked(e, g, 1.into());
+/// rhs.add_edge_unchecked(g, f, 1.into());
+/// rhs.add_edge_unchecked(f, d, 1.into());
+///
+/// // Insert a vertex and connect it to both ends of the path.
+/// assert_eq!(edit_distance(&lhs, &rhs), 3);
+/// ```
+pub fn edit_distance<W, E1, E2, G1, G2>(lhs: &G1, rhs: &G2) -> usize
+where
+    E1: Edge<W>,
+    E2: Edge<W>,
+    G1: Vertices + Edges<W, E1> + Direction,
+    G2: Vertices + Edges<W, E2> + Direction,
+{
+    let is_directed = lhs.is_directed() || rhs.is_directed();
+    let lhs = CountMatrix::init(lhs, is_directed);
+    let rhs = CountMatrix::init(rhs, is_directed);
+
+    // Vertices with more edges are mapped first, so mismatches are found sooner.
+    let mut order = (0..lhs.vertex_count).collect::<Vec<usize>>();
+    order.sort_by_key(|virt_id| Reverse(lhs.degree_of(*virt_id)));
+
+    let mut states = vec![State {
+        mapping: vec![],
+        is_used: vec![false; rhs.vertex_count],
+        cost: 0,
+        lhs_inner_edge_count: 0,
+        rhs_inner_edge_count: 0,
+        is_complete: false,
+    }];
+    if lhs.vertex_count == 0 {
+        states[0].complete(&rhs);
+    }
+
+    let mut heap = BinaryHeap::new();
+    heap.push((Reverse(states[0].cost), 0, 0));
+
+    while let Some((_, depth, index)) = heap.pop() {
+        if states[index].is_complete {
+            return states[index].cost;
+        }
+
+        let src_id = order[depth];
+        let targets = (0..rhs.vertex_count)
+            .filter(|dst_id| !states[index].is_used[*dst_id])
+            .map(Some)
+            .chain(std::iter::once(None))
+            .collect::<Vec<Option<usize>>>();
+
+        for target in targets {
+            let mut state = states[index].extend(&lhs, &rhs, &order, src_id, target);
+
+            let estimate = if depth + 1 == lhs.vertex_count {
+                state.complete(&rhs);
+                0
+            } else {
+                state.estimate(&lhs, &rhs)
+            };
+
+            heap.push((Reverse(state.cost + estimate), depth + 1, states.len()));
+            states.push(state);
+        }
+    }
+
+    unreachable!("Search always reaches a complete mapping")
+}
+
+// Number of edges between each ordered pair of vertices, indexed by virtual ids.
+// In undirected graphs, edges are counted for both orders of their end points.
+struct CountMatrix {
+    is_directed: bool,
+    vertex_count: usize,
+    edge_count: usize,
+    counts: Vec<usize>,
+}
+
+impl CountMatrix {
+    fn init<W, E, G>(graph: &G, is_directed: bool) -> Self
+    where
+        E: Edge<W>,
+        G: Vertices + Edges<W, E> + Direction,
+    {
+        let id_map = graph.continuous_id_map();
+        let vertex_count = graph.vertex_count();
+
+        let mut edge_count = 0;
+        let mut counts = vec![0; vertex_count * vertex_count];
+        for ((src_id, dst_id), count) in edge_counts(graph, is_directed) {
+            let src_id = id_map.virt_id_of(src_id);
+            let dst_id = id_map.virt_id_of(dst_id);
+
+            edge_count += count;
+            counts[src_id * vertex_count + dst_id] = count;
+            if !is_directed {
+                counts[dst_id * vertex_count + src_id] = count;
+            }
+        }
+
+        CountMatrix {
+            is_directed,
+            vertex_count,
+            edge_count,
+            counts,
+        }
+    }
+
+    fn count(&self, src_id: usize, dst_id: usize) -> usize {
+        self.counts[src_id * self.vertex_count + dst_id]
+    }
+
+    fn degree_of(&self, src_id: usize) -> usize {
+        (0..self.vertex_count)
+            .map(|dst_id| self.count(src_id, dst_id) + self.count(dst_id, src_id))
+            .sum()
+    }
+
+    // Number of edges between two vertices, in any direction.
+    fn count_between(&self, src_id: usize, dst_id: usize) -> usize {
+        if self.is_directed && src_id != dst_id {
+            self.count(src_id, dst_id) + self.count(dst_id, src_id)
+        } else {
+            self.count(src_id, dst_id)
+        }
+    }
+}
+
+// Partial mapping of vertices of `lhs`, in the search order, to vertices of `rhs`.
+struct State {
+    mapping: Vec<Option<usize>>,
+    is_used: Vec<bool>,
+    cost: usize,
+
+    // Number of edges between mapped vertices of `lhs`, and between used vertices of `rhs`.
+    lhs_inner_edge_count: usize,
+    rhs_inner_edge_count: usize,
+
+    is_complete: bool,
+}
+
+impl State {
+    // Returns a new state, in which `src_id` is mapped to `target`. If `target` is `None`, `src_id` is deleted.
+    fn extend(
+        &self,
+        lhs: &CountMatrix,
+        rhs: &CountMatrix,
+        order: &[usize],
+        src_id: usize,
+        target: Option<usize>,
+    ) -> State {
+        let mut cost = self.cost;
+        let mut lhs_inner_edge_count = self.lhs_inner_edge_count + lhs.count(src_id, src_id);
+        let mut rhs_inner_edge_count = self.rhs_inner_edge_count;
+
+        let rhs_count = |src_id: Option<usize>, dst_id: Option<usize>| match (src_id, dst_id) {
+            (Some(src_id), Some(dst_id)) => rhs.count(src_id, dst_id),
+            _ => 0,
+        };
+
+        if let Some(dst_id) = target {
+            rhs_inner_edge_count += rhs.count(dst_id, dst_id);
+        } else {
+            cost += 1;
+        }
+        cost += difference(lhs.count(src_id, src_id), rhs_count(target, target));
+
+        for (mapped_id, mapped_target) in order.iter().zip(&self.mapping) {
+            lhs_inner_edge_count += lhs.count_between(src_id, *mapped_id);
+            if let (Some(dst_id), Some(mapped_dst_id)) = (target, mapped_target) {
+                rhs_inner_edge_count += rhs.count_between(dst_id, *mapped_dst_id);
+            }
+
+            cost += difference(
+                lhs.count(src_id, *mapped_id),
+                rhs_count(target, *mapped_target),
+            );
+            if lhs.is_directed {
+                cost += difference(
+                    lhs.count(*mapped_id, src_id),
+                    rhs_count(*mapped_target, target),
+                );
+            }
+        }
+
+        let mut mapping = self.mapping.clone();
+        mapping.push(target);
+
+        let mut is_used = self.is_used.clone();
+        if let Some(dst_id) = target {
+            is_used[dst_id] = true;
+        }
+
+        State {
+            mapping,
+            is_used,
+            cost,
+            lhs_inner_edge_count,
+            rhs_inner_edge_count,
+            is_complete: false,
+        }
+    }
+
+    // Lower bound of the cost of mapping the remaining vertices.
+    fn estimate(&self, lhs: &CountMatrix, rhs: &CountMatrix) -> usize {
+        let unused_count = self.is_used.iter().filter(|is_used| !**is_used).count();
+
+        difference(lhs.vertex_count - self.mapping.len(), unused_count)
+            + difference(
+                lhs.edge_count - self.lhs_inner_edge_count,
+                rhs.edge_count - self.rhs_inner_edge_count,
+            )
+    }
+
+    // Inserts the unused vertices of `rhs`, and the edges that are connected to them.
+    fn complete(&mut self, rhs: &CountMatrix) {
+        self.cost += self.is_used.iter().filter(|is_used| !**is_used).count();
+        self.cost += rhs.edge_count - self.rhs_inner_edge_count;
+        self.is_complete = true;
+    }
+}
+
+fn difference(lhs: usize, rhs: usize) -> usize {
+    lhs.max(rhs) - lhs.min(rhs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ListGraph, MatGraph};
+    use crate::provide::*;
+    use crate::storage::{DiList, DiMat, List, Mat};
+
+    #[test]
+    fn empty_graphs() {
+        // Given: Graphs
+        //
+        //      (empty)         a  ---  b
+        //
+        let lhs = MatGraph::init(Mat::<usize>::init());
+        let mut rhs = MatGraph::init(Mat::<usize>::init());
+        let a = rhs.add_vertex();
+        let b = rhs.add_vertex();
+        rhs.add_edge_unchecked(a, b, 1.into());
+
+        // When:
+        let lhs_to_rhs = edit_distance(&lhs, &rhs);
+        let rhs_to_lhs = edit_distance(&rhs, &lhs);
+        let lhs_to_lhs = edit_distance(&lhs, &lhs);
+
+        // Then:
+        assert_eq!(lhs_to_rhs, 3);
+        assert_eq!(rhs_to_lhs, 3);
+        assert_eq!(lhs_to_lhs, 0);
+    }
+
+    #[test]
+    fn isomorphic_graphs() {
+        // Given: Graphs
+        //
+        //      a  ---  b  ---  c  ---  d       d  ---  a  ---  c  ---  b
+        //
+        let mut lhs = MatGraph::init(Mat::<usize>::init());
+        let vertices = (0..4).map(|_| lhs.add_vertex()).collect::<Vec<usize>>();
+        lhs.add_edge_unchecked(vertices[0], vertices[1], 1.into());
+        lhs.add_edge_unchecked(vertices[1], vertices[2], 1.into());
+        lhs.add_edge_unchecked(vertices[2], vertices[3], 1.into());
+
+        let mut rhs = ListGraph::init(List::<usize>::init());
+        (0..4).for_each(|_| {
+            rhs.add_vertex();
+        });
+        rhs.add_edge_unchecked(vertices[3], vertices[0], 5.into());
+        rhs.add_edge_unchecked(vertices[0], vertices[2], 5.into());
+        rhs.add_edge_unchecked(vertices[2], vertices[1], 5.into());
+
+        // When:
+        let distance = edit_distance(&lhs, &rhs);
+
+        // Then:
+        assert_eq!(distance, 0);
+    }
+
+    #[test]
+    fn star_and_path() {
+        // Given: Graphs
+        //
+        //      b  ---  a  ---  c       a  ---  b  ---  c  ---  d  ---  e
+        //            /   \
+        //          d       e
+        //
+        let mut lhs = MatGraph::init(Mat::<usize>::init());
+        let center = lhs.add_vertex();
+        for _ in 0..4 {
+            let leaf = lhs.add_vertex();
+            lhs.add_edge_unchecked(center, leaf, 1.into());
+        }
+
+        let mut rhs = MatGraph::init(Mat::<usize>::init());
+        let vertices = (0..5).map(|_| rhs.add_vertex()).collect::<Vec<usize>>();
+        for i in 0..4 {
+            rhs.add_edge_unchecked(vertices[i], vertices[i + 1], 1.into());
+        }
+
+        // When:
+        let distance = edit_distance(&lhs, &rhs);
+
+        // Then: Two edges of the center must be moved to the leaves.
+        assert_eq!(distance, 4);
+    }
+
+    #[test]
+    fn parallel_edges_and_loops() {
+        // Given: Graphs
+        //
+        //      a  ===  b       a  ---  b
+        //      ↺
+        //
+        let mut lhs = ListGraph::init(List::<usize>::init());
+        let a = lhs.add_vertex();
+        let b = lhs.add_vertex();
+        lhs.add_edge_unchecked(a, b, 1.into());
+        lhs.add_edge_unchecked(a, b, 1.into());
+        lhs.add_edge_unchecked(a, a, 1.into());
+
+        let mut rhs = ListGraph::init(List::<usize>::init());
+        rhs.add_vertex();
+        rhs.add_vertex();
+        rhs.add_edge_unchecked(a, b, 1.into());
+
+        // When:
+        let distance = edit_distance(&lhs, &rhs);
+
+        // Then:
+        assert_eq!(distance, 2);
+    }
+
+    #[test]
+    fn directed_graphs() {
+        // Given: Graphs
+        //
+        //      a  -->  b  -->  c       a  -->  b  <--  c
+        //
+        let mut lhs = ListGraph::init(DiList::<usize>::init());
+        let a = lhs.add_vertex();
+        let b = lhs.add_vertex();
+        let c = lhs.add_vertex();
+        lhs.add_edge_unchecked(a, b, 1.into());
+        lhs.add_edge_unchecked(b, c, 1.into());
+
+        let mut rhs = MatGraph::init(DiMat::<usize>::init());
+        rhs.add_vertex();
+        rhs.add_vertex();
+        rhs.add_vertex();
+        rhs.add_edge_unchecked(a, b, 1.into());
+        rhs.add_edge_unchecked(c, b, 1.into());
+
+        // When:
+        let distance = edit_distance(&lhs, &rhs);
+
+        // Then: Reversing an edge takes a deletion and an insertion.
+        assert_eq!(distance, 2);
+    }
+
+    #[test]
+    fn mixed_directions() {
+        // Given: Graphs
+        //
+        //      a  ---  b       a  -->  b
+        //
+        let mut lhs = MatGraph::init(Mat::<usize>::init());
+        let a = lhs.add_vertex();
+        let b = lhs.add_vertex();
+        lhs.add_edge_unchecked(a, b, 1.into());
+
+        let mut rhs = MatGraph::init(DiMat::<usize>::init());
+        rhs.add_vertex();
+        rhs.add_vertex();
+        rhs.add_edge_unchecked(a, b, 1.into());
+
+        // When:
+        let distance = edit_distance(&lhs, &rhs);
+
+        // Then: Undirected edge is considered as a --> b and b --> a.
+        assert_eq!(distance, 1);
+    }
+}
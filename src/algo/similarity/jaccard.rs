@@ -0,0 +1,195 @@
+use std::collections::HashSet;
+
+use crate::algo::similarity::edge_counts;
+use crate::graph::Edge;
+use crate::provide::{Direction, Edges, Vertices};
+
+/// Computes Jaccard similarity of the vertex sets of two graphs that share the same id space.
+///
+/// Similarity is the number of vertex ids present in both graphs, divided by the number of vertex ids present in either of them.
+/// Two graphs without any vertex are considered identical.
+///
+/// # Arguments
+/// * `lhs`: First graph.
+/// * `rhs`: Second graph.
+///
+/// # Returns
+/// Jaccard similarity of the vertex sets, in range of 0 to 1.
+///
+/// # Complexity
+/// O(|V|)
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::similarity::vertex_jaccard;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// let mut lhs = MatGraph::init(Mat::<usize>::init());
+/// let a = lhs.add_vertex();
+/// lhs.add_vertex();
+/// lhs.add_vertex();
+///
+/// let mut rhs = MatGraph::init(Mat::<usize>::init());
+/// rhs.add_vertex();
+/// rhs.add_vertex();
+/// rhs.add_vertex();
+/// rhs.remove_vertex_unchecked(a);
+///
+/// assert_eq!(vertex_jaccard(&lhs, &rhs), 2.0 / 3.0);
+/// ```
+pub fn vertex_jaccard<G1, G2>(lhs: &G1, rhs: &G2) -> f64
+where
+    G1: Vertices,
+    G2: Vertices,
+{
+    let lhs_vertices = lhs.vertices().into_iter().collect::<HashSet<usize>>();
+    let rhs_vertices = rhs.vertices().into_iter().collect::<HashSet<usize>>();
+
+    jaccard(&lhs_vertices, &rhs_vertices)
+}
+
+/// Computes Jaccard similarity of the edge sets of two graphs that share the same id space.
+///
+/// Edges are identified by their end points, so ids and weights of the edges are ignored and parallel edges are counted once.
+/// End points of undirected edges are unordered.
+/// If one of the graphs is directed and the other is not, each undirected edge is considered as two edges in opposite directions.
+/// Two graphs without any edge are considered identical.
+///
+/// # Arguments
+/// * `lhs`: First graph.
+/// * `rhs`: Second graph.
+///
+/// # Returns
+/// Jaccard similarity of the edge sets, in range of 0 to 1.
+///
+/// # Complexity
+/// O(|E|)
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::similarity::edge_jaccard;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graphs
+/// //
+/// //      a  ---  b  ---  c       a  ---  b       c
+/// //                              |               |
+/// //                              '---------------'
+/// //
+/// let mut lhs = MatGraph::init(Mat::<usize>::init());
+/// let a = lhs.add_vertex();
+/// let b = lhs.add_vertex();
+/// let c = lhs.add_vertex();
+/// lhs.add_edge_unchecked(a, b, 1.into());
+/// lhs.add_edge_unchecked(b, c, 1.into());
+///
+/// let mut rhs = MatGraph::init(Mat::<usize>::init());
+/// rhs.add_vertex();
+/// rhs.add_vertex();
+/// rhs.add_vertex();
+/// rhs.add_edge_unchecked(b, a, 2.into());
+/// rhs.add_edge_unchecked(a, c, 1.into());
+///
+/// assert_eq!(edge_jaccard(&lhs, &rhs), 1.0 / 3.0);
+/// ```
+pub fn edge_jaccard<W, E1, E2, G1, G2>(lhs: &G1, rhs: &G2) -> f64
+where
+    E1: Edge<W>,
+    E2: Edge<W>,
+    G1: Edges<W, E1> + Direction,
+    G2: Edges<W, E2> + Direction,
+{
+    let as_directed = lhs.is_directed() || rhs.is_directed();
+
+    let lhs_edges = edge_counts(lhs, as_directed)
+        .into_keys()
+        .collect::<HashSet<(usize, usize)>>();
+    let rhs_edges = edge_counts(rhs, as_directed)
+        .into_keys()
+        .collect::<HashSet<(usize, usize)>>();
+
+    jaccard(&lhs_edges, &rhs_edges)
+}
+
+fn jaccard<T: Eq + std::hash::Hash>(lhs: &HashSet<T>, rhs: &HashSet<T>) -> f64 {
+    let intersection_count = lhs.intersection(rhs).count();
+    let union_count = lhs.len() + rhs.len() - intersection_count;
+
+    if union_count == 0 {
+        1.0
+    } else {
+        intersection_count as f64 / union_count as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ListGraph, MatGraph};
+    use crate::provide::*;
+    use crate::storage::{DiMat, List, Mat};
+
+    #[test]
+    fn empty_graphs() {
+        let lhs = MatGraph::init(Mat::<usize>::init());
+        let rhs = ListGraph::init(List::<usize>::init());
+
+        assert_eq!(vertex_jaccard(&lhs, &rhs), 1.0);
+        assert_eq!(edge_jaccard(&lhs, &rhs), 1.0);
+    }
+
+    #[test]
+    fn parallel_edges() {
+        // Given: Graphs
+        //
+        //      a  ===  b       a  ---  b  ---  c
+        //
+        let mut lhs = ListGraph::init(List::<usize>::init());
+        let a = lhs.add_vertex();
+        let b = lhs.add_vertex();
+        lhs.add_edge_unchecked(a, b, 1.into());
+        lhs.add_edge_unchecked(b, a, 1.into());
+
+        let mut rhs = ListGraph::init(List::<usize>::init());
+        rhs.add_vertex();
+        rhs.add_vertex();
+        let c = rhs.add_vertex();
+        rhs.add_edge_unchecked(a, b, 1.into());
+        rhs.add_edge_unchecked(b, c, 1.into());
+
+        // When:
+        let vertex_similarity = vertex_jaccard(&lhs, &rhs);
+        let edge_similarity = edge_jaccard(&lhs, &rhs);
+
+        // Then:
+        assert_eq!(vertex_similarity, 2.0 / 3.0);
+        assert_eq!(edge_similarity, 0.5);
+    }
+
+    #[test]
+    fn mixed_directions() {
+        // Given: Graphs
+        //
+        //      a  ---  b       a  -->  b
+        //
+        let mut lhs = MatGraph::init(Mat::<usize>::init());
+        let a = lhs.add_vertex();
+        let b = lhs.add_vertex();
+        lhs.add_edge_unchecked(a, b, 1.into());
+
+        let mut rhs = MatGraph::init(DiMat::<usize>::init());
+        rhs.add_vertex();
+        rhs.add_vertex();
+        rhs.add_edge_unchecked(a, b, 1.into());
+
+        // When:
+        let similarity = edge_jaccard(&lhs, &rhs);
+
+        // Then: Undirected edge is considered as a --> b and b --> a.
+        assert_eq!(similarity, 0.5);
+    }
+}
@@ -0,0 +1,36 @@
+mod edit_distance;
+mod jaccard;
+
+pub use edit_distance::edit_distance;
+pub use jaccard::{edge_jaccard, vertex_jaccard};
+
+use std::collections::HashMap;
+
+use crate::graph::Edge;
+use crate::provide::{Direction, Edges};
+
+// Maps end points of each edge to the number of edges between them.
+// If `as_directed` is true, each undirected edge is counted in both directions. Otherwise end points of undirected edges are ordered so `src_id` <= `dst_id`.
+fn edge_counts<W, E, G>(graph: &G, as_directed: bool) -> HashMap<(usize, usize), usize>
+where
+    E: Edge<W>,
+    G: Edges<W, E> + Direction,
+{
+    let mut edge_counts = HashMap::new();
+
+    for (src_id, dst_id, edge) in graph.edges() {
+        let (src_id, dst_id) = if graph.is_undirected() && dst_id < src_id {
+            (dst_id, src_id)
+        } else {
+            (src_id, dst_id)
+        };
+
+        *edge_counts.entry((src_id, dst_id)).or_insert(0) += 1;
+
+        if as_directed && graph.is_undirected() && src_id != dst_id {
+            *edge_counts.entry((dst_id, src_id)).or_insert(0) += 1;
+        }
+    }
+
+    edge_counts
+}
@@ -0,0 +1,363 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use magnitude::Magnitude;
+use num_traits::ToPrimitive;
+
+use crate::graph::subgraph::Subgraph;
+use crate::graph::{Edge, EdgeDir, Error, OrdFloat};
+use crate::provide::{Edges, Graph, IdMap, Neighbors, Vertices};
+use crate::util::IndexedHeap;
+
+/// Finds a t-spanner of a graph: a subgraph in which distance between every two vertices is at most t times their distance in the graph.
+///
+/// Edges are visited from the lightest to the heaviest, and an edge is added only if the spanner built so far has no path between its end points
+/// shorter than t times its weight(Althöfer et al.). For t = 1 the spanner preserves all distances, and for t >= |V| it's a minimum spanning forest.
+/// For undirected graphs with t = 2k - 1, the spanner has O(|V|^(1 + 1/k)) edges.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::GreedySpanner;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  --(1)--  b
+/// //       \          |
+/// //       (3)       (1)
+/// //         \        |
+/// //          '-----  c
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let ab = graph.add_edge_unchecked(a, b, 1.into());
+/// let bc = graph.add_edge_unchecked(b, c, 1.into());
+/// let ac = graph.add_edge_unchecked(a, c, 3.into());
+///
+/// let spanner = GreedySpanner::init(&graph, 1.0).unwrap().execute(&graph);
+///
+/// // a -> b -> c is shorter than edge between a and c, so the edge is not needed.
+/// assert!(spanner.contains_edge(ab));
+/// assert!(spanner.contains_edge(bc));
+/// assert!(!spanner.contains_edge(ac));
+/// ```
+pub struct GreedySpanner {
+    id_map: IdMap,
+
+    // Edges except loops and edges with infinite weight, sorted by weight in the format of: (src virtual id, dst virtual id, edge id, weight).
+    edges: Vec<(usize, usize, usize, f64)>,
+    stretch: f64,
+    is_directed: bool,
+}
+
+impl GreedySpanner {
+    /// # Arguments
+    /// * `graph`: Graph to find its spanner.
+    /// * `stretch`: Maximum ratio(t) between distances in the spanner and distances in the graph.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing the initialized algorithm.
+    /// * `Err`: [`InvalidWeight`](crate::graph::ErrorKind::InvalidWeight) if weight of an edge is negative or can not be represented as `f64`.
+    ///
+    /// # Panics
+    /// If `stretch` is less than one or is not a number.
+    pub fn init<W, E, Ty, G>(graph: &G, stretch: f64) -> Result<Self>
+    where
+        W: ToPrimitive,
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Graph<W, E, Ty> + Edges<W, E> + Vertices,
+    {
+        assert!(stretch >= 1.0, "Stretch must be at least one");
+
+        let id_map = graph.continuous_id_map();
+
+        let mut edges = vec![];
+        for (src_id, dst_id, edge) in graph.edges() {
+            let weight = match edge.get_weight() {
+                Magnitude::Finite(weight) => match weight.to_f64() {
+                    Some(weight) if weight >= 0.0 => weight,
+                    _ => Err(Error::new_iw(edge.get_id()))?,
+                },
+                Magnitude::PosInfinite => continue,
+                Magnitude::NegInfinite => Err(Error::new_iw(edge.get_id()))?,
+            };
+
+            if src_id != dst_id {
+                edges.push((
+                    id_map.virt_id_of(src_id),
+                    id_map.virt_id_of(dst_id),
+                    edge.get_id(),
+                    weight,
+                ));
+            }
+        }
+
+        // Sorting is stable, so edges with the same weight keep the order of the graph.
+        edges.sort_by(|(.., w1), (.., w2)| w1.partial_cmp(w2).unwrap());
+
+        Ok(GreedySpanner {
+            id_map,
+            edges,
+            stretch,
+            is_directed: Ty::is_directed(),
+        })
+    }
+
+    /// # Arguments
+    /// `graph`: Graph to find its spanner.
+    ///
+    /// # Returns
+    /// Subgraph containing all vertices of the graph and edges of the spanner.
+    ///
+    /// # Complexity
+    /// O(|E| * (|V| + |E'|) * log(|V|)), where |E'| is the number of edges of the spanner.
+    pub fn execute<'a, W, E, Ty, G>(self, graph: &'a G) -> Subgraph<'a, W, E, Ty, G>
+    where
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Graph<W, E, Ty> + Edges<W, E> + Neighbors + Vertices,
+    {
+        let vertex_count = self.id_map.len();
+
+        let mut adjacents_of = vec![vec![]; vertex_count];
+        let mut distance_of = vec![f64::INFINITY; vertex_count];
+        let mut heap = IndexedHeap::with_capacity(vertex_count);
+        let mut spanner_edges = vec![];
+
+        for (src_virt_id, dst_virt_id, edge_id, weight) in &self.edges {
+            let limit = self.stretch * weight;
+
+            if !Self::is_within(
+                &adjacents_of,
+                &mut distance_of,
+                &mut heap,
+                *src_virt_id,
+                *dst_virt_id,
+                limit,
+            ) {
+                adjacents_of[*src_virt_id].push((*dst_virt_id, *weight));
+                if !self.is_directed {
+                    adjacents_of[*dst_virt_id].push((*src_virt_id, *weight));
+                }
+
+                spanner_edges.push((
+                    self.id_map.real_id_of(*src_virt_id),
+                    self.id_map.real_id_of(*dst_virt_id),
+                    *edge_id,
+                ));
+            }
+        }
+
+        let vertices = graph.vertices().into_iter().collect::<HashSet<usize>>();
+
+        Subgraph::init(graph, spanner_edges, vertices)
+    }
+
+    // Runs Dijkstra from `src_virt_id` but only over paths not longer than `limit`, and checks if `dst_virt_id` is reached.
+    // `distance_of` must be all infinite and `heap` must be empty, and they are left the same way for the next call.
+    fn is_within(
+        adjacents_of: &[Vec<(usize, f64)>],
+        distance_of: &mut [f64],
+        heap: &mut IndexedHeap<OrdFloat<f64>>,
+        src_virt_id: usize,
+        dst_virt_id: usize,
+        limit: f64,
+    ) -> bool {
+        let mut visited = vec![src_virt_id];
+        let mut is_reached = false;
+
+        distance_of[src_virt_id] = 0.0;
+        heap.push(src_virt_id, OrdFloat::new(0.0).unwrap());
+
+        while let Some((virt_id, distance)) = heap.pop() {
+            let distance = distance.value();
+
+            if virt_id == dst_virt_id {
+                is_reached = true;
+                break;
+            }
+
+            for (n_virt_id, weight) in &adjacents_of[virt_id] {
+                let n_distance = distance + weight;
+
+                if n_distance <= limit && n_distance < distance_of[*n_virt_id] {
+                    if distance_of[*n_virt_id].is_infinite() {
+                        visited.push(*n_virt_id);
+                    }
+                    distance_of[*n_virt_id] = n_distance;
+                    heap.push_or_decrease(*n_virt_id, OrdFloat::new(n_distance).unwrap());
+                }
+            }
+        }
+
+        for virt_id in visited {
+            distance_of[virt_id] = f64::INFINITY;
+        }
+        heap.clear();
+
+        is_reached
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ErrorKind, MatGraph, UndirectedEdge};
+    use crate::storage::{DiMat, Mat};
+    use crate::util::seeded;
+
+    // Distances between every two vertices, using Floyd-Warshall over the given edges.
+    fn distances(vertex_count: usize, edges: &[(usize, usize, f64)]) -> Vec<Vec<f64>> {
+        let mut distance = vec![vec![f64::INFINITY; vertex_count]; vertex_count];
+        for virt_id in 0..vertex_count {
+            distance[virt_id][virt_id] = 0.0;
+        }
+        for (src, dst, weight) in edges {
+            distance[*src][*dst] = distance[*src][*dst].min(*weight);
+            distance[*dst][*src] = distance[*dst][*src].min(*weight);
+        }
+
+        for k in 0..vertex_count {
+            for i in 0..vertex_count {
+                for j in 0..vertex_count {
+                    distance[i][j] = distance[i][j].min(distance[i][k] + distance[k][j]);
+                }
+            }
+        }
+
+        distance
+    }
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let spanner = GreedySpanner::init(&graph, 2.0).unwrap().execute(&graph);
+
+        assert_eq!(spanner.vertex_count(), 0);
+        assert_eq!(spanner.edges_count(), 0);
+    }
+
+    #[test]
+    fn large_stretch_gives_minimum_spanning_tree() {
+        // Given: Complete graph with 6 vertices, where weight of edge between i and j is i + j.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let ids: Vec<usize> = (0..6).map(|_| graph.add_vertex()).collect();
+        for i in 0..6 {
+            for j in i + 1..6 {
+                graph.add_edge_unchecked(ids[i], ids[j], (i + j).into());
+            }
+        }
+
+        // When:
+        let spanner = GreedySpanner::init(&graph, 100.0).unwrap().execute(&graph);
+
+        // Then: Star around the first vertex, which has weight of 1 + 2 + 3 + 4 + 5.
+        assert_eq!(spanner.vertex_count(), 6);
+        assert_eq!(spanner.edges_count(), 5);
+        assert_eq!(
+            spanner
+                .edges()
+                .into_iter()
+                .map(|(_, _, edge)| edge.get_weight().unwrap())
+                .sum::<usize>(),
+            15
+        );
+    }
+
+    #[test]
+    fn distances_are_stretched_at_most_by_the_factor() {
+        // Given: Complete graph with 12 vertices and random weights.
+        let mut rng = seeded(4);
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let ids: Vec<usize> = (0..12).map(|_| graph.add_vertex()).collect();
+        let mut edges = vec![];
+        for i in 0..12 {
+            for j in i + 1..12 {
+                let weight = 1 + rng(20);
+                graph.add_edge_unchecked(ids[i], ids[j], weight.into());
+                edges.push((i, j, weight as f64));
+            }
+        }
+
+        // When:
+        let spanner = GreedySpanner::init(&graph, 3.0).unwrap().execute(&graph);
+
+        // Then:
+        let spanner_edges = spanner
+            .edges()
+            .into_iter()
+            .map(|(src_id, dst_id, edge)| {
+                (
+                    ids.iter().position(|id| *id == src_id).unwrap(),
+                    ids.iter().position(|id| *id == dst_id).unwrap(),
+                    edge.get_weight().unwrap() as f64,
+                )
+            })
+            .collect::<Vec<(usize, usize, f64)>>();
+        let original = distances(12, &edges);
+        let stretched = distances(12, &spanner_edges);
+
+        assert!(spanner_edges.len() < edges.len());
+        for i in 0..12 {
+            for j in 0..12 {
+                assert!(stretched[i][j] <= 3.0 * original[i][j]);
+            }
+        }
+    }
+
+    #[test]
+    fn directed_graph() {
+        // Given: Graph
+        //
+        //      a  --(1)-->  b  --(1)-->  c
+        //      ^                         |
+        //      '-----------(2)-----------'
+        //      '-----------(2)---------->'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        let ca = graph.add_edge_unchecked(c, a, 2.into());
+        let ac = graph.add_edge_unchecked(a, c, 2.into());
+
+        // When:
+        let spanner = GreedySpanner::init(&graph, 1.0).unwrap().execute(&graph);
+
+        // Then: There is no other path from c to a, but a -> b -> c is as short as the edge from a to c.
+        assert_eq!(spanner.edges_count(), 3);
+        assert!(spanner.contains_edge(ca));
+        assert!(!spanner.contains_edge(ac));
+    }
+
+    #[test]
+    fn negative_weight() {
+        let mut graph = MatGraph::init(Mat::<isize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, (-1).into());
+
+        let error = GreedySpanner::init(&graph, 2.0).err().unwrap();
+
+        assert_eq!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            &ErrorKind::InvalidWeight
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Stretch must be at least one")]
+    fn stretch_less_than_one() {
+        let graph = MatGraph::<usize, UndirectedEdge>::init(Mat::<usize>::init());
+
+        GreedySpanner::init(&graph, 0.5).unwrap();
+    }
+}
@@ -0,0 +1,484 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+use magnitude::Magnitude;
+use num_traits::ToPrimitive;
+
+use crate::graph::subgraph::Subgraph;
+use crate::graph::{Edge, Error, UndirectedEdge};
+use crate::provide::{Edges, Graph, IdMap, Neighbors, Vertices};
+use crate::util::unit;
+
+// Conjugate gradient stops when the residual shrinks by this factor(in squared norm) relative to the right hand side.
+const SOLVER_TOLERANCE: f64 = 1e-20;
+
+/// Method used by [`Sparsifier`](crate::algo::Sparsifier) to choose the probability of sampling each edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SparsifyMethod {
+    /// Samples each edge with probability proportional to its weight times its effective resistance(Spielman & Srivastava).
+    /// Edges that are the only way between parts of the graph, like bridges, have high resistance and are likely to be kept.
+    /// So weight of every cut, and the Laplacian quadratic form in general, is approximately preserved.
+    EffectiveResistance,
+
+    /// Samples each edge with the same probability. It's faster, but cuts with few edges may be lost.
+    Uniform,
+}
+
+/// Shrinks an undirected graph to a target number of edges, while approximately preserving weight of its cuts.
+///
+/// Edges are sampled `edge_budget` times with replacement, and each sampled edge gets a new weight so that the expected weight of every cut stays the same:
+/// weight of the edge times the number of times it's sampled, divided by the expected number of times.
+/// So the sparsifier has at most `edge_budget` edges. Loops are ignored.
+///
+/// Effective resistances are estimated with random projections: each projection solves a Laplacian system with conjugate gradient.
+/// More projections give more accurate resistances.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::{Sparsifier, SparsifyMethod};
+/// use prepona::storage::List;
+/// use prepona::graph::ListGraph;
+/// use prepona::util::seeded;
+///
+/// // Given: Complete graph with 20 vertices and 190 edges.
+/// let mut graph = ListGraph::init(List::<usize>::init());
+/// let ids: Vec<usize> = (0..20).map(|_| graph.add_vertex()).collect();
+/// for src in 0..20 {
+///     for dst in src + 1..20 {
+///         graph.add_edge_unchecked(ids[src], ids[dst], 1.into());
+///     }
+/// }
+///
+/// let sparsifier = Sparsifier::init(&graph).unwrap().with_method(SparsifyMethod::EffectiveResistance);
+/// let (subgraph, weight_of) = sparsifier.execute(&graph, 60, &mut seeded(1));
+///
+/// assert!(subgraph.edges_count() <= 60);
+/// assert_eq!(subgraph.vertex_count(), 20);
+///
+/// // Total weight is approximately preserved.
+/// let total_weight: f64 = weight_of.values().sum();
+/// assert!((total_weight - 190.0).abs() < 40.0);
+/// ```
+pub struct Sparsifier {
+    id_map: IdMap,
+
+    // Edges except loops, in the format of: (src virtual id, dst virtual id, edge id, weight).
+    edges: Vec<(usize, usize, usize, f64)>,
+
+    // Neighbors of each vertex in the format of: (neighbor virtual id, weight).
+    adjacents_of: Vec<Vec<(usize, f64)>>,
+
+    method: SparsifyMethod,
+    projection_count: Option<usize>,
+}
+
+impl Sparsifier {
+    /// # Arguments
+    /// `graph`: Graph to sparsify.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing sparsifier that uses effective resistances, with 8 * ln(|V|) projections.
+    /// * `Err`: [`InvalidWeight`](crate::graph::ErrorKind::InvalidWeight) if weight of an edge is negative, infinite or can not be represented as `f64`.
+    pub fn init<W, E, G>(graph: &G) -> Result<Self>
+    where
+        W: ToPrimitive,
+        E: Edge<W>,
+        G: Graph<W, E, UndirectedEdge> + Edges<W, E> + Vertices,
+    {
+        let id_map = graph.continuous_id_map();
+
+        let mut edges = vec![];
+        let mut adjacents_of = vec![vec![]; graph.vertex_count()];
+        for (src_id, dst_id, edge) in graph.edges() {
+            let weight = match edge.get_weight() {
+                Magnitude::Finite(weight) => weight
+                    .to_f64()
+                    .filter(|weight| *weight >= 0.0 && weight.is_finite()),
+                _ => None,
+            };
+            let weight = match weight {
+                Some(weight) => weight,
+                None => Err(Error::new_iw(edge.get_id()))?,
+            };
+
+            let src_virt_id = id_map.virt_id_of(src_id);
+            let dst_virt_id = id_map.virt_id_of(dst_id);
+            if src_virt_id != dst_virt_id {
+                edges.push((src_virt_id, dst_virt_id, edge.get_id(), weight));
+                adjacents_of[src_virt_id].push((dst_virt_id, weight));
+                adjacents_of[dst_virt_id].push((src_virt_id, weight));
+            }
+        }
+
+        Ok(Sparsifier {
+            id_map,
+            edges,
+            adjacents_of,
+            method: SparsifyMethod::EffectiveResistance,
+            projection_count: None,
+        })
+    }
+
+    /// # Arguments
+    /// `method`: Method to choose the probability of sampling each edge.
+    ///
+    /// # Returns
+    /// The sparsifier with the specified method.
+    pub fn with_method(mut self, method: SparsifyMethod) -> Self {
+        self.method = method;
+
+        self
+    }
+
+    /// # Arguments
+    /// `projection_count`: Number of random projections used to estimate effective resistances. Error of the estimates shrinks with its square root.
+    ///
+    /// # Returns
+    /// The sparsifier with the specified number of projections.
+    pub fn with_projection_count(mut self, projection_count: usize) -> Self {
+        self.projection_count = Some(projection_count.max(1));
+
+        self
+    }
+
+    // Computes L * `vector`, where L is the Laplacian of the graph.
+    fn laplacian_mul(&self, vector: &[f64]) -> Vec<f64> {
+        self.adjacents_of
+            .iter()
+            .enumerate()
+            .map(|(virt_id, adjacents)| {
+                adjacents
+                    .iter()
+                    .map(|(n_virt_id, weight)| weight * (vector[virt_id] - vector[*n_virt_id]))
+                    .sum()
+            })
+            .collect()
+    }
+
+    // Solves L * x = `rhs` using conjugate gradient. Entries of `rhs` must sum to zero in each component, so the system has a solution.
+    fn solve(&self, rhs: Vec<f64>) -> Vec<f64> {
+        let dot = |a: &[f64], b: &[f64]| a.iter().zip(b).map(|(x, y)| x * y).sum::<f64>();
+
+        let mut solution = vec![0.0; rhs.len()];
+        let mut direction = rhs.clone();
+        let mut residual = rhs;
+        let mut residual_norm = dot(&residual, &residual);
+        let threshold = residual_norm * SOLVER_TOLERANCE;
+
+        for _ in 0..2 * solution.len() + 10 {
+            if residual_norm <= threshold {
+                break;
+            }
+
+            let product = self.laplacian_mul(&direction);
+            let curvature = dot(&direction, &product);
+            if curvature <= 0.0 {
+                break;
+            }
+
+            let step = residual_norm / curvature;
+            for index in 0..solution.len() {
+                solution[index] += step * direction[index];
+                residual[index] -= step * product[index];
+            }
+
+            let next_residual_norm = dot(&residual, &residual);
+            let ratio = next_residual_norm / residual_norm;
+            for index in 0..direction.len() {
+                direction[index] = residual[index] + ratio * direction[index];
+            }
+            residual_norm = next_residual_norm;
+        }
+
+        solution
+    }
+
+    /// Estimates effective resistance of each edge, which is the voltage between its end points when a unit current flows from one to the other,
+    /// considering each edge as a resistor with conductance equal to its weight.
+    ///
+    /// Resistance of an edge that is the only way between its end points(a bridge) is one over its weight, and it's smaller when there are other ways.
+    ///
+    /// # Arguments
+    /// `rng`: Source of randomness: when called with `n`, returns a uniformly random number in range of 0 to `n` - 1.
+    ///
+    /// # Returns
+    /// Estimated effective resistance of each edge, keyed by id of the edge. Loops are not included.
+    ///
+    /// # Complexity
+    /// O(projection count * |V| * |E|) in the worst case, but conjugate gradient usually converges in far fewer than |V| iterations.
+    pub fn effective_resistances<R>(&self, rng: &mut R) -> HashMap<usize, f64>
+    where
+        R: FnMut(usize) -> usize,
+    {
+        let vertex_count = self.adjacents_of.len();
+        let projection_count = self
+            .projection_count
+            .unwrap_or_else(|| (8.0 * (vertex_count.max(2) as f64).ln()).ceil() as usize);
+        let scale = 1.0 / (projection_count as f64).sqrt();
+
+        // Resistance of edge (u, v) is ||Z * (e_u - e_v)||^2, where Z = Q * W^(1/2) * B * L^+ and Q is a random matrix of ±1/sqrt(projection_count).
+        let mut resistances = vec![0.0; self.edges.len()];
+        for _ in 0..projection_count {
+            let mut rhs = vec![0.0; vertex_count];
+            for (src_virt_id, dst_virt_id, _, weight) in &self.edges {
+                let sign = if rng(2) == 0 { scale } else { -scale };
+
+                rhs[*src_virt_id] += sign * weight.sqrt();
+                rhs[*dst_virt_id] -= sign * weight.sqrt();
+            }
+
+            let projection = self.solve(rhs);
+            for (index, (src_virt_id, dst_virt_id, _, _)) in self.edges.iter().enumerate() {
+                resistances[index] += (projection[*src_virt_id] - projection[*dst_virt_id]).powi(2);
+            }
+        }
+
+        self.edges
+            .iter()
+            .zip(resistances)
+            .map(|((_, _, edge_id, _), resistance)| (*edge_id, resistance))
+            .collect()
+    }
+
+    /// # Arguments
+    /// * `graph`: Graph to sparsify.
+    /// * `edge_budget`: Number of samples, which is the maximum number of edges of the sparsifier.
+    ///   If the graph has at most `edge_budget` edges, the sparsifier has all of them with their own weights.
+    /// * `rng`: Source of randomness: when called with `n`, returns a uniformly random number in range of 0 to `n` - 1.
+    ///   Use [`seeded`](crate::util::seeded) for reproducible results.
+    ///
+    /// # Returns
+    /// * Subgraph containing all vertices of the graph and the sampled edges.
+    /// * New weight of each sampled edge, keyed by id of the edge.
+    ///
+    /// # Complexity
+    /// Cost of computing effective resistances(if used) plus O(|E| + `edge_budget` * log(|E|)).
+    pub fn execute<'a, W, E, G, R>(
+        &self,
+        graph: &'a G,
+        edge_budget: usize,
+        rng: &mut R,
+    ) -> (Subgraph<'a, W, E, UndirectedEdge, G>, HashMap<usize, f64>)
+    where
+        E: Edge<W>,
+        G: Graph<W, E, UndirectedEdge> + Edges<W, E> + Neighbors + Vertices,
+        R: FnMut(usize) -> usize,
+    {
+        let weight_of = if self.edges.len() <= edge_budget {
+            self.edges
+                .iter()
+                .map(|(_, _, edge_id, weight)| (*edge_id, *weight))
+                .collect()
+        } else {
+            self.sample(edge_budget, rng)
+        };
+
+        let edges = self
+            .edges
+            .iter()
+            .filter(|(_, _, edge_id, _)| weight_of.contains_key(edge_id))
+            .map(|(src_virt_id, dst_virt_id, edge_id, _)| {
+                (
+                    self.id_map.real_id_of(*src_virt_id),
+                    self.id_map.real_id_of(*dst_virt_id),
+                    *edge_id,
+                )
+            })
+            .collect();
+        let vertices = graph.vertices().into_iter().collect::<HashSet<usize>>();
+
+        (Subgraph::init(graph, edges, vertices), weight_of)
+    }
+
+    // Samples `edge_budget` edges with replacement, and computes new weight of each sampled edge.
+    fn sample<R>(&self, edge_budget: usize, rng: &mut R) -> HashMap<usize, f64>
+    where
+        R: FnMut(usize) -> usize,
+    {
+        let scores = match self.method {
+            SparsifyMethod::EffectiveResistance => {
+                let resistances = self.effective_resistances(rng);
+
+                self.edges
+                    .iter()
+                    .map(|(_, _, edge_id, weight)| weight * resistances[edge_id])
+                    .collect::<Vec<f64>>()
+            }
+            SparsifyMethod::Uniform => self
+                .edges
+                .iter()
+                .map(|(_, _, _, weight)| if *weight > 0.0 { 1.0 } else { 0.0 })
+                .collect(),
+        };
+
+        let mut cumulative_scores = Vec::with_capacity(scores.len());
+        let mut total_score = 0.0;
+        for score in &scores {
+            total_score += score;
+            cumulative_scores.push(total_score);
+        }
+
+        let mut weight_of = HashMap::new();
+        if total_score <= 0.0 {
+            return weight_of;
+        }
+
+        for _ in 0..edge_budget {
+            let target = unit(rng) * total_score;
+            let index = cumulative_scores
+                .partition_point(|score| *score <= target)
+                .min(scores.len() - 1);
+
+            // Each sample adds weight / (edge_budget * probability of the edge), so the expected weight equals the original weight.
+            let (_, _, edge_id, weight) = self.edges[index];
+            let probability = scores[index] / total_score;
+            *weight_of.entry(edge_id).or_insert(0.0) += weight / (edge_budget as f64 * probability);
+        }
+
+        weight_of
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ErrorKind, ListGraph, MatGraph, OrdFloat};
+    use crate::storage::{List, Mat};
+    use crate::util::seeded;
+
+    fn complete_graph(vertex_count: usize) -> ListGraph<usize, UndirectedEdge> {
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let ids: Vec<usize> = (0..vertex_count).map(|_| graph.add_vertex()).collect();
+        for src in 0..vertex_count {
+            for dst in src + 1..vertex_count {
+                graph.add_edge_unchecked(ids[src], ids[dst], 1.into());
+            }
+        }
+
+        graph
+    }
+
+    #[test]
+    fn small_graph_is_kept() {
+        // Given: Graph
+        //
+        //      a  --(2)--  b  --(3)--  c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 2.into());
+        let bc = graph.add_edge_unchecked(b, c, 3.into());
+
+        // When:
+        let (subgraph, weight_of) =
+            Sparsifier::init(&graph)
+                .unwrap()
+                .execute(&graph, 2, &mut seeded(0));
+
+        // Then:
+        assert_eq!(subgraph.edges_count(), 2);
+        assert_eq!(weight_of[&ab], 2.0);
+        assert_eq!(weight_of[&bc], 3.0);
+    }
+
+    #[test]
+    fn resistances_of_bridges_and_cycles() {
+        // Given: Triangle a, b, c with a bridge of weight 2 from c to d.
+        //
+        //      a  ---  b
+        //       \     /
+        //          c  --(2)--  d
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let ids: Vec<usize> = (0..4).map(|_| graph.add_vertex()).collect();
+        let ab = graph.add_edge_unchecked(ids[0], ids[1], 1.into());
+        graph.add_edge_unchecked(ids[1], ids[2], 1.into());
+        graph.add_edge_unchecked(ids[2], ids[0], 1.into());
+        let cd = graph.add_edge_unchecked(ids[2], ids[3], 2.into());
+
+        // When:
+        let resistances = Sparsifier::init(&graph)
+            .unwrap()
+            .with_projection_count(2000)
+            .effective_resistances(&mut seeded(3));
+
+        // Then: Resistance of the bridge is 1/2, and resistance of each edge of the triangle is 2/3.
+        assert!((resistances[&cd] - 0.5).abs() < 0.1);
+        assert!((resistances[&ab] - 2.0 / 3.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn uniform_sparsifier_preserves_total_weight() {
+        // Given:
+        let graph = complete_graph(30);
+
+        // When:
+        let (subgraph, weight_of) = Sparsifier::init(&graph)
+            .unwrap()
+            .with_method(SparsifyMethod::Uniform)
+            .execute(&graph, 100, &mut seeded(7));
+
+        // Then: Every edge has the same probability, so each sample adds the same weight.
+        assert!(subgraph.edges_count() <= 100);
+        assert_eq!(subgraph.vertex_count(), 30);
+        assert!((weight_of.values().sum::<f64>() - 435.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn effective_resistance_sparsifier_approximately_preserves_total_weight() {
+        // Given:
+        let graph = complete_graph(30);
+
+        // When:
+        let (subgraph, weight_of) =
+            Sparsifier::init(&graph)
+                .unwrap()
+                .execute(&graph, 100, &mut seeded(7));
+
+        // Then: Resistances are estimated, so probabilities are only about the same.
+        assert!(subgraph.edges_count() <= 100);
+        assert!((weight_of.values().sum::<f64>() - 435.0).abs() < 435.0 * 0.2);
+    }
+
+    #[test]
+    fn effective_resistance_keeps_bridges() {
+        // Given: Two complete graphs connected by a bridge.
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let ids: Vec<usize> = (0..24).map(|_| graph.add_vertex()).collect();
+        for half in [0, 12] {
+            for src in half..half + 12 {
+                for dst in src + 1..half + 12 {
+                    graph.add_edge_unchecked(ids[src], ids[dst], 1.into());
+                }
+            }
+        }
+        let bridge = graph.add_edge_unchecked(ids[0], ids[12], 1.into());
+
+        // When: Sampling 120 of the 133 edges.
+        let (_, weight_of) =
+            Sparsifier::init(&graph)
+                .unwrap()
+                .execute(&graph, 120, &mut seeded(11));
+
+        // Then: Bridge has the highest probability(about 1/23 per sample), so it's kept.
+        assert!(weight_of[&bridge] > 0.0);
+    }
+
+    #[test]
+    fn negative_weight() {
+        let mut graph = MatGraph::init(Mat::<OrdFloat<f64>>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, OrdFloat::new(-1.0).unwrap().into());
+
+        let error = Sparsifier::init(&graph).err().unwrap();
+
+        assert_eq!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            &ErrorKind::InvalidWeight
+        );
+    }
+}
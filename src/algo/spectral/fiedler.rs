@@ -0,0 +1,290 @@
+use super::{SpectralKind, SpectralMatrix};
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+// Scales `vector` to unit length. Returns false if `vector` is zero.
+fn normalize(vector: &mut [f64]) -> bool {
+    let norm = dot(vector, vector).sqrt();
+    if norm == 0.0 {
+        return false;
+    }
+
+    vector.iter_mut().for_each(|entry| *entry /= norm);
+
+    true
+}
+
+// Removes component of `vector` along the unit vector `direction`.
+fn orthogonalize(vector: &mut [f64], direction: &[f64]) {
+    let projection = dot(vector, direction);
+
+    vector
+        .iter_mut()
+        .zip(direction)
+        .for_each(|(entry, d)| *entry -= projection * d);
+}
+
+impl SpectralMatrix {
+    // Unit eigenvector of the Laplacian with eigenvalue zero.
+    fn null_vector(&self) -> Vec<f64> {
+        let mut null_vector = match self.kind() {
+            SpectralKind::NormalizedLaplacian if self.degrees().iter().any(|d| *d > 0.0) => {
+                self.degrees().iter().map(|d| d.sqrt()).collect()
+            }
+            _ => vec![1.0; self.size()],
+        };
+        normalize(&mut null_vector);
+
+        null_vector
+    }
+
+    /// Computes the Fiedler vector: eigenvector of the second smallest eigenvalue of a Laplacian, using power iteration.
+    ///
+    /// Power iteration is applied to c * I - L, where c is an upper bound of the eigenvalues of L(by Gershgorin circle theorem),
+    /// while removing the component along the eigenvector of the smallest eigenvalue(zero) in each iteration.
+    /// Sign of the vector is chosen so that its first entry is not negative.
+    ///
+    /// # Arguments
+    /// * `max_iterations`: Maximum number of iterations.
+    /// * `tolerance`: Iteration stops when the vector changes less than `tolerance`(in euclidean distance) in an iteration.
+    ///
+    /// # Returns
+    /// * `Some`: Containing the second smallest eigenvalue(algebraic connectivity) and its eigenvector, indexed by rows of the matrix.
+    /// * `None`: If the matrix is not a Laplacian or it has less than two rows.
+    ///
+    /// # Complexity
+    /// O(`max_iterations` * |V|^2)
+    pub fn fiedler_vector(&self, max_iterations: usize, tolerance: f64) -> Option<(f64, Vec<f64>)> {
+        if self.kind() == SpectralKind::Adjacency || self.size() < 2 {
+            return None;
+        }
+
+        let size = self.size();
+        let null_vector = self.null_vector();
+
+        let bound = self
+            .entries()
+            .iter()
+            .map(|row| row.iter().map(|entry| entry.abs()).sum::<f64>())
+            .fold(0.0, f64::max);
+        let bound = if bound == 0.0 { 1.0 } else { bound };
+
+        // Starting vector must not be orthogonal to the Fiedler vector, so it avoids symmetric patterns.
+        let mut vector = (0..size)
+            .map(|index| (index + 1) as f64 + ((index * 37) % 11) as f64 / 11.0)
+            .collect::<Vec<f64>>();
+        orthogonalize(&mut vector, &null_vector);
+        normalize(&mut vector);
+
+        for _ in 0..max_iterations {
+            let mut next = self
+                .mul_vec(&vector)
+                .iter()
+                .zip(&vector)
+                .map(|(lx, x)| bound * x - lx)
+                .collect::<Vec<f64>>();
+            orthogonalize(&mut next, &null_vector);
+
+            if !normalize(&mut next) {
+                break;
+            }
+
+            let change = next
+                .iter()
+                .zip(&vector)
+                .map(|(a, b)| (a - b) * (a - b))
+                .sum::<f64>()
+                .sqrt();
+
+            vector = next;
+
+            if change < tolerance {
+                break;
+            }
+        }
+
+        if vector[0] < 0.0 {
+            vector.iter_mut().for_each(|entry| *entry = -*entry);
+        }
+
+        let eigenvalue = dot(&vector, &self.mul_vec(&vector));
+
+        Some((eigenvalue, vector))
+    }
+
+    /// Splits vertices of the graph into two sets by signs of the entries of the Fiedler vector.
+    ///
+    /// # Arguments
+    /// * `max_iterations`: Maximum number of iterations to compute the Fiedler vector.
+    /// * `tolerance`: Tolerance used to compute the Fiedler vector. Checkout [`fiedler_vector`](crate::algo::SpectralMatrix::fiedler_vector).
+    ///
+    /// # Returns
+    /// * `Some`: Containing ids of vertices with non negative entries and ids of vertices with negative entries.
+    /// * `None`: If the matrix is not a Laplacian or it has less than two rows.
+    ///
+    /// # Complexity
+    /// O(`max_iterations` * |V|^2)
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::algo::SpectralMatrix;
+    /// use prepona::storage::Mat;
+    /// use prepona::graph::MatGraph;
+    ///
+    /// // Given: Graph
+    /// //
+    /// //      a  ---  b  ---  c  ---  d
+    /// //
+    /// let mut graph = MatGraph::init(Mat::<usize>::init());
+    /// let a = graph.add_vertex();
+    /// let b = graph.add_vertex();
+    /// let c = graph.add_vertex();
+    /// let d = graph.add_vertex();
+    /// graph.add_edge_unchecked(a, b, 1.into());
+    /// graph.add_edge_unchecked(b, c, 1.into());
+    /// graph.add_edge_unchecked(c, d, 1.into());
+    ///
+    /// let laplacian = SpectralMatrix::laplacian(&graph).unwrap();
+    /// let (mut first, mut second) = laplacian.spectral_bisection(1000, 1e-10).unwrap();
+    /// first.sort();
+    /// second.sort();
+    ///
+    /// let mut halves = vec![first, second];
+    /// halves.sort();
+    /// assert_eq!(halves, vec![vec![a, b], vec![c, d]]);
+    /// ```
+    pub fn spectral_bisection(
+        &self,
+        max_iterations: usize,
+        tolerance: f64,
+    ) -> Option<(Vec<usize>, Vec<usize>)> {
+        let (_, vector) = self.fiedler_vector(max_iterations, tolerance)?;
+
+        let (non_negatives, negatives): (Vec<usize>, Vec<usize>) =
+            (0..self.size()).partition(|index| vector[*index] >= 0.0);
+
+        let to_real_ids = |indices: Vec<usize>| {
+            indices
+                .into_iter()
+                .map(|index| self.id_map().real_id_of(index))
+                .collect()
+        };
+
+        Some((to_real_ids(non_negatives), to_real_ids(negatives)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::*;
+    use crate::storage::Mat;
+
+    #[test]
+    fn not_a_laplacian() {
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+
+        let adjacency = SpectralMatrix::adjacency(&graph).unwrap();
+
+        assert!(adjacency.fiedler_vector(100, 1e-10).is_none());
+    }
+
+    #[test]
+    fn path_graph() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c  ---  d
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+
+        // When: Computing the Fiedler vector.
+        let laplacian = SpectralMatrix::laplacian(&graph).unwrap();
+        let (eigenvalue, vector) = laplacian.fiedler_vector(1000, 1e-12).unwrap();
+
+        // Then: Algebraic connectivity of a path with 4 vertices is 2 - sqrt(2).
+        assert!((eigenvalue - (2.0 - 2.0_f64.sqrt())).abs() < 1e-9);
+
+        // And: Vector is an eigenvector.
+        let product = laplacian.mul_vec(&vector);
+        for (lx, x) in product.iter().zip(&vector) {
+            assert!((lx - eigenvalue * x).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn normalized_complete_graph() {
+        // Given: Complete graph with 4 vertices.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let ids = (0..4).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        for i in 0..4 {
+            for j in i + 1..4 {
+                graph.add_edge_unchecked(ids[i], ids[j], 1.into());
+            }
+        }
+
+        // When: Computing the Fiedler vector of the normalized Laplacian.
+        let laplacian = SpectralMatrix::normalized_laplacian(&graph).unwrap();
+        let (eigenvalue, _) = laplacian.fiedler_vector(1000, 1e-12).unwrap();
+
+        // Then: Second smallest eigenvalue of normalized Laplacian of K(n) is n / (n - 1).
+        assert!((eigenvalue - 4.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn bisect_two_triangles() {
+        // Given: Graph
+        //
+        //      a  ---  b       d  ---  e
+        //      |     /  \     /  \     |
+        //      |   /     \   /     \   |
+        //      | /        \ /        \ |
+        //      c           f  ------  g
+        //
+        // Two dense groups: {a, b, c} and {d, e, f, g}, connected by the edge b --- f.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        let g = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, a, 1.into());
+        graph.add_edge_unchecked(b, f, 1.into());
+        graph.add_edge_unchecked(d, e, 1.into());
+        graph.add_edge_unchecked(d, f, 1.into());
+        graph.add_edge_unchecked(e, g, 1.into());
+        graph.add_edge_unchecked(f, g, 1.into());
+        graph.add_edge_unchecked(d, g, 1.into());
+
+        for laplacian in [
+            SpectralMatrix::laplacian(&graph).unwrap(),
+            SpectralMatrix::normalized_laplacian(&graph).unwrap(),
+        ] {
+            // When: Bisecting the graph.
+            let (mut first, mut second) = laplacian.spectral_bisection(1000, 1e-10).unwrap();
+            first.sort_unstable();
+            second.sort_unstable();
+
+            // Then: Groups are separated. a is in the first set, because first entry of the Fiedler vector is not negative.
+            assert_eq!(first, vec![a, b, c]);
+            assert_eq!(second, vec![d, e, f, g]);
+        }
+    }
+}
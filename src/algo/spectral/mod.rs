@@ -0,0 +1,5 @@
+#[cfg(feature = "linalg")]
+mod fiedler;
+mod spectral_matrix;
+
+pub use spectral_matrix::{SpectralKind, SpectralMatrix};
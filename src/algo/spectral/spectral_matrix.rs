@@ -0,0 +1,393 @@
+use std::any::Any;
+
+use anyhow::Result;
+use num_traits::ToPrimitive;
+
+use crate::graph::{Edge, EdgeDir, Error, UndirectedEdge};
+use crate::provide::{Edges, Graph, IdMap, Vertices};
+
+/// Kind of a [`SpectralMatrix`](crate::algo::SpectralMatrix).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpectralKind {
+    /// A: Entry (i, j) is the total weight of edges from i to j.
+    Adjacency,
+
+    /// L = D - A: Where D is the diagonal matrix of weighted degrees.
+    Laplacian,
+
+    /// L = I - D^(-1/2) * A * D^(-1/2): Rows and columns of isolated vertices are zero.
+    NormalizedLaplacian,
+}
+
+/// Dense matrix of a graph, used to study the graph through eigenvalues and eigenvectors of the matrix.
+///
+/// Row and column i of the matrix belong to the vertex with virtual id i. Use [`id_map`](crate::algo::SpectralMatrix::id_map) to map rows to vertices and vice versa.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::SpectralMatrix;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  --(2)--  b  --(3)--  c
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 2.into());
+/// graph.add_edge_unchecked(b, c, 3.into());
+///
+/// let laplacian = SpectralMatrix::laplacian(&graph).unwrap();
+/// let b_index = laplacian.id_map().virt_id_of(b);
+/// let c_index = laplacian.id_map().virt_id_of(c);
+///
+/// assert_eq!(laplacian.entry(b_index, b_index), 5.0);
+/// assert_eq!(laplacian.entry(b_index, c_index), -3.0);
+/// ```
+pub struct SpectralMatrix {
+    kind: SpectralKind,
+    entries: Vec<Vec<f64>>,
+
+    // Weighted degree of each vertex: sum of the row of the vertex in the adjacency matrix.
+    degrees: Vec<f64>,
+
+    id_map: IdMap,
+}
+
+impl SpectralMatrix {
+    // Adjacency matrix and id map of the graph.
+    fn adjacency_entries<W, E, Ty, G>(graph: &G) -> Result<(Vec<Vec<f64>>, IdMap)>
+    where
+        W: Any + Copy + ToPrimitive,
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+    {
+        let id_map = graph.continuous_id_map();
+        let vertex_count = graph.vertex_count();
+
+        let mut entries = vec![vec![0.0; vertex_count]; vertex_count];
+        for (src_id, dst_id, edge) in graph.as_directed_edges() {
+            let weight = edge.get_weight();
+            let weight = match weight.is_finite() {
+                true => weight.unwrap().to_f64(),
+                false => None,
+            };
+
+            if let Some(weight) = weight {
+                entries[id_map.virt_id_of(src_id)][id_map.virt_id_of(dst_id)] += weight;
+            } else {
+                Err(Error::new_iw(edge.get_id()))?
+            }
+        }
+
+        Ok((entries, id_map))
+    }
+
+    fn degrees_of(entries: &[Vec<f64>]) -> Vec<f64> {
+        entries.iter().map(|row| row.iter().sum()).collect()
+    }
+
+    /// # Arguments
+    /// `graph`: Graph to compute its adjacency matrix.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing the weighted adjacency matrix of the graph. For undirected graphs the matrix is symmetric.
+    /// * `Err`: [`InvalidWeight`](crate::graph::ErrorKind::InvalidWeight) if weight of an edge is infinite or can not be represented as `f64`.
+    ///
+    /// # Complexity
+    /// O(|V|^2 + |E|)
+    pub fn adjacency<W, E, Ty, G>(graph: &G) -> Result<Self>
+    where
+        W: Any + Copy + ToPrimitive,
+        E: Edge<W>,
+        Ty: EdgeDir,
+        G: Edges<W, E> + Vertices + Graph<W, E, Ty>,
+    {
+        let (entries, id_map) = Self::adjacency_entries(graph)?;
+
+        Ok(SpectralMatrix {
+            kind: SpectralKind::Adjacency,
+            degrees: Self::degrees_of(&entries),
+            entries,
+            id_map,
+        })
+    }
+
+    /// # Arguments
+    /// `graph`: Graph to compute its Laplacian matrix.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing the Laplacian matrix of the graph: L = D - A.
+    /// * `Err`: [`InvalidWeight`](crate::graph::ErrorKind::InvalidWeight) if weight of an edge is infinite or can not be represented as `f64`.
+    ///
+    /// # Complexity
+    /// O(|V|^2 + |E|)
+    pub fn laplacian<W, E, G>(graph: &G) -> Result<Self>
+    where
+        W: Any + Copy + ToPrimitive,
+        E: Edge<W>,
+        G: Edges<W, E> + Vertices + Graph<W, E, UndirectedEdge>,
+    {
+        let (mut entries, id_map) = Self::adjacency_entries(graph)?;
+        let degrees = Self::degrees_of(&entries);
+
+        for (i, row) in entries.iter_mut().enumerate() {
+            for (j, entry) in row.iter_mut().enumerate() {
+                *entry = if i == j { degrees[i] - *entry } else { -*entry };
+            }
+        }
+
+        Ok(SpectralMatrix {
+            kind: SpectralKind::Laplacian,
+            entries,
+            degrees,
+            id_map,
+        })
+    }
+
+    /// # Arguments
+    /// `graph`: Graph to compute its normalized Laplacian matrix.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing the symmetric normalized Laplacian matrix of the graph: L = I - D^(-1/2) * A * D^(-1/2).
+    /// * `Err`: [`InvalidWeight`](crate::graph::ErrorKind::InvalidWeight) if weight of an edge is infinite or can not be represented as `f64`.
+    ///
+    /// # Complexity
+    /// O(|V|^2 + |E|)
+    pub fn normalized_laplacian<W, E, G>(graph: &G) -> Result<Self>
+    where
+        W: Any + Copy + ToPrimitive,
+        E: Edge<W>,
+        G: Edges<W, E> + Vertices + Graph<W, E, UndirectedEdge>,
+    {
+        let (mut entries, id_map) = Self::adjacency_entries(graph)?;
+        let degrees = Self::degrees_of(&entries);
+
+        for (i, row) in entries.iter_mut().enumerate() {
+            for (j, entry) in row.iter_mut().enumerate() {
+                let scaled = if degrees[i] > 0.0 && degrees[j] > 0.0 {
+                    *entry / (degrees[i] * degrees[j]).sqrt()
+                } else {
+                    0.0
+                };
+
+                *entry = if i == j && degrees[i] > 0.0 {
+                    1.0 - scaled
+                } else {
+                    -scaled
+                };
+            }
+        }
+
+        Ok(SpectralMatrix {
+            kind: SpectralKind::NormalizedLaplacian,
+            entries,
+            degrees,
+            id_map,
+        })
+    }
+
+    /// # Returns
+    /// Kind of the matrix.
+    pub fn kind(&self) -> SpectralKind {
+        self.kind
+    }
+
+    /// # Returns
+    /// Number of rows(and columns) of the matrix, which is the number of vertices.
+    pub fn size(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// # Returns
+    /// Rows of the matrix.
+    pub fn entries(&self) -> &Vec<Vec<f64>> {
+        &self.entries
+    }
+
+    /// # Arguments
+    /// * `row`: Index of the row.
+    /// * `col`: Index of the column.
+    ///
+    /// # Returns
+    /// Entry at (`row`, `col`).
+    ///
+    /// # Panics
+    /// If `row` or `col` is not less than size of the matrix.
+    pub fn entry(&self, row: usize, col: usize) -> f64 {
+        self.entries[row][col]
+    }
+
+    /// # Returns
+    /// Weighted degree of each vertex, indexed by its row.
+    pub fn degrees(&self) -> &Vec<f64> {
+        &self.degrees
+    }
+
+    /// # Returns
+    /// Mapping between ids of the vertices(real ids) and rows of the matrix(virtual ids).
+    pub fn id_map(&self) -> &IdMap {
+        &self.id_map
+    }
+
+    /// # Arguments
+    /// `vector`: Vector to multiply.
+    ///
+    /// # Returns
+    /// Product of the matrix and `vector`.
+    ///
+    /// # Panics
+    /// If length of `vector` is not equal to size of the matrix.
+    pub fn mul_vec(&self, vector: &[f64]) -> Vec<f64> {
+        assert_eq!(vector.len(), self.size());
+
+        self.entries
+            .iter()
+            .map(|row| row.iter().zip(vector).map(|(a, b)| a * b).sum())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DefaultEdge, MatGraph};
+    use crate::storage::{DiMat, Mat};
+    use magnitude::Magnitude;
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let laplacian = SpectralMatrix::laplacian(&graph).unwrap();
+
+        assert_eq!(laplacian.size(), 0);
+        assert_eq!(laplacian.kind(), SpectralKind::Laplacian);
+    }
+
+    #[test]
+    fn infinite_weight() {
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, DefaultEdge::init(Magnitude::PosInfinite));
+
+        assert!(SpectralMatrix::adjacency(&graph).is_err());
+        assert!(SpectralMatrix::laplacian(&graph).is_err());
+    }
+
+    #[test]
+    fn directed_adjacency() {
+        // Given: Graph
+        //
+        //      a  --(2)-->  b  --(3)-->  c
+        //      ^                         |
+        //      '-----------(1)-----------'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 2.into());
+        graph.add_edge_unchecked(b, c, 3.into());
+        graph.add_edge_unchecked(c, a, 1.into());
+
+        // When: Computing the adjacency matrix.
+        let adjacency = SpectralMatrix::adjacency(&graph).unwrap();
+
+        // Then:
+        let id_map = adjacency.id_map();
+        let (a, b, c) = (
+            id_map.virt_id_of(a),
+            id_map.virt_id_of(b),
+            id_map.virt_id_of(c),
+        );
+        assert_eq!(adjacency.entry(a, b), 2.0);
+        assert_eq!(adjacency.entry(b, a), 0.0);
+        assert_eq!(adjacency.entry(b, c), 3.0);
+        assert_eq!(adjacency.entry(c, a), 1.0);
+        assert_eq!(adjacency.degrees()[b], 3.0);
+    }
+
+    #[test]
+    fn undirected_laplacian() {
+        // Given: Graph
+        //
+        //      a  --(1)--  b  --(2)--  c       d
+        //      |                       |
+        //      '-----------(3)---------'
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 2.into());
+        graph.add_edge_unchecked(a, c, 3.into());
+
+        // When: Computing the Laplacian matrix.
+        let laplacian = SpectralMatrix::laplacian(&graph).unwrap();
+
+        // Then: Matrix is symmetric, rows sum up to zero and diagonal contains the weighted degrees.
+        let id_map = laplacian.id_map();
+        let (a, c, d) = (
+            id_map.virt_id_of(a),
+            id_map.virt_id_of(c),
+            id_map.virt_id_of(d),
+        );
+        for i in 0..4 {
+            assert_eq!(laplacian.entries()[i].iter().sum::<f64>(), 0.0);
+            for j in 0..4 {
+                assert_eq!(laplacian.entry(i, j), laplacian.entry(j, i));
+            }
+        }
+        assert_eq!(laplacian.entry(a, a), 4.0);
+        assert_eq!(laplacian.entry(c, c), 5.0);
+        assert_eq!(laplacian.entry(a, c), -3.0);
+        assert_eq!(laplacian.entry(d, d), 0.0);
+
+        // And: Constant vector is in its null space.
+        assert!(laplacian
+            .mul_vec(&[1.0; 4])
+            .iter()
+            .all(|entry| *entry == 0.0));
+    }
+
+    #[test]
+    fn undirected_normalized_laplacian() {
+        // Given: Graph
+        //
+        //      a  --(1)--  b  --(3)--  c       d
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 3.into());
+
+        // When: Computing the normalized Laplacian matrix.
+        let laplacian = SpectralMatrix::normalized_laplacian(&graph).unwrap();
+
+        // Then:
+        let id_map = laplacian.id_map();
+        let (a, b, c, d) = (
+            id_map.virt_id_of(a),
+            id_map.virt_id_of(b),
+            id_map.virt_id_of(c),
+            id_map.virt_id_of(d),
+        );
+        assert_eq!(laplacian.entry(a, a), 1.0);
+        assert_eq!(laplacian.entry(d, d), 0.0);
+        assert!((laplacian.entry(a, b) + 1.0 / 4.0_f64.sqrt()).abs() < 1e-12);
+        assert!((laplacian.entry(c, b) + 3.0 / 12.0_f64.sqrt()).abs() < 1e-12);
+        assert_eq!(laplacian.entry(a, c), 0.0);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::algo::{Dfs, DfsListener};
+use crate::algo::{Control, Dfs, DfsListener};
 use crate::graph::{DirectedEdge, Edge};
 use crate::provide;
 
@@ -7,8 +7,10 @@ pub struct TopologicalSort {
 }
 
 impl DfsListener for TopologicalSort {
-    fn on_black(&mut self, _: &Dfs<Self>, virt_id: usize) {
+    fn on_black(&mut self, _: &Dfs<Self>, virt_id: usize) -> Control {
         self.sorted_vertex_ids.push(virt_id);
+
+        Control::Continue
     }
 }
 
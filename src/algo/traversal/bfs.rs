@@ -1,46 +1,704 @@
-// use std::collections::{HashSet, VecDeque};
+use magnitude::Magnitude;
+use std::collections::VecDeque;
+use std::sync::Arc;
 
-// use crate::graph::Edge;
-// use crate::provide;
+use super::{Color, Control};
+use crate::algo::AlgoContext;
+use crate::graph::Edge;
+use crate::provide::{self, IdMap};
 
+/// Callbacks that are called by [`Bfs`] during the traversal.
+///
+/// Each callback returns a [`Control`] value to let the listener terminate the traversal early or prune it.
+/// Callbacks get virtual id of the vertex, which can be mapped to its real id using [`get_id_map`](Bfs::get_id_map).
+#[allow(unused_variables)]
+pub trait BfsListener {
+    /// Called when a vertex is added to the queue, after its distance is set.
+    fn on_discover(&mut self, bfs: &Bfs, virt_id: usize) -> Control {
+        Control::Continue
+    }
+
+    /// Called when a vertex is removed from the queue, before its neighbors are discovered.
+    /// Returning `Control::SkipNeighbors` prevents neighbors of the vertex from being discovered through it.
+    fn on_visit(&mut self, bfs: &Bfs, virt_id: usize) -> Control {
+        Control::Continue
+    }
+}
+
+// Listener of traversals that are performed without one.
+struct NoListener;
+
+impl BfsListener for NoListener {}
+
+/// Visits vertices of the graph in breadth first order.
+///
+/// `Bfs` only depends on [`Vertices`](crate::provide::Vertices) and [`Neighbors`](crate::provide::Neighbors) traits.
+/// So it can be used on graphs, subgraphs and also on storages like [`BitMatrix`](crate::storage::BitMatrix) that store no edges.
+///
+/// When initialized with start vertices, all of them are visited first and the search expands from all of them simultaneously.
+/// So distance of each vertex is its distance from the nearest start vertex, and vertices that are not reachable from any start vertex are not visited.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::Bfs;
+/// use prepona::storage::BitMat;
+///
+/// // Given: Unweighted graph
+/// //
+/// //      a  ---  b  ---  c
+/// //
+/// let mut mat = BitMat::init();
+/// let a = mat.add_vertex();
+/// let b = mat.add_vertex();
+/// let c = mat.add_vertex();
+/// mat.add_edge_unchecked(a, b);
+/// mat.add_edge_unchecked(b, c);
+///
+/// let mut bfs = Bfs::init_with_starts(&mat, vec![a]);
+/// let visited = bfs.execute(&mat);
+///
+/// assert_eq!(visited, vec![a, b, c]);
+/// assert_eq!(bfs.distance_of(c), 2.into());
+/// ```
 pub struct Bfs {
-    // queue: VecDeque<usize>,
-// discovered: HashSet<usize>,
+    queue: VecDeque<usize>,
+    colors: Vec<Color>,
+    distances: Vec<Magnitude<usize>>,
+    id_map: Arc<IdMap>,
+    start_ids: Vec<usize>,
+}
+
+impl Bfs {
+    pub fn init<G>(graph: &G) -> Self
+    where
+        G: provide::Vertices + provide::Neighbors,
+    {
+        Bfs::init_with_starts(graph, vec![])
+    }
+
+    /// # Arguments
+    /// * `graph`: Graph to traverse.
+    /// * `start_ids`: Id of the vertices to start the search from, like roots of a [`MultiRootSubgraph`](crate::graph::subgraph::MultiRootSubgraph).
+    pub fn init_with_starts<G>(graph: &G, mut start_ids: Vec<usize>) -> Self
+    where
+        G: provide::Vertices + provide::Neighbors,
+    {
+        let vertex_count = graph.vertex_count();
+
+        let id_map = graph.continuous_id_map();
+
+        start_ids = start_ids
+            .into_iter()
+            .map(|real_id| id_map.virt_id_of(real_id))
+            .collect();
+
+        Bfs {
+            queue: VecDeque::new(),
+            colors: vec![Color::White; vertex_count],
+            distances: vec![Magnitude::PosInfinite; vertex_count],
+            id_map: Arc::new(id_map),
+            start_ids,
+        }
+    }
+
+    /// Same as `init_with_starts`, but takes the id map and buffers from `context` instead of allocating them.
+    /// Use `release` after the traversal to give the buffers back.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to traverse.
+    /// * `start_ids`: Id of the vertices to start the search from. Empty to search from every part of the graph.
+    /// * `context`: Context that is used with `graph`.
+    pub fn init_in<G>(graph: &G, start_ids: Vec<usize>, context: &mut AlgoContext) -> Self
+    where
+        G: provide::Vertices + provide::Neighbors,
+    {
+        let id_map = context.id_map(graph);
+        let vertex_count = id_map.len();
+
+        Bfs {
+            queue: VecDeque::new(),
+            colors: context.take_buffer(vertex_count, Color::White),
+            distances: context.take_buffer(vertex_count, Magnitude::PosInfinite),
+            start_ids: start_ids
+                .into_iter()
+                .map(|real_id| id_map.virt_id_of(real_id))
+                .collect(),
+            id_map,
+        }
+    }
+
+    /// Gives buffers of the traversal back to `context`, so they can be reused by the next algorithm.
+    ///
+    /// # Arguments
+    /// `context`: Context to keep the buffers.
+    pub fn release(self, context: &mut AlgoContext) {
+        context.put_buffer(self.colors);
+        context.put_buffer(self.distances);
+    }
+
+    fn next_start_id(&self) -> Option<usize> {
+        if self.start_ids.is_empty() {
+            self.colors.iter().position(|color| *color == Color::White)
+        } else {
+            self.start_ids
+                .iter()
+                .find(|virt_id| self.colors[**virt_id] == Color::White)
+                .copied()
+        }
+    }
+
+    /// # Returns
+    /// Id of visited vertices in the order they were visited.
+    pub fn execute<G>(&mut self, graph: &G) -> Vec<usize>
+    where
+        G: provide::Vertices + provide::Neighbors,
+    {
+        self.execute_with_listener(graph, &mut NoListener)
+    }
+
+    /// Performs the traversal and calls the listener callbacks.
+    ///
+    /// Traversal terminates as soon as a callback returns `Control::Stop`.
+    /// In that case vertices that were discovered but not visited remain in the queue and can be retrieved using [`get_queue`](Bfs::get_queue).
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to traverse.
+    /// * `listener`: Listener to be notified during the traversal.
+    ///
+    /// # Returns
+    /// Id of visited vertices in the order they were visited.
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::algo::{Bfs, BfsListener, Control};
+    /// use prepona::storage::BitMat;
+    ///
+    /// // Stops at the first vertex that is at least 2 edges away from the start vertex.
+    /// struct Listener {
+    ///     found: Option<usize>,
+    /// }
+    ///
+    /// impl BfsListener for Listener {
+    ///     fn on_visit(&mut self, bfs: &Bfs, virt_id: usize) -> Control {
+    ///         if bfs.get_distances()[virt_id] >= 2.into() {
+    ///             self.found = Some(bfs.get_id_map().real_id_of(virt_id));
+    ///             Control::Stop
+    ///         } else {
+    ///             Control::Continue
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// // Given: Unweighted graph
+    /// //
+    /// //      a  ---  b  ---  c  ---  d
+    /// //
+    /// let mut mat = BitMat::init();
+    /// let a = mat.add_vertex();
+    /// let b = mat.add_vertex();
+    /// let c = mat.add_vertex();
+    /// let d = mat.add_vertex();
+    /// mat.add_edge_unchecked(a, b);
+    /// mat.add_edge_unchecked(b, c);
+    /// mat.add_edge_unchecked(c, d);
+    ///
+    /// let mut listener = Listener { found: None };
+    /// let visited = Bfs::init_with_starts(&mat, vec![a]).execute_with_listener(&mat, &mut listener);
+    ///
+    /// assert_eq!(listener.found, Some(c));
+    /// assert_eq!(visited, vec![a, b, c]);
+    /// ```
+    pub fn execute_with_listener<G, L>(&mut self, graph: &G, listener: &mut L) -> Vec<usize>
+    where
+        G: provide::Vertices + provide::Neighbors,
+        L: BfsListener,
+    {
+        self.traverse(listener, |real_id| graph.neighbors_unchecked(real_id))
+    }
+
+    /// Performs the traversal only through edges that satisfy `filter`, as if other edges do not exist in the graph.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to traverse.
+    /// * `filter`: Gets source id, destination id and the edge and returns `true` if the edge can be traversed.
+    ///
+    /// # Returns
+    /// Id of visited vertices in the order they were visited.
+    pub fn execute_filtered<G, W, E, F>(&mut self, graph: &G, filter: F) -> Vec<usize>
+    where
+        E: Edge<W>,
+        G: provide::Vertices + provide::Edges<W, E>,
+        F: Fn(usize, usize, &E) -> bool,
+    {
+        self.execute_filtered_with_listener(graph, filter, &mut NoListener)
+    }
+
+    /// Same as `execute_filtered`, but calls the listener callbacks like `execute_with_listener`.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to traverse.
+    /// * `filter`: Gets source id, destination id and the edge and returns `true` if the edge can be traversed.
+    /// * `listener`: Listener to be notified during the traversal.
+    ///
+    /// # Returns
+    /// Id of visited vertices in the order they were visited.
+    pub fn execute_filtered_with_listener<G, W, E, F, L>(
+        &mut self,
+        graph: &G,
+        filter: F,
+        listener: &mut L,
+    ) -> Vec<usize>
+    where
+        E: Edge<W>,
+        G: provide::Vertices + provide::Edges<W, E>,
+        F: Fn(usize, usize, &E) -> bool,
+        L: BfsListener,
+    {
+        self.traverse(listener, |real_id| {
+            graph
+                .edges_from_unchecked(real_id)
+                .into_iter()
+                .filter(|(dst_id, edge)| filter(real_id, *dst_id, edge))
+                .map(|(dst_id, _)| dst_id)
+                .collect()
+        })
+    }
+
+    // Performs the traversal using `neighbors_of` to find neighbors of each vertex, and stops as soon as `listener` returns `Control::Stop`.
+    // `neighbors_of` gets and returns real ids.
+    fn traverse<L, F>(&mut self, listener: &mut L, neighbors_of: F) -> Vec<usize>
+    where
+        L: BfsListener,
+        F: Fn(usize) -> Vec<usize>,
+    {
+        let mut visited = vec![];
+
+        while let Some(start_id) = self.next_start_id() {
+            // Without explicit start vertices, each connected part of the graph is searched from a single vertex.
+            let start_ids = if self.start_ids.is_empty() {
+                vec![start_id]
+            } else {
+                self.start_ids.clone()
+            };
+
+            for start_id in start_ids {
+                if self.colors[start_id] == Color::White {
+                    self.colors[start_id] = Color::Gray;
+                    self.distances[start_id] = 0.into();
+                    self.queue.push_back(start_id);
+
+                    if listener.on_discover(self, start_id) == Control::Stop {
+                        return visited;
+                    }
+                }
+            }
+
+            while let Some(virt_id) = self.queue.pop_front() {
+                let real_id = self.id_map.real_id_of(virt_id);
+                let distance = self.distances[virt_id] + 1.into();
+
+                self.colors[virt_id] = Color::Black;
+                visited.push(real_id);
+
+                match listener.on_visit(self, virt_id) {
+                    Control::Stop => return visited,
+                    Control::SkipNeighbors => continue,
+                    Control::Continue => {}
+                }
+
+                for n_real_id in neighbors_of(real_id) {
+                    let n_virt_id = self.id_map.virt_id_of(n_real_id);
+
+                    if self.colors[n_virt_id] == Color::White {
+                        self.colors[n_virt_id] = Color::Gray;
+                        self.distances[n_virt_id] = distance;
+                        self.queue.push_back(n_virt_id);
+
+                        if listener.on_discover(self, n_virt_id) == Control::Stop {
+                            return visited;
+                        }
+                    }
+                }
+            }
+        }
+
+        visited
+    }
+
+    /// # Arguments
+    /// `real_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Number of edges between the nearest start vertex and the vertex itself.
+    /// `PosInfinite` if vertex is not visited yet.
+    pub fn distance_of(&self, real_id: usize) -> Magnitude<usize> {
+        self.distances[self.id_map.virt_id_of(real_id)]
+    }
+
+    pub fn get_queue(&self) -> &VecDeque<usize> {
+        &self.queue
+    }
+
+    pub fn get_colors(&self) -> &Vec<Color> {
+        &self.colors
+    }
+
+    pub fn get_distances(&self) -> &Vec<Magnitude<usize>> {
+        &self.distances
+    }
+
+    pub fn get_id_map(&self) -> &IdMap {
+        &self.id_map
+    }
+
+    pub fn dissolve(self) -> (Vec<Magnitude<usize>>, IdMap) {
+        // Id map is only copied if it's still shared with a context.
+        let id_map = Arc::try_unwrap(self.id_map).unwrap_or_else(|id_map| (*id_map).clone());
+
+        (self.distances, id_map)
+    }
 }
 
-// impl Bfs {
-//     pub fn init(src_index: usize) -> Self {
-//         let mut queue = VecDeque::with_capacity(1);
-//         queue.push_back(src_index);
-
-//         Bfs {
-//             queue,
-//             discovered: HashSet::new(),
-//         }
-//     }
-
-//     // pub fn next<G, W, E: Edge<W>>(&mut self, graph: &G) -> Option<usize>
-//     // where
-//     //     G: provide::Graph<W, E> + provide::Neighbors,
-//     // {
-//     //     if let Some(v_index) = self.queue.pop_front() {
-//     //         let undiscovered_neighbors = graph
-//     //             .neighbors(v_index)
-//     //             .iter()
-//     //             .filter(|&neighbor_index| {
-//     //                 !self.discovered.contains(neighbor_index)
-//     //                     && !self.queue.contains(neighbor_index)
-//     //             })
-//     //             .copied()
-//     //             .collect::<Vec<usize>>();
-
-//     //         self.queue.append(&mut undiscovered_neighbors.into());
-
-//     //         self.discovered.insert(v_index);
-
-//     //         Some(v_index)
-//     //     } else {
-//     //         None
-//     //     }
-//     // }
-// }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::subgraph::MultiRootSubgraph;
+    use crate::graph::MatGraph;
+    use crate::provide::*;
+    use crate::storage::{BitMat, DiBitMat, DiMat, Mat};
+
+    #[test]
+    fn empty_graph() {
+        // Given: An empty graph.
+        let mat = BitMat::init();
+
+        // When: Performing Bfs algorithm.
+        let mut bfs = Bfs::init(&mat);
+        let visited = bfs.execute(&mat);
+
+        // Then:
+        assert!(visited.is_empty());
+    }
+
+    #[test]
+    fn directed_bit_matrix() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  d  -->  e
+        //      ^       |
+        //      |       v
+        //      '______ c               f
+        //
+        let mut mat = DiBitMat::init();
+        let a = mat.add_vertex();
+        let b = mat.add_vertex();
+        let c = mat.add_vertex();
+        let d = mat.add_vertex();
+        let e = mat.add_vertex();
+        let f = mat.add_vertex();
+
+        mat.add_edge_unchecked(a, b);
+        mat.add_edge_unchecked(b, c);
+        mat.add_edge_unchecked(c, a);
+        mat.add_edge_unchecked(b, d);
+        mat.add_edge_unchecked(d, e);
+
+        // When: Performing Bfs algorithm starting from a.
+        let mut bfs = Bfs::init_with_starts(&mat, vec![a]);
+        let visited = bfs.execute(&mat);
+
+        // Then:
+        assert_eq!(visited, vec![a, b, c, d, e]);
+        assert_eq!(bfs.distance_of(a), 0.into());
+        assert_eq!(bfs.distance_of(b), 1.into());
+        assert_eq!(bfs.distance_of(c), 2.into());
+        assert_eq!(bfs.distance_of(d), 2.into());
+        assert_eq!(bfs.distance_of(e), 3.into());
+        assert!(bfs.distance_of(f).is_pos_infinite());
+    }
+
+    #[test]
+    fn undirected_bit_matrix_not_connected() {
+        // Given: Graph
+        //
+        //      a  ---  b       d  ---  e
+        //              |       |
+        //              c       f
+        //
+        let mut mat = BitMat::init();
+        let a = mat.add_vertex();
+        let b = mat.add_vertex();
+        let c = mat.add_vertex();
+        let d = mat.add_vertex();
+        let e = mat.add_vertex();
+        let f = mat.add_vertex();
+
+        mat.add_edge_unchecked(a, b);
+        mat.add_edge_unchecked(b, c);
+        mat.add_edge_unchecked(d, e);
+        mat.add_edge_unchecked(d, f);
+
+        // When: Performing Bfs algorithm.
+        let mut bfs = Bfs::init(&mat);
+        let visited = bfs.execute(&mat);
+
+        // Then:
+        assert_eq!(visited, vec![a, b, c, d, e, f]);
+        assert_eq!(bfs.distance_of(c), 2.into());
+        assert_eq!(bfs.distance_of(d), 0.into());
+        assert_eq!(bfs.distance_of(f), 1.into());
+    }
+
+    #[test]
+    fn directed_graph() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c
+        //      |               ^
+        //      '---------------'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(a, c, 1.into());
+
+        // When: Performing Bfs algorithm starting from a.
+        let mut bfs = Bfs::init_with_starts(&graph, vec![a]);
+        let visited = bfs.execute(&graph);
+
+        // Then:
+        assert_eq!(visited.len(), 3);
+        assert_eq!(visited[0], a);
+        assert_eq!(bfs.distance_of(b), 1.into());
+        assert_eq!(bfs.distance_of(c), 1.into());
+    }
+
+    #[test]
+    fn multiple_starts() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c  ---  d  ---  e       f
+        //
+        let mut mat = BitMat::init();
+        let a = mat.add_vertex();
+        let b = mat.add_vertex();
+        let c = mat.add_vertex();
+        let d = mat.add_vertex();
+        let e = mat.add_vertex();
+        let f = mat.add_vertex();
+
+        mat.add_edge_unchecked(a, b);
+        mat.add_edge_unchecked(b, c);
+        mat.add_edge_unchecked(c, d);
+        mat.add_edge_unchecked(d, e);
+
+        // When: Performing Bfs algorithm starting from both a and e.
+        let mut bfs = Bfs::init_with_starts(&mat, vec![a, e]);
+        let visited = bfs.execute(&mat);
+
+        // Then:
+        assert_eq!(visited, vec![a, e, b, d, c]);
+        assert_eq!(bfs.distance_of(b), 1.into());
+        assert_eq!(bfs.distance_of(c), 2.into());
+        assert_eq!(bfs.distance_of(d), 1.into());
+        assert!(bfs.distance_of(f).is_pos_infinite());
+    }
+
+    #[test]
+    fn filtered_edges() {
+        // Given: Graph
+        //
+        //      a  --(1)--  b  --(5)--  c
+        //      |                       |
+        //     (2)                     (1)
+        //      |                       |
+        //      d  --------(2)----------'
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 5.into());
+        graph.add_edge_unchecked(a, d, 2.into());
+        graph.add_edge_unchecked(d, c, 2.into());
+
+        // When: Performing Bfs algorithm from b only through edges with weight less than 3.
+        let mut bfs = Bfs::init_with_starts(&graph, vec![b]);
+        let visited = bfs.execute_filtered(&graph, |_, _, edge| *edge.get_weight() < 3.into());
+
+        // Then: c is reached through a and d instead of the direct edge.
+        assert_eq!(visited, vec![b, a, d, c]);
+        assert_eq!(bfs.distance_of(c), 3.into());
+    }
+
+    #[test]
+    fn roots_of_subgraph() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c       d  -->  e
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+        let bc = graph.add_edge_unchecked(b, c, 1.into());
+        let de = graph.add_edge_unchecked(d, e, 1.into());
+
+        // And: Subgraph containing all edges with b and d as roots.
+        let subgraph = MultiRootSubgraph::init(
+            &graph,
+            vec![(a, b, ab), (b, c, bc), (d, e, de)],
+            vec![a, b, c, d, e].into_iter().collect(),
+            vec![b, d],
+        );
+
+        // When: Performing Bfs algorithm starting from roots of the subgraph.
+        let mut bfs = Bfs::init_with_starts(&subgraph, subgraph.roots().clone());
+        let visited = bfs.execute(&subgraph);
+
+        // Then:
+        assert_eq!(visited, vec![b, d, c, e]);
+        assert!(bfs.distance_of(a).is_pos_infinite());
+        assert_eq!(bfs.distance_of(e), 1.into());
+    }
+
+    #[test]
+    fn reuses_buffers_of_context() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        let mut context = AlgoContext::init();
+
+        // When: Traversing from each vertex using the same context.
+        let mut visited = vec![];
+        for start_id in &[a, b, c] {
+            let mut bfs = Bfs::init_in(&graph, vec![*start_id], &mut context);
+            visited.push(bfs.execute(&graph));
+            assert_eq!(bfs.distance_of(*start_id), 0.into());
+
+            bfs.release(&mut context);
+        }
+
+        // Then: Results are the same as fresh traversals, and buffers are kept in the context.
+        assert_eq!(visited, vec![vec![a, b, c], vec![b, c], vec![c]]);
+        assert_eq!(context.buffer_count::<Color>(), 1);
+        assert_eq!(context.buffer_count::<Magnitude<usize>>(), 1);
+    }
+
+    // Does not go further than `max_distance` from the start vertices, and stops as soon as `target` is discovered.
+    struct BoundedListener {
+        max_distance: usize,
+        target: Option<usize>,
+        discovered: Vec<usize>,
+    }
+
+    impl BfsListener for BoundedListener {
+        fn on_discover(&mut self, bfs: &Bfs, virt_id: usize) -> Control {
+            let real_id = bfs.get_id_map().real_id_of(virt_id);
+            self.discovered.push(real_id);
+
+            if Some(real_id) == self.target {
+                Control::Stop
+            } else {
+                Control::Continue
+            }
+        }
+
+        fn on_visit(&mut self, bfs: &Bfs, virt_id: usize) -> Control {
+            if bfs.get_distances()[virt_id] >= self.max_distance.into() {
+                Control::SkipNeighbors
+            } else {
+                Control::Continue
+            }
+        }
+    }
+
+    #[test]
+    fn listener_skips_neighbors() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c  ---  d
+        //
+        let mut mat = BitMat::init();
+        let a = mat.add_vertex();
+        let b = mat.add_vertex();
+        let c = mat.add_vertex();
+        let d = mat.add_vertex();
+        mat.add_edge_unchecked(a, b);
+        mat.add_edge_unchecked(b, c);
+        mat.add_edge_unchecked(c, d);
+
+        // When: Traversing at most 1 edge away from a.
+        let mut listener = BoundedListener {
+            max_distance: 1,
+            target: None,
+            discovered: vec![],
+        };
+        let mut bfs = Bfs::init_with_starts(&mat, vec![a]);
+        let visited = bfs.execute_with_listener(&mat, &mut listener);
+
+        // Then:
+        assert_eq!(visited, vec![a, b]);
+        assert_eq!(listener.discovered, vec![a, b]);
+        assert!(bfs.distance_of(c).is_pos_infinite());
+    }
+
+    #[test]
+    fn listener_stops_traversal() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  d
+        //      |
+        //      c  ---  e
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(a, c, 1.into());
+        graph.add_edge_unchecked(b, d, 1.into());
+        graph.add_edge_unchecked(c, e, 5.into());
+
+        // When: Stopping as soon as d is discovered, only through edges with weight less than 3.
+        let mut listener = BoundedListener {
+            max_distance: usize::MAX,
+            target: Some(d),
+            discovered: vec![],
+        };
+        let mut bfs = Bfs::init_with_starts(&graph, vec![a]);
+        let visited = bfs.execute_filtered_with_listener(
+            &graph,
+            |_, _, edge| *edge.get_weight() < 3.into(),
+            &mut listener,
+        );
+
+        // Then: c and d are left in the queue.
+        assert_eq!(visited, vec![a, b]);
+        assert_eq!(listener.discovered, vec![a, b, c, d]);
+        assert_eq!(bfs.get_queue().len(), 2);
+        assert_eq!(bfs.distance_of(d), 2.into());
+    }
+}
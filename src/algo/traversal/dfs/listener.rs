@@ -1,10 +1,29 @@
 use super::Dfs;
+use crate::algo::Control;
 
+/// Callbacks that are called by [`Dfs`] during the traversal.
+///
+/// Each callback returns a [`Control`] value to let the listener terminate the traversal early or prune it.
 #[allow(unused_variables)]
 pub trait DfsListener<L: DfsListener = Self> {
-    fn on_start(&mut self, dfs: &Dfs<L>, virt_id: usize) {}
-    fn on_white(&mut self, dfs: &Dfs<L>, virt_id: usize) {}
-    fn on_gray(&mut self, dfs: &Dfs<L>, virt_id: usize) {}
-    fn on_black(&mut self, dfs: &Dfs<L>, virt_id: usize) {}
-    fn on_finish(&mut self, dfs: &Dfs<L>) {}
+    fn on_start(&mut self, dfs: &Dfs<L>, virt_id: usize) -> Control {
+        Control::Continue
+    }
+
+    /// Returning `Control::SkipNeighbors` prevents neighbors of the vertex from being discovered through it.
+    fn on_white(&mut self, dfs: &Dfs<L>, virt_id: usize) -> Control {
+        Control::Continue
+    }
+
+    fn on_gray(&mut self, dfs: &Dfs<L>, virt_id: usize) -> Control {
+        Control::Continue
+    }
+
+    fn on_black(&mut self, dfs: &Dfs<L>, virt_id: usize) -> Control {
+        Control::Continue
+    }
+
+    fn on_finish(&mut self, dfs: &Dfs<L>) -> Control {
+        Control::Continue
+    }
 }
@@ -4,8 +4,11 @@ pub use listener::DfsListener;
 
 use magnitude::Magnitude;
 use std::cell::RefCell;
+use std::sync::Arc;
 
-use super::Color;
+use super::{Color, Control};
+use crate::algo::AlgoContext;
+use crate::graph::Edge;
 use crate::provide::{self, IdMap};
 
 pub struct Dfs<'a, L: DfsListener> {
@@ -13,8 +16,10 @@ pub struct Dfs<'a, L: DfsListener> {
     colors: Vec<Color>,
     discovered: Vec<Magnitude<usize>>,
     finished: Vec<Magnitude<usize>>,
+    depths: Vec<Magnitude<usize>>,
+    max_depth: Magnitude<usize>,
     time: usize,
-    id_map: IdMap,
+    id_map: Arc<IdMap>,
     start_ids: Vec<usize>,
     listener: RefCell<&'a mut L>,
 }
@@ -27,13 +32,19 @@ impl<'a, L: DfsListener> Dfs<'a, L> {
         Dfs::init_with_starts(graph, listener, vec![])
     }
 
+    /// # Arguments
+    /// * `graph`: Graph to traverse.
+    /// * `listener`: Listener to be notified during the traversal.
+    /// * `start_ids`: Id of the vertices to start the search from, like roots of a [`MultiRootSubgraph`](crate::graph::subgraph::MultiRootSubgraph).
+    ///
+    /// Vertices that are not reachable from any of the start vertices are not visited.
     pub fn init_with_starts<G>(graph: &G, listener: &'a mut L, mut start_ids: Vec<usize>) -> Self
     where
         G: provide::Vertices + provide::Neighbors,
     {
         let vertex_count = graph.vertex_count();
 
-        let id_map = graph.continuos_id_map();
+        let id_map = graph.continuous_id_map();
 
         start_ids = start_ids
             .into_iter()
@@ -45,13 +56,77 @@ impl<'a, L: DfsListener> Dfs<'a, L> {
             colors: vec![Color::White; vertex_count],
             discovered: vec![Magnitude::PosInfinite; vertex_count],
             finished: vec![Magnitude::PosInfinite; vertex_count],
+            depths: vec![Magnitude::PosInfinite; vertex_count],
+            max_depth: Magnitude::PosInfinite,
             time: 0,
-            id_map: graph.continuos_id_map(),
+            id_map: Arc::new(id_map),
             listener: RefCell::new(listener),
             start_ids,
         }
     }
 
+    /// Same as `init_with_starts`, but takes the id map and buffers from `context` instead of allocating them.
+    /// Use `release` after the traversal to give the buffers back.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to traverse.
+    /// * `listener`: Listener to be notified during the traversal.
+    /// * `start_ids`: Id of the vertices to start the search from. Empty to search from every part of the graph.
+    /// * `context`: Context that is used with `graph`.
+    pub fn init_in<G>(
+        graph: &G,
+        listener: &'a mut L,
+        start_ids: Vec<usize>,
+        context: &mut AlgoContext,
+    ) -> Self
+    where
+        G: provide::Vertices + provide::Neighbors,
+    {
+        let id_map = context.id_map(graph);
+        let vertex_count = id_map.len();
+
+        Dfs {
+            stack: context.take_buffer(0, 0),
+            colors: context.take_buffer(vertex_count, Color::White),
+            discovered: context.take_buffer(vertex_count, Magnitude::PosInfinite),
+            finished: context.take_buffer(vertex_count, Magnitude::PosInfinite),
+            depths: context.take_buffer(vertex_count, Magnitude::PosInfinite),
+            max_depth: Magnitude::PosInfinite,
+            time: 0,
+            start_ids: start_ids
+                .into_iter()
+                .map(|real_id| id_map.virt_id_of(real_id))
+                .collect(),
+            id_map,
+            listener: RefCell::new(listener),
+        }
+    }
+
+    /// Gives buffers of the traversal back to `context`, so they can be reused by the next algorithm.
+    ///
+    /// # Arguments
+    /// `context`: Context to keep the buffers.
+    pub fn release(self, context: &mut AlgoContext) {
+        context.put_buffer(self.stack);
+        context.put_buffer(self.colors);
+        context.put_buffer(self.discovered);
+        context.put_buffer(self.finished);
+        context.put_buffer(self.depths);
+    }
+
+    /// Limits the search to vertices that are at most `max_depth` edges away from the start vertex that discovered them.
+    /// Neighbors of vertices at depth `max_depth` are not discovered through them.
+    ///
+    /// Note that each vertex is visited at most once. So a vertex that is first discovered through a long path,
+    /// may prevent its neighbors from being discovered even if there is a shorter path to it.
+    /// For a search that visits each vertex at its minimum depth use [`IddfsIter`](crate::algo::IddfsIter).
+    ///
+    /// # Arguments
+    /// `max_depth`: Maximum depth of the search.
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth.into();
+    }
+
     fn next_start_id(&self) -> Option<usize> {
         if self.start_ids.is_empty() {
             self.colors.iter().position(|color| *color == Color::White)
@@ -63,14 +138,82 @@ impl<'a, L: DfsListener> Dfs<'a, L> {
         }
     }
 
+    /// Performs the traversal and calls the listener callbacks.
+    ///
+    /// Traversal terminates as soon as a callback returns `Control::Stop`.
+    /// In that case vertices that were on the stack remain on it and can be retrieved using [`get_stack`](Dfs::get_stack).
     pub fn execute<G>(&mut self, graph: &G)
     where
         G: provide::Vertices + provide::Neighbors,
+    {
+        self.traverse(|real_id| graph.neighbors_unchecked(real_id))
+    }
+
+    /// Performs the traversal only through edges that satisfy `filter`, as if other edges do not exist in the graph.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to traverse.
+    /// * `filter`: Gets source id, destination id and the edge and returns `true` if the edge can be traversed.
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::algo::{Dfs, DfsListener};
+    /// use prepona::storage::Mat;
+    /// use prepona::graph::MatGraph;
+    ///
+    /// struct Listener;
+    /// impl DfsListener for Listener {}
+    ///
+    /// // Given: Graph
+    /// //
+    /// //      a  --(1)--  b  --(5)--  c
+    /// //
+    /// let mut graph = MatGraph::init(Mat::<usize>::init());
+    /// let a = graph.add_vertex();
+    /// let b = graph.add_vertex();
+    /// let c = graph.add_vertex();
+    /// graph.add_edge_unchecked(a, b, 1.into());
+    /// graph.add_edge_unchecked(b, c, 5.into());
+    ///
+    /// // When: Only traversing edges with weight less than 3.
+    /// let mut listener = Listener;
+    /// let mut dfs = Dfs::init_with_starts(&graph, &mut listener, vec![a]);
+    /// dfs.execute_filtered(&graph, |_, _, edge| *edge.get_weight() < 3.into());
+    ///
+    /// // Then: c is not visited.
+    /// let c_virt_id = dfs.get_id_map().virt_id_of(c);
+    /// assert!(dfs.get_discovered()[c_virt_id].is_pos_infinite());
+    /// ```
+    pub fn execute_filtered<G, W, E, F>(&mut self, graph: &G, filter: F)
+    where
+        E: Edge<W>,
+        G: provide::Vertices + provide::Edges<W, E>,
+        F: Fn(usize, usize, &E) -> bool,
+    {
+        self.traverse(|real_id| {
+            graph
+                .edges_from_unchecked(real_id)
+                .into_iter()
+                .filter(|(dst_id, edge)| filter(real_id, *dst_id, edge))
+                .map(|(dst_id, _)| dst_id)
+                .collect()
+        })
+    }
+
+    // Performs the traversal using `neighbors_of` to find neighbors of each vertex.
+    // `neighbors_of` gets and returns real ids.
+    fn traverse<F>(&mut self, neighbors_of: F)
+    where
+        F: Fn(usize) -> Vec<usize>,
     {
         while let Some(start_id) = self.next_start_id() {
             self.time += 1;
             self.stack.push(start_id);
-            self.listener.borrow_mut().on_start(self, start_id);
+            self.depths[start_id] = 0.into();
+            if self.listener.borrow_mut().on_start(self, start_id) == Control::Stop {
+                return;
+            }
 
             while let Some(virt_id) = self.stack.pop() {
                 let color = self.colors[virt_id];
@@ -79,34 +222,56 @@ impl<'a, L: DfsListener> Dfs<'a, L> {
                     Color::White => {
                         self.time += 1;
                         self.discovered[virt_id] = self.time.into();
-                        self.listener.borrow_mut().on_white(self, virt_id);
+                        let control = self.listener.borrow_mut().on_white(self, virt_id);
 
                         self.colors[virt_id] = Color::Gray;
+                        self.stack.push(virt_id);
+
+                        match control {
+                            Control::Stop => return,
+                            Control::SkipNeighbors => continue,
+                            Control::Continue => {}
+                        }
+
+                        let depth = self.depths[virt_id];
+                        if depth >= self.max_depth {
+                            continue;
+                        }
 
                         let real_id = self.id_map.real_id_of(virt_id);
 
-                        let mut neighbors = graph
-                            .neighbors_unchecked(real_id)
+                        let mut neighbors = neighbors_of(real_id)
                             .into_iter()
                             .map(|real_id| self.id_map.virt_id_of(real_id))
                             .filter(|virt_id| self.colors[*virt_id] == Color::White)
-                            .collect();
+                            .collect::<Vec<usize>>();
+
+                        // Depth of a vertex is determined by the last vertex that pushed it, because that is the one it will be popped for.
+                        for n_virt_id in &neighbors {
+                            self.depths[*n_virt_id] = depth + 1.into();
+                        }
 
-                        self.stack.push(virt_id);
                         self.stack.append(&mut neighbors);
                     }
                     Color::Gray => {
-                        self.listener.borrow_mut().on_gray(self, virt_id);
+                        if self.listener.borrow_mut().on_gray(self, virt_id) == Control::Stop {
+                            return;
+                        }
 
                         self.colors[virt_id] = Color::Black;
                         self.time += 1;
                         self.finished[virt_id] = self.time.into();
-                        self.listener.borrow_mut().on_black(self, virt_id);
+                        if self.listener.borrow_mut().on_black(self, virt_id) == Control::Stop {
+                            return;
+                        }
                     }
                     Color::Black => {}
                 }
             }
-            self.listener.borrow_mut().on_finish(self);
+
+            if self.listener.borrow_mut().on_finish(self) == Control::Stop {
+                return;
+            }
         }
     }
 
@@ -118,6 +283,29 @@ impl<'a, L: DfsListener> Dfs<'a, L> {
         &self.colors
     }
 
+    /// # Arguments
+    /// `real_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Time at which the vertex is discovered(colored gray).
+    /// `PosInfinite` if vertex is not discovered yet.
+    ///
+    /// Time starts from zero and is incremented each time a search starts, a vertex is discovered or a vertex is finished.
+    /// So it is also available to listeners while traversal is in progress.
+    pub fn discovery_time(&self, real_id: usize) -> Magnitude<usize> {
+        self.discovered[self.id_map.virt_id_of(real_id)]
+    }
+
+    /// # Arguments
+    /// `real_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Time at which all vertices reachable from the vertex are visited and the vertex is finished(colored black).
+    /// `PosInfinite` if vertex is not finished yet.
+    pub fn finish_time(&self, real_id: usize) -> Magnitude<usize> {
+        self.finished[self.id_map.virt_id_of(real_id)]
+    }
+
     pub fn get_discovered(&self) -> &Vec<Magnitude<usize>> {
         &self.discovered
     }
@@ -126,18 +314,29 @@ impl<'a, L: DfsListener> Dfs<'a, L> {
         &self.finished
     }
 
+    /// # Returns
+    /// Depth of each vertex, which is the number of edges between the vertex and the start vertex that discovered it.
+    /// `PosInfinite` for vertices that are never pushed to the stack.
+    pub fn get_depths(&self) -> &Vec<Magnitude<usize>> {
+        &self.depths
+    }
+
     pub fn get_id_map(&self) -> &IdMap {
         &self.id_map
     }
 
     pub fn dissolve(self) -> (Vec<Magnitude<usize>>, Vec<Magnitude<usize>>, IdMap) {
-        (self.discovered, self.finished, self.id_map)
+        // Id map is only copied if it's still shared with a context.
+        let id_map = Arc::try_unwrap(self.id_map).unwrap_or_else(|id_map| (*id_map).clone());
+
+        (self.discovered, self.finished, id_map)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::graph::subgraph::MultiRootSubgraph;
     use crate::graph::MatGraph;
     use crate::provide::*;
     use crate::storage::{DiMat, Mat};
@@ -163,24 +362,34 @@ mod tests {
     }
 
     impl DfsListener for DefaultListener {
-        fn on_start(&mut self, _: &Dfs<Self>, _: usize) {
+        fn on_start(&mut self, _: &Dfs<Self>, _: usize) -> Control {
             self.on_start_called += 1;
+
+            Control::Continue
         }
 
-        fn on_white(&mut self, _: &Dfs<Self>, _: usize) {
+        fn on_white(&mut self, _: &Dfs<Self>, _: usize) -> Control {
             self.on_white_called += 1;
+
+            Control::Continue
         }
 
-        fn on_gray(&mut self, _: &Dfs<Self>, _: usize) {
+        fn on_gray(&mut self, _: &Dfs<Self>, _: usize) -> Control {
             self.on_gray_called += 1;
+
+            Control::Continue
         }
 
-        fn on_black(&mut self, _: &Dfs<Self>, _: usize) {
+        fn on_black(&mut self, _: &Dfs<Self>, _: usize) -> Control {
             self.on_black_called += 1;
+
+            Control::Continue
         }
 
-        fn on_finish(&mut self, _: &Dfs<Self>) {
+        fn on_finish(&mut self, _: &Dfs<Self>) -> Control {
             self.on_finish_called += 1;
+
+            Control::Continue
         }
     }
 
@@ -436,4 +645,294 @@ mod tests {
         assert_eq!(listener.on_black_called, 5);
         assert_eq!(listener.on_finish_called, 1);
     }
+
+    #[test]
+    fn roots_of_subgraph() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c       d  -->  e       f
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+        let bc = graph.add_edge_unchecked(b, c, 1.into());
+        let de = graph.add_edge_unchecked(d, e, 1.into());
+
+        // And: Subgraph containing all edges and vertices with b and d as roots.
+        let subgraph = MultiRootSubgraph::init(
+            &graph,
+            vec![(a, b, ab), (b, c, bc), (d, e, de)],
+            vec![a, b, c, d, e, f].into_iter().collect(),
+            vec![b, d],
+        );
+
+        // When: Performing Dfs algorithm starting from roots of the subgraph.
+        let mut listener = DefaultListener::init();
+        let mut dfs = Dfs::init_with_starts(&subgraph, &mut listener, subgraph.roots().clone());
+        dfs.execute(&subgraph);
+
+        // Then:
+        let id_map = dfs.get_id_map();
+        assert!(vec![a, f]
+            .into_iter()
+            .all(|vertex_id| dfs.get_colors()[id_map.virt_id_of(vertex_id)] == Color::White));
+        assert_eq!(listener.on_start_called, 2);
+        assert_eq!(listener.on_white_called, 4);
+        assert_eq!(listener.on_finish_called, 2);
+    }
+
+    #[test]
+    fn max_depth() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c  -->  d
+        //      |
+        //      v
+        //      e  -->  f
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+        graph.add_edge_unchecked(a, e, 1.into());
+        graph.add_edge_unchecked(e, f, 1.into());
+
+        // When: Performing Dfs algorithm from a with max depth of 1.
+        let mut listener = DefaultListener::init();
+        let mut dfs = Dfs::init_with_starts(&graph, &mut listener, vec![a]);
+        dfs.set_max_depth(1);
+        dfs.execute(&graph);
+
+        // Then:
+        let id_map = dfs.get_id_map();
+        assert_eq!(dfs.get_depths()[id_map.virt_id_of(a)], 0.into());
+        assert_eq!(dfs.get_depths()[id_map.virt_id_of(b)], 1.into());
+        assert_eq!(dfs.get_depths()[id_map.virt_id_of(e)], 1.into());
+        assert!(vec![c, d, f]
+            .into_iter()
+            .all(|vertex_id| dfs.get_colors()[id_map.virt_id_of(vertex_id)] == Color::White));
+        assert_eq!(listener.on_white_called, 3);
+        assert_eq!(listener.on_black_called, 3);
+    }
+
+    #[test]
+    fn timestamps() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c       d
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // When: Performing Dfs algorithm starting from a.
+        let mut listener = DefaultListener::init();
+        let mut dfs = Dfs::init_with_starts(&graph, &mut listener, vec![a]);
+        dfs.execute(&graph);
+
+        // Then: Intervals of descendants are nested in intervals of their ancestors.
+        assert_eq!(dfs.discovery_time(a), 2.into());
+        assert_eq!(dfs.discovery_time(b), 3.into());
+        assert_eq!(dfs.discovery_time(c), 4.into());
+        assert_eq!(dfs.finish_time(c), 5.into());
+        assert_eq!(dfs.finish_time(b), 6.into());
+        assert_eq!(dfs.finish_time(a), 7.into());
+        assert!(dfs.discovery_time(d).is_pos_infinite());
+        assert!(dfs.finish_time(d).is_pos_infinite());
+    }
+
+    #[test]
+    fn filtered_edges() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c
+        //      |               ^
+        //      v               |
+        //      d  -------------'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        let ad = graph.add_edge_unchecked(a, d, 1.into());
+        graph.add_edge_unchecked(d, c, 1.into());
+
+        // When: Performing Dfs algorithm only through edges that start from a.
+        let mut listener = DefaultListener::init();
+        let mut dfs = Dfs::init_with_starts(&graph, &mut listener, vec![a]);
+        dfs.execute_filtered(&graph, |src_id, _, edge| {
+            src_id == a && (edge.get_id() == ab || edge.get_id() == ad)
+        });
+
+        // Then:
+        let id_map = dfs.get_id_map();
+        assert_eq!(dfs.get_colors()[id_map.virt_id_of(c)], Color::White);
+        assert_eq!(dfs.get_colors()[id_map.virt_id_of(b)], Color::Black);
+        assert_eq!(dfs.get_colors()[id_map.virt_id_of(d)], Color::Black);
+        assert_eq!(listener.on_white_called, 3);
+    }
+
+    // Stops as soon as it finds the vertex it is looking for.
+    struct FindListener {
+        target_id: usize,
+        visited: Vec<usize>,
+    }
+
+    impl DfsListener for FindListener {
+        fn on_white(&mut self, dfs: &Dfs<Self>, virt_id: usize) -> Control {
+            let real_id = dfs.get_id_map().real_id_of(virt_id);
+            self.visited.push(real_id);
+
+            if real_id == self.target_id {
+                Control::Stop
+            } else {
+                Control::Continue
+            }
+        }
+    }
+
+    // Does not explore neighbors of the vertices in the blocked list.
+    struct PruneListener {
+        blocked_ids: Vec<usize>,
+        visited: Vec<usize>,
+    }
+
+    impl DfsListener for PruneListener {
+        fn on_white(&mut self, dfs: &Dfs<Self>, virt_id: usize) -> Control {
+            let real_id = dfs.get_id_map().real_id_of(virt_id);
+            self.visited.push(real_id);
+
+            if self.blocked_ids.contains(&real_id) {
+                Control::SkipNeighbors
+            } else {
+                Control::Continue
+            }
+        }
+    }
+
+    #[test]
+    fn stop_on_target() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c  -->  d
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+
+        // When: Performing Dfs algorithm looking for c.
+        let mut listener = FindListener {
+            target_id: c,
+            visited: vec![],
+        };
+        let mut dfs = Dfs::init_with_starts(&graph, &mut listener, vec![a]);
+        dfs.execute(&graph);
+
+        // Then:
+        assert_eq!(
+            dfs.get_colors()[dfs.get_id_map().virt_id_of(d)],
+            Color::White
+        );
+        assert!(dfs.get_discovered()[dfs.get_id_map().virt_id_of(d)].is_pos_infinite());
+        assert_eq!(listener.visited, vec![a, b, c]);
+    }
+
+    #[test]
+    fn skip_neighbors() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c
+        //      |
+        //      d  ---  e
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(a, d, 1.into());
+        graph.add_edge_unchecked(d, e, 1.into());
+
+        // When: Performing Dfs algorithm from a without exploring beyond b and d.
+        let mut listener = PruneListener {
+            blocked_ids: vec![b, d],
+            visited: vec![],
+        };
+        let mut dfs = Dfs::init_with_starts(&graph, &mut listener, vec![a]);
+        dfs.execute(&graph);
+
+        // Then:
+        assert_eq!(
+            dfs.get_colors()[dfs.get_id_map().virt_id_of(b)],
+            Color::Black
+        );
+        assert_eq!(
+            dfs.get_colors()[dfs.get_id_map().virt_id_of(d)],
+            Color::Black
+        );
+        assert_eq!(listener.visited.len(), 3);
+        assert!(vec![a, b, d]
+            .iter()
+            .all(|vertex_id| listener.visited.contains(vertex_id)));
+    }
+
+    #[test]
+    fn reuses_buffers_of_context() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        let mut context = AlgoContext::init();
+
+        // When: Traversing from each vertex using the same context.
+        let mut white_counts = vec![];
+        for start_id in &[a, b, c] {
+            let mut listener = DefaultListener::init();
+            let mut dfs = Dfs::init_in(&graph, &mut listener, vec![*start_id], &mut context);
+            dfs.execute(&graph);
+            assert_eq!(dfs.discovery_time(*start_id), 2.into());
+
+            dfs.release(&mut context);
+            white_counts.push(listener.on_white_called);
+        }
+
+        // Then: Results are the same as fresh traversals, and buffers are kept in the context.
+        assert_eq!(white_counts, vec![3, 2, 1]);
+        assert_eq!(context.buffer_count::<Color>(), 1);
+        assert_eq!(context.buffer_count::<Magnitude<usize>>(), 3);
+    }
 }
@@ -0,0 +1,231 @@
+use std::collections::HashSet;
+
+use magnitude::Magnitude;
+
+use crate::provide;
+
+/// Iterates over vertices of the graph using iterative deepening depth first search.
+///
+/// Performs depth limited searches from the start vertex with limits 0, 1, 2, ... and yields each vertex the first time it is reached,
+/// along with its depth. So vertices are yielded in ascending order of their distance(number of edges) from the start vertex.
+/// Each depth limited search only keeps the current path and its unexplored neighbors in memory,
+/// which makes it suitable for deep searches in graphs with a large branching factor.
+/// Iteration ends when a search does not reach its limit or does not find any new vertex, or when `max_depth` is reached.
+///
+/// ## Note
+/// * d: Means depth of the deepest vertex that is yielded.
+/// * b: Means maximum number of neighbors of a vertex.
+///
+/// # Complexity
+/// O(b^d) in time and O(b * d + |V|) in memory to remember the vertices that are already yielded.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::IddfsIter;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  -->  b  -->  c
+/// //      |               ^
+/// //      '---------------'
+/// //
+/// let mut graph = MatGraph::init(DiMat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(a, c, 1.into());
+///
+/// let depth_of_c = IddfsIter::init(&graph, a)
+///     .find(|(vertex_id, _)| *vertex_id == c)
+///     .map(|(_, depth)| depth);
+///
+/// assert_eq!(depth_of_c, Some(1));
+/// ```
+pub struct IddfsIter<'a, G: provide::Neighbors> {
+    graph: &'a G,
+    start_id: usize,
+
+    // Vertices that are waiting to be explored in the current search, along with their depth.
+    stack: Vec<(usize, usize)>,
+
+    // Vertices on the path from start vertex to the vertex that is being explored.
+    path: Vec<usize>,
+
+    depth_limit: usize,
+    max_depth: Magnitude<usize>,
+
+    // Whether current search has vertices at its depth limit that have unexplored neighbors.
+    is_cut_off: bool,
+
+    // Whether current search has yielded any vertex. If no vertex is at distance d from the start vertex,
+    // there is no vertex farther than d either.
+    has_yielded: bool,
+
+    yielded: HashSet<usize>,
+}
+
+impl<'a, G: provide::Neighbors> IddfsIter<'a, G> {
+    /// # Arguments
+    /// * `graph`: Graph to search.
+    /// * `start_id`: Id of the vertex to start the search from.
+    ///
+    /// # Returns
+    /// Iterator that searches the graph without any limit on depth.
+    pub fn init(graph: &'a G, start_id: usize) -> Self {
+        IddfsIter {
+            graph,
+            start_id,
+            stack: vec![(start_id, 0)],
+            path: vec![],
+            depth_limit: 0,
+            max_depth: Magnitude::PosInfinite,
+            is_cut_off: false,
+            has_yielded: false,
+            yielded: HashSet::new(),
+        }
+    }
+
+    /// # Arguments
+    /// * `graph`: Graph to search.
+    /// * `start_id`: Id of the vertex to start the search from.
+    /// * `max_depth`: Maximum depth of the search.
+    ///
+    /// # Returns
+    /// Iterator that only yields vertices that are at most `max_depth` edges away from the start vertex.
+    pub fn init_with_max_depth(graph: &'a G, start_id: usize, max_depth: usize) -> Self {
+        let mut iddfs = IddfsIter::init(graph, start_id);
+
+        iddfs.max_depth = max_depth.into();
+
+        iddfs
+    }
+
+    /// # Returns
+    /// Depth limit of the search that is being performed.
+    pub fn depth_limit(&self) -> usize {
+        self.depth_limit
+    }
+}
+
+impl<'a, G: provide::Neighbors> Iterator for IddfsIter<'a, G> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((vertex_id, depth)) = self.stack.pop() {
+                self.path.truncate(depth);
+                self.path.push(vertex_id);
+
+                let neighbors = self
+                    .graph
+                    .neighbors_unchecked(vertex_id)
+                    .into_iter()
+                    .filter(|n_id| !self.path.contains(n_id))
+                    .collect::<Vec<usize>>();
+
+                if depth < self.depth_limit {
+                    // Push in reverse so neighbors are explored in the order graph returns them.
+                    self.stack
+                        .extend(neighbors.into_iter().rev().map(|n_id| (n_id, depth + 1)));
+                } else if !neighbors.is_empty() {
+                    self.is_cut_off = true;
+                }
+
+                if self.yielded.insert(vertex_id) {
+                    self.has_yielded = true;
+                    return Some((vertex_id, depth));
+                }
+            } else {
+                let next_limit = Magnitude::from(self.depth_limit + 1);
+
+                if !self.is_cut_off || !self.has_yielded || next_limit > self.max_depth {
+                    return None;
+                }
+
+                self.depth_limit += 1;
+                self.is_cut_off = false;
+                self.has_yielded = false;
+                self.stack.push((self.start_id, 0));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::*;
+    use crate::storage::{BitMat, DiMat};
+
+    #[test]
+    fn single_vertex() {
+        let mut mat = BitMat::init();
+        let a = mat.add_vertex();
+
+        let visited = IddfsIter::init(&mat, a).collect::<Vec<(usize, usize)>>();
+
+        assert_eq!(visited, vec![(a, 0)]);
+    }
+
+    #[test]
+    fn vertices_in_order_of_depth() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c  ---  d
+        //      |                       |
+        //      e  ---------------------'       f
+        //
+        let mut mat = BitMat::init();
+        let a = mat.add_vertex();
+        let b = mat.add_vertex();
+        let c = mat.add_vertex();
+        let d = mat.add_vertex();
+        let e = mat.add_vertex();
+        let f = mat.add_vertex();
+        mat.add_edge_unchecked(a, b);
+        mat.add_edge_unchecked(b, c);
+        mat.add_edge_unchecked(c, d);
+        mat.add_edge_unchecked(a, e);
+        mat.add_edge_unchecked(e, d);
+
+        // When: Performing iterative deepening search from a.
+        let mut iddfs = IddfsIter::init(&mat, a);
+        let visited = iddfs.by_ref().collect::<Vec<(usize, usize)>>();
+
+        // Then:
+        assert_eq!(visited, vec![(a, 0), (b, 1), (e, 1), (c, 2), (d, 2)]);
+        assert!(visited.iter().all(|(vertex_id, _)| *vertex_id != f));
+        assert_eq!(iddfs.depth_limit(), 3);
+    }
+
+    #[test]
+    fn max_depth() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c  -->  d
+        //      ^               |
+        //      '---------------'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, a, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+
+        // When: Performing iterative deepening search from a with max depth of 2.
+        let visited = IddfsIter::init_with_max_depth(&graph, a, 2).collect::<Vec<(usize, usize)>>();
+
+        // Then:
+        assert_eq!(visited, vec![(a, 0), (b, 1), (c, 2)]);
+    }
+}
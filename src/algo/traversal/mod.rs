@@ -1,8 +1,10 @@
 mod bfs;
 mod dfs;
+mod iddfs;
 
-pub use bfs::Bfs;
+pub use bfs::{Bfs, BfsListener};
 pub use dfs::{Dfs, DfsListener};
+pub use iddfs::IddfsIter;
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum Color {
@@ -10,3 +12,17 @@ pub enum Color {
     Gray,
     Black,
 }
+
+/// Returned from callbacks of traversal listeners to control how the traversal proceeds.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Control {
+    /// Continue the traversal as usual.
+    Continue,
+
+    /// Do not discover neighbors of the vertex that is just visited.
+    /// Has the same effect as `Continue` when returned from callbacks other than the one that visits a vertex for the first time.
+    SkipNeighbors,
+
+    /// Terminate the traversal immediately. No further callback will be called.
+    Stop,
+}
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+
+use super::recognition::ensure_tree;
+use super::rooted_tree::root_at;
+use crate::graph::{Edge, UndirectedEdge};
+use crate::provide::{Edges, Graph, Neighbors, Vertices};
+
+/// Finds the centroids of a tree: vertices whose removal leaves components with at most half of the vertices each.
+///
+/// Every tree has one centroid, or two adjacent centroids.
+///
+/// # Arguments
+/// `graph`: Tree to find its centroids.
+///
+/// # Returns
+/// * `Ok`: Containing ids of the centroids(sorted).
+/// * `Err`: [`NotTree`](crate::graph::ErrorKind::NotTree) if graph is not a tree.
+///
+/// # Complexity
+/// O(|V| + |E|)
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::tree::centroids;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b  ---  c  ---  d
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+///
+/// assert_eq!(centroids(&graph).unwrap(), vec![b, c]);
+/// ```
+pub fn centroids<W, E, G>(graph: &G) -> Result<Vec<usize>>
+where
+    E: Edge<W>,
+    G: Edges<W, E> + Neighbors + Vertices + Graph<W, E, UndirectedEdge>,
+{
+    ensure_tree(graph)?;
+
+    let vertex_count = graph.vertex_count();
+    let tree = root_at(graph, graph.vertices()[0])?;
+
+    // Number of vertices in the subtree of each vertex, computed bottom up.
+    let mut size_of: HashMap<usize, usize> = HashMap::new();
+    for vertex_id in tree.order().iter().rev() {
+        let size = tree
+            .children_of(*vertex_id)
+            .iter()
+            .map(|child_id| size_of[child_id])
+            .sum::<usize>();
+
+        size_of.insert(*vertex_id, size + 1);
+    }
+
+    let mut centroids = tree
+        .order()
+        .iter()
+        .copied()
+        .filter(|vertex_id| {
+            let largest_child = tree
+                .children_of(*vertex_id)
+                .iter()
+                .map(|child_id| size_of[child_id])
+                .max()
+                .unwrap_or(0);
+            let above = vertex_count - size_of[vertex_id];
+
+            2 * largest_child.max(above) <= vertex_count
+        })
+        .collect::<Vec<usize>>();
+    centroids.sort_unstable();
+
+    Ok(centroids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::storage::Mat;
+
+    #[test]
+    fn not_a_tree() {
+        // Given: Graph
+        //
+        //      a  ---  b       c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+
+        // Then:
+        assert!(centroids(&graph).is_err());
+    }
+
+    #[test]
+    fn single_vertex() {
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+
+        assert_eq!(centroids(&graph).unwrap(), vec![a]);
+    }
+
+    #[test]
+    fn star_and_path() {
+        // Given: Graph
+        //
+        //      b  ---  a  ---  c
+        //            /   \
+        //          d       e  ---  f  ---  g
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        let g = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(a, c, 1.into());
+        graph.add_edge_unchecked(a, d, 1.into());
+        graph.add_edge_unchecked(a, e, 1.into());
+        graph.add_edge_unchecked(e, f, 1.into());
+        graph.add_edge_unchecked(f, g, 1.into());
+
+        // Then: Removing a leaves components with 1, 1, 1 and 3 vertices.
+        assert_eq!(centroids(&graph).unwrap(), vec![a]);
+    }
+}
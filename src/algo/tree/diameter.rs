@@ -0,0 +1,131 @@
+use anyhow::Result;
+
+use super::recognition::ensure_tree;
+use super::rooted_tree::{root_at, RootedTree};
+use crate::graph::{Edge, UndirectedEdge};
+use crate::provide::{Edges, Graph, Neighbors, Vertices};
+
+/// Finds a longest path(in number of edges) of a tree using two breadth first searches.
+///
+/// First search finds the farthest vertex u from an arbitrary vertex, which is an end point of a longest path.
+/// Second search finds the farthest vertex from u, which is the other end point.
+///
+/// # Arguments
+/// `graph`: Tree to find its diameter.
+///
+/// # Returns
+/// * `Ok`: Containing number of edges of the longest path, and ids of the vertices on it in order.
+/// * `Err`: [`NotTree`](crate::graph::ErrorKind::NotTree) if graph is not a tree.
+///
+/// # Complexity
+/// O(|V| + |E|)
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::tree::diameter;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b  ---  c
+/// //              |
+/// //              d
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(b, d, 1.into());
+///
+/// let (length, path) = diameter(&graph).unwrap();
+///
+/// assert_eq!(length, 2);
+/// assert_eq!(path[1], b);
+/// ```
+pub fn diameter<W, E, G>(graph: &G) -> Result<(usize, Vec<usize>)>
+where
+    E: Edge<W>,
+    G: Edges<W, E> + Neighbors + Vertices + Graph<W, E, UndirectedEdge>,
+{
+    ensure_tree(graph)?;
+
+    // Any vertex can be the start of the first search.
+    let tree = root_at(graph, graph.vertices()[0])?;
+
+    let farthest_of = |tree: &RootedTree| *tree.order().last().unwrap();
+
+    let tree = root_at(graph, farthest_of(&tree))?;
+    let end_id = farthest_of(&tree);
+
+    let mut path = vec![end_id];
+    while let Some(parent_id) = tree.parent_of(*path.last().unwrap()) {
+        path.push(parent_id);
+    }
+
+    Ok((path.len() - 1, path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::storage::Mat;
+
+    #[test]
+    fn not_a_tree() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        assert!(diameter(&graph).is_err());
+    }
+
+    #[test]
+    fn single_vertex() {
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+
+        assert_eq!(diameter(&graph).unwrap(), (0, vec![a]));
+    }
+
+    #[test]
+    fn caterpillar() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c  ---  d  ---  e
+        //              |       |
+        //              f       g  ---  h  ---  i  ---  j
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let ids = (0..10).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        for (src, dst) in [
+            (0, 1),
+            (1, 2),
+            (2, 3),
+            (3, 4),
+            (1, 5),
+            (2, 6),
+            (6, 7),
+            (7, 8),
+            (8, 9),
+        ] {
+            graph.add_edge_unchecked(ids[src], ids[dst], 1.into());
+        }
+
+        // When:
+        let (length, path) = diameter(&graph).unwrap();
+
+        // Then: Longest paths are from a or e to j.
+        assert_eq!(length, 6);
+        assert_eq!(path.len(), 7);
+        let mut ends = vec![path[0], path[6]];
+        ends.sort_unstable();
+        assert!(ends == vec![ids[0], ids[9]] || ends == vec![ids[4], ids[9]]);
+        for pair in path.windows(2) {
+            assert!(graph.has_any_edge_unchecked(pair[0], pair[1]));
+        }
+    }
+}
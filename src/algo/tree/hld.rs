@@ -0,0 +1,479 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::ops::Add;
+
+use anyhow::Result;
+use num_traits::Zero;
+
+use super::rooted_tree::root_at;
+use crate::graph::{Edge, Error, ErrorKind, UndirectedEdge};
+use crate::provide::{Edges, Graph, Neighbors, Vertices};
+
+/// Heavy-light decomposition of a rooted tree, supporting sum and maximum of edge weights on the path between two vertices.
+///
+/// Each vertex continues the chain of its child with the largest subtree(heavy child), so any path crosses O(log(|V|)) chains.
+/// Vertices of each chain take consecutive positions, and weight of the edge from each vertex to its parent is stored at the position of the vertex
+/// in two segment trees: one for sums and one for maximums.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::tree::HldTree;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  --(2)--  b  --(5)--  c
+/// //                  |
+/// //                 (1)
+/// //                  |
+/// //                  d
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 2.into());
+/// graph.add_edge_unchecked(b, c, 5.into());
+/// graph.add_edge_unchecked(b, d, 1.into());
+///
+/// let mut hld = HldTree::init(&graph, a).unwrap();
+///
+/// assert_eq!(hld.path_sum(c, d).unwrap(), 6);
+/// assert_eq!(hld.path_max(a, d).unwrap(), Some(2));
+///
+/// hld.set_edge_weight(b, d, 7).unwrap();
+/// assert_eq!(hld.path_max(c, d).unwrap(), Some(7));
+/// ```
+pub struct HldTree<W> {
+    // Index of each vertex, which is its order in the rooted tree.
+    index_of: HashMap<usize, usize>,
+    id_of: Vec<usize>,
+
+    parent: Vec<Option<usize>>,
+    depth: Vec<usize>,
+
+    // First vertex of the chain of each vertex.
+    head: Vec<usize>,
+
+    // Position of each vertex in the segment trees.
+    pos: Vec<usize>,
+
+    sums: Vec<W>,
+    maxs: Vec<Option<W>>,
+}
+
+impl<W: Copy + Ord + Zero + Any + Add<Output = W>> HldTree<W> {
+    /// # Arguments
+    /// * `graph`: Tree to decompose.
+    /// * `root_id`: Id of the root.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing the decomposed tree.
+    /// * `Err`:
+    ///     * [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if root does not exist.
+    ///     * [`NotTree`](crate::graph::ErrorKind::NotTree) if graph is not a tree.
+    ///     * [`InvalidWeight`](crate::graph::ErrorKind::InvalidWeight) if weight of an edge is infinite.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|)
+    pub fn init<E, G>(graph: &G, root_id: usize) -> Result<Self>
+    where
+        E: Edge<W>,
+        G: Edges<W, E> + Neighbors + Vertices + Graph<W, E, UndirectedEdge>,
+    {
+        let tree = root_at(graph, root_id)?;
+        let vertex_count = tree.order().len();
+
+        let id_of = tree.order().clone();
+        let index_of = id_of
+            .iter()
+            .enumerate()
+            .map(|(index, vertex_id)| (*vertex_id, index))
+            .collect::<HashMap<usize, usize>>();
+
+        let parent = id_of
+            .iter()
+            .map(|vertex_id| tree.parent_of(*vertex_id).map(|p_id| index_of[&p_id]))
+            .collect::<Vec<Option<usize>>>();
+        let depth = id_of
+            .iter()
+            .map(|vertex_id| tree.depth_of(*vertex_id).unwrap())
+            .collect::<Vec<usize>>();
+        let children_of = id_of
+            .iter()
+            .map(|vertex_id| {
+                tree.children_of(*vertex_id)
+                    .iter()
+                    .map(|child_id| index_of[child_id])
+                    .collect()
+            })
+            .collect::<Vec<Vec<usize>>>();
+
+        // Children come after their parents in the order, so sizes are computed in reverse.
+        let mut size = vec![1; vertex_count];
+        for index in (0..vertex_count).rev() {
+            size[index] += children_of[index]
+                .iter()
+                .map(|child| size[*child])
+                .sum::<usize>();
+        }
+
+        let heavy_child_of = |index: usize| {
+            children_of[index]
+                .iter()
+                .copied()
+                .max_by_key(|child| size[*child])
+        };
+
+        let mut head = vec![0; vertex_count];
+        let mut pos = vec![0; vertex_count];
+        let mut next_pos = 0;
+
+        // Each popped vertex starts a chain, which is followed through heavy children. Light children start new chains.
+        let mut stack = vec![0];
+        while let Some(chain_head) = stack.pop() {
+            let mut current = Some(chain_head);
+            while let Some(index) = current {
+                head[index] = chain_head;
+                pos[index] = next_pos;
+                next_pos += 1;
+
+                let heavy_child = heavy_child_of(index);
+                stack.extend(
+                    children_of[index]
+                        .iter()
+                        .copied()
+                        .filter(|child| Some(*child) != heavy_child),
+                );
+                current = heavy_child;
+            }
+        }
+
+        let mut hld = HldTree {
+            index_of,
+            id_of,
+            parent,
+            depth,
+            head,
+            pos,
+            sums: vec![W::zero(); 2 * vertex_count],
+            maxs: vec![None; 2 * vertex_count],
+        };
+
+        for index in 1..vertex_count {
+            let src_id = hld.id_of[hld.parent[index].unwrap()];
+            let dst_id = hld.id_of[index];
+
+            let edge = graph.edges_between_unchecked(src_id, dst_id)[0];
+            if !edge.get_weight().is_finite() {
+                Err(Error::new_iw(edge.get_id()))?
+            }
+
+            hld.update(hld.pos[index], edge.get_weight().unwrap());
+        }
+
+        Ok(hld)
+    }
+
+    fn update(&mut self, pos: usize, weight: W) {
+        let mut pos = pos + self.id_of.len();
+        self.sums[pos] = weight;
+        self.maxs[pos] = Some(weight);
+
+        while pos > 1 {
+            pos /= 2;
+            self.sums[pos] = self.sums[2 * pos] + self.sums[2 * pos + 1];
+            self.maxs[pos] = self.maxs[2 * pos].max(self.maxs[2 * pos + 1]);
+        }
+    }
+
+    // Sum and maximum of positions in range [`start`, `end`).
+    fn query(&self, start: usize, end: usize) -> (W, Option<W>) {
+        let mut sum = W::zero();
+        let mut max = None;
+
+        let mut start = start + self.id_of.len();
+        let mut end = end + self.id_of.len();
+        while start < end {
+            if start & 1 == 1 {
+                sum = sum + self.sums[start];
+                max = max.max(self.maxs[start]);
+                start += 1;
+            }
+            if end & 1 == 1 {
+                end -= 1;
+                sum = sum + self.sums[end];
+                max = max.max(self.maxs[end]);
+            }
+
+            start /= 2;
+            end /= 2;
+        }
+
+        (sum, max)
+    }
+
+    fn index_of(&self, vertex_id: usize) -> Result<usize> {
+        match self.index_of.get(&vertex_id) {
+            Some(index) => Ok(*index),
+            None => Err(Error::new_vnf(vertex_id))?,
+        }
+    }
+
+    // Lowest common ancestor of `u` and `v`, along with sum and maximum of edge weights on the path between them.
+    fn path(&self, u_id: usize, v_id: usize) -> Result<(usize, W, Option<W>)> {
+        let mut u = self.index_of(u_id)?;
+        let mut v = self.index_of(v_id)?;
+
+        let mut sum = W::zero();
+        let mut max = None;
+
+        while self.head[u] != self.head[v] {
+            if self.depth[self.head[u]] < self.depth[self.head[v]] {
+                std::mem::swap(&mut u, &mut v);
+            }
+
+            let (chain_sum, chain_max) = self.query(self.pos[self.head[u]], self.pos[u] + 1);
+            sum = sum + chain_sum;
+            max = max.max(chain_max);
+
+            u = self.parent[self.head[u]].unwrap();
+        }
+
+        if self.depth[u] > self.depth[v] {
+            std::mem::swap(&mut u, &mut v);
+        }
+
+        // Edge from `u`(the common ancestor) to its parent is not on the path.
+        let (chain_sum, chain_max) = self.query(self.pos[u] + 1, self.pos[v] + 1);
+
+        Ok((self.id_of[u], sum + chain_sum, max.max(chain_max)))
+    }
+
+    /// # Arguments
+    /// * `u_id`: Id of the first vertex.
+    /// * `v_id`: Id of the second vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing id of the lowest common ancestor of the two vertices.
+    /// * `Err`: [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if either of the vertices does not exist.
+    ///
+    /// # Complexity
+    /// O(log(|V|)^2)
+    pub fn lca(&self, u_id: usize, v_id: usize) -> Result<usize> {
+        Ok(self.path(u_id, v_id)?.0)
+    }
+
+    /// # Arguments
+    /// * `u_id`: Id of the first vertex.
+    /// * `v_id`: Id of the second vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing sum of weights of the edges on the path between the two vertices.
+    /// * `Err`: [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if either of the vertices does not exist.
+    ///
+    /// # Complexity
+    /// O(log(|V|)^2)
+    pub fn path_sum(&self, u_id: usize, v_id: usize) -> Result<W> {
+        Ok(self.path(u_id, v_id)?.1)
+    }
+
+    /// # Arguments
+    /// * `u_id`: Id of the first vertex.
+    /// * `v_id`: Id of the second vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing maximum weight of the edges on the path between the two vertices, or `None` if the vertices are the same.
+    /// * `Err`: [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if either of the vertices does not exist.
+    ///
+    /// # Complexity
+    /// O(log(|V|)^2)
+    pub fn path_max(&self, u_id: usize, v_id: usize) -> Result<Option<W>> {
+        Ok(self.path(u_id, v_id)?.2)
+    }
+
+    /// Changes weight of the edge between two vertices. Graph that the tree was built from is not changed.
+    ///
+    /// # Arguments
+    /// * `u_id`: Id of one end point of the edge.
+    /// * `v_id`: Id of the other end point of the edge.
+    /// * `weight`: New weight of the edge.
+    ///
+    /// # Returns
+    /// * `Ok`: If weight is updated.
+    /// * `Err`:
+    ///     * [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if either of the vertices does not exist.
+    ///     * [`EdgeNotFound`](crate::graph::ErrorKind::EdgeNotFound) if there is no edge between the vertices.
+    ///
+    /// # Complexity
+    /// O(log(|V|))
+    pub fn set_edge_weight(&mut self, u_id: usize, v_id: usize, weight: W) -> Result<()> {
+        let u = self.index_of(u_id)?;
+        let v = self.index_of(v_id)?;
+
+        let child = if self.parent[v] == Some(u) {
+            v
+        } else if self.parent[u] == Some(v) {
+            u
+        } else {
+            Err(Error::new(
+                ErrorKind::EdgeNotFound,
+                format!("There is no edge between {} and {}", u_id, v_id),
+            ))?
+        };
+
+        self.update(self.pos[child], weight);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::storage::Mat;
+
+    // Sum and maximum of weights on the path between `u` and `v`, found by walking up the parents.
+    fn naive(
+        parent: &[Option<usize>],
+        weights: &[usize],
+        u: usize,
+        v: usize,
+    ) -> (usize, Option<usize>) {
+        let ancestors_of = |mut x: usize| {
+            let mut ancestors = vec![x];
+            while let Some(p) = parent[x] {
+                ancestors.push(p);
+                x = p;
+            }
+            ancestors
+        };
+
+        let u_ancestors = ancestors_of(u);
+        let v_ancestors = ancestors_of(v);
+        let lca = *u_ancestors
+            .iter()
+            .find(|x| v_ancestors.contains(x))
+            .unwrap();
+
+        let on_path = u_ancestors
+            .iter()
+            .take_while(|x| **x != lca)
+            .chain(v_ancestors.iter().take_while(|x| **x != lca))
+            .map(|x| weights[*x])
+            .collect::<Vec<usize>>();
+
+        (on_path.iter().sum(), on_path.iter().max().copied())
+    }
+
+    #[test]
+    fn invalid_input() {
+        // Given: Graph
+        //
+        //      a  --(1)--  b       c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+
+        // Then: Graph is not a tree.
+        assert!(HldTree::init(&graph, a).is_err());
+
+        // When: Connecting c.
+        graph.add_edge_unchecked(b, c, 1.into());
+        let mut hld = HldTree::init(&graph, a).unwrap();
+
+        // Then:
+        assert!(hld.path_sum(a, c + 1).is_err());
+        assert!(hld.set_edge_weight(a, c, 3).is_err());
+        assert_eq!(hld.path_max(b, b).unwrap(), None);
+    }
+
+    #[test]
+    fn random_tree() {
+        // Given: Tree with 60 vertices, where parent of vertex i is a pseudo random vertex before it.
+        let vertex_count = 60;
+        let mut parent = vec![None; vertex_count];
+        let mut weights = vec![0; vertex_count];
+        let mut state = 7usize;
+        let mut next = |n: usize| {
+            state = (state * 1103515245 + 12345) % (1 << 31);
+            state % n
+        };
+
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let ids = (0..vertex_count)
+            .map(|_| graph.add_vertex())
+            .collect::<Vec<usize>>();
+        for i in 1..vertex_count {
+            // Favor recent vertices so that the tree has long paths.
+            let p = i - 1 - next(i.min(4));
+            parent[i] = Some(p);
+            weights[i] = next(100);
+            graph.add_edge_unchecked(ids[p], ids[i], weights[i].into());
+        }
+
+        // When: Decomposing the tree rooted at the first vertex.
+        let mut hld = HldTree::init(&graph, ids[0]).unwrap();
+
+        // Then: Queries match walking up the parents.
+        for _ in 0..200 {
+            let (u, v) = (next(vertex_count), next(vertex_count));
+            let (sum, max) = naive(&parent, &weights, u, v);
+
+            assert_eq!(hld.path_sum(ids[u], ids[v]).unwrap(), sum);
+            assert_eq!(hld.path_max(ids[u], ids[v]).unwrap(), max);
+        }
+
+        // And: After updating weights.
+        for _ in 0..50 {
+            let child = 1 + next(vertex_count - 1);
+            weights[child] = next(1000);
+            hld.set_edge_weight(ids[parent[child].unwrap()], ids[child], weights[child])
+                .unwrap();
+
+            let (u, v) = (next(vertex_count), next(vertex_count));
+            let (sum, max) = naive(&parent, &weights, u, v);
+
+            assert_eq!(hld.path_sum(ids[u], ids[v]).unwrap(), sum);
+            assert_eq!(hld.path_max(ids[u], ids[v]).unwrap(), max);
+        }
+    }
+
+    #[test]
+    fn lca() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c  ---  d
+        //              |
+        //              e  ---  f
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+        graph.add_edge_unchecked(b, e, 1.into());
+        graph.add_edge_unchecked(e, f, 1.into());
+
+        // When: Rooting at a.
+        let hld = HldTree::init(&graph, a).unwrap();
+
+        // Then:
+        assert_eq!(hld.lca(d, f).unwrap(), b);
+        assert_eq!(hld.lca(c, d).unwrap(), c);
+        assert_eq!(hld.lca(f, a).unwrap(), a);
+        assert_eq!(hld.path_sum(d, f).unwrap(), 4);
+    }
+}
@@ -0,0 +1,11 @@
+mod centroid;
+mod diameter;
+mod hld;
+mod recognition;
+mod rooted_tree;
+
+pub use centroid::centroids;
+pub use diameter::diameter;
+pub use hld::HldTree;
+pub use recognition::{is_forest, is_tree};
+pub use rooted_tree::{root_at, RootedTree};
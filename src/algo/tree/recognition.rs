@@ -0,0 +1,195 @@
+use anyhow::Result;
+
+use crate::graph::{Edge, Error, UndirectedEdge};
+use crate::provide::{Edges, Graph, Vertices};
+
+// Number of trees of the graph if it's a forest, None otherwise.
+fn forest_components<W, E, G>(graph: &G) -> Option<usize>
+where
+    E: Edge<W>,
+    G: Edges<W, E> + Vertices + Graph<W, E, UndirectedEdge>,
+{
+    let id_map = graph.continuous_id_map();
+    let vertex_count = graph.vertex_count();
+
+    let mut is_visited = vec![false; vertex_count];
+    let mut components = 0;
+
+    for root in 0..vertex_count {
+        if is_visited[root] {
+            continue;
+        }
+        components += 1;
+
+        is_visited[root] = true;
+        let mut stack = vec![(root, None)];
+
+        while let Some((virt_id, parent_edge_id)) = stack.pop() {
+            for (n_id, edge) in graph.edges_from_unchecked(id_map.real_id_of(virt_id)) {
+                if Some(edge.get_id()) == parent_edge_id {
+                    continue;
+                }
+
+                let n_virt_id = id_map.virt_id_of(n_id);
+                if is_visited[n_virt_id] {
+                    return None;
+                }
+
+                is_visited[n_virt_id] = true;
+                stack.push((n_virt_id, Some(edge.get_id())));
+            }
+        }
+    }
+
+    Some(components)
+}
+
+pub(super) fn ensure_tree<W, E, G>(graph: &G) -> Result<()>
+where
+    E: Edge<W>,
+    G: Edges<W, E> + Vertices + Graph<W, E, UndirectedEdge>,
+{
+    if !is_tree(graph) {
+        Err(Error::new_nt())?
+    }
+
+    Ok(())
+}
+
+/// # Arguments
+/// `graph`: Graph to check.
+///
+/// # Returns
+/// `true` if graph has no cycle, `false` otherwise. Loops and multiple edges between two vertices are cycles.
+///
+/// # Complexity
+/// O(|V| + |E|)
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::tree::is_forest;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b       c
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+///
+/// assert!(is_forest(&graph));
+/// ```
+pub fn is_forest<W, E, G>(graph: &G) -> bool
+where
+    E: Edge<W>,
+    G: Edges<W, E> + Vertices + Graph<W, E, UndirectedEdge>,
+{
+    forest_components(graph).is_some()
+}
+
+/// # Arguments
+/// `graph`: Graph to check.
+///
+/// # Returns
+/// `true` if graph is connected and has no cycle, `false` otherwise. Empty graph is not a tree.
+///
+/// # Complexity
+/// O(|V| + |E|)
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::tree::is_tree;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b       c
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+///
+/// assert!(!is_tree(&graph));
+///
+/// graph.add_edge_unchecked(b, c, 1.into());
+///
+/// assert!(is_tree(&graph));
+/// ```
+pub fn is_tree<W, E, G>(graph: &G) -> bool
+where
+    E: Edge<W>,
+    G: Edges<W, E> + Vertices + Graph<W, E, UndirectedEdge>,
+{
+    forest_components(graph) == Some(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ListGraph, MatGraph};
+    use crate::storage::{List, Mat};
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        assert!(is_forest(&graph));
+        assert!(!is_tree(&graph));
+        assert!(ensure_tree(&graph).is_err());
+    }
+
+    #[test]
+    fn cycles() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c       d
+        //
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // Then:
+        assert!(is_forest(&graph));
+        assert!(!is_tree(&graph));
+
+        // When: Connecting d.
+        let cd = graph.add_edge_unchecked(c, d, 1.into());
+
+        // Then:
+        assert!(is_tree(&graph));
+        assert!(ensure_tree(&graph).is_ok());
+
+        // When: Adding a second edge between a and b.
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+
+        // Then:
+        assert!(!is_forest(&graph));
+
+        // When: Replacing it with a loop.
+        graph.remove_edge_unchecked(a, b, ab);
+        graph.add_edge_unchecked(d, d, 1.into());
+
+        // Then:
+        assert!(!is_forest(&graph));
+
+        // When: Replacing the loop with an edge that closes a --- b --- c --- a.
+        graph.remove_edge_unchecked(c, d, cd);
+        graph.add_edge_unchecked(c, a, 1.into());
+
+        // Then:
+        assert!(!is_forest(&graph));
+    }
+}
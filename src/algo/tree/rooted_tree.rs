@@ -0,0 +1,232 @@
+use std::collections::{HashMap, VecDeque};
+
+use anyhow::Result;
+
+use super::recognition::ensure_tree;
+use crate::graph::{Edge, Error, UndirectedEdge};
+use crate::provide::{Edges, Graph, Neighbors, Vertices};
+
+/// A free tree rooted at one of its vertices.
+pub struct RootedTree {
+    root: usize,
+    parent_of: HashMap<usize, Option<usize>>,
+    children_of: HashMap<usize, Vec<usize>>,
+    depth_of: HashMap<usize, usize>,
+    order: Vec<usize>,
+}
+
+impl RootedTree {
+    /// # Returns
+    /// Id of the root.
+    pub fn root(&self) -> usize {
+        self.root
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing id of the parent of the vertex.
+    /// * `None`: If vertex is the root or it does not exist.
+    pub fn parent_of(&self, vertex_id: usize) -> Option<usize> {
+        self.parent_of.get(&vertex_id).copied().flatten()
+    }
+
+    /// # Returns
+    /// Parent of each vertex, which is `None` for the root.
+    pub fn parents(&self) -> &HashMap<usize, Option<usize>> {
+        &self.parent_of
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Ids of the children of the vertex. It's empty for leaves and vertices that do not exist.
+    pub fn children_of(&self, vertex_id: usize) -> &[usize] {
+        self.children_of
+            .get(&vertex_id)
+            .map(|children| children.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing number of edges between the root and the vertex.
+    /// * `None`: If vertex does not exist.
+    pub fn depth_of(&self, vertex_id: usize) -> Option<usize> {
+        self.depth_of.get(&vertex_id).copied()
+    }
+
+    /// # Returns
+    /// Ids of the vertices in breadth first order: each vertex comes after its parent.
+    /// Iterating it in reverse visits each vertex before its parent, which is useful for computing values bottom up.
+    pub fn order(&self) -> &Vec<usize> {
+        &self.order
+    }
+}
+
+/// Roots a free tree at the given vertex.
+///
+/// # Arguments
+/// * `graph`: Tree to root.
+/// * `root_id`: Id of the vertex to become the root.
+///
+/// # Returns
+/// * `Ok`: Containing the rooted tree.
+/// * `Err`:
+///     * [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if root does not exist.
+///     * [`NotTree`](crate::graph::ErrorKind::NotTree) if graph is not a tree.
+///
+/// # Complexity
+/// O(|V| + |E|)
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::tree::root_at;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b  ---  c
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+///
+/// let tree = root_at(&graph, b).unwrap();
+///
+/// assert_eq!(tree.parent_of(a), Some(b));
+/// assert_eq!(tree.parent_of(b), None);
+/// assert_eq!(tree.depth_of(c), Some(1));
+/// ```
+pub fn root_at<W, E, G>(graph: &G, root_id: usize) -> Result<RootedTree>
+where
+    E: Edge<W>,
+    G: Edges<W, E> + Neighbors + Vertices + Graph<W, E, UndirectedEdge>,
+{
+    if !graph.contains_vertex(root_id) {
+        Err(Error::new_vnf(root_id))?
+    }
+
+    ensure_tree(graph)?;
+
+    let mut parent_of = HashMap::new();
+    let mut children_of = HashMap::new();
+    let mut depth_of = HashMap::new();
+    let mut order = vec![];
+
+    parent_of.insert(root_id, None);
+    depth_of.insert(root_id, 0);
+
+    let mut queue = VecDeque::new();
+    queue.push_back(root_id);
+
+    while let Some(vertex_id) = queue.pop_front() {
+        order.push(vertex_id);
+
+        let children = graph
+            .neighbors_unchecked(vertex_id)
+            .into_iter()
+            .filter(|n_id| !parent_of.contains_key(n_id))
+            .collect::<Vec<usize>>();
+
+        for child_id in &children {
+            parent_of.insert(*child_id, Some(vertex_id));
+            depth_of.insert(*child_id, depth_of[&vertex_id] + 1);
+            queue.push_back(*child_id);
+        }
+
+        children_of.insert(vertex_id, children);
+    }
+
+    Ok(RootedTree {
+        root: root_id,
+        parent_of,
+        children_of,
+        depth_of,
+        order,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::storage::Mat;
+
+    #[test]
+    fn invalid_input() {
+        // Given: Graph
+        //
+        //      a  ---  b       c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+
+        // Then: Graph is not connected.
+        assert!(root_at(&graph, a).is_err());
+
+        // Then: Root does not exist.
+        graph.add_edge_unchecked(b, c, 1.into());
+        assert!(root_at(&graph, c + 1).is_err());
+    }
+
+    #[test]
+    fn rooted_tree() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c  ---  d
+        //              |
+        //              e  ---  f
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+        graph.add_edge_unchecked(b, e, 1.into());
+        graph.add_edge_unchecked(e, f, 1.into());
+
+        // When: Rooting the tree at c.
+        let tree = root_at(&graph, c).unwrap();
+
+        // Then:
+        assert_eq!(tree.root(), c);
+        assert_eq!(tree.parents().len(), 6);
+        assert_eq!(tree.parent_of(c), None);
+        assert_eq!(tree.parent_of(b), Some(c));
+        assert_eq!(tree.parent_of(f), Some(e));
+        assert_eq!(tree.depth_of(f), Some(3));
+        assert_eq!(tree.depth_of(f + 1), None);
+
+        let mut children = tree.children_of(b).to_vec();
+        children.sort_unstable();
+        assert_eq!(children, vec![a, e]);
+        assert!(tree.children_of(d).is_empty());
+
+        // And: Each vertex comes after its parent.
+        let order = tree.order();
+        assert_eq!(order.len(), 6);
+        assert_eq!(order[0], c);
+        for (index, vertex_id) in order.iter().enumerate().skip(1) {
+            let parent_id = tree.parent_of(*vertex_id).unwrap();
+            assert!(order[..index].contains(&parent_id));
+        }
+    }
+}
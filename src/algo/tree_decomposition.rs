@@ -0,0 +1,303 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::algo::metrics::adjacency;
+use crate::algo::{EliminationHeuristic, EliminationOrdering};
+use crate::graph::{MatGraph, UndirectedEdge};
+use crate::provide::{Graph, IdMap, Neighbors, Vertices};
+use crate::storage::Mat;
+
+/// Result of a [`TreeDecomposition`](crate::algo::TreeDecomposition).
+pub struct Decomposition {
+    tree: MatGraph<usize, UndirectedEdge>,
+    bag_of: HashMap<usize, Vec<usize>>,
+}
+
+impl Decomposition {
+    /// # Returns
+    /// The decomposition tree. Weight of each edge is the number of vertices that its two bags share.
+    pub fn tree(&self) -> &MatGraph<usize, UndirectedEdge> {
+        &self.tree
+    }
+
+    /// # Returns
+    /// Vertices of each bag in ascending order, keyed by id of the bag in the decomposition tree.
+    pub fn bags(&self) -> &HashMap<usize, Vec<usize>> {
+        &self.bag_of
+    }
+
+    /// # Arguments
+    /// `bag_id`: Id of the bag in the decomposition tree.
+    ///
+    /// # Returns
+    /// * `Some`: Containing vertices of the bag in ascending order.
+    /// * `None`: If bag does not exist.
+    pub fn bag_of(&self, bag_id: usize) -> Option<&Vec<usize>> {
+        self.bag_of.get(&bag_id)
+    }
+
+    /// # Returns
+    /// Size of the largest bag minus one. Zero if graph has no vertices.
+    pub fn width(&self) -> usize {
+        self.bag_of
+            .values()
+            .map(|bag| bag.len().saturating_sub(1))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// # Returns
+    /// The decomposition tree and the bags, consuming the decomposition.
+    pub fn into_parts(self) -> (MatGraph<usize, UndirectedEdge>, HashMap<usize, Vec<usize>>) {
+        (self.tree, self.bag_of)
+    }
+}
+
+/// Builds a tree decomposition of a graph from an elimination ordering.
+///
+/// There is a bag for each vertex, containing the vertex and its neighbors at the time it's eliminated. Bag of each vertex is connected to the bag of
+/// its neighbor that is eliminated first. Bags of different connected components share no vertices, and they are connected to each other with edges of weight zero,
+/// so the decomposition is always a single tree. Width of the decomposition is the [`treewidth_bound`](crate::algo::EliminationOrder::treewidth_bound) of the ordering.
+///
+/// Direction of the edges and loops are ignored.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::{EliminationHeuristic, TreeDecomposition};
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b  ---  c
+/// //      |     /
+/// //      |   /
+/// //      | /
+/// //      d
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(a, d, 1.into());
+/// graph.add_edge_unchecked(b, d, 1.into());
+///
+/// let decomposition = TreeDecomposition::init(&graph).execute(&graph, EliminationHeuristic::MinDegree);
+///
+/// assert_eq!(decomposition.width(), 2);
+/// assert_eq!(decomposition.tree().vertex_count(), 4);
+/// assert_eq!(decomposition.tree().edges_count(), 3);
+/// assert!(decomposition.bags().values().any(|bag| bag == &vec![a, b, d]));
+/// ```
+pub struct TreeDecomposition {
+    id_map: IdMap,
+
+    // Distinct vertices adjacent to each vertex, using virtual ids.
+    adjacent_to: Vec<HashSet<usize>>,
+
+    ordering: EliminationOrdering,
+}
+
+impl TreeDecomposition {
+    pub fn init<G: Neighbors + Vertices>(graph: &G) -> Self {
+        let (id_map, adjacent_to) = adjacency(graph);
+
+        TreeDecomposition {
+            id_map,
+            adjacent_to,
+            ordering: EliminationOrdering::init(graph),
+        }
+    }
+
+    /// # Arguments
+    /// * `graph`: Graph to decompose.
+    /// * `heuristic`: Heuristic used to order the vertices.
+    ///
+    /// # Returns
+    /// The decomposition tree and bag of each of its vertices.
+    ///
+    /// # Complexity
+    /// Complexity of the [`EliminationOrdering`](crate::algo::EliminationOrdering) plus O(|V|^2 + |V| * Δ^2), where Δ is the largest number of neighbors of a vertex after fill-in.
+    pub fn execute<G: Neighbors + Vertices>(
+        &self,
+        graph: &G,
+        heuristic: EliminationHeuristic,
+    ) -> Decomposition {
+        let order = self
+            .ordering
+            .execute(graph, heuristic)
+            .order()
+            .iter()
+            .map(|vertex_id| self.id_map.virt_id_of(*vertex_id))
+            .collect::<Vec<usize>>();
+
+        let mut position_of = vec![0; order.len()];
+        for (position, virt_id) in order.iter().enumerate() {
+            position_of[*virt_id] = position;
+        }
+
+        let mut tree = MatGraph::init(Mat::init());
+        let tree_id_of = order
+            .iter()
+            .map(|_| tree.add_vertex())
+            .collect::<Vec<usize>>();
+
+        let mut adjacent_to = self.adjacent_to.clone();
+        let mut bag_of = HashMap::new();
+        let mut last_root = None;
+        for (position, virt_id) in order.iter().enumerate() {
+            let neighbors = adjacent_to[*virt_id]
+                .iter()
+                .copied()
+                .collect::<Vec<usize>>();
+
+            let tree_id = tree_id_of[position];
+            match neighbors.iter().min_by_key(|n_id| position_of[**n_id]) {
+                Some(parent_id) => {
+                    let parent_tree_id = tree_id_of[position_of[*parent_id]];
+                    tree.add_edge_unchecked(tree_id, parent_tree_id, neighbors.len().into());
+                }
+                None => {
+                    if let Some(root_tree_id) = last_root {
+                        tree.add_edge_unchecked(tree_id, root_tree_id, 0.into());
+                    }
+                    last_root = Some(tree_id);
+                }
+            }
+
+            let mut bag = neighbors
+                .iter()
+                .chain(std::iter::once(virt_id))
+                .map(|virt_id| self.id_map.real_id_of(*virt_id))
+                .collect::<Vec<usize>>();
+            bag.sort_unstable();
+            bag_of.insert(tree_id, bag);
+
+            EliminationOrdering::eliminate(&mut adjacent_to, *virt_id);
+        }
+
+        Decomposition { tree, bag_of }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Edge;
+    use crate::provide::Edges;
+
+    const HEURISTICS: [EliminationHeuristic; 3] = [
+        EliminationHeuristic::MaximumCardinalitySearch,
+        EliminationHeuristic::MinDegree,
+        EliminationHeuristic::MinFill,
+    ];
+
+    // Checks that every vertex is in a bag, end points of every edge share a bag, bags containing each vertex form a connected subtree,
+    // and decomposition is a tree.
+    fn assert_valid<G: Neighbors + Vertices>(graph: &G, decomposition: &Decomposition) {
+        let tree = decomposition.tree();
+        assert_eq!(tree.edges_count() + 1, tree.vertex_count().max(1));
+
+        for vertex_id in graph.vertices() {
+            let bags = decomposition
+                .bags()
+                .iter()
+                .filter(|(_, bag)| bag.contains(&vertex_id))
+                .map(|(bag_id, _)| *bag_id)
+                .collect::<HashSet<usize>>();
+            assert!(!bags.is_empty());
+
+            let edges_inside = tree
+                .edges()
+                .into_iter()
+                .filter(|(src_id, dst_id, _)| bags.contains(src_id) && bags.contains(dst_id))
+                .count();
+            assert_eq!(edges_inside + 1, bags.len());
+
+            for n_id in graph.neighbors_unchecked(vertex_id) {
+                assert!(decomposition
+                    .bags()
+                    .values()
+                    .any(|bag| bag.contains(&vertex_id) && bag.contains(&n_id)));
+            }
+        }
+    }
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let decomposition =
+            TreeDecomposition::init(&graph).execute(&graph, EliminationHeuristic::MinFill);
+
+        assert_eq!(decomposition.tree().vertex_count(), 0);
+        assert_eq!(decomposition.width(), 0);
+    }
+
+    #[test]
+    fn disconnected_graph() {
+        // Given: Graph
+        //
+        //      a  ---  b       c       d  ---  e
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(d, e, 1.into());
+
+        for heuristic in HEURISTICS.iter() {
+            // When:
+            let decomposition = TreeDecomposition::init(&graph).execute(&graph, *heuristic);
+
+            // Then: Components are connected to each other with edges of weight zero.
+            assert_valid(&graph, &decomposition);
+            assert_eq!(decomposition.width(), 1);
+            let zero_edges = decomposition
+                .tree()
+                .edges()
+                .into_iter()
+                .filter(|(_, _, edge)| edge.get_weight().unwrap() == 0)
+                .count();
+            assert_eq!(zero_edges, 2);
+        }
+    }
+
+    #[test]
+    fn wheel() {
+        // Given: Wheel with a hub connected to a cycle of length 5.
+        //
+        //          b
+        //        / | \
+        //      a - h - c
+        //      |  / \  |
+        //      e ----- d
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let hub = graph.add_vertex();
+        let rim = (0..5).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        for index in 0..5 {
+            graph.add_edge_unchecked(hub, rim[index], 1.into());
+            graph.add_edge_unchecked(rim[index], rim[(index + 1) % 5], 1.into());
+        }
+
+        for heuristic in HEURISTICS.iter() {
+            // When:
+            let decomposition = TreeDecomposition::init(&graph).execute(&graph, *heuristic);
+
+            // Then: Treewidth of a wheel is 3.
+            assert_valid(&graph, &decomposition);
+            assert!(decomposition.width() >= 3);
+        }
+
+        let decomposition =
+            TreeDecomposition::init(&graph).execute(&graph, EliminationHeuristic::MinFill);
+        assert_eq!(decomposition.width(), 3);
+    }
+}
@@ -0,0 +1,384 @@
+use num_traits::Float;
+
+use crate::algo::anytime::BudgetClock;
+use crate::algo::{AnytimeSolution, Budget, DistanceMatrix};
+use crate::graph::Edge;
+use crate::provide::{Edges, Vertices};
+
+/// Finds the shortest tour that visits every vertex of a graph exactly once and returns to the first vertex(traveling salesman problem), using branch and bound.
+///
+/// Tours can only use edges of the graph, so the graph does not need to be complete. Edges with positive infinite weight are ignored.
+/// In directed graphs, each edge of the tour must be traversed in its direction.
+/// Tours are built by extending a path from the first vertex, trying the closest vertices first. So the first tour found is the nearest neighbor tour, if there is one.
+/// A path is pruned when its length plus the lightest edge leaving each vertex that is not left yet can not beat the shortest tour found so far.
+///
+/// Running time is exponential in the worst case. Use [`execute_within`](crate::algo::Tsp::execute_within) to get the shortest tour found within a [`Budget`](crate::algo::Budget).
+///
+/// ## Generic Parameters
+/// * `F`: **F**loating point type of the weights: `f32` or `f64`.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::{Budget, Tsp};
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //          1.0
+/// //      a  -----  b
+/// //      |  \   /  |
+/// //  1.0 |   \ /   | 1.0
+/// //      |   / \   |
+/// //      |  /   \  |
+/// //      d  -----  c
+/// //          1.0
+/// // Diagonals have weight 5.0.
+/// let mut graph = MatGraph::init(Mat::<f64>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.0.into());
+/// graph.add_edge_unchecked(b, c, 1.0.into());
+/// graph.add_edge_unchecked(c, d, 1.0.into());
+/// graph.add_edge_unchecked(d, a, 1.0.into());
+/// graph.add_edge_unchecked(a, c, 5.0.into());
+/// graph.add_edge_unchecked(b, d, 5.0.into());
+///
+/// let tour = Tsp::init(&graph).execute_within(&graph, Budget::init().with_iteration_limit(1000));
+///
+/// assert_eq!(tour.value(), 4.0);
+/// assert!(tour.is_optimal());
+/// assert_eq!(tour.solution()[0], a);
+/// ```
+pub struct Tsp<F> {
+    distances: DistanceMatrix<F>,
+
+    // Weight of the lightest edge from each vertex to another vertex, using virtual ids.
+    min_edge_of: Vec<F>,
+}
+
+impl<F: Float> Tsp<F> {
+    pub fn init<E, G>(graph: &G) -> Self
+    where
+        E: Edge<F>,
+        G: Edges<F, E> + Vertices,
+    {
+        let distances = DistanceMatrix::from_graph(graph);
+
+        let size = distances.size();
+        let min_edge_of = distances
+            .entries()
+            .chunks(size.max(1))
+            .enumerate()
+            .map(|(virt_id, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(dst_virt_id, _)| *dst_virt_id != virt_id)
+                    .fold(F::infinity(), |min, (_, weight)| min.min(*weight))
+            })
+            .collect();
+
+        Tsp {
+            distances,
+            min_edge_of,
+        }
+    }
+
+    fn distance(&self, src_virt_id: usize, dst_virt_id: usize) -> F {
+        self.distances.entries()[src_virt_id * self.distances.size() + dst_virt_id]
+    }
+
+    // `tour` is a path from the first vertex with the given `length`, and `visited` marks its vertices.
+    // If budget gets exhausted, the smallest bound of the paths that are not explored is kept in `unexplored_bound`.
+    fn branch(
+        &self,
+        tour: &mut Vec<usize>,
+        visited: &mut [bool],
+        length: F,
+        best: &mut (Vec<usize>, F),
+        clock: &mut BudgetClock,
+        unexplored_bound: &mut F,
+    ) {
+        let last = *tour.last().unwrap();
+
+        if tour.len() == visited.len() {
+            let total = length + self.distance(last, tour[0]);
+            if total < best.1 {
+                *best = (tour.clone(), total);
+            }
+
+            return;
+        }
+
+        // The last vertex and each vertex that is not visited yet must be left through one of their edges.
+        let bound = (0..visited.len())
+            .filter(|virt_id| !visited[*virt_id])
+            .fold(length + self.min_edge_of[last], |sum, virt_id| {
+                sum + self.min_edge_of[virt_id]
+            });
+        if bound >= best.1 {
+            return;
+        }
+
+        if !clock.tick() {
+            *unexplored_bound = unexplored_bound.min(bound);
+            return;
+        }
+
+        let mut next_ids = (0..visited.len())
+            .filter(|virt_id| !visited[*virt_id])
+            .map(|virt_id| (self.distance(last, virt_id), virt_id))
+            .filter(|(weight, _)| weight.is_finite())
+            .collect::<Vec<(F, usize)>>();
+        next_ids.sort_by(|(w1, _), (w2, _)| w1.partial_cmp(w2).unwrap());
+
+        for (weight, virt_id) in next_ids {
+            visited[virt_id] = true;
+            tour.push(virt_id);
+
+            self.branch(
+                tour,
+                visited,
+                length + weight,
+                best,
+                clock,
+                unexplored_bound,
+            );
+
+            tour.pop();
+            visited[virt_id] = false;
+        }
+    }
+
+    /// # Arguments
+    /// `graph`: Graph that the algorithm is initialized with.
+    ///
+    /// # Returns
+    /// * `Some`: Containing ids of the vertices in the order of the shortest tour, starting from the first vertex of the graph, and length of the tour.
+    /// * `None`: If there is no tour.
+    ///
+    /// # Complexity
+    /// O(|V|! * |V|) in the worst case.
+    pub fn execute<E, G>(self, graph: &G) -> Option<(Vec<usize>, F)>
+    where
+        E: Edge<F>,
+        G: Edges<F, E> + Vertices,
+    {
+        let tour = self.execute_within(graph, Budget::init());
+
+        if tour.value().is_finite() {
+            let length = tour.value();
+
+            Some((tour.into_solution(), length))
+        } else {
+            None
+        }
+    }
+
+    /// Each node of the search tree uses one iteration of `budget`.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph that the algorithm is initialized with.
+    /// * `budget`: Limits the time and iterations of the search.
+    ///
+    /// # Returns
+    /// Ids of the vertices in the order of the shortest tour found, starting from the first vertex of the graph, and length of the tour.
+    /// If no tour is found, the tour is empty and its length is infinity. Tour of an empty graph is empty and has zero length.
+    /// Bound of the solution is a lower bound on the length of any tour.
+    ///
+    /// # Complexity
+    /// O(|V|! * |V|) in the worst case.
+    pub fn execute_within<E, G>(self, _: &G, budget: Budget) -> AnytimeSolution<Vec<usize>, F>
+    where
+        E: Edge<F>,
+        G: Edges<F, E> + Vertices,
+    {
+        let size = self.distances.size();
+        if size == 0 {
+            return AnytimeSolution::init(vec![], F::zero(), F::zero());
+        }
+
+        let mut clock = budget.start();
+        let mut best = (vec![], F::infinity());
+        let mut unexplored_bound = F::infinity();
+
+        let mut visited = vec![false; size];
+        visited[0] = true;
+        self.branch(
+            &mut vec![0],
+            &mut visited,
+            F::zero(),
+            &mut best,
+            &mut clock,
+            &mut unexplored_bound,
+        );
+
+        let (tour, length) = best;
+        let tour = tour
+            .into_iter()
+            .map(|virt_id| self.distances.id_map().real_id_of(virt_id))
+            .collect();
+
+        AnytimeSolution::init(tour, length, length.min(unexplored_bound))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ListGraph, MatGraph};
+    use crate::provide::*;
+    use crate::storage::{DiList, Mat};
+    use crate::util::seeded;
+
+    // Length of the shortest tour by trying all orders of the vertices after the first one.
+    fn brute_force(weights: &[Vec<f64>]) -> f64 {
+        fn shortest(weights: &[Vec<f64>], tour: &mut Vec<usize>) -> f64 {
+            let size = weights.len();
+            if tour.len() == size {
+                let length: f64 = tour.windows(2).map(|pair| weights[pair[0]][pair[1]]).sum();
+
+                return length + weights[tour[size - 1]][tour[0]];
+            }
+
+            let mut best = f64::INFINITY;
+            for virt_id in 0..size {
+                if !tour.contains(&virt_id) {
+                    tour.push(virt_id);
+                    best = best.min(shortest(weights, tour));
+                    tour.pop();
+                }
+            }
+
+            best
+        }
+
+        shortest(weights, &mut vec![0])
+    }
+
+    fn tour_length(weights: &[Vec<f64>], ids: &[usize], tour: &[usize]) -> f64 {
+        let virt_id_of = |id: usize| ids.iter().position(|other_id| *other_id == id).unwrap();
+
+        (0..tour.len())
+            .map(|index| {
+                let src = virt_id_of(tour[index]);
+                let dst = virt_id_of(tour[(index + 1) % tour.len()]);
+
+                weights[src][dst]
+            })
+            .sum()
+    }
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<f64>::init());
+
+        let tour = Tsp::init(&graph).execute_within(&graph, Budget::init());
+
+        assert!(tour.solution().is_empty());
+        assert_eq!(tour.value(), 0.0);
+        assert!(tour.is_optimal());
+    }
+
+    #[test]
+    fn no_tour() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c
+        //
+        let mut graph = ListGraph::init(DiList::<f64>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.0.into());
+        graph.add_edge_unchecked(b, c, 1.0.into());
+
+        // When:
+        let tour = Tsp::init(&graph).execute(&graph);
+
+        // Then:
+        assert!(tour.is_none());
+    }
+
+    #[test]
+    fn directed_cycle() {
+        // Given: Graph
+        //          1.0       2.0
+        //      a  -----> b  -----> c
+        //      ^                   |
+        //      '-------------------'
+        //              3.0
+        let mut graph = ListGraph::init(DiList::<f64>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.0.into());
+        graph.add_edge_unchecked(b, c, 2.0.into());
+        graph.add_edge_unchecked(c, a, 3.0.into());
+        graph.add_edge_unchecked(a, c, 1.0.into());
+
+        // When:
+        let (tour, length) = Tsp::init(&graph).execute(&graph).unwrap();
+
+        // Then: Edge from a to c is lighter, but there is no way back from c through b.
+        assert_eq!(tour, vec![a, b, c]);
+        assert_eq!(length, 6.0);
+    }
+
+    #[test]
+    fn matches_brute_force_on_random_graphs() {
+        let mut rng = seeded(5);
+        for _ in 0..5 {
+            // Given: Complete graph with 7 vertices and random weights.
+            let mut graph = MatGraph::init(Mat::<f64>::init());
+            let ids = (0..7).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+            let mut weights = vec![vec![0.0; 7]; 7];
+            for src in 0..7 {
+                for dst in src + 1..7 {
+                    let weight = (1 + rng(20)) as f64;
+                    weights[src][dst] = weight;
+                    weights[dst][src] = weight;
+                    graph.add_edge_unchecked(ids[src], ids[dst], weight.into());
+                }
+            }
+
+            // When:
+            let tour = Tsp::init(&graph).execute_within(&graph, Budget::init());
+
+            // Then:
+            assert!(tour.is_optimal());
+            assert_eq!(tour.value(), brute_force(&weights));
+            assert_eq!(tour.solution().len(), 7);
+            assert_eq!(tour_length(&weights, &ids, tour.solution()), tour.value());
+        }
+    }
+
+    #[test]
+    fn stops_when_budget_is_exhausted() {
+        // Given: Complete graph with 9 vertices and random weights.
+        let mut rng = seeded(9);
+        let mut graph = MatGraph::init(Mat::<f64>::init());
+        let ids = (0..9).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        let mut weights = vec![vec![0.0; 9]; 9];
+        for src in 0..9 {
+            for dst in src + 1..9 {
+                let weight = (1 + rng(50)) as f64;
+                weights[src][dst] = weight;
+                weights[dst][src] = weight;
+                graph.add_edge_unchecked(ids[src], ids[dst], weight.into());
+            }
+        }
+        let optimal = brute_force(&weights);
+
+        // When: Search is stopped right after the first tour is found.
+        let tour = Tsp::init(&graph).execute_within(&graph, Budget::init().with_iteration_limit(8));
+
+        // Then: Nearest neighbor tour is found, and the optimal length is between its length and the bound.
+        assert_eq!(tour.solution().len(), 9);
+        assert_eq!(tour_length(&weights, &ids, tour.solution()), tour.value());
+        assert!(tour.bound() <= optimal);
+        assert!(optimal <= tour.value());
+    }
+}
@@ -31,7 +31,7 @@ impl<'a, W, E: Edge<W>> VertexEdgeCut<'a, W, E> {
             depth_of: vec![Magnitude::PosInfinite; vertex_count],
             low_of: vec![Magnitude::PosInfinite; vertex_count],
             parent_of: vec![Magnitude::PosInfinite; vertex_count],
-            id_map: graph.continuos_id_map(),
+            id_map: graph.continuous_id_map(),
             cut_vertices: vec![],
             cut_edges: vec![],
 
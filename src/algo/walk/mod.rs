@@ -0,0 +1,3 @@
+mod node2vec;
+
+pub use node2vec::Node2Vec;
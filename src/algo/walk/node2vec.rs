@@ -0,0 +1,452 @@
+use anyhow::Result;
+
+use crate::graph::Error;
+use crate::provide::{IdMap, Neighbors, Vertices};
+
+// Resolution of the uniform random numbers used to accept or reject candidate steps.
+const RESOLUTION: usize = 1 << 24;
+
+/// Generates a corpus of biased random walks, as described in node2vec, for training vertex embeddings.
+///
+/// Each step of a walk moves from the current vertex to one of its neighbors. Likelihood of each neighbor depends on the previous vertex of the walk:
+/// * Moving back to the previous vertex has weight 1 / `p`.
+/// * Moving to a neighbor of the previous vertex has weight 1.
+/// * Moving to any other vertex has weight 1 / `q`.
+///
+/// So a low `p` keeps walks close to their start, like breadth first search, and a low `q` pushes walks away from it, like depth first search.
+/// With `p` and `q` equal to one, walks are uniform, as described in DeepWalk.
+/// The first step of each walk is always uniform.
+///
+/// Weights of the edges are ignored and parallel edges are counted once. In directed graphs, walks follow direction of the edges and stop early at vertices without any outgoing edge.
+/// Steps are sampled by rejection, so no transition table is built and memory usage is O(|V| + |E|).
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::walk::Node2Vec;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  -->  b  -->  c
+/// //      ^               |
+/// //      '---------------'
+/// //
+/// let mut graph = MatGraph::init(DiMat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, a, 1.into());
+///
+/// let mut next = 0;
+/// let walks = Node2Vec::init(&graph)
+///     .with_walk_length(4)
+///     .with_walks_per_vertex(2)
+///     .execute(&graph, &mut |n| {
+///         next = (next + 7) % 11;
+///         next % n
+///     });
+///
+/// // Every vertex has a single successor, so walks are the same regardless of randomness.
+/// assert_eq!(walks.len(), 6);
+/// assert_eq!(walks[0], vec![a, b, c, a]);
+/// assert_eq!(walks[4], vec![b, c, a, b]);
+/// ```
+pub struct Node2Vec {
+    id_map: IdMap,
+    neighbors_of: Vec<Vec<usize>>,
+
+    p: f64,
+    q: f64,
+    walk_length: usize,
+    walks_per_vertex: usize,
+}
+
+impl Node2Vec {
+    /// # Arguments
+    /// `graph`: Graph to walk on.
+    ///
+    /// # Returns
+    /// Initialized walk generator with `p` and `q` of 1, walks of length 80 and 10 walks per vertex.
+    pub fn init<G: Neighbors + Vertices>(graph: &G) -> Self {
+        let id_map = graph.continuous_id_map();
+
+        let neighbors_of = (0..graph.vertex_count())
+            .map(|src_id| {
+                let mut neighbors = graph
+                    .neighbors_unchecked(id_map.real_id_of(src_id))
+                    .into_iter()
+                    .map(|dst_id| id_map.virt_id_of(dst_id))
+                    .collect::<Vec<usize>>();
+
+                neighbors.sort_unstable();
+                neighbors.dedup();
+
+                neighbors
+            })
+            .collect();
+
+        Node2Vec {
+            id_map,
+            neighbors_of,
+
+            p: 1.0,
+            q: 1.0,
+            walk_length: 80,
+            walks_per_vertex: 10,
+        }
+    }
+
+    /// # Arguments
+    /// `p`: Return parameter, which must be positive. Moving back to the previous vertex has weight 1 / `p`.
+    ///
+    /// # Returns
+    /// Walk generator with the specified return parameter.
+    pub fn with_p(mut self, p: f64) -> Self {
+        self.p = p;
+
+        self
+    }
+
+    /// # Arguments
+    /// `q`: In-out parameter, which must be positive. Moving away from the previous vertex has weight 1 / `q`.
+    ///
+    /// # Returns
+    /// Walk generator with the specified in-out parameter.
+    pub fn with_q(mut self, q: f64) -> Self {
+        self.q = q;
+
+        self
+    }
+
+    /// # Arguments
+    /// `walk_length`: Maximum number of vertices in each walk, including its start.
+    ///
+    /// # Returns
+    /// Walk generator with the specified walk length.
+    pub fn with_walk_length(mut self, walk_length: usize) -> Self {
+        self.walk_length = walk_length;
+
+        self
+    }
+
+    /// # Arguments
+    /// `walks_per_vertex`: Number of walks that start from each vertex.
+    ///
+    /// # Returns
+    /// Walk generator with the specified number of walks per vertex.
+    pub fn with_walks_per_vertex(mut self, walks_per_vertex: usize) -> Self {
+        self.walks_per_vertex = walks_per_vertex;
+
+        self
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the vertex to start the walk from.
+    /// * `rng`: Source of randomness: when called with `n`, returns a uniformly random number in range of 0 to `n` - 1.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing id of the visited vertices, in order.
+    /// * `Err`: [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if vertex with id: `src_id` does not exist.
+    pub fn walk<R: FnMut(usize) -> usize>(&self, src_id: usize, rng: &mut R) -> Result<Vec<usize>> {
+        match self.id_map.get_virt_id(src_id) {
+            Some(virt_id) => Ok(self.walk_unchecked(virt_id, rng)),
+            None => Err(Error::new_vnf(src_id))?,
+        }
+    }
+
+    /// Generates `walks_per_vertex` rounds of walks. Each round contains a walk from every vertex.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to walk on.
+    /// * `rng`: Source of randomness: when called with `n`, returns a uniformly random number in range of 0 to `n` - 1.
+    ///   Use [`seeded`](crate::util::seeded) for reproducible results, or `util::from_rng` with the `rand` feature.
+    ///
+    /// # Returns
+    /// Walks, as id of the visited vertices in order.
+    ///
+    /// # Complexity
+    /// O(`walks_per_vertex` * |V| * `walk_length`) expected steps.
+    pub fn execute<G, R>(&self, _: &G, rng: &mut R) -> Vec<Vec<usize>>
+    where
+        G: Neighbors + Vertices,
+        R: FnMut(usize) -> usize,
+    {
+        self.starts()
+            .map(|virt_id| self.walk_unchecked(virt_id, rng))
+            .collect()
+    }
+
+    /// Generates walks like [`execute`](crate::algo::walk::Node2Vec::execute) does, on multiple threads.
+    ///
+    /// Walks are split into `thread_count` contiguous chunks, and each chunk is generated by a thread with its own source of randomness.
+    /// So walks start from the same vertices and are in the same order as the ones returned by `execute`, but the walks themselves differ.
+    /// The corpus is reproducible for a fixed `thread_count` and `rng_of`.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to walk on.
+    /// * `thread_count`: Number of threads to use.
+    /// * `rng_of`: When called with index of a thread, returns the source of randomness of that thread.
+    ///
+    /// # Returns
+    /// Walks, as id of the visited vertices in order.
+    ///
+    /// # Panics
+    /// If `thread_count` is zero.
+    #[cfg(feature = "parallel")]
+    pub fn execute_parallel<G, F, R>(
+        &self,
+        _: &G,
+        thread_count: usize,
+        rng_of: F,
+    ) -> Vec<Vec<usize>>
+    where
+        G: Neighbors + Vertices,
+        F: Fn(usize) -> R + Sync,
+        R: FnMut(usize) -> usize,
+    {
+        assert!(thread_count > 0, "Thread count must be positive");
+
+        let starts = self.starts().collect::<Vec<usize>>();
+        if starts.is_empty() {
+            return vec![];
+        }
+
+        let chunk_size = starts.len().div_ceil(thread_count);
+
+        std::thread::scope(|scope| {
+            let handles = starts
+                .chunks(chunk_size)
+                .enumerate()
+                .map(|(thread_index, chunk)| {
+                    let rng_of = &rng_of;
+
+                    scope.spawn(move || {
+                        let mut rng = rng_of(thread_index);
+
+                        chunk
+                            .iter()
+                            .map(|virt_id| self.walk_unchecked(*virt_id, &mut rng))
+                            .collect::<Vec<Vec<usize>>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect()
+        })
+    }
+
+    // Virtual id of the start of each walk.
+    fn starts(&self) -> impl Iterator<Item = usize> {
+        let vertex_count = self.neighbors_of.len();
+
+        (0..self.walks_per_vertex).flat_map(move |_| 0..vertex_count)
+    }
+
+    fn walk_unchecked<R: FnMut(usize) -> usize>(&self, src_id: usize, rng: &mut R) -> Vec<usize> {
+        let mut walk = vec![src_id];
+        let max_weight = (1.0 / self.p).max(1.0).max(1.0 / self.q);
+
+        while walk.len() < self.walk_length {
+            let current_id = walk[walk.len() - 1];
+            let neighbors = &self.neighbors_of[current_id];
+            if neighbors.is_empty() {
+                break;
+            }
+
+            let next_id = if walk.len() == 1 {
+                neighbors[rng(neighbors.len())]
+            } else {
+                let prev_id = walk[walk.len() - 2];
+
+                loop {
+                    let candidate_id = neighbors[rng(neighbors.len())];
+                    let weight = if candidate_id == prev_id {
+                        1.0 / self.p
+                    } else if self.neighbors_of[prev_id]
+                        .binary_search(&candidate_id)
+                        .is_ok()
+                    {
+                        1.0
+                    } else {
+                        1.0 / self.q
+                    };
+
+                    if (rng(RESOLUTION) as f64) < weight / max_weight * RESOLUTION as f64 {
+                        break candidate_id;
+                    }
+                }
+            };
+
+            walk.push(next_id);
+        }
+
+        walk.into_iter()
+            .map(|virt_id| self.id_map.real_id_of(virt_id))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ErrorKind, MatGraph};
+    use crate::provide::*;
+    use crate::storage::{DiMat, Mat};
+    use crate::util::seeded;
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let walks = Node2Vec::init(&graph).execute(&graph, &mut seeded(0));
+
+        assert!(walks.is_empty());
+    }
+
+    #[test]
+    fn dead_end() {
+        // Given: Graph
+        //
+        //      a  -->  b
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+
+        // When:
+        let walks = Node2Vec::init(&graph)
+            .with_walks_per_vertex(1)
+            .execute(&graph, &mut seeded(0));
+
+        // Then: Walks stop at b.
+        assert_eq!(walks, vec![vec![a, b], vec![b]]);
+    }
+
+    #[test]
+    fn return_bias() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // When:
+        let mut rng = seeded(1);
+        let mut count_returns = |node2vec: Node2Vec| {
+            (0..200)
+                .filter(|_| node2vec.walk(a, &mut rng).unwrap()[2] == a)
+                .count()
+        };
+        let low_p_returns = count_returns(Node2Vec::init(&graph).with_p(0.01).with_walk_length(3));
+        let high_p_returns =
+            count_returns(Node2Vec::init(&graph).with_p(100.0).with_walk_length(3));
+
+        // Then: Walks almost always return to a with low p, and almost never with high p.
+        assert!(low_p_returns > 190);
+        assert!(high_p_returns < 10);
+    }
+
+    #[test]
+    fn in_out_bias() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  d
+        //       \     /
+        //        \   /
+        //          c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, a, 1.into());
+        graph.add_edge_unchecked(b, d, 1.into());
+
+        // When: Walks from a that move to b first.
+        let node2vec = Node2Vec::init(&graph)
+            .with_p(1000.0)
+            .with_q(0.01)
+            .with_walk_length(3);
+        let mut rng = seeded(2);
+        let walks = (0..400)
+            .map(|_| node2vec.walk(a, &mut rng).unwrap())
+            .filter(|walk| walk[1] == b)
+            .collect::<Vec<Vec<usize>>>();
+
+        // Then: d is not adjacent to a, so low q favors it over c.
+        let to_d = walks.iter().filter(|walk| walk[2] == d).count();
+        assert!(to_d * 10 > walks.len() * 9);
+    }
+
+    #[test]
+    fn vertex_not_found() {
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+
+        let result = Node2Vec::init(&graph).walk(a + 1, &mut seeded(0));
+
+        let error = result.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            ErrorKind::VertexNotFound
+        ));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_walks() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c  ---  d
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let vertices = (0..4).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        for i in 0..3 {
+            graph.add_edge_unchecked(vertices[i], vertices[i + 1], 1.into());
+        }
+
+        // When:
+        let node2vec = Node2Vec::init(&graph)
+            .with_walk_length(5)
+            .with_walks_per_vertex(5);
+        let walks =
+            node2vec.execute_parallel(&graph, 3, |thread_index| seeded(thread_index as u64));
+
+        // Then: Walks are in the same order as the sequential corpus, and each of them is a valid walk.
+        assert_eq!(walks.len(), 20);
+        for (index, walk) in walks.iter().enumerate() {
+            assert_eq!(walk[0], vertices[index % 4]);
+            assert_eq!(walk.len(), 5);
+            assert!(walk
+                .windows(2)
+                .all(|pair| graph.has_any_edge_unchecked(pair[0], pair[1])));
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    #[should_panic(expected = "Thread count must be positive")]
+    fn zero_threads() {
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        graph.add_vertex();
+
+        Node2Vec::init(&graph)
+            .execute_parallel(&graph, 0, |thread_index| seeded(thread_index as u64));
+    }
+}
@@ -0,0 +1,55 @@
+use std::any::Any;
+
+use crate::algo::{ConnectedComponents, WeaklyConnectedComponents};
+use crate::graph::{DirectedEdge, ListGraph, UndirectedEdge};
+use crate::storage::{DiList, List};
+
+/// Graph behind each of the language bindings.
+///
+/// Direction of a graph is chosen by the caller at runtime, so the graph is stored as whichever of the two variants matches it.
+pub(crate) enum Inner<W> {
+    Undirected(ListGraph<W, UndirectedEdge>),
+    Directed(ListGraph<W, DirectedEdge>),
+}
+
+impl<W: Any + Copy> Inner<W> {
+    /// # Arguments
+    /// `directed`: If `true` edges are directed, otherwise they are undirected.
+    ///
+    /// # Returns
+    /// An empty graph.
+    pub(crate) fn init(directed: bool) -> Self {
+        if directed {
+            Inner::Directed(ListGraph::init(DiList::init()))
+        } else {
+            Inner::Undirected(ListGraph::init(List::init()))
+        }
+    }
+
+    /// # Returns
+    /// `true` if edges of the graph are directed, `false` otherwise.
+    pub(crate) fn is_directed(&self) -> bool {
+        matches!(self, Inner::Directed(_))
+    }
+
+    /// # Returns
+    /// Connected components of the graph, or weakly connected components if the graph is directed.
+    pub(crate) fn components(&self) -> Vec<Vec<usize>> {
+        match self {
+            Inner::Undirected(graph) => ConnectedComponents::init(graph).execute(graph),
+            Inner::Directed(graph) => WeaklyConnectedComponents::init(graph).execute(graph),
+        }
+    }
+}
+
+// Evaluates `$body` with `$graph` bound to the graph stored in `$inner`, whichever its direction is.
+macro_rules! with_graph {
+    ($inner:expr, $graph:ident => $body:expr) => {
+        match $inner {
+            $crate::bindings::Inner::Undirected($graph) => $body,
+            $crate::bindings::Inner::Directed($graph) => $body,
+        }
+    };
+}
+
+pub(crate) use with_graph;
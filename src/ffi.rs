@@ -0,0 +1,552 @@
+use std::os::raw::c_char;
+
+use anyhow::Result;
+use magnitude::Magnitude;
+
+use crate::algo::{AStar, Dijkstra};
+use crate::bindings::{with_graph, Inner};
+use crate::graph::{Error, ErrorKind};
+use crate::provide::{Edges, Graph, Vertices};
+
+/// Distance written for vertices that are not reachable from the source vertex.
+/// So a vertex with distance of exactly `u64::MAX` looks unreachable.
+pub const PREPONA_UNREACHABLE: u64 = u64::MAX;
+
+/// Result of each function of the C API.
+///
+/// Values of the variants are part of the ABI and never change. New variants are only added to the end.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreponaStatus {
+    Ok = 0,
+    NullPointer = 1,
+    BufferTooSmall = 2,
+    VertexNotFound = 3,
+    Loop = 4,
+    MultiEdge = 5,
+    WeightOverflow = 6,
+    Other = 7,
+}
+
+impl PreponaStatus {
+    fn of(error: anyhow::Error) -> Self {
+        match error.downcast_ref::<Error>().map(|error| error.kind()) {
+            Some(ErrorKind::VertexNotFound) => PreponaStatus::VertexNotFound,
+            Some(ErrorKind::Loop) => PreponaStatus::Loop,
+            Some(ErrorKind::MultiEdge) => PreponaStatus::MultiEdge,
+            Some(ErrorKind::WeightOverflow) => PreponaStatus::WeightOverflow,
+            _ => PreponaStatus::Other,
+        }
+    }
+
+    fn from_u32(value: u32) -> Option<Self> {
+        let status = match value {
+            0 => PreponaStatus::Ok,
+            1 => PreponaStatus::NullPointer,
+            2 => PreponaStatus::BufferTooSmall,
+            3 => PreponaStatus::VertexNotFound,
+            4 => PreponaStatus::Loop,
+            5 => PreponaStatus::MultiEdge,
+            6 => PreponaStatus::WeightOverflow,
+            7 => PreponaStatus::Other,
+            _ => return None,
+        };
+
+        Some(status)
+    }
+}
+
+/// Opaque graph with `u64` weights, handed to C as a pointer.
+///
+/// Vertices can not be removed through the C API, so ids of the vertices are `0..vertex_count`.
+/// This lets algorithms write their results to arrays indexed by id of the vertices.
+pub struct PreponaGraph {
+    inner: Inner<u64>,
+}
+
+impl PreponaGraph {
+    fn vertex_count(&self) -> usize {
+        with_graph!(&self.inner, graph => graph.vertex_count())
+    }
+
+    fn add_edge(&mut self, src_id: usize, dst_id: usize, weight: u64) -> Result<usize> {
+        with_graph!(&mut self.inner, graph => graph.add_edge(src_id, dst_id, weight.into()))
+    }
+
+    // Distance of each vertex from `src_id`, indexed by id of the vertex.
+    fn distances(&self, src_id: usize) -> Result<Vec<u64>> {
+        with_graph!(&self.inner, graph => {
+            if !graph.contains_vertex(src_id) {
+                Err(Error::new_vnf(src_id))?
+            }
+
+            let sp_subgraph = Dijkstra::init(graph).execute_checked(graph, src_id)?;
+
+            Ok((0..graph.vertex_count())
+                .map(|vertex_id| match sp_subgraph.distance_to(vertex_id) {
+                    Some(Magnitude::Finite(distance)) => distance,
+                    _ => PREPONA_UNREACHABLE,
+                })
+                .collect())
+        })
+    }
+
+    fn shortest_path(&self, src_id: usize, dst_id: usize) -> Result<Option<(Vec<usize>, u64)>> {
+        let shortest_path = with_graph!(&self.inner, graph => {
+            AStar::init(graph).execute(graph, src_id, dst_id, |_| 0)?
+        });
+
+        Ok(match shortest_path {
+            Some((path, Magnitude::Finite(distance))) => Some((path, distance)),
+            _ => None,
+        })
+    }
+}
+
+/// # Arguments
+/// `directed`: If `true` edges are directed, otherwise they are undirected.
+///
+/// # Returns
+/// Pointer to an empty graph. It must be freed by `prepona_graph_free`.
+#[no_mangle]
+pub extern "C" fn prepona_graph_new(directed: bool) -> *mut PreponaGraph {
+    let inner = Inner::init(directed);
+
+    Box::into_raw(Box::new(PreponaGraph { inner }))
+}
+
+/// # Arguments
+/// `graph`: Graph to free. Nothing happens if it's null.
+///
+/// # Safety
+/// `graph` must be null or returned by `prepona_graph_new`, and not freed before.
+#[no_mangle]
+pub unsafe extern "C" fn prepona_graph_free(graph: *mut PreponaGraph) {
+    if !graph.is_null() {
+        drop(Box::from_raw(graph));
+    }
+}
+
+/// # Arguments
+/// * `graph`: Graph to add the vertex to.
+/// * `out_vertex_id`: Id of the new vertex is written to it.
+///
+/// # Safety
+/// `graph` must be returned by `prepona_graph_new`, and `out_vertex_id` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn prepona_graph_add_vertex(
+    graph: *mut PreponaGraph,
+    out_vertex_id: *mut usize,
+) -> PreponaStatus {
+    let graph = match graph.as_mut() {
+        Some(graph) if !out_vertex_id.is_null() => graph,
+        _ => return PreponaStatus::NullPointer,
+    };
+
+    *out_vertex_id = with_graph!(&mut graph.inner, graph => graph.add_vertex());
+
+    PreponaStatus::Ok
+}
+
+/// # Arguments
+/// * `graph`: Graph to add the edge to.
+/// * `src_id`: Id of the source vertex.
+/// * `dst_id`: Id of the destination vertex.
+/// * `weight`: Weight of the edge.
+/// * `out_edge_id`: Id of the new edge is written to it. It can be null if the id is not needed.
+///
+/// # Returns
+/// `Loop` and `MultiEdge` if the edge is not allowed, and `VertexNotFound` if any of the vertices is not in the graph.
+///
+/// # Safety
+/// `graph` must be returned by `prepona_graph_new`, and `out_edge_id` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn prepona_graph_add_edge(
+    graph: *mut PreponaGraph,
+    src_id: usize,
+    dst_id: usize,
+    weight: u64,
+    out_edge_id: *mut usize,
+) -> PreponaStatus {
+    let graph = match graph.as_mut() {
+        Some(graph) => graph,
+        None => return PreponaStatus::NullPointer,
+    };
+
+    match graph.add_edge(src_id, dst_id, weight) {
+        Ok(edge_id) => {
+            if let Some(out_edge_id) = out_edge_id.as_mut() {
+                *out_edge_id = edge_id;
+            }
+
+            PreponaStatus::Ok
+        }
+        Err(error) => PreponaStatus::of(error),
+    }
+}
+
+/// # Returns
+/// Number of vertices in the graph, or zero if `graph` is null.
+///
+/// # Safety
+/// `graph` must be null or returned by `prepona_graph_new`.
+#[no_mangle]
+pub unsafe extern "C" fn prepona_graph_vertex_count(graph: *const PreponaGraph) -> usize {
+    graph.as_ref().map_or(0, |graph| graph.vertex_count())
+}
+
+/// # Returns
+/// Number of edges in the graph, or zero if `graph` is null.
+///
+/// # Safety
+/// `graph` must be null or returned by `prepona_graph_new`.
+#[no_mangle]
+pub unsafe extern "C" fn prepona_graph_edge_count(graph: *const PreponaGraph) -> usize {
+    graph.as_ref().map_or(
+        0,
+        |graph| with_graph!(&graph.inner, graph => graph.edges_count()),
+    )
+}
+
+/// Computes distance of every vertex from a source vertex using Dijkstra algorithm.
+///
+/// # Arguments
+/// * `graph`: Graph to search for shortest paths in.
+/// * `src_id`: Id of the source vertex.
+/// * `out_distances`: Distance of each vertex is written to it, indexed by id of the vertex.
+///   Distance of vertices that are not reachable is `PREPONA_UNREACHABLE`.
+/// * `distances_len`: Length of `out_distances`. It must be at least the number of vertices.
+///
+/// # Returns
+/// `BufferTooSmall` if `distances_len` is less than the number of vertices, and `WeightOverflow` if a distance does not fit in `u64`.
+///
+/// # Safety
+/// `graph` must be returned by `prepona_graph_new`, and `out_distances` must be valid for writing `distances_len` elements.
+#[no_mangle]
+pub unsafe extern "C" fn prepona_dijkstra(
+    graph: *const PreponaGraph,
+    src_id: usize,
+    out_distances: *mut u64,
+    distances_len: usize,
+) -> PreponaStatus {
+    let graph = match graph.as_ref() {
+        Some(graph) if !out_distances.is_null() => graph,
+        _ => return PreponaStatus::NullPointer,
+    };
+    if distances_len < graph.vertex_count() {
+        return PreponaStatus::BufferTooSmall;
+    }
+
+    match graph.distances(src_id) {
+        Ok(distances) => {
+            std::slice::from_raw_parts_mut(out_distances, distances_len)[..distances.len()]
+                .copy_from_slice(&distances);
+
+            PreponaStatus::Ok
+        }
+        Err(error) => PreponaStatus::of(error),
+    }
+}
+
+/// Finds shortest path between two vertices.
+///
+/// # Arguments
+/// * `graph`: Graph to search for the shortest path in.
+/// * `src_id`: Id of the source vertex.
+/// * `dst_id`: Id of the destination vertex.
+/// * `out_path`: Ids of the vertices on the path, including `src_id` and `dst_id`, are written to it.
+/// * `path_capacity`: Length of `out_path`.
+/// * `out_path_len`: Number of vertices on the path is written to it. It's zero if `dst_id` is not reachable from `src_id`.
+/// * `out_distance`: Length of the path is written to it. It's `PREPONA_UNREACHABLE` if `dst_id` is not reachable from `src_id`.
+///
+/// # Returns
+/// `BufferTooSmall` if the path does not fit in `out_path`. Number of vertices on the path is still written to `out_path_len`,
+/// so the call can be repeated with a large enough buffer.
+///
+/// # Safety
+/// `graph` must be returned by `prepona_graph_new`, `out_path` must be valid for writing `path_capacity` elements,
+/// and `out_path_len` and `out_distance` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn prepona_shortest_path(
+    graph: *const PreponaGraph,
+    src_id: usize,
+    dst_id: usize,
+    out_path: *mut usize,
+    path_capacity: usize,
+    out_path_len: *mut usize,
+    out_distance: *mut u64,
+) -> PreponaStatus {
+    let graph = match graph.as_ref() {
+        Some(graph)
+            if !out_path.is_null() && !out_path_len.is_null() && !out_distance.is_null() =>
+        {
+            graph
+        }
+        _ => return PreponaStatus::NullPointer,
+    };
+
+    let (path, distance) = match graph.shortest_path(src_id, dst_id) {
+        Ok(Some((path, distance))) => (path, distance),
+        Ok(None) => (vec![], PREPONA_UNREACHABLE),
+        Err(error) => return PreponaStatus::of(error),
+    };
+
+    *out_path_len = path.len();
+    *out_distance = distance;
+    if path.len() > path_capacity {
+        return PreponaStatus::BufferTooSmall;
+    }
+    std::slice::from_raw_parts_mut(out_path, path_capacity)[..path.len()].copy_from_slice(&path);
+
+    PreponaStatus::Ok
+}
+
+/// Connected components of undirected graphs, and weakly connected components of directed graphs.
+///
+/// # Arguments
+/// * `graph`: Graph to find its components.
+/// * `out_labels`: Index of the component of each vertex is written to it, indexed by id of the vertex. Indices start from zero.
+/// * `labels_len`: Length of `out_labels`. It must be at least the number of vertices.
+/// * `out_component_count`: Number of components is written to it. It can be null if the count is not needed.
+///
+/// # Safety
+/// `graph` must be returned by `prepona_graph_new`, `out_labels` must be valid for writing `labels_len` elements,
+/// and `out_component_count` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn prepona_connected_components(
+    graph: *const PreponaGraph,
+    out_labels: *mut usize,
+    labels_len: usize,
+    out_component_count: *mut usize,
+) -> PreponaStatus {
+    let graph = match graph.as_ref() {
+        Some(graph) if !out_labels.is_null() => graph,
+        _ => return PreponaStatus::NullPointer,
+    };
+    if labels_len < graph.vertex_count() {
+        return PreponaStatus::BufferTooSmall;
+    }
+
+    let components = graph.inner.components();
+
+    let out_labels = std::slice::from_raw_parts_mut(out_labels, labels_len);
+    for (label, component) in components.iter().enumerate() {
+        for vertex_id in component {
+            out_labels[*vertex_id] = label;
+        }
+    }
+    if let Some(out_component_count) = out_component_count.as_mut() {
+        *out_component_count = components.len();
+    }
+
+    PreponaStatus::Ok
+}
+
+/// # Arguments
+/// `status`: Value of a `PreponaStatus`. It's taken as an integer, because C code can pass any value in place of an enum.
+///
+/// # Returns
+/// Null terminated description of `status`, or "unknown status" if `status` is not a value of `PreponaStatus`.
+/// It's statically allocated and must not be freed.
+#[no_mangle]
+pub extern "C" fn prepona_status_message(status: u32) -> *const c_char {
+    let message: &'static [u8] = match PreponaStatus::from_u32(status) {
+        Some(PreponaStatus::Ok) => b"ok\0",
+        Some(PreponaStatus::NullPointer) => b"a required pointer is null\0",
+        Some(PreponaStatus::BufferTooSmall) => b"output buffer is too small\0",
+        Some(PreponaStatus::VertexNotFound) => b"vertex does not exist\0",
+        Some(PreponaStatus::Loop) => b"edge from a vertex to itself is not allowed\0",
+        Some(PreponaStatus::MultiEdge) => b"there is already an edge between the vertices\0",
+        Some(PreponaStatus::WeightOverflow) => b"distance does not fit in u64\0",
+        Some(PreponaStatus::Other) => b"operation failed\0",
+        None => b"unknown status\0",
+    };
+
+    message.as_ptr() as *const c_char
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+    use std::ptr;
+
+    // Path a --(1)--> b --(2)--> c and an isolated vertex d.
+    unsafe fn path_graph(directed: bool) -> *mut PreponaGraph {
+        let graph = prepona_graph_new(directed);
+        let mut ids = [0; 4];
+        for id in ids.iter_mut() {
+            assert_eq!(prepona_graph_add_vertex(graph, id), PreponaStatus::Ok);
+        }
+        assert_eq!(
+            prepona_graph_add_edge(graph, ids[0], ids[1], 1, ptr::null_mut()),
+            PreponaStatus::Ok
+        );
+        assert_eq!(
+            prepona_graph_add_edge(graph, ids[1], ids[2], 2, ptr::null_mut()),
+            PreponaStatus::Ok
+        );
+
+        graph
+    }
+
+    #[test]
+    fn build_graph() {
+        unsafe {
+            // Given:
+            let graph = path_graph(true);
+            let mut edge_id = usize::MAX;
+
+            // When:
+            let loop_status = prepona_graph_add_edge(graph, 0, 0, 1, &mut edge_id);
+            let missing_status = prepona_graph_add_edge(graph, 0, 4, 1, &mut edge_id);
+            let status = prepona_graph_add_edge(graph, 2, 3, 1, &mut edge_id);
+
+            // Then:
+            assert_eq!(loop_status, PreponaStatus::Loop);
+            assert_eq!(missing_status, PreponaStatus::VertexNotFound);
+            assert_eq!(status, PreponaStatus::Ok);
+            assert_ne!(edge_id, usize::MAX);
+            assert_eq!(prepona_graph_vertex_count(graph), 4);
+            assert_eq!(prepona_graph_edge_count(graph), 3);
+
+            prepona_graph_free(graph);
+        }
+    }
+
+    #[test]
+    fn null_pointers() {
+        unsafe {
+            let mut id = 0;
+
+            assert_eq!(
+                prepona_graph_add_vertex(ptr::null_mut(), &mut id),
+                PreponaStatus::NullPointer
+            );
+            assert_eq!(prepona_graph_vertex_count(ptr::null()), 0);
+            prepona_graph_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn dijkstra() {
+        unsafe {
+            // Given:
+            let graph = path_graph(true);
+            let mut distances = [0; 4];
+
+            // When:
+            let small_buffer_status = prepona_dijkstra(graph, 1, distances.as_mut_ptr(), 3);
+            let status = prepona_dijkstra(graph, 1, distances.as_mut_ptr(), 4);
+
+            // Then:
+            assert_eq!(small_buffer_status, PreponaStatus::BufferTooSmall);
+            assert_eq!(status, PreponaStatus::Ok);
+            assert_eq!(distances, [PREPONA_UNREACHABLE, 0, 2, PREPONA_UNREACHABLE]);
+            assert_eq!(
+                prepona_dijkstra(graph, 4, distances.as_mut_ptr(), 4),
+                PreponaStatus::VertexNotFound
+            );
+
+            prepona_graph_free(graph);
+        }
+    }
+
+    #[test]
+    fn shortest_path() {
+        unsafe {
+            // Given:
+            let graph = path_graph(false);
+            let mut path = [0; 3];
+            let mut path_len = 0;
+            let mut distance = 0;
+
+            // When: Buffer is too small for the path.
+            let status = prepona_shortest_path(
+                graph,
+                2,
+                0,
+                path.as_mut_ptr(),
+                2,
+                &mut path_len,
+                &mut distance,
+            );
+
+            // Then: Length of the path is reported.
+            assert_eq!(status, PreponaStatus::BufferTooSmall);
+            assert_eq!(path_len, 3);
+
+            // When:
+            let status = prepona_shortest_path(
+                graph,
+                2,
+                0,
+                path.as_mut_ptr(),
+                3,
+                &mut path_len,
+                &mut distance,
+            );
+
+            // Then:
+            assert_eq!(status, PreponaStatus::Ok);
+            assert_eq!(path, [2, 1, 0]);
+            assert_eq!(distance, 3);
+
+            // When: Destination is not reachable.
+            let status = prepona_shortest_path(
+                graph,
+                0,
+                3,
+                path.as_mut_ptr(),
+                3,
+                &mut path_len,
+                &mut distance,
+            );
+
+            // Then:
+            assert_eq!(status, PreponaStatus::Ok);
+            assert_eq!(path_len, 0);
+            assert_eq!(distance, PREPONA_UNREACHABLE);
+
+            prepona_graph_free(graph);
+        }
+    }
+
+    #[test]
+    fn connected_components() {
+        unsafe {
+            // Given:
+            let graph = path_graph(true);
+            let mut labels = [0; 4];
+            let mut component_count = 0;
+
+            // When:
+            let status =
+                prepona_connected_components(graph, labels.as_mut_ptr(), 4, &mut component_count);
+
+            // Then:
+            assert_eq!(status, PreponaStatus::Ok);
+            assert_eq!(component_count, 2);
+            assert_eq!(labels[0], labels[1]);
+            assert_eq!(labels[1], labels[2]);
+            assert_ne!(labels[2], labels[3]);
+
+            prepona_graph_free(graph);
+        }
+    }
+
+    #[test]
+    fn status_messages_are_null_terminated() {
+        let message =
+            unsafe { CStr::from_ptr(prepona_status_message(PreponaStatus::BufferTooSmall as u32)) };
+
+        assert_eq!(message.to_str().unwrap(), "output buffer is too small");
+    }
+
+    #[test]
+    fn message_of_unknown_status() {
+        let message = unsafe { CStr::from_ptr(prepona_status_message(42)) };
+
+        assert_eq!(message.to_str().unwrap(), "unknown status");
+    }
+}
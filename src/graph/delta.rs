@@ -0,0 +1,345 @@
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+
+use anyhow::Result;
+
+use crate::graph::{Edge, EdgeDir, Error};
+use crate::provide::{Direction, Edges, Graph, Vertices};
+
+// Maps id of each edge to its end points and the edge itself. End points of undirected edges are ordered so `src_id` <= `dst_id`.
+fn edges_by_id<W, E, G>(graph: &G) -> HashMap<usize, ((usize, usize), &E)>
+where
+    E: Edge<W>,
+    G: Edges<W, E> + Direction,
+{
+    graph
+        .edges()
+        .into_iter()
+        .map(|(src_id, dst_id, edge)| {
+            let end_points = if graph.is_undirected() && dst_id < src_id {
+                (dst_id, src_id)
+            } else {
+                (src_id, dst_id)
+            };
+
+            (edge.get_id(), (end_points, edge))
+        })
+        .collect()
+}
+
+/// Difference between two graphs, which can be applied to the first graph to turn it into the second one.
+///
+/// Vertices and edges of the two graphs are matched by their ids. So a delta is meaningful between graphs that share their ids,
+/// like different versions of the same graph, or a graph and its copy created by [`map_edges`](crate::graph::SimpleGraph::map_edges).
+/// An edge with the same id but different end points is considered to be removed and added again.
+///
+/// All lists in the delta are sorted by id of the vertices or edges.
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that graphs use.
+pub struct GraphDelta<W, E: Edge<W>> {
+    added_vertices: Vec<usize>,
+    removed_vertices: Vec<usize>,
+
+    added_edges: Vec<(usize, usize, E)>,
+    removed_edges: Vec<(usize, usize, usize)>,
+    updated_edges: Vec<(usize, usize, E)>,
+
+    phantom_w: PhantomData<W>,
+}
+
+impl<W, E: Edge<W> + Clone + PartialEq> GraphDelta<W, E> {
+    /// # Arguments
+    /// * `lhs`: Graph to compute the difference from.
+    /// * `rhs`: Graph to compute the difference to.
+    ///
+    /// # Returns
+    /// Delta that turns `lhs` into `rhs`.
+    ///
+    /// # Complexity
+    /// O(|V| * log(|V|) + |E| * log(|E|))
+    pub fn between<G1, G2>(lhs: &G1, rhs: &G2) -> Self
+    where
+        G1: Vertices + Edges<W, E> + Direction,
+        G2: Vertices + Edges<W, E> + Direction,
+    {
+        let lhs_vertices = lhs.vertices().into_iter().collect::<HashSet<usize>>();
+        let rhs_vertices = rhs.vertices().into_iter().collect::<HashSet<usize>>();
+
+        let mut added_vertices = rhs_vertices
+            .difference(&lhs_vertices)
+            .copied()
+            .collect::<Vec<usize>>();
+        let mut removed_vertices = lhs_vertices
+            .difference(&rhs_vertices)
+            .copied()
+            .collect::<Vec<usize>>();
+        added_vertices.sort_unstable();
+        removed_vertices.sort_unstable();
+
+        let lhs_edges = edges_by_id(lhs);
+        let rhs_edges = edges_by_id(rhs);
+
+        let mut removed_edges = vec![];
+        let mut updated_edges = vec![];
+        for (edge_id, ((src_id, dst_id), edge)) in &lhs_edges {
+            match rhs_edges.get(edge_id) {
+                Some((end_points, rhs_edge)) if *end_points == (*src_id, *dst_id) => {
+                    if edge != rhs_edge {
+                        updated_edges.push((*src_id, *dst_id, (*rhs_edge).clone()));
+                    }
+                }
+                _ => removed_edges.push((*src_id, *dst_id, *edge_id)),
+            }
+        }
+
+        let mut added_edges = rhs_edges
+            .iter()
+            .filter(|(edge_id, (end_points, _))| {
+                lhs_edges
+                    .get(edge_id)
+                    .map(|(lhs_end_points, _)| lhs_end_points)
+                    != Some(end_points)
+            })
+            .map(|(_, ((src_id, dst_id), edge))| (*src_id, *dst_id, (*edge).clone()))
+            .collect::<Vec<(usize, usize, E)>>();
+
+        removed_edges.sort_unstable_by_key(|(_, _, edge_id)| *edge_id);
+        updated_edges.sort_unstable_by_key(|(_, _, edge)| edge.get_id());
+        added_edges.sort_unstable_by_key(|(_, _, edge)| edge.get_id());
+
+        GraphDelta {
+            added_vertices,
+            removed_vertices,
+
+            added_edges,
+            removed_edges,
+            updated_edges,
+
+            phantom_w: PhantomData,
+        }
+    }
+
+    /// Applies the delta to `graph`, in this order: removing edges, updating edges, removing vertices, adding vertices and adding edges.
+    ///
+    /// Added vertices and edges get new ids from `graph`, which are not necessarily the same as their ids in the delta.
+    /// End points of added edges are translated to the new ids of the added vertices.
+    ///
+    /// Note that changes are applied one by one. So if an error occurs, the changes before it remain applied.
+    ///
+    /// # Arguments
+    /// `graph`: Graph to apply the delta to.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing a map from id of each added vertex in the delta to its id in `graph`.
+    /// * `Err`:
+    ///     * [`EdgeNotFound`](crate::graph::ErrorKind::EdgeNotFound) if an edge to be removed does not exist.
+    ///     * Any error that mutating `graph` returns. For example if a vertex to be removed does not exist.
+    pub fn apply_to<Dir, G>(&self, graph: &mut G) -> Result<HashMap<usize, usize>>
+    where
+        Dir: EdgeDir,
+        G: Graph<W, E, Dir>,
+    {
+        for (src_id, dst_id, edge_id) in &self.removed_edges {
+            if graph.remove_edge(*src_id, *dst_id, *edge_id)?.is_none() {
+                Err(Error::new_enf(*edge_id))?
+            }
+        }
+
+        for (src_id, dst_id, edge) in &self.updated_edges {
+            graph.update_edge(*src_id, *dst_id, edge.get_id(), edge.clone())?;
+        }
+
+        for vertex_id in &self.removed_vertices {
+            graph.remove_vertex(*vertex_id)?;
+        }
+
+        let new_id_of = self
+            .added_vertices
+            .iter()
+            .map(|vertex_id| (*vertex_id, graph.add_vertex()))
+            .collect::<HashMap<usize, usize>>();
+
+        for (src_id, dst_id, edge) in &self.added_edges {
+            let src_id = new_id_of.get(src_id).copied().unwrap_or(*src_id);
+            let dst_id = new_id_of.get(dst_id).copied().unwrap_or(*dst_id);
+
+            graph.add_edge(src_id, dst_id, edge.clone())?;
+        }
+
+        Ok(new_id_of)
+    }
+}
+
+impl<W, E: Edge<W>> GraphDelta<W, E> {
+    /// # Returns
+    /// Id of the vertices that are in the second graph but not in the first one.
+    pub fn added_vertices(&self) -> &[usize] {
+        &self.added_vertices
+    }
+
+    /// # Returns
+    /// Id of the vertices that are in the first graph but not in the second one.
+    pub fn removed_vertices(&self) -> &[usize] {
+        &self.removed_vertices
+    }
+
+    /// # Returns
+    /// Edges that are in the second graph but not in the first one, in the format of: (`src_id`, `dst_id`, `edge`).
+    pub fn added_edges(&self) -> &[(usize, usize, E)] {
+        &self.added_edges
+    }
+
+    /// # Returns
+    /// Edges that are in the first graph but not in the second one, including the edges of removed vertices, in the format of: (`src_id`, `dst_id`, `edge_id`).
+    pub fn removed_edges(&self) -> &[(usize, usize, usize)] {
+        &self.removed_edges
+    }
+
+    /// # Returns
+    /// Edges that are in both graphs but have different values, like a different weight, in the format of: (`src_id`, `dst_id`, `edge`).
+    /// Each edge has the value it has in the second graph.
+    pub fn updated_edges(&self) -> &[(usize, usize, E)] {
+        &self.updated_edges
+    }
+
+    /// # Returns
+    /// `true` if two graphs are the same, `false` otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.added_vertices.is_empty()
+            && self.removed_vertices.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.updated_edges.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ListGraph;
+    use crate::graph::{structurally_equal, DefaultEdge, MatGraph};
+    use crate::storage::{DiList, Mat};
+
+    #[test]
+    fn identical_graphs() {
+        // Given: Graph
+        //
+        //      a  --(1)--  b
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+
+        // When:
+        let delta = GraphDelta::between(&graph, &graph);
+
+        // Then:
+        assert!(delta.is_empty());
+    }
+
+    #[test]
+    fn diff_and_apply() {
+        // Given: Graph
+        //
+        //      a  --(1)-->  b  --(2)-->  c
+        //
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+        let bc = graph.add_edge_unchecked(b, c, 2.into());
+
+        // And: Its modified copy
+        //
+        //      a  --(5)-->  b      c  --(3)-->  d
+        //
+        let mut modified = graph.map_edges(DiList::<usize>::init(), |_, _, edge| *edge);
+        modified.update_edge_unchecked(a, b, ab, 5.into());
+        modified.remove_edge_unchecked(b, c, bc);
+        let d = modified.add_vertex();
+        let cd = modified.add_edge_unchecked(c, d, 3.into());
+
+        // When:
+        let delta = GraphDelta::between(&graph, &modified);
+
+        // Then:
+        assert_eq!(delta.added_vertices(), &[d]);
+        assert!(delta.removed_vertices().is_empty());
+        assert_eq!(delta.removed_edges(), &[(b, c, bc)]);
+        assert_eq!(delta.updated_edges().len(), 1);
+        assert_eq!(delta.updated_edges()[0].2.get_weight(), &5.into());
+        assert_eq!(delta.added_edges().len(), 1);
+        assert_eq!(delta.added_edges()[0].0, c);
+        assert_eq!(delta.added_edges()[0].1, d);
+        assert_eq!(delta.added_edges()[0].2.get_id(), cd);
+
+        // When: Applying the delta to the graph.
+        let new_id_of = delta.apply_to(&mut graph).unwrap();
+
+        // Then:
+        assert_eq!(new_id_of.len(), 1);
+        assert!(structurally_equal(&graph, &modified));
+    }
+
+    #[test]
+    fn removed_vertex() {
+        // Given: Graph
+        //
+        //      a  --(1)--  b  --(2)--  c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+        let bc = graph.add_edge_unchecked(c, b, 2.into());
+
+        // And: Its copy without b.
+        let mut modified = graph.map_edges(Mat::<usize>::init(), |_, _, edge| *edge);
+        modified.remove_vertex_unchecked(b);
+
+        // When:
+        let delta = GraphDelta::between(&graph, &modified);
+
+        // Then: Edges of b are removed with it.
+        assert_eq!(delta.removed_vertices(), &[b]);
+        assert_eq!(delta.removed_edges(), &[(a, b, ab), (b, c, bc)]);
+
+        // When: Applying the delta to the graph.
+        delta.apply_to(&mut graph).unwrap();
+
+        // Then:
+        assert_eq!(graph.vertices(), vec![a, c]);
+        assert_eq!(graph.edges_count(), 0);
+
+        // And: Applying the delta again fails.
+        assert!(delta.apply_to(&mut graph).is_err());
+    }
+
+    #[test]
+    fn edge_with_reused_id() {
+        // Given: Graph with edge from a to b, and its copy in which the edge is moved to b and c with the same id.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+
+        let mut modified = graph.map_edges(Mat::<usize>::init(), |_, _, edge| *edge);
+        modified.remove_edge_unchecked(a, b, ab);
+        let bc = modified.add_edge_unchecked(b, c, 1.into());
+
+        // When:
+        let delta: GraphDelta<usize, DefaultEdge<usize>> = GraphDelta::between(&graph, &modified);
+
+        // Then: Edge is removed and added again.
+        assert_eq!(bc, ab);
+        assert_eq!(delta.removed_edges(), &[(a, b, ab)]);
+        assert_eq!(delta.added_edges().len(), 1);
+        assert!(delta.updated_edges().is_empty());
+    }
+}
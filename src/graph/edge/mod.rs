@@ -6,7 +6,7 @@ use magnitude::Magnitude;
 pub use default_edge::DefaultEdge;
 pub use flow_edge::FlowEdge;
 
-/// Defines functionalities to determine wether edges in the graph are directed or not.
+/// Defines functionalities to determine whether edges in the graph are directed or not.
 pub trait EdgeDir {
     /// # Returns
     /// * `true`: If edge is directed.
@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+
+use magnitude::Magnitude;
+
+use crate::graph::Edge;
+use crate::provide::{Direction, Edges, Vertices};
+
+// Maps end points of each edge to weights of the edges between them. End points of undirected edges are ordered so `src_id` <= `dst_id`.
+fn weights_between<'a, W, E, G>(graph: &'a G) -> HashMap<(usize, usize), Vec<&'a Magnitude<W>>>
+where
+    E: Edge<W> + 'a,
+    G: Edges<W, E> + Direction,
+{
+    let mut weights_between = HashMap::new();
+
+    for (src_id, dst_id, edge) in graph.edges() {
+        let end_points = if graph.is_undirected() && dst_id < src_id {
+            (dst_id, src_id)
+        } else {
+            (src_id, dst_id)
+        };
+
+        weights_between
+            .entry(end_points)
+            .or_insert_with(Vec::new)
+            .push(edge.get_weight());
+    }
+
+    weights_between
+}
+
+// Checks whether `rhs` is a permutation of `lhs`.
+fn is_permutation<T: PartialEq>(lhs: &[T], rhs: &[T]) -> bool {
+    let mut is_matched = vec![false; rhs.len()];
+
+    lhs.len() == rhs.len()
+        && lhs.iter().all(|item| {
+            match (0..rhs.len()).find(|&index| !is_matched[index] && rhs[index] == *item) {
+                Some(index) => {
+                    is_matched[index] = true;
+                    true
+                }
+                None => false,
+            }
+        })
+}
+
+/// Compares two graphs without caring about ids of the edges or the order they were added in.
+///
+/// Graphs are structurally equal if they have the same direction, vertices with the same ids and
+/// the same number of edges with the same weights between each pair of vertices.
+/// Unlike `==`, graphs can have different storages or edge types, and they can also be subgraphs.
+///
+/// # Arguments
+/// * `lhs`: First graph.
+/// * `rhs`: Second graph.
+///
+/// # Returns
+/// `true` if graphs are structurally equal, `false` otherwise.
+///
+/// # Complexity
+/// O(|V|.log(|V|) + |E|)
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::{List, Mat};
+/// use prepona::graph::{structurally_equal, ListGraph, MatGraph};
+///
+/// let mut mat_graph = MatGraph::init(Mat::<usize>::init());
+/// let a = mat_graph.add_vertex();
+/// let b = mat_graph.add_vertex();
+/// let c = mat_graph.add_vertex();
+/// mat_graph.add_edge_unchecked(a, b, 1.into());
+/// mat_graph.add_edge_unchecked(b, c, 2.into());
+///
+/// let mut list_graph = ListGraph::init(List::<usize>::init());
+/// list_graph.add_vertex();
+/// list_graph.add_vertex();
+/// list_graph.add_vertex();
+/// list_graph.add_edge_unchecked(c, b, 2.into());
+/// list_graph.add_edge_unchecked(b, a, 1.into());
+///
+/// assert!(structurally_equal(&mat_graph, &list_graph));
+/// ```
+pub fn structurally_equal<W, E1, E2, G1, G2>(lhs: &G1, rhs: &G2) -> bool
+where
+    W: PartialEq,
+    E1: Edge<W>,
+    E2: Edge<W>,
+    G1: Vertices + Edges<W, E1> + Direction,
+    G2: Vertices + Edges<W, E2> + Direction,
+{
+    if lhs.is_directed() != rhs.is_directed() {
+        return false;
+    }
+
+    let mut lhs_vertices = lhs.vertices();
+    let mut rhs_vertices = rhs.vertices();
+    lhs_vertices.sort_unstable();
+    rhs_vertices.sort_unstable();
+
+    if lhs_vertices != rhs_vertices {
+        return false;
+    }
+
+    let lhs_weights_between = weights_between(lhs);
+    let rhs_weights_between = weights_between(rhs);
+
+    lhs_weights_between.len() == rhs_weights_between.len()
+        && lhs_weights_between.iter().all(|(end_points, lhs_weights)| {
+            match rhs_weights_between.get(end_points) {
+                Some(rhs_weights) => is_permutation(lhs_weights, rhs_weights),
+                None => false,
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::graph::subgraph::Subgraph;
+    use crate::graph::{ListGraph, MatGraph, SparseMatGraph};
+    use crate::provide::*;
+    use crate::storage::{DiList, DiMat, List, Mat, SparseMat};
+
+    #[test]
+    fn empty_graphs() {
+        let mat_graph = MatGraph::init(Mat::<usize>::init());
+        let list_graph = ListGraph::init(List::<usize>::init());
+        let di_list_graph = ListGraph::init(DiList::<usize>::init());
+
+        assert!(structurally_equal(&mat_graph, &list_graph));
+        assert!(!structurally_equal(&mat_graph, &di_list_graph));
+    }
+
+    #[test]
+    fn edges_added_in_different_order() {
+        // Given: Graph
+        //
+        //      a  --(1)--  b  --(2)--  c
+        //      |                       |
+        //      '----------(1)----------'
+        //
+        let mut mat_graph = MatGraph::init(Mat::<usize>::init());
+        let a = mat_graph.add_vertex();
+        let b = mat_graph.add_vertex();
+        let c = mat_graph.add_vertex();
+        mat_graph.add_edge_unchecked(a, b, 1.into());
+        mat_graph.add_edge_unchecked(b, c, 2.into());
+        mat_graph.add_edge_unchecked(a, c, 1.into());
+
+        // And: Same graph with a different storage and order of edges.
+        let mut sparse_mat_graph = SparseMatGraph::init(SparseMat::<usize>::init());
+        sparse_mat_graph.add_vertex();
+        sparse_mat_graph.add_vertex();
+        sparse_mat_graph.add_vertex();
+        sparse_mat_graph.add_edge_unchecked(c, a, 1.into());
+        sparse_mat_graph.add_edge_unchecked(c, b, 2.into());
+        sparse_mat_graph.add_edge_unchecked(b, a, 1.into());
+
+        // Then:
+        assert!(structurally_equal(&mat_graph, &sparse_mat_graph));
+        assert!(structurally_equal(&sparse_mat_graph, &mat_graph));
+
+        // When: Changing weight of an edge.
+        let ca = sparse_mat_graph.edges_between_unchecked(c, a)[0].get_id();
+        sparse_mat_graph.update_edge_unchecked(c, a, ca, 2.into());
+
+        // Then:
+        assert!(!structurally_equal(&mat_graph, &sparse_mat_graph));
+    }
+
+    #[test]
+    fn directed_graphs() {
+        // Given: Graphs
+        //
+        //      a  --(1)-->  b          a  <--(1)--  b
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+
+        let mut reversed_graph = ListGraph::init(DiList::<usize>::init());
+        reversed_graph.add_vertex();
+        reversed_graph.add_vertex();
+        reversed_graph.add_edge_unchecked(b, a, 1.into());
+
+        // Then:
+        assert!(!structurally_equal(&graph, &reversed_graph));
+
+        // When: Adding the reversed edge to both graphs.
+        graph.add_edge_unchecked(b, a, 1.into());
+        reversed_graph.add_edge_unchecked(a, b, 1.into());
+
+        // Then:
+        assert!(structurally_equal(&graph, &reversed_graph));
+    }
+
+    #[test]
+    fn vertex_ids() {
+        // Given: Graphs with the same number of vertices but different vertex ids.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        graph.add_vertex();
+        graph.add_vertex();
+
+        let mut other_graph = MatGraph::init(Mat::<usize>::init());
+        let a = other_graph.add_vertex();
+        other_graph.add_vertex();
+        other_graph.add_vertex();
+        other_graph.remove_vertex_unchecked(a);
+
+        // Then:
+        assert!(!structurally_equal(&graph, &other_graph));
+    }
+
+    #[test]
+    fn subgraph() {
+        // Given: Graph
+        //
+        //      a  --(1)--  b  --(2)--  c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 2.into());
+
+        // And: Subgraph containing only a and b.
+        let subgraph = Subgraph::init(
+            &graph,
+            vec![(a, b, ab)],
+            [a, b].iter().copied().collect::<HashSet<usize>>(),
+        );
+
+        // And: A graph with only a and b.
+        let mut other_graph = ListGraph::init(List::<usize>::init());
+        other_graph.add_vertex();
+        other_graph.add_vertex();
+        other_graph.add_edge_unchecked(b, a, 1.into());
+
+        // Then:
+        assert!(structurally_equal(&subgraph, &other_graph));
+        assert!(!structurally_equal(&graph, &other_graph));
+    }
+}
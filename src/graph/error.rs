@@ -4,7 +4,19 @@ pub enum ErrorKind {
     VertexNotFound,
     EdgeNotFound,
     EdgeAlreadyExists,
-    RootAlreadyExists
+    RootAlreadyExists,
+    Cycle,
+    InvalidWeight,
+    InvalidPartition,
+    NotTree,
+    WeightOverflow,
+    LabelAlreadyExists,
+    LabelNotFound,
+    InvalidFormat,
+    NotConverged,
+    InvalidMapping,
+    NegativeCycle,
+    Cancelled,
 }
 
 pub struct Error {
@@ -59,6 +71,97 @@ impl Error {
         }
     }
 
+    pub fn new_c(src_id: usize, dst_id: usize) -> Self {
+        Error {
+            kind: ErrorKind::Cycle,
+            msg: format!("Adding edge from {} to {} creates a cycle", src_id, dst_id)
+        }
+    }
+
+    pub fn new_iw(edge_id: usize) -> Self {
+        Error {
+            kind: ErrorKind::InvalidWeight,
+            msg: format!("Weight of edge with id: {} is not supported", edge_id)
+        }
+    }
+
+    pub fn new_ip(vertex_id: usize) -> Self {
+        Error {
+            kind: ErrorKind::InvalidPartition,
+            msg: format!("Vertex with id: {} must be in exactly one block of the partition", vertex_id)
+        }
+    }
+
+    pub fn new_nt() -> Self {
+        Error {
+            kind: ErrorKind::NotTree,
+            msg: String::from("Graph is not a tree")
+        }
+    }
+
+    pub fn new_wo() -> Self {
+        Error {
+            kind: ErrorKind::WeightOverflow,
+            msg: String::from("Sum of weights overflowed")
+        }
+    }
+
+    pub fn new_lae<L: std::fmt::Debug>(label: &L, vertex_id: usize) -> Self {
+        Error {
+            kind: ErrorKind::LabelAlreadyExists,
+            msg: format!("Label: {:?} is already used by vertex with id: {}", label, vertex_id)
+        }
+    }
+
+    pub fn new_lnf<L: std::fmt::Debug>(label: &L) -> Self {
+        Error {
+            kind: ErrorKind::LabelNotFound,
+            msg: format!("Vertex with label: {:?} does not exist", label)
+        }
+    }
+
+    pub fn new_if(line_number: usize, line: &str) -> Self {
+        Error {
+            kind: ErrorKind::InvalidFormat,
+            msg: format!("Line {}: {:?} is not a valid edge", line_number, line)
+        }
+    }
+
+    pub fn new_imd(line_number: usize, line: &str) -> Self {
+        Error {
+            kind: ErrorKind::InvalidFormat,
+            msg: format!("Line {}: {:?} is not a valid metadata entry", line_number, line)
+        }
+    }
+
+    pub fn new_ncv(iterations: usize) -> Self {
+        Error {
+            kind: ErrorKind::NotConverged,
+            msg: format!("Did not converge after {} iterations", iterations)
+        }
+    }
+
+    pub fn new_im(vertex_id: usize) -> Self {
+        Error {
+            kind: ErrorKind::InvalidMapping,
+            msg: format!("Vertex with id: {} must be mapped to an id that no other vertex is mapped to", vertex_id)
+        }
+    }
+
+    pub fn new_nc(vertex_id: usize) -> Self {
+        Error {
+            kind: ErrorKind::NegativeCycle,
+            msg: format!("Vertex with id: {} is on a negative cycle", vertex_id)
+        }
+    }
+
+    pub fn new_cnl(completed: usize, total: usize) -> Self {
+        Error {
+            kind: ErrorKind::Cancelled,
+            msg: format!("Cancelled after {} of {} steps", completed, total)
+        }
+    }
+
     pub fn msg(&self) -> &str {
         self.msg.as_str()
     }
@@ -0,0 +1,39 @@
+/// Describes a mutation that happened to a graph.
+///
+/// Graphs notify their observers about each successful mutation by passing the corresponding event to them.
+/// Failed mutations(for example adding an edge to a vertex that does not exist) do not produce any event.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GraphEvent {
+    /// A vertex with the contained id is added.
+    VertexAdded(usize),
+
+    /// A vertex with the contained id is removed.
+    /// Each edge connected to the vertex produces an `EdgeRemoved` event before this one.
+    VertexRemoved(usize),
+
+    /// An edge is added from `src_id` to `dst_id`.
+    EdgeAdded {
+        src_id: usize,
+        dst_id: usize,
+        edge_id: usize,
+    },
+
+    /// The edge from `src_id` to `dst_id` is replaced with a new one.
+    EdgeUpdated {
+        src_id: usize,
+        dst_id: usize,
+        edge_id: usize,
+    },
+
+    /// The edge from `src_id` to `dst_id` is removed.
+    EdgeRemoved {
+        src_id: usize,
+        dst_id: usize,
+        edge_id: usize,
+    },
+}
+
+/// A callback that gets called on each mutation of the graph.
+///
+/// Observers only need to be `Send`, so graphs with observers can still be moved to other threads.
+pub type Observer = Box<dyn FnMut(&GraphEvent) + Send>;
@@ -0,0 +1,495 @@
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{self, Debug, Formatter};
+use std::io::Write;
+
+use anyhow::Result;
+
+use crate::graph::Error;
+
+// Prefix of the comment lines that hold metadata entries in edge lists.
+const COMMENT_PREFIX: &str = "#@";
+
+const NAME_KEY: &str = "name";
+const DESCRIPTION_KEY: &str = "description";
+
+/// Value of a metadata entry that can be written to and read back from files.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetadataValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+impl MetadataValue {
+    /// # Returns
+    /// * `Some`: Containing the boolean, if value is a boolean.
+    /// * `None`: Otherwise.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            MetadataValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// # Returns
+    /// * `Some`: Containing the integer, if value is an integer.
+    /// * `None`: Otherwise.
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            MetadataValue::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// # Returns
+    /// * `Some`: Containing the number, if value is a float or an integer.
+    /// * `None`: Otherwise.
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            MetadataValue::Float(value) => Some(*value),
+            MetadataValue::Int(value) => Some(*value as f64),
+            _ => None,
+        }
+    }
+
+    /// # Returns
+    /// * `Some`: Containing the text, if value is a text.
+    /// * `None`: Otherwise.
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            MetadataValue::Text(value) => Some(value.as_str()),
+            _ => None,
+        }
+    }
+
+    // Type tag and text of the value, as they are written in files.
+    fn to_columns(&self) -> (&'static str, String) {
+        match self {
+            MetadataValue::Bool(value) => ("bool", value.to_string()),
+            MetadataValue::Int(value) => ("int", value.to_string()),
+            MetadataValue::Float(value) => ("float", value.to_string()),
+            MetadataValue::Text(value) => ("text", escape(value)),
+        }
+    }
+
+    fn from_columns(tag: &str, text: &str) -> Option<Self> {
+        match tag {
+            "bool" => text.parse().ok().map(MetadataValue::Bool),
+            "int" => text.parse().ok().map(MetadataValue::Int),
+            "float" => text.parse().ok().map(MetadataValue::Float),
+            "text" => unescape(text).map(MetadataValue::Text),
+            _ => None,
+        }
+    }
+}
+
+impl From<bool> for MetadataValue {
+    fn from(value: bool) -> Self {
+        MetadataValue::Bool(value)
+    }
+}
+
+impl From<i32> for MetadataValue {
+    fn from(value: i32) -> Self {
+        MetadataValue::Int(value as i64)
+    }
+}
+
+impl From<i64> for MetadataValue {
+    fn from(value: i64) -> Self {
+        MetadataValue::Int(value)
+    }
+}
+
+impl From<f64> for MetadataValue {
+    fn from(value: f64) -> Self {
+        MetadataValue::Float(value)
+    }
+}
+
+impl From<&str> for MetadataValue {
+    fn from(value: &str) -> Self {
+        MetadataValue::Text(value.to_string())
+    }
+}
+
+impl From<String> for MetadataValue {
+    fn from(value: String) -> Self {
+        MetadataValue::Text(value)
+    }
+}
+
+/// Key-value metadata of a graph, like its name, description and where it came from, so pipelines can carry provenance along with the graph.
+///
+/// There are two kinds of entries:
+/// * Values: Booleans, integers, floats and texts, that are written by [`write_comments`](crate::graph::Metadata::write_comments)
+///   and read back by [`EdgeListLoader`](crate::io::EdgeListLoader). Name and description are text values with keys `name` and `description`.
+/// * Attachments: Values of any type, that only live in memory and are not written to files.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::Mat;
+/// use prepona::graph::{MatGraph, Metadata};
+///
+/// let mut graph = MatGraph::init(Mat::<usize>::init()).with_metadata(
+///     Metadata::init()
+///         .with_name("roads")
+///         .with_description("Roads of the city"),
+/// );
+/// graph.metadata_mut().set("year", 2021);
+/// graph.metadata_mut().attach("source", vec![1u8, 2, 3]);
+///
+/// assert_eq!(graph.metadata().name(), Some("roads"));
+/// assert_eq!(graph.metadata().get("year").unwrap().as_int(), Some(2021));
+/// assert_eq!(graph.metadata().attachment::<Vec<u8>>("source"), Some(&vec![1, 2, 3]));
+/// ```
+#[derive(Default)]
+pub struct Metadata {
+    values: BTreeMap<String, MetadataValue>,
+    attachments: HashMap<String, Box<dyn Any + Send + Sync>>,
+}
+
+impl Metadata {
+    /// # Returns
+    /// Metadata without any entry.
+    pub fn init() -> Self {
+        Metadata::default()
+    }
+
+    /// # Arguments
+    /// `name`: Name of the graph.
+    ///
+    /// # Returns
+    /// The metadata with the specified name.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.set_name(name);
+
+        self
+    }
+
+    /// # Arguments
+    /// `description`: Description of the graph.
+    ///
+    /// # Returns
+    /// The metadata with the specified description.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.set_description(description);
+
+        self
+    }
+
+    /// # Returns
+    /// * `Some`: Containing name of the graph.
+    /// * `None`: If graph has no name, or value of `name` is not a text.
+    pub fn name(&self) -> Option<&str> {
+        self.get(NAME_KEY).and_then(|value| value.as_text())
+    }
+
+    /// # Arguments
+    /// `name`: New name of the graph.
+    pub fn set_name(&mut self, name: impl Into<String>) {
+        self.set(NAME_KEY, name.into());
+    }
+
+    /// # Returns
+    /// * `Some`: Containing description of the graph.
+    /// * `None`: If graph has no description, or value of `description` is not a text.
+    pub fn description(&self) -> Option<&str> {
+        self.get(DESCRIPTION_KEY).and_then(|value| value.as_text())
+    }
+
+    /// # Arguments
+    /// `description`: New description of the graph.
+    pub fn set_description(&mut self, description: impl Into<String>) {
+        self.set(DESCRIPTION_KEY, description.into());
+    }
+
+    /// # Arguments
+    /// `key`: Key of the value.
+    ///
+    /// # Returns
+    /// * `Some`: Containing the value associated with `key`.
+    /// * `None`: If there is no value associated with `key`.
+    pub fn get(&self, key: &str) -> Option<&MetadataValue> {
+        self.values.get(key)
+    }
+
+    /// # Arguments
+    /// * `key`: Key of the value.
+    /// * `value`: Value to associate with `key`.
+    ///
+    /// # Returns
+    /// * `Some`: Containing the value that was associated with `key` before.
+    /// * `None`: If there was no value associated with `key`.
+    pub fn set(&mut self, key: &str, value: impl Into<MetadataValue>) -> Option<MetadataValue> {
+        self.values.insert(key.to_string(), value.into())
+    }
+
+    /// # Arguments
+    /// `key`: Key of the value to remove.
+    ///
+    /// # Returns
+    /// * `Some`: Containing the removed value.
+    /// * `None`: If there was no value associated with `key`.
+    pub fn remove(&mut self, key: &str) -> Option<MetadataValue> {
+        self.values.remove(key)
+    }
+
+    /// # Returns
+    /// Keys of the values in ascending order. Keys of the attachments are not included.
+    pub fn keys(&self) -> Vec<&str> {
+        self.values.keys().map(|key| key.as_str()).collect()
+    }
+
+    /// # Arguments
+    /// * `key`: Key of the attachment.
+    /// * `attachment`: Value of any type to associate with `key`. It replaces the previous attachment of `key`, if any.
+    pub fn attach<T: Any + Send + Sync>(&mut self, key: &str, attachment: T) {
+        self.attachments
+            .insert(key.to_string(), Box::new(attachment));
+    }
+
+    /// # Arguments
+    /// `key`: Key of the attachment.
+    ///
+    /// # Returns
+    /// * `Some`: Containing the attachment associated with `key`.
+    /// * `None`: If there is no attachment associated with `key`, or it's not of type `T`.
+    pub fn attachment<T: Any>(&self, key: &str) -> Option<&T> {
+        self.attachments
+            .get(key)
+            .and_then(|attachment| attachment.downcast_ref())
+    }
+
+    /// # Arguments
+    /// `key`: Key of the attachment.
+    ///
+    /// # Returns
+    /// * `Some`: Containing mutable reference to the attachment associated with `key`.
+    /// * `None`: If there is no attachment associated with `key`, or it's not of type `T`.
+    pub fn attachment_mut<T: Any>(&mut self, key: &str) -> Option<&mut T> {
+        self.attachments
+            .get_mut(key)
+            .and_then(|attachment| attachment.downcast_mut())
+    }
+
+    /// # Arguments
+    /// `key`: Key of the attachment to remove.
+    ///
+    /// # Returns
+    /// * `Some`: Containing the removed attachment.
+    /// * `None`: If there was no attachment associated with `key`.
+    pub fn detach(&mut self, key: &str) -> Option<Box<dyn Any + Send + Sync>> {
+        self.attachments.remove(key)
+    }
+
+    /// # Returns
+    /// True if there is no value and no attachment, false otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty() && self.attachments.is_empty()
+    }
+
+    /// Writes the values as comment lines of an edge list, so they can be written before the edges and get read back by [`EdgeListLoader`](crate::io::EdgeListLoader).
+    /// Other tools that read edge lists skip them like other comments.
+    ///
+    /// Each value is written in a line with the format of: `#@ key type value`, separated by tabs.
+    /// Backslashes, tabs and line breaks of keys and texts are escaped.
+    ///
+    /// # Arguments
+    /// `writer`: Writer of the edge list.
+    ///
+    /// # Returns
+    /// * `Ok`: If values are written successfully. Attachments are not written.
+    /// * `Err`: Any error that writing to `writer` returns.
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::graph::{ListGraph, Metadata};
+    /// use prepona::io::EdgeListLoader;
+    /// use prepona::storage::DiList;
+    ///
+    /// let mut file = vec![];
+    /// Metadata::init().with_name("citations").write_comments(&mut file).unwrap();
+    /// file.extend_from_slice(b"1 2\n");
+    ///
+    /// let mut graph = ListGraph::init(DiList::<usize>::init());
+    /// let mut loader = EdgeListLoader::init();
+    /// loader.load(file.as_slice(), &mut graph).unwrap();
+    /// *graph.metadata_mut() = loader.take_metadata();
+    ///
+    /// assert_eq!(graph.metadata().name(), Some("citations"));
+    /// ```
+    pub fn write_comments<Wr: Write>(&self, mut writer: Wr) -> Result<()> {
+        for (key, value) in &self.values {
+            let (tag, text) = value.to_columns();
+            writeln!(
+                writer,
+                "{}\t{}\t{}\t{}",
+                COMMENT_PREFIX,
+                escape(key),
+                tag,
+                text
+            )?;
+        }
+
+        Ok(())
+    }
+
+    // Returns false if line is not a metadata comment, and `Err` if it is but its format is not valid.
+    pub(crate) fn read_comment(&mut self, line_number: usize, line: &str) -> Result<bool> {
+        let trimmed = line.trim_end_matches(|c| c == '\n' || c == '\r');
+        let mut columns = trimmed.splitn(4, '\t');
+        if columns.next() != Some(COMMENT_PREFIX) {
+            return Ok(false);
+        }
+
+        let entry = (|| {
+            let key = unescape(columns.next()?).filter(|key| !key.is_empty())?;
+            let value = MetadataValue::from_columns(columns.next()?, columns.next()?)?;
+
+            Some((key, value))
+        })();
+
+        match entry {
+            Some((key, value)) => {
+                self.values.insert(key, value);
+
+                Ok(true)
+            }
+            None => Err(Error::new_imd(line_number, trimmed))?,
+        }
+    }
+}
+
+impl Debug for Metadata {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut attachment_keys = self.attachments.keys().collect::<Vec<&String>>();
+        attachment_keys.sort_unstable();
+
+        f.debug_struct("Metadata")
+            .field("values", &self.values)
+            .field("attachments", &attachment_keys)
+            .finish()
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+// Returns `None` if text ends in the middle of an escape sequence or contains an unknown one.
+fn unescape(text: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next()? {
+            '\\' => unescaped.push('\\'),
+            't' => unescaped.push('\t'),
+            'n' => unescaped.push('\n'),
+            'r' => unescaped.push('\r'),
+            _ => return None,
+        }
+    }
+
+    Some(unescaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ErrorKind;
+
+    #[test]
+    fn name_and_description_are_text_values() {
+        let mut metadata = Metadata::init().with_name("a").with_description("b");
+
+        metadata.set("name", 1);
+
+        assert_eq!(metadata.name(), None);
+        assert_eq!(metadata.description(), Some("b"));
+        assert_eq!(metadata.keys(), vec!["description", "name"]);
+    }
+
+    #[test]
+    fn attachments() {
+        let mut metadata = Metadata::init();
+
+        metadata.attach("ids", vec![1usize, 2]);
+        metadata
+            .attachment_mut::<Vec<usize>>("ids")
+            .unwrap()
+            .push(3);
+
+        assert_eq!(
+            metadata.attachment::<Vec<usize>>("ids"),
+            Some(&vec![1, 2, 3])
+        );
+        assert_eq!(metadata.attachment::<String>("ids"), None);
+        assert!(metadata.keys().is_empty());
+        assert!(metadata.detach("ids").is_some());
+        assert!(metadata.is_empty());
+    }
+
+    #[test]
+    fn write_and_read_comments() {
+        // Given: Metadata with every type of value, and text that needs escaping.
+        let mut metadata = Metadata::init().with_description("line 1\nline 2\t\\");
+        metadata.set("weighted", true);
+        metadata.set("year", -2021);
+        metadata.set("scale", 0.1);
+        metadata.set("source key", "konect");
+        metadata.attach("in memory", 1);
+
+        // When:
+        let mut file = vec![];
+        metadata.write_comments(&mut file).unwrap();
+
+        let mut read = Metadata::init();
+        for (index, line) in String::from_utf8(file).unwrap().lines().enumerate() {
+            assert!(read.read_comment(index + 1, line).unwrap());
+        }
+
+        // Then:
+        assert_eq!(read.values, metadata.values);
+        assert!(read.attachment::<i32>("in memory").is_none());
+    }
+
+    #[test]
+    fn invalid_comments() {
+        let mut metadata = Metadata::init();
+
+        assert!(!metadata.read_comment(1, "# 1 2").unwrap());
+        for line in &[
+            "#@\tyear\tint\tmany",
+            "#@\tyear\tdate\t2021",
+            "#@\tkey\ttext",
+        ] {
+            let error = metadata.read_comment(2, line).unwrap_err();
+
+            assert_eq!(
+                error.downcast_ref::<Error>().unwrap().kind(),
+                &ErrorKind::InvalidFormat
+            );
+        }
+    }
+}
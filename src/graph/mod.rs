@@ -1,6 +1,13 @@
+mod delta;
 mod edge;
+mod equality;
+mod event;
+mod metadata;
 mod structs;
 mod error;
+mod ord_float;
+mod pretty;
+mod validation;
 
 /// Each subgraph must implement [`AsSubgraph`](crate::graph::subgraph::AsSubgraph) trait.
 /// This makes sure that from point of view of algorithms, there is no difference from graph and subgraphs.
@@ -10,6 +17,14 @@ mod error;
 /// So it just forwards every call to `AsSubgraph` functions to the inner `Subgraph`.
 pub mod subgraph;
 
+pub use delta::GraphDelta;
 pub use edge::{DefaultEdge, DirectedEdge, Edge, EdgeDir, FlowEdge, UndirectedEdge};
-pub use structs::{FlowMatGraph, MatGraph, ListGraph, FlowListGraph, SimpleGraph};
+pub use equality::structurally_equal;
+pub use structs::{FlowMatGraph, MatGraph, ListGraph, FlowListGraph, SimpleGraph, SparseMatGraph, FlowSparseMatGraph, LabeledGraph, SyncGraph, JournaledGraph, TemporalGraph, Interval, MultiLayerGraph, Hypergraph, PersistentGraph, DiPersistentGraph, FrozenGraph};
 pub use error::{Error, ErrorKind};
+pub use event::{GraphEvent, Observer};
+pub use metadata::{Metadata, MetadataValue};
+pub use ord_float::OrdFloat;
+pub use validation::Violation;
+
+pub(crate) use pretty::{impl_pretty, Layout};
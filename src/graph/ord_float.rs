@@ -0,0 +1,271 @@
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+use std::ops::{Add, Div, Mul, Sub};
+
+use anyhow::Result;
+use num_traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Float, One, ToPrimitive, Zero};
+
+use crate::graph::{Error, ErrorKind};
+
+/// A floating point number that is always finite, so it can be totally ordered.
+///
+/// Many algorithms like [`Kruskal`](crate::algo::Kruskal) and [`BellmanFord`](crate::algo::BellmanFord) require weights to implement `Ord`,
+/// which `f32` and `f64` do not implement because of NaN. Use `OrdFloat<f32>` or `OrdFloat<f64>` as weight of the graph instead.
+///
+/// Infinite weights are represented by [`Magnitude`](magnitude::Magnitude), so infinities are rejected too. This way arithmetic can not
+/// produce NaN, like infinity minus infinity. Arithmetic whose result is too large to be finite is treated like integer overflow:
+/// operators panic, and `checked_add`, `checked_sub`, `checked_mul` and `checked_div` return `None`.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::Kruskal;
+/// use prepona::storage::Mat;
+/// use prepona::graph::{MatGraph, OrdFloat};
+///
+/// let mut graph = MatGraph::init(Mat::<OrdFloat<f64>>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, OrdFloat::new(0.5).unwrap().into());
+/// graph.add_edge_unchecked(b, c, OrdFloat::new(1.5).unwrap().into());
+/// graph.add_edge_unchecked(a, c, OrdFloat::new(2.5).unwrap().into());
+///
+/// let mst = Kruskal::init(&graph).execute(&graph);
+///
+/// assert_eq!(mst.total_weight(), OrdFloat::new(2.0).unwrap().into());
+/// assert!(OrdFloat::new(f64::NAN).is_err());
+/// assert!(OrdFloat::new(f64::INFINITY).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct OrdFloat<F: Float>(F);
+
+impl<F: Float> OrdFloat<F> {
+    /// # Arguments
+    /// `value`: Value to wrap.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing the wrapped value.
+    /// * `Err`: [`InvalidWeight`](crate::graph::ErrorKind::InvalidWeight) if `value` is NaN or infinite.
+    pub fn new(value: F) -> Result<Self> {
+        if !value.is_finite() {
+            Err(Error::new(
+                ErrorKind::InvalidWeight,
+                String::from("NaN and infinities can not be used as weight"),
+            ))?
+        } else {
+            Ok(OrdFloat(value))
+        }
+    }
+
+    /// # Returns
+    /// The wrapped value.
+    pub fn value(&self) -> F {
+        self.0
+    }
+
+    // Result of arithmetic on two finite numbers can still be infinite, for example when multiplying two large numbers.
+    fn from_result(value: F) -> Self {
+        OrdFloat::new(value).expect("Arithmetic on weights overflowed")
+    }
+}
+
+impl<F: Float> Eq for OrdFloat<F> {}
+
+impl<F: Float> PartialOrd for OrdFloat<F> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<F: Float> Ord for OrdFloat<F> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Both values are finite, so they are always comparable.
+        self.0.partial_cmp(&other.0).unwrap()
+    }
+}
+
+impl<F: Float> Add for OrdFloat<F> {
+    type Output = Self;
+
+    /// # Panics
+    /// If the result is not finite.
+    fn add(self, rhs: Self) -> Self::Output {
+        OrdFloat::from_result(self.0 + rhs.0)
+    }
+}
+
+impl<F: Float> CheckedAdd for OrdFloat<F> {
+    /// Returns `None` if the result is not finite.
+    fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        OrdFloat::new(self.0 + rhs.0).ok()
+    }
+}
+
+impl<F: Float> CheckedSub for OrdFloat<F> {
+    /// Returns `None` if the result is not finite.
+    fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        OrdFloat::new(self.0 - rhs.0).ok()
+    }
+}
+
+impl<F: Float> CheckedMul for OrdFloat<F> {
+    /// Returns `None` if the result is not finite.
+    fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        OrdFloat::new(self.0 * rhs.0).ok()
+    }
+}
+
+impl<F: Float> CheckedDiv for OrdFloat<F> {
+    /// Returns `None` if the result is not finite, which includes division by zero.
+    fn checked_div(&self, rhs: &Self) -> Option<Self> {
+        OrdFloat::new(self.0 / rhs.0).ok()
+    }
+}
+
+impl<F: Float> Sub for OrdFloat<F> {
+    type Output = Self;
+
+    /// # Panics
+    /// If the result is not finite.
+    fn sub(self, rhs: Self) -> Self::Output {
+        OrdFloat::from_result(self.0 - rhs.0)
+    }
+}
+
+impl<F: Float> Mul for OrdFloat<F> {
+    type Output = Self;
+
+    /// # Panics
+    /// If the result is not finite.
+    fn mul(self, rhs: Self) -> Self::Output {
+        OrdFloat::from_result(self.0 * rhs.0)
+    }
+}
+
+impl<F: Float> Div for OrdFloat<F> {
+    type Output = Self;
+
+    /// # Panics
+    /// If the result is not finite, which includes division by zero.
+    fn div(self, rhs: Self) -> Self::Output {
+        OrdFloat::from_result(self.0 / rhs.0)
+    }
+}
+
+impl<F: Float> Zero for OrdFloat<F> {
+    fn zero() -> Self {
+        OrdFloat(F::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+}
+
+impl<F: Float> One for OrdFloat<F> {
+    fn one() -> Self {
+        OrdFloat(F::one())
+    }
+}
+
+impl<F: Float> ToPrimitive for OrdFloat<F> {
+    fn to_i64(&self) -> Option<i64> {
+        self.0.to_i64()
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        self.0.to_u64()
+    }
+
+    fn to_f64(&self) -> Option<f64> {
+        self.0.to_f64()
+    }
+}
+
+impl<F: Float + Display> Display for OrdFloat<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::BellmanFord;
+    use crate::graph::MatGraph;
+    use crate::provide::*;
+    use crate::storage::DiMat;
+
+    fn ord(value: f64) -> OrdFloat<f64> {
+        OrdFloat::new(value).unwrap()
+    }
+
+    #[test]
+    fn reject_non_finite() {
+        assert!(OrdFloat::new(f64::NAN).is_err());
+        assert!(OrdFloat::new(f32::NAN).is_err());
+        assert!(OrdFloat::new(f64::INFINITY).is_err());
+        assert!(OrdFloat::new(f32::NEG_INFINITY).is_err());
+        assert!(OrdFloat::new(f64::MAX).is_ok());
+    }
+
+    #[test]
+    fn total_order() {
+        let mut values = [ord(2.5), ord(-1.0), ord(f64::MAX), ord(0.0), ord(-0.0)];
+
+        values.sort();
+
+        assert_eq!(values[0], ord(-1.0));
+        assert_eq!(values[1].cmp(&values[2]), Ordering::Equal);
+        assert_eq!(values[3], ord(2.5));
+        assert_eq!(values[4], ord(f64::MAX));
+        assert_eq!(ord(1.5).max(ord(0.5)).value(), 1.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflowed")]
+    fn overflowing_result() {
+        let _ = ord(f64::MAX) + ord(f64::MAX);
+    }
+
+    #[test]
+    fn checked_ops() {
+        assert_eq!(ord(1.5).checked_add(&ord(2.0)), Some(ord(3.5)));
+        assert_eq!(ord(f64::MAX).checked_add(&ord(f64::MAX)), None);
+
+        assert_eq!(ord(1.5).checked_sub(&ord(2.0)), Some(ord(-0.5)));
+        assert_eq!(ord(-f64::MAX).checked_sub(&ord(f64::MAX)), None);
+
+        assert_eq!(ord(1.5).checked_mul(&ord(2.0)), Some(ord(3.0)));
+        assert_eq!(ord(f64::MAX).checked_mul(&ord(2.0)), None);
+
+        assert_eq!(ord(1.5).checked_div(&ord(2.0)), Some(ord(0.75)));
+        assert_eq!(ord(1.5).checked_div(&ord(0.0)), None);
+    }
+
+    #[test]
+    fn shortest_paths_with_float_weights() {
+        // Given: Graph
+        //         0.5      0.25
+        //      a  -->  b  -->  c
+        //      |               ^
+        //      '---------------'
+        //             1.0
+        //
+        let mut graph = MatGraph::init(DiMat::<OrdFloat<f64>>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, ord(0.5).into());
+        graph.add_edge_unchecked(b, c, ord(0.25).into());
+        graph.add_edge_unchecked(a, c, ord(1.0).into());
+
+        // When:
+        let sp_subgraph = BellmanFord::init(&graph).execute(&graph, a).unwrap();
+
+        // Then:
+        assert_eq!(sp_subgraph.distance_to(c), Some(ord(0.75).into()));
+        assert_eq!(sp_subgraph.path_to(c).unwrap(), vec![a, b, c]);
+    }
+}
@@ -0,0 +1,382 @@
+use std::collections::HashSet;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::marker::PhantomData;
+
+use magnitude::Magnitude;
+
+use crate::graph::Edge;
+
+// Graphs with at most this many vertices are displayed as a matrix.
+const MAX_MATRIX_SIZE: usize = 16;
+
+// Marks a pair of vertices that has no edge between them in matrix rendering.
+const NO_EDGE: &str = ".";
+
+fn weight_to_string<W>(weight: &Magnitude<W>, to_string: impl Fn(&W) -> String) -> String {
+    match weight {
+        Magnitude::Finite(value) => to_string(value),
+        _ if weight.is_pos_infinite() => String::from("inf"),
+        _ => String::from("-inf"),
+    }
+}
+
+/// Snapshot of the vertices and edges of a graph, used to implement `Debug` and `Display` for graphs, storages and subgraphs.
+///
+/// `Debug` writes an adjacency list, in which each line contains a vertex followed by its edges in the format of: `dst_id (id: edge_id, weight: weight)`.
+/// `Display` writes a matrix of weights if graph has at most 16 vertices, otherwise it writes an adjacency list in the format of: `dst_id: weight`.
+pub(crate) struct Layout<'a, W, E: Edge<W>> {
+    name: &'static str,
+    is_directed: bool,
+
+    // Vertices sorted by their ids, and edges from each vertex sorted by their destination ids.
+    vertices: Vec<usize>,
+    edges_of: Vec<Vec<(usize, &'a E)>>,
+
+    phantom_w: PhantomData<W>,
+}
+
+impl<'a, W, E: Edge<W>> Layout<'a, W, E> {
+    /// # Arguments
+    /// * `name`: Name of the type that is being formatted.
+    /// * `is_directed`: Whether edges are directed.
+    /// * `vertices`: Vertices of the graph.
+    /// * `edges_from`: Returns edges from a vertex in the format of: (`dst_id`, `edge`).
+    pub fn init(
+        name: &'static str,
+        is_directed: bool,
+        mut vertices: Vec<usize>,
+        edges_from: impl Fn(usize) -> Vec<(usize, &'a E)>,
+    ) -> Self {
+        vertices.sort_unstable();
+
+        let edges_of = vertices
+            .iter()
+            .map(|src_id| {
+                let mut edges = edges_from(*src_id);
+                edges.sort_by_key(|(dst_id, edge)| (*dst_id, edge.get_id()));
+                edges
+            })
+            .collect();
+
+        Layout {
+            name,
+            is_directed,
+            vertices,
+            edges_of,
+            phantom_w: PhantomData,
+        }
+    }
+
+    fn edge_count(&self) -> usize {
+        self.edges_of
+            .iter()
+            .flatten()
+            .map(|(_, edge)| edge.get_id())
+            .collect::<HashSet<usize>>()
+            .len()
+    }
+
+    fn fmt_header(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}({}, |V| = {}, |E| = {})",
+            self.name,
+            if self.is_directed {
+                "directed"
+            } else {
+                "undirected"
+            },
+            self.vertices.len(),
+            self.edge_count()
+        )
+    }
+
+    fn fmt_adjacency_list(
+        &self,
+        f: &mut Formatter<'_>,
+        edge_to_string: impl Fn(usize, &E) -> String,
+    ) -> fmt::Result {
+        self.fmt_header(f)?;
+        writeln!(f, " {{")?;
+
+        for (src_id, edges) in self.vertices.iter().zip(&self.edges_of) {
+            let edges = edges
+                .iter()
+                .map(|(dst_id, edge)| edge_to_string(*dst_id, edge))
+                .collect::<Vec<String>>();
+
+            writeln!(f, "    {}: [{}]", src_id, edges.join(", "))?;
+        }
+
+        write!(f, "}}")
+    }
+
+    fn fmt_matrix(&self, f: &mut Formatter<'_>, to_string: impl Fn(&W) -> String) -> fmt::Result {
+        let cells = self
+            .edges_of
+            .iter()
+            .map(|edges| {
+                self.vertices
+                    .iter()
+                    .map(|dst_id| {
+                        let weights = edges
+                            .iter()
+                            .filter(|(id, _)| id == dst_id)
+                            .map(|(_, edge)| weight_to_string(edge.get_weight(), &to_string))
+                            .collect::<Vec<String>>();
+
+                        if weights.is_empty() {
+                            String::from(NO_EDGE)
+                        } else {
+                            weights.join(",")
+                        }
+                    })
+                    .collect::<Vec<String>>()
+            })
+            .collect::<Vec<Vec<String>>>();
+
+        let id_width = self
+            .vertices
+            .iter()
+            .map(|vertex_id| vertex_id.to_string().len())
+            .max()
+            .unwrap_or(0);
+        let cell_width = cells
+            .iter()
+            .flatten()
+            .map(|cell| cell.len())
+            .max()
+            .unwrap_or(0)
+            .max(id_width);
+
+        self.fmt_header(f)?;
+        if self.vertices.is_empty() {
+            return Ok(());
+        }
+
+        write!(f, "\n{:>width$}", "", width = id_width)?;
+        for vertex_id in &self.vertices {
+            write!(f, " {:>width$}", vertex_id, width = cell_width)?;
+        }
+
+        for (src_id, row) in self.vertices.iter().zip(&cells) {
+            write!(f, "\n{:>width$}", src_id, width = id_width)?;
+            for cell in row {
+                write!(f, " {:>width$}", cell, width = cell_width)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Implements `Debug` and `Display` for a graph, storage or subgraph type by formatting its `Layout`.
+//
+// Arguments are the name written in the header, generic parameters of the impl in brackets, the type and bounds on the parameters.
+// Type must provide `vertices` and `edges_from_unchecked`, and the traits providing them must be in scope where the macro is used.
+macro_rules! impl_pretty {
+    ($name:literal, [$($generics:tt)*] $ty:ty, where $($bounds:tt)*) => {
+        /// Writes each vertex followed by its edges. `Display` implementation uses a more compact format.
+        impl<$($generics)*> std::fmt::Debug for $ty
+        where
+            W: std::fmt::Debug,
+            $($bounds)*
+        {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let layout = $crate::graph::Layout::init(
+                    $name,
+                    Dir::is_directed(),
+                    self.vertices(),
+                    |src_id| self.edges_from_unchecked(src_id),
+                );
+
+                std::fmt::Debug::fmt(&layout, f)
+            }
+        }
+
+        /// Writes a matrix of weights if there are at most 16 vertices, otherwise writes each vertex followed by its edges.
+        impl<$($generics)*> std::fmt::Display for $ty
+        where
+            W: std::fmt::Display,
+            $($bounds)*
+        {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let layout = $crate::graph::Layout::init(
+                    $name,
+                    Dir::is_directed(),
+                    self.vertices(),
+                    |src_id| self.edges_from_unchecked(src_id),
+                );
+
+                std::fmt::Display::fmt(&layout, f)
+            }
+        }
+    };
+}
+
+pub(crate) use impl_pretty;
+
+impl<'a, W: Debug, E: Edge<W>> Debug for Layout<'a, W, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_adjacency_list(f, |dst_id, edge| {
+            format!(
+                "{} (id: {}, weight: {})",
+                dst_id,
+                edge.get_id(),
+                weight_to_string(edge.get_weight(), |weight| format!("{:?}", weight))
+            )
+        })
+    }
+}
+
+impl<'a, W: Display, E: Edge<W>> Display for Layout<'a, W, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.vertices.len() <= MAX_MATRIX_SIZE {
+            self.fmt_matrix(f, |weight| weight.to_string())
+        } else {
+            self.fmt_adjacency_list(f, |dst_id, edge| {
+                format!(
+                    "{}: {}",
+                    dst_id,
+                    weight_to_string(edge.get_weight(), |weight| weight.to_string())
+                )
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::graph::subgraph::Subgraph;
+    use crate::graph::{DefaultEdge, ListGraph, MatGraph};
+    use crate::provide::*;
+    use crate::storage::{DiList, DiMat, GraphStorage, Mat};
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        assert_eq!(
+            format!("{:?}", graph),
+            "SimpleGraph(undirected, |V| = 0, |E| = 0) {\n}"
+        );
+        assert_eq!(
+            format!("{}", graph),
+            "SimpleGraph(undirected, |V| = 0, |E| = 0)"
+        );
+    }
+
+    #[test]
+    fn undirected_graph() {
+        // Given: Graph
+        //
+        //      a  --(1)--  b  --(12)--  c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(c, b, 12.into());
+
+        // Then:
+        assert_eq!(
+            format!("{:?}", graph),
+            "SimpleGraph(undirected, |V| = 3, |E| = 2) {\n\
+             \x20   0: [1 (id: 0, weight: 1)]\n\
+             \x20   1: [0 (id: 0, weight: 1), 2 (id: 1, weight: 12)]\n\
+             \x20   2: [1 (id: 1, weight: 12)]\n\
+             }"
+        );
+        assert_eq!(
+            format!("{}", graph),
+            "SimpleGraph(undirected, |V| = 3, |E| = 2)\n\
+             \x20  0  1  2\n\
+             0  .  1  .\n\
+             1  1  . 12\n\
+             2  . 12  ."
+        );
+    }
+
+    #[test]
+    fn directed_storage_with_infinite_weight() {
+        // Given: Storage
+        //
+        //      a  --(inf)-->  b
+        //
+        let mut storage = DiMat::<usize>::init();
+        let a = storage.add_vertex();
+        let b = storage.add_vertex();
+        storage.add_edge_unchecked(a, b, DefaultEdge::init(Magnitude::PosInfinite));
+
+        // Then:
+        assert_eq!(
+            format!("{:?}", storage),
+            "AdjMatrix(directed, |V| = 2, |E| = 1) {\n\
+             \x20   0: [1 (id: 0, weight: inf)]\n\
+             \x20   1: []\n\
+             }"
+        );
+        assert_eq!(
+            format!("{}", storage),
+            "AdjMatrix(directed, |V| = 2, |E| = 1)\n\
+             \x20   0   1\n\
+             0   . inf\n\
+             1   .   ."
+        );
+    }
+
+    #[test]
+    fn subgraph() {
+        // Given: Graph
+        //
+        //      a  --(1)--  b  --(2)--  c
+        //
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 2.into());
+
+        // When: Formatting subgraph containing a and b.
+        let subgraph = Subgraph::init(
+            &graph,
+            vec![(a, b, ab)],
+            [a, b].iter().copied().collect::<HashSet<usize>>(),
+        );
+
+        // Then:
+        assert_eq!(
+            format!("{:?}", subgraph),
+            "Subgraph(directed, |V| = 2, |E| = 1) {\n\
+             \x20   0: [1 (id: 0, weight: 1)]\n\
+             \x20   1: []\n\
+             }"
+        );
+    }
+
+    #[test]
+    fn large_graph() {
+        // Given: Path graph with 17 vertices.
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let vertices: Vec<usize> = (0..17).map(|_| graph.add_vertex()).collect();
+        for window in vertices.windows(2) {
+            graph.add_edge_unchecked(window[0], window[1], 1.into());
+        }
+
+        // When:
+        let display = format!("{}", graph);
+
+        // Then: Graph is displayed as an adjacency list.
+        let lines: Vec<&str> = display.lines().collect();
+        assert_eq!(lines.len(), 19);
+        assert_eq!(lines[0], "SimpleGraph(directed, |V| = 17, |E| = 16) {");
+        assert_eq!(lines[1], "    0: [1: 1]");
+        assert_eq!(lines[17], "    16: []");
+        assert_eq!(lines[18], "}");
+    }
+}
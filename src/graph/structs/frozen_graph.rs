@@ -0,0 +1,452 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use anyhow::Result;
+
+use crate::graph::{Edge, EdgeDir, Error, UndirectedEdge};
+use crate::provide::{self, Direction, Edges, IdMap, Neighbors, Vertices};
+
+/// Immutable copy of a graph, laid out for fast reads by algorithms.
+///
+/// Edges of all vertices are stored in one array in compressed sparse row(CSR) format, and edges of each vertex are sorted by their destination.
+/// The continuous id map and degree of each vertex are computed once when the graph is frozen.
+/// So running many algorithms on the same graph does not rebuild the id map on each run, and neighbors are read from contiguous memory.
+///
+/// Use [`SimpleGraph::freeze`](crate::graph::SimpleGraph::freeze) or [`from_graph`](crate::graph::FrozenGraph::from_graph) to create a frozen graph.
+/// Ids of vertices and edges are the same as the ids in the original graph.
+///
+/// ## Note
+/// From now on
+/// * |V|: Means number of vertices present in the graph.
+/// * |E|: Means number of edges present in the graph.
+/// * d: Means number of edges from a vertex.
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that graph uses.
+/// * `Dir`: **Dir**ection of edges: [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge).
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::MatGraph;
+/// use prepona::algo::Bfs;
+///
+/// // Given: Graph
+/// //
+/// //      a  -->  b  -->  c
+/// //      |               ^
+/// //      '---------------'
+/// //
+/// let mut graph = MatGraph::init(DiMat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(a, c, 1.into());
+///
+/// let frozen = graph.freeze();
+///
+/// assert_eq!(frozen.out_degree(a), 2);
+/// assert_eq!(frozen.in_degree(c), 2);
+/// assert_eq!(frozen.neighbors_unchecked(a), vec![b, c]);
+/// assert_eq!(Bfs::init_with_starts(&frozen, vec![a]).execute(&frozen).len(), 3);
+/// ```
+pub struct FrozenGraph<W, E: Edge<W>, Dir: EdgeDir = UndirectedEdge> {
+    id_map: IdMap,
+
+    // Edges from the vertex with virtual id `i` are in `offsets[i]..offsets[i + 1]` of `dst_ids` and `edges`.
+    offsets: Vec<usize>,
+    dst_ids: Vec<usize>,
+    edges: Vec<E>,
+
+    in_degrees: Vec<usize>,
+
+    // Position of each edge in `edges`. Undirected edges are stored twice, and only the position of the first one is kept.
+    position_of: HashMap<usize, usize>,
+
+    phantom_w: PhantomData<W>,
+    phantom_dir: PhantomData<Dir>,
+}
+
+impl<W, E: Edge<W> + Clone, Dir: EdgeDir> FrozenGraph<W, E, Dir> {
+    /// # Arguments
+    /// `graph`: Graph to be frozen. Direction of `graph` must match `Dir`.
+    ///
+    /// # Returns
+    /// Frozen copy of the graph.
+    ///
+    /// # Complexity
+    /// O(|V| + |E| * log(d))
+    pub fn from_graph<G>(graph: &G) -> Self
+    where
+        G: Vertices + Edges<W, E>,
+    {
+        let id_map = graph.continuous_id_map();
+        let vertex_count = id_map.len();
+
+        let mut offsets = Vec::with_capacity(vertex_count + 1);
+        let mut dst_ids = vec![];
+        let mut edges = vec![];
+        let mut in_degrees = vec![0; vertex_count];
+        let mut position_of = HashMap::new();
+
+        offsets.push(0);
+        for virt_id in 0..vertex_count {
+            let mut edges_from = graph.edges_from_unchecked(id_map.real_id_of(virt_id));
+            edges_from.sort_by_key(|(dst_id, _)| *dst_id);
+
+            for (dst_id, edge) in edges_from {
+                in_degrees[id_map.virt_id_of(dst_id)] += 1;
+                position_of.entry(edge.get_id()).or_insert(edges.len());
+
+                dst_ids.push(dst_id);
+                edges.push(edge.clone());
+            }
+
+            offsets.push(edges.len());
+        }
+
+        FrozenGraph {
+            id_map,
+
+            offsets,
+            dst_ids,
+            edges,
+
+            in_degrees,
+
+            position_of,
+
+            phantom_w: PhantomData,
+            phantom_dir: PhantomData,
+        }
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Number of edges from the vertex. For undirected graphs, it's the degree of the vertex.
+    ///
+    /// # Panics
+    /// If vertex does not exist.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn out_degree(&self, vertex_id: usize) -> usize {
+        let virt_id = self.id_map.virt_id_of(vertex_id);
+
+        self.offsets[virt_id + 1] - self.offsets[virt_id]
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Number of edges to the vertex. For undirected graphs, it's the degree of the vertex.
+    ///
+    /// # Panics
+    /// If vertex does not exist.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn in_degree(&self, vertex_id: usize) -> usize {
+        self.in_degrees[self.id_map.virt_id_of(vertex_id)]
+    }
+
+    // Range of the edges from the vertex in `dst_ids` and `edges`.
+    fn range_of(&self, src_id: usize) -> std::ops::Range<usize> {
+        let virt_id = self.id_map.virt_id_of(src_id);
+
+        self.offsets[virt_id]..self.offsets[virt_id + 1]
+    }
+
+    // Range of the edges from source to destination, found by binary search because edges of each vertex are sorted by their destination.
+    fn range_between(&self, src_id: usize, dst_id: usize) -> std::ops::Range<usize> {
+        let range = self.range_of(src_id);
+        let dst_ids = &self.dst_ids[range.clone()];
+
+        let start = dst_ids.partition_point(|n_id| *n_id < dst_id);
+        let end = dst_ids.partition_point(|n_id| *n_id <= dst_id);
+
+        range.start + start..range.start + end
+    }
+}
+
+/// For documentation about each function checkout [`Neighbors`](crate::provide::Neighbors) trait.
+impl<W, E: Edge<W> + Clone, Dir: EdgeDir> Neighbors for FrozenGraph<W, E, Dir> {
+    fn neighbors(&self, src_id: usize) -> Result<Vec<usize>> {
+        if !self.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else {
+            Ok(self.neighbors_unchecked(src_id))
+        }
+    }
+
+    /// # Returns
+    /// Id of vertices accessible from source vertex using one edge, in ascending order.
+    ///
+    /// # Complexity
+    /// O(d)
+    fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
+        let mut neighbors = self.dst_ids[self.range_of(src_id)].to_vec();
+
+        neighbors.dedup();
+
+        neighbors
+    }
+}
+
+/// For documentation about each function checkout [`Vertices`](crate::provide::Vertices) trait.
+impl<W, E: Edge<W> + Clone, Dir: EdgeDir> Vertices for FrozenGraph<W, E, Dir> {
+    fn vertices(&self) -> Vec<usize> {
+        (0..self.id_map.len())
+            .map(|virt_id| self.id_map.real_id_of(virt_id))
+            .collect()
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.id_map.len()
+    }
+
+    /// # Returns
+    /// The id map computed when the graph got frozen.
+    ///
+    /// # Complexity
+    /// O(1)
+    fn continuous_id_map(&self) -> IdMap {
+        self.id_map.clone()
+    }
+
+    fn contains_vertex(&self, vertex_id: usize) -> bool {
+        self.id_map.get_virt_id(vertex_id).is_some()
+    }
+}
+
+/// For documentation about each function checkout [`Direction`](crate::provide::Direction) trait.
+impl<W, E: Edge<W>, Dir: EdgeDir> Direction for FrozenGraph<W, E, Dir> {
+    fn is_directed(&self) -> bool {
+        Dir::is_directed()
+    }
+}
+
+/// For documentation about each function checkout [`Edges`](crate::provide::Edges) trait.
+impl<W, E: Edge<W> + Clone, Dir: EdgeDir> Edges<W, E> for FrozenGraph<W, E, Dir> {
+    fn edges_from(&self, src_id: usize) -> Result<Vec<(usize, &E)>> {
+        if !self.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else {
+            Ok(self.edges_from_unchecked(src_id))
+        }
+    }
+
+    /// # Returns
+    /// All edges from the source vertex in the format of: (`dst_id`, `edge`), in ascending order of `dst_id`.
+    ///
+    /// # Complexity
+    /// O(d)
+    fn edges_from_unchecked(&self, src_id: usize) -> Vec<(usize, &E)> {
+        self.range_of(src_id)
+            .map(|index| (self.dst_ids[index], &self.edges[index]))
+            .collect()
+    }
+
+    fn edges_between(&self, src_id: usize, dst_id: usize) -> Result<Vec<&E>> {
+        if !self.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else if !self.contains_vertex(dst_id) {
+            Err(Error::new_vnf(dst_id))?
+        } else {
+            Ok(self.edges_between_unchecked(src_id, dst_id))
+        }
+    }
+
+    /// # Complexity
+    /// O(log(d))
+    fn edges_between_unchecked(&self, src_id: usize, dst_id: usize) -> Vec<&E> {
+        self.edges[self.range_between(src_id, dst_id)]
+            .iter()
+            .collect()
+    }
+
+    fn edge_between(&self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<Option<&E>> {
+        if !self.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else if !self.contains_vertex(dst_id) {
+            Err(Error::new_vnf(dst_id))?
+        } else if !self.contains_edge(edge_id) {
+            Err(Error::new_enf(edge_id))?
+        } else {
+            Ok(self.edge_between_unchecked(src_id, dst_id, edge_id))
+        }
+    }
+
+    fn edge_between_unchecked(&self, src_id: usize, dst_id: usize, edge_id: usize) -> Option<&E> {
+        self.edges[self.range_between(src_id, dst_id)]
+            .iter()
+            .find(|edge| edge.get_id() == edge_id)
+    }
+
+    fn edge(&self, edge_id: usize) -> Result<Option<&E>> {
+        if !self.contains_edge(edge_id) {
+            Err(Error::new_enf(edge_id))?
+        } else {
+            Ok(self.edge_unchecked(edge_id))
+        }
+    }
+
+    /// # Complexity
+    /// O(1)
+    fn edge_unchecked(&self, edge_id: usize) -> Option<&E> {
+        self.position_of
+            .get(&edge_id)
+            .map(|position| &self.edges[*position])
+    }
+
+    fn has_any_edge(&self, src_id: usize, dst_id: usize) -> Result<bool> {
+        if !self.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else if !self.contains_vertex(dst_id) {
+            Err(Error::new_vnf(dst_id))?
+        } else {
+            Ok(self.has_any_edge_unchecked(src_id, dst_id))
+        }
+    }
+
+    /// # Complexity
+    /// O(log(d))
+    fn has_any_edge_unchecked(&self, src_id: usize, dst_id: usize) -> bool {
+        !self.range_between(src_id, dst_id).is_empty()
+    }
+
+    fn edges(&self) -> Vec<(usize, usize, &E)> {
+        if Dir::is_directed() {
+            self.as_directed_edges()
+        } else {
+            provide::undirected_edges(self.as_directed_edges())
+        }
+    }
+
+    fn as_directed_edges(&self) -> Vec<(usize, usize, &E)> {
+        (0..self.id_map.len())
+            .flat_map(|virt_id| {
+                let src_id = self.id_map.real_id_of(virt_id);
+
+                (self.offsets[virt_id]..self.offsets[virt_id + 1])
+                    .map(move |index| (src_id, self.dst_ids[index], &self.edges[index]))
+            })
+            .collect()
+    }
+
+    fn edges_count(&self) -> usize {
+        self.position_of.len()
+    }
+
+    fn contains_edge(&self, edge_id: usize) -> bool {
+        self.position_of.contains_key(&edge_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use magnitude::Magnitude;
+
+    use super::*;
+    use crate::graph::{ListGraph, MatGraph};
+    use crate::provide::Graph;
+    use crate::storage::{DiList, Mat};
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let frozen = graph.freeze();
+
+        assert_eq!(frozen.vertex_count(), 0);
+        assert_eq!(frozen.edges_count(), 0);
+        assert!(frozen.edges().is_empty());
+    }
+
+    #[test]
+    fn undirected_graph() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c       d
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+        let bc = graph.add_edge_unchecked(b, c, 2.into());
+        graph.remove_vertex_unchecked(a);
+
+        // When: Freezing the graph.
+        let frozen = graph.freeze();
+
+        // Then:
+        assert_eq!(frozen.vertices(), vec![b, c, d]);
+        assert!(!frozen.contains_vertex(a));
+        assert_eq!(frozen.edges_count(), 1);
+        assert!(!frozen.contains_edge(ab));
+        assert_eq!(frozen.edge_unchecked(bc).unwrap().get_weight(), &2.into());
+        assert_eq!(frozen.neighbors_unchecked(c), vec![b]);
+        assert!(frozen.has_any_edge_unchecked(c, b));
+        assert_eq!(frozen.out_degree(b), 1);
+        assert_eq!(frozen.in_degree(b), 1);
+        assert_eq!(frozen.out_degree(d), 0);
+        assert_eq!(frozen.edges().len(), 1);
+        assert_eq!(frozen.as_directed_edges().len(), 2);
+        assert!(frozen.neighbors(a).is_err());
+    }
+
+    #[test]
+    fn matches_original_graph() {
+        // Given: Directed list graph, where edges are added in descending order of destination.
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let vertices: Vec<usize> = (0..6).map(|_| graph.add_vertex()).collect();
+        for src_id in &vertices {
+            for dst_id in vertices
+                .iter()
+                .rev()
+                .filter(|dst_id| (*dst_id + src_id) % 2 == 1)
+            {
+                graph.add_edge_unchecked(*src_id, *dst_id, (src_id * 10 + dst_id).into());
+            }
+        }
+
+        // When: Freezing the graph.
+        let frozen = graph.freeze();
+
+        // Then: Frozen graph has the same edges, sorted by destination.
+        assert_eq!(frozen.continuous_id_map().len(), 6);
+        for src_id in &vertices {
+            let mut expected = graph
+                .edges_from_unchecked(*src_id)
+                .into_iter()
+                .map(|(dst_id, edge)| (dst_id, edge.get_id(), *edge.get_weight()))
+                .collect::<Vec<(usize, usize, Magnitude<usize>)>>();
+            expected.sort();
+            let actual = frozen
+                .edges_from_unchecked(*src_id)
+                .into_iter()
+                .map(|(dst_id, edge)| (dst_id, edge.get_id(), *edge.get_weight()))
+                .collect::<Vec<(usize, usize, Magnitude<usize>)>>();
+
+            assert_eq!(actual, expected);
+            assert_eq!(frozen.out_degree(*src_id), 3);
+            assert_eq!(frozen.in_degree(*src_id), 3);
+
+            for dst_id in &vertices {
+                assert_eq!(
+                    frozen.has_any_edge_unchecked(*src_id, *dst_id),
+                    graph.has_any_edge_unchecked(*src_id, *dst_id)
+                );
+            }
+        }
+    }
+}
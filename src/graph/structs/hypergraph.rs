@@ -0,0 +1,452 @@
+use std::collections::{BTreeSet, HashMap};
+use std::ops::Add;
+
+use anyhow::Result;
+use magnitude::Magnitude;
+use num_traits::One;
+
+use crate::graph::{Edge, Error, UndirectedEdge};
+use crate::provide::Graph;
+use crate::storage::IdAllocator;
+
+/// Graph whose edges, called hyperedges, can connect any number of vertices.
+///
+/// Algorithms of this crate work on graphs, so a hypergraph can be projected to a graph in two ways:
+/// * [`two_section_into`](crate::graph::Hypergraph::two_section_into): Connects every two vertices that are members of the same hyperedge.
+/// * [`incidence_graph_into`](crate::graph::Hypergraph::incidence_graph_into): Creates a bipartite graph with a vertex for each vertex and each hyperedge,
+///   and connects each hyperedge to its members.
+///
+/// Ids of vertices and hyperedges are allocated separately, and released ids are reused.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::Mat;
+/// use prepona::graph::{Hypergraph, MatGraph};
+///
+/// let mut hypergraph = Hypergraph::init();
+/// let a = hypergraph.add_vertex();
+/// let b = hypergraph.add_vertex();
+/// let c = hypergraph.add_vertex();
+/// let abc = hypergraph.add_hyperedge(&[a, b, c]).unwrap();
+/// hypergraph.add_hyperedge(&[a, b]).unwrap();
+///
+/// assert_eq!(hypergraph.members_of(abc).unwrap(), vec![a, b, c]);
+///
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let id_map = hypergraph.two_section_into(&mut graph);
+///
+/// // a and b are members of two hyperedges.
+/// let ab = graph.edges_between_unchecked(id_map[&a], id_map[&b]);
+/// assert_eq!(ab[0].get_weight(), &2.into());
+/// assert_eq!(graph.edges_count(), 3);
+/// ```
+#[derive(Debug)]
+pub struct Hypergraph {
+    vertex_ids: IdAllocator,
+    hyperedge_ids: IdAllocator,
+
+    members_of: HashMap<usize, BTreeSet<usize>>,
+    hyperedges_of: HashMap<usize, BTreeSet<usize>>,
+}
+
+impl Hypergraph {
+    /// # Returns
+    /// An empty hypergraph.
+    pub fn init() -> Self {
+        Hypergraph {
+            vertex_ids: IdAllocator::init(),
+            hyperedge_ids: IdAllocator::init(),
+
+            members_of: HashMap::new(),
+            hyperedges_of: HashMap::new(),
+        }
+    }
+
+    /// # Returns
+    /// Id of the new vertex.
+    pub fn add_vertex(&mut self) -> usize {
+        let vertex_id = self.vertex_ids.allocate();
+        self.hyperedges_of.insert(vertex_id, BTreeSet::new());
+
+        vertex_id
+    }
+
+    /// Removes the vertex and removes it from the hyperedges that it is a member of. Hyperedges are kept, even if they become empty.
+    ///
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex to be removed.
+    ///
+    /// # Returns
+    /// * `Ok`: If vertex is removed successfully.
+    /// * `Err`: [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if vertex does not exist.
+    pub fn remove_vertex(&mut self, vertex_id: usize) -> Result<()> {
+        let hyperedges = match self.hyperedges_of.remove(&vertex_id) {
+            Some(hyperedges) => hyperedges,
+            None => Err(Error::new_vnf(vertex_id))?,
+        };
+
+        for hyperedge_id in hyperedges {
+            if let Some(members) = self.members_of.get_mut(&hyperedge_id) {
+                members.remove(&vertex_id);
+            }
+        }
+        self.vertex_ids.release(vertex_id);
+
+        Ok(())
+    }
+
+    /// # Arguments
+    /// `members`: Id of the vertices that hyperedge connects. Repeated ids are counted once.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing id of the new hyperedge.
+    /// * `Err`: [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if a member does not exist.
+    pub fn add_hyperedge(&mut self, members: &[usize]) -> Result<usize> {
+        if let Some(vertex_id) = members
+            .iter()
+            .find(|vertex_id| !self.contains_vertex(**vertex_id))
+        {
+            Err(Error::new_vnf(*vertex_id))?
+        }
+
+        let hyperedge_id = self.hyperedge_ids.allocate();
+        for vertex_id in members {
+            self.hyperedges_of
+                .get_mut(vertex_id)
+                .unwrap()
+                .insert(hyperedge_id);
+        }
+        self.members_of
+            .insert(hyperedge_id, members.iter().copied().collect());
+
+        Ok(hyperedge_id)
+    }
+
+    /// # Arguments
+    /// `hyperedge_id`: Id of the hyperedge to be removed.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing id of the members of the removed hyperedge, in ascending order.
+    /// * `Err`: [`EdgeNotFound`](crate::graph::ErrorKind::EdgeNotFound) if hyperedge does not exist.
+    pub fn remove_hyperedge(&mut self, hyperedge_id: usize) -> Result<Vec<usize>> {
+        let members = match self.members_of.remove(&hyperedge_id) {
+            Some(members) => members,
+            None => Err(Error::new_enf(hyperedge_id))?,
+        };
+
+        for vertex_id in &members {
+            if let Some(hyperedges) = self.hyperedges_of.get_mut(vertex_id) {
+                hyperedges.remove(&hyperedge_id);
+            }
+        }
+        self.hyperedge_ids.release(hyperedge_id);
+
+        Ok(members.into_iter().collect())
+    }
+
+    /// # Returns
+    /// Id of the vertices, in ascending order.
+    pub fn vertices(&self) -> Vec<usize> {
+        let mut vertices = self.hyperedges_of.keys().copied().collect::<Vec<usize>>();
+        vertices.sort_unstable();
+
+        vertices
+    }
+
+    /// # Returns
+    /// Id of the hyperedges, in ascending order.
+    pub fn hyperedges(&self) -> Vec<usize> {
+        let mut hyperedges = self.members_of.keys().copied().collect::<Vec<usize>>();
+        hyperedges.sort_unstable();
+
+        hyperedges
+    }
+
+    /// # Returns
+    /// Number of vertices.
+    pub fn vertex_count(&self) -> usize {
+        self.hyperedges_of.len()
+    }
+
+    /// # Returns
+    /// Number of hyperedges.
+    pub fn hyperedge_count(&self) -> usize {
+        self.members_of.len()
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// `true` if vertex exists, `false` otherwise.
+    pub fn contains_vertex(&self, vertex_id: usize) -> bool {
+        self.hyperedges_of.contains_key(&vertex_id)
+    }
+
+    /// # Arguments
+    /// `hyperedge_id`: Id of the hyperedge.
+    ///
+    /// # Returns
+    /// `true` if hyperedge exists, `false` otherwise.
+    pub fn contains_hyperedge(&self, hyperedge_id: usize) -> bool {
+        self.members_of.contains_key(&hyperedge_id)
+    }
+
+    /// # Arguments
+    /// `hyperedge_id`: Id of the hyperedge.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing id of the members of the hyperedge, in ascending order.
+    /// * `Err`: [`EdgeNotFound`](crate::graph::ErrorKind::EdgeNotFound) if hyperedge does not exist.
+    pub fn members_of(&self, hyperedge_id: usize) -> Result<Vec<usize>> {
+        match self.members_of.get(&hyperedge_id) {
+            Some(members) => Ok(members.iter().copied().collect()),
+            None => Err(Error::new_enf(hyperedge_id))?,
+        }
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing id of the hyperedges that vertex is a member of, in ascending order.
+    /// * `Err`: [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if vertex does not exist.
+    pub fn hyperedges_of(&self, vertex_id: usize) -> Result<Vec<usize>> {
+        match self.hyperedges_of.get(&vertex_id) {
+            Some(hyperedges) => Ok(hyperedges.iter().copied().collect()),
+            None => Err(Error::new_vnf(vertex_id))?,
+        }
+    }
+
+    /// Adds vertices of the hypergraph to `target`, and connects every two vertices that are members of the same hyperedge.
+    /// Weight of each edge is the number of hyperedges that both of its end points are members of.
+    ///
+    /// # Arguments
+    /// `target`: Graph to add the vertices and edges of the 2-section to.
+    ///
+    /// # Returns
+    /// Id of each vertex in `target`, mapped from its id in the hypergraph.
+    ///
+    /// # Complexity
+    /// O(|V| + sum of squared sizes of hyperedges) plus cost of adding vertices and edges to `target`.
+    pub fn two_section_into<W, E, G>(&self, target: &mut G) -> HashMap<usize, usize>
+    where
+        W: One + Add<Output = W> + Copy,
+        E: Edge<W>,
+        G: Graph<W, E, UndirectedEdge>,
+    {
+        let id_map = self.add_vertices_into(target);
+
+        let mut end_points = vec![];
+        let mut weight_between: HashMap<(usize, usize), Magnitude<W>> = HashMap::new();
+        for hyperedge_id in self.hyperedges() {
+            let members = self.members_of[&hyperedge_id]
+                .iter()
+                .copied()
+                .collect::<Vec<usize>>();
+
+            for (index, src_id) in members.iter().enumerate() {
+                for dst_id in &members[index + 1..] {
+                    match weight_between.get_mut(&(*src_id, *dst_id)) {
+                        Some(weight) => *weight += W::one().into(),
+                        None => {
+                            end_points.push((*src_id, *dst_id));
+                            weight_between.insert((*src_id, *dst_id), W::one().into());
+                        }
+                    }
+                }
+            }
+        }
+
+        for (src_id, dst_id) in end_points {
+            let weight = weight_between[&(src_id, dst_id)];
+
+            target.add_edge_unchecked(id_map[&src_id], id_map[&dst_id], E::init(weight));
+        }
+
+        id_map
+    }
+
+    /// Adds a vertex to `target` for each vertex and each hyperedge of the hypergraph, and connects each hyperedge to its members with an edge of weight one.
+    ///
+    /// # Arguments
+    /// `target`: Graph to add the vertices and edges of the incidence graph to.
+    ///
+    /// # Returns
+    /// Id of each vertex in `target` mapped from its id in the hypergraph, and id of each hyperedge in `target` mapped from its id in the hypergraph.
+    ///
+    /// # Complexity
+    /// O(|V| + number of hyperedges + sum of sizes of hyperedges) plus cost of adding vertices and edges to `target`.
+    pub fn incidence_graph_into<W, E, G>(
+        &self,
+        target: &mut G,
+    ) -> (HashMap<usize, usize>, HashMap<usize, usize>)
+    where
+        W: One,
+        E: Edge<W>,
+        G: Graph<W, E, UndirectedEdge>,
+    {
+        let vertex_map = self.add_vertices_into(target);
+
+        let hyperedge_map = self
+            .hyperedges()
+            .into_iter()
+            .map(|hyperedge_id| (hyperedge_id, target.add_vertex()))
+            .collect::<HashMap<usize, usize>>();
+
+        for hyperedge_id in self.hyperedges() {
+            for vertex_id in &self.members_of[&hyperedge_id] {
+                target.add_edge_unchecked(
+                    vertex_map[vertex_id],
+                    hyperedge_map[&hyperedge_id],
+                    E::init(W::one().into()),
+                );
+            }
+        }
+
+        (vertex_map, hyperedge_map)
+    }
+
+    fn add_vertices_into<W, E, G>(&self, target: &mut G) -> HashMap<usize, usize>
+    where
+        E: Edge<W>,
+        G: Graph<W, E, UndirectedEdge>,
+    {
+        self.vertices()
+            .into_iter()
+            .map(|vertex_id| (vertex_id, target.add_vertex()))
+            .collect()
+    }
+}
+
+impl Default for Hypergraph {
+    fn default() -> Self {
+        Hypergraph::init()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ErrorKind, ListGraph, MatGraph};
+    use crate::provide::*;
+    use crate::storage::{List, Mat};
+
+    #[test]
+    fn empty_hypergraph() {
+        let hypergraph = Hypergraph::init();
+
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let id_map = hypergraph.two_section_into(&mut graph);
+
+        assert!(id_map.is_empty());
+        assert_eq!(hypergraph.vertex_count(), 0);
+        assert_eq!(hypergraph.hyperedge_count(), 0);
+    }
+
+    #[test]
+    fn add_and_remove() {
+        // Given: Hypergraph with hyperedges {a, b, c} and {c, d}.
+        let mut hypergraph = Hypergraph::init();
+        let a = hypergraph.add_vertex();
+        let b = hypergraph.add_vertex();
+        let c = hypergraph.add_vertex();
+        let d = hypergraph.add_vertex();
+        let abc = hypergraph.add_hyperedge(&[c, a, b, a]).unwrap();
+        let cd = hypergraph.add_hyperedge(&[c, d]).unwrap();
+
+        // When:
+        hypergraph.remove_vertex(c).unwrap();
+        let removed_members = hypergraph.remove_hyperedge(abc).unwrap();
+
+        // Then:
+        assert_eq!(removed_members, vec![a, b]);
+        assert_eq!(hypergraph.vertices(), vec![a, b, d]);
+        assert_eq!(hypergraph.hyperedges(), vec![cd]);
+        assert_eq!(hypergraph.members_of(cd).unwrap(), vec![d]);
+        assert!(hypergraph.hyperedges_of(a).unwrap().is_empty());
+        assert!(!hypergraph.contains_hyperedge(abc));
+    }
+
+    #[test]
+    fn missing_vertices_and_hyperedges() {
+        let mut hypergraph = Hypergraph::init();
+        let a = hypergraph.add_vertex();
+
+        let add_result = hypergraph.add_hyperedge(&[a, a + 1]);
+        let remove_result = hypergraph.remove_hyperedge(0);
+
+        assert!(matches!(
+            add_result
+                .unwrap_err()
+                .downcast_ref::<Error>()
+                .unwrap()
+                .kind(),
+            ErrorKind::VertexNotFound
+        ));
+        assert!(matches!(
+            remove_result
+                .unwrap_err()
+                .downcast_ref::<Error>()
+                .unwrap()
+                .kind(),
+            ErrorKind::EdgeNotFound
+        ));
+        assert!(hypergraph.hyperedges_of(a).unwrap().is_empty());
+    }
+
+    #[test]
+    fn incidence_graph() {
+        // Given: Hypergraph with hyperedges {a, b, c} and {c, d}.
+        let mut hypergraph = Hypergraph::init();
+        let a = hypergraph.add_vertex();
+        let b = hypergraph.add_vertex();
+        let c = hypergraph.add_vertex();
+        let d = hypergraph.add_vertex();
+        let abc = hypergraph.add_hyperedge(&[a, b, c]).unwrap();
+        let cd = hypergraph.add_hyperedge(&[c, d]).unwrap();
+
+        // When:
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let (vertex_map, hyperedge_map) = hypergraph.incidence_graph_into(&mut graph);
+
+        // Then:
+        assert_eq!(graph.vertex_count(), 6);
+        assert_eq!(graph.edges_count(), 5);
+        assert!(graph.has_any_edge_unchecked(vertex_map[&c], hyperedge_map[&abc]));
+        assert!(graph.has_any_edge_unchecked(vertex_map[&c], hyperedge_map[&cd]));
+        assert!(!graph.has_any_edge_unchecked(vertex_map[&a], hyperedge_map[&cd]));
+        assert!(!graph.has_any_edge_unchecked(vertex_map[&a], vertex_map[&b]));
+    }
+
+    #[test]
+    fn two_section() {
+        // Given: Hypergraph with hyperedges {a, b, c}, {b, c} and {d}.
+        let mut hypergraph = Hypergraph::init();
+        let a = hypergraph.add_vertex();
+        let b = hypergraph.add_vertex();
+        let c = hypergraph.add_vertex();
+        let d = hypergraph.add_vertex();
+        hypergraph.add_hyperedge(&[a, b, c]).unwrap();
+        hypergraph.add_hyperedge(&[b, c]).unwrap();
+        hypergraph.add_hyperedge(&[d]).unwrap();
+
+        // When:
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let id_map = hypergraph.two_section_into(&mut graph);
+
+        // Then:
+        let weight_between = |src_id, dst_id| {
+            graph.edges_between_unchecked(id_map[&src_id], id_map[&dst_id])[0]
+                .get_weight()
+                .unwrap()
+        };
+        assert_eq!(graph.vertex_count(), 4);
+        assert_eq!(graph.edges_count(), 3);
+        assert_eq!(weight_between(a, b), 1);
+        assert_eq!(weight_between(a, c), 1);
+        assert_eq!(weight_between(b, c), 2);
+        assert!(graph.neighbors_unchecked(id_map[&d]).is_empty());
+    }
+}
@@ -0,0 +1,716 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use anyhow::Result;
+use provide::{Direction, Edges, Graph, Neighbors, Vertices};
+
+use crate::graph::{Edge, EdgeDir};
+use crate::provide;
+
+// Restoring a removed vertex or edge may assign a different id to it. So entries of the journal refer to vertices and edges by ids that stay the same
+// during the lifetime of the journal, and `Ids` translates them to the ids that are currently used by the graph.
+#[derive(Default)]
+struct Ids {
+    real_of: HashMap<usize, usize>,
+    journal_of: HashMap<usize, usize>,
+    next_id: usize,
+}
+
+impl Ids {
+    fn journal_id(&mut self, real_id: usize) -> usize {
+        if let Some(journal_id) = self.journal_of.get(&real_id) {
+            return *journal_id;
+        }
+
+        let journal_id = self.next_id;
+        self.next_id += 1;
+        self.bind(journal_id, real_id);
+
+        journal_id
+    }
+
+    fn real_id(&self, journal_id: usize) -> usize {
+        self.real_of[&journal_id]
+    }
+
+    fn bind(&mut self, journal_id: usize, real_id: usize) {
+        self.real_of.insert(journal_id, real_id);
+        self.journal_of.insert(real_id, journal_id);
+    }
+
+    fn unbind(&mut self, journal_id: usize) {
+        if let Some(real_id) = self.real_of.remove(&journal_id) {
+            self.journal_of.remove(&real_id);
+        }
+    }
+}
+
+// Edges are stored in the format of: (`src_id`, `dst_id`, `edge_id`, `edge`).
+enum Mutation<E> {
+    AddVertex(usize),
+    RemoveVertex(usize, Vec<(usize, usize, usize, E)>),
+    AddEdge(usize, usize, usize, E),
+    UpdateEdge(usize, usize, usize, E, E),
+    RemoveEdge(usize, usize, usize, E),
+}
+
+/// Wraps a graph and records its mutations, so they can be undone and redone.
+///
+/// All mutations through the [`Graph`](crate::provide::Graph) trait are recorded. Mutating the graph after undoing some mutations discards them,
+/// so they can no longer be redone. A checkpoint marks the current position in the journal, and the graph can be rolled back to it later.
+///
+/// Restored vertices and edges get new ids from the wrapped graph, which are not necessarily the same as their previous ids.
+/// The journal keeps track of these changes, so its entries remain valid regardless.
+///
+/// `JournaledGraph` implements all provider traits that the wrapped graph implements, so it can be passed directly to algorithms.
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that graph uses.
+/// * `Dir`: **Dir**ection of edges: [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge).
+/// * `G`: **G**raph that is being journaled.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::Mat;
+/// use prepona::graph::{JournaledGraph, MatGraph};
+///
+/// let mut graph = JournaledGraph::init(MatGraph::init(Mat::<usize>::init()));
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let checkpoint = graph.checkpoint();
+///
+/// let ab = graph.add_edge_unchecked(a, b, 1.into());
+/// graph.update_edge_unchecked(a, b, ab, 2.into());
+///
+/// assert!(graph.undo());
+/// assert_eq!(graph.edge_unchecked(ab).unwrap().get_weight(), &1.into());
+///
+/// graph.rollback_to(checkpoint);
+/// assert_eq!(graph.edges_count(), 0);
+///
+/// assert!(graph.redo());
+/// assert_eq!(graph.edges_count(), 1);
+/// ```
+pub struct JournaledGraph<W, E, Dir, G>
+where
+    E: Edge<W> + Clone,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices + Edges<W, E>,
+{
+    graph: G,
+
+    undo_stack: Vec<Mutation<E>>,
+    redo_stack: Vec<Mutation<E>>,
+
+    vertex_ids: Ids,
+    edge_ids: Ids,
+
+    phantom_w: PhantomData<W>,
+    phantom_dir: PhantomData<Dir>,
+}
+
+impl<W, E, Dir, G> JournaledGraph<W, E, Dir, G>
+where
+    E: Edge<W> + Clone,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices + Edges<W, E>,
+{
+    /// # Arguments
+    /// `graph`: Graph to be journaled. Mutations before wrapping the graph are not recorded.
+    ///
+    /// # Returns
+    /// Initialized journaled graph with empty journal.
+    pub fn init(graph: G) -> Self {
+        JournaledGraph {
+            graph,
+
+            undo_stack: vec![],
+            redo_stack: vec![],
+
+            vertex_ids: Ids::default(),
+            edge_ids: Ids::default(),
+
+            phantom_w: PhantomData,
+            phantom_dir: PhantomData,
+        }
+    }
+
+    /// Reverts the last recorded mutation that is not undone yet.
+    ///
+    /// # Returns
+    /// `true` if a mutation got undone, `false` if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        if let Some(mutation) = self.undo_stack.pop() {
+            match &mutation {
+                Mutation::AddVertex(vertex_id) => {
+                    self.remove_vertex_of(*vertex_id);
+                }
+                Mutation::RemoveVertex(vertex_id, edges) => {
+                    self.add_vertex_as(*vertex_id);
+                    for (src_id, dst_id, edge_id, edge) in edges {
+                        self.add_edge_as(*src_id, *dst_id, *edge_id, edge.clone());
+                    }
+                }
+                Mutation::AddEdge(src_id, dst_id, edge_id, _) => {
+                    self.remove_edge_of(*src_id, *dst_id, *edge_id);
+                }
+                Mutation::UpdateEdge(src_id, dst_id, edge_id, old_edge, _) => {
+                    self.update_edge_of(*src_id, *dst_id, *edge_id, old_edge.clone())
+                }
+                Mutation::RemoveEdge(src_id, dst_id, edge_id, edge) => {
+                    self.add_edge_as(*src_id, *dst_id, *edge_id, edge.clone())
+                }
+            }
+
+            self.redo_stack.push(mutation);
+
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Applies the last undone mutation again.
+    ///
+    /// # Returns
+    /// `true` if a mutation got redone, `false` if there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        if let Some(mutation) = self.redo_stack.pop() {
+            match &mutation {
+                Mutation::AddVertex(vertex_id) => self.add_vertex_as(*vertex_id),
+                Mutation::RemoveVertex(vertex_id, _) => {
+                    self.remove_vertex_of(*vertex_id);
+                }
+                Mutation::AddEdge(src_id, dst_id, edge_id, edge) => {
+                    self.add_edge_as(*src_id, *dst_id, *edge_id, edge.clone())
+                }
+                Mutation::UpdateEdge(src_id, dst_id, edge_id, _, new_edge) => {
+                    self.update_edge_of(*src_id, *dst_id, *edge_id, new_edge.clone())
+                }
+                Mutation::RemoveEdge(src_id, dst_id, edge_id, _) => {
+                    self.remove_edge_of(*src_id, *dst_id, *edge_id);
+                }
+            }
+
+            self.undo_stack.push(mutation);
+
+            true
+        } else {
+            false
+        }
+    }
+
+    /// # Returns
+    /// `true` if there is any mutation to undo, `false` otherwise.
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    /// # Returns
+    /// `true` if there is any mutation to redo, `false` otherwise.
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// # Returns
+    /// Checkpoint of the current state of the graph, to be used with [`rollback_to`](crate::graph::JournaledGraph::rollback_to).
+    /// A checkpoint is no longer valid after the mutations before it are undone and the graph is mutated.
+    pub fn checkpoint(&self) -> usize {
+        self.undo_stack.len()
+    }
+
+    /// Undoes mutations until the graph is in the state of `checkpoint`. Undone mutations can be redone.
+    /// Does nothing if no mutation is recorded after `checkpoint`.
+    ///
+    /// # Arguments
+    /// `checkpoint`: Checkpoint returned by [`checkpoint`](crate::graph::JournaledGraph::checkpoint).
+    pub fn rollback_to(&mut self, checkpoint: usize) {
+        while self.undo_stack.len() > checkpoint {
+            self.undo();
+        }
+    }
+
+    /// Discards all recorded mutations, so they can not be undone or redone.
+    pub fn clear_journal(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+
+    /// # Returns
+    /// The wrapped graph.
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+
+    /// # Returns
+    /// The wrapped graph, dropping the journal.
+    pub fn into_graph(self) -> G {
+        self.graph
+    }
+
+    fn record(&mut self, mutation: Mutation<E>) {
+        self.undo_stack.push(mutation);
+        self.redo_stack.clear();
+    }
+
+    fn add_vertex_as(&mut self, vertex_id: usize) {
+        let real_id = self.graph.add_vertex();
+
+        self.vertex_ids.bind(vertex_id, real_id);
+    }
+
+    // Returns the removed vertex and its edges with their journal ids.
+    fn remove_real_vertex(&mut self, real_id: usize) -> (usize, Vec<(usize, usize, usize, E)>) {
+        let vertex_id = self.vertex_ids.journal_id(real_id);
+
+        let edges = self
+            .graph
+            .remove_vertex_collect_unchecked(real_id)
+            .into_iter()
+            .map(|(src_id, dst_id, edge)| {
+                let edge_id = self.edge_ids.journal_id(edge.get_id());
+                self.edge_ids.unbind(edge_id);
+
+                (
+                    self.vertex_ids.journal_id(src_id),
+                    self.vertex_ids.journal_id(dst_id),
+                    edge_id,
+                    edge,
+                )
+            })
+            .collect();
+
+        self.vertex_ids.unbind(vertex_id);
+
+        (vertex_id, edges)
+    }
+
+    fn remove_vertex_of(&mut self, vertex_id: usize) {
+        let real_id = self.vertex_ids.real_id(vertex_id);
+
+        self.remove_real_vertex(real_id);
+    }
+
+    fn add_edge_as(&mut self, src_id: usize, dst_id: usize, edge_id: usize, edge: E) {
+        let real_id = self.graph.add_edge_unchecked(
+            self.vertex_ids.real_id(src_id),
+            self.vertex_ids.real_id(dst_id),
+            edge,
+        );
+
+        self.edge_ids.bind(edge_id, real_id);
+    }
+
+    fn update_edge_of(&mut self, src_id: usize, dst_id: usize, edge_id: usize, edge: E) {
+        self.graph.update_edge_unchecked(
+            self.vertex_ids.real_id(src_id),
+            self.vertex_ids.real_id(dst_id),
+            self.edge_ids.real_id(edge_id),
+            edge,
+        );
+    }
+
+    fn remove_edge_of(&mut self, src_id: usize, dst_id: usize, edge_id: usize) {
+        self.graph.remove_edge_unchecked(
+            self.vertex_ids.real_id(src_id),
+            self.vertex_ids.real_id(dst_id),
+            self.edge_ids.real_id(edge_id),
+        );
+
+        self.edge_ids.unbind(edge_id);
+    }
+
+    fn record_add_edge(&mut self, src_id: usize, dst_id: usize, edge_id: usize) {
+        if let Some(edge) = self.graph.edge_unchecked(edge_id).cloned() {
+            let mutation = Mutation::AddEdge(
+                self.vertex_ids.journal_id(src_id),
+                self.vertex_ids.journal_id(dst_id),
+                self.edge_ids.journal_id(edge_id),
+                edge,
+            );
+
+            self.record(mutation);
+        }
+    }
+
+    fn record_update_edge(&mut self, src_id: usize, dst_id: usize, edge_id: usize, old_edge: E) {
+        if let Some(new_edge) = self.graph.edge_unchecked(edge_id).cloned() {
+            let mutation = Mutation::UpdateEdge(
+                self.vertex_ids.journal_id(src_id),
+                self.vertex_ids.journal_id(dst_id),
+                self.edge_ids.journal_id(edge_id),
+                old_edge,
+                new_edge,
+            );
+
+            self.record(mutation);
+        }
+    }
+
+    fn record_remove_edge(&mut self, src_id: usize, dst_id: usize, edge: &E) {
+        let edge_id = self.edge_ids.journal_id(edge.get_id());
+        self.edge_ids.unbind(edge_id);
+
+        let mutation = Mutation::RemoveEdge(
+            self.vertex_ids.journal_id(src_id),
+            self.vertex_ids.journal_id(dst_id),
+            edge_id,
+            edge.clone(),
+        );
+
+        self.record(mutation);
+    }
+}
+
+/// For documentation about each function checkout [`Graph`](crate::provide::Graph) trait.
+/// Each successful mutation is recorded in the journal.
+impl<W, E, Dir, G> Graph<W, E, Dir> for JournaledGraph<W, E, Dir, G>
+where
+    E: Edge<W> + Clone,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices + Edges<W, E>,
+{
+    fn add_vertex(&mut self) -> usize {
+        let real_id = self.graph.add_vertex();
+
+        let vertex_id = self.vertex_ids.journal_id(real_id);
+        self.record(Mutation::AddVertex(vertex_id));
+
+        real_id
+    }
+
+    fn remove_vertex(&mut self, vertex_id: usize) -> Result<()> {
+        if !self.graph.contains_vertex(vertex_id) {
+            self.graph.remove_vertex(vertex_id)
+        } else {
+            self.remove_vertex_unchecked(vertex_id);
+
+            Ok(())
+        }
+    }
+
+    fn remove_vertex_unchecked(&mut self, vertex_id: usize) {
+        let (vertex_id, edges) = self.remove_real_vertex(vertex_id);
+
+        self.record(Mutation::RemoveVertex(vertex_id, edges));
+    }
+
+    fn add_edge(&mut self, src_id: usize, dst_id: usize, edge: E) -> Result<usize> {
+        let edge_id = self.graph.add_edge(src_id, dst_id, edge)?;
+        self.record_add_edge(src_id, dst_id, edge_id);
+
+        Ok(edge_id)
+    }
+
+    fn add_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge: E) -> usize {
+        let edge_id = self.graph.add_edge_unchecked(src_id, dst_id, edge);
+        self.record_add_edge(src_id, dst_id, edge_id);
+
+        edge_id
+    }
+
+    fn update_edge(&mut self, src_id: usize, dst_id: usize, edge_id: usize, edge: E) -> Result<()> {
+        let old_edge = self.graph.edge_between(src_id, dst_id, edge_id)?.cloned();
+
+        self.graph.update_edge(src_id, dst_id, edge_id, edge)?;
+        if let Some(old_edge) = old_edge {
+            self.record_update_edge(src_id, dst_id, edge_id, old_edge);
+        }
+
+        Ok(())
+    }
+
+    fn update_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize, edge: E) {
+        let old_edge = self.graph.edge_unchecked(edge_id).cloned();
+
+        self.graph
+            .update_edge_unchecked(src_id, dst_id, edge_id, edge);
+        if let Some(old_edge) = old_edge {
+            self.record_update_edge(src_id, dst_id, edge_id, old_edge);
+        }
+    }
+
+    fn remove_edge(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<Option<E>> {
+        let edge = self.graph.remove_edge(src_id, dst_id, edge_id)?;
+        if let Some(edge) = &edge {
+            self.record_remove_edge(src_id, dst_id, edge);
+        }
+
+        Ok(edge)
+    }
+
+    fn remove_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> Option<E> {
+        let edge = self.graph.remove_edge_unchecked(src_id, dst_id, edge_id);
+        if let Some(edge) = &edge {
+            self.record_remove_edge(src_id, dst_id, edge);
+        }
+
+        edge
+    }
+}
+
+/// For documentation about each function checkout [`Neighbors`](crate::provide::Neighbors) trait.
+impl<W, E, Dir, G> Neighbors for JournaledGraph<W, E, Dir, G>
+where
+    E: Edge<W> + Clone,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices + Edges<W, E> + Neighbors,
+{
+    fn neighbors(&self, src_id: usize) -> Result<Vec<usize>> {
+        self.graph.neighbors(src_id)
+    }
+
+    fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
+        self.graph.neighbors_unchecked(src_id)
+    }
+}
+
+/// For documentation about each function checkout [`Vertices`](crate::provide::Vertices) trait.
+impl<W, E, Dir, G> Vertices for JournaledGraph<W, E, Dir, G>
+where
+    E: Edge<W> + Clone,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices + Edges<W, E>,
+{
+    fn vertices(&self) -> Vec<usize> {
+        self.graph.vertices()
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.graph.vertex_count()
+    }
+
+    fn contains_vertex(&self, vertex_id: usize) -> bool {
+        self.graph.contains_vertex(vertex_id)
+    }
+}
+
+/// For documentation about each function checkout [`Direction`](crate::provide::Direction) trait.
+impl<W, E, Dir, G> Direction for JournaledGraph<W, E, Dir, G>
+where
+    E: Edge<W> + Clone,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices + Edges<W, E>,
+{
+    fn is_directed(&self) -> bool {
+        Dir::is_directed()
+    }
+}
+
+/// For documentation about each function checkout [`Edges`](crate::provide::Edges) trait.
+impl<W, E, Dir, G> Edges<W, E> for JournaledGraph<W, E, Dir, G>
+where
+    E: Edge<W> + Clone,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices + Edges<W, E>,
+{
+    fn edges_from(&self, src_id: usize) -> Result<Vec<(usize, &E)>> {
+        self.graph.edges_from(src_id)
+    }
+
+    fn edges_from_unchecked(&self, src_id: usize) -> Vec<(usize, &E)> {
+        self.graph.edges_from_unchecked(src_id)
+    }
+
+    fn edges_between(&self, src_id: usize, dst_id: usize) -> Result<Vec<&E>> {
+        self.graph.edges_between(src_id, dst_id)
+    }
+
+    fn edges_between_unchecked(&self, src_id: usize, dst_id: usize) -> Vec<&E> {
+        self.graph.edges_between_unchecked(src_id, dst_id)
+    }
+
+    fn edge_between(&self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<Option<&E>> {
+        self.graph.edge_between(src_id, dst_id, edge_id)
+    }
+
+    fn edge_between_unchecked(&self, src_id: usize, dst_id: usize, edge_id: usize) -> Option<&E> {
+        self.graph.edge_between_unchecked(src_id, dst_id, edge_id)
+    }
+
+    fn edge(&self, edge_id: usize) -> Result<Option<&E>> {
+        self.graph.edge(edge_id)
+    }
+
+    fn edge_unchecked(&self, edge_id: usize) -> Option<&E> {
+        self.graph.edge_unchecked(edge_id)
+    }
+
+    fn has_any_edge(&self, src_id: usize, dst_id: usize) -> Result<bool> {
+        self.graph.has_any_edge(src_id, dst_id)
+    }
+
+    fn has_any_edge_unchecked(&self, src_id: usize, dst_id: usize) -> bool {
+        self.graph.has_any_edge_unchecked(src_id, dst_id)
+    }
+
+    fn edges(&self) -> Vec<(usize, usize, &E)> {
+        self.graph.edges()
+    }
+
+    fn as_directed_edges(&self) -> Vec<(usize, usize, &E)> {
+        self.graph.as_directed_edges()
+    }
+
+    fn edges_count(&self) -> usize {
+        self.graph.edges_count()
+    }
+
+    fn contains_edge(&self, edge_id: usize) -> bool {
+        self.graph.contains_edge(edge_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{structurally_equal, ListGraph, MatGraph};
+    use crate::storage::{DiList, Mat};
+
+    #[test]
+    fn undo_and_redo() {
+        // Given: Empty graph.
+        let mut graph = JournaledGraph::init(ListGraph::init(DiList::<usize>::init()));
+        assert!(!graph.can_undo());
+        assert!(!graph.undo());
+
+        // When: Building graph
+        //
+        //      a  --(1)-->  b  --(2)-->  c
+        //
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let ab = graph.add_edge(a, b, 1.into()).unwrap();
+        let bc = graph.add_edge(b, c, 2.into()).unwrap();
+
+        // And: Undoing the last edge.
+        assert!(graph.undo());
+
+        // Then:
+        assert!(!graph.contains_edge(bc));
+        assert!(graph.can_redo());
+
+        // When: Redoing it.
+        assert!(graph.redo());
+
+        // Then:
+        assert_eq!(graph.edges_count(), 2);
+        assert!(!graph.can_redo());
+
+        // When: Undoing everything.
+        while graph.undo() {}
+
+        // Then:
+        assert_eq!(graph.vertex_count(), 0);
+
+        // When: Redoing everything.
+        while graph.redo() {}
+
+        // Then:
+        assert_eq!(graph.vertex_count(), 3);
+        assert_eq!(graph.neighbors(a).unwrap(), vec![b]);
+        assert_eq!(graph.neighbors(b).unwrap(), vec![c]);
+        assert!(graph.edge_between_unchecked(a, b, ab).is_some());
+    }
+
+    #[test]
+    fn undo_vertex_removal() {
+        // Given: Graph
+        //
+        //      a  --(1)--  b  --(2)--  c
+        //      |                       |
+        //      '----------(3)----------'
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 2.into());
+        let ca = graph.add_edge_unchecked(c, a, 3.into());
+        let copy = graph.map_edges(Mat::<usize>::init(), |_, _, edge| *edge);
+
+        let mut graph = JournaledGraph::init(graph);
+
+        // When: Removing b, updating an edge and undoing both.
+        graph.remove_vertex(b).unwrap();
+        graph.update_edge(c, a, ca, 4.into()).unwrap();
+        assert_eq!(graph.edges_count(), 1);
+        graph.undo();
+        graph.undo();
+
+        // Then:
+        assert!(structurally_equal(&graph, &copy));
+        assert!(!graph.can_undo());
+
+        // When: Redoing the removal of b.
+        graph.redo();
+
+        // Then:
+        assert_eq!(graph.vertex_count(), 2);
+        assert_eq!(graph.edges_count(), 1);
+    }
+
+    #[test]
+    fn rollback_to_checkpoint() {
+        // Given: Graph with vertices a and b.
+        let mut graph = JournaledGraph::init(MatGraph::init(Mat::<usize>::init()));
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+
+        // When: Taking a checkpoint and mutating the graph.
+        let checkpoint = graph.checkpoint();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(b, c, 2.into());
+        graph.remove_edge_unchecked(a, b, ab);
+        graph.remove_vertex_unchecked(a);
+
+        // And: Rolling back to the checkpoint.
+        graph.rollback_to(checkpoint);
+
+        // Then:
+        assert_eq!(graph.vertex_count(), 2);
+        assert_eq!(graph.edges_count(), 1);
+        assert_eq!(graph.checkpoint(), checkpoint);
+        assert_eq!(graph.edges()[0].2.get_weight(), &1.into());
+
+        // When: Mutating the graph after rollback.
+        graph.add_vertex();
+
+        // Then: Rolled back mutations can no longer be redone.
+        assert!(!graph.can_redo());
+        assert!(!graph.redo());
+    }
+
+    #[test]
+    fn restored_edges_keep_their_place_in_journal() {
+        // Given: Graph with edge from a to b.
+        let mut graph = JournaledGraph::init(ListGraph::init(DiList::<usize>::init()));
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+
+        // When: Updating and removing the edge, then adding and removing an edge from b to a.
+        graph.update_edge_unchecked(a, b, ab, 2.into());
+        graph.remove_edge_unchecked(a, b, ab);
+        let ba = graph.add_edge_unchecked(b, a, 3.into());
+        graph.remove_edge_unchecked(b, a, ba);
+
+        // And: Undoing all of them except adding the first edge.
+        graph.rollback_to(3);
+
+        // Then:
+        assert_eq!(graph.edges_count(), 1);
+        assert_eq!(graph.edges()[0].2.get_weight(), &1.into());
+
+        // When: Redoing all of them.
+        while graph.redo() {}
+
+        // Then:
+        assert_eq!(graph.edges_count(), 0);
+    }
+}
@@ -0,0 +1,477 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use anyhow::Result;
+use provide::{Direction, Edges, Graph, Neighbors, Vertices};
+
+use crate::graph::{Edge, EdgeDir, Error};
+use crate::provide;
+
+/// Wraps a graph and maps each vertex to a unique label, so vertices can be accessed by their labels instead of their ids.
+///
+/// Labels are optional: vertices added using `add_vertex` of the [`Graph`](crate::provide::Graph) trait have no label until one is assigned by `set_label`.
+/// Removing a vertex, either by its label or by its id, also removes its label. So the label can be used for another vertex.
+///
+/// `LabeledGraph` implements all provider traits that the wrapped graph implements, so it can be passed directly to algorithms.
+///
+/// ## Generic Parameters
+/// * `L`: **L**abel type of vertices.
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that graph uses.
+/// * `Dir`: **Dir**ection of edges: [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge).
+/// * `G`: **G**raph that is being labeled.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::Mat;
+/// use prepona::graph::{LabeledGraph, MatGraph};
+///
+/// let mut graph = LabeledGraph::init(MatGraph::init(Mat::<usize>::init()));
+/// let a = graph.add_labeled_vertex("a").unwrap();
+/// graph.add_labeled_vertex("b").unwrap();
+/// graph.add_labeled_edge(&"a", &"b", 1.into()).unwrap();
+///
+/// assert_eq!(graph.id_of(&"a"), Some(a));
+/// assert_eq!(graph.label_of(a), Some(&"a"));
+/// assert!(graph.has_any_edge_unchecked(a, graph.id_of(&"b").unwrap()));
+///
+/// graph.remove_labeled_vertex(&"a").unwrap();
+/// assert_eq!(graph.id_of(&"a"), None);
+/// assert_eq!(graph.label_of(a), None);
+/// ```
+pub struct LabeledGraph<L, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir>,
+{
+    graph: G,
+
+    id_of: HashMap<L, usize>,
+    label_of: HashMap<usize, L>,
+
+    phantom_w: PhantomData<W>,
+    phantom_e: PhantomData<E>,
+    phantom_dir: PhantomData<Dir>,
+}
+
+impl<L, W, E, Dir, G> LabeledGraph<L, W, E, Dir, G>
+where
+    L: Hash + Eq + Clone + Debug,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices,
+{
+    /// # Arguments
+    /// `graph`: Graph to be labeled. Vertices that are already in the graph have no label.
+    ///
+    /// # Returns
+    /// Initialized labeled graph.
+    pub fn init(graph: G) -> Self {
+        LabeledGraph {
+            graph,
+
+            id_of: HashMap::new(),
+            label_of: HashMap::new(),
+
+            phantom_w: PhantomData,
+            phantom_e: PhantomData,
+            phantom_dir: PhantomData,
+        }
+    }
+
+    /// # Arguments
+    /// `label`: Label of the new vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing id of the newly added vertex.
+    /// * `Err`: [`LabelAlreadyExists`](crate::graph::ErrorKind::LabelAlreadyExists) if another vertex has the same label.
+    pub fn add_labeled_vertex(&mut self, label: L) -> Result<usize> {
+        if let Some(vertex_id) = self.id_of.get(&label) {
+            Err(Error::new_lae(&label, *vertex_id))?
+        }
+
+        let vertex_id = self.graph.add_vertex();
+        self.id_of.insert(label.clone(), vertex_id);
+        self.label_of.insert(vertex_id, label);
+
+        Ok(vertex_id)
+    }
+
+    /// Assigns `label` to the vertex with id: `vertex_id`, replacing its previous label if any.
+    ///
+    /// # Arguments
+    /// * `vertex_id`: Id of the vertex.
+    /// * `label`: New label of the vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: If label is assigned successfully.
+    /// * `Err`:
+    ///   * [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if vertex does not exist.
+    ///   * [`LabelAlreadyExists`](crate::graph::ErrorKind::LabelAlreadyExists) if another vertex has the same label.
+    pub fn set_label(&mut self, vertex_id: usize, label: L) -> Result<()> {
+        if !self.graph.contains_vertex(vertex_id) {
+            Err(Error::new_vnf(vertex_id))?
+        }
+
+        match self.id_of.get(&label) {
+            Some(other_id) if *other_id != vertex_id => Err(Error::new_lae(&label, *other_id))?,
+            _ => {
+                self.unlabel(vertex_id);
+                self.id_of.insert(label.clone(), vertex_id);
+                self.label_of.insert(vertex_id, label);
+
+                Ok(())
+            }
+        }
+    }
+
+    /// # Arguments
+    /// `label`: Label of the vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing id of the vertex with the specified label.
+    /// * `None`: If no vertex has the specified label.
+    pub fn id_of(&self, label: &L) -> Option<usize> {
+        self.id_of.get(label).copied()
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing label of the vertex.
+    /// * `None`: If vertex does not exist or has no label.
+    pub fn label_of(&self, vertex_id: usize) -> Option<&L> {
+        self.label_of.get(&vertex_id)
+    }
+
+    /// Adds `edge` from vertex with label: `src_label` to vertex with label: `dst_label`.
+    ///
+    /// # Arguments
+    /// * `src_label`: Label of the source vertex.
+    /// * `dst_label`: Label of the destination vertex.
+    /// * `edge`: Edge to be added from source to destination.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing id of the newly added edge.
+    /// * `Err`:
+    ///   * [`LabelNotFound`](crate::graph::ErrorKind::LabelNotFound) if no vertex has one of the labels.
+    ///   * Any error that `add_edge` of the wrapped graph returns.
+    pub fn add_labeled_edge(&mut self, src_label: &L, dst_label: &L, edge: E) -> Result<usize> {
+        let src_id = self.checked_id_of(src_label)?;
+        let dst_id = self.checked_id_of(dst_label)?;
+
+        self.graph.add_edge(src_id, dst_id, edge)
+    }
+
+    /// Removes the vertex with label: `label` and its label.
+    ///
+    /// # Arguments
+    /// `label`: Label of the vertex to be removed.
+    ///
+    /// # Returns
+    /// * `Ok`: If vertex is removed successfully.
+    /// * `Err`: [`LabelNotFound`](crate::graph::ErrorKind::LabelNotFound) if no vertex has the specified label.
+    pub fn remove_labeled_vertex(&mut self, label: &L) -> Result<()> {
+        let vertex_id = self.checked_id_of(label)?;
+
+        self.remove_vertex(vertex_id)
+    }
+
+    /// # Returns
+    /// The wrapped graph.
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+
+    /// # Returns
+    /// The wrapped graph, dropping the labels.
+    pub fn into_graph(self) -> G {
+        self.graph
+    }
+
+    fn checked_id_of(&self, label: &L) -> Result<usize> {
+        match self.id_of(label) {
+            Some(vertex_id) => Ok(vertex_id),
+            None => Err(Error::new_lnf(label))?,
+        }
+    }
+
+    fn unlabel(&mut self, vertex_id: usize) {
+        if let Some(label) = self.label_of.remove(&vertex_id) {
+            self.id_of.remove(&label);
+        }
+    }
+}
+
+/// For documentation about each function checkout [`Graph`](crate::provide::Graph) trait.
+/// Removing a vertex also removes its label.
+impl<L, W, E, Dir, G> Graph<W, E, Dir> for LabeledGraph<L, W, E, Dir, G>
+where
+    L: Hash + Eq + Clone + Debug,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices,
+{
+    fn add_vertex(&mut self) -> usize {
+        self.graph.add_vertex()
+    }
+
+    fn remove_vertex(&mut self, vertex_id: usize) -> Result<()> {
+        self.graph.remove_vertex(vertex_id)?;
+        self.unlabel(vertex_id);
+
+        Ok(())
+    }
+
+    fn remove_vertex_unchecked(&mut self, vertex_id: usize) {
+        self.graph.remove_vertex_unchecked(vertex_id);
+        self.unlabel(vertex_id);
+    }
+
+    fn add_edge(&mut self, src_id: usize, dst_id: usize, edge: E) -> Result<usize> {
+        self.graph.add_edge(src_id, dst_id, edge)
+    }
+
+    fn add_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge: E) -> usize {
+        self.graph.add_edge_unchecked(src_id, dst_id, edge)
+    }
+
+    fn update_edge(&mut self, src_id: usize, dst_id: usize, edge_id: usize, edge: E) -> Result<()> {
+        self.graph.update_edge(src_id, dst_id, edge_id, edge)
+    }
+
+    fn update_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize, edge: E) {
+        self.graph
+            .update_edge_unchecked(src_id, dst_id, edge_id, edge)
+    }
+
+    fn remove_edge(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<Option<E>> {
+        self.graph.remove_edge(src_id, dst_id, edge_id)
+    }
+
+    fn remove_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> Option<E> {
+        self.graph.remove_edge_unchecked(src_id, dst_id, edge_id)
+    }
+}
+
+/// For documentation about each function checkout [`Neighbors`](crate::provide::Neighbors) trait.
+impl<L, W, E, Dir, G> Neighbors for LabeledGraph<L, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Neighbors,
+{
+    fn neighbors(&self, src_id: usize) -> Result<Vec<usize>> {
+        self.graph.neighbors(src_id)
+    }
+
+    fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
+        self.graph.neighbors_unchecked(src_id)
+    }
+}
+
+/// For documentation about each function checkout [`Vertices`](crate::provide::Vertices) trait.
+impl<L, W, E, Dir, G> Vertices for LabeledGraph<L, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices,
+{
+    fn vertices(&self) -> Vec<usize> {
+        self.graph.vertices()
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.graph.vertex_count()
+    }
+
+    fn contains_vertex(&self, vertex_id: usize) -> bool {
+        self.graph.contains_vertex(vertex_id)
+    }
+}
+
+/// For documentation about each function checkout [`Direction`](crate::provide::Direction) trait.
+impl<L, W, E, Dir, G> Direction for LabeledGraph<L, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir>,
+{
+    fn is_directed(&self) -> bool {
+        Dir::is_directed()
+    }
+}
+
+/// For documentation about each function checkout [`Edges`](crate::provide::Edges) trait.
+impl<L, W, E, Dir, G> Edges<W, E> for LabeledGraph<L, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Edges<W, E>,
+{
+    fn edges_from(&self, src_id: usize) -> Result<Vec<(usize, &E)>> {
+        self.graph.edges_from(src_id)
+    }
+
+    fn edges_from_unchecked(&self, src_id: usize) -> Vec<(usize, &E)> {
+        self.graph.edges_from_unchecked(src_id)
+    }
+
+    fn edges_between(&self, src_id: usize, dst_id: usize) -> Result<Vec<&E>> {
+        self.graph.edges_between(src_id, dst_id)
+    }
+
+    fn edges_between_unchecked(&self, src_id: usize, dst_id: usize) -> Vec<&E> {
+        self.graph.edges_between_unchecked(src_id, dst_id)
+    }
+
+    fn edge_between(&self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<Option<&E>> {
+        self.graph.edge_between(src_id, dst_id, edge_id)
+    }
+
+    fn edge_between_unchecked(&self, src_id: usize, dst_id: usize, edge_id: usize) -> Option<&E> {
+        self.graph.edge_between_unchecked(src_id, dst_id, edge_id)
+    }
+
+    fn edge(&self, edge_id: usize) -> Result<Option<&E>> {
+        self.graph.edge(edge_id)
+    }
+
+    fn edge_unchecked(&self, edge_id: usize) -> Option<&E> {
+        self.graph.edge_unchecked(edge_id)
+    }
+
+    fn has_any_edge(&self, src_id: usize, dst_id: usize) -> Result<bool> {
+        self.graph.has_any_edge(src_id, dst_id)
+    }
+
+    fn has_any_edge_unchecked(&self, src_id: usize, dst_id: usize) -> bool {
+        self.graph.has_any_edge_unchecked(src_id, dst_id)
+    }
+
+    fn edges(&self) -> Vec<(usize, usize, &E)> {
+        self.graph.edges()
+    }
+
+    fn as_directed_edges(&self) -> Vec<(usize, usize, &E)> {
+        self.graph.as_directed_edges()
+    }
+
+    fn edges_count(&self) -> usize {
+        self.graph.edges_count()
+    }
+
+    fn contains_edge(&self, edge_id: usize) -> bool {
+        self.graph.contains_edge(edge_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::Dijkstra;
+    use crate::graph::{ErrorKind, ListGraph, MatGraph};
+    use crate::storage::{DiList, Mat};
+
+    #[test]
+    fn labels_of_vertices() {
+        // Given: Graph
+        //
+        //      a  --(1)--  b  --(2)--  c
+        //
+        let mut graph = LabeledGraph::init(MatGraph::init(Mat::<usize>::init()));
+        let a = graph.add_labeled_vertex("a").unwrap();
+        let b = graph.add_labeled_vertex("b").unwrap();
+        let c = graph.add_labeled_vertex("c").unwrap();
+        let ab = graph.add_labeled_edge(&"a", &"b", 1.into()).unwrap();
+        graph.add_labeled_edge(&"c", &"b", 2.into()).unwrap();
+
+        // Then:
+        assert_eq!(graph.id_of(&"a"), Some(a));
+        assert_eq!(graph.id_of(&"b"), Some(b));
+        assert_eq!(graph.id_of(&"c"), Some(c));
+        assert_eq!(graph.id_of(&"d"), None);
+        assert_eq!(graph.label_of(b), Some(&"b"));
+        assert!(graph.edge_between_unchecked(b, a, ab).is_some());
+        assert_eq!(graph.edges_count(), 2);
+
+        // And: Algorithms can be executed on the labeled graph.
+        let sp_subgraph = Dijkstra::init(&graph).execute(&graph, a);
+        assert_eq!(
+            sp_subgraph.distance_to(graph.id_of(&"c").unwrap()),
+            Some(3.into())
+        );
+    }
+
+    #[test]
+    fn duplicate_and_missing_labels() {
+        // Given: Graph with vertex a.
+        let mut graph = LabeledGraph::init(ListGraph::init(DiList::<usize>::init()));
+        let a = graph.add_labeled_vertex(String::from("a")).unwrap();
+
+        // When: Adding another vertex with the same label.
+        let result = graph.add_labeled_vertex(String::from("a"));
+
+        // Then:
+        assert!(matches!(
+            result.unwrap_err().downcast_ref::<Error>().unwrap().kind(),
+            ErrorKind::LabelAlreadyExists
+        ));
+        assert_eq!(graph.vertex_count(), 1);
+
+        // When: Adding an edge to a vertex that does not exist.
+        let result = graph.add_labeled_edge(&String::from("a"), &String::from("b"), 1.into());
+
+        // Then:
+        assert!(matches!(
+            result.unwrap_err().downcast_ref::<Error>().unwrap().kind(),
+            ErrorKind::LabelNotFound
+        ));
+        assert_eq!(graph.id_of(&String::from("a")), Some(a));
+    }
+
+    #[test]
+    fn labels_are_consistent_after_removals() {
+        // Given: Graph
+        //
+        //      a  --(1)-->  b
+        //
+        let mut graph = LabeledGraph::init(ListGraph::init(DiList::<usize>::init()));
+        let a = graph.add_labeled_vertex('a').unwrap();
+        let b = graph.add_labeled_vertex('b').unwrap();
+        graph.add_labeled_edge(&'a', &'b', 1.into()).unwrap();
+
+        // When: Removing a by its label, and b by its id.
+        graph.remove_labeled_vertex(&'a').unwrap();
+        graph.remove_vertex(b).unwrap();
+
+        // Then:
+        assert_eq!(graph.vertex_count(), 0);
+        assert_eq!(graph.id_of(&'a'), None);
+        assert_eq!(graph.label_of(a), None);
+        assert_eq!(graph.id_of(&'b'), None);
+        assert!(graph.remove_labeled_vertex(&'a').is_err());
+
+        // When: Reusing the labels.
+        let new_a = graph.add_labeled_vertex('a').unwrap();
+        let unlabeled = graph.add_vertex();
+        graph.set_label(unlabeled, 'b').unwrap();
+
+        // Then:
+        assert_eq!(graph.id_of(&'a'), Some(new_a));
+        assert_eq!(graph.label_of(unlabeled), Some(&'b'));
+        assert!(graph.set_label(unlabeled, 'a').is_err());
+
+        // When: Relabeling b.
+        graph.set_label(unlabeled, 'c').unwrap();
+
+        // Then:
+        assert_eq!(graph.id_of(&'b'), None);
+        assert_eq!(graph.id_of(&'c'), Some(unlabeled));
+    }
+}
@@ -1,3 +1,20 @@
+mod frozen_graph;
+mod hypergraph;
+mod journaled_graph;
+mod labeled_graph;
+mod multi_layer_graph;
+mod persistent_graph;
+mod persistent_vec;
 mod simple_graph;
+mod sync_graph;
+mod temporal_graph;
 
-pub use simple_graph::{FlowMatGraph, MatGraph, ListGraph, FlowListGraph, SimpleGraph};
+pub use simple_graph::{FlowMatGraph, MatGraph, ListGraph, FlowListGraph, SimpleGraph, SparseMatGraph, FlowSparseMatGraph};
+pub use frozen_graph::FrozenGraph;
+pub use hypergraph::Hypergraph;
+pub use journaled_graph::JournaledGraph;
+pub use labeled_graph::LabeledGraph;
+pub use multi_layer_graph::MultiLayerGraph;
+pub use persistent_graph::{DiPersistentGraph, PersistentGraph};
+pub use sync_graph::SyncGraph;
+pub use temporal_graph::{Interval, TemporalGraph};
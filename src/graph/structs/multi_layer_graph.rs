@@ -0,0 +1,677 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::ops::Add;
+
+use anyhow::Result;
+use magnitude::Magnitude;
+
+use crate::graph::subgraph::Subgraph;
+use crate::graph::{Edge, EdgeDir};
+use crate::provide::{Direction, Edges, Graph, Neighbors, Vertices};
+
+/// Wraps a graph and assigns its edges to layers, so different kinds of relationships between the same vertices can be kept in one graph.
+///
+/// All layers share the vertices of the wrapped graph. Each edge belongs to at most one layer,
+/// and edges added using `add_edge` of the [`Graph`](crate::provide::Graph) trait belong to no layer.
+/// Two vertices can be connected in multiple layers, so the storage of the wrapped graph must support multi edges(like [`List`](crate::storage::List)) when layers overlap.
+/// Within each layer, edges are checked by the wrapped graph as usual.
+///
+/// A layer can be viewed on its own as a subgraph, containing all vertices and the edges of that layer.
+/// The flattened view contains the edges of all layers, and [`aggregate_into`](crate::graph::MultiLayerGraph::aggregate_into) merges
+/// edges between the same vertices into a single edge whose weight is the sum of their weights.
+///
+/// `MultiLayerGraph` implements all provider traits that the wrapped graph implements, so it can be passed directly to algorithms.
+///
+/// ## Generic Parameters
+/// * `K`: **K**ey type of layers.
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that graph uses.
+/// * `Dir`: **Dir**ection of edges: [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge).
+/// * `G`: **G**raph that is being layered.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::List;
+/// use prepona::graph::{ListGraph, MultiLayerGraph};
+///
+/// let mut graph = MultiLayerGraph::init(ListGraph::init(List::<usize>::init()));
+/// let alice = graph.add_vertex();
+/// let bob = graph.add_vertex();
+/// let carol = graph.add_vertex();
+/// graph.add_layered_edge("friend", alice, bob, 1.into()).unwrap();
+/// graph.add_layered_edge("colleague", alice, bob, 2.into()).unwrap();
+/// graph.add_layered_edge("colleague", bob, carol, 3.into()).unwrap();
+///
+/// let friends = graph.layer(&"friend").unwrap();
+/// assert!(!friends.has_any_edge_unchecked(bob, carol));
+/// assert_eq!(friends.vertex_count(), 3);
+///
+/// let mut aggregated = ListGraph::init(List::<usize>::init());
+/// let id_map = graph.aggregate_into(&mut aggregated);
+/// let weights = aggregated.edges_between_unchecked(id_map[&alice], id_map[&bob]);
+/// assert_eq!(weights.len(), 1);
+/// assert_eq!(weights[0].get_weight(), &3.into());
+/// ```
+pub struct MultiLayerGraph<K, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir>,
+{
+    graph: G,
+
+    layer_of: HashMap<usize, K>,
+    edges_of: HashMap<K, HashSet<usize>>,
+
+    phantom_w: PhantomData<W>,
+    phantom_e: PhantomData<E>,
+    phantom_dir: PhantomData<Dir>,
+}
+
+impl<K, W, E, Dir, G> MultiLayerGraph<K, W, E, Dir, G>
+where
+    K: Hash + Eq + Clone,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices + Neighbors + Edges<W, E>,
+{
+    /// # Arguments
+    /// `graph`: Graph to be layered. Edges that are already in the graph belong to no layer.
+    ///
+    /// # Returns
+    /// Initialized multi-layer graph without any layer.
+    pub fn init(graph: G) -> Self {
+        MultiLayerGraph {
+            graph,
+
+            layer_of: HashMap::new(),
+            edges_of: HashMap::new(),
+
+            phantom_w: PhantomData,
+            phantom_e: PhantomData,
+            phantom_dir: PhantomData,
+        }
+    }
+
+    /// # Arguments
+    /// `layer`: Key of the new layer.
+    ///
+    /// # Returns
+    /// `true` if layer is added, `false` if it already exists.
+    pub fn add_layer(&mut self, layer: K) -> bool {
+        match self.edges_of.entry(layer) {
+            Entry::Occupied(_) => false,
+            Entry::Vacant(entry) => {
+                entry.insert(HashSet::new());
+
+                true
+            }
+        }
+    }
+
+    /// Removes the layer along with all of its edges.
+    ///
+    /// # Arguments
+    /// `layer`: Key of the layer.
+    ///
+    /// # Returns
+    /// `true` if layer is removed, `false` if it does not exist.
+    pub fn remove_layer(&mut self, layer: &K) -> bool {
+        let edge_ids = match self.edges_of.remove(layer) {
+            Some(edge_ids) => edge_ids,
+            None => return false,
+        };
+
+        let edges = self
+            .graph
+            .edges()
+            .into_iter()
+            .filter(|(_, _, edge)| edge_ids.contains(&edge.get_id()))
+            .map(|(src_id, dst_id, edge)| (src_id, dst_id, edge.get_id()))
+            .collect::<Vec<(usize, usize, usize)>>();
+
+        for (src_id, dst_id, edge_id) in edges {
+            if self.layer_of.remove(&edge_id).is_some() {
+                self.graph.remove_edge_unchecked(src_id, dst_id, edge_id);
+            }
+        }
+
+        true
+    }
+
+    /// # Arguments
+    /// `layer`: Key of the layer.
+    ///
+    /// # Returns
+    /// `true` if layer exists, `false` otherwise.
+    pub fn contains_layer(&self, layer: &K) -> bool {
+        self.edges_of.contains_key(layer)
+    }
+
+    /// # Returns
+    /// Keys of the layers, in no particular order.
+    pub fn layers(&self) -> Vec<&K> {
+        self.edges_of.keys().collect()
+    }
+
+    /// Adds `edge` to `layer`. Layer gets added if it does not exist.
+    ///
+    /// # Arguments
+    /// * `layer`: Key of the layer.
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    /// * `edge`: Edge to be added from source to destination.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing id of the newly added edge.
+    /// * `Err`: Error of adding the edge to the wrapped graph, ignoring edges of other layers between the same vertices.
+    pub fn add_layered_edge(
+        &mut self,
+        layer: K,
+        src_id: usize,
+        dst_id: usize,
+        edge: E,
+    ) -> Result<usize> {
+        let edge_ids = self
+            .graph
+            .edges_between(src_id, dst_id)?
+            .into_iter()
+            .map(|edge| edge.get_id())
+            .collect::<Vec<usize>>();
+
+        // Edges of other layers would make the wrapped graph reject the edge as a multi edge.
+        let is_in_other_layers = !edge_ids.is_empty()
+            && edge_ids.iter().all(
+                |edge_id| matches!(self.layer_of.get(edge_id), Some(other) if *other != layer),
+            );
+
+        let edge_id = if is_in_other_layers {
+            self.graph.add_edge_unchecked(src_id, dst_id, edge)
+        } else {
+            self.graph.add_edge(src_id, dst_id, edge)?
+        };
+
+        self.edges_of
+            .entry(layer.clone())
+            .or_default()
+            .insert(edge_id);
+        self.layer_of.insert(edge_id, layer);
+
+        Ok(edge_id)
+    }
+
+    /// # Arguments
+    /// `edge_id`: Id of the edge.
+    ///
+    /// # Returns
+    /// * `Some`: Containing key of the layer that edge belongs to.
+    /// * `None`: If edge does not exist or it belongs to no layer.
+    pub fn layer_of(&self, edge_id: usize) -> Option<&K> {
+        self.layer_of.get(&edge_id)
+    }
+
+    /// # Arguments
+    /// `layer`: Key of the layer.
+    ///
+    /// # Returns
+    /// * `Some`: Containing subgraph of all vertices and the edges of the layer.
+    /// * `None`: If layer does not exist.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|)
+    pub fn layer(&self, layer: &K) -> Option<Subgraph<'_, W, E, Dir, G>> {
+        let edge_ids = self.edges_of.get(layer)?;
+
+        Some(self.view(|edge_id| edge_ids.contains(&edge_id)))
+    }
+
+    /// # Returns
+    /// Subgraph of all vertices and the edges of all layers. Edges that belong to no layer are left out.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|)
+    pub fn flattened(&self) -> Subgraph<'_, W, E, Dir, G> {
+        self.view(|edge_id| self.layer_of.contains_key(&edge_id))
+    }
+
+    /// Adds vertices of the graph to `target`, and connects each pair of vertices that are connected in any layer by a single edge.
+    /// Weight of the edge is the sum of weights of the edges between the pair in all layers.
+    ///
+    /// # Arguments
+    /// `target`: Graph to add the aggregated vertices and edges to.
+    ///
+    /// # Returns
+    /// Id of each vertex in `target`, mapped from its id in this graph.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|) plus cost of adding vertices and edges to `target`.
+    pub fn aggregate_into<E2, H>(&self, target: &mut H) -> HashMap<usize, usize>
+    where
+        W: Add<Output = W> + Copy,
+        E2: Edge<W>,
+        H: Graph<W, E2, Dir>,
+    {
+        let mut vertices = self.graph.vertices();
+        vertices.sort_unstable();
+
+        let id_map = vertices
+            .into_iter()
+            .map(|vertex_id| (vertex_id, target.add_vertex()))
+            .collect::<HashMap<usize, usize>>();
+
+        let mut end_points = vec![];
+        let mut weight_between: HashMap<(usize, usize), Magnitude<W>> = HashMap::new();
+        for (src_id, dst_id, edge) in self.graph.edges() {
+            if !self.layer_of.contains_key(&edge.get_id()) {
+                continue;
+            }
+
+            let key = if Dir::is_undirected() && dst_id < src_id {
+                (dst_id, src_id)
+            } else {
+                (src_id, dst_id)
+            };
+
+            match weight_between.get_mut(&key) {
+                Some(weight) => *weight += *edge.get_weight(),
+                None => {
+                    end_points.push(key);
+                    weight_between.insert(key, *edge.get_weight());
+                }
+            }
+        }
+
+        for (src_id, dst_id) in end_points {
+            let weight = weight_between[&(src_id, dst_id)];
+
+            target.add_edge_unchecked(id_map[&src_id], id_map[&dst_id], E2::init(weight));
+        }
+
+        id_map
+    }
+
+    /// # Returns
+    /// The wrapped graph.
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+
+    /// # Returns
+    /// The wrapped graph, dropping the layers.
+    pub fn into_graph(self) -> G {
+        self.graph
+    }
+
+    fn view<F: Fn(usize) -> bool>(&self, contains_edge: F) -> Subgraph<'_, W, E, Dir, G> {
+        let edges = self
+            .graph
+            .edges()
+            .into_iter()
+            .filter(|(_, _, edge)| contains_edge(edge.get_id()))
+            .map(|(src_id, dst_id, edge)| (src_id, dst_id, edge.get_id()))
+            .collect();
+
+        let vertex_ids = self
+            .graph
+            .vertices()
+            .into_iter()
+            .collect::<HashSet<usize>>();
+
+        Subgraph::init(&self.graph, edges, vertex_ids)
+    }
+}
+
+impl<K, W, E, Dir, G> MultiLayerGraph<K, W, E, Dir, G>
+where
+    K: Hash + Eq,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Edges<W, E>,
+{
+    fn unlayer(&mut self, edge_id: usize) {
+        if let Some(layer) = self.layer_of.remove(&edge_id) {
+            if let Some(edge_ids) = self.edges_of.get_mut(&layer) {
+                edge_ids.remove(&edge_id);
+            }
+        }
+    }
+
+    // Removes edges that no longer exist from their layers, so their ids can be reused by the wrapped graph.
+    fn remove_stale_edges(&mut self) {
+        let graph = &self.graph;
+
+        self.layer_of
+            .retain(|edge_id, _| graph.contains_edge(*edge_id));
+        self.edges_of
+            .values_mut()
+            .for_each(|edge_ids| edge_ids.retain(|edge_id| graph.contains_edge(*edge_id)));
+    }
+}
+
+/// For documentation about each function checkout [`Graph`](crate::provide::Graph) trait.
+/// Removing an edge, or a vertex along with its edges, also removes them from their layers.
+impl<K, W, E, Dir, G> Graph<W, E, Dir> for MultiLayerGraph<K, W, E, Dir, G>
+where
+    K: Hash + Eq,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Edges<W, E>,
+{
+    fn add_vertex(&mut self) -> usize {
+        self.graph.add_vertex()
+    }
+
+    fn remove_vertex(&mut self, vertex_id: usize) -> Result<()> {
+        self.graph.remove_vertex(vertex_id)?;
+        self.remove_stale_edges();
+
+        Ok(())
+    }
+
+    fn remove_vertex_unchecked(&mut self, vertex_id: usize) {
+        self.graph.remove_vertex_unchecked(vertex_id);
+        self.remove_stale_edges();
+    }
+
+    fn add_edge(&mut self, src_id: usize, dst_id: usize, edge: E) -> Result<usize> {
+        self.graph.add_edge(src_id, dst_id, edge)
+    }
+
+    fn add_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge: E) -> usize {
+        self.graph.add_edge_unchecked(src_id, dst_id, edge)
+    }
+
+    fn update_edge(&mut self, src_id: usize, dst_id: usize, edge_id: usize, edge: E) -> Result<()> {
+        self.graph.update_edge(src_id, dst_id, edge_id, edge)
+    }
+
+    fn update_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize, edge: E) {
+        self.graph
+            .update_edge_unchecked(src_id, dst_id, edge_id, edge)
+    }
+
+    fn remove_edge(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<Option<E>> {
+        let edge = self.graph.remove_edge(src_id, dst_id, edge_id)?;
+        self.unlayer(edge_id);
+
+        Ok(edge)
+    }
+
+    fn remove_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> Option<E> {
+        self.unlayer(edge_id);
+        self.graph.remove_edge_unchecked(src_id, dst_id, edge_id)
+    }
+}
+
+/// For documentation about each function checkout [`Neighbors`](crate::provide::Neighbors) trait.
+impl<K, W, E, Dir, G> Neighbors for MultiLayerGraph<K, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Neighbors,
+{
+    fn neighbors(&self, src_id: usize) -> Result<Vec<usize>> {
+        self.graph.neighbors(src_id)
+    }
+
+    fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
+        self.graph.neighbors_unchecked(src_id)
+    }
+}
+
+/// For documentation about each function checkout [`Vertices`](crate::provide::Vertices) trait.
+impl<K, W, E, Dir, G> Vertices for MultiLayerGraph<K, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices,
+{
+    fn vertices(&self) -> Vec<usize> {
+        self.graph.vertices()
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.graph.vertex_count()
+    }
+
+    fn contains_vertex(&self, vertex_id: usize) -> bool {
+        self.graph.contains_vertex(vertex_id)
+    }
+}
+
+/// For documentation about each function checkout [`Direction`](crate::provide::Direction) trait.
+impl<K, W, E, Dir, G> Direction for MultiLayerGraph<K, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir>,
+{
+    fn is_directed(&self) -> bool {
+        Dir::is_directed()
+    }
+}
+
+/// For documentation about each function checkout [`Edges`](crate::provide::Edges) trait.
+impl<K, W, E, Dir, G> Edges<W, E> for MultiLayerGraph<K, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Edges<W, E>,
+{
+    fn edges_from(&self, src_id: usize) -> Result<Vec<(usize, &E)>> {
+        self.graph.edges_from(src_id)
+    }
+
+    fn edges_from_unchecked(&self, src_id: usize) -> Vec<(usize, &E)> {
+        self.graph.edges_from_unchecked(src_id)
+    }
+
+    fn edges_between(&self, src_id: usize, dst_id: usize) -> Result<Vec<&E>> {
+        self.graph.edges_between(src_id, dst_id)
+    }
+
+    fn edges_between_unchecked(&self, src_id: usize, dst_id: usize) -> Vec<&E> {
+        self.graph.edges_between_unchecked(src_id, dst_id)
+    }
+
+    fn edge_between(&self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<Option<&E>> {
+        self.graph.edge_between(src_id, dst_id, edge_id)
+    }
+
+    fn edge_between_unchecked(&self, src_id: usize, dst_id: usize, edge_id: usize) -> Option<&E> {
+        self.graph.edge_between_unchecked(src_id, dst_id, edge_id)
+    }
+
+    fn edge(&self, edge_id: usize) -> Result<Option<&E>> {
+        self.graph.edge(edge_id)
+    }
+
+    fn edge_unchecked(&self, edge_id: usize) -> Option<&E> {
+        self.graph.edge_unchecked(edge_id)
+    }
+
+    fn has_any_edge(&self, src_id: usize, dst_id: usize) -> Result<bool> {
+        self.graph.has_any_edge(src_id, dst_id)
+    }
+
+    fn has_any_edge_unchecked(&self, src_id: usize, dst_id: usize) -> bool {
+        self.graph.has_any_edge_unchecked(src_id, dst_id)
+    }
+
+    fn edges(&self) -> Vec<(usize, usize, &E)> {
+        self.graph.edges()
+    }
+
+    fn as_directed_edges(&self) -> Vec<(usize, usize, &E)> {
+        self.graph.as_directed_edges()
+    }
+
+    fn edges_count(&self) -> usize {
+        self.graph.edges_count()
+    }
+
+    fn contains_edge(&self, edge_id: usize) -> bool {
+        self.graph.contains_edge(edge_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Error, ErrorKind, ListGraph};
+    use crate::storage::{DiList, List};
+
+    fn edge_ids_of<E: Edge<usize>, G: Edges<usize, E>>(graph: &G) -> Vec<usize> {
+        let mut edge_ids = graph
+            .edges()
+            .into_iter()
+            .map(|(_, _, edge)| edge.get_id())
+            .collect::<Vec<usize>>();
+        edge_ids.sort_unstable();
+
+        edge_ids
+    }
+
+    #[test]
+    fn layer_views() {
+        // Given: Graph
+        //
+        //      a  --[x]--  b  --[y]--  c
+        //      |           |
+        //      '----[y]----'
+        //
+        let mut graph = MultiLayerGraph::init(ListGraph::init(List::<usize>::init()));
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let ab_x = graph.add_layered_edge('x', a, b, 1.into()).unwrap();
+        let bc_y = graph.add_layered_edge('y', b, c, 1.into()).unwrap();
+        let ab_y = graph.add_layered_edge('y', a, b, 1.into()).unwrap();
+        let ac = graph.add_edge_unchecked(a, c, 1.into());
+        graph.add_layer('z');
+
+        // When:
+        let x = graph.layer(&'x').unwrap();
+        let y = graph.layer(&'y').unwrap();
+        let z = graph.layer(&'z').unwrap();
+        let flattened = graph.flattened();
+
+        // Then:
+        assert_eq!(edge_ids_of(&x), vec![ab_x]);
+        assert_eq!(edge_ids_of(&y), vec![bc_y, ab_y]);
+        assert!(edge_ids_of(&z).is_empty());
+        assert_eq!(edge_ids_of(&flattened), vec![ab_x, bc_y, ab_y]);
+        assert_eq!(z.vertex_count(), 3);
+        assert_eq!(graph.layer_of(ab_y), Some(&'y'));
+        assert_eq!(graph.layer_of(ac), None);
+        assert!(graph.layer(&'w').is_none());
+    }
+
+    #[test]
+    fn multi_edge_in_same_layer() {
+        // Given: Graph
+        //
+        //      a  --[x]--  b
+        //
+        let mut graph = MultiLayerGraph::init(ListGraph::init(List::<usize>::init()));
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_layered_edge('x', a, b, 1.into()).unwrap();
+
+        // When:
+        let same_layer = graph.add_layered_edge('x', b, a, 1.into());
+        let other_layer = graph.add_layered_edge('y', b, a, 1.into());
+
+        // Then:
+        let error = same_layer.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            ErrorKind::MultiEdge
+        ));
+        assert!(other_layer.is_ok());
+        assert_eq!(graph.edges_count(), 2);
+    }
+
+    #[test]
+    fn remove_layer() {
+        // Given: Graph
+        //
+        //      a  --[x]-->  b  --[y]-->  c
+        //
+        let mut graph = MultiLayerGraph::init(ListGraph::init(DiList::<usize>::init()));
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let ab = graph.add_layered_edge('x', a, b, 1.into()).unwrap();
+        let bc = graph.add_layered_edge('y', b, c, 1.into()).unwrap();
+
+        // When:
+        let is_removed = graph.remove_layer(&'y');
+
+        // Then:
+        assert!(is_removed);
+        assert!(!graph.remove_layer(&'y'));
+        assert!(!graph.contains_layer(&'y'));
+        assert_eq!(graph.layers(), vec![&'x']);
+        assert!(graph.contains_edge(ab));
+        assert!(!graph.contains_edge(bc));
+        assert_eq!(graph.layer_of(bc), None);
+    }
+
+    #[test]
+    fn removed_edges_leave_their_layers() {
+        // Given: Graph
+        //
+        //      a  --[x]--  b  --[x]--  c
+        //
+        let mut graph = MultiLayerGraph::init(ListGraph::init(List::<usize>::init()));
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let ab = graph.add_layered_edge('x', a, b, 1.into()).unwrap();
+        let bc = graph.add_layered_edge('x', b, c, 1.into()).unwrap();
+
+        // When:
+        graph.remove_edge_unchecked(a, b, ab);
+        graph.remove_vertex_unchecked(c);
+
+        // Then:
+        assert_eq!(graph.layer_of(ab), None);
+        assert_eq!(graph.layer_of(bc), None);
+        assert!(edge_ids_of(&graph.layer(&'x').unwrap()).is_empty());
+    }
+
+    #[test]
+    fn aggregate_into() {
+        // Given: Graph
+        //
+        //      a  --[x: 1]--  b
+        //      a  --[y: 2]--  b
+        //      b  --[z: 4]--  a
+        //      b  --[x: 8]--  c
+        //
+        let mut graph = MultiLayerGraph::init(ListGraph::init(List::<usize>::init()));
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_layered_edge('x', a, b, 1.into()).unwrap();
+        graph.add_layered_edge('y', a, b, 2.into()).unwrap();
+        graph.add_layered_edge('z', b, a, 4.into()).unwrap();
+        graph.add_layered_edge('x', b, c, 8.into()).unwrap();
+
+        // When:
+        let mut aggregated = ListGraph::init(List::<usize>::init());
+        let id_map = graph.aggregate_into(&mut aggregated);
+
+        // Then:
+        assert_eq!(aggregated.vertex_count(), 3);
+        assert_eq!(aggregated.edges_count(), 2);
+        let ab = aggregated.edges_between_unchecked(id_map[&a], id_map[&b]);
+        let bc = aggregated.edges_between_unchecked(id_map[&b], id_map[&c]);
+        assert_eq!(ab[0].get_weight(), &7.into());
+        assert_eq!(bc[0].get_weight(), &8.into());
+    }
+}
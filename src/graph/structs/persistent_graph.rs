@@ -0,0 +1,665 @@
+use std::collections::HashSet;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use super::persistent_vec::PersistentVec;
+use crate::graph::{DefaultEdge, DirectedEdge, Edge, EdgeDir, Error, UndirectedEdge};
+use crate::provide::{self, Direction, Edges, Neighbors, Vertices};
+
+pub type DiPersistentGraph<W> = PersistentGraph<W, DefaultEdge<W>, DirectedEdge>;
+
+/// Immutable graph whose mutations return a new graph, and leave the old one untouched.
+///
+/// The new graph shares most of its structure with the old one. Vertices and their edges are stored in persistent vectors,
+/// so a mutation only copies the edges of the vertices it touches and a logarithmic number of nodes of the vectors.
+/// This makes it cheap to keep many versions of a graph around, for example to backtrack in a search over the states of a graph.
+///
+/// Like [`SimpleGraph`](crate::graph::SimpleGraph), checked mutations do not allow loops and multiple edges between two vertices.
+/// Ids of removed vertices and edges are never reused, so an id refers to the same vertex or edge in all versions of the graph.
+///
+/// `PersistentGraph` implements the provider traits needed by algorithms that only read the graph, so it can be passed directly to them.
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that graph uses.
+/// * `Dir`: **Dir**ection of edges: [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge).
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::graph::PersistentGraph;
+///
+/// let graph = PersistentGraph::<usize>::init();
+/// let (graph, a) = graph.add_vertex();
+/// let (graph, b) = graph.add_vertex();
+///
+/// let (with_edge, ab) = graph.add_edge(a, b, 1.into()).unwrap();
+/// let updated = with_edge.update_edge(a, b, ab, 2.into()).unwrap();
+///
+/// assert_eq!(graph.edges_count(), 0);
+/// assert_eq!(with_edge.edge_unchecked(ab).unwrap().get_weight(), &1.into());
+/// assert_eq!(updated.edge_unchecked(ab).unwrap().get_weight(), &2.into());
+/// ```
+pub struct PersistentGraph<W, E: Edge<W> = DefaultEdge<W>, Dir: EdgeDir = UndirectedEdge> {
+    // Edges from each vertex in the format of: (`dst_id`, `edge`), or `None` if vertex is removed.
+    // Undirected edges are stored in the edges of both of their end points.
+    edges_of: PersistentVec<Option<Arc<Vec<(usize, E)>>>>,
+
+    // Source and destination of each edge, or `None` if edge is removed.
+    end_points_of: PersistentVec<Option<(usize, usize)>>,
+
+    vertex_count: usize,
+    edge_count: usize,
+
+    phantom_w: PhantomData<W>,
+    phantom_dir: PhantomData<Dir>,
+}
+
+impl<W, E: Edge<W> + Clone, Dir: EdgeDir> PersistentGraph<W, E, Dir> {
+    /// # Returns
+    /// An empty graph.
+    pub fn init() -> Self {
+        PersistentGraph {
+            edges_of: PersistentVec::init(),
+            end_points_of: PersistentVec::init(),
+
+            vertex_count: 0,
+            edge_count: 0,
+
+            phantom_w: PhantomData,
+            phantom_dir: PhantomData,
+        }
+    }
+
+    /// # Returns
+    /// (`graph`, `vertex_id`): New graph that contains a new vertex, and id of the new vertex.
+    ///
+    /// # Complexity
+    /// O(log(|V|))
+    pub fn add_vertex(&self) -> (Self, usize) {
+        let vertex_id = self.edges_of.len();
+
+        let graph = PersistentGraph {
+            edges_of: self.edges_of.push(Some(Arc::new(vec![]))),
+            vertex_count: self.vertex_count + 1,
+            ..self.clone()
+        };
+
+        (graph, vertex_id)
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex to be removed.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing new graph without the vertex and the edges connected to it.
+    /// * `Err`: [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if vertex does not exist.
+    pub fn remove_vertex(&self, vertex_id: usize) -> Result<Self> {
+        if !self.contains_vertex(vertex_id) {
+            Err(Error::new_vnf(vertex_id))?
+        } else {
+            Ok(self.remove_vertex_unchecked(vertex_id))
+        }
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex to be removed.
+    ///
+    /// # Returns
+    /// New graph without the vertex and the edges connected to it.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|) for directed graphs, because any vertex may have an edge to the removed vertex.
+    /// For undirected graphs, only the neighbors of the removed vertex are visited.
+    pub fn remove_vertex_unchecked(&self, vertex_id: usize) -> Self {
+        let mut graph = self.clone();
+
+        let other_ids = if Dir::is_directed() {
+            self.vertices()
+        } else {
+            self.neighbors_unchecked(vertex_id)
+        };
+        for other_id in other_ids
+            .into_iter()
+            .filter(|other_id| *other_id != vertex_id)
+        {
+            if self.has_any_edge_unchecked(other_id, vertex_id) {
+                let edges = graph.edges_of_unchecked(other_id);
+                let edges = edges
+                    .iter()
+                    .filter(|(dst_id, _)| *dst_id != vertex_id)
+                    .cloned()
+                    .collect::<Vec<(usize, E)>>();
+                graph.edges_of = graph.edges_of.set(other_id, Some(Arc::new(edges)));
+            }
+        }
+
+        for (_, edge) in self.edges_of_unchecked(vertex_id).iter() {
+            graph.end_points_of = graph.end_points_of.set(edge.get_id(), None);
+            graph.edge_count -= 1;
+        }
+        if Dir::is_directed() {
+            for (src_id, dst_id, edge) in self.as_directed_edges() {
+                if dst_id == vertex_id && src_id != vertex_id {
+                    graph.end_points_of = graph.end_points_of.set(edge.get_id(), None);
+                    graph.edge_count -= 1;
+                }
+            }
+        }
+
+        graph.edges_of = graph.edges_of.set(vertex_id, None);
+        graph.vertex_count -= 1;
+
+        graph
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    /// * `edge`: Edge to be added from source to destination.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing (`graph`, `edge_id`): New graph that contains the edge, and id of the new edge.
+    /// * `Err`:
+    ///     * [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if source or destination does not exist.
+    ///     * [`MultiEdge`](crate::graph::ErrorKind::MultiEdge) if there is already an edge from source to destination.
+    ///     * [`Loop`](crate::graph::ErrorKind::Loop) if source and destination are the same.
+    pub fn add_edge(&self, src_id: usize, dst_id: usize, edge: E) -> Result<(Self, usize)> {
+        if !self.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else if !self.contains_vertex(dst_id) {
+            Err(Error::new_vnf(dst_id))?
+        } else if self.has_any_edge_unchecked(src_id, dst_id) {
+            Err(Error::new_me(src_id, dst_id))?
+        } else if src_id == dst_id {
+            Err(Error::new_l(src_id))?
+        } else {
+            Ok(self.add_edge_unchecked(src_id, dst_id, edge))
+        }
+    }
+
+    /// Same as `add_edge` but does not check the arguments. So loops and multiple edges can be added.
+    ///
+    /// # Complexity
+    /// O(log(|V|) + log(|E|) + |E<sub>src</sub>| + |E<sub>dst</sub>|)
+    pub fn add_edge_unchecked(&self, src_id: usize, dst_id: usize, mut edge: E) -> (Self, usize) {
+        let edge_id = self.end_points_of.len();
+        edge.set_id(edge_id);
+
+        let mut graph = PersistentGraph {
+            end_points_of: self.end_points_of.push(Some((src_id, dst_id))),
+            edge_count: self.edge_count + 1,
+            ..self.clone()
+        };
+
+        if Dir::is_undirected() && src_id != dst_id {
+            graph.edit_edges_of(dst_id, |edges| edges.push((src_id, edge.clone())));
+        }
+        graph.edit_edges_of(src_id, |edges| edges.push((dst_id, edge)));
+
+        (graph, edge_id)
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    /// * `edge_id`: Id of the edge to be updated.
+    /// * `edge`: New edge to replace the old one.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing new graph where the edge is replaced.
+    /// * `Err`: If vertices or the edge do not exist, or edge is not from source to destination.
+    pub fn update_edge(
+        &self,
+        src_id: usize,
+        dst_id: usize,
+        edge_id: usize,
+        edge: E,
+    ) -> Result<Self> {
+        self.check_edge(src_id, dst_id, edge_id)?;
+
+        Ok(self.update_edge_unchecked(src_id, dst_id, edge_id, edge))
+    }
+
+    /// # Complexity
+    /// O(log(|V|) + |E<sub>src</sub>| + |E<sub>dst</sub>|)
+    pub fn update_edge_unchecked(
+        &self,
+        src_id: usize,
+        dst_id: usize,
+        edge_id: usize,
+        mut edge: E,
+    ) -> Self {
+        edge.set_id(edge_id);
+
+        let mut graph = self.clone();
+        let replace = |edges: &mut Vec<(usize, E)>, other_id: usize| {
+            for (n_id, old_edge) in edges.iter_mut() {
+                if *n_id == other_id && old_edge.get_id() == edge_id {
+                    *old_edge = edge.clone();
+                }
+            }
+        };
+
+        graph.edit_edges_of(src_id, |edges| replace(edges, dst_id));
+        if Dir::is_undirected() && src_id != dst_id {
+            graph.edit_edges_of(dst_id, |edges| replace(edges, src_id));
+        }
+
+        graph
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    /// * `edge_id`: Id of the edge to be removed.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing new graph without the edge.
+    /// * `Err`: If vertices or the edge do not exist, or edge is not from source to destination.
+    pub fn remove_edge(&self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<Self> {
+        self.check_edge(src_id, dst_id, edge_id)?;
+
+        Ok(self.remove_edge_unchecked(src_id, dst_id, edge_id))
+    }
+
+    /// # Complexity
+    /// O(log(|V|) + log(|E|) + |E<sub>src</sub>| + |E<sub>dst</sub>|)
+    pub fn remove_edge_unchecked(&self, src_id: usize, dst_id: usize, edge_id: usize) -> Self {
+        let mut graph = PersistentGraph {
+            end_points_of: self.end_points_of.set(edge_id, None),
+            edge_count: self.edge_count - 1,
+            ..self.clone()
+        };
+
+        graph.edit_edges_of(src_id, |edges| {
+            edges.retain(|(_, edge)| edge.get_id() != edge_id)
+        });
+        if Dir::is_undirected() && src_id != dst_id {
+            graph.edit_edges_of(dst_id, |edges| {
+                edges.retain(|(_, edge)| edge.get_id() != edge_id)
+            });
+        }
+
+        graph
+    }
+
+    fn check_edge(&self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<()> {
+        if !self.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else if !self.contains_vertex(dst_id) {
+            Err(Error::new_vnf(dst_id))?
+        } else if self
+            .edge_between_unchecked(src_id, dst_id, edge_id)
+            .is_none()
+        {
+            Err(Error::new_enf(edge_id))?
+        } else {
+            Ok(())
+        }
+    }
+
+    fn edges_of_unchecked(&self, vertex_id: usize) -> &Arc<Vec<(usize, E)>> {
+        self.edges_of.get(vertex_id).unwrap().as_ref().unwrap()
+    }
+
+    // Replaces edges of the vertex with an edited copy of them. Other versions of the graph keep the old edges.
+    fn edit_edges_of<F: FnOnce(&mut Vec<(usize, E)>)>(&mut self, vertex_id: usize, edit: F) {
+        let mut edges = self.edges_of_unchecked(vertex_id).as_ref().clone();
+        edit(&mut edges);
+
+        self.edges_of = self.edges_of.set(vertex_id, Some(Arc::new(edges)));
+    }
+}
+
+impl<W, E: Edge<W> + Clone, Dir: EdgeDir> Default for PersistentGraph<W, E, Dir> {
+    fn default() -> Self {
+        PersistentGraph::init()
+    }
+}
+
+/// Cloning is O(1), because the clone shares all of its structure with the original graph.
+impl<W, E: Edge<W>, Dir: EdgeDir> Clone for PersistentGraph<W, E, Dir> {
+    fn clone(&self) -> Self {
+        PersistentGraph {
+            edges_of: self.edges_of.clone(),
+            end_points_of: self.end_points_of.clone(),
+
+            vertex_count: self.vertex_count,
+            edge_count: self.edge_count,
+
+            phantom_w: PhantomData,
+            phantom_dir: PhantomData,
+        }
+    }
+}
+
+/// For documentation about each function checkout [`Neighbors`](crate::provide::Neighbors) trait.
+impl<W, E: Edge<W> + Clone, Dir: EdgeDir> Neighbors for PersistentGraph<W, E, Dir> {
+    fn neighbors(&self, src_id: usize) -> Result<Vec<usize>> {
+        if !self.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else {
+            Ok(self.neighbors_unchecked(src_id))
+        }
+    }
+
+    fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
+        let mut seen = HashSet::new();
+
+        self.edges_of_unchecked(src_id)
+            .iter()
+            .map(|(dst_id, _)| *dst_id)
+            .filter(|dst_id| seen.insert(*dst_id))
+            .collect()
+    }
+}
+
+/// For documentation about each function checkout [`Vertices`](crate::provide::Vertices) trait.
+impl<W, E: Edge<W> + Clone, Dir: EdgeDir> Vertices for PersistentGraph<W, E, Dir> {
+    fn vertices(&self) -> Vec<usize> {
+        self.edges_of
+            .iter()
+            .enumerate()
+            .filter(|(_, edges)| edges.is_some())
+            .map(|(vertex_id, _)| vertex_id)
+            .collect()
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.vertex_count
+    }
+
+    fn contains_vertex(&self, vertex_id: usize) -> bool {
+        matches!(self.edges_of.get(vertex_id), Some(Some(_)))
+    }
+}
+
+/// For documentation about each function checkout [`Direction`](crate::provide::Direction) trait.
+impl<W, E: Edge<W>, Dir: EdgeDir> Direction for PersistentGraph<W, E, Dir> {
+    fn is_directed(&self) -> bool {
+        Dir::is_directed()
+    }
+}
+
+/// For documentation about each function checkout [`Edges`](crate::provide::Edges) trait.
+impl<W, E: Edge<W> + Clone, Dir: EdgeDir> Edges<W, E> for PersistentGraph<W, E, Dir> {
+    fn edges_from(&self, src_id: usize) -> Result<Vec<(usize, &E)>> {
+        if !self.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else {
+            Ok(self.edges_from_unchecked(src_id))
+        }
+    }
+
+    fn edges_from_unchecked(&self, src_id: usize) -> Vec<(usize, &E)> {
+        self.edges_of_unchecked(src_id)
+            .iter()
+            .map(|(dst_id, edge)| (*dst_id, edge))
+            .collect()
+    }
+
+    fn edges_between(&self, src_id: usize, dst_id: usize) -> Result<Vec<&E>> {
+        if !self.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else if !self.contains_vertex(dst_id) {
+            Err(Error::new_vnf(dst_id))?
+        } else {
+            Ok(self.edges_between_unchecked(src_id, dst_id))
+        }
+    }
+
+    fn edges_between_unchecked(&self, src_id: usize, dst_id: usize) -> Vec<&E> {
+        self.edges_of_unchecked(src_id)
+            .iter()
+            .filter(|(n_id, _)| *n_id == dst_id)
+            .map(|(_, edge)| edge)
+            .collect()
+    }
+
+    fn edge_between(&self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<Option<&E>> {
+        if !self.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else if !self.contains_vertex(dst_id) {
+            Err(Error::new_vnf(dst_id))?
+        } else if !self.contains_edge(edge_id) {
+            Err(Error::new_enf(edge_id))?
+        } else {
+            Ok(self.edge_between_unchecked(src_id, dst_id, edge_id))
+        }
+    }
+
+    fn edge_between_unchecked(&self, src_id: usize, dst_id: usize, edge_id: usize) -> Option<&E> {
+        self.edges_between_unchecked(src_id, dst_id)
+            .into_iter()
+            .find(|edge| edge.get_id() == edge_id)
+    }
+
+    fn edge(&self, edge_id: usize) -> Result<Option<&E>> {
+        if !self.contains_edge(edge_id) {
+            Err(Error::new_enf(edge_id))?
+        } else {
+            Ok(self.edge_unchecked(edge_id))
+        }
+    }
+
+    /// # Complexity
+    /// O(log(|E|) + |E<sub>src</sub>|), because source and destination of each edge are stored.
+    fn edge_unchecked(&self, edge_id: usize) -> Option<&E> {
+        let (src_id, dst_id) = (*self.end_points_of.get(edge_id)?)?;
+
+        self.edge_between_unchecked(src_id, dst_id, edge_id)
+    }
+
+    fn has_any_edge(&self, src_id: usize, dst_id: usize) -> Result<bool> {
+        if !self.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else if !self.contains_vertex(dst_id) {
+            Err(Error::new_vnf(dst_id))?
+        } else {
+            Ok(self.has_any_edge_unchecked(src_id, dst_id))
+        }
+    }
+
+    fn has_any_edge_unchecked(&self, src_id: usize, dst_id: usize) -> bool {
+        self.edges_of_unchecked(src_id)
+            .iter()
+            .any(|(n_id, _)| *n_id == dst_id)
+    }
+
+    fn edges(&self) -> Vec<(usize, usize, &E)> {
+        if Dir::is_directed() {
+            self.as_directed_edges()
+        } else {
+            provide::undirected_edges(self.as_directed_edges())
+        }
+    }
+
+    fn as_directed_edges(&self) -> Vec<(usize, usize, &E)> {
+        self.vertices()
+            .into_iter()
+            .flat_map(|src_id| {
+                self.edges_from_unchecked(src_id)
+                    .into_iter()
+                    .map(move |(dst_id, edge)| (src_id, dst_id, edge))
+            })
+            .collect()
+    }
+
+    fn edges_count(&self) -> usize {
+        self.edge_count
+    }
+
+    fn contains_edge(&self, edge_id: usize) -> bool {
+        matches!(self.end_points_of.get(edge_id), Some(Some(_)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::Bfs;
+
+    #[test]
+    fn empty_graph() {
+        let graph = PersistentGraph::<usize>::init();
+
+        assert_eq!(graph.vertex_count(), 0);
+        assert_eq!(graph.edges_count(), 0);
+        assert!(graph.vertices().is_empty());
+        assert!(!graph.contains_vertex(0));
+    }
+
+    #[test]
+    fn versions_are_independent() {
+        // Given: Undirected graph: a --- b --- c
+        let graph = PersistentGraph::<usize>::init();
+        let (graph, a) = graph.add_vertex();
+        let (graph, b) = graph.add_vertex();
+        let (graph, c) = graph.add_vertex();
+        let (graph, ab) = graph.add_edge(a, b, 1.into()).unwrap();
+        let (graph, bc) = graph.add_edge(b, c, 2.into()).unwrap();
+
+        // When: Removing b in a new version and an edge in another one.
+        let without_b = graph.remove_vertex(b).unwrap();
+        let without_ab = graph.remove_edge(b, a, ab).unwrap();
+
+        // Then: Each version only sees its own mutation.
+        assert_eq!(graph.vertex_count(), 3);
+        assert_eq!(graph.edges_count(), 2);
+        assert_eq!(graph.neighbors_unchecked(b), vec![a, c]);
+
+        assert_eq!(without_b.vertices(), vec![a, c]);
+        assert_eq!(without_b.edges_count(), 0);
+        assert!(without_b.neighbors_unchecked(a).is_empty());
+        assert!(!without_b.contains_edge(bc));
+
+        assert_eq!(without_ab.edges_count(), 1);
+        assert_eq!(without_ab.neighbors_unchecked(b), vec![c]);
+        assert!(without_ab.edge_unchecked(ab).is_none());
+        assert_eq!(
+            without_ab.edge_unchecked(bc).unwrap().get_weight(),
+            &2.into()
+        );
+    }
+
+    #[test]
+    fn directed_graph() {
+        // Given: Directed graph: a --> b --> c --> a
+        let graph = DiPersistentGraph::<usize>::init();
+        let (graph, a) = graph.add_vertex();
+        let (graph, b) = graph.add_vertex();
+        let (graph, c) = graph.add_vertex();
+        let (graph, _) = graph.add_edge(a, b, 1.into()).unwrap();
+        let (graph, bc) = graph.add_edge(b, c, 1.into()).unwrap();
+        let (graph, ca) = graph.add_edge(c, a, 1.into()).unwrap();
+
+        // When: Removing a.
+        let without_a = graph.remove_vertex(a).unwrap();
+
+        // Then:
+        assert!(graph.is_directed());
+        assert_eq!(graph.neighbors_unchecked(b), vec![c]);
+        assert!(graph.neighbors_unchecked(c).contains(&a));
+        assert_eq!(
+            graph
+                .edges()
+                .into_iter()
+                .map(|(src_id, dst_id, _)| (src_id, dst_id))
+                .collect::<Vec<(usize, usize)>>(),
+            vec![(a, b), (b, c), (c, a)]
+        );
+        assert_eq!(without_a.edges_count(), 1);
+        assert!(without_a.neighbors_unchecked(c).is_empty());
+        assert!(without_a.contains_edge(bc));
+        assert!(!without_a.contains_edge(ca));
+    }
+
+    #[test]
+    fn update_edge() {
+        // Given: Undirected graph: a --- b
+        let graph = PersistentGraph::<usize>::init();
+        let (graph, a) = graph.add_vertex();
+        let (graph, b) = graph.add_vertex();
+        let (graph, ab) = graph.add_edge(a, b, 1.into()).unwrap();
+
+        // When: Updating the edge from the other end point.
+        let updated = graph.update_edge(b, a, ab, 5.into()).unwrap();
+
+        // Then: Both end points see the new edge.
+        assert_eq!(
+            updated.edges_between_unchecked(a, b)[0].get_weight(),
+            &5.into()
+        );
+        assert_eq!(
+            updated.edges_between_unchecked(b, a)[0].get_weight(),
+            &5.into()
+        );
+        assert_eq!(
+            graph.edges_between_unchecked(a, b)[0].get_weight(),
+            &1.into()
+        );
+        assert_eq!(updated.edges_between_unchecked(a, b)[0].get_id(), ab);
+    }
+
+    #[test]
+    fn invalid_mutations() {
+        let graph = PersistentGraph::<usize>::init();
+        let (graph, a) = graph.add_vertex();
+        let (graph, b) = graph.add_vertex();
+        let (graph, ab) = graph.add_edge(a, b, 1.into()).unwrap();
+
+        assert!(graph.add_edge(a, a, 1.into()).is_err());
+        assert!(graph.add_edge(b, a, 1.into()).is_err());
+        assert!(graph.add_edge(a, 2, 1.into()).is_err());
+        assert!(graph.remove_vertex(2).is_err());
+        assert!(graph.remove_edge(a, b, ab + 1).is_err());
+        assert!(graph
+            .remove_edge(a, b, ab)
+            .unwrap()
+            .remove_edge(a, b, ab)
+            .is_err());
+    }
+
+    #[test]
+    fn ids_are_not_reused() {
+        let graph = PersistentGraph::<usize>::init();
+        let (graph, a) = graph.add_vertex();
+        let graph = graph.remove_vertex(a).unwrap();
+
+        let (graph, b) = graph.add_vertex();
+
+        assert_ne!(a, b);
+        assert_eq!(graph.vertices(), vec![b]);
+    }
+
+    #[test]
+    fn backtracking_over_many_versions() {
+        // Given: Path of 100 vertices, where each version adds one more edge.
+        let graph = PersistentGraph::<usize>::init();
+        let (mut graph, first) = graph.add_vertex();
+        let mut versions = vec![graph.clone()];
+        let mut prev_id = first;
+        for _ in 0..99 {
+            let (next, vertex_id) = graph.add_vertex();
+            let (next, _) = next.add_edge(prev_id, vertex_id, 1.into()).unwrap();
+            graph = next;
+            prev_id = vertex_id;
+            versions.push(graph.clone());
+        }
+
+        // Then: Each version still sees exactly its own path.
+        for (index, version) in versions.iter().enumerate() {
+            assert_eq!(version.vertex_count(), index + 1);
+            assert_eq!(version.edges_count(), index);
+            assert_eq!(
+                Bfs::init_with_starts(version, vec![first])
+                    .execute(version)
+                    .len(),
+                index + 1
+            );
+        }
+    }
+}
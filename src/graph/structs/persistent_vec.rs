@@ -0,0 +1,198 @@
+use std::sync::Arc;
+
+const BITS: usize = 5;
+const WIDTH: usize = 1 << BITS;
+const MASK: usize = WIDTH - 1;
+
+#[derive(Debug)]
+enum Node<T> {
+    Branch(Vec<Arc<Node<T>>>),
+    Leaf(Vec<T>),
+}
+
+/// Immutable vector that shares structure between its versions.
+///
+/// Elements are stored in a trie where each node has at most 32 children. Updating an element copies only the nodes on the path to it,
+/// and the new version shares every other node with the old one.
+#[derive(Debug)]
+pub(crate) struct PersistentVec<T> {
+    root: Arc<Node<T>>,
+    len: usize,
+
+    // Number of bits that index of an element is shifted by, to find the child of the root that contains it.
+    shift: usize,
+}
+
+impl<T: Clone> PersistentVec<T> {
+    pub fn init() -> Self {
+        PersistentVec {
+            root: Arc::new(Node::Leaf(vec![])),
+            len: 0,
+            shift: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+
+        let mut node = &self.root;
+        let mut shift = self.shift;
+        loop {
+            match node.as_ref() {
+                Node::Branch(children) => {
+                    node = &children[(index >> shift) & MASK];
+                    shift -= BITS;
+                }
+                Node::Leaf(values) => return values.get(index & MASK),
+            }
+        }
+    }
+
+    /// # Complexity
+    /// O(log(n))
+    pub fn set(&self, index: usize, value: T) -> Self {
+        assert!(index < self.len, "Index {} is out of bounds", index);
+
+        PersistentVec {
+            root: Arc::new(Self::set_in(&self.root, self.shift, index, value)),
+            len: self.len,
+            shift: self.shift,
+        }
+    }
+
+    /// # Complexity
+    /// O(log(n))
+    pub fn push(&self, value: T) -> Self {
+        let (root, shift) = if self.len == 1 << (self.shift + BITS) {
+            (
+                Arc::new(Node::Branch(vec![self.root.clone()])),
+                self.shift + BITS,
+            )
+        } else {
+            (self.root.clone(), self.shift)
+        };
+
+        PersistentVec {
+            root: Arc::new(Self::push_in(&root, shift, self.len, value)),
+            len: self.len + 1,
+            shift,
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        (0..self.len).map(move |index| self.get(index).unwrap())
+    }
+
+    fn set_in(node: &Node<T>, shift: usize, index: usize, value: T) -> Node<T> {
+        match node {
+            Node::Branch(children) => {
+                let mut children = children.clone();
+                let child_index = (index >> shift) & MASK;
+                children[child_index] = Arc::new(Self::set_in(
+                    &children[child_index],
+                    shift - BITS,
+                    index,
+                    value,
+                ));
+
+                Node::Branch(children)
+            }
+            Node::Leaf(values) => {
+                let mut values = values.clone();
+                values[index & MASK] = value;
+
+                Node::Leaf(values)
+            }
+        }
+    }
+
+    fn push_in(node: &Node<T>, shift: usize, index: usize, value: T) -> Node<T> {
+        match node {
+            Node::Branch(children) => {
+                let mut children = children.clone();
+                let child_index = (index >> shift) & MASK;
+                if child_index < children.len() {
+                    children[child_index] = Arc::new(Self::push_in(
+                        &children[child_index],
+                        shift - BITS,
+                        index,
+                        value,
+                    ));
+                } else {
+                    children.push(Arc::new(Self::path_to(shift - BITS, value)));
+                }
+
+                Node::Branch(children)
+            }
+            Node::Leaf(values) => {
+                let mut values = values.clone();
+                values.push(value);
+
+                Node::Leaf(values)
+            }
+        }
+    }
+
+    // Creates a path of nodes that only contains `value`.
+    fn path_to(shift: usize, value: T) -> Node<T> {
+        if shift == 0 {
+            Node::Leaf(vec![value])
+        } else {
+            Node::Branch(vec![Arc::new(Self::path_to(shift - BITS, value))])
+        }
+    }
+}
+
+impl<T> Clone for PersistentVec<T> {
+    fn clone(&self) -> Self {
+        PersistentVec {
+            root: self.root.clone(),
+            len: self.len,
+            shift: self.shift,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_and_get() {
+        // Given: Vector with enough elements to need three levels.
+        let mut vec = PersistentVec::init();
+        for value in 0..2000 {
+            vec = vec.push(value);
+        }
+
+        // Then:
+        assert_eq!(vec.len(), 2000);
+        assert!(vec.iter().copied().eq(0..2000));
+        assert_eq!(vec.get(2000), None);
+    }
+
+    #[test]
+    fn versions_are_independent() {
+        // Given: Two versions of a vector.
+        let mut old = PersistentVec::init();
+        for value in 0..100 {
+            old = old.push(value);
+        }
+
+        // When: Updating and extending the new version.
+        let new = old.set(40, 1000).push(100);
+
+        // Then: Old version is not changed.
+        assert_eq!(old.get(40), Some(&40));
+        assert_eq!(old.len(), 100);
+        assert_eq!(new.get(40), Some(&1000));
+        assert_eq!(new.get(100), Some(&100));
+        assert_eq!(new.get(39), Some(&39));
+    }
+}
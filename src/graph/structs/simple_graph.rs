@@ -1,12 +1,17 @@
 use std::any::Any;
+use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
+use std::sync::Mutex;
 
 use anyhow::{Context, Result};
-use provide::{Edges, Graph, Neighbors, Vertices};
+use magnitude::Magnitude;
+use provide::{Direction, Edges, Graph, IdMap, Neighbors, Vertices};
 
-use crate::graph::{error::Error, DefaultEdge, Edge, EdgeDir, FlowEdge};
+use crate::graph::{
+    error::Error, impl_pretty, validation, DefaultEdge, DirectedEdge, Edge, EdgeDir, FlowEdge, FrozenGraph, GraphDelta, GraphEvent, Metadata, Observer, UndirectedEdge, Violation,
+};
 use crate::provide;
-use crate::storage::{FlowList, FlowMat, GraphStorage, List, Mat};
+use crate::storage::{FlowList, FlowMat, FlowSparseMat, GraphStorage, List, Mat, MemoryUsage, SparseMat};
 
 /// A `SimpleGraph` that uses [`Mat`](crate::storage::Mat) as its storage.
 pub type MatGraph<W, Dir> = SimpleGraph<W, DefaultEdge<W>, Dir, Mat<W, Dir>>;
@@ -20,11 +25,27 @@ pub type FlowMatGraph<W, Dir> = SimpleGraph<W, FlowEdge<W>, Dir, FlowMat<W>>;
 /// A `SimpleGraph` that uses [`FlowList`](crate::storage::FlowList) as its storage.
 pub type FlowListGraph<W, Dir> = SimpleGraph<W, DefaultEdge<W>, Dir, FlowList<W, Dir>>;
 
+/// A `SimpleGraph` that uses [`SparseMat`](crate::storage::SparseMat) as its storage.
+pub type SparseMatGraph<W, Dir> = SimpleGraph<W, DefaultEdge<W>, Dir, SparseMat<W, Dir>>;
+
+/// A `SimpleGraph` that uses [`FlowSparseMat`](crate::storage::FlowSparseMat) as its storage.
+pub type FlowSparseMatGraph<W, Dir> = SimpleGraph<W, FlowEdge<W>, Dir, FlowSparseMat<W, Dir>>;
+
 /// Representing a graph with no loops and multiple edges.
 ///
 /// ## Note
 /// `SimpleGraph` forwards most of its function calls to its underlying storage. So the complexities of its functions are dependent to what storage you use to initialize the graph.
-/// Therefore `SimpleGraph` only documents complexity of functions that it adds some additional logic to. For `SimpleGraph`, only `add_edge` function adds additional logic.
+/// Therefore `SimpleGraph` only documents complexity of functions that it adds some additional logic to. For `SimpleGraph`, only `add_edge` and `add_edges` functions add additional logic.
+///
+/// ## Observers
+/// Observers can be registered using `subscribe` function. After each successful mutation, every observer gets called with a [`GraphEvent`](crate::graph::GraphEvent) describing the mutation.
+/// When no observer is registered, notifying them has no overhead. But note that when there is at least one observer, removing a vertex takes O(|E|) more time to find the edges connected to the vertex.
+///
+/// ## Metadata
+/// Each graph carries a [`Metadata`](crate::graph::Metadata), like its name, description and provenance, which is empty by default.
+///
+/// ## Id Map
+/// The id map returned by `continuous_id_map` is cached, so it is only computed again after a vertex is added or removed.
 ///
 /// ## Generic Parameters
 /// * `W`: **W**eight type associated with edges.
@@ -34,6 +55,14 @@ pub type FlowListGraph<W, Dir> = SimpleGraph<W, DefaultEdge<W>, Dir, FlowList<W,
 pub struct SimpleGraph<W, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>> {
     storage: S,
 
+    // Observers are only called through `&mut self`, so the mutexes are never contended. They keep the graph `Sync` while observers are only `Send`.
+    observers: Vec<Option<Mutex<Observer>>>,
+
+    metadata: Metadata,
+
+    // Cached result of `continuous_id_map`, cleared whenever the set of vertices changes.
+    id_map: Mutex<Option<IdMap>>,
+
     phantom_w: PhantomData<W>,
     phantom_e: PhantomData<E>,
     phantom_dir: PhantomData<Dir>,
@@ -69,6 +98,12 @@ impl<W: Any, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>> SimpleGraph<W
         SimpleGraph {
             storage,
 
+            observers: vec![],
+
+            metadata: Metadata::init(),
+
+            id_map: Mutex::new(None),
+
             phantom_e: PhantomData,
             phantom_w: PhantomData,
             phantom_dir: PhantomData,
@@ -76,6 +111,522 @@ impl<W: Any, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>> SimpleGraph<W
     }
 }
 
+impl<W, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>> SimpleGraph<W, E, Dir, S> {
+    /// Registers `observer` to be called after each mutation of the graph.
+    ///
+    /// # Arguments
+    /// `observer`: Callback that receives the events.
+    ///
+    /// # Returns
+    /// Id of the observer that can be used to unsubscribe it.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::mpsc;
+    /// use prepona::prelude::*;
+    /// use prepona::storage::Mat;
+    /// use prepona::graph::{MatGraph, GraphEvent};
+    ///
+    /// let mut graph = MatGraph::init(Mat::<usize>::init());
+    ///
+    /// // Forward all events to a channel.
+    /// let (sender, receiver) = mpsc::channel();
+    /// graph.subscribe(Box::new(move |event| sender.send(*event).unwrap()));
+    ///
+    /// let a = graph.add_vertex();
+    /// let b = graph.add_vertex();
+    /// let ab = graph.add_edge(a, b, 1.into()).unwrap();
+    ///
+    /// assert_eq!(
+    ///     receiver.try_iter().collect::<Vec<GraphEvent>>(),
+    ///     vec![
+    ///         GraphEvent::VertexAdded(a),
+    ///         GraphEvent::VertexAdded(b),
+    ///         GraphEvent::EdgeAdded { src_id: a, dst_id: b, edge_id: ab },
+    ///     ]
+    /// );
+    /// ```
+    pub fn subscribe(&mut self, observer: Observer) -> usize {
+        self.observers.push(Some(Mutex::new(observer)));
+
+        self.observers.len() - 1
+    }
+
+    /// Removes the observer with id: `observer_id`.
+    ///
+    /// # Arguments
+    /// `observer_id`: Id of the observer returned by `subscribe`.
+    ///
+    /// # Returns
+    /// * `Some`: Containing the removed observer.
+    /// * `None`: If there is no observer with the specified id.
+    pub fn unsubscribe(&mut self, observer_id: usize) -> Option<Observer> {
+        self.observers
+            .get_mut(observer_id)
+            .and_then(|observer| observer.take())
+            .map(|observer| observer.into_inner().unwrap())
+    }
+
+    /// # Arguments
+    /// `metadata`: Metadata of the graph.
+    ///
+    /// # Returns
+    /// The graph with the specified metadata.
+    pub fn with_metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = metadata;
+
+        self
+    }
+
+    /// # Returns
+    /// Metadata of the graph.
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// # Returns
+    /// Mutable reference to metadata of the graph.
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.metadata
+    }
+
+    /// Checks the structural invariants of the graph.
+    /// It's useful to make sure a custom storage or a series of unchecked mutations left the graph in a consistent state.
+    ///
+    /// # Returns
+    /// List of [`violations`](crate::graph::Violation) found in the graph. Empty if the graph is valid.
+    ///
+    /// # Complexity
+    /// O(|V| + |E| * *finding edges between two vertices*)
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::storage::Mat;
+    /// use prepona::graph::{MatGraph, Violation};
+    /// use magnitude::Magnitude;
+    ///
+    /// let mut graph = MatGraph::init(Mat::<usize>::init());
+    /// let a = graph.add_vertex();
+    /// let b = graph.add_vertex();
+    /// let ab = graph.add_edge(a, b, 1.into()).unwrap();
+    /// assert!(graph.validate().is_empty());
+    ///
+    /// graph.update_edge(a, b, ab, DefaultEdge::init(Magnitude::NegInfinite)).unwrap();
+    /// assert_eq!(graph.validate(), vec![Violation::InvalidWeight { src_id: a, dst_id: b, edge_id: ab }]);
+    /// ```
+    pub fn validate(&self) -> Vec<Violation>
+    where
+        W: PartialEq,
+    {
+        validation::validate::<W, E, Dir, Self>(self)
+    }
+
+    /// Creates a new graph with the same vertices and topology, but with each edge transformed by `map`.
+    ///
+    /// Vertex ids are preserved. Edges are added to the new graph in the order of their ids,
+    /// so edge ids are preserved too as long as no edge has been removed from this graph. Observers and metadata are not copied.
+    ///
+    /// # Arguments
+    /// * `storage`: Empty storage for the new graph.
+    /// * `map`: Creates edge of the new graph from source id, destination id and edge of this graph.
+    ///
+    /// # Returns
+    /// The new graph.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|.log(|E|)) plus cost of adding the vertices and edges to the new storage.
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::storage::{Mat, FlowList};
+    /// use prepona::graph::{FlowEdge, MatGraph};
+    ///
+    /// let mut graph = MatGraph::init(Mat::<usize>::init());
+    /// let a = graph.add_vertex();
+    /// let b = graph.add_vertex();
+    /// let ab = graph.add_edge_unchecked(a, b, 3.into());
+    ///
+    /// // Use weight of each edge as capacity of a flow edge.
+    /// let flow_graph = graph.map_edges(FlowList::<usize>::init(), |_, _, edge| {
+    ///     FlowEdge::init_with(*edge.get_weight(), edge.get_weight().unwrap(), 0)
+    /// });
+    ///
+    /// assert_eq!(flow_graph.edge_unchecked(ab).unwrap().get_capacity(), 3);
+    /// ```
+    pub fn map_edges<W2, E2, S2, F>(&self, storage: S2, map: F) -> SimpleGraph<W2, E2, Dir, S2>
+    where
+        W2: Any,
+        E2: Edge<W2>,
+        S2: GraphStorage<W2, E2, Dir>,
+        F: Fn(usize, usize, &E) -> E2,
+    {
+        let mut graph = self.copy_vertices(storage);
+
+        for (src_id, dst_id, edge) in self.edges_by_id() {
+            graph
+                .storage
+                .add_edge_unchecked(src_id, dst_id, map(src_id, dst_id, edge));
+        }
+
+        graph
+    }
+
+    /// Creates a new graph with the same vertices and topology, but with weight of each edge transformed by `map`.
+    /// It's useful for converting distances to similarities or integer weights to floating point ones.
+    ///
+    /// For details about how ids are preserved checkout [`map_edges`](crate::graph::SimpleGraph::map_edges).
+    ///
+    /// # Arguments
+    /// * `storage`: Empty storage for the new graph.
+    /// * `map`: Computes weight of each edge of the new graph from weight of the edge in this graph.
+    ///
+    /// # Returns
+    /// The new graph.
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::storage::Mat;
+    /// use prepona::graph::MatGraph;
+    ///
+    /// let mut graph = MatGraph::init(Mat::<usize>::init());
+    /// let a = graph.add_vertex();
+    /// let b = graph.add_vertex();
+    /// let ab = graph.add_edge_unchecked(a, b, 4.into());
+    ///
+    /// // Convert distances to similarities.
+    /// let similarity_graph = graph.map_weights(Mat::<f64>::init(), |weight| {
+    ///     (1.0 / weight.unwrap() as f64).into()
+    /// });
+    ///
+    /// assert_eq!(similarity_graph.edge_unchecked(ab).unwrap().get_weight(), &0.25.into());
+    /// ```
+    pub fn map_weights<W2, E2, S2, F>(&self, storage: S2, map: F) -> SimpleGraph<W2, E2, Dir, S2>
+    where
+        W2: Any,
+        E2: Edge<W2>,
+        S2: GraphStorage<W2, E2, Dir>,
+        F: Fn(&Magnitude<W>) -> Magnitude<W2>,
+    {
+        self.map_edges(storage, |_, _, edge| E2::init(map(edge.get_weight())))
+    }
+
+    /// Creates a directed graph with the same vertices, where each undirected edge becomes two arcs in opposite directions, both holding a copy of the edge.
+    /// Loops become a single arc. If this graph is already directed, each edge is copied as it is.
+    ///
+    /// Vertex ids are preserved, but edge ids are not. Observers and metadata are not copied.
+    ///
+    /// # Arguments
+    /// `storage`: Empty directed storage for the new graph.
+    ///
+    /// # Returns
+    /// The directed graph.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|.log(|E|)) plus cost of adding the vertices and edges to the new storage.
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::storage::{DiMat, Mat};
+    /// use prepona::graph::MatGraph;
+    ///
+    /// let mut graph = MatGraph::init(Mat::<usize>::init());
+    /// let a = graph.add_vertex();
+    /// let b = graph.add_vertex();
+    /// graph.add_edge_unchecked(a, b, 3.into());
+    ///
+    /// let directed_graph = graph.to_directed(DiMat::<usize>::init());
+    ///
+    /// assert_eq!(directed_graph.edges_count(), 2);
+    /// assert_eq!(directed_graph.edges_between_unchecked(b, a)[0].get_weight(), &3.into());
+    /// ```
+    pub fn to_directed<S2>(&self, storage: S2) -> SimpleGraph<W, E, DirectedEdge, S2>
+    where
+        W: Any,
+        E: Clone,
+        S2: GraphStorage<W, E, DirectedEdge>,
+    {
+        let mut graph = self.copy_vertices(storage);
+
+        for (src_id, dst_id, edge) in self.edges_by_id() {
+            graph
+                .storage
+                .add_edge_unchecked(src_id, dst_id, edge.clone());
+
+            if Dir::is_undirected() && src_id != dst_id {
+                graph
+                    .storage
+                    .add_edge_unchecked(dst_id, src_id, edge.clone());
+            }
+        }
+
+        graph
+    }
+
+    /// Creates an undirected graph with the same vertices, where arcs between the same two vertices collapse into one edge.
+    /// An arc that has no reciprocal arc becomes an undirected edge holding a copy of the arc.
+    /// Edge that replaces more than one arc is a copy of the arc with the smallest id, and its weight is computed by folding weights of the arcs with `merge`,
+    /// in the order of their ids. So other fields of the edge, like capacity and flow of a [`FlowEdge`](crate::graph::FlowEdge), are kept from that arc.
+    /// If this graph is already undirected, each edge is copied as it is.
+    ///
+    /// Vertex ids are preserved, but edge ids are not. Observers and metadata are not copied.
+    ///
+    /// # Arguments
+    /// * `storage`: Empty undirected storage for the new graph.
+    /// * `merge`: Combines weights of two arcs between the same vertices, like sum or minimum of them.
+    ///
+    /// # Returns
+    /// The undirected graph.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|.log(|E|)) plus cost of adding the vertices and edges to the new storage.
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::storage::{DiMat, Mat};
+    /// use prepona::graph::MatGraph;
+    ///
+    /// // Given: Graph
+    /// //
+    /// //      a  --(2)-->  b  --(1)-->  c
+    /// //      ^            |
+    /// //      '----(5)-----'
+    /// //
+    /// let mut graph = MatGraph::init(DiMat::<usize>::init());
+    /// let a = graph.add_vertex();
+    /// let b = graph.add_vertex();
+    /// let c = graph.add_vertex();
+    /// graph.add_edge_unchecked(a, b, 2.into());
+    /// graph.add_edge_unchecked(b, a, 5.into());
+    /// graph.add_edge_unchecked(b, c, 1.into());
+    ///
+    /// // Keep the lighter arc of each reciprocal pair.
+    /// let undirected_graph = graph.to_undirected(Mat::<usize>::init(), |w1, w2| *w1.min(w2));
+    ///
+    /// assert_eq!(undirected_graph.edges_count(), 2);
+    /// assert_eq!(undirected_graph.edges_between_unchecked(b, a)[0].get_weight(), &2.into());
+    /// ```
+    pub fn to_undirected<S2, F>(&self, storage: S2, merge: F) -> SimpleGraph<W, E, UndirectedEdge, S2>
+    where
+        W: Any,
+        E: Clone,
+        S2: GraphStorage<W, E, UndirectedEdge>,
+        F: Fn(&Magnitude<W>, &Magnitude<W>) -> Magnitude<W>,
+    {
+        let mut graph = self.copy_vertices(storage);
+
+        // Edges of the new graph, and index of the edge that replaces the arcs between each pair of vertices.
+        let mut edges: Vec<(usize, usize, E)> = vec![];
+        let mut index_of = HashMap::new();
+        for (src_id, dst_id, edge) in self.edges_by_id() {
+            let end_points = (src_id.min(dst_id), src_id.max(dst_id));
+
+            match index_of.get(&end_points) {
+                Some(index) if Dir::is_directed() => {
+                    let merged = &mut edges[*index].2;
+                    let weight = merge(merged.get_weight(), edge.get_weight());
+                    merged.set_weight(weight);
+                }
+                _ => {
+                    index_of.insert(end_points, edges.len());
+                    edges.push((src_id, dst_id, edge.clone()));
+                }
+            }
+        }
+
+        for (src_id, dst_id, edge) in edges {
+            graph.storage.add_edge_unchecked(src_id, dst_id, edge);
+        }
+
+        graph
+    }
+
+    // Creates a graph with `storage` that has the same vertex ids as this graph, and no edges.
+    fn copy_vertices<W2, E2, Dir2, S2>(&self, storage: S2) -> SimpleGraph<W2, E2, Dir2, S2>
+    where
+        W2: Any,
+        E2: Edge<W2>,
+        Dir2: EdgeDir,
+        S2: GraphStorage<W2, E2, Dir2>,
+    {
+        let mut graph = SimpleGraph::init(storage);
+
+        let vertex_ids = self.storage.vertices();
+
+        // Add vertices until every id of this graph exists, then remove the ones that do not exist in this graph.
+        let vertex_id_bound = vertex_ids.iter().max().map_or(0, |max_id| max_id + 1);
+        let added_ids = (0..vertex_id_bound)
+            .map(|_| graph.storage.add_vertex())
+            .collect::<HashSet<usize>>();
+        let vertex_ids = vertex_ids.into_iter().collect::<HashSet<usize>>();
+        for vertex_id in added_ids.difference(&vertex_ids) {
+            graph.storage.remove_vertex_unchecked(*vertex_id);
+        }
+
+        graph
+    }
+
+    // Edges of the graph in the order of their ids.
+    fn edges_by_id(&self) -> Vec<(usize, usize, &E)> {
+        let mut edges = self.storage.edges();
+        edges.sort_by_key(|(_, _, edge)| edge.get_id());
+
+        edges
+    }
+
+    /// # Arguments
+    /// `other`: Graph to compute the difference to.
+    ///
+    /// # Returns
+    /// Delta that turns this graph into `other`. For details checkout [`GraphDelta`](crate::graph::GraphDelta).
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::storage::Mat;
+    /// use prepona::graph::{structurally_equal, MatGraph};
+    ///
+    /// let mut graph = MatGraph::init(Mat::<usize>::init());
+    /// let a = graph.add_vertex();
+    /// let b = graph.add_vertex();
+    /// let ab = graph.add_edge_unchecked(a, b, 1.into());
+    ///
+    /// let mut snapshot = graph.map_edges(Mat::<usize>::init(), |_, _, edge| *edge);
+    /// snapshot.update_edge_unchecked(a, b, ab, 2.into());
+    /// let c = snapshot.add_vertex();
+    /// snapshot.add_edge_unchecked(b, c, 3.into());
+    ///
+    /// let delta = graph.diff(&snapshot);
+    /// assert_eq!(delta.added_vertices(), &[c]);
+    /// assert_eq!(delta.updated_edges().len(), 1);
+    ///
+    /// graph.apply(&delta).unwrap();
+    /// assert!(structurally_equal(&graph, &snapshot));
+    /// ```
+    pub fn diff<G>(&self, other: &G) -> GraphDelta<W, E>
+    where
+        E: Clone + PartialEq,
+        G: Vertices + Edges<W, E> + Direction,
+    {
+        GraphDelta::between(self, other)
+    }
+
+    /// Applies `delta` to the graph. Observers are notified about each change.
+    ///
+    /// # Arguments
+    /// `delta`: Delta to be applied.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing a map from id of each added vertex in `delta` to its id in the graph.
+    /// * `Err`: If `delta` does not match the graph. For details checkout [`apply_to`](crate::graph::GraphDelta::apply_to).
+    pub fn apply(&mut self, delta: &GraphDelta<W, E>) -> Result<HashMap<usize, usize>>
+    where
+        E: Clone + PartialEq,
+    {
+        delta.apply_to(self)
+    }
+
+    /// Reports the memory used by the storage, plus the cached id map(if there is one) as part of the id sets.
+    /// It can be used to compare storages on the same graph, or to check how much capacity is left unused after removals.
+    ///
+    /// # Returns
+    /// Number of bytes the graph has allocated. Checkout [`MemoryUsage`](crate::storage::MemoryUsage) for what is and is not counted.
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::storage::{List, Mat};
+    /// use prepona::graph::{ListGraph, MatGraph};
+    ///
+    /// let mut mat_graph = MatGraph::init(Mat::<usize>::init());
+    /// let mut list_graph = ListGraph::init(List::<usize>::init());
+    /// for _ in 0..50 {
+    ///     mat_graph.add_vertex();
+    ///     list_graph.add_vertex();
+    /// }
+    ///
+    /// assert_eq!(list_graph.memory_usage().payloads(), 0);
+    /// assert!(mat_graph.memory_usage().edge_structure() > list_graph.memory_usage().edge_structure());
+    /// ```
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let id_map = match self.id_map.lock().unwrap().as_ref() {
+            Some(id_map) => id_map.memory_usage(),
+            None => 0,
+        };
+
+        self.storage.memory_usage() + MemoryUsage::init(0, id_map, 0)
+    }
+
+    /// Creates an immutable copy of the graph in compressed sparse row format, with its id map and degrees computed once.
+    /// Checkout [`FrozenGraph`](crate::graph::FrozenGraph).
+    ///
+    /// # Returns
+    /// Frozen copy of the graph.
+    ///
+    /// # Complexity
+    /// O(|V| + |E| * log(|E|))
+    pub fn freeze(&self) -> FrozenGraph<W, E, Dir>
+    where
+        E: Clone,
+    {
+        FrozenGraph::from_graph(self)
+    }
+
+    fn invalidate_id_map(&mut self) {
+        *self.id_map.get_mut().unwrap() = None;
+    }
+
+    fn has_observers(&self) -> bool {
+        self.observers.iter().any(|observer| observer.is_some())
+    }
+
+    fn notify(&mut self, event: GraphEvent) {
+        for observer in self.observers.iter_mut().flatten() {
+            (observer.get_mut().unwrap())(&event);
+        }
+    }
+
+    // Notifies observers about removal of edges connected to `vertex_id` and the vertex itself.
+    fn notify_vertex_removal(&mut self, vertex_id: usize, removed_edges: Vec<(usize, usize, usize)>) {
+        for (src_id, dst_id, edge_id) in removed_edges {
+            self.notify(GraphEvent::EdgeRemoved {
+                src_id,
+                dst_id,
+                edge_id,
+            });
+        }
+
+        self.notify(GraphEvent::VertexRemoved(vertex_id));
+    }
+
+    fn edges_of(&self, vertex_id: usize) -> Vec<(usize, usize, usize)> {
+        if self.has_observers() {
+            self.storage
+                .edges()
+                .into_iter()
+                .filter(|(src_id, dst_id, _)| *src_id == vertex_id || *dst_id == vertex_id)
+                .map(|(src_id, dst_id, edge)| (src_id, dst_id, edge.get_id()))
+                .collect()
+        } else {
+            vec![]
+        }
+    }
+
+    fn notify_edges_added(&mut self, edges: &[(usize, usize)], edge_ids: &[usize]) {
+        for ((src_id, dst_id), edge_id) in edges.iter().zip(edge_ids) {
+            self.notify(GraphEvent::EdgeAdded {
+                src_id: *src_id,
+                dst_id: *dst_id,
+                edge_id: *edge_id,
+            });
+        }
+    }
+}
+
 /// For documentation about each function checkout [`Neighbors`](crate::provide::Neighbors) trait.
 impl<W, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>> Neighbors
     for SimpleGraph<W, E, Dir, S>
@@ -101,11 +652,43 @@ impl<W, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>> Vertices
         self.storage.vertex_count()
     }
 
+    fn continuous_id_map(&self) -> IdMap {
+        self.id_map
+            .lock()
+            .unwrap()
+            .get_or_insert_with(|| IdMap::continuous(self.storage.vertices()))
+            .clone()
+    }
+
     fn contains_vertex(&self, vertex_id: usize) -> bool {
         self.storage.contains_vertex(vertex_id)
     }
 }
 
+/// For documentation about each function checkout [`Direction`](crate::provide::Direction) trait.
+impl<W, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>> Direction
+    for SimpleGraph<W, E, Dir, S>
+{
+    fn is_directed(&self) -> bool {
+        Dir::is_directed()
+    }
+}
+
+/// Two graphs are equal if their storages are equal. Registered observers are not compared.
+impl<W, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir> + PartialEq> PartialEq
+    for SimpleGraph<W, E, Dir, S>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.storage == other.storage
+    }
+}
+
+impl_pretty!(
+    "SimpleGraph",
+    [W, E, Dir, S] SimpleGraph<W, E, Dir, S>,
+    where E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>
+);
+
 /// For documentation about each function checkout [`Edges`](crate::provide::Edges) trait.
 impl<W, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>> Edges<W, E>
     for SimpleGraph<W, E, Dir, S>
@@ -172,15 +755,36 @@ impl<W, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>> Graph<W, E, Dir>
     for SimpleGraph<W, E, Dir, S>
 {
     fn add_vertex(&mut self) -> usize {
-        self.storage.add_vertex()
+        let vertex_id = self.storage.add_vertex();
+        self.invalidate_id_map();
+
+        self.notify(GraphEvent::VertexAdded(vertex_id));
+
+        vertex_id
     }
 
     fn remove_vertex(&mut self, vertex_id: usize) -> Result<()> {
-        self.storage.remove_vertex(vertex_id)
+        let removed_edges = if self.storage.contains_vertex(vertex_id) {
+            self.edges_of(vertex_id)
+        } else {
+            vec![]
+        };
+
+        self.storage.remove_vertex(vertex_id)?;
+        self.invalidate_id_map();
+
+        self.notify_vertex_removal(vertex_id, removed_edges);
+
+        Ok(())
     }
 
     fn remove_vertex_unchecked(&mut self, vertex_id: usize) {
-        self.storage.remove_vertex_unchecked(vertex_id)
+        let removed_edges = self.edges_of(vertex_id);
+
+        self.storage.remove_vertex_unchecked(vertex_id);
+        self.invalidate_id_map();
+
+        self.notify_vertex_removal(vertex_id, removed_edges);
     }
 
     fn add_edge(&mut self, src_id: usize, dst_id: usize, edge: E) -> Result<usize> {
@@ -192,36 +796,159 @@ impl<W, E: Edge<W>, Dir: EdgeDir, S: GraphStorage<W, E, Dir>> Graph<W, E, Dir>
         } else if src_id == dst_id {
             Err(Error::new_l(src_id))?
         } else {
-            self.add_edge(src_id, dst_id, edge)
+            Ok(self.add_edge_unchecked(src_id, dst_id, edge))
         }
     }
 
     fn add_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge: E) -> usize {
-        self.storage.add_edge_unchecked(src_id, dst_id, edge)
+        let edge_id = self.storage.add_edge_unchecked(src_id, dst_id, edge);
+
+        self.notify(GraphEvent::EdgeAdded {
+            src_id,
+            dst_id,
+            edge_id,
+        });
+
+        edge_id
+    }
+
+    /// Adds all `edges` to the graph in one pass.
+    ///
+    /// All edges are checked before any of them gets added. So either all edges are added or none of them.
+    ///
+    /// # Arguments
+    /// `edges`: Edges to be added in the format of: (`src_id`, `dst_id`, `edge`).
+    ///
+    /// # Returns
+    /// * `Ok`: Containing unique ids of the newly added edges, in the same order as `edges`.
+    /// * `Err`:
+    ///     * If source or destination of any of the edges does not exist.
+    ///     * If any of the edges is a loop.
+    ///     * If any of the edges is parallel to an edge already in the graph or to another edge in `edges`.
+    ///
+    /// # Complexity
+    /// O(*number of edges to be added* * *checking existence of an edge between two vertices* + *adding the edges to storage*)
+    fn add_edges<I>(&mut self, edges: I) -> Result<Vec<usize>>
+    where
+        I: IntoIterator<Item = (usize, usize, E)>,
+    {
+        let edges = edges.into_iter().collect::<Vec<(usize, usize, E)>>();
+
+        let mut seen = HashSet::new();
+        for (src_id, dst_id, _) in &edges {
+            let (src_id, dst_id) = (*src_id, *dst_id);
+
+            let key = if Dir::is_undirected() && dst_id < src_id {
+                (dst_id, src_id)
+            } else {
+                (src_id, dst_id)
+            };
+
+            if self
+                .has_any_edge(src_id, dst_id)
+                .with_context(|| "Invalid arguments")?
+                || !seen.insert(key)
+            {
+                Err(Error::new_me(src_id, dst_id))?
+            } else if src_id == dst_id {
+                Err(Error::new_l(src_id))?
+            }
+        }
+
+        Ok(self.add_edges_unchecked(edges))
+    }
+
+    fn add_edges_unchecked<I>(&mut self, edges: I) -> Vec<usize>
+    where
+        I: IntoIterator<Item = (usize, usize, E)>,
+    {
+        if !self.has_observers() {
+            return self.storage.add_edges_unchecked(edges);
+        }
+
+        let edges = edges.into_iter().collect::<Vec<(usize, usize, E)>>();
+        let endpoints = edges
+            .iter()
+            .map(|(src_id, dst_id, _)| (*src_id, *dst_id))
+            .collect::<Vec<(usize, usize)>>();
+
+        let edge_ids = self.storage.add_edges_unchecked(edges);
+
+        self.notify_edges_added(&endpoints, &edge_ids);
+
+        edge_ids
     }
 
     fn update_edge(&mut self, src_id: usize, dst_id: usize, edge_id: usize, edge: E) -> Result<()> {
-        self.storage.update_edge(src_id, dst_id, edge_id, edge)
+        // Storages only update the edge if it is between `src_id` and `dst_id`.
+        let is_updated = matches!(self.storage.edge_between(src_id, dst_id, edge_id), Ok(Some(_)));
+
+        self.storage.update_edge(src_id, dst_id, edge_id, edge)?;
+
+        if is_updated {
+            self.notify(GraphEvent::EdgeUpdated {
+                src_id,
+                dst_id,
+                edge_id,
+            });
+        }
+
+        Ok(())
     }
 
     fn update_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize, edge: E) {
+        let is_updated = self
+            .storage
+            .edge_between_unchecked(src_id, dst_id, edge_id)
+            .is_some();
+
         self.storage
-            .update_edge_unchecked(src_id, dst_id, edge_id, edge)
+            .update_edge_unchecked(src_id, dst_id, edge_id, edge);
+
+        if is_updated {
+            self.notify(GraphEvent::EdgeUpdated {
+                src_id,
+                dst_id,
+                edge_id,
+            });
+        }
     }
 
     fn remove_edge(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<Option<E>> {
-        self.storage.remove_edge(src_id, dst_id, edge_id)
+        let removed_edge = self.storage.remove_edge(src_id, dst_id, edge_id)?;
+
+        if removed_edge.is_some() {
+            self.notify(GraphEvent::EdgeRemoved {
+                src_id,
+                dst_id,
+                edge_id,
+            });
+        }
+
+        Ok(removed_edge)
     }
 
     fn remove_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> Option<E> {
-        self.storage.remove_edge_unchecked(src_id, dst_id, edge_id)
+        let removed_edge = self.storage.remove_edge_unchecked(src_id, dst_id, edge_id);
+
+        if removed_edge.is_some() {
+            self.notify(GraphEvent::EdgeRemoved {
+                src_id,
+                dst_id,
+                edge_id,
+            });
+        }
+
+        removed_edge
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::graph::DirectedEdge;
     use crate::provide::*;
+    use crate::storage::{DiFlowMat, DiList, DiMat};
 
     #[test]
     fn add_loop() {
@@ -248,4 +975,439 @@ mod tests {
 
         // Then: Code should panic.
     }
+
+    #[test]
+    fn add_edges() {
+        // Given: Graph
+        //
+        //      a   b   c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+
+        // When: Adding edges a --- b and b --- c at once.
+        let edge_ids = graph
+            .add_edges(vec![(a, b, 1.into()), (b, c, 2.into())])
+            .unwrap();
+
+        // Then:
+        assert_eq!(edge_ids.len(), 2);
+        assert_eq!(graph.edges_count(), 2);
+        assert!(graph.edge_between(a, b, edge_ids[0]).unwrap().is_some());
+        assert!(graph.edge_between(b, c, edge_ids[1]).unwrap().is_some());
+    }
+
+    #[test]
+    fn add_edges_with_duplicate() {
+        // Given: Graph
+        //
+        //      a   b   c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+
+        // When: Adding edges that contain a --- b twice.
+        let res = graph.add_edges(vec![(a, b, 1.into()), (b, c, 2.into()), (b, a, 3.into())]);
+
+        // Then: No edge must be added.
+        assert!(res.is_err());
+        assert_eq!(graph.edges_count(), 0);
+    }
+
+    #[test]
+    fn add_edges_with_loop() {
+        // Given: Graph
+        //
+        //      a   b
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+
+        // When: Adding edges that contain a loop.
+        let res = graph.add_edges(vec![(a, b, 1.into()), (b, b, 2.into())]);
+
+        // Then: No edge must be added.
+        assert!(res.is_err());
+        assert_eq!(graph.edges_count(), 0);
+    }
+
+    fn record_events(
+        graph: &mut MatGraph<usize, DirectedEdge>,
+    ) -> (usize, std::sync::Arc<std::sync::Mutex<Vec<GraphEvent>>>) {
+        let events = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+
+        let recorder = events.clone();
+        let observer_id = graph.subscribe(Box::new(move |event| recorder.lock().unwrap().push(*event)));
+
+        (observer_id, events)
+    }
+
+    #[test]
+    fn observe_mutations() {
+        // Given: Graph
+        //
+        //      a   b   c
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let (_, events) = record_events(&mut graph);
+
+        // When: Adding edges a --> b and c --> a, updating a --> b and removing vertex a.
+        let ab = graph.add_edge(a, b, 1.into()).unwrap();
+        let ca = graph.add_edges(vec![(c, a, 1.into())]).unwrap()[0];
+        graph.update_edge(a, b, ab, 2.into()).unwrap();
+        graph.remove_vertex(a).unwrap();
+
+        // Then:
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 6);
+        assert_eq!(events[0], GraphEvent::EdgeAdded { src_id: a, dst_id: b, edge_id: ab });
+        assert_eq!(events[1], GraphEvent::EdgeAdded { src_id: c, dst_id: a, edge_id: ca });
+        assert_eq!(events[2], GraphEvent::EdgeUpdated { src_id: a, dst_id: b, edge_id: ab });
+        assert!(events[3..5].contains(&GraphEvent::EdgeRemoved { src_id: a, dst_id: b, edge_id: ab }));
+        assert!(events[3..5].contains(&GraphEvent::EdgeRemoved { src_id: c, dst_id: a, edge_id: ca }));
+        assert_eq!(events[5], GraphEvent::VertexRemoved(a));
+    }
+
+    #[test]
+    fn failed_mutations_are_not_observed() {
+        // Given: Graph
+        //
+        //      a  -->  b
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+        let (_, events) = record_events(&mut graph);
+
+        // When: Performing invalid mutations.
+        assert!(graph.add_edge(a, b, 1.into()).is_err());
+        assert!(graph.add_edge(a, a, 1.into()).is_err());
+        assert!(graph.remove_vertex(b + 1).is_err());
+        assert!(graph.remove_edge(b, a, ab).unwrap().is_none());
+        graph.update_edge_unchecked(b, a, ab, 2.into());
+        graph.update_edge_unchecked(a, b, ab + 1, 2.into());
+
+        // Then:
+        assert!(events.lock().unwrap().is_empty());
+        assert_eq!(graph.edges_between_unchecked(a, b)[0].get_weight(), &1.into());
+    }
+
+    #[test]
+    fn remove_vertex_collect() {
+        // Given: Graph
+        //
+        //      a  --(1)-->  b  --(2)-->  c
+        //      ^            |
+        //      '----(3)-----'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+        let bc = graph.add_edge_unchecked(b, c, 2.into());
+        let ba = graph.add_edge_unchecked(b, a, 3.into());
+        let (_, events) = record_events(&mut graph);
+
+        // When: Removing b.
+        let removed_edges = graph.remove_vertex_collect(b).unwrap();
+
+        // Then: Removed edges are returned and observed.
+        let mut removed_ids = removed_edges
+            .iter()
+            .map(|(src_id, dst_id, edge)| (*src_id, *dst_id, edge.get_id()))
+            .collect::<Vec<(usize, usize, usize)>>();
+        removed_ids.sort_unstable();
+        assert_eq!(removed_ids, vec![(a, b, ab), (b, a, ba), (b, c, bc)]);
+        assert_eq!(graph.edges_count(), 0);
+        assert_eq!(events.lock().unwrap().len(), 4);
+        assert_eq!(events.lock().unwrap()[3], GraphEvent::VertexRemoved(b));
+        assert!(graph.remove_vertex_collect(b).is_err());
+
+        // When: Undoing the removal.
+        assert_eq!(graph.add_vertex(), b);
+        graph.add_edges(removed_edges).unwrap();
+
+        // Then:
+        assert_eq!(graph.edges_count(), 3);
+        assert_eq!(
+            graph.edges_between(b, c).unwrap()[0].get_weight(),
+            &2.into()
+        );
+    }
+
+    #[test]
+    fn unsubscribe() {
+        // Given: Graph with an observer.
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let (observer_id, events) = record_events(&mut graph);
+        let a = graph.add_vertex();
+
+        // When: Unsubscribing the observer and adding another vertex.
+        assert!(graph.unsubscribe(observer_id).is_some());
+        assert!(graph.unsubscribe(observer_id).is_none());
+        graph.add_vertex();
+
+        // Then: Only the first vertex must be observed.
+        assert_eq!(*events.lock().unwrap(), vec![GraphEvent::VertexAdded(a)]);
+    }
+
+    #[test]
+    fn neighbors_within() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c  -->  d
+        //      ^       |
+        //      '-------'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+        graph.add_edge_unchecked(b, a, 1.into());
+
+        // When: Finding vertices within 0, 2 and 5 hops of b.
+        let within_0 = graph.neighbors_within(b, 0).unwrap();
+        let within_2 = graph.neighbors_within(b, 2).unwrap();
+        let within_5 = graph.neighbors_within(b, 5).unwrap();
+
+        // Then:
+        assert_eq!(within_0.len(), 1);
+        assert_eq!(within_0[&b], 0);
+
+        assert_eq!(within_2.len(), 4);
+        assert_eq!(within_2[&a], 1);
+        assert_eq!(within_2[&c], 1);
+        assert_eq!(within_2[&d], 2);
+
+        assert_eq!(within_5, within_2);
+        assert_eq!(graph.neighbors_within(c, 5).unwrap().len(), 2);
+        assert!(graph.neighbors_within(d + 1, 1).is_err());
+    }
+
+    #[test]
+    fn map_edges_preserves_ids() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  d
+        //      ^               |
+        //      '---------------'
+        //
+        // And: Vertex c that was removed.
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+        let bd = graph.add_edge_unchecked(b, d, 2.into());
+        let da = graph.add_edge_unchecked(d, a, 3.into());
+        graph.remove_vertex_unchecked(c);
+
+        // When: Mapping each edge to its source and destination.
+        let mapped = graph.map_edges(DiList::<usize>::init(), |src_id, dst_id, edge| {
+            DefaultEdge::init((10 * src_id + dst_id + edge.get_weight().unwrap()).into())
+        });
+
+        // Then:
+        let mut vertices = mapped.vertices();
+        vertices.sort_unstable();
+        assert_eq!(vertices, vec![a, b, d]);
+        assert!(!mapped.contains_vertex(c));
+        assert!(mapped.is_directed());
+        assert_eq!(mapped.edges_count(), 3);
+        for (src_id, dst_id, edge_id) in [(a, b, ab), (b, d, bd), (d, a, da)] {
+            let weight = *mapped.edge_between_unchecked(src_id, dst_id, edge_id).unwrap().get_weight();
+            let old_weight = graph.edge_unchecked(edge_id).unwrap().get_weight().unwrap();
+
+            assert_eq!(weight, (10 * src_id + dst_id + old_weight).into());
+        }
+    }
+
+    #[test]
+    fn map_weights() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 2.into());
+        let bc = graph.add_edge_unchecked(b, c, 4.into());
+
+        // When: Converting weights to floats.
+        let mapped = graph.map_weights(Mat::<f64>::init(), |weight| (weight.unwrap() as f64 / 4.0).into());
+
+        // Then:
+        assert!(mapped.is_undirected());
+        assert_eq!(mapped.vertex_count(), 3);
+        assert_eq!(mapped.edge_between_unchecked(b, a, ab).unwrap().get_weight(), &0.5.into());
+        assert_eq!(mapped.edge_between_unchecked(c, b, bc).unwrap().get_weight(), &1.0.into());
+
+        // And: Original graph is not changed.
+        assert_eq!(graph.edge_unchecked(ab).unwrap().get_weight(), &2.into());
+    }
+
+    #[test]
+    fn to_directed() {
+        // Given: Graph
+        //
+        //      a  --(1)--  b  --(2)--  d
+        //
+        // And: Vertex c that was removed.
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, d, 2.into());
+        graph.remove_vertex_unchecked(c);
+
+        // When:
+        let directed = graph.to_directed(DiList::<usize>::init());
+
+        // Then: Each edge is replaced by two arcs with the same weight.
+        assert!(directed.is_directed());
+        assert!(!directed.contains_vertex(c));
+        assert_eq!(directed.vertex_count(), 3);
+        assert_eq!(directed.edges_count(), 4);
+        for (src_id, dst_id, weight) in [(a, b, 1), (b, a, 1), (b, d, 2), (d, b, 2)] {
+            assert_eq!(directed.edges_between_unchecked(src_id, dst_id)[0].get_weight(), &weight.into());
+        }
+
+        // And: Converting a directed graph copies its arcs.
+        assert_eq!(directed.to_directed(DiMat::<usize>::init()).edges_count(), 4);
+    }
+
+    #[test]
+    fn to_undirected() {
+        // Given: Graph
+        //
+        //      a  --(1)-->  b  --(4)-->  c
+        //      ^            |
+        //      '----(2)-----'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, a, 2.into());
+        graph.add_edge_unchecked(b, c, 4.into());
+
+        // When: Summing weights of the reciprocal arcs.
+        let undirected = graph.to_undirected(Mat::<usize>::init(), |w1, w2| *w1 + *w2);
+
+        // Then:
+        assert!(undirected.is_undirected());
+        assert_eq!(undirected.edges_count(), 2);
+        assert_eq!(undirected.edges_between_unchecked(a, b)[0].get_weight(), &3.into());
+        assert_eq!(undirected.edges_between_unchecked(c, b)[0].get_weight(), &4.into());
+
+        // And: Converting back to directed and undirected again doubles the weight of each edge.
+        let round_trip = undirected
+            .to_directed(DiMat::<usize>::init())
+            .to_undirected(Mat::<usize>::init(), |w1, w2| *w1 + *w2);
+        assert_eq!(round_trip.edges_count(), 2);
+        assert_eq!(round_trip.edges_between_unchecked(b, a)[0].get_weight(), &6.into());
+        assert_eq!(round_trip.edges_between_unchecked(b, c)[0].get_weight(), &8.into());
+    }
+
+    #[test]
+    fn to_undirected_keeps_flow() {
+        // Given: Graph with weight and flow/capacity of the edges
+        //
+        //      a  --(1, 2/5)-->  b
+        //      ^                 |
+        //      '----(4, 0/3)-----'
+        //
+        let mut graph = SimpleGraph::init(DiFlowMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, FlowEdge::init_with(1.into(), 5, 2));
+        graph.add_edge_unchecked(b, a, FlowEdge::init_with(4.into(), 3, 0));
+
+        // When:
+        let undirected = graph.to_undirected(FlowMat::<usize>::init(), |w1, w2| *w1 + *w2);
+
+        // Then: Weights are merged, and capacity and flow are kept from the first arc.
+        let edge = undirected.edges_between_unchecked(b, a)[0];
+        assert_eq!(undirected.edges_count(), 1);
+        assert_eq!(edge.get_weight(), &5.into());
+        assert_eq!(edge.get_capacity(), 5);
+        assert_eq!(edge.get_flow(), 2);
+    }
+
+    #[test]
+    fn equality() {
+        // Given: Graph
+        //
+        //      a  --(1)-->  b  --(2)-->  c
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 2.into());
+
+        // When: Mapping graph to a graph with the same edges, which preserves ids.
+        let mut copy = graph.map_edges(DiMat::<usize>::init(), |_, _, edge| *edge);
+
+        // Then:
+        assert!(graph == copy);
+
+        // When: Copy has a new edge.
+        copy.add_edge_unchecked(c, a, 1.into());
+
+        // Then:
+        assert!(graph != copy);
+    }
+
+    #[test]
+    fn cached_id_map() {
+        // Given: Graph with vertices a, b and c.
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+
+        // When: Computing id map and adding an edge.
+        let id_map = graph.continuous_id_map();
+        graph.add_edge_unchecked(a, b, 1.into());
+
+        // Then: Cached id map is returned.
+        assert!(graph.id_map.lock().unwrap().is_some());
+        assert_eq!(
+            graph.continuous_id_map().virt_id_of(c),
+            id_map.virt_id_of(c)
+        );
+
+        // When: Removing b.
+        graph.remove_vertex(b).unwrap();
+
+        // Then: Id map is computed again.
+        assert!(graph.id_map.lock().unwrap().is_none());
+        let id_map = graph.continuous_id_map();
+        assert_eq!(id_map.len(), 2);
+        assert_eq!(id_map.get_virt_id(b), None);
+        assert_eq!(id_map.virt_id_of(a), 0);
+        assert_eq!(id_map.virt_id_of(c), 1);
+    }
 }
@@ -0,0 +1,186 @@
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// Shares a graph between threads.
+///
+/// Cloning a `SyncGraph` is cheap and returns a handle to the same graph. Any number of threads can read the graph at the same time using `read`,
+/// and algorithms can be executed on the returned guard just like a regular graph. Mutating the graph using `write` waits until all readers are done.
+///
+/// `SyncGraph` does not implement any of the provider traits, because taking the lock on each call would make algorithms lock once per visited vertex
+/// and let writers change the graph in the middle of an algorithm. Take the guard once using `read` and pass it to algorithms instead.
+///
+/// ## Generic Parameters
+/// `G`: **G**raph that is being shared.
+///
+/// # Examples
+/// ```
+/// use std::thread;
+/// use prepona::prelude::*;
+/// use prepona::algo::Dijkstra;
+/// use prepona::storage::Mat;
+/// use prepona::graph::{MatGraph, SyncGraph};
+///
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+///
+/// let graph = SyncGraph::init(graph);
+///
+/// let handles: Vec<_> = (0..4)
+///     .map(|_| {
+///         let graph = graph.clone();
+///         thread::spawn(move || {
+///             let graph = graph.read();
+///             Dijkstra::init(&*graph).execute(&*graph, a).distance_to(b)
+///         })
+///     })
+///     .collect();
+///
+/// for handle in handles {
+///     assert_eq!(handle.join().unwrap(), Some(1.into()));
+/// }
+/// ```
+pub struct SyncGraph<G> {
+    graph: Arc<RwLock<G>>,
+}
+
+impl<G> SyncGraph<G> {
+    /// # Arguments
+    /// `graph`: Graph to be shared.
+    ///
+    /// # Returns
+    /// Initialized shared graph.
+    pub fn init(graph: G) -> Self {
+        SyncGraph {
+            graph: Arc::new(RwLock::new(graph)),
+        }
+    }
+
+    /// Blocks the current thread until no thread is writing to the graph.
+    ///
+    /// # Returns
+    /// Guard that gives read-only access to the graph. Other threads can read the graph while the guard is alive.
+    ///
+    /// # Panics
+    /// If a thread panicked while writing to the graph.
+    pub fn read(&self) -> RwLockReadGuard<'_, G> {
+        self.graph.read().unwrap()
+    }
+
+    /// Blocks the current thread until no other thread is reading or writing the graph.
+    ///
+    /// # Returns
+    /// Guard that gives exclusive access to the graph.
+    ///
+    /// # Panics
+    /// If a thread panicked while writing to the graph.
+    pub fn write(&self) -> RwLockWriteGuard<'_, G> {
+        self.graph.write().unwrap()
+    }
+
+    /// # Returns
+    /// * `Ok`: Containing the shared graph, if this is the only handle to the graph.
+    /// * `Err`: Containing this handle, if there are other handles to the graph.
+    ///
+    /// # Panics
+    /// If a thread panicked while writing to the graph.
+    pub fn into_inner(self) -> std::result::Result<G, Self> {
+        match Arc::try_unwrap(self.graph) {
+            Ok(graph) => Ok(graph.into_inner().unwrap()),
+            Err(graph) => Err(SyncGraph { graph }),
+        }
+    }
+}
+
+impl<G> Clone for SyncGraph<G> {
+    fn clone(&self) -> Self {
+        SyncGraph {
+            graph: Arc::clone(&self.graph),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+    use crate::algo::{Bfs, Dijkstra};
+    use crate::graph::{ListGraph, MatGraph, UndirectedEdge};
+    use crate::provide::*;
+    use crate::storage::{DiList, Mat};
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn is_send_and_sync() {
+        assert_send_sync::<SyncGraph<MatGraph<usize, UndirectedEdge>>>();
+    }
+
+    #[test]
+    fn concurrent_readers() {
+        // Given: Graph
+        //
+        //      a  --(1)-->  b  --(2)-->  c
+        //
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 2.into());
+        let graph = SyncGraph::init(graph);
+
+        // When: Running algorithms on the graph from multiple threads.
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let graph = graph.clone();
+                thread::spawn(move || {
+                    let graph = graph.read();
+                    let order = Bfs::init_with_starts(&*graph, vec![a]).execute(&*graph);
+                    let distance = Dijkstra::init(&*graph).execute(&*graph, a).distance_to(c);
+
+                    (distance, order)
+                })
+            })
+            .collect();
+
+        // Then:
+        for handle in handles {
+            let (distance, order) = handle.join().unwrap();
+            assert_eq!(distance, Some(3.into()));
+            assert_eq!(order, vec![a, b, c]);
+        }
+    }
+
+    #[test]
+    fn writes_are_visible_to_all_handles() {
+        // Given: Empty graph shared by two handles.
+        let graph = SyncGraph::init(MatGraph::init(Mat::<usize>::init()));
+        let handle = graph.clone();
+
+        // When: Adding vertices from another thread.
+        let (a, b) = thread::spawn(move || {
+            let mut graph = handle.write();
+            let a = graph.add_vertex();
+            let b = graph.add_vertex();
+            graph.add_edge_unchecked(a, b, 1.into());
+
+            (a, b)
+        })
+        .join()
+        .unwrap();
+
+        // Then:
+        let guard = graph.read();
+        assert_eq!(guard.vertex_count(), 2);
+        assert!(guard.is_undirected());
+        assert_eq!(guard.neighbors(a).unwrap(), vec![b]);
+        assert_eq!(guard.edges_count(), 1);
+        drop(guard);
+
+        // And: Graph can be taken back once it is no longer shared.
+        let graph = graph.into_inner().ok().unwrap();
+        assert_eq!(graph.vertex_count(), 2);
+    }
+}
@@ -0,0 +1,527 @@
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+
+use anyhow::Result;
+
+use crate::graph::subgraph::Subgraph;
+use crate::graph::{Edge, EdgeDir, Error};
+use crate::provide::{Direction, Edges, Graph, Neighbors, Vertices};
+
+/// Closed interval of time in which an edge of a [`TemporalGraph`](crate::graph::TemporalGraph) is valid.
+///
+/// An edge that exists only at a single timestamp has an interval that starts and ends at that timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interval<T> {
+    start: T,
+    end: T,
+}
+
+impl<T: Ord + Copy> Interval<T> {
+    /// # Arguments
+    /// * `start`: First moment of the interval.
+    /// * `end`: Last moment of the interval.
+    ///
+    /// # Returns
+    /// Initialized interval.
+    ///
+    /// # Panics
+    /// If `end` is before `start`.
+    pub fn init(start: T, end: T) -> Self {
+        assert!(start <= end, "Interval can not end before it starts");
+
+        Interval { start, end }
+    }
+
+    /// # Arguments
+    /// `time`: The only moment of the interval.
+    ///
+    /// # Returns
+    /// Interval that starts and ends at `time`.
+    pub fn instant(time: T) -> Self {
+        Interval {
+            start: time,
+            end: time,
+        }
+    }
+
+    /// # Returns
+    /// First moment of the interval.
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    /// # Returns
+    /// Last moment of the interval.
+    pub fn end(&self) -> T {
+        self.end
+    }
+
+    /// # Returns
+    /// `true` if `time` is in the interval, `false` otherwise.
+    pub fn contains(&self, time: T) -> bool {
+        self.start <= time && time <= self.end
+    }
+}
+
+/// Wraps a graph and assigns a validity interval to its edges, so the graph can change over time.
+///
+/// Edges without an interval, like the ones added using `add_edge` of the [`Graph`](crate::provide::Graph) trait, are valid at all times.
+/// A snapshot of the graph at a moment contains all vertices of the graph, and the edges that are valid at that moment.
+/// Use [`EarliestArrival`](crate::algo::EarliestArrival) to find time-respecting paths, that traverse each edge while it is valid.
+///
+/// `TemporalGraph` implements all provider traits that the wrapped graph implements, so it can be passed directly to algorithms.
+/// Algorithms see every edge regardless of its interval. Pass a snapshot instead, to only consider edges that are valid at a moment.
+///
+/// ## Generic Parameters
+/// * `T`: **T**ime type of the intervals.
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that graph uses.
+/// * `Dir`: **Dir**ection of edges: [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge).
+/// * `G`: **G**raph whose edges are being timed.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::{Interval, MatGraph, TemporalGraph};
+///
+/// let mut graph = TemporalGraph::init(MatGraph::init(DiMat::<usize>::init()));
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_temporal_edge(a, b, 1.into(), Interval::init(0, 10)).unwrap();
+/// graph.add_temporal_edge(b, c, 1.into(), Interval::instant(5)).unwrap();
+///
+/// let snapshot = graph.snapshot_at(7);
+/// assert!(snapshot.has_any_edge_unchecked(a, b));
+/// assert!(!snapshot.has_any_edge_unchecked(b, c));
+/// assert_eq!(snapshot.vertex_count(), 3);
+///
+/// assert_eq!(graph.snapshot_at(5).edges_count(), 2);
+/// ```
+pub struct TemporalGraph<T, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir>,
+{
+    graph: G,
+
+    interval_of: HashMap<usize, Interval<T>>,
+
+    phantom_w: PhantomData<W>,
+    phantom_e: PhantomData<E>,
+    phantom_dir: PhantomData<Dir>,
+}
+
+impl<T, W, E, Dir, G> TemporalGraph<T, W, E, Dir, G>
+where
+    T: Ord + Copy,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices + Neighbors + Edges<W, E>,
+{
+    /// # Arguments
+    /// `graph`: Graph to be timed. Edges that are already in the graph are valid at all times.
+    ///
+    /// # Returns
+    /// Initialized temporal graph.
+    pub fn init(graph: G) -> Self {
+        TemporalGraph {
+            graph,
+
+            interval_of: HashMap::new(),
+
+            phantom_w: PhantomData,
+            phantom_e: PhantomData,
+            phantom_dir: PhantomData,
+        }
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    /// * `edge`: Edge to be added from source to destination.
+    /// * `interval`: Interval in which the edge is valid.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing id of the newly added edge.
+    /// * `Err`: Error of adding the edge to the wrapped graph.
+    pub fn add_temporal_edge(
+        &mut self,
+        src_id: usize,
+        dst_id: usize,
+        edge: E,
+        interval: Interval<T>,
+    ) -> Result<usize> {
+        let edge_id = self.graph.add_edge(src_id, dst_id, edge)?;
+        self.interval_of.insert(edge_id, interval);
+
+        Ok(edge_id)
+    }
+
+    /// Sets interval of the edge with id: `edge_id`, replacing its previous interval if any.
+    ///
+    /// # Arguments
+    /// * `edge_id`: Id of the edge.
+    /// * `interval`: Interval in which the edge is valid.
+    ///
+    /// # Returns
+    /// * `Ok`: If interval is set successfully.
+    /// * `Err`: [`EdgeNotFound`](crate::graph::ErrorKind::EdgeNotFound) if edge does not exist.
+    pub fn set_interval(&mut self, edge_id: usize, interval: Interval<T>) -> Result<()> {
+        if !self.graph.contains_edge(edge_id) {
+            Err(Error::new_enf(edge_id))?
+        }
+
+        self.interval_of.insert(edge_id, interval);
+
+        Ok(())
+    }
+
+    /// # Arguments
+    /// `edge_id`: Id of the edge.
+    ///
+    /// # Returns
+    /// * `Some`: Containing interval of the edge.
+    /// * `None`: If edge does not exist or it is valid at all times.
+    pub fn interval_of(&self, edge_id: usize) -> Option<&Interval<T>> {
+        self.interval_of.get(&edge_id)
+    }
+
+    /// # Arguments
+    /// * `edge_id`: Id of the edge.
+    /// * `time`: Moment to check validity of the edge at.
+    ///
+    /// # Returns
+    /// `true` if edge exists and it is valid at `time`, `false` otherwise.
+    pub fn is_valid_at(&self, edge_id: usize, time: T) -> bool {
+        self.graph.contains_edge(edge_id)
+            && match self.interval_of.get(&edge_id) {
+                Some(interval) => interval.contains(time),
+                None => true,
+            }
+    }
+
+    /// # Arguments
+    /// `time`: Moment to take the snapshot at.
+    ///
+    /// # Returns
+    /// Subgraph containing all vertices of the graph, and the edges that are valid at `time`.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|)
+    pub fn snapshot_at(&self, time: T) -> Subgraph<'_, W, E, Dir, G> {
+        let edges = self
+            .graph
+            .edges()
+            .into_iter()
+            .filter(|(_, _, edge)| self.is_valid_at(edge.get_id(), time))
+            .map(|(src_id, dst_id, edge)| (src_id, dst_id, edge.get_id()))
+            .collect();
+
+        let vertex_ids = self
+            .graph
+            .vertices()
+            .into_iter()
+            .collect::<HashSet<usize>>();
+
+        Subgraph::init(&self.graph, edges, vertex_ids)
+    }
+
+    /// # Returns
+    /// The wrapped graph.
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+
+    /// # Returns
+    /// The wrapped graph, dropping the intervals.
+    pub fn into_graph(self) -> G {
+        self.graph
+    }
+}
+
+/// For documentation about each function checkout [`Graph`](crate::provide::Graph) trait.
+/// Removing an edge, or a vertex along with its edges, also removes their intervals.
+impl<T, W, E, Dir, G> Graph<W, E, Dir> for TemporalGraph<T, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Edges<W, E>,
+{
+    fn add_vertex(&mut self) -> usize {
+        self.graph.add_vertex()
+    }
+
+    fn remove_vertex(&mut self, vertex_id: usize) -> Result<()> {
+        self.graph.remove_vertex(vertex_id)?;
+        self.remove_stale_intervals();
+
+        Ok(())
+    }
+
+    fn remove_vertex_unchecked(&mut self, vertex_id: usize) {
+        self.graph.remove_vertex_unchecked(vertex_id);
+        self.remove_stale_intervals();
+    }
+
+    fn add_edge(&mut self, src_id: usize, dst_id: usize, edge: E) -> Result<usize> {
+        self.graph.add_edge(src_id, dst_id, edge)
+    }
+
+    fn add_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge: E) -> usize {
+        self.graph.add_edge_unchecked(src_id, dst_id, edge)
+    }
+
+    fn update_edge(&mut self, src_id: usize, dst_id: usize, edge_id: usize, edge: E) -> Result<()> {
+        self.graph.update_edge(src_id, dst_id, edge_id, edge)
+    }
+
+    fn update_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize, edge: E) {
+        self.graph
+            .update_edge_unchecked(src_id, dst_id, edge_id, edge)
+    }
+
+    fn remove_edge(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<Option<E>> {
+        let edge = self.graph.remove_edge(src_id, dst_id, edge_id)?;
+        self.interval_of.remove(&edge_id);
+
+        Ok(edge)
+    }
+
+    fn remove_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> Option<E> {
+        self.interval_of.remove(&edge_id);
+        self.graph.remove_edge_unchecked(src_id, dst_id, edge_id)
+    }
+}
+
+impl<T, W, E, Dir, G> TemporalGraph<T, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Edges<W, E>,
+{
+    // Removes intervals of the edges that no longer exist, so their ids can be reused by the wrapped graph.
+    fn remove_stale_intervals(&mut self) {
+        let graph = &self.graph;
+
+        self.interval_of
+            .retain(|edge_id, _| graph.contains_edge(*edge_id));
+    }
+}
+
+/// For documentation about each function checkout [`Neighbors`](crate::provide::Neighbors) trait.
+impl<T, W, E, Dir, G> Neighbors for TemporalGraph<T, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Neighbors,
+{
+    fn neighbors(&self, src_id: usize) -> Result<Vec<usize>> {
+        self.graph.neighbors(src_id)
+    }
+
+    fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
+        self.graph.neighbors_unchecked(src_id)
+    }
+}
+
+/// For documentation about each function checkout [`Vertices`](crate::provide::Vertices) trait.
+impl<T, W, E, Dir, G> Vertices for TemporalGraph<T, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Vertices,
+{
+    fn vertices(&self) -> Vec<usize> {
+        self.graph.vertices()
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.graph.vertex_count()
+    }
+
+    fn contains_vertex(&self, vertex_id: usize) -> bool {
+        self.graph.contains_vertex(vertex_id)
+    }
+}
+
+/// For documentation about each function checkout [`Direction`](crate::provide::Direction) trait.
+impl<T, W, E, Dir, G> Direction for TemporalGraph<T, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir>,
+{
+    fn is_directed(&self) -> bool {
+        Dir::is_directed()
+    }
+}
+
+/// For documentation about each function checkout [`Edges`](crate::provide::Edges) trait.
+impl<T, W, E, Dir, G> Edges<W, E> for TemporalGraph<T, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Edges<W, E>,
+{
+    fn edges_from(&self, src_id: usize) -> Result<Vec<(usize, &E)>> {
+        self.graph.edges_from(src_id)
+    }
+
+    fn edges_from_unchecked(&self, src_id: usize) -> Vec<(usize, &E)> {
+        self.graph.edges_from_unchecked(src_id)
+    }
+
+    fn edges_between(&self, src_id: usize, dst_id: usize) -> Result<Vec<&E>> {
+        self.graph.edges_between(src_id, dst_id)
+    }
+
+    fn edges_between_unchecked(&self, src_id: usize, dst_id: usize) -> Vec<&E> {
+        self.graph.edges_between_unchecked(src_id, dst_id)
+    }
+
+    fn edge_between(&self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<Option<&E>> {
+        self.graph.edge_between(src_id, dst_id, edge_id)
+    }
+
+    fn edge_between_unchecked(&self, src_id: usize, dst_id: usize, edge_id: usize) -> Option<&E> {
+        self.graph.edge_between_unchecked(src_id, dst_id, edge_id)
+    }
+
+    fn edge(&self, edge_id: usize) -> Result<Option<&E>> {
+        self.graph.edge(edge_id)
+    }
+
+    fn edge_unchecked(&self, edge_id: usize) -> Option<&E> {
+        self.graph.edge_unchecked(edge_id)
+    }
+
+    fn has_any_edge(&self, src_id: usize, dst_id: usize) -> Result<bool> {
+        self.graph.has_any_edge(src_id, dst_id)
+    }
+
+    fn has_any_edge_unchecked(&self, src_id: usize, dst_id: usize) -> bool {
+        self.graph.has_any_edge_unchecked(src_id, dst_id)
+    }
+
+    fn edges(&self) -> Vec<(usize, usize, &E)> {
+        self.graph.edges()
+    }
+
+    fn as_directed_edges(&self) -> Vec<(usize, usize, &E)> {
+        self.graph.as_directed_edges()
+    }
+
+    fn edges_count(&self) -> usize {
+        self.graph.edges_count()
+    }
+
+    fn contains_edge(&self, edge_id: usize) -> bool {
+        self.graph.contains_edge(edge_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ErrorKind, ListGraph, MatGraph};
+    use crate::storage::{DiList, Mat};
+
+    #[test]
+    fn snapshots() {
+        // Given: Graph
+        //
+        //      a  --[0, 5]--  b  --[3, 8]--  c
+        //      |                             |
+        //      '------------ always ---------'
+        //
+        let mut graph = TemporalGraph::init(MatGraph::init(Mat::<usize>::init()));
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let ab = graph
+            .add_temporal_edge(a, b, 1.into(), Interval::init(0, 5))
+            .unwrap();
+        let bc = graph
+            .add_temporal_edge(b, c, 1.into(), Interval::init(3, 8))
+            .unwrap();
+        let ac = graph.add_edge_unchecked(a, c, 1.into());
+
+        // When:
+        let edge_ids_at = |time| {
+            let mut edge_ids = graph
+                .snapshot_at(time)
+                .edges()
+                .into_iter()
+                .map(|(_, _, edge)| edge.get_id())
+                .collect::<Vec<usize>>();
+            edge_ids.sort_unstable();
+            edge_ids.dedup();
+
+            edge_ids
+        };
+
+        // Then:
+        assert_eq!(edge_ids_at(0), vec![ab, ac]);
+        assert_eq!(edge_ids_at(4), vec![ab, bc, ac]);
+        assert_eq!(edge_ids_at(8), vec![bc, ac]);
+        assert_eq!(edge_ids_at(9), vec![ac]);
+        assert_eq!(graph.interval_of(ac), None);
+        assert_eq!(graph.snapshot_at(9).vertex_count(), 3);
+    }
+
+    #[test]
+    fn intervals_are_removed_with_edges() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c
+        //
+        let mut graph = TemporalGraph::init(ListGraph::init(DiList::<usize>::init()));
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let ab = graph
+            .add_temporal_edge(a, b, 1.into(), Interval::instant(1))
+            .unwrap();
+        let bc = graph
+            .add_temporal_edge(b, c, 1.into(), Interval::instant(2))
+            .unwrap();
+
+        // When:
+        graph.remove_edge_unchecked(a, b, ab);
+        graph.remove_vertex_unchecked(c);
+
+        // Then:
+        assert_eq!(graph.interval_of(ab), None);
+        assert_eq!(graph.interval_of(bc), None);
+        assert!(!graph.is_valid_at(bc, 2));
+    }
+
+    #[test]
+    fn set_interval() {
+        let mut graph = TemporalGraph::init(MatGraph::init(Mat::<usize>::init()));
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+
+        graph.set_interval(ab, Interval::init(2, 4)).unwrap();
+        let result = graph.set_interval(ab + 1, Interval::instant(0));
+
+        assert_eq!(graph.interval_of(ab), Some(&Interval::init(2, 4)));
+        assert!(graph.is_valid_at(ab, 3));
+        assert!(!graph.is_valid_at(ab, 5));
+        let error = result.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            ErrorKind::EdgeNotFound
+        ));
+    }
+
+    #[test]
+    #[should_panic]
+    fn reversed_interval() {
+        Interval::init(2, 1);
+    }
+}
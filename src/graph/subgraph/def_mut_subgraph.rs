@@ -1,8 +1,9 @@
-use std::{collections::HashSet, marker::PhantomData};
+use std::collections::{BTreeSet, HashSet};
+use std::marker::PhantomData;
 
 use crate::{
-    graph::{error::Error, EdgeDir},
-    prelude::{Edge, Edges, Graph, Neighbors, Vertices},
+    graph::{error::Error, impl_pretty, EdgeDir},
+    prelude::{Direction, Edge, Edges, Graph, Neighbors, Vertices},
 };
 use anyhow::{Context, Result};
 
@@ -12,7 +13,8 @@ pub struct MutSubgraph<'a, W, E: Edge<W>, Dir: EdgeDir, G: Graph<W, E, Dir>> {
     graph: &'a mut G,
 
     edges: Vec<(usize, usize, usize)>,
-    vertex_ids: HashSet<usize>,
+    // Kept sorted so vertices are returned in ascending order.
+    vertex_ids: BTreeSet<usize>,
 
     phantom_w: PhantomData<W>,
     phantom_e: PhantomData<E>,
@@ -33,7 +35,7 @@ where
         MutSubgraph {
             graph,
             edges,
-            vertex_ids,
+            vertex_ids: vertex_ids.into_iter().collect(),
 
             phantom_w: PhantomData,
             phantom_e: PhantomData,
@@ -79,6 +81,23 @@ where
     }
 }
 
+impl<'a, W, E, Dir, G> Direction for MutSubgraph<'a, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir>,
+{
+    fn is_directed(&self) -> bool {
+        Dir::is_directed()
+    }
+}
+
+impl_pretty!(
+    "MutSubgraph",
+    ['a, W, E, Dir, G] MutSubgraph<'a, W, E, Dir, G>,
+    where E: Edge<W>, Dir: EdgeDir, G: Graph<W, E, Dir> + Edges<W, E> + Neighbors
+);
+
 impl<'a, W, E, Dir, G> Edges<W, E> for MutSubgraph<'a, W, E, Dir, G>
 where
     E: Edge<W>,
@@ -1,10 +1,11 @@
-use std::{collections::HashSet, marker::PhantomData};
+use std::collections::{BTreeSet, HashSet};
+use std::marker::PhantomData;
 
 use anyhow::{Context, Result};
 
 use crate::{
-    graph::{error::Error, EdgeDir},
-    prelude::{Edge, Edges, Graph, Neighbors, Vertices},
+    graph::{error::Error, impl_pretty, EdgeDir},
+    prelude::{Direction, Edge, Edges, Graph, Neighbors, Vertices},
 };
 
 use super::{AsFrozenSubgraph, AsSubgraph};
@@ -18,7 +19,8 @@ where
     graph: &'a G,
 
     edges: Vec<(usize, usize, usize)>,
-    vertex_ids: HashSet<usize>,
+    // Kept sorted so vertices are returned in ascending order.
+    vertex_ids: BTreeSet<usize>,
 
     phantom_w: PhantomData<W>,
     phantom_e: PhantomData<E>,
@@ -39,7 +41,7 @@ where
         Subgraph {
             graph,
             edges,
-            vertex_ids,
+            vertex_ids: vertex_ids.into_iter().collect(),
 
             phantom_w: PhantomData,
             phantom_e: PhantomData,
@@ -85,6 +87,23 @@ where
     }
 }
 
+impl<'a, W, E, Dir, G> Direction for Subgraph<'a, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir>,
+{
+    fn is_directed(&self) -> bool {
+        Dir::is_directed()
+    }
+}
+
+impl_pretty!(
+    "Subgraph",
+    ['a, W, E, Dir, G] Subgraph<'a, W, E, Dir, G>,
+    where E: Edge<W>, Dir: EdgeDir, G: Graph<W, E, Dir> + Edges<W, E> + Neighbors
+);
+
 impl<'a, W, E, Dir, G> Edges<W, E> for Subgraph<'a, W, E, Dir, G>
 where
     E: Edge<W>,
@@ -2,6 +2,7 @@ mod def_mut_subgraph;
 mod def_subgraph;
 mod mr_subgraph;
 mod sp_subgraph;
+mod st_subgraph;
 
 use crate::graph::Edge;
 use crate::provide::{Edges, Neighbors, Vertices};
@@ -11,6 +12,7 @@ pub use def_mut_subgraph::MutSubgraph;
 pub use def_subgraph::Subgraph;
 pub use mr_subgraph::MultiRootSubgraph;
 pub use sp_subgraph::ShortestPathSubgraph;
+pub use st_subgraph::SpanningTreeSubgraph;
 
 /// Describes a subgraph that neither graph nor subgraph can be mutated.
 pub trait AsFrozenSubgraph<W, E: Edge<W>>: Neighbors + Vertices + Edges<W, E> {}
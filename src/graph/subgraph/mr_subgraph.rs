@@ -2,10 +2,10 @@ use std::collections::HashSet;
 
 use anyhow::{Context, Result};
 
-use crate::{graph::error::Error, provide::{Edges, Graph, Neighbors, Vertices}};
+use crate::{graph::error::Error, provide::{Direction, Edges, Graph, Neighbors, Vertices}};
 
 use super::{AsFrozenSubgraph, AsSubgraph, Subgraph};
-use crate::graph::{Edge, EdgeDir};
+use crate::graph::{impl_pretty, Edge, EdgeDir};
 
 /// A subgraph with some vertices elected as root.
 ///
@@ -64,7 +64,7 @@ where
     }
 
     /// # Arguments
-    /// `vertex_id`: Id of the vertex to be checked wether is a root or not.
+    /// `vertex_id`: Id of the vertex to be checked whether is a root or not.
     ///
     /// # Returns
     /// * `true`: If vertex with id: `vertex_id` is a root.
@@ -129,6 +129,24 @@ where
     }
 }
 
+/// For documentation about each function checkout [`Direction`](crate::provide::Direction) trait.
+impl<'a, W, E, Dir, G> Direction for MultiRootSubgraph<'a, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Edges<W, E> + Neighbors,
+{
+    fn is_directed(&self) -> bool {
+        Dir::is_directed()
+    }
+}
+
+impl_pretty!(
+    "MultiRootSubgraph",
+    ['a, W, E, Dir, G] MultiRootSubgraph<'a, W, E, Dir, G>,
+    where E: Edge<W>, Dir: EdgeDir, G: Graph<W, E, Dir> + Edges<W, E> + Neighbors
+);
+
 /// For documentation about each function checkout [`Edges`](crate::provide::Edges) trait.
 /// `MultiRootSubgraph` uses `Subgraph` internally so for complexity of each function checkout [`Subgraph`](crate::graph::subgraph::Subgraph).
 impl<'a, W, E, Dir, G> Edges<W, E> for MultiRootSubgraph<'a, W, E, Dir, G>
@@ -1,10 +1,11 @@
 use std::collections::{HashMap, HashSet};
 
 use magnitude::Magnitude;
-use provide::{Edges, Graph, Neighbors, Vertices};
+use provide::{Direction, Edges, Graph, Neighbors, Vertices};
 
 use super::{AsFrozenSubgraph, Subgraph};
-use crate::graph::{Edge, EdgeDir};
+use crate::algo::PathTracer;
+use crate::graph::{impl_pretty, Edge, EdgeDir};
 use crate::provide;
 
 /// Subgraph containing edges and vertices that participate in the shortest path tree.
@@ -18,6 +19,9 @@ where
     G: Graph<W, E, Dir> + Edges<W, E> + Neighbors,
 {
     distance_map: HashMap<usize, Magnitude<W>>,
+
+    tracer: PathTracer,
+
     subgraph: Subgraph<'a, W, E, Dir, G>,
 }
 
@@ -42,8 +46,11 @@ where
         vertices: HashSet<usize>,
         distance_map: HashMap<usize, Magnitude<W>>,
     ) -> Self {
+        let tracer = PathTracer::from_edges(&edges);
+
         ShortestPathSubgraph {
             distance_map,
+            tracer,
             subgraph: Subgraph::init(graph, edges, vertices),
         }
     }
@@ -60,6 +67,29 @@ where
     pub fn distance_to(&self, dst_id: usize) -> Option<Magnitude<W>> {
         self.distance_map.get(&dst_id).copied()
     }
+
+    /// # Arguments
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing id of the vertices on the shortest path from source vertex to vertex with id: `dst_id`, including both of them.
+    /// * `None`: If vertex with id: `dst_id` is not in the subgraph.
+    ///
+    /// # Complexity
+    /// O(length of the path)
+    pub fn path_to(&self, dst_id: usize) -> Option<Vec<usize>> {
+        if !self.subgraph.contains_vertex(dst_id) {
+            return None;
+        }
+
+        self.tracer.vertex_path(dst_id).ok()
+    }
+
+    /// # Returns
+    /// Tracer to reconstruct paths from the source vertex, in terms of edges or vertices.
+    pub fn tracer(&self) -> &PathTracer {
+        &self.tracer
+    }
 }
 
 /// For documentation about each function checkout [`Neighbors`](crate::provide::Neighbors) trait.
@@ -96,6 +126,24 @@ where
     }
 }
 
+/// For documentation about each function checkout [`Direction`](crate::provide::Direction) trait.
+impl<'a, W, E, Dir, G> Direction for ShortestPathSubgraph<'a, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Edges<W, E> + Neighbors,
+{
+    fn is_directed(&self) -> bool {
+        Dir::is_directed()
+    }
+}
+
+impl_pretty!(
+    "ShortestPathSubgraph",
+    ['a, W, E, Dir, G] ShortestPathSubgraph<'a, W, E, Dir, G>,
+    where E: Edge<W>, Dir: EdgeDir, G: Graph<W, E, Dir> + Edges<W, E> + Neighbors
+);
+
 /// For documentation about each function checkout [`Edges`](crate::provide::Edges) trait.
 /// `ShortestPathSubgraph` uses `Subgraph` internally so for complexity of each function checkout [`Subgraph`](crate::graph::subgraph::Subgraph).
 impl<'a, W, E, Dir, G> Edges<W, E> for ShortestPathSubgraph<'a, W, E, Dir, G>
@@ -0,0 +1,190 @@
+use std::any::Any;
+use std::collections::HashSet;
+
+use magnitude::Magnitude;
+use num_traits::Zero;
+use provide::{Direction, Edges, Graph, Neighbors, Vertices};
+
+use super::{AsFrozenSubgraph, Subgraph};
+use crate::graph::{impl_pretty, Edge, EdgeDir};
+use crate::provide;
+
+/// Subgraph containing edges and vertices that participate in a spanning tree(or forest) of the graph.
+///
+/// This subgraph will be returned from algorithms like [`Kruskal`](crate::algo::Kruskal).
+pub struct SpanningTreeSubgraph<'a, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Edges<W, E> + Neighbors,
+{
+    subgraph: Subgraph<'a, W, E, Dir, G>,
+}
+
+impl<'a, W, E, Dir, G> SpanningTreeSubgraph<'a, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Edges<W, E> + Neighbors,
+{
+    /// # Arguments
+    /// * `graph`: Graph that owns the `edges` and `vertices`.
+    /// * `edges`: Edges that are in the subgraph in the format of: (src_id, dst_id, edge).
+    /// * `vertices`: Vertices that are in the subgraph.
+    ///
+    /// # Returns
+    /// Initialized subgraph containing the specified `edges` and `vertices`.
+    pub fn init(graph: &'a G, edges: Vec<(usize, usize, usize)>, vertices: HashSet<usize>) -> Self {
+        SpanningTreeSubgraph {
+            subgraph: Subgraph::init(graph, edges, vertices),
+        }
+    }
+
+    /// # Returns
+    /// Sum of the weights of the edges in the spanning tree.
+    ///
+    /// # Complexity
+    /// O(|E|)
+    pub fn total_weight(&self) -> Magnitude<W>
+    where
+        W: Copy + Zero + Any,
+    {
+        self.subgraph
+            .edges()
+            .into_iter()
+            .fold(W::zero().into(), |total_weight, (_, _, edge)| {
+                total_weight + *edge.get_weight()
+            })
+    }
+}
+
+/// For documentation about each function checkout [`Neighbors`](crate::provide::Neighbors) trait.
+/// `SpanningTreeSubgraph` uses `Subgraph` internally so for complexity of each function checkout [`Subgraph`](crate::graph::subgraph::Subgraph).
+impl<'a, W, E, Dir, G> Neighbors for SpanningTreeSubgraph<'a, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Edges<W, E> + Neighbors,
+{
+    fn neighbors(&self, src_id: usize) -> anyhow::Result<Vec<usize>> {
+        self.subgraph.neighbors(src_id)
+    }
+
+    fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
+        self.subgraph.neighbors_unchecked(src_id)
+    }
+}
+
+/// For documentation about each function checkout [`Vertices`](crate::provide::Vertices) trait.
+/// `SpanningTreeSubgraph` uses `Subgraph` internally so for complexity of each function checkout [`Subgraph`](crate::graph::subgraph::Subgraph).
+impl<'a, W, E, Dir, G> Vertices for SpanningTreeSubgraph<'a, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Edges<W, E> + Neighbors,
+{
+    fn vertices(&self) -> Vec<usize> {
+        self.subgraph.vertices()
+    }
+
+    fn contains_vertex(&self, vertex_id: usize) -> bool {
+        self.subgraph.contains_vertex(vertex_id)
+    }
+}
+
+/// For documentation about each function checkout [`Direction`](crate::provide::Direction) trait.
+impl<'a, W, E, Dir, G> Direction for SpanningTreeSubgraph<'a, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Edges<W, E> + Neighbors,
+{
+    fn is_directed(&self) -> bool {
+        Dir::is_directed()
+    }
+}
+
+impl_pretty!(
+    "SpanningTreeSubgraph",
+    ['a, W, E, Dir, G] SpanningTreeSubgraph<'a, W, E, Dir, G>,
+    where E: Edge<W>, Dir: EdgeDir, G: Graph<W, E, Dir> + Edges<W, E> + Neighbors
+);
+
+/// For documentation about each function checkout [`Edges`](crate::provide::Edges) trait.
+/// `SpanningTreeSubgraph` uses `Subgraph` internally so for complexity of each function checkout [`Subgraph`](crate::graph::subgraph::Subgraph).
+impl<'a, W, E, Dir, G> Edges<W, E> for SpanningTreeSubgraph<'a, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Edges<W, E> + Neighbors,
+{
+    fn edges_from(&self, src_id: usize) -> anyhow::Result<Vec<(usize, &E)>> {
+        self.subgraph.edges_from(src_id)
+    }
+
+    fn edges_from_unchecked(&self, src_id: usize) -> Vec<(usize, &E)> {
+        self.subgraph.edges_from_unchecked(src_id)
+    }
+
+    fn edges_between(&self, src_id: usize, dst_id: usize) -> anyhow::Result<Vec<&E>> {
+        self.subgraph.edges_between(src_id, dst_id)
+    }
+
+    fn edges_between_unchecked(&self, src_id: usize, dst_id: usize) -> Vec<&E> {
+        self.subgraph.edges_between_unchecked(src_id, dst_id)
+    }
+
+    fn edge_between(
+        &self,
+        src_id: usize,
+        dst_id: usize,
+        edge_id: usize,
+    ) -> anyhow::Result<Option<&E>> {
+        self.subgraph.edge_between(src_id, dst_id, edge_id)
+    }
+
+    fn edge_between_unchecked(&self, src_id: usize, dst_id: usize, edge_id: usize) -> Option<&E> {
+        self.subgraph
+            .edge_between_unchecked(src_id, dst_id, edge_id)
+    }
+
+    fn edge(&self, edge_id: usize) -> anyhow::Result<Option<&E>> {
+        self.subgraph.edge(edge_id)
+    }
+
+    fn edge_unchecked(&self, edge_id: usize) -> Option<&E> {
+        self.subgraph.edge_unchecked(edge_id)
+    }
+
+    fn has_any_edge(&self, src_id: usize, dst_id: usize) -> anyhow::Result<bool> {
+        self.subgraph.has_any_edge(src_id, dst_id)
+    }
+
+    fn has_any_edge_unchecked(&self, src_id: usize, dst_id: usize) -> bool {
+        self.subgraph.has_any_edge_unchecked(src_id, dst_id)
+    }
+
+    fn edges(&self) -> Vec<(usize, usize, &E)> {
+        self.subgraph.edges()
+    }
+
+    fn as_directed_edges(&self) -> Vec<(usize, usize, &E)> {
+        self.subgraph.as_directed_edges()
+    }
+
+    fn edges_count(&self) -> usize {
+        self.subgraph.edges_count()
+    }
+
+    fn contains_edge(&self, edge_id: usize) -> bool {
+        self.subgraph.contains_edge(edge_id)
+    }
+}
+
+impl<'a, W, E, Dir, G> AsFrozenSubgraph<W, E> for SpanningTreeSubgraph<'a, W, E, Dir, G>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Graph<W, E, Dir> + Edges<W, E> + Neighbors,
+{
+}
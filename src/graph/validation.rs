@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+
+use magnitude::Magnitude;
+
+use crate::graph::{Edge, EdgeDir};
+use crate::provide::{Edges, Vertices};
+
+/// A structural problem found while validating a graph.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// Edge is connected to a vertex that does not exist in the graph.
+    DanglingEdge {
+        src_id: usize,
+        dst_id: usize,
+        edge_id: usize,
+    },
+
+    /// Undirected edge is only accessible from one of its end points.
+    AsymmetricEdge {
+        src_id: usize,
+        dst_id: usize,
+        edge_id: usize,
+    },
+
+    /// Same id is used by edges between different vertices.
+    DuplicateEdgeId(usize),
+
+    /// Weight of the edge is either NaN or negative infinity.
+    InvalidWeight {
+        src_id: usize,
+        dst_id: usize,
+        edge_id: usize,
+    },
+
+    /// Number of edges reported by the graph does not match the number of edges that are actually stored.
+    EdgeCountMismatch { reported: usize, found: usize },
+}
+
+fn is_valid_weight<W: PartialEq>(weight: &Magnitude<W>) -> bool {
+    match weight {
+        // A weight that is not equal to itself is NaN.
+        Magnitude::Finite(value) => value == value,
+        _ => !weight.is_neg_infinite(),
+    }
+}
+
+/// Checks the structural invariants of `graph` using only the public api that the graph provides.
+///
+/// # Returns
+/// List of violations, which is empty if graph is valid.
+///
+/// # Complexity
+/// O(|V| + |E| * *finding edges between two vertices*)
+pub(crate) fn validate<W, E, Dir, G>(graph: &G) -> Vec<Violation>
+where
+    W: PartialEq,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    G: Vertices + Edges<W, E>,
+{
+    let mut violations = vec![];
+
+    // Maps each edge id to the end points of the edge.
+    let mut end_points_of = HashMap::new();
+
+    for src_id in graph.vertices() {
+        for (dst_id, edge) in graph.edges_from_unchecked(src_id) {
+            let edge_id = edge.get_id();
+
+            let end_points = if Dir::is_undirected() && dst_id < src_id {
+                (dst_id, src_id)
+            } else {
+                (src_id, dst_id)
+            };
+
+            // Undirected edges are visited once from each of their end points.
+            let is_first_visit = match end_points_of.get(&edge_id) {
+                Some(other_end_points) => {
+                    if *other_end_points != end_points || Dir::is_directed() {
+                        violations.push(Violation::DuplicateEdgeId(edge_id));
+                    }
+
+                    false
+                }
+                None => {
+                    end_points_of.insert(edge_id, end_points);
+
+                    true
+                }
+            };
+
+            if !graph.contains_vertex(dst_id) {
+                violations.push(Violation::DanglingEdge {
+                    src_id,
+                    dst_id,
+                    edge_id,
+                });
+                continue;
+            }
+
+            if is_first_visit && !is_valid_weight(edge.get_weight()) {
+                violations.push(Violation::InvalidWeight {
+                    src_id,
+                    dst_id,
+                    edge_id,
+                });
+            }
+
+            if Dir::is_undirected()
+                && !graph
+                    .edges_between_unchecked(dst_id, src_id)
+                    .iter()
+                    .any(|edge| edge.get_id() == edge_id)
+            {
+                violations.push(Violation::AsymmetricEdge {
+                    src_id,
+                    dst_id,
+                    edge_id,
+                });
+            }
+        }
+    }
+
+    if graph.edges_count() != end_points_of.len() {
+        violations.push(Violation::EdgeCountMismatch {
+            reported: graph.edges_count(),
+            found: end_points_of.len(),
+        });
+    }
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DefaultEdge, ListGraph, MatGraph, SparseMatGraph};
+    use crate::provide::*;
+    use crate::storage::{DiList, DiMat, DiSparseMat, List, Mat, SparseMat};
+
+    #[test]
+    fn valid_graphs_after_mutations() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c  ---  d
+        //
+        let mut mat_graph = MatGraph::init(Mat::<usize>::init());
+        let mut di_mat_graph = MatGraph::init(DiMat::<usize>::init());
+        let mut list_graph = ListGraph::init(List::<usize>::init());
+        let mut di_list_graph = ListGraph::init(DiList::<usize>::init());
+        let mut sparse_graph = SparseMatGraph::init(SparseMat::<usize>::init());
+        let mut di_sparse_graph = SparseMatGraph::init(DiSparseMat::<usize>::init());
+
+        macro_rules! mutate_and_validate {
+            ($graph: expr) => {
+                let a = $graph.add_vertex();
+                let b = $graph.add_vertex();
+                let c = $graph.add_vertex();
+                let d = $graph.add_vertex();
+                let ab = $graph.add_edge_unchecked(a, b, 1.into());
+                $graph.add_edge_unchecked(b, c, 1.into());
+                $graph.add_edge_unchecked(c, d, 1.into());
+                assert_eq!($graph.validate(), vec![]);
+
+                // When: Updating a --- b, removing vertex c and adding a --- d.
+                $graph.update_edge_unchecked(a, b, ab, 2.into());
+                $graph.remove_vertex_unchecked(c);
+                $graph.add_edge_unchecked(a, d, 1.into());
+
+                // Then:
+                assert_eq!($graph.validate(), vec![]);
+            };
+        }
+
+        mutate_and_validate!(mat_graph);
+        mutate_and_validate!(di_mat_graph);
+        mutate_and_validate!(list_graph);
+        mutate_and_validate!(di_list_graph);
+        mutate_and_validate!(sparse_graph);
+        mutate_and_validate!(di_sparse_graph);
+    }
+
+    #[test]
+    fn matrix_after_edge_removal() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let ab = graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // When: Removing a --- b.
+        graph.remove_edge_unchecked(a, b, ab);
+
+        // Then:
+        assert!(graph.validate().is_empty());
+    }
+
+    #[test]
+    fn invalid_weights() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c
+        //
+        let mut graph = MatGraph::init(Mat::<f64>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+
+        // When: Adding an edge with NaN weight and an edge with negative infinite weight.
+        let ab = graph.add_edge_unchecked(a, b, f64::NAN.into());
+        let bc = graph.add_edge_unchecked(b, c, DefaultEdge::init(Magnitude::NegInfinite));
+
+        // Then:
+        let violations = graph.validate();
+        assert_eq!(violations.len(), 2);
+        assert!(violations.iter().any(|violation| match violation {
+            Violation::InvalidWeight { edge_id, .. } => *edge_id == ab,
+            _ => false,
+        }));
+        assert!(violations.iter().any(|violation| match violation {
+            Violation::InvalidWeight { edge_id, .. } => *edge_id == bc,
+            _ => false,
+        }));
+    }
+}
@@ -0,0 +1,407 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+
+use anyhow::Result;
+use magnitude::Magnitude;
+
+use crate::graph::{Edge, EdgeDir, Error};
+use crate::provide::{Edges, Graph, Vertices};
+
+/// Writes graphs as dense adjacency matrices in CSV format and reads them back, so spreadsheets and dataframe libraries like pandas can consume them.
+///
+/// Cell at row i and column j holds weight of the edge from i-th vertex to j-th vertex, or the infinity placeholder if there is no such edge.
+/// Vertices are ordered by their ids. By default the first row and the first column hold id of the vertices, so the file can be read by
+/// `pandas.read_csv(path, index_col=0)`.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::io::AdjacencyCsv;
+/// use prepona::storage::{DiMat, Mat};
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  --(2)-->  b
+/// //
+/// let mut graph = MatGraph::init(DiMat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 2.into());
+///
+/// let mut file = vec![];
+/// AdjacencyCsv::init().write(&graph, &mut file).unwrap();
+/// assert_eq!(String::from_utf8(file.clone()).unwrap(), ",0,1\n0,inf,2\n1,inf,inf\n");
+///
+/// let mut copy = MatGraph::init(DiMat::<usize>::init());
+/// let id_of = AdjacencyCsv::init().read(file.as_slice(), &mut copy).unwrap();
+/// assert_eq!(copy.edges_between_unchecked(id_of[&a], id_of[&b])[0].get_weight(), &2.into());
+/// ```
+pub struct AdjacencyCsv {
+    delimiter: char,
+    infinity: String,
+    has_header: bool,
+}
+
+impl AdjacencyCsv {
+    /// # Returns
+    /// Matrix format with `,` as delimiter, `inf` as infinity placeholder and a header row and column.
+    pub fn init() -> Self {
+        AdjacencyCsv {
+            delimiter: ',',
+            infinity: String::from("inf"),
+            has_header: true,
+        }
+    }
+
+    /// # Arguments
+    /// `delimiter`: Character that separates the cells of a row.
+    ///
+    /// # Returns
+    /// The format with the specified delimiter.
+    pub fn with_delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+
+        self
+    }
+
+    /// # Arguments
+    /// `infinity`: Text of the cells that there is no edge for. It's prefixed with `-` for edges with negative infinite weight.
+    ///
+    /// # Returns
+    /// The format with the specified infinity placeholder.
+    pub fn with_infinity(mut self, infinity: &str) -> Self {
+        self.infinity = infinity.to_string();
+
+        self
+    }
+
+    /// # Arguments
+    /// `has_header`: Whether the first row and the first column hold id of the vertices.
+    /// Without header, vertices are identified by their index in the matrix when reading.
+    ///
+    /// # Returns
+    /// The format with or without header.
+    pub fn with_header(mut self, has_header: bool) -> Self {
+        self.has_header = has_header;
+
+        self
+    }
+
+    /// # Arguments
+    /// * `graph`: Graph to write.
+    /// * `writer`: Writer of the CSV file.
+    ///
+    /// # Returns
+    /// * `Ok`: If graph is written successfully. If there is more than one edge between two vertices, weight of the first one is written.
+    ///   Edges with positive infinite weight are written as the infinity placeholder, so they are indistinguishable from missing edges.
+    ///   Graphs without vertices are written as an empty file.
+    /// * `Err`: Any error that writing to `writer` returns.
+    ///
+    /// # Complexity
+    /// O(|V|^2) calls to [`edges_between_unchecked`](crate::provide::Edges::edges_between_unchecked).
+    pub fn write<W, E, Dir, G, Wr>(&self, graph: &G, mut writer: Wr) -> Result<()>
+    where
+        W: Display,
+        E: Edge<W>,
+        Dir: EdgeDir,
+        G: Graph<W, E, Dir> + Vertices + Edges<W, E>,
+        Wr: Write,
+    {
+        let mut vertices = graph.vertices();
+        vertices.sort_unstable();
+
+        let delimiter = self.delimiter.to_string();
+        if self.has_header && !vertices.is_empty() {
+            let header = vertices
+                .iter()
+                .map(|vertex_id| vertex_id.to_string())
+                .collect::<Vec<String>>();
+            writeln!(writer, "{}{}", delimiter, header.join(&delimiter))?;
+        }
+
+        for src_id in &vertices {
+            let mut cells = Vec::with_capacity(vertices.len() + 1);
+            if self.has_header {
+                cells.push(src_id.to_string());
+            }
+
+            for dst_id in &vertices {
+                let cell = match graph.edges_between_unchecked(*src_id, *dst_id).first() {
+                    Some(edge) => match edge.get_weight() {
+                        Magnitude::Finite(weight) => weight.to_string(),
+                        Magnitude::PosInfinite => self.infinity.clone(),
+                        Magnitude::NegInfinite => format!("-{}", self.infinity),
+                    },
+                    None => self.infinity.clone(),
+                };
+                cells.push(cell);
+            }
+
+            writeln!(writer, "{}", cells.join(&delimiter))?;
+        }
+
+        Ok(())
+    }
+
+    /// # Arguments
+    /// * `reader`: Reader of the CSV file.
+    /// * `graph`: Graph to add vertices and edges to.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing a map from id of each vertex in the file to its id in `graph`. Without header, ids in the file are the indices of the rows.
+    ///   For undirected graphs only the cells on and above the diagonal are read.
+    /// * `Err`:
+    ///     * [`InvalidFormat`](crate::graph::ErrorKind::InvalidFormat) if matrix is not square, a cell is not a valid weight or an id of the header is not valid or is repeated.
+    ///       The whole file is validated before any vertex is added, so `graph` is left untouched.
+    ///     * Any error that reading from `reader` or adding edges to `graph` returns.
+    pub fn read<W, E, Dir, G, R>(&self, reader: R, graph: &mut G) -> Result<HashMap<usize, usize>>
+    where
+        W: FromStr,
+        E: Edge<W>,
+        Dir: EdgeDir,
+        G: Graph<W, E, Dir>,
+        R: BufRead,
+    {
+        let mut rows = vec![];
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                rows.push((index + 1, line));
+            }
+        }
+
+        let split = |line: &str| {
+            line.split(self.delimiter)
+                .map(|cell| cell.trim().to_string())
+                .collect::<Vec<String>>()
+        };
+
+        // Id of each vertex in the file, and index of the first row of the matrix.
+        let (ids, first_row) = if self.has_header {
+            match rows.first() {
+                Some((line_number, line)) => {
+                    let ids = split(line)
+                        .iter()
+                        .skip(1)
+                        .map(|id| id.parse::<usize>())
+                        .collect::<std::result::Result<Vec<usize>, _>>();
+
+                    match ids {
+                        Ok(ids) if ids.iter().collect::<HashSet<&usize>>().len() == ids.len() => {
+                            (ids, 1)
+                        }
+                        _ => Err(Error::new_if(*line_number, line))?,
+                    }
+                }
+                None => (vec![], 0),
+            }
+        } else {
+            ((0..rows.len()).collect(), 0)
+        };
+
+        if rows.len() - first_row != ids.len() {
+            let (line_number, line) = rows.last().unwrap();
+            Err(Error::new_if(*line_number, line))?
+        }
+
+        // Edges in the format of: (`src_index`, `dst_index`, `weight`), where indices are positions of the end points in `ids`.
+        let negative_infinity = format!("-{}", self.infinity);
+        let mut edges = vec![];
+        for (src_index, (line_number, line)) in rows[first_row..].iter().enumerate() {
+            let mut cells = split(line);
+            if self.has_header {
+                match cells.first().map(|id| id.parse::<usize>()) {
+                    Some(Ok(id)) if id == ids[src_index] => cells.remove(0),
+                    _ => Err(Error::new_if(*line_number, line))?,
+                };
+            }
+
+            if cells.len() != ids.len() {
+                Err(Error::new_if(*line_number, line))?
+            }
+
+            for (dst_index, cell) in cells.iter().enumerate() {
+                if Dir::is_undirected() && dst_index < src_index {
+                    continue;
+                }
+
+                let weight = if *cell == self.infinity {
+                    continue;
+                } else if *cell == negative_infinity {
+                    Magnitude::NegInfinite
+                } else {
+                    match cell.parse::<W>() {
+                        Ok(weight) => Magnitude::Finite(weight),
+                        Err(_) => Err(Error::new_if(*line_number, line))?,
+                    }
+                };
+
+                edges.push((src_index, dst_index, weight));
+            }
+        }
+
+        let vertex_ids = ids
+            .iter()
+            .map(|_| graph.add_vertex())
+            .collect::<Vec<usize>>();
+
+        let edges = edges
+            .into_iter()
+            .map(|(src_index, dst_index, weight)| {
+                (
+                    vertex_ids[src_index],
+                    vertex_ids[dst_index],
+                    E::init(weight),
+                )
+            })
+            .collect();
+        graph.add_edges(edges)?;
+
+        let id_of = ids.into_iter().zip(vertex_ids).collect();
+
+        Ok(id_of)
+    }
+}
+
+impl Default for AdjacencyCsv {
+    fn default() -> Self {
+        AdjacencyCsv::init()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ErrorKind, ListGraph, MatGraph};
+    use crate::storage::{DiList, Mat};
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let mut file = vec![];
+        AdjacencyCsv::init().write(&graph, &mut file).unwrap();
+
+        let mut copy = MatGraph::init(Mat::<usize>::init());
+        let id_of = AdjacencyCsv::init()
+            .read(file.as_slice(), &mut copy)
+            .unwrap();
+
+        assert!(id_of.is_empty());
+        assert_eq!(copy.vertex_count(), 0);
+    }
+
+    #[test]
+    fn undirected_round_trip() {
+        // Given: Graph
+        //
+        //      a  --(1.5)--  b  --(-2)--  c
+        //
+        let mut graph = MatGraph::init(Mat::<f64>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.5.into());
+        graph.add_edge_unchecked(b, c, (-2.0).into());
+
+        // When: Writing without header and with custom delimiter and infinity placeholder.
+        let format = AdjacencyCsv::init()
+            .with_header(false)
+            .with_delimiter(';')
+            .with_infinity("");
+        let mut file = vec![];
+        format.write(&graph, &mut file).unwrap();
+
+        let mut copy = MatGraph::init(Mat::<f64>::init());
+        let id_of = format.read(file.as_slice(), &mut copy).unwrap();
+
+        // Then:
+        assert_eq!(String::from_utf8(file).unwrap(), ";1.5;\n1.5;;-2\n;-2;\n");
+        assert_eq!(copy.edges_count(), 2);
+        assert_eq!(
+            copy.edges_between_unchecked(id_of[&0], id_of[&1])[0].get_weight(),
+            &1.5.into()
+        );
+        assert_eq!(
+            copy.edges_between_unchecked(id_of[&2], id_of[&1])[0].get_weight(),
+            &(-2.0).into()
+        );
+    }
+
+    #[test]
+    fn directed_with_gaps_in_ids() {
+        // Given: Directed graph with ids 0, 2 and 3.
+        //
+        //      a  --(1)-->  c  --(4)-->  d
+        //      ^                         |
+        //      '-----------(3)-----------'
+        //
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, c, 1.into());
+        graph.add_edge_unchecked(c, d, 4.into());
+        graph.add_edge_unchecked(d, a, 3.into());
+        graph.remove_vertex_unchecked(b);
+
+        // When:
+        let mut file = vec![];
+        AdjacencyCsv::init().write(&graph, &mut file).unwrap();
+
+        let mut copy = ListGraph::init(DiList::<usize>::init());
+        let id_of = AdjacencyCsv::init()
+            .read(file.as_slice(), &mut copy)
+            .unwrap();
+
+        // Then:
+        assert_eq!(
+            String::from_utf8(file).unwrap(),
+            ",0,2,3\n0,inf,1,inf\n2,inf,inf,4\n3,3,inf,inf\n"
+        );
+        assert_eq!(copy.edges_count(), 3);
+        assert!(copy.has_any_edge_unchecked(id_of[&a], id_of[&c]));
+        assert!(!copy.has_any_edge_unchecked(id_of[&c], id_of[&a]));
+        assert_eq!(
+            copy.edges_between_unchecked(id_of[&d], id_of[&a])[0].get_weight(),
+            &3.into()
+        );
+    }
+
+    #[test]
+    fn invalid_files() {
+        let is_invalid = |file: &str| {
+            let mut graph = ListGraph::init(DiList::<usize>::init());
+            let result = AdjacencyCsv::init().read(file.as_bytes(), &mut graph);
+
+            // Graph must not be changed by an invalid file.
+            graph.vertex_count() == 0
+                && matches!(
+                    result.unwrap_err().downcast_ref::<Error>().unwrap().kind(),
+                    ErrorKind::InvalidFormat
+                )
+        };
+
+        // Then: Header id is not a number.
+        assert!(is_invalid(",0,a\n0,inf,1\na,inf,inf\n"));
+
+        // Then: Header id is repeated.
+        assert!(is_invalid(",0,0\n0,inf,1\n0,inf,inf\n"));
+
+        // Then: Row is missing.
+        assert!(is_invalid(",0,1\n0,inf,1\n"));
+
+        // Then: Row has too few cells.
+        assert!(is_invalid(",0,1\n0,inf,1\n1,inf\n"));
+
+        // Then: Row id does not match the header.
+        assert!(is_invalid(",0,1\n0,inf,1\n2,inf,inf\n"));
+
+        // Then: Cell is not a valid weight.
+        assert!(is_invalid(",0,1\n0,inf,x\n1,inf,inf\n"));
+    }
+}
@@ -0,0 +1,476 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use num_traits::One;
+
+use crate::graph::{Edge, EdgeDir, Error, Metadata};
+use crate::provide::Graph;
+
+// Default number of edges that are added to the graph at once.
+const DEFAULT_BATCH_SIZE: usize = 1 << 16;
+
+type ProgressCallback<'a> = Box<dyn FnMut(&LoadProgress) + 'a>;
+
+/// Progress of loading an edge list, which is reported after adding each batch of edges to the graph.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LoadProgress {
+    line_count: usize,
+    byte_count: usize,
+    vertex_count: usize,
+    edge_count: usize,
+    skipped_count: usize,
+}
+
+impl LoadProgress {
+    /// # Returns
+    /// Number of lines read so far, including comments.
+    pub fn line_count(&self) -> usize {
+        self.line_count
+    }
+
+    /// # Returns
+    /// Number of bytes read so far. Comparing it to size of the file gives the fraction of the file that is loaded.
+    pub fn byte_count(&self) -> usize {
+        self.byte_count
+    }
+
+    /// # Returns
+    /// Number of vertices added to the graph so far.
+    pub fn vertex_count(&self) -> usize {
+        self.vertex_count
+    }
+
+    /// # Returns
+    /// Number of edges added to the graph so far.
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// # Returns
+    /// Number of edges skipped so far, because they were loops or multi edges.
+    pub fn skipped_count(&self) -> usize {
+        self.skipped_count
+    }
+}
+
+/// Loads graphs from edge list files, like the datasets published by [SNAP](https://snap.stanford.edu/data/) and [KONECT](http://konect.cc/networks/).
+///
+/// Each line of the file contains id of the source vertex, id of the destination vertex and optionally weight of the edge, separated by whitespaces or commas.
+/// Extra columns, like timestamps, are ignored. Empty lines and lines starting with `#` or `%` are comments.
+/// Comments written by [`Metadata::write_comments`](crate::graph::Metadata::write_comments) are read into the [`metadata`](crate::io::EdgeListLoader::metadata) of the loader.
+/// Edges without weight get the weight of one.
+///
+/// The file is streamed line by line, and edges are added to the graph in batches using [`add_edges`](crate::provide::Graph::add_edges).
+/// Vertices get added to the graph when they are seen for the first time. So ids in the file can be any number and they are mapped to ids of the graph.
+///
+/// Loops and multi edges are skipped by default, so datasets that list each undirected edge in both directions can be loaded into a [`SimpleGraph`](crate::graph::SimpleGraph).
+///
+/// Compressed files can be loaded by passing a decompressing reader to [`load`](crate::io::EdgeListLoader::load).
+/// For example using the `flate2` crate: `loader.load(BufReader::new(GzDecoder::new(file)), &mut graph)`.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::io::EdgeListLoader;
+/// use prepona::storage::DiList;
+/// use prepona::graph::ListGraph;
+///
+/// let file = "# FromNodeId\tToNodeId\n10\t20\n20\t30\n10\t20\n";
+///
+/// let mut graph = ListGraph::init(DiList::<usize>::init());
+/// let mut batches = 0;
+/// let id_of = EdgeListLoader::init()
+///     .with_batch_size(1)
+///     .on_progress(|_| batches += 1)
+///     .load(file.as_bytes(), &mut graph)
+///     .unwrap();
+///
+/// assert_eq!(graph.vertex_count(), 3);
+/// assert_eq!(graph.edges_count(), 2);
+/// assert!(graph.has_any_edge_unchecked(id_of[&10], id_of[&20]));
+/// assert_eq!(batches, 2);
+/// ```
+pub struct EdgeListLoader<'a> {
+    batch_size: usize,
+    allow_loops: bool,
+    allow_multi_edges: bool,
+
+    on_progress: Option<ProgressCallback<'a>>,
+
+    metadata: Metadata,
+}
+
+impl<'a> EdgeListLoader<'a> {
+    /// # Returns
+    /// Loader that adds 65536 edges at a time and skips loops and multi edges.
+    pub fn init() -> Self {
+        EdgeListLoader {
+            batch_size: DEFAULT_BATCH_SIZE,
+            allow_loops: false,
+            allow_multi_edges: false,
+
+            on_progress: None,
+
+            metadata: Metadata::init(),
+        }
+    }
+
+    /// # Arguments
+    /// `batch_size`: Number of edges to add to the graph at once. It's at least one.
+    ///
+    /// # Returns
+    /// The loader with the specified batch size.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+
+        self
+    }
+
+    /// # Arguments
+    /// `allow_loops`: Whether edges from a vertex to itself should be added to the graph.
+    ///
+    /// # Returns
+    /// The loader that keeps or skips loops.
+    pub fn allow_loops(mut self, allow_loops: bool) -> Self {
+        self.allow_loops = allow_loops;
+
+        self
+    }
+
+    /// # Arguments
+    /// `allow_multi_edges`: Whether more than one edge between two vertices should be added to the graph.
+    /// If not, only the first edge between them is added. In undirected graphs a --> b and b --> a are the same edge.
+    ///
+    /// # Returns
+    /// The loader that keeps or skips multi edges.
+    pub fn allow_multi_edges(mut self, allow_multi_edges: bool) -> Self {
+        self.allow_multi_edges = allow_multi_edges;
+
+        self
+    }
+
+    /// # Arguments
+    /// `on_progress`: Gets called after each batch of edges is added to the graph.
+    ///
+    /// # Returns
+    /// The loader that reports its progress to `on_progress`.
+    pub fn on_progress<F: FnMut(&LoadProgress) + 'a>(mut self, on_progress: F) -> Self {
+        self.on_progress = Some(Box::new(on_progress));
+
+        self
+    }
+
+    /// # Returns
+    /// Metadata read from the last loaded edge list.
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// # Returns
+    /// Metadata read from the last loaded edge list, leaving the loader with empty metadata.
+    /// It can be assigned to the loaded graph, like: `*graph.metadata_mut() = loader.take_metadata()`.
+    pub fn take_metadata(&mut self) -> Metadata {
+        std::mem::take(&mut self.metadata)
+    }
+
+    /// # Arguments
+    /// * `path`: Path of the edge list file.
+    /// * `graph`: Graph to add vertices and edges to.
+    ///
+    /// # Returns
+    /// Checkout [`load`](crate::io::EdgeListLoader::load).
+    pub fn load_file<W, E, Dir, G, P>(
+        &mut self,
+        path: P,
+        graph: &mut G,
+    ) -> Result<HashMap<usize, usize>>
+    where
+        W: FromStr + One,
+        E: Edge<W>,
+        Dir: EdgeDir,
+        G: Graph<W, E, Dir>,
+        P: AsRef<Path>,
+    {
+        let file = File::open(path.as_ref())
+            .with_context(|| format!("Failed to open {}", path.as_ref().display()))?;
+
+        self.load(BufReader::new(file), graph)
+    }
+
+    /// # Arguments
+    /// * `reader`: Reader of the edge list.
+    /// * `graph`: Graph to add vertices and edges to.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing a map from id of each vertex in the edge list to its id in `graph`.
+    /// * `Err`:
+    ///     * [`InvalidFormat`](crate::graph::ErrorKind::InvalidFormat) if a line does not contain a valid edge or a valid metadata entry.
+    ///     * Any error that reading from `reader` or adding edges to `graph` returns.
+    ///       Edges of the batches before the error remain in the graph.
+    pub fn load<W, E, Dir, G, R>(
+        &mut self,
+        mut reader: R,
+        graph: &mut G,
+    ) -> Result<HashMap<usize, usize>>
+    where
+        W: FromStr + One,
+        E: Edge<W>,
+        Dir: EdgeDir,
+        G: Graph<W, E, Dir>,
+        R: BufRead,
+    {
+        let mut id_of = HashMap::new();
+        let mut seen_edges = HashSet::new();
+        let mut batch = Vec::with_capacity(self.batch_size);
+        let mut progress = LoadProgress::default();
+        self.metadata = Metadata::init();
+
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let byte_count = reader.read_line(&mut line)?;
+            if byte_count == 0 {
+                break;
+            }
+
+            progress.line_count += 1;
+            progress.byte_count += byte_count;
+
+            if self.metadata.read_comment(progress.line_count, &line)? {
+                continue;
+            }
+
+            let (src, dst, weight) = match parse_line::<W>(progress.line_count, &line)? {
+                Some(edge) => edge,
+                None => continue,
+            };
+
+            let src_id = *id_of.entry(src).or_insert_with(|| graph.add_vertex());
+            let dst_id = *id_of.entry(dst).or_insert_with(|| graph.add_vertex());
+            progress.vertex_count = id_of.len();
+
+            let end_points = if Dir::is_undirected() && dst_id < src_id {
+                (dst_id, src_id)
+            } else {
+                (src_id, dst_id)
+            };
+
+            if (!self.allow_loops && src_id == dst_id)
+                || (!self.allow_multi_edges && !seen_edges.insert(end_points))
+            {
+                progress.skipped_count += 1;
+                continue;
+            }
+
+            batch.push((src_id, dst_id, E::init(weight.into())));
+            if batch.len() == self.batch_size {
+                self.add_batch(graph, &mut batch, &mut progress)?;
+            }
+        }
+
+        if !batch.is_empty() || progress.edge_count == 0 {
+            self.add_batch(graph, &mut batch, &mut progress)?;
+        }
+
+        Ok(id_of)
+    }
+
+    fn add_batch<W, E, Dir, G>(
+        &mut self,
+        graph: &mut G,
+        batch: &mut Vec<(usize, usize, E)>,
+        progress: &mut LoadProgress,
+    ) -> Result<()>
+    where
+        E: Edge<W>,
+        Dir: EdgeDir,
+        G: Graph<W, E, Dir>,
+    {
+        progress.edge_count += graph.add_edges(batch.drain(..))?.len();
+
+        if let Some(on_progress) = &mut self.on_progress {
+            on_progress(progress);
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for EdgeListLoader<'_> {
+    fn default() -> Self {
+        EdgeListLoader::init()
+    }
+}
+
+// Returns `None` for comments, and `Err` if line is not a valid edge. Otherwise returns the edge in the format of: (`src`, `dst`, `weight`).
+pub(crate) fn parse_line<W: FromStr + One>(
+    line_number: usize,
+    line: &str,
+) -> Result<Option<(usize, usize, W)>> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('%') {
+        return Ok(None);
+    }
+
+    let mut columns = trimmed
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|column| !column.is_empty());
+
+    let parsed = (|| {
+        let src = columns.next()?.parse().ok()?;
+        let dst = columns.next()?.parse().ok()?;
+        let weight = match columns.next() {
+            Some(weight) => weight.parse().ok()?,
+            None => W::one(),
+        };
+
+        Some((src, dst, weight))
+    })();
+
+    match parsed {
+        Some(edge) => Ok(Some(edge)),
+        None => Err(Error::new_if(line_number, trimmed))?,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{ErrorKind, ListGraph, MatGraph};
+    use crate::provide::{Edges, Vertices};
+    use crate::storage::{DiList, List, Mat};
+
+    #[test]
+    fn konect_file_with_weights() {
+        // Given: Undirected weighted edge list in KONECT format, which has a timestamp column.
+        let file = "% sym positive\n\
+                    % 3 3 3\n\
+                    1 2 5 978300760\n\
+                    2 3 7 978300761\n\
+                    \n\
+                    3 1 2 978300762\n";
+
+        // When:
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let id_of = EdgeListLoader::init()
+            .load(file.as_bytes(), &mut graph)
+            .unwrap();
+
+        // Then:
+        assert_eq!(graph.vertex_count(), 3);
+        assert_eq!(graph.edges_count(), 3);
+        assert_eq!(
+            graph.edges_between_unchecked(id_of[&3], id_of[&2])[0].get_weight(),
+            &7.into()
+        );
+    }
+
+    #[test]
+    fn skip_loops_and_multi_edges() {
+        // Given: Edge list that lists each undirected edge in both directions, and has a loop.
+        let file = "0,1\n1,0\n1,2\n2,1\n2,2\n";
+
+        // When: Loading into a directed graph, an undirected graph and an undirected graph while allowing multi edges.
+        let mut directed = ListGraph::init(DiList::<usize>::init());
+        EdgeListLoader::init()
+            .load(file.as_bytes(), &mut directed)
+            .unwrap();
+
+        let mut progress = LoadProgress::default();
+        let mut undirected = ListGraph::init(List::<usize>::init());
+        EdgeListLoader::init()
+            .on_progress(|p| progress = *p)
+            .load(file.as_bytes(), &mut undirected)
+            .unwrap();
+
+        let mut multi_graph = ListGraph::init(List::<usize>::init());
+        let result = EdgeListLoader::init()
+            .allow_multi_edges(true)
+            .load(file.as_bytes(), &mut multi_graph);
+
+        // Then:
+        assert_eq!(directed.edges_count(), 4);
+        assert_eq!(undirected.edges_count(), 2);
+        assert_eq!(progress.line_count(), 5);
+        assert_eq!(progress.byte_count(), file.len());
+        assert_eq!(progress.vertex_count(), 3);
+        assert_eq!(progress.edge_count(), 2);
+        assert_eq!(progress.skipped_count(), 3);
+        assert!(matches!(
+            result.unwrap_err().downcast_ref::<Error>().unwrap().kind(),
+            ErrorKind::MultiEdge
+        ));
+    }
+
+    #[test]
+    fn batches() {
+        // Given: Edge list with 5 edges.
+        let file = "0 1\n1 2\n2 3\n3 4\n4 5\n";
+
+        // When: Loading it with batches of 2 edges.
+        let mut reported_edges = vec![];
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        EdgeListLoader::init()
+            .with_batch_size(2)
+            .on_progress(|progress| reported_edges.push(progress.edge_count()))
+            .load(file.as_bytes(), &mut graph)
+            .unwrap();
+
+        // Then:
+        assert_eq!(reported_edges, vec![2, 4, 5]);
+    }
+
+    #[test]
+    fn invalid_line() {
+        // Given: Edge list with an invalid line.
+        let file = "# comment\n0 1\n1 two\n";
+
+        // When:
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let result = EdgeListLoader::init().load(file.as_bytes(), &mut graph);
+
+        // Then:
+        let error = result.unwrap_err();
+        let error = error.downcast_ref::<Error>().unwrap();
+        assert!(matches!(error.kind(), ErrorKind::InvalidFormat));
+        assert!(error.msg().starts_with("Line 3"));
+    }
+
+    #[test]
+    fn metadata_survives_round_trip() {
+        // Given: Graph with metadata, written as an edge list.
+        let mut metadata = Metadata::init().with_name("karate club");
+        metadata.set("members", 34);
+        metadata.attach("in memory", 1);
+
+        let mut file = vec![];
+        metadata.write_comments(&mut file).unwrap();
+        file.extend_from_slice(b"# source target\n1 2\n2 3\n");
+
+        // When:
+        let mut graph = ListGraph::init(List::<usize>::init());
+        let mut loader = EdgeListLoader::init();
+        loader.load(file.as_slice(), &mut graph).unwrap();
+        *graph.metadata_mut() = loader.take_metadata();
+
+        // Then: Values are read back, but attachments only live in memory.
+        assert_eq!(graph.edges_count(), 2);
+        assert_eq!(graph.metadata().name(), Some("karate club"));
+        assert_eq!(graph.metadata().get("members").unwrap().as_int(), Some(34));
+        assert!(graph.metadata().attachment::<i32>("in memory").is_none());
+        assert!(loader.metadata().is_empty());
+    }
+
+    #[test]
+    fn missing_file() {
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+
+        assert!(EdgeListLoader::init()
+            .load_file("/nonexistent/edges.txt", &mut graph)
+            .is_err());
+    }
+}
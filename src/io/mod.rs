@@ -0,0 +1,6 @@
+mod csv;
+mod edge_list;
+
+pub use csv::AdjacencyCsv;
+pub use edge_list::{EdgeListLoader, LoadProgress};
+pub(crate) use edge_list::parse_line;
@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+use crate::algo::metrics::adjacency;
+use crate::provide::{Neighbors, Vertices};
+
+/// Fruchterman–Reingold force-directed layout.
+///
+/// Vertices repel each other and edges pull their end points together, like springs. In each iteration vertices move along the net force,
+/// by at most a temperature that cools down linearly to zero, and they stay inside a `width` x `height` frame.
+/// Vertices start evenly spaced on a circle, so the layout is deterministic.
+///
+/// Direction of the edges and loops are ignored.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::layout::FruchtermanReingold;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b  ---  c  ---  d
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(c, d, 1.into());
+///
+/// let position_of = FruchtermanReingold::init().with_iterations(100).execute(&graph);
+///
+/// let distance = |src_id: usize, dst_id: usize| {
+///     let (x1, y1) = position_of[&src_id];
+///     let (x2, y2) = position_of[&dst_id];
+///     (x1 - x2).hypot(y1 - y2)
+/// };
+/// assert!(distance(a, b) < distance(a, d));
+/// ```
+pub struct FruchtermanReingold {
+    width: f64,
+    height: f64,
+    iterations: usize,
+}
+
+impl FruchtermanReingold {
+    /// # Returns
+    /// Layout with a 1 x 1 frame that runs for 50 iterations.
+    pub fn init() -> Self {
+        FruchtermanReingold {
+            width: 1.0,
+            height: 1.0,
+            iterations: 50,
+        }
+    }
+
+    /// # Arguments
+    /// * `width`: Width of the frame.
+    /// * `height`: Height of the frame.
+    ///
+    /// # Returns
+    /// The layout with the specified frame.
+    pub fn with_frame(mut self, width: f64, height: f64) -> Self {
+        self.width = width;
+        self.height = height;
+
+        self
+    }
+
+    /// # Arguments
+    /// `iterations`: Number of times to move the vertices.
+    ///
+    /// # Returns
+    /// The layout with the specified number of iterations.
+    pub fn with_iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+
+        self
+    }
+
+    /// # Arguments
+    /// `graph`: Graph to lay out.
+    ///
+    /// # Returns
+    /// Position of each vertex, with 0 <= x <= width and 0 <= y <= height.
+    ///
+    /// # Complexity
+    /// O(iterations * (|V|^2 + |E|))
+    pub fn execute<G: Neighbors + Vertices>(&self, graph: &G) -> HashMap<usize, (f64, f64)> {
+        let (id_map, adjacent_to) = adjacency(graph);
+        let vertex_count = adjacent_to.len();
+
+        // Optimal distance between vertices.
+        let k = (self.width * self.height / vertex_count.max(1) as f64).sqrt();
+
+        let mut positions = (0..vertex_count)
+            .map(|virt_id| {
+                let angle = 2.0 * PI * virt_id as f64 / vertex_count as f64;
+
+                (
+                    self.width / 2.0 * (1.0 + 0.5 * angle.cos()),
+                    self.height / 2.0 * (1.0 + 0.5 * angle.sin()),
+                )
+            })
+            .collect::<Vec<(f64, f64)>>();
+
+        // Distance between two vertices, never zero so the forces stay finite.
+        let delta_of = |src: (f64, f64), dst: (f64, f64)| {
+            let (dx, dy) = (src.0 - dst.0, src.1 - dst.1);
+            (dx, dy, dx.hypot(dy).max(1e-9))
+        };
+
+        let initial_temperature = self.width.max(self.height) / 10.0;
+        for iteration in 0..self.iterations {
+            let mut displacements = vec![(0.0, 0.0); vertex_count];
+
+            for src_id in 0..vertex_count {
+                for dst_id in src_id + 1..vertex_count {
+                    let (dx, dy, distance) = delta_of(positions[src_id], positions[dst_id]);
+                    let force = k * k / distance;
+
+                    displacements[src_id].0 += dx / distance * force;
+                    displacements[src_id].1 += dy / distance * force;
+                    displacements[dst_id].0 -= dx / distance * force;
+                    displacements[dst_id].1 -= dy / distance * force;
+                }
+            }
+
+            for (src_id, neighbors) in adjacent_to.iter().enumerate() {
+                for dst_id in neighbors.iter().filter(|dst_id| src_id < **dst_id) {
+                    let (dx, dy, distance) = delta_of(positions[src_id], positions[*dst_id]);
+                    let force = distance * distance / k;
+
+                    displacements[src_id].0 -= dx / distance * force;
+                    displacements[src_id].1 -= dy / distance * force;
+                    displacements[*dst_id].0 += dx / distance * force;
+                    displacements[*dst_id].1 += dy / distance * force;
+                }
+            }
+
+            let temperature =
+                initial_temperature * (1.0 - iteration as f64 / self.iterations as f64);
+            for (position, (dx, dy)) in positions.iter_mut().zip(displacements) {
+                let length = dx.hypot(dy).max(1e-9);
+                let step = length.min(temperature);
+
+                position.0 = (position.0 + dx / length * step).max(0.0).min(self.width);
+                position.1 = (position.1 + dy / length * step).max(0.0).min(self.height);
+            }
+        }
+
+        positions
+            .into_iter()
+            .enumerate()
+            .map(|(virt_id, position)| (id_map.real_id_of(virt_id), position))
+            .collect()
+    }
+}
+
+impl Default for FruchtermanReingold {
+    fn default() -> Self {
+        FruchtermanReingold::init()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::*;
+    use crate::storage::{DiMat, Mat};
+
+    fn distance(position_of: &HashMap<usize, (f64, f64)>, src_id: usize, dst_id: usize) -> f64 {
+        let (x1, y1) = position_of[&src_id];
+        let (x2, y2) = position_of[&dst_id];
+
+        (x1 - x2).hypot(y1 - y2)
+    }
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        let position_of = FruchtermanReingold::init().execute(&graph);
+
+        assert!(position_of.is_empty());
+    }
+
+    #[test]
+    fn one_vertex_graph() {
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+
+        let position_of = FruchtermanReingold::init().execute(&graph);
+
+        let (x, y) = position_of[&a];
+        assert!(x.is_finite() && y.is_finite());
+    }
+
+    #[test]
+    fn two_clusters() {
+        // Given: Two triangles connected by one edge.
+        //
+        //      a           d
+        //      | \       / |
+        //      |  c --- e  |
+        //      | /       \ |
+        //      b           f
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+        graph.add_edge_unchecked(c, a, 1.into());
+        graph.add_edge_unchecked(d, e, 1.into());
+        graph.add_edge_unchecked(e, f, 1.into());
+        graph.add_edge_unchecked(f, d, 1.into());
+        graph.add_edge_unchecked(c, e, 1.into());
+
+        // When:
+        let position_of = FruchtermanReingold::init()
+            .with_frame(10.0, 5.0)
+            .with_iterations(200)
+            .execute(&graph);
+
+        // Then: Vertices of a triangle are closer to each other than to vertices of the other triangle.
+        assert!(position_of
+            .values()
+            .all(|(x, y)| (0.0..=10.0).contains(x) && (0.0..=5.0).contains(y)));
+        assert!(distance(&position_of, a, b) < distance(&position_of, a, d));
+        assert!(distance(&position_of, a, b) < distance(&position_of, b, f));
+        assert!(distance(&position_of, d, f) < distance(&position_of, a, f));
+    }
+}
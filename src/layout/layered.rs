@@ -0,0 +1,334 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+
+use crate::algo::{Layering, LayeringMethod};
+use crate::graph::{DirectedEdge, Edge};
+use crate::provide::{Graph, Neighbors, Vertices};
+
+/// Sugiyama style layered layout of directed acyclic graphs.
+///
+/// Vertices are assigned to layers using a [`Layering`](crate::algo::Layering), so every edge points downward. Then order of the vertices in each layer
+/// is improved by sweeping down and up the layers and sorting each layer by the average position of the neighbors in the layers before it(barycenter heuristic),
+/// which reduces the number of edge crossings.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::layout::LayeredLayout;
+/// use prepona::storage::DiMat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  -->  b  -->  c
+/// //      |               ^
+/// //      '-----> d  -----'
+/// //
+/// let mut graph = MatGraph::init(DiMat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// let d = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+/// graph.add_edge_unchecked(a, d, 1.into());
+/// graph.add_edge_unchecked(d, c, 1.into());
+///
+/// let position_of = LayeredLayout::init().with_layer_spacing(2.0).execute(&graph).unwrap();
+///
+/// assert_eq!(position_of[&a].1, 0.0);
+/// assert_eq!(position_of[&b].1, 2.0);
+/// assert_eq!(position_of[&d].1, 2.0);
+/// assert_eq!(position_of[&c].1, 4.0);
+/// assert_ne!(position_of[&b].0, position_of[&d].0);
+/// ```
+pub struct LayeredLayout {
+    method: LayeringMethod,
+    layer_spacing: f64,
+    vertex_spacing: f64,
+    sweep_count: usize,
+}
+
+impl LayeredLayout {
+    /// # Returns
+    /// Layout that uses [`LongestPath`](crate::algo::LayeringMethod::LongestPath) layering, spacing of one between layers and between vertices, and four sweeps.
+    pub fn init() -> Self {
+        LayeredLayout {
+            method: LayeringMethod::LongestPath,
+            layer_spacing: 1.0,
+            vertex_spacing: 1.0,
+            sweep_count: 4,
+        }
+    }
+
+    /// # Arguments
+    /// `method`: Method used to assign vertices to layers.
+    ///
+    /// # Returns
+    /// The layout with the specified layering method.
+    pub fn with_method(mut self, method: LayeringMethod) -> Self {
+        self.method = method;
+
+        self
+    }
+
+    /// # Arguments
+    /// `layer_spacing`: Vertical distance between two consecutive layers.
+    ///
+    /// # Returns
+    /// The layout with the specified layer spacing.
+    pub fn with_layer_spacing(mut self, layer_spacing: f64) -> Self {
+        self.layer_spacing = layer_spacing;
+
+        self
+    }
+
+    /// # Arguments
+    /// `vertex_spacing`: Horizontal distance between two consecutive vertices of a layer.
+    ///
+    /// # Returns
+    /// The layout with the specified vertex spacing.
+    pub fn with_vertex_spacing(mut self, vertex_spacing: f64) -> Self {
+        self.vertex_spacing = vertex_spacing;
+
+        self
+    }
+
+    /// # Arguments
+    /// `sweep_count`: Number of times to sweep down and up the layers to reduce edge crossings. Zero keeps the order of the layering.
+    ///
+    /// # Returns
+    /// The layout with the specified number of sweeps.
+    pub fn with_sweep_count(mut self, sweep_count: usize) -> Self {
+        self.sweep_count = sweep_count;
+
+        self
+    }
+
+    // Sorts each layer by the average position of the given neighbors of its vertices. Vertices without such neighbors keep their position.
+    fn sort_by_barycenter(
+        layer: &mut Vec<usize>,
+        neighbors_of: &HashMap<usize, Vec<usize>>,
+        position_of: &HashMap<usize, usize>,
+    ) {
+        let mut barycenters = layer
+            .iter()
+            .enumerate()
+            .map(|(index, vertex_id)| {
+                let positions = neighbors_of[vertex_id]
+                    .iter()
+                    .filter_map(|n_id| position_of.get(n_id))
+                    .collect::<Vec<&usize>>();
+
+                let barycenter = if positions.is_empty() {
+                    index as f64
+                } else {
+                    positions
+                        .iter()
+                        .map(|position| **position as f64)
+                        .sum::<f64>()
+                        / positions.len() as f64
+                };
+
+                (barycenter, *vertex_id)
+            })
+            .collect::<Vec<(f64, usize)>>();
+        barycenters.sort_by(|(lhs, _), (rhs, _)| lhs.partial_cmp(rhs).unwrap());
+
+        *layer = barycenters
+            .into_iter()
+            .map(|(_, vertex_id)| vertex_id)
+            .collect();
+    }
+
+    // Positions of the vertices of the given layers in their layer.
+    fn positions_in(layers: &[Vec<usize>]) -> HashMap<usize, usize> {
+        layers
+            .iter()
+            .flat_map(|layer| layer.iter().enumerate())
+            .map(|(position, vertex_id)| (*vertex_id, position))
+            .collect()
+    }
+
+    /// # Arguments
+    /// `graph`: Graph to lay out.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing position of each vertex. Layer of a vertex determines its y, and its order in the layer determines its x. Each layer is centered around x = 0.
+    /// * `Err`: [`Cycle`](crate::graph::ErrorKind::Cycle) if graph has a cycle.
+    ///
+    /// # Panics
+    /// If width of [`CoffmanGraham`](crate::algo::LayeringMethod::CoffmanGraham) is zero.
+    ///
+    /// # Complexity
+    /// Complexity of the [`Layering`](crate::algo::Layering) plus O(sweep count * (|E| + |V| * log(|V|)))
+    pub fn execute<W, E, G>(&self, graph: &G) -> Result<HashMap<usize, (f64, f64)>>
+    where
+        E: Edge<W>,
+        G: Graph<W, E, DirectedEdge> + Vertices + Neighbors,
+    {
+        let mut layers = Layering::init(graph)
+            .execute(graph, self.method)?
+            .layers()
+            .clone();
+
+        let mut successors_of: HashMap<usize, Vec<usize>> = HashMap::new();
+        let mut predecessors_of: HashMap<usize, Vec<usize>> = HashMap::new();
+        for vertex_id in graph.vertices() {
+            successors_of.entry(vertex_id).or_default();
+            predecessors_of.entry(vertex_id).or_default();
+
+            for n_id in graph.neighbors_unchecked(vertex_id) {
+                successors_of.entry(vertex_id).or_default().push(n_id);
+                predecessors_of.entry(n_id).or_default().push(vertex_id);
+            }
+        }
+
+        for _ in 0..self.sweep_count {
+            for index in 1..layers.len() {
+                let position_of = Self::positions_in(&layers[..index]);
+                Self::sort_by_barycenter(&mut layers[index], &predecessors_of, &position_of);
+            }
+
+            for index in (0..layers.len().saturating_sub(1)).rev() {
+                let position_of = Self::positions_in(&layers[index + 1..]);
+                Self::sort_by_barycenter(&mut layers[index], &successors_of, &position_of);
+            }
+        }
+
+        let mut position_of = HashMap::new();
+        for (layer_index, layer) in layers.iter().enumerate() {
+            let center = (layer.len() as f64 - 1.0) / 2.0;
+
+            for (index, vertex_id) in layer.iter().enumerate() {
+                position_of.insert(
+                    *vertex_id,
+                    (
+                        (index as f64 - center) * self.vertex_spacing,
+                        layer_index as f64 * self.layer_spacing,
+                    ),
+                );
+            }
+        }
+
+        Ok(position_of)
+    }
+}
+
+impl Default for LayeredLayout {
+    fn default() -> Self {
+        LayeredLayout::init()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::storage::DiMat;
+
+    // Number of pairs of edges between the first two layers that cross each other.
+    fn crossing_count(edges: &[(usize, usize)], position_of: &HashMap<usize, (f64, f64)>) -> usize {
+        let mut count = 0;
+        for (index, (src1, dst1)) in edges.iter().enumerate() {
+            for (src2, dst2) in &edges[index + 1..] {
+                let src_order = position_of[src1].0 - position_of[src2].0;
+                let dst_order = position_of[dst1].0 - position_of[dst2].0;
+
+                if src_order * dst_order < 0.0 {
+                    count += 1;
+                }
+            }
+        }
+
+        count
+    }
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(DiMat::<usize>::init());
+
+        let position_of = LayeredLayout::init().execute(&graph).unwrap();
+
+        assert!(position_of.is_empty());
+    }
+
+    #[test]
+    fn cyclic_graph() {
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, a, 1.into());
+
+        assert!(LayeredLayout::init().execute(&graph).is_err());
+    }
+
+    #[test]
+    fn reduces_crossings() {
+        // Given: Graph where the initial order of the second layer makes every pair of edges cross.
+        //
+        //      a       b       c
+        //      |       |       |
+        //      v       v       v
+        //      f       e       d
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        let e = graph.add_vertex();
+        let f = graph.add_vertex();
+        graph.add_edge_unchecked(a, f, 1.into());
+        graph.add_edge_unchecked(b, e, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+
+        // When:
+        let position_of = LayeredLayout::init()
+            .with_vertex_spacing(3.0)
+            .execute(&graph)
+            .unwrap();
+
+        // Then:
+        assert_eq!(crossing_count(&[(a, f), (b, e), (c, d)], &position_of), 0);
+        assert_eq!(position_of[&a].1, 0.0);
+        assert_eq!(position_of[&f].1, 1.0);
+        let mut xs = [a, b, c]
+            .iter()
+            .map(|vertex_id| position_of[vertex_id].0)
+            .collect::<Vec<f64>>();
+        xs.sort_by(|lhs, rhs| lhs.partial_cmp(rhs).unwrap());
+        assert_eq!(xs, vec![-3.0, 0.0, 3.0]);
+    }
+
+    #[test]
+    fn edges_point_downward() {
+        // Given: Graph
+        //
+        //      a  -->  b  -->  c  -->  d
+        //      |       |               ^
+        //      |       '-----> e  -----'
+        //      '-------------> f
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let ids = (0..6).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        let edges = [(0, 1), (1, 2), (2, 3), (1, 4), (4, 3), (0, 5)];
+        for (src, dst) in edges.iter() {
+            graph.add_edge_unchecked(ids[*src], ids[*dst], 1.into());
+        }
+
+        // When:
+        let position_of = LayeredLayout::init()
+            .with_method(LayeringMethod::CoffmanGraham(1))
+            .execute(&graph)
+            .unwrap();
+
+        // Then: Each layer has one vertex.
+        for (src, dst) in edges.iter() {
+            assert!(position_of[&ids[*src]].1 < position_of[&ids[*dst]].1);
+        }
+        assert!(position_of.values().all(|(x, _)| *x == 0.0));
+    }
+}
@@ -0,0 +1,7 @@
+mod force_directed;
+mod layered;
+mod spring_electrical;
+
+pub use force_directed::FruchtermanReingold;
+pub use layered::LayeredLayout;
+pub use spring_electrical::ForceDirected;
@@ -0,0 +1,329 @@
+use crate::algo::metrics::adjacency;
+use crate::provide::{Neighbors, Vertices};
+use crate::util::{unit, SplitMix64};
+
+// Fraction of the net force that a vertex moves by in each iteration. Moving by the whole force makes springs overshoot and oscillate.
+const DAMPING: f64 = 0.1;
+
+/// Force-directed layout with configurable physics.
+///
+/// Each pair of vertices repels with a force of `repulsion / distance^2`, and each edge acts as a spring that pulls or pushes its end points
+/// with a force of `attraction * (distance - spring_length)`. In each iteration vertices move by a tenth of the net force,
+/// but at most by a step that shrinks linearly from `spring_length` to zero.
+///
+/// Initial positions are drawn uniformly from a square around the origin, using a pseudo random generator seeded with `seed`.
+/// So the same graph and settings always produce the same layout. Use [`execute_with_rng`](crate::layout::ForceDirected::execute_with_rng) to draw them with another generator.
+///
+/// Direction of the edges and loops are ignored.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::layout::ForceDirected;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+///
+/// let positions = ForceDirected::init()
+///     .with_repulsion(0.0)
+///     .with_spring_length(2.0)
+///     .with_iterations(200)
+///     .with_seed(7)
+///     .execute(&graph);
+///
+/// // Without repulsion, the spring settles at its length.
+/// let (_, x1, y1) = positions[0];
+/// let (_, x2, y2) = positions[1];
+/// assert!(((x1 - x2).hypot(y1 - y2) - 2.0).abs() < 1e-3);
+/// ```
+pub struct ForceDirected {
+    repulsion: f64,
+    attraction: f64,
+    spring_length: f64,
+    iterations: usize,
+    seed: u64,
+}
+
+impl ForceDirected {
+    /// # Returns
+    /// Layout with repulsion and attraction of one, spring length of one, 100 iterations and seed of zero.
+    pub fn init() -> Self {
+        ForceDirected {
+            repulsion: 1.0,
+            attraction: 1.0,
+            spring_length: 1.0,
+            iterations: 100,
+            seed: 0,
+        }
+    }
+
+    /// # Arguments
+    /// `repulsion`: Strength of the force that pushes every two vertices apart.
+    ///
+    /// # Returns
+    /// The layout with the specified repulsion.
+    pub fn with_repulsion(mut self, repulsion: f64) -> Self {
+        self.repulsion = repulsion;
+
+        self
+    }
+
+    /// # Arguments
+    /// `attraction`: Stiffness of the springs that edges act as.
+    ///
+    /// # Returns
+    /// The layout with the specified attraction.
+    pub fn with_attraction(mut self, attraction: f64) -> Self {
+        self.attraction = attraction;
+
+        self
+    }
+
+    /// # Arguments
+    /// `spring_length`: Length of the springs when they are at rest. It also determines the size of the square that vertices start in.
+    ///
+    /// # Returns
+    /// The layout with the specified spring length.
+    pub fn with_spring_length(mut self, spring_length: f64) -> Self {
+        self.spring_length = spring_length;
+
+        self
+    }
+
+    /// # Arguments
+    /// `iterations`: Number of times to move the vertices.
+    ///
+    /// # Returns
+    /// The layout with the specified number of iterations.
+    pub fn with_iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+
+        self
+    }
+
+    /// # Arguments
+    /// `seed`: Seed of the generator of initial positions.
+    ///
+    /// # Returns
+    /// The layout with the specified seed.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+
+        self
+    }
+
+    /// # Arguments
+    /// `graph`: Graph to lay out.
+    ///
+    /// # Returns
+    /// Position of each vertex in the format of: (`vertex_id`, `x`, `y`), ordered by id of the vertices.
+    ///
+    /// # Complexity
+    /// O(iterations * (|V|^2 + |E|))
+    pub fn execute<G: Neighbors + Vertices>(&self, graph: &G) -> Vec<(usize, f64, f64)> {
+        let mut generator = SplitMix64::init(self.seed);
+
+        self.layout(graph, || generator.next_unit())
+    }
+
+    /// Same as [`execute`](crate::layout::ForceDirected::execute), but initial positions are drawn using `rng` instead of the seed of the layout.
+    ///
+    /// `rng` only returns integers, so positions are drawn with a coarser resolution than `execute` draws them.
+    /// So `util::seeded(seed)` does not reproduce the layout that `execute` produces with the same seed.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to lay out.
+    /// * `rng`: Source of randomness: when called with `n`, returns a uniformly random number in range of 0 to `n` - 1.
+    ///   With the `rand` feature, `util::from_rng` creates it from any generator of the rand ecosystem.
+    ///
+    /// # Returns
+    /// Position of each vertex in the format of: (`vertex_id`, `x`, `y`), ordered by id of the vertices.
+    ///
+    /// # Complexity
+    /// O(iterations * (|V|^2 + |E|))
+    pub fn execute_with_rng<G, R>(&self, graph: &G, rng: &mut R) -> Vec<(usize, f64, f64)>
+    where
+        G: Neighbors + Vertices,
+        R: FnMut(usize) -> usize,
+    {
+        self.layout(graph, || unit(rng))
+    }
+
+    // Lays out the graph starting from positions drawn by `next_unit`, which returns a number in range of [0, 1).
+    fn layout<G, U>(&self, graph: &G, mut next_unit: U) -> Vec<(usize, f64, f64)>
+    where
+        G: Neighbors + Vertices,
+        U: FnMut() -> f64,
+    {
+        let (id_map, adjacent_to) = adjacency(graph);
+        let vertex_count = adjacent_to.len();
+
+        let side = self.spring_length * (vertex_count as f64).sqrt();
+        let mut positions = (0..vertex_count)
+            .map(|_| {
+                let x = (next_unit() - 0.5) * side;
+                let y = (next_unit() - 0.5) * side;
+
+                (x, y)
+            })
+            .collect::<Vec<(f64, f64)>>();
+
+        // Difference and distance of two vertices, never zero so the forces stay finite.
+        let delta_of = |src: (f64, f64), dst: (f64, f64)| {
+            let (dx, dy) = (src.0 - dst.0, src.1 - dst.1);
+            (dx, dy, dx.hypot(dy).max(1e-9))
+        };
+
+        for iteration in 0..self.iterations {
+            let mut forces = vec![(0.0, 0.0); vertex_count];
+
+            for src_id in 0..vertex_count {
+                for dst_id in src_id + 1..vertex_count {
+                    let (dx, dy, distance) = delta_of(positions[src_id], positions[dst_id]);
+                    let force = self.repulsion / (distance * distance);
+
+                    forces[src_id].0 += dx / distance * force;
+                    forces[src_id].1 += dy / distance * force;
+                    forces[dst_id].0 -= dx / distance * force;
+                    forces[dst_id].1 -= dy / distance * force;
+                }
+            }
+
+            for (src_id, neighbors) in adjacent_to.iter().enumerate() {
+                for dst_id in neighbors.iter().filter(|dst_id| src_id < **dst_id) {
+                    let (dx, dy, distance) = delta_of(positions[src_id], positions[*dst_id]);
+                    let force = self.attraction * (distance - self.spring_length);
+
+                    forces[src_id].0 -= dx / distance * force;
+                    forces[src_id].1 -= dy / distance * force;
+                    forces[*dst_id].0 += dx / distance * force;
+                    forces[*dst_id].1 += dy / distance * force;
+                }
+            }
+
+            let max_step = self.spring_length * (1.0 - iteration as f64 / self.iterations as f64);
+            for (position, (fx, fy)) in positions.iter_mut().zip(forces) {
+                let length = fx.hypot(fy).max(1e-9);
+                let step = (DAMPING * length).min(max_step);
+
+                position.0 += fx / length * step;
+                position.1 += fy / length * step;
+            }
+        }
+
+        let mut positions = positions
+            .into_iter()
+            .enumerate()
+            .map(|(virt_id, (x, y))| (id_map.real_id_of(virt_id), x, y))
+            .collect::<Vec<(usize, f64, f64)>>();
+        positions.sort_unstable_by_key(|(vertex_id, _, _)| *vertex_id);
+
+        positions
+    }
+}
+
+impl Default for ForceDirected {
+    fn default() -> Self {
+        ForceDirected::init()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::MatGraph;
+    use crate::provide::*;
+    use crate::storage::Mat;
+    use crate::util::seeded;
+
+    fn distance(positions: &[(usize, f64, f64)], src_id: usize, dst_id: usize) -> f64 {
+        let (_, x1, y1) = positions.iter().find(|(id, _, _)| *id == src_id).unwrap();
+        let (_, x2, y2) = positions.iter().find(|(id, _, _)| *id == dst_id).unwrap();
+
+        (x1 - x2).hypot(y1 - y2)
+    }
+
+    #[test]
+    fn empty_graph() {
+        let graph = MatGraph::init(Mat::<usize>::init());
+
+        assert!(ForceDirected::init().execute(&graph).is_empty());
+    }
+
+    #[test]
+    fn same_seed_same_layout() {
+        // Given: Graph
+        //
+        //      a  ---  b  ---  c
+        //
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+        graph.add_edge_unchecked(b, c, 1.into());
+
+        // When:
+        let first = ForceDirected::init().with_seed(42).execute(&graph);
+        let second = ForceDirected::init().with_seed(42).execute(&graph);
+        let other = ForceDirected::init().with_seed(43).execute(&graph);
+
+        // Then:
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+        assert_eq!(
+            ForceDirected::init().execute_with_rng(&graph, &mut seeded(42)),
+            ForceDirected::init().execute_with_rng(&graph, &mut seeded(42))
+        );
+        assert_eq!(
+            first.iter().map(|(id, _, _)| *id).collect::<Vec<usize>>(),
+            vec![a, b, c]
+        );
+    }
+
+    #[test]
+    fn repulsion_spreads_vertices() {
+        // Given: Star with a center and four leaves.
+        let mut graph = MatGraph::init(Mat::<usize>::init());
+        let center = graph.add_vertex();
+        let leaves = (0..4).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        for leaf_id in &leaves {
+            graph.add_edge_unchecked(center, *leaf_id, 1.into());
+        }
+
+        // When:
+        let weak = ForceDirected::init()
+            .with_repulsion(0.01)
+            .with_iterations(300)
+            .execute(&graph);
+        let strong = ForceDirected::init()
+            .with_repulsion(10.0)
+            .with_iterations(300)
+            .execute(&graph);
+
+        // Then: Stronger repulsion stretches the springs further.
+        let average_length = |positions: &[(usize, f64, f64)]| {
+            leaves
+                .iter()
+                .map(|leaf_id| distance(positions, center, *leaf_id))
+                .sum::<f64>()
+                / leaves.len() as f64
+        };
+        assert!(average_length(&weak) < average_length(&strong));
+        assert!(positions_are_finite(&strong));
+    }
+
+    fn positions_are_finite(positions: &[(usize, f64, f64)]) -> bool {
+        positions
+            .iter()
+            .all(|(_, x, y)| x.is_finite() && y.is_finite())
+    }
+}
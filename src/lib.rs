@@ -18,22 +18,91 @@ pub mod graph;
 ///
 /// Algorithms only depend on functionalities that are defined in this module and not on specific structures like graphs.
 /// This enables us to decouple algorithms from data structures that they receive as input.
-/// So you can define your own structure wether it's a graph, subgraph or an augmented graph and run algorithms defined in `algo` module on them.
+/// So you can define your own structure whether it's a graph, subgraph or an augmented graph and run algorithms defined in `algo` module on them.
 /// All you have to do is to implement the traits that are needed by the algorithm you want to use.
 pub mod provide;
 
 /// Storages are structures that graphs use to store information about vertices and edges.
 ///
-/// There are two storage types that are supported:
+/// There are three storage types that are supported:
 /// * Adjacency matrix: Is a matrix used to represent a finite graph. 
 ///                     The elements of the matrix indicate whether pairs of vertices are adjacent or not in the graph.
 ///                     For more info read [`AdjMatrix`](crate::storage::AdjMatrix).
 /// * Adjacency list:   Is a collection of unordered lists used to represent a finite graph. 
 ///                     Each list describes the set of neighbors of a vertex in the graph.
 ///                     For more info read [`AdjList`](crate::storage::AdjList)
+/// * Sparse matrix:    Is a matrix that only stores its non-empty cells.
+///                     Edges between two vertices can be inserted, removed and looked up in constant time regardless of the number of vertices.
+///                     For more info read [`SparseMatrix`](crate::storage::SparseMatrix)
+///
+/// For unweighted graphs there is also [`BitMatrix`](crate::storage::BitMatrix) which only uses one bit for each pair of vertices.
+/// It does not store edges so it can not be used as a storage of a graph, but algorithms that only depend on vertices and neighbors of the graph accept it.
 ///
 /// Each storage must implement the [`GraphStorage`](crate::storage::GraphStorage) trait.
 /// So You can create your own storage and after implementing the `GraphStorage`, pass it to the graph to use it as backend storage of the graph.
 ///
 /// For a more informed decision regarding to which storage to choose, checkout documentation of each storage and compare their memory usage and time complexity of each operation.
 pub mod storage;
+
+/// Utilities for testing storages against a naive reference model using random sequences of operations.
+///
+/// [`check_storage`](crate::testing::check_storage) can be used to validate a custom implementation of [`GraphStorage`](crate::storage::GraphStorage)
+/// with the same harness that storages of prepona are tested with.
+pub mod testing;
+
+/// Reading graphs from files and writing them.
+///
+/// [`EdgeListLoader`](crate::io::EdgeListLoader) streams large edge lists, like datasets of SNAP and KONECT, into any graph.
+/// So storages can be compared on real datasets.
+/// [`AdjacencyCsv`](crate::io::AdjacencyCsv) writes and reads dense adjacency matrices in CSV format, for spreadsheets and dataframe libraries.
+pub mod io;
+
+/// Computing 2D coordinates of the vertices, so graphs can be drawn by visualization front-ends.
+///
+/// [`LayeredLayout`](crate::layout::LayeredLayout) draws directed acyclic graphs in layers.
+/// [`FruchtermanReingold`](crate::layout::FruchtermanReingold) and [`ForceDirected`](crate::layout::ForceDirected) draw any graph using force-directed simulations. Positions are `(x, y)` pairs with y growing downward.
+pub mod layout;
+
+/// Processing graphs that do not fit in memory, as a stream of edges.
+///
+/// Each algorithm implements [`EdgeStream`](crate::stream::EdgeStream) and keeps only a summary of the edges it has seen,
+/// like [`DegreeCounter`](crate::stream::DegreeCounter), [`StreamComponents`](crate::stream::StreamComponents) and [`TriangleEstimator`](crate::stream::TriangleEstimator).
+/// [`read_edges`](crate::stream::read_edges) lazily reads the edges of an edge list file.
+pub mod stream;
+
+/// Data structures that algorithms of prepona are built on, and are useful for writing new algorithms.
+///
+/// [`IndexedHeap`](crate::util::IndexedHeap) is a priority queue of vertex ids that supports decreasing the key of an id, as needed by Dijkstra and similar algorithms.
+/// [`seeded`](crate::util::seeded) creates a reproducible source of randomness for randomized algorithms from a seed. With the `rand` feature, `from_rng` creates one from any `rand::Rng`.
+pub mod util;
+
+// Graph shared by the language bindings `wasm`, `ffi` and `python`.
+#[cfg(any(feature = "wasm", feature = "ffi", feature = "python"))]
+mod bindings;
+
+/// JavaScript facade of prepona, so it can power browser-based visualizers when compiled to WebAssembly.
+///
+/// [`JsGraph`](crate::wasm::JsGraph) builds directed or undirected graphs, runs shortest path and connected components algorithms on them, and serializes results and the graph itself to JSON.
+///
+/// The package can be built by [wasm-pack](https://rustwasm.github.io/wasm-pack/): `wasm-pack build --target web -- --features wasm`.
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+/// C API of prepona, so services written in other languages can embed it.
+///
+/// Graphs are handed to C as opaque [`PreponaGraph`](crate::ffi::PreponaGraph) pointers, and each function reports its result with a [`PreponaStatus`](crate::ffi::PreponaStatus).
+/// Declarations of the functions are in `include/prepona.h`.
+///
+/// prepona is only built as an `rlib` by default. The shared or static library can be built by
+/// `cargo rustc --release --features ffi --crate-type cdylib`, or `--crate-type staticlib`.
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+/// Python bindings of prepona, importable as the `prepona` module.
+///
+/// [`PyGraph`](crate::python::PyGraph) is exposed as `prepona.Graph`. It can be built from NumPy arrays of edges or by generators like `Graph.grid`,
+/// and algorithms return NumPy arrays indexed by id of the vertices.
+///
+/// The extension module can be built by [maturin](https://www.maturin.rs): `maturin build --release --features python`.
+#[cfg(feature = "python")]
+pub mod python;
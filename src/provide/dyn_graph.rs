@@ -0,0 +1,309 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+
+use super::{Direction, Edges, Graph, IdMap, Neighbors, Vertices};
+use crate::graph::{Edge, EdgeDir};
+
+/// Read access to a graph through a single trait, so different kinds of graphs can be used as `dyn GraphView<W, E>`.
+///
+/// Each provider trait is object safe on its own, but a trait object can only have one non-auto trait.
+/// This trait combines [`Neighbors`](crate::provide::Neighbors), [`Vertices`](crate::provide::Vertices), [`Edges`](crate::provide::Edges)
+/// and [`Direction`](crate::provide::Direction), and it's implemented for every type that implements them.
+///
+/// Provider traits are also implemented for references and boxes of any type that implements them, including trait objects.
+/// So `&dyn GraphView<W, E>` and `Box<dyn GraphView<W, E>>` can be passed to algorithms like any other graph.
+/// Graphs with different storages, subgraphs and views can be kept in the same collection this way, and algorithms can be chosen at runtime.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::centrality::Betweenness;
+/// use prepona::storage::{List, Mat};
+/// use prepona::graph::{ListGraph, MatGraph};
+///
+/// // Given: Path of three vertices stored in a matrix, and two isolated vertices stored in a list.
+/// let mut path = MatGraph::init(Mat::<usize>::init());
+/// let a = path.add_vertex();
+/// let b = path.add_vertex();
+/// let c = path.add_vertex();
+/// path.add_edge_unchecked(a, b, 1.into());
+/// path.add_edge_unchecked(b, c, 1.into());
+///
+/// let mut isolated = ListGraph::init(List::<usize>::init());
+/// isolated.add_vertex();
+/// isolated.add_vertex();
+///
+/// let graphs: Vec<Box<dyn GraphView<usize, DefaultEdge<usize>>>> =
+///     vec![Box::new(path), Box::new(isolated)];
+///
+/// // When: Running the same algorithm on each of them.
+/// let total_betweenness: Vec<f64> = graphs
+///     .iter()
+///     .map(|graph| {
+///         let (vertex_betweenness, _) = Betweenness::init(graph).execute(graph);
+///
+///         vertex_betweenness.values().sum()
+///     })
+///     .collect();
+///
+/// assert_eq!(total_betweenness, vec![1.0, 0.0]);
+/// ```
+pub trait GraphView<W, E: Edge<W>>: Neighbors + Vertices + Edges<W, E> + Direction {}
+
+impl<W, E, G> GraphView<W, E> for G
+where
+    E: Edge<W>,
+    G: Neighbors + Vertices + Edges<W, E> + Direction + ?Sized,
+{
+}
+
+// Implements provider traits for `$ptr`, which is a pointer to `T`, by forwarding each function to `T`.
+macro_rules! forward_providers {
+    ($($ptr:ty),*) => {
+        $(
+            /// For documentation about each function checkout [`Neighbors`](crate::provide::Neighbors) trait.
+            impl<T: Neighbors + ?Sized> Neighbors for $ptr {
+                fn neighbors(&self, src_id: usize) -> Result<Vec<usize>> {
+                    (**self).neighbors(src_id)
+                }
+
+                fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
+                    (**self).neighbors_unchecked(src_id)
+                }
+
+                fn neighbors_within(&self, src_id: usize, k: usize) -> Result<HashMap<usize, usize>> {
+                    (**self).neighbors_within(src_id, k)
+                }
+
+                fn neighbors_within_unchecked(&self, src_id: usize, k: usize) -> HashMap<usize, usize> {
+                    (**self).neighbors_within_unchecked(src_id, k)
+                }
+            }
+
+            /// For documentation about each function checkout [`Vertices`](crate::provide::Vertices) trait.
+            impl<T: Vertices + ?Sized> Vertices for $ptr {
+                fn vertices(&self) -> Vec<usize> {
+                    (**self).vertices()
+                }
+
+                fn vertex_count(&self) -> usize {
+                    (**self).vertex_count()
+                }
+
+                fn continuous_id_map(&self) -> IdMap {
+                    (**self).continuous_id_map()
+                }
+
+                fn contains_vertex(&self, vertex_id: usize) -> bool {
+                    (**self).contains_vertex(vertex_id)
+                }
+            }
+
+            /// For documentation about each function checkout [`Edges`](crate::provide::Edges) trait.
+            impl<W, E: Edge<W>, T: Edges<W, E> + ?Sized> Edges<W, E> for $ptr {
+                fn edges_from(&self, src_id: usize) -> Result<Vec<(usize, &E)>> {
+                    (**self).edges_from(src_id)
+                }
+
+                fn edges_from_unchecked(&self, src_id: usize) -> Vec<(usize, &E)> {
+                    (**self).edges_from_unchecked(src_id)
+                }
+
+                fn edges_between(&self, src_id: usize, dst_id: usize) -> Result<Vec<&E>> {
+                    (**self).edges_between(src_id, dst_id)
+                }
+
+                fn edges_between_unchecked(&self, src_id: usize, dst_id: usize) -> Vec<&E> {
+                    (**self).edges_between_unchecked(src_id, dst_id)
+                }
+
+                fn edge_between(&self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<Option<&E>> {
+                    (**self).edge_between(src_id, dst_id, edge_id)
+                }
+
+                fn edge_between_unchecked(&self, src_id: usize, dst_id: usize, edge_id: usize) -> Option<&E> {
+                    (**self).edge_between_unchecked(src_id, dst_id, edge_id)
+                }
+
+                fn edge(&self, edge_id: usize) -> Result<Option<&E>> {
+                    (**self).edge(edge_id)
+                }
+
+                fn edge_unchecked(&self, edge_id: usize) -> Option<&E> {
+                    (**self).edge_unchecked(edge_id)
+                }
+
+                fn has_any_edge(&self, src_id: usize, dst_id: usize) -> Result<bool> {
+                    (**self).has_any_edge(src_id, dst_id)
+                }
+
+                fn has_any_edge_unchecked(&self, src_id: usize, dst_id: usize) -> bool {
+                    (**self).has_any_edge_unchecked(src_id, dst_id)
+                }
+
+                fn edges(&self) -> Vec<(usize, usize, &E)> {
+                    (**self).edges()
+                }
+
+                fn as_directed_edges(&self) -> Vec<(usize, usize, &E)> {
+                    (**self).as_directed_edges()
+                }
+
+                fn edges_count(&self) -> usize {
+                    (**self).edges_count()
+                }
+
+                fn contains_edge(&self, edge_id: usize) -> bool {
+                    (**self).contains_edge(edge_id)
+                }
+            }
+
+            /// For documentation about each function checkout [`Direction`](crate::provide::Direction) trait.
+            impl<T: Direction + ?Sized> Direction for $ptr {
+                fn is_directed(&self) -> bool {
+                    (**self).is_directed()
+                }
+
+                fn is_undirected(&self) -> bool {
+                    (**self).is_undirected()
+                }
+            }
+        )*
+    };
+}
+
+forward_providers!(&T, &mut T, Box<T>);
+
+/// For documentation about each function checkout [`Graph`](crate::provide::Graph) trait.
+impl<W, E: Edge<W>, Ty: EdgeDir, T: Graph<W, E, Ty> + ?Sized> Graph<W, E, Ty> for &mut T {
+    fn add_vertex(&mut self) -> usize {
+        (**self).add_vertex()
+    }
+
+    fn remove_vertex(&mut self, vertex_id: usize) -> Result<()> {
+        (**self).remove_vertex(vertex_id)
+    }
+
+    fn remove_vertex_unchecked(&mut self, vertex_id: usize) {
+        (**self).remove_vertex_unchecked(vertex_id)
+    }
+
+    fn add_edge(&mut self, src_id: usize, dst_id: usize, edge: E) -> Result<usize> {
+        (**self).add_edge(src_id, dst_id, edge)
+    }
+
+    fn add_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge: E) -> usize {
+        (**self).add_edge_unchecked(src_id, dst_id, edge)
+    }
+
+    fn update_edge(&mut self, src_id: usize, dst_id: usize, edge_id: usize, edge: E) -> Result<()> {
+        (**self).update_edge(src_id, dst_id, edge_id, edge)
+    }
+
+    fn update_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize, edge: E) {
+        (**self).update_edge_unchecked(src_id, dst_id, edge_id, edge)
+    }
+
+    fn remove_edge(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<Option<E>> {
+        (**self).remove_edge(src_id, dst_id, edge_id)
+    }
+
+    fn remove_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> Option<E> {
+        (**self).remove_edge_unchecked(src_id, dst_id, edge_id)
+    }
+}
+
+/// For documentation about each function checkout [`Graph`](crate::provide::Graph) trait.
+impl<W, E: Edge<W>, Ty: EdgeDir, T: Graph<W, E, Ty> + ?Sized> Graph<W, E, Ty> for Box<T> {
+    fn add_vertex(&mut self) -> usize {
+        (**self).add_vertex()
+    }
+
+    fn remove_vertex(&mut self, vertex_id: usize) -> Result<()> {
+        (**self).remove_vertex(vertex_id)
+    }
+
+    fn remove_vertex_unchecked(&mut self, vertex_id: usize) {
+        (**self).remove_vertex_unchecked(vertex_id)
+    }
+
+    fn add_edge(&mut self, src_id: usize, dst_id: usize, edge: E) -> Result<usize> {
+        (**self).add_edge(src_id, dst_id, edge)
+    }
+
+    fn add_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge: E) -> usize {
+        (**self).add_edge_unchecked(src_id, dst_id, edge)
+    }
+
+    fn update_edge(&mut self, src_id: usize, dst_id: usize, edge_id: usize, edge: E) -> Result<()> {
+        (**self).update_edge(src_id, dst_id, edge_id, edge)
+    }
+
+    fn update_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize, edge: E) {
+        (**self).update_edge_unchecked(src_id, dst_id, edge_id, edge)
+    }
+
+    fn remove_edge(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> Result<Option<E>> {
+        (**self).remove_edge(src_id, dst_id, edge_id)
+    }
+
+    fn remove_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> Option<E> {
+        (**self).remove_edge_unchecked(src_id, dst_id, edge_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::Bfs;
+    use crate::graph::{DefaultEdge, DirectedEdge, ListGraph, MatGraph};
+    use crate::storage::{DiList, DiMat};
+
+    fn add_path<G: Graph<usize, DefaultEdge<usize>, DirectedEdge> + ?Sized>(
+        graph: &mut G,
+        length: usize,
+    ) -> Vec<usize> {
+        let ids: Vec<usize> = (0..=length).map(|_| graph.add_vertex()).collect();
+        for window in ids.windows(2) {
+            graph.add_edge_unchecked(window[0], window[1], 1.into());
+        }
+
+        ids
+    }
+
+    #[test]
+    fn boxed_graphs_are_mutated_through_trait_objects() {
+        // Given: Graphs with different storages behind the same trait object type.
+        let mut graphs: Vec<Box<dyn Graph<usize, DefaultEdge<usize>, DirectedEdge>>> = vec![
+            Box::new(MatGraph::init(DiMat::<usize>::init())),
+            Box::new(ListGraph::init(DiList::<usize>::init())),
+        ];
+
+        // When:
+        let ids: Vec<Vec<usize>> = graphs.iter_mut().map(|graph| add_path(graph, 2)).collect();
+
+        // Then:
+        assert_eq!(
+            ids.iter().map(|ids| ids.len()).collect::<Vec<usize>>(),
+            vec![3, 3]
+        );
+    }
+
+    #[test]
+    fn algorithms_accept_references_to_trait_objects() {
+        // Given: Path a --> b --> c as a trait object.
+        let mut graph = ListGraph::init(DiList::<usize>::init());
+        let ids = add_path(&mut graph, 2);
+        let view: &dyn GraphView<usize, DefaultEdge<usize>> = &graph;
+
+        // When:
+        let visited = Bfs::init_with_starts(&view, vec![ids[0]]).execute(&view);
+
+        // Then:
+        assert_eq!(visited, ids);
+        assert!(view.is_directed());
+        assert_eq!(view.vertex_count(), 3);
+        assert_eq!(view.edges_from_unchecked(ids[1]).len(), 1);
+    }
+}
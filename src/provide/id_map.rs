@@ -1,12 +1,45 @@
 use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::storage::{map_bytes, IndexType};
 
 /// Stores a two-way mapping between set of real and virtual ids.
-pub struct IdMap {
-    real_to_virt: HashMap<usize, usize>,
-    virt_to_real: HashMap<usize, usize>,
+///
+/// Lookups in both directions take O(1). Mappings are shared between clones of an id map until one of them gets modified,
+/// so cloning an id map is cheap.
+///
+/// ## Generic Parameters
+/// * `Ix`: **I**nde**x** type used to store the ids internally. Checkout [`IndexType`](crate::storage::IndexType) for more info.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+///
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.remove_vertex(b).unwrap();
+///
+/// let id_map = graph.continuous_id_map();
+///
+/// assert_eq!(id_map.len(), 2);
+/// assert_eq!(id_map.real_id_of(id_map.virt_id_of(c)), c);
+/// assert_eq!(id_map.get_virt_id(b), None);
+///
+/// let mut pairs: Vec<(usize, usize)> = id_map.iter().collect();
+/// pairs.sort_unstable();
+/// assert_eq!(pairs, vec![(a, 0), (c, 1)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct IdMap<Ix: IndexType = usize> {
+    real_to_virt: Arc<HashMap<Ix, Ix>>,
+    virt_to_real: Arc<HashMap<Ix, Ix>>,
 }
 
-impl IdMap {
+impl<Ix: IndexType> IdMap<Ix> {
     /// # Arguments
     /// `entries_count`: Number of ids that are gonna be mapped.
     ///
@@ -14,8 +47,8 @@ impl IdMap {
     /// An empty id map.
     pub fn init(entries_count: usize) -> Self {
         IdMap {
-            real_to_virt: HashMap::with_capacity(entries_count),
-            virt_to_real: HashMap::with_capacity(entries_count),
+            real_to_virt: Arc::new(HashMap::with_capacity(entries_count)),
+            virt_to_real: Arc::new(HashMap::with_capacity(entries_count)),
         }
     }
 
@@ -25,7 +58,7 @@ impl IdMap {
     /// * `real_id`: Real id of the mapping.
     /// * `virt_id`: Virtual id of the mapping.
     pub fn put_real_to_virt(&mut self, real_id: usize, virt_id: usize) {
-        self.real_to_virt.insert(real_id, virt_id);
+        Arc::make_mut(&mut self.real_to_virt).insert(Ix::new(real_id), Ix::new(virt_id));
     }
 
     /// Inserts a mapping from `real_id` to `virt_id`.
@@ -34,7 +67,7 @@ impl IdMap {
     /// * `virt_id`: Virtual id of the mapping.
     /// * `real_id`: Real id of the mapping.
     pub fn put_virt_to_real(&mut self, virt_id: usize, real_id: usize) {
-        self.virt_to_real.insert(virt_id, real_id);
+        Arc::make_mut(&mut self.virt_to_real).insert(Ix::new(virt_id), Ix::new(real_id));
     }
 
     /// # Arguments
@@ -42,8 +75,11 @@ impl IdMap {
     ///
     /// # Returns
     /// Virtual id of id: `real_id`.
+    ///
+    /// # Panics
+    /// If `real_id` is not mapped.
     pub fn virt_id_of(&self, real_id: usize) -> usize {
-        self.real_to_virt.get(&real_id).copied().unwrap()
+        self.real_to_virt[&Ix::new(real_id)].index()
     }
 
     /// # Arguments
@@ -51,8 +87,88 @@ impl IdMap {
     ///
     /// # Returns
     /// Real id of id: `virt_id`.
+    ///
+    /// # Panics
+    /// If `virt_id` is not mapped.
     pub fn real_id_of(&self, virt_id: usize) -> usize {
-        self.virt_to_real.get(&virt_id).copied().unwrap()
+        self.virt_to_real[&Ix::new(virt_id)].index()
+    }
+
+    /// # Arguments
+    /// `real_id`: Real id of the mapping.
+    ///
+    /// # Returns
+    /// * `Some`: Containing virtual id of id: `real_id`.
+    /// * `None`: If `real_id` is not mapped.
+    pub fn get_virt_id(&self, real_id: usize) -> Option<usize> {
+        if real_id > Ix::max_index() {
+            return None;
+        }
+
+        self.real_to_virt
+            .get(&Ix::new(real_id))
+            .map(|virt_id| virt_id.index())
+    }
+
+    /// # Arguments
+    /// `virt_id`: Virtual id of the mapping.
+    ///
+    /// # Returns
+    /// * `Some`: Containing real id of id: `virt_id`.
+    /// * `None`: If `virt_id` is not mapped.
+    pub fn get_real_id(&self, virt_id: usize) -> Option<usize> {
+        if virt_id > Ix::max_index() {
+            return None;
+        }
+
+        self.virt_to_real
+            .get(&Ix::new(virt_id))
+            .map(|real_id| real_id.index())
+    }
+
+    /// # Returns
+    /// Number of real ids that are mapped to a virtual id.
+    pub fn len(&self) -> usize {
+        self.real_to_virt.len()
+    }
+
+    /// # Returns
+    /// `true` if no real id is mapped to a virtual id, `false` otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.real_to_virt.is_empty()
+    }
+
+    /// # Returns
+    /// Iterator over mappings from real ids to virtual ids in the format of: (`real_id`, `virt_id`), in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.real_to_virt
+            .iter()
+            .map(|(real_id, virt_id)| (real_id.index(), virt_id.index()))
+    }
+
+    /// # Returns
+    /// Estimated number of bytes allocated by both directions of the map.
+    pub fn memory_usage(&self) -> usize {
+        map_bytes(&self.real_to_virt) + map_bytes(&self.virt_to_real)
+    }
+}
+
+impl IdMap {
+    // Maps `vertices` to a continuous series of ids, assigned in the ascending order of `vertices`.
+    pub(crate) fn continuous(mut vertices: Vec<usize>) -> Self {
+        vertices.sort_unstable();
+
+        let mut id_map = IdMap::init(vertices.len());
+
+        vertices
+            .into_iter()
+            .enumerate()
+            .for_each(|(virt_id, real_id)| {
+                id_map.put_virt_to_real(virt_id, real_id);
+                id_map.put_real_to_virt(real_id, virt_id);
+            });
+
+        id_map
     }
 }
 
@@ -63,7 +179,7 @@ mod tests {
     #[test]
     fn init() {
         // Given: An empty id map.
-        let id_map = IdMap::init(0);
+        let id_map = IdMap::<usize>::init(0);
 
         // When: Doing nothing.
 
@@ -75,7 +191,7 @@ mod tests {
     #[test]
     fn put_real_to_virt() {
         // Given: An empty id map.
-        let mut id_map = IdMap::init(1);
+        let mut id_map = IdMap::<usize>::init(1);
 
         // When: Adding a mapping from 1(real) to 2(virtual)
         id_map.put_real_to_virt(1, 2);
@@ -88,7 +204,7 @@ mod tests {
     #[test]
     fn put_virt_to_real() {
         // Given: An empty id map.
-        let mut id_map = IdMap::init(1);
+        let mut id_map = IdMap::<usize>::init(1);
 
         // When: Adding a mapping from 1(virtual) to 2(real)
         id_map.put_virt_to_real(0, 1);
@@ -101,7 +217,7 @@ mod tests {
     #[test]
     fn get_real_to_virt() {
         // Given: An empty id map.
-        let mut id_map = IdMap::init(1);
+        let mut id_map = IdMap::<usize>::init(1);
 
         // When: Adding a mapping from 1(real) to 2(virtual).
         id_map.put_real_to_virt(0, 1);
@@ -113,7 +229,7 @@ mod tests {
     #[test]
     fn get_virt_to_real() {
         // Given: An empty id map.
-        let mut id_map = IdMap::init(1);
+        let mut id_map = IdMap::<usize>::init(1);
 
         // When: Adding a mapping between from 1(virtual) to 2(real).
         id_map.put_virt_to_real(0, 1);
@@ -121,4 +237,61 @@ mod tests {
         // Then: Api must return the mapping.
         assert_eq!(id_map.real_id_of(0), 1);
     }
+
+    #[test]
+    fn compact_index_type() {
+        // Given: An empty id map that uses u32 as its index type.
+        let mut id_map = IdMap::<u32>::init(1);
+
+        // When: Adding a mapping between 7(real) and 0(virtual).
+        id_map.put_real_to_virt(7, 0);
+        id_map.put_virt_to_real(0, 7);
+
+        // Then: Api must return the mapping as usize.
+        assert_eq!(id_map.virt_id_of(7), 0);
+        assert_eq!(id_map.real_id_of(0), 7);
+    }
+
+    #[test]
+    fn optional_lookups() {
+        // Given: Id map with a mapping between 3(real) and 0(virtual).
+        let mut id_map = IdMap::<usize>::init(1);
+        id_map.put_real_to_virt(3, 0);
+        id_map.put_virt_to_real(0, 3);
+
+        // Then:
+        assert_eq!(id_map.get_virt_id(3), Some(0));
+        assert_eq!(id_map.get_real_id(0), Some(3));
+        assert_eq!(id_map.get_virt_id(0), None);
+        assert_eq!(id_map.get_real_id(3), None);
+        assert_eq!(id_map.len(), 1);
+        assert!(!id_map.is_empty());
+        assert_eq!(id_map.iter().collect::<Vec<(usize, usize)>>(), vec![(3, 0)]);
+    }
+
+    #[test]
+    fn clones_are_independent() {
+        // Given: Id map with a mapping from 1(real) to 0(virtual), and its clone.
+        let mut id_map = IdMap::<usize>::init(1);
+        id_map.put_real_to_virt(1, 0);
+        let clone = id_map.clone();
+
+        // When: Modifying the original id map.
+        id_map.put_real_to_virt(2, 1);
+
+        // Then: Clone must not change.
+        assert_eq!(id_map.len(), 2);
+        assert_eq!(clone.len(), 1);
+        assert_eq!(clone.get_virt_id(2), None);
+    }
+
+    #[test]
+    fn lookup_beyond_index_type() {
+        let mut id_map = IdMap::<u8>::init(1);
+        id_map.put_real_to_virt(1, 0);
+
+        assert_eq!(id_map.get_virt_id(1), Some(0));
+        assert_eq!(id_map.get_virt_id(256), None);
+        assert_eq!(id_map.get_real_id(usize::MAX), None);
+    }
 }
@@ -1,9 +1,14 @@
+mod dyn_graph;
 mod id_map;
 
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+
 use anyhow::Result;
+pub use dyn_graph::GraphView;
 pub use id_map::IdMap;
 
-use crate::graph::{Edge, EdgeDir};
+use crate::graph::{Edge, EdgeDir, Error};
 
 /// Provides access to neighbors of an arbitrary vertex.
 pub trait Neighbors {
@@ -15,6 +20,48 @@ pub trait Neighbors {
     fn neighbors(&self, src_id: usize) -> Result<Vec<usize>>;
 
     fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize>;
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `k`: Maximum number of hops from the source vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing vertices that are accessible from source vertex using at most `k` edges, mapped to their distance(number of edges) from source vertex.
+    ///   Source vertex itself is included with distance of 0.
+    /// * `Err`: If source vertex does not exist.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|) of the visited part of the graph.
+    fn neighbors_within(&self, src_id: usize, k: usize) -> Result<HashMap<usize, usize>> {
+        self.neighbors(src_id)?;
+
+        Ok(self.neighbors_within_unchecked(src_id, k))
+    }
+
+    fn neighbors_within_unchecked(&self, src_id: usize, k: usize) -> HashMap<usize, usize> {
+        let mut distances = HashMap::new();
+        let mut queue = VecDeque::new();
+
+        distances.insert(src_id, 0);
+        queue.push_back(src_id);
+
+        while let Some(vertex_id) = queue.pop_front() {
+            let distance = distances[&vertex_id];
+
+            if distance == k {
+                continue;
+            }
+
+            for n_id in self.neighbors_unchecked(vertex_id) {
+                if let Entry::Vacant(entry) = distances.entry(n_id) {
+                    entry.insert(distance + 1);
+                    queue.push_back(n_id);
+                }
+            }
+        }
+
+        distances
+    }
 }
 
 /// Provides access to vertices of the graph.
@@ -29,26 +76,20 @@ pub trait Vertices {
         self.vertices().len()
     }
 
-    /// In many algorithms assuming graph vertices have a continuos series of ids makes implementing the algorithms easier.
-    /// So this function maps potentially scattered vertex ids into a continuos one.
-    /// In this mapping, scattered ids are real and continuos ones are virtual.
+    /// In many algorithms assuming graph vertices have a continuous series of ids makes implementing the algorithms easier.
+    /// So this function maps potentially scattered vertex ids into a continuous one.
+    /// In this mapping, scattered ids are real and continuous ones are virtual.
+    /// Virtual ids are assigned in the ascending order of real ids, so the mapping only depends on the set of vertices.
     ///
     /// # Returns
-    /// The two-way mapping between scattered and continuos ids.
-    fn continuos_id_map(&self) -> IdMap {
-        let vertex_count = self.vertex_count();
-
-        let mut id_map = IdMap::init(vertex_count);
-
-        self.vertices()
-            .iter()
-            .enumerate()
-            .for_each(|(virt_id, &real_id)| {
-                id_map.put_virt_to_real(virt_id, real_id);
-                id_map.put_real_to_virt(real_id, virt_id);
-            });
+    /// The two-way mapping between scattered and continuous ids.
+    fn continuous_id_map(&self) -> IdMap {
+        IdMap::continuous(self.vertices())
+    }
 
-        id_map
+    #[deprecated(note = "use `continuous_id_map` instead")]
+    fn continuos_id_map(&self) -> IdMap {
+        self.continuous_id_map()
     }
 
     fn contains_vertex(&self, vertex_id: usize) -> bool;
@@ -117,6 +158,7 @@ pub trait Edges<W, E: Edge<W>> {
 
     /// # Returns
     /// All edges in the graph in the format: (`src_id`, `dst_id`, `edge`).
+    /// Each edge is returned once, and end points of undirected edges are ordered so `src_id` <= `dst_id`.
     fn edges(&self) -> Vec<(usize, usize, &E)>;
 
     /// Difference between this function and `edges` is that this function treats each edge as a directed edge. \
@@ -137,6 +179,24 @@ pub trait Edges<W, E: Edge<W>> {
     fn contains_edge(&self, edge_id: usize) -> bool;
 }
 
+/// Provides direction of the edges of the graph.
+///
+/// Unlike [`Graph`](crate::provide::Graph), it's also implemented by subgraphs.
+/// So algorithms that only read the graph can use it to treat directed and undirected inputs differently.
+pub trait Direction {
+    /// # Returns
+    /// * `true`: If edges are directed.
+    /// * `false`: Otherwise.
+    fn is_directed(&self) -> bool;
+
+    /// # Returns
+    /// * `true`: If edges are undirected.
+    /// * `false`: Otherwise.
+    fn is_undirected(&self) -> bool {
+        !self.is_directed()
+    }
+}
+
 /// Provides basic functionalities to store graph information.
 pub trait Graph<W, E: Edge<W>, Ty: EdgeDir> {
     /// Adds a vertex to the graph.
@@ -153,6 +213,78 @@ pub trait Graph<W, E: Edge<W>, Ty: EdgeDir> {
 
     fn remove_vertex_unchecked(&mut self, vertex_id: usize);
 
+    /// Removes the vertex with id: `vertex_id` from graph, and returns the edges that got removed along with it.
+    ///
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex to be removed.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing the removed edges in the format of: (`src_id`, `dst_id`, `edge`). Checkout [`remove_vertex_collect_unchecked`](crate::provide::Graph::remove_vertex_collect_unchecked).
+    /// * `Err`: [`VertexNotFound`](crate::graph::ErrorKind::VertexNotFound) if vertex with specified id does not exist.
+    fn remove_vertex_collect(&mut self, vertex_id: usize) -> Result<Vec<(usize, usize, E)>>
+    where
+        Self: Vertices + Edges<W, E> + Sized,
+    {
+        if !self.contains_vertex(vertex_id) {
+            Err(Error::new_vnf(vertex_id))?
+        } else {
+            Ok(self.remove_vertex_collect_unchecked(vertex_id))
+        }
+    }
+
+    /// Removes the vertex with id: `vertex_id` from graph, and returns the edges that got removed along with it.
+    ///
+    /// Edges are removed one by one using `remove_edge_unchecked` before the vertex gets removed.
+    /// So for graphs with observers, an event is emitted for each removed edge.
+    ///
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex to be removed.
+    ///
+    /// # Returns
+    /// Edges that were connected to the vertex in the format of: (`src_id`, `dst_id`, `edge`). Like `edges`, each edge is returned once
+    /// and end points of undirected edges are ordered so `src_id` <= `dst_id`. Removed edges can be added back using `add_edges`, but they get new ids.
+    ///
+    /// # Complexity
+    /// O(|E|) for directed graphs, because edges that end at the vertex are found by going through edges of the other vertices.
+    /// For undirected graphs, only edges of the vertex itself are visited.
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::storage::DiMat;
+    /// use prepona::graph::MatGraph;
+    ///
+    /// let mut graph = MatGraph::init(DiMat::<usize>::init());
+    /// let a = graph.add_vertex();
+    /// let b = graph.add_vertex();
+    /// let c = graph.add_vertex();
+    /// graph.add_edge_unchecked(a, b, 1.into());
+    /// graph.add_edge_unchecked(b, c, 2.into());
+    ///
+    /// let removed_edges = graph.remove_vertex_collect_unchecked(b);
+    /// let removed_edges: Vec<(usize, usize, usize)> = removed_edges
+    ///     .into_iter()
+    ///     .map(|(src_id, dst_id, edge)| (src_id, dst_id, edge.get_weight().unwrap()))
+    ///     .collect();
+    ///
+    /// assert_eq!(removed_edges, vec![(a, b, 1), (b, c, 2)]);
+    /// assert_eq!(graph.edges_count(), 0);
+    /// ```
+    fn remove_vertex_collect_unchecked(&mut self, vertex_id: usize) -> Vec<(usize, usize, E)>
+    where
+        Self: Vertices + Edges<W, E> + Sized,
+    {
+        remove_vertex_collect(
+            self,
+            vertex_id,
+            Ty::is_directed(),
+            <Self as Vertices>::vertices,
+            <Self as Edges<W, E>>::edges_from_unchecked,
+            Self::remove_edge_unchecked,
+            Self::remove_vertex_unchecked,
+        )
+    }
+
     /// Adds `edge` from vertex with id `src_id`: to vertex with id: `dst_id`.
     ///
     /// # Arguments
@@ -166,6 +298,35 @@ pub trait Graph<W, E: Edge<W>, Ty: EdgeDir> {
 
     fn add_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge: E) -> usize;
 
+    /// Adds all `edges` to the graph.
+    ///
+    /// # Arguments
+    /// `edges`: Edges to be added in the format of: (`src_id`, `dst_id`, `edge`).
+    ///
+    /// # Returns
+    /// Unique ids of the newly added edges, in the same order as `edges`.
+    fn add_edges<I>(&mut self, edges: I) -> Result<Vec<usize>>
+    where
+        I: IntoIterator<Item = (usize, usize, E)>,
+        Self: Sized,
+    {
+        edges
+            .into_iter()
+            .map(|(src_id, dst_id, edge)| self.add_edge(src_id, dst_id, edge))
+            .collect()
+    }
+
+    fn add_edges_unchecked<I>(&mut self, edges: I) -> Vec<usize>
+    where
+        I: IntoIterator<Item = (usize, usize, E)>,
+        Self: Sized,
+    {
+        edges
+            .into_iter()
+            .map(|(src_id, dst_id, edge)| self.add_edge_unchecked(src_id, dst_id, edge))
+            .collect()
+    }
+
     /// Replaces the edge with id: `edge_id` with `edge`.
     ///
     /// # Arguments
@@ -191,3 +352,62 @@ pub trait Graph<W, E: Edge<W>, Ty: EdgeDir> {
 
     fn remove_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> Option<E>;
 }
+
+// Picks each edge of an undirected graph once out of its `directed_edges`, which contain each edge from both of its end points,
+// and some storages contain each loop twice. End points of the picked edges are ordered so `src_id` <= `dst_id`.
+pub(crate) fn undirected_edges<W, E: Edge<W>>(
+    directed_edges: Vec<(usize, usize, &E)>,
+) -> Vec<(usize, usize, &E)> {
+    let mut visited_edges = HashSet::new();
+
+    directed_edges
+        .into_iter()
+        .filter(|(src_id, dst_id, edge)| src_id <= dst_id && visited_edges.insert(edge.get_id()))
+        .collect()
+}
+
+// Removes the vertex with id: `vertex_id` along with its edges, for `remove_vertex_collect_unchecked` of both `Graph` and `GraphStorage`.
+// Edges are found using `edges_from` of the vertex. For directed graphs `edges_from` of the other vertices is also needed, to find the edges that end at the vertex.
+pub(crate) fn remove_vertex_collect<G: ?Sized, W, E: Edge<W>>(
+    graph: &mut G,
+    vertex_id: usize,
+    is_directed: bool,
+    vertices: impl Fn(&G) -> Vec<usize>,
+    edges_from: impl for<'a> Fn(&'a G, usize) -> Vec<(usize, &'a E)>,
+    mut remove_edge: impl FnMut(&mut G, usize, usize, usize) -> Option<E>,
+    remove_vertex: impl FnOnce(&mut G, usize),
+) -> Vec<(usize, usize, E)> {
+    let src_ids = if is_directed {
+        vertices(graph)
+    } else {
+        vec![vertex_id]
+    };
+
+    // Some storages report each undirected loop twice.
+    let mut visited_edges = HashSet::new();
+    let mut incident_edges = vec![];
+    for src_id in src_ids {
+        for (dst_id, edge) in edges_from(graph, src_id) {
+            if (src_id == vertex_id || dst_id == vertex_id) && visited_edges.insert(edge.get_id()) {
+                let end_points = if is_directed || src_id <= dst_id {
+                    (src_id, dst_id)
+                } else {
+                    (dst_id, src_id)
+                };
+
+                incident_edges.push((end_points, edge.get_id()));
+            }
+        }
+    }
+
+    let removed_edges = incident_edges
+        .into_iter()
+        .filter_map(|((src_id, dst_id), edge_id)| {
+            remove_edge(graph, src_id, dst_id, edge_id).map(|edge| (src_id, dst_id, edge))
+        })
+        .collect();
+
+    remove_vertex(graph, vertex_id);
+
+    removed_edges
+}
@@ -0,0 +1,445 @@
+use anyhow::Result;
+use magnitude::Magnitude;
+use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
+use pyo3::exceptions::{PyIndexError, PyValueError};
+use pyo3::prelude::*;
+
+use crate::algo::centrality::{Betweenness, PageRank};
+use crate::algo::{AStar, Dijkstra};
+use crate::bindings::{with_graph, Inner};
+use crate::graph::{Edge, Error, ErrorKind};
+use crate::provide::{Edges, Graph, Vertices};
+
+/// Graph with non-negative integer weights, exposed to Python as `prepona.Graph`.
+///
+/// Vertices can not be removed from Python, so ids of the vertices are `0..vertex_count`.
+/// Algorithms return NumPy arrays indexed by id of the vertices, and graphs can be built from arrays of edges.
+///
+/// # Examples
+/// ```python
+/// import numpy as np
+/// import prepona
+///
+/// graph = prepona.Graph.from_edges(3, np.array([0, 1]), np.array([1, 2]), np.array([4, 2]))
+/// graph.dijkstra(0)               # array([0., 4., 6.])
+/// graph.connected_components()    # array([0, 0, 0], dtype=uint64)
+/// ```
+#[pyclass(name = "Graph")]
+pub struct PyGraph {
+    inner: Inner<u64>,
+}
+
+#[pymethods]
+impl PyGraph {
+    /// # Arguments
+    /// `directed`: If `true` edges are directed, otherwise they are undirected.
+    ///
+    /// # Returns
+    /// An empty graph.
+    #[new]
+    #[pyo3(signature = (directed = false))]
+    pub fn init(directed: bool) -> Self {
+        let inner = Inner::init(directed);
+
+        PyGraph { inner }
+    }
+
+    /// # Arguments
+    /// * `vertex_count`: Number of vertices of the graph.
+    /// * `src_ids`: Id of the source vertex of each edge.
+    /// * `dst_ids`: Id of the destination vertex of each edge.
+    /// * `weights`: Weight of each edge. If not passed, every edge has weight of one.
+    /// * `directed`: If `true` edges are directed, otherwise they are undirected.
+    ///
+    /// # Returns
+    /// Graph with the specified edges.
+    /// Raises `ValueError` if lengths of the arrays differ, a weight is negative or an edge is not allowed, like a loop.
+    /// Raises `IndexError` if an id is not in range of `0..vertex_count`.
+    #[staticmethod]
+    #[pyo3(signature = (vertex_count, src_ids, dst_ids, weights = None, directed = false))]
+    pub fn from_edges(
+        vertex_count: usize,
+        src_ids: PyReadonlyArray1<i64>,
+        dst_ids: PyReadonlyArray1<i64>,
+        weights: Option<PyReadonlyArray1<i64>>,
+        directed: bool,
+    ) -> PyResult<Self> {
+        let src_ids = src_ids.as_array();
+        let dst_ids = dst_ids.as_array();
+        let weights = weights.as_ref().map(|weights| weights.as_array());
+
+        let edge_count = src_ids.len();
+        if dst_ids.len() != edge_count || weights.is_some_and(|weights| weights.len() != edge_count)
+        {
+            return Err(PyValueError::new_err(
+                "Arrays of edges must have the same length",
+            ));
+        }
+
+        let mut graph = PyGraph::init(directed);
+        for _ in 0..vertex_count {
+            graph.add_vertex();
+        }
+
+        for index in 0..edge_count {
+            let weight = weights.map_or(1, |weights| weights[index]);
+
+            graph.add_edge(
+                to_id(src_ids[index])?,
+                to_id(dst_ids[index])?,
+                to_weight(weight)?,
+            )?;
+        }
+
+        Ok(graph)
+    }
+
+    /// # Arguments
+    /// * `vertex_count`: Number of vertices of the path.
+    /// * `directed`: If `true` edges are directed from each vertex to the next one.
+    ///
+    /// # Returns
+    /// Path where each vertex is connected to the next one by an edge with weight of one.
+    #[staticmethod]
+    #[pyo3(signature = (vertex_count, directed = false))]
+    pub fn path(vertex_count: usize, directed: bool) -> Self {
+        let edges = (1..vertex_count).map(|dst_id| (dst_id - 1, dst_id));
+
+        PyGraph::generate(vertex_count, edges, directed)
+    }
+
+    /// # Arguments
+    /// * `vertex_count`: Number of vertices of the cycle. It must be at least three.
+    /// * `directed`: If `true` edges are directed from each vertex to the next one.
+    ///
+    /// # Returns
+    /// Cycle where each vertex is connected to the next one by an edge with weight of one.
+    /// Raises `ValueError` if `vertex_count` is less than three.
+    #[staticmethod]
+    #[pyo3(signature = (vertex_count, directed = false))]
+    pub fn cycle(vertex_count: usize, directed: bool) -> PyResult<Self> {
+        if vertex_count < 3 {
+            return Err(PyValueError::new_err(
+                "Cycle must have at least three vertices",
+            ));
+        }
+
+        let edges = (0..vertex_count).map(|src_id| (src_id, (src_id + 1) % vertex_count));
+
+        Ok(PyGraph::generate(vertex_count, edges, directed))
+    }
+
+    /// # Arguments
+    /// * `vertex_count`: Number of vertices of the graph.
+    /// * `directed`: If `true` there is an edge in both directions between every two vertices.
+    ///
+    /// # Returns
+    /// Graph where every two vertices are connected by an edge with weight of one.
+    #[staticmethod]
+    #[pyo3(signature = (vertex_count, directed = false))]
+    pub fn complete(vertex_count: usize, directed: bool) -> Self {
+        let edges = (0..vertex_count)
+            .flat_map(|src_id| (0..vertex_count).map(move |dst_id| (src_id, dst_id)))
+            .filter(|(src_id, dst_id)| src_id < dst_id || (directed && src_id != dst_id));
+
+        PyGraph::generate(vertex_count, edges, directed)
+    }
+
+    /// # Arguments
+    /// * `rows`: Number of rows of the grid.
+    /// * `columns`: Number of columns of the grid.
+    ///
+    /// # Returns
+    /// Undirected grid where each vertex is connected to its right and bottom neighbors by an edge with weight of one.
+    /// Id of the vertex at (`row`, `column`) is `row * columns + column`.
+    #[staticmethod]
+    pub fn grid(rows: usize, columns: usize) -> Self {
+        let edges = (0..rows * columns).flat_map(|src_id| {
+            let right = Some(src_id + 1).filter(|_| (src_id + 1) % columns != 0);
+            let bottom = Some(src_id + columns).filter(|dst_id| *dst_id < rows * columns);
+
+            right
+                .into_iter()
+                .chain(bottom)
+                .map(move |dst_id| (src_id, dst_id))
+        });
+
+        PyGraph::generate(rows * columns, edges, false)
+    }
+
+    /// `True` if edges of the graph are directed, `False` otherwise.
+    #[getter]
+    pub fn is_directed(&self) -> bool {
+        self.inner.is_directed()
+    }
+
+    /// # Returns
+    /// Id of the new vertex.
+    pub fn add_vertex(&mut self) -> usize {
+        with_graph!(&mut self.inner, graph => graph.add_vertex())
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    /// * `weight`: Weight of the edge.
+    ///
+    /// # Returns
+    /// Id of the new edge.
+    /// Raises `IndexError` if any of the vertices is not in the graph, and `ValueError` if the edge is not allowed, like a loop.
+    #[pyo3(signature = (src_id, dst_id, weight = 1))]
+    pub fn add_edge(&mut self, src_id: usize, dst_id: usize, weight: u64) -> PyResult<usize> {
+        with_graph!(&mut self.inner, graph => graph.add_edge(src_id, dst_id, weight.into()))
+            .map_err(to_py_error)
+    }
+
+    /// # Returns
+    /// Number of vertices in the graph.
+    pub fn vertex_count(&self) -> usize {
+        with_graph!(&self.inner, graph => graph.vertex_count())
+    }
+
+    /// # Returns
+    /// Number of edges in the graph.
+    pub fn edge_count(&self) -> usize {
+        with_graph!(&self.inner, graph => graph.edges_count())
+    }
+
+    /// # Returns
+    /// Arrays of source ids, destination ids and weights of the edges, in the same format that `from_edges` accepts.
+    pub fn edges<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> (&'py PyArray1<u64>, &'py PyArray1<u64>, &'py PyArray1<u64>) {
+        let edges = with_graph!(&self.inner, graph => {
+            graph
+                .edges()
+                .into_iter()
+                .map(|(src_id, dst_id, edge)| {
+                    let weight = match edge.get_weight() {
+                        Magnitude::Finite(weight) => *weight,
+                        _ => u64::MAX,
+                    };
+
+                    (src_id as u64, dst_id as u64, weight)
+                })
+                .collect::<Vec<(u64, u64, u64)>>()
+        });
+
+        let src_ids = edges.iter().map(|edge| edge.0).collect::<Vec<u64>>();
+        let dst_ids = edges.iter().map(|edge| edge.1).collect::<Vec<u64>>();
+        let weights = edges.iter().map(|edge| edge.2).collect::<Vec<u64>>();
+
+        (
+            src_ids.into_pyarray(py),
+            dst_ids.into_pyarray(py),
+            weights.into_pyarray(py),
+        )
+    }
+
+    /// # Arguments
+    /// `src_id`: Id of the source vertex.
+    ///
+    /// # Returns
+    /// Distance of each vertex from `src_id`, which is `inf` for vertices that are not reachable.
+    /// Distances larger than 2<sup>53</sup> are rounded.
+    /// Raises `IndexError` if `src_id` is not in the graph, and `ValueError` if a distance does not fit in 64 bits.
+    pub fn dijkstra<'py>(&self, py: Python<'py>, src_id: usize) -> PyResult<&'py PyArray1<f64>> {
+        let distances = self.distances(src_id).map_err(to_py_error)?;
+
+        Ok(distances.into_pyarray(py))
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// Ids of the vertices on the shortest path from `src_id` to `dst_id`(including both) and length of the path,
+    /// or `None` if `dst_id` is not reachable from `src_id`.
+    /// Raises `IndexError` if any of the vertices is not in the graph.
+    pub fn shortest_path<'py>(
+        &self,
+        py: Python<'py>,
+        src_id: usize,
+        dst_id: usize,
+    ) -> PyResult<Option<(&'py PyArray1<usize>, u64)>> {
+        let shortest_path = with_graph!(&self.inner, graph => {
+            AStar::init(graph).execute(graph, src_id, dst_id, |_| 0)
+        })
+        .map_err(to_py_error)?;
+
+        Ok(match shortest_path {
+            Some((path, Magnitude::Finite(distance))) => Some((path.into_pyarray(py), distance)),
+            _ => None,
+        })
+    }
+
+    /// Connected components of undirected graphs, and weakly connected components of directed graphs.
+    ///
+    /// # Returns
+    /// Index of the component of each vertex. Indices start from zero.
+    pub fn connected_components<'py>(&self, py: Python<'py>) -> &'py PyArray1<usize> {
+        self.component_labels().into_pyarray(py)
+    }
+
+    /// Betweenness centrality of the vertices, ignoring weights of the edges.
+    ///
+    /// # Returns
+    /// Betweenness of each vertex.
+    pub fn betweenness<'py>(&self, py: Python<'py>) -> &'py PyArray1<f64> {
+        let vertex_betweenness = with_graph!(&self.inner, graph => {
+            Betweenness::init(graph).execute(graph).0
+        });
+
+        self.by_id(|vertex_id| vertex_betweenness[&vertex_id])
+            .into_pyarray(py)
+    }
+
+    /// # Arguments
+    /// `damping`: Probability of following an edge instead of teleporting.
+    ///
+    /// # Returns
+    /// PageRank of each vertex.
+    #[pyo3(signature = (damping = 0.85))]
+    pub fn page_rank<'py>(&self, py: Python<'py>, damping: f64) -> &'py PyArray1<f64> {
+        let ranks = with_graph!(&self.inner, graph => {
+            PageRank::init(graph).with_damping(damping).execute(graph)
+        });
+
+        self.by_id(|vertex_id| ranks[&vertex_id]).into_pyarray(py)
+    }
+}
+
+impl PyGraph {
+    fn generate<I>(vertex_count: usize, edges: I, directed: bool) -> Self
+    where
+        I: Iterator<Item = (usize, usize)>,
+    {
+        let mut graph = PyGraph::init(directed);
+        for _ in 0..vertex_count {
+            graph.add_vertex();
+        }
+        for (src_id, dst_id) in edges {
+            with_graph!(&mut graph.inner, graph => graph.add_edge_unchecked(src_id, dst_id, 1.into()));
+        }
+
+        graph
+    }
+
+    // Values of `value_of` for each vertex, indexed by id of the vertex.
+    fn by_id<T, F: Fn(usize) -> T>(&self, value_of: F) -> Vec<T> {
+        (0..self.vertex_count()).map(value_of).collect()
+    }
+
+    fn distances(&self, src_id: usize) -> Result<Vec<f64>> {
+        with_graph!(&self.inner, graph => {
+            if !graph.contains_vertex(src_id) {
+                Err(Error::new_vnf(src_id))?
+            }
+
+            let sp_subgraph = Dijkstra::init(graph).execute_checked(graph, src_id)?;
+
+            Ok(self.by_id(|vertex_id| match sp_subgraph.distance_to(vertex_id) {
+                Some(Magnitude::Finite(distance)) => distance as f64,
+                _ => f64::INFINITY,
+            }))
+        })
+    }
+
+    fn component_labels(&self) -> Vec<usize> {
+        let components = self.inner.components();
+
+        let mut labels = vec![0; self.vertex_count()];
+        for (label, component) in components.iter().enumerate() {
+            for vertex_id in component {
+                labels[*vertex_id] = label;
+            }
+        }
+
+        labels
+    }
+}
+
+fn to_py_error(error: anyhow::Error) -> PyErr {
+    match error.downcast_ref::<Error>().map(|error| error.kind()) {
+        Some(ErrorKind::VertexNotFound) => PyIndexError::new_err(error.to_string()),
+        _ => PyValueError::new_err(error.to_string()),
+    }
+}
+
+fn to_id(id: i64) -> PyResult<usize> {
+    if id < 0 {
+        Err(PyIndexError::new_err(format!(
+            "Vertex with id: {} does not exist",
+            id
+        )))
+    } else {
+        Ok(id as usize)
+    }
+}
+
+fn to_weight(weight: i64) -> PyResult<u64> {
+    if weight < 0 {
+        Err(PyValueError::new_err(format!(
+            "Weight: {} is negative",
+            weight
+        )))
+    } else {
+        Ok(weight as u64)
+    }
+}
+
+/// Python module of prepona. Build it with [maturin](https://www.maturin.rs) and the `python` feature, then `import prepona`.
+#[pymodule]
+fn prepona(_py: Python, module: &PyModule) -> PyResult<()> {
+    module.add_class::<PyGraph>()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generators() {
+        assert_eq!(PyGraph::path(4, false).edge_count(), 3);
+        assert_eq!(PyGraph::cycle(4, true).unwrap().edge_count(), 4);
+        assert_eq!(PyGraph::complete(4, false).edge_count(), 6);
+        assert_eq!(PyGraph::complete(4, true).edge_count(), 12);
+        assert!(PyGraph::cycle(2, false).is_err());
+
+        let grid = PyGraph::grid(2, 3);
+        assert_eq!(grid.vertex_count(), 6);
+        assert_eq!(grid.edge_count(), 7);
+    }
+
+    #[test]
+    fn distances() {
+        // Given: Path 0 --> 1 --> 2 and an isolated vertex.
+        let mut graph = PyGraph::path(3, true);
+        graph.add_vertex();
+
+        // When:
+        let distances = graph.distances(1).unwrap();
+
+        // Then:
+        assert_eq!(distances, vec![f64::INFINITY, 0.0, 1.0, f64::INFINITY]);
+        assert!(graph.distances(4).is_err());
+    }
+
+    #[test]
+    fn component_labels() {
+        // Given: Grid and an isolated vertex.
+        let mut graph = PyGraph::grid(2, 2);
+        let isolated = graph.add_vertex();
+
+        // When:
+        let labels = graph.component_labels();
+
+        // Then:
+        assert!(labels[..isolated].iter().all(|label| *label == labels[0]));
+        assert_ne!(labels[isolated], labels[0]);
+    }
+}
@@ -1,8 +1,8 @@
-use std::collections::HashSet;
+use std::mem::size_of;
 use std::marker::PhantomData;
 
 use crate::graph::{DefaultEdge, DirectedEdge, Edge, EdgeDir, FlowEdge, UndirectedEdge};
-use crate::storage::GraphStorage;
+use crate::storage::{storage_eq, vec_bytes, GraphStorage, IdAllocator, IndexType, MemoryUsage};
 
 pub type List<W, Dir = UndirectedEdge> = AdjList<W, DefaultEdge<W>, Dir>;
 pub type DiList<W> = AdjList<W, DefaultEdge<W>, DirectedEdge>;
@@ -24,7 +24,7 @@ pub type DiFlowList<W> = AdjList<W, FlowEdge<W>, DirectedEdge>;
 /// * |E<sup>*</sup>|: Means number of edges exiting a vertex(out degree of the vertex).
 ///
 /// ## Space complexity
-/// Space complexity of `AdjList` depends on wether `Dir` is [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge). \
+/// Space complexity of `AdjList` depends on whether `Dir` is [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge). \
 /// * **Directed**: For directed graphs `AdjList` stores |V| + |E| elements.
 /// * **Undirected**: For undirected graphs `AdjList` stores each edge twice so it stores |V| + 2*|E| elements.
 ///
@@ -32,20 +32,34 @@ pub type DiFlowList<W> = AdjList<W, FlowEdge<W>, DirectedEdge>;
 /// * `W`: **W**eight type associated with edges.
 /// * `E`: **E**dge type that graph uses.
 /// * `Dir`: **Dir**ection of edges: [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge).
-pub struct AdjList<W, E: Edge<W>, Dir: EdgeDir = UndirectedEdge> {
-    edges_of: Vec<Vec<(usize, E)>>,
-    reusable_vertex_ids: HashSet<usize>,
-
-    max_edge_id: usize,
-    reusable_edge_ids: HashSet<usize>,
+/// * `Ix`: **I**nde**x** type used to store the destination of each edge. Checkout [`IndexType`](crate::storage::IndexType) for more info.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::graph::{DefaultEdge, UndirectedEdge};
+/// use prepona::storage::AdjList;
+///
+/// // Stores destination of each edge as u32 instead of usize.
+/// let mut list = AdjList::<usize, DefaultEdge<usize>, UndirectedEdge, u32>::init();
+///
+/// let a = list.add_vertex();
+/// let b = list.add_vertex();
+/// list.add_edge_unchecked(a, b, 1.into());
+///
+/// assert_eq!(list.neighbors_unchecked(a), vec![b]);
+/// ```
+pub struct AdjList<W, E: Edge<W>, Dir: EdgeDir = UndirectedEdge, Ix: IndexType = usize> {
+    edges_of: Vec<Vec<(Ix, E)>>,
 
-    vertex_count: usize,
+    vertex_ids: IdAllocator,
+    edge_ids: IdAllocator,
 
     phantom_w: PhantomData<W>,
     phantom_dir: PhantomData<Dir>,
 }
 
-impl<W, E: Edge<W>, Dir: EdgeDir> AdjList<W, E, Dir> {
+impl<W, E: Edge<W>, Dir: EdgeDir, Ix: IndexType> AdjList<W, E, Dir, Ix> {
     /// Initializes an empty adjacency list.
     ///
     /// `AdjList` defines multiple types with different combination of values for generic parameters.
@@ -79,36 +93,74 @@ impl<W, E: Edge<W>, Dir: EdgeDir> AdjList<W, E, Dir> {
     pub fn init() -> Self {
         AdjList {
             edges_of: vec![],
-            reusable_vertex_ids: HashSet::new(),
-
-            max_edge_id: 0,
-            reusable_edge_ids: HashSet::new(),
 
-            vertex_count: 0,
+            vertex_ids: IdAllocator::init(),
+            edge_ids: IdAllocator::init(),
 
             phantom_w: PhantomData,
             phantom_dir: PhantomData,
         }
     }
 
-    fn next_reusable_vertex_id(&mut self) -> Option<usize> {
-        if let Some(id) = self.reusable_vertex_ids.iter().take(1).next().copied() {
-            self.reusable_vertex_ids.remove(&id);
+    /// Initializes an empty adjacency list with enough preallocated memory to store `vertex_count` vertices.
+    ///
+    /// # Arguments
+    /// `vertex_count`: Number of vertices to allocate memory for.
+    ///
+    /// # Returns
+    /// An empty `AdjList` that can store `vertex_count` vertices without reallocating.
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::storage::DiList;
+    ///
+    /// let mut list = DiList::<usize>::with_capacity(2);
+    ///
+    /// let a = list.add_vertex();
+    /// let b = list.add_vertex();
+    ///
+    /// assert_eq!(list.vertex_count(), 2);
+    /// ```
+    pub fn with_capacity(vertex_count: usize) -> Self {
+        let mut list = AdjList::init();
+
+        list.reserve_vertices(vertex_count);
 
-            Some(id)
-        } else {
-            None
-        }
+        list
     }
 
-    fn next_reusable_edge_id(&mut self) -> Option<usize> {
-        if let Some(id) = self.reusable_edge_ids.iter().take(1).next().copied() {
-            self.reusable_edge_ids.remove(&id);
+    /// Reserves memory for at least `additional` more vertices to be added to the storage.
+    ///
+    /// # Arguments
+    /// `additional`: Number of vertices to reserve memory for.
+    ///
+    /// # Complexity
+    /// O(|V|): If reallocation happens.
+    pub fn reserve_vertices(&mut self, additional: usize) {
+        self.edges_of.reserve(additional);
+    }
 
-            Some(id)
-        } else {
-            None
-        }
+    /// # Returns
+    /// Number of vertices that storage can hold without reallocating.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn vertex_capacity(&self) -> usize {
+        self.edges_of.capacity()
+    }
+
+    /// Disables reusing ids of removed vertices and edges, so each id is used at most once during the lifetime of the storage.
+    ///
+    /// Note that the list keeps growing with each vertex that is added, because memory of removed vertices is not reused either.
+    ///
+    /// # Returns
+    /// The storage that never reuses ids.
+    pub fn without_id_reuse(mut self) -> Self {
+        self.vertex_ids.set_reuse(false);
+        self.edge_ids.set_reuse(false);
+
+        self
     }
 
     /// # Returns
@@ -121,24 +173,32 @@ impl<W, E: Edge<W>, Dir: EdgeDir> AdjList<W, E, Dir> {
     }
 }
 
-impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjList<W, E, Dir> {
+impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir, Ix: IndexType> GraphStorage<W, E, Dir>
+    for AdjList<W, E, Dir, Ix>
+{
     /// Adds a vertex to the graph.
     ///
     /// # Returns
     /// Unique id of the newly added vertex.
     ///
+    /// # Panics
+    /// If id of the new vertex can not be represented by `Ix`.
+    ///
     /// # Complexity
     /// O(|1|)
     fn add_vertex(&mut self) -> usize {
-        self.vertex_count += 1;
+        let vertex_id = self.vertex_ids.allocate();
 
-        if let Some(reusable_id) = self.next_reusable_vertex_id() {
-            reusable_id
-        } else {
-            self.edges_of.push(vec![]);
+        if vertex_id == self.edges_of.len() {
+            assert!(
+                vertex_id <= Ix::max_index(),
+                "Vertex id exceeds the maximum value of the index type"
+            );
 
-            self.edges_of.len() - 1
+            self.edges_of.push(vec![]);
         }
+
+        vertex_id
     }
 
     /// Removes the vertex with id: `vertex_id` from graph.
@@ -149,15 +209,28 @@ impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjLi
     /// # Complexity
     /// O(|V| + |V<sup>\*</sup>| * |max(E<sup>\*</sup>)|)
     fn remove_vertex_unchecked(&mut self, vertex_id: usize) {
-        self.edges_of[vertex_id].clear();
+        let mut removed_edge_ids = self.edges_of[vertex_id]
+            .drain(..)
+            .map(|(_, edge)| edge.get_id())
+            .collect::<Vec<usize>>();
 
         for src_id in self.vertices() {
-            self.edges_of[src_id].retain(|(dst_id, _)| *dst_id != vertex_id)
+            self.edges_of[src_id].retain(|(dst_id, edge)| {
+                if dst_id.index() == vertex_id {
+                    removed_edge_ids.push(edge.get_id());
+
+                    false
+                } else {
+                    true
+                }
+            })
         }
 
-        self.vertex_count -= 1;
+        for edge_id in removed_edge_ids {
+            self.edge_ids.release(edge_id);
+        }
 
-        self.reusable_vertex_ids.insert(vertex_id);
+        self.vertex_ids.release(vertex_id);
     }
 
     /// Adds `edge` from vertex with id `src_id`: to vertex with id: `dst_id`.
@@ -173,25 +246,56 @@ impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjLi
     /// # Complexity
     /// O(1)
     fn add_edge_unchecked(&mut self, src_id: usize, dst_id: usize, mut edge: E) -> usize {
-        let edge_id = if let Some(id) = self.next_reusable_edge_id() {
-            id
-        } else {
-            self.max_edge_id += 1;
-
-            self.max_edge_id - 1
-        };
+        let edge_id = self.edge_ids.allocate();
 
         edge.set_id(edge_id);
 
-        self.edges_of[src_id].push((dst_id, edge));
+        self.edges_of[src_id].push((Ix::new(dst_id), edge));
 
         if self.is_undirected() {
-            self.edges_of[dst_id].push((src_id, edge));
+            self.edges_of[dst_id].push((Ix::new(src_id), edge));
         }
 
         edge_id
     }
 
+    /// Adds all `edges` to the storage in one pass.
+    ///
+    /// Edges are counted per vertex beforehand, so each list gets allocated at most once.
+    ///
+    /// # Arguments
+    /// `edges`: Edges to be added in the format of: (`src_id`, `dst_id`, `edge`).
+    ///
+    /// # Returns
+    /// Unique ids of the newly added edges, in the same order as `edges`.
+    ///
+    /// # Complexity
+    /// O(|V| + *number of edges to be added*)
+    fn add_edges_unchecked<I>(&mut self, edges: I) -> Vec<usize>
+    where
+        I: IntoIterator<Item = (usize, usize, E)>,
+    {
+        let edges = edges.into_iter().collect::<Vec<(usize, usize, E)>>();
+
+        let mut additional = vec![0; self.total_vertex_count()];
+        for (src_id, dst_id, _) in &edges {
+            additional[*src_id] += 1;
+
+            if self.is_undirected() {
+                additional[*dst_id] += 1;
+            }
+        }
+
+        for (vertex_id, count) in additional.into_iter().enumerate() {
+            self.edges_of[vertex_id].reserve(count);
+        }
+
+        edges
+            .into_iter()
+            .map(|(src_id, dst_id, edge)| self.add_edge_unchecked(src_id, dst_id, edge))
+            .collect()
+    }
+
     /// Replaces the edge with id: `edge_id` with `edge`.
     ///
     /// # Arguments
@@ -201,12 +305,30 @@ impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjLi
     /// * `edge`: New edge to replace the old one.
     ///
     /// # Complexity
-    /// O(*removing an edge* + *adding an edge*)
+    /// * Directed: O(E<sup>\*</sup><sub>src</sub>)
+    /// * Undirected: O(E<sup>\*</sup><sub>src</sub> + E<sup>\*</sup><sub>dst</sub>)
     fn update_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize, mut edge: E) {
-        if let Some(removed_edge) = self.remove_edge_unchecked(src_id, dst_id, edge_id) {
-            edge.set_id(removed_edge.get_id());
+        if !self.edges_of[src_id]
+            .iter()
+            .any(|(_, edge)| edge.get_id() == edge_id)
+        {
+            return;
+        }
+
+        edge.set_id(edge_id);
+
+        let mut vertex_ids = vec![src_id];
+        if self.is_undirected() && src_id != dst_id {
+            vertex_ids.push(dst_id);
+        }
 
-            self.add_edge_unchecked(src_id, dst_id, edge);
+        for vertex_id in vertex_ids {
+            for (_, old_edge) in self.edges_of[vertex_id]
+                .iter_mut()
+                .filter(|(_, old_edge)| old_edge.get_id() == edge_id)
+            {
+                *old_edge = edge;
+            }
         }
     }
 
@@ -229,13 +351,15 @@ impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjLi
             .iter()
             .position(|(_, edge)| edge.get_id() == edge_id)
         {
-            self.reusable_edge_ids.insert(edge_id);
+            self.edge_ids.release(edge_id);
 
+            // Removed before the copy in list of `dst_id`, because for loops both copies are in the same list.
+            let (_, edge) = self.edges_of[src_id].remove(index);
             if self.is_undirected() {
                 self.edges_of[dst_id].retain(|(_, edge)| edge.get_id() != edge_id);
             }
 
-            Some(self.edges_of[src_id].remove(index).1)
+            Some(edge)
         } else {
             None
         }
@@ -247,11 +371,11 @@ impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjLi
     /// # Complexity
     /// O(1)
     fn vertex_count(&self) -> usize {
-        self.vertex_count
+        self.vertex_ids.count()
     }
 
     fn edge_count(&self) -> usize {
-        self.max_edge_id - self.reusable_edge_ids.len()
+        self.edge_ids.count()
     }
 
     /// # Returns
@@ -260,9 +384,7 @@ impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjLi
     /// # Complexity
     /// O(|V|)
     fn vertices(&self) -> Vec<usize> {
-        (0..self.edges_of.len())
-            .filter(|vertex_id| !self.reusable_vertex_ids.contains(vertex_id))
-            .collect()
+        self.vertex_ids.iter().collect()
     }
 
     /// # Arguments
@@ -276,16 +398,40 @@ impl<W: Copy, E: Edge<W> + Copy, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjLi
     fn edges_from_unchecked(&self, src_id: usize) -> Vec<(usize, &E)> {
         self.edges_of[src_id]
             .iter()
-            .map(|(dst_id, edge)| (*dst_id, edge))
+            .map(|(dst_id, edge)| (dst_id.index(), edge))
             .collect()
     }
 
     fn contains_vertex(&self, vertex_id: usize) -> bool {
-        vertex_id < self.total_vertex_count() && !self.reusable_vertex_ids.contains(&vertex_id)
+        self.vertex_ids.is_allocated(vertex_id)
     }
 
     fn contains_edge(&self, edge_id: usize) -> bool {
-        edge_id < self.max_edge_id && !self.reusable_edge_ids.contains(&edge_id)
+        self.edge_ids.is_allocated(edge_id)
+    }
+
+    /// Destination ids of the edges are part of the edge structure, so choosing a smaller `Ix` reduces it.
+    ///
+    /// # Complexity
+    /// O(|V|)
+    fn memory_usage(&self) -> MemoryUsage {
+        let payloads: usize = self.edges_of.iter().map(|edges| edges.len() * size_of::<E>()).sum();
+        let lists: usize = self.edges_of.iter().map(vec_bytes).sum();
+
+        MemoryUsage::init(
+            vec_bytes(&self.edges_of) + lists - payloads,
+            self.vertex_ids.memory_usage() + self.edge_ids.memory_usage(),
+            payloads,
+        )
+    }
+}
+
+/// Two lists are equal if they contain vertices with the same ids, and edges with the same ids, end points and values.
+impl<W: Copy, E: Edge<W> + Copy + PartialEq, Dir: EdgeDir, Ix: IndexType> PartialEq
+    for AdjList<W, E, Dir, Ix>
+{
+    fn eq(&self, other: &Self) -> bool {
+        storage_eq(self, other)
     }
 }
 
@@ -304,7 +450,7 @@ mod tests {
         assert_eq!(list.edges().len(), 0);
         assert_eq!(list.vertex_count(), 0);
         assert_eq!(list.edges_of.len(), 0);
-        assert_eq!(list.reusable_vertex_ids.len(), 0);
+        assert_eq!(list.vertex_ids.released_count(), 0);
         assert_eq!(list.is_directed(), true);
     }
 
@@ -319,7 +465,7 @@ mod tests {
         assert_eq!(list.edges().len(), 0);
         assert_eq!(list.vertex_count(), 0);
         assert_eq!(list.edges_of.len(), 0);
-        assert_eq!(list.reusable_vertex_ids.len(), 0);
+        assert_eq!(list.vertex_ids.released_count(), 0);
         assert_eq!(list.is_directed(), false);
     }
 
@@ -338,7 +484,7 @@ mod tests {
         assert_eq!(list.vertex_count(), 3);
         assert_eq!(list.edges_of.len(), 3);
         assert!(list.edges_of.iter().all(|edges| edges.is_empty()));
-        assert_eq!(list.reusable_vertex_ids.len(), 0);
+        assert_eq!(list.vertex_ids.released_count(), 0);
 
         assert_eq!(list.vertices().len(), 3);
         assert!(vec![a, b, c]
@@ -367,7 +513,7 @@ mod tests {
         assert_eq!(list.edges().len(), 0);
         assert_eq!(list.vertex_count(), 3);
         assert_eq!(list.edges_of.len(), 3);
-        assert_eq!(list.reusable_vertex_ids.len(), 0);
+        assert_eq!(list.vertex_ids.released_count(), 0);
 
         assert_eq!(list.vertices().len(), 3);
         assert!(vec![a, b, c]
@@ -403,10 +549,10 @@ mod tests {
         assert_eq!(list.edges_of.len(), 3);
 
         // Vertices a and b must be reusable.
-        assert_eq!(list.reusable_vertex_ids.len(), 2);
+        assert_eq!(list.vertex_ids.released_count(), 2);
         assert!(vec![a, b]
             .iter()
-            .all(|vertex_id| list.reusable_vertex_ids.contains(vertex_id)));
+            .all(|vertex_id| !list.vertex_ids.is_allocated(*vertex_id)));
 
         // list must only contain c.
         assert_eq!(list.vertices().len(), 1);
@@ -436,10 +582,10 @@ mod tests {
         assert_eq!(list.edges_of.len(), 3);
 
         // Vertices a and b must be reusable.
-        assert_eq!(list.reusable_vertex_ids.len(), 2);
+        assert_eq!(list.vertex_ids.released_count(), 2);
         assert!(vec![a, b]
             .iter()
-            .all(|vertex_id| list.reusable_vertex_ids.contains(vertex_id)));
+            .all(|vertex_id| !list.vertex_ids.is_allocated(*vertex_id)));
 
         // list must only contain c.
         assert_eq!(list.vertices().len(), 1);
@@ -471,7 +617,7 @@ mod tests {
         assert_eq!(list.edges_of.len(), 3);
 
         // There must be no reusable id.
-        assert_eq!(list.reusable_vertex_ids.len(), 0);
+        assert_eq!(list.vertex_ids.released_count(), 0);
 
         // Vertex ids a and b must be reused.
         assert_eq!(list.vertices().len(), 3);
@@ -510,7 +656,7 @@ mod tests {
         assert_eq!(list.edges_of.len(), 3);
 
         // There must be no reusable id.
-        assert_eq!(list.reusable_vertex_ids.len(), 0);
+        assert_eq!(list.vertex_ids.released_count(), 0);
 
         // Vertex ids a and b must be reused.
         assert_eq!(list.vertices().len(), 3);
@@ -872,6 +1018,58 @@ mod tests {
             .all(|vertex_id| list.neighbors_unchecked(c).contains(vertex_id)));
     }
 
+    #[test]
+    fn undirected_loop_is_reported_once() {
+        // Given: Undirected list
+        //
+        //      a  ---  b
+        //      |  |
+        //      '--'
+        //
+        let mut list = List::<usize>::init();
+        let a = list.add_vertex();
+        let b = list.add_vertex();
+        list.add_edge_unchecked(a, a, 1.into());
+        list.add_edge_unchecked(a, b, 2.into());
+
+        // When: Doing nothing.
+
+        // Then: Loop is stored twice in list of a, but each edge is reported once.
+        assert_eq!(list.edges_of[a].len(), 3);
+        assert_eq!(list.edges().len(), 2);
+        assert_eq!(list.edge_count(), 2);
+    }
+
+    #[test]
+    fn remove_vertex_collect_with_loop() {
+        // Given: Undirected list
+        //
+        //      a  ---  b  ---  c
+        //      |  |
+        //      '--'
+        //
+        let mut list = List::<usize>::init();
+        let a = list.add_vertex();
+        let b = list.add_vertex();
+        let c = list.add_vertex();
+        let aa = list.add_edge_unchecked(a, a, 1.into());
+        let ab = list.add_edge_unchecked(a, b, 2.into());
+        list.add_edge_unchecked(b, c, 3.into());
+
+        // When: Removing a.
+        let removed_edges = list.remove_vertex_collect(a).unwrap();
+
+        // Then: Each edge of a is returned once, and the edge between b and c is kept.
+        let mut removed_ids = removed_edges
+            .iter()
+            .map(|(src_id, dst_id, edge)| (*src_id, *dst_id, edge.get_id()))
+            .collect::<Vec<(usize, usize, usize)>>();
+        removed_ids.sort_unstable();
+        assert_eq!(removed_ids, vec![(a, a, aa), (a, b, ab)]);
+        assert_eq!(list.edge_count(), 1);
+        assert_eq!(list.neighbors_unchecked(b), vec![c]);
+    }
+
     // #[test]
     // #[should_panic(expected = "Vertex with id: 0 is not present in the graph")]
     // fn first_vertex_not_present() {
@@ -941,4 +1139,146 @@ mod tests {
 
     //     // Then: Code should panic.
     // }
+
+    #[test]
+    fn with_capacity() {
+        // Given: A directed list with capacity for 3 vertices.
+        let mut list = DiList::<usize>::with_capacity(3);
+        let capacity = list.vertex_capacity();
+
+        // When: Adding 3 vertices.
+        for _ in 0..3 {
+            list.add_vertex();
+        }
+
+        // Then: List must not get reallocated.
+        assert!(capacity >= 3);
+        assert_eq!(list.vertex_capacity(), capacity);
+        assert_eq!(list.vertex_count(), 3);
+    }
+
+    #[test]
+    fn reserve_vertices() {
+        // Given: Undirected list
+        //
+        //      a   b
+        //
+        let mut list = List::<usize>::init();
+        list.add_vertex();
+        list.add_vertex();
+
+        // When: Reserving memory for 2 more vertices.
+        list.reserve_vertices(2);
+
+        // Then:
+        assert!(list.vertex_capacity() >= 4);
+    }
+
+    #[test]
+    fn add_edges() {
+        // Given: Undirected list
+        //
+        //      a   b   c
+        //
+        let mut list = List::<usize>::init();
+        let a = list.add_vertex();
+        let b = list.add_vertex();
+        let c = list.add_vertex();
+
+        // When: Adding edges
+        //
+        //      a  ---  b  ---  c
+        //      |               |
+        //      '----------------
+        //
+        let edge_ids = list.add_edges_unchecked(vec![(a, b, 1.into()), (b, c, 2.into()), (c, a, 3.into())]);
+
+        // Then:
+        assert_eq!(edge_ids.len(), 3);
+        assert_eq!(list.edges().len(), 3);
+        assert_eq!(list.edges_from_unchecked(a).len(), 2);
+        assert_eq!(list.edges_from_unchecked(b).len(), 2);
+        assert_eq!(list.edges_from_unchecked(c).len(), 2);
+        assert_eq!(list.edge_between_unchecked(c, a, edge_ids[2]).unwrap().get_weight().unwrap(), 3);
+    }
+
+    #[test]
+    fn add_edges_with_invalid_vertex() {
+        // Given: Directed list
+        //
+        //      a   b
+        //
+        let mut list = DiList::<usize>::init();
+        let a = list.add_vertex();
+        let b = list.add_vertex();
+
+        // When: Adding edges that one of them ends in a vertex that does not exist.
+        let res = list.add_edges(vec![(a, b, 1.into()), (b, 2, 2.into())]);
+
+        // Then: No edge must be added.
+        assert!(res.is_err());
+        assert_eq!(list.edges().len(), 0);
+    }
+
+    #[test]
+    fn without_id_reuse() {
+        // Given: Undirected list that never reuses ids.
+        //
+        //      a  --(1)--  b  --(2)--  c
+        //
+        let mut list = List::<usize>::init().without_id_reuse();
+        let a = list.add_vertex();
+        let b = list.add_vertex();
+        let c = list.add_vertex();
+        let ab = list.add_edge_unchecked(a, b, 1.into());
+        let bc = list.add_edge_unchecked(b, c, 2.into());
+
+        // When: Removing a and updating edge between b and c.
+        list.remove_vertex_unchecked(a);
+        list.update_edge_unchecked(c, b, bc, 3.into());
+
+        // Then: Updated edge keeps its id.
+        assert_eq!(list.edge_between_unchecked(b, c, bc).unwrap().get_weight().unwrap(), 3);
+        assert_eq!(list.edge_between_unchecked(c, b, bc).unwrap().get_weight().unwrap(), 3);
+
+        // And: Ids of a and edge between a and b are not used again.
+        let d = list.add_vertex();
+        let bd = list.add_edge_unchecked(b, d, 4.into());
+        assert_eq!(d, 3);
+        assert_eq!(bd, 2);
+        assert!(!list.contains_vertex(a));
+        assert!(!list.contains_edge(ab));
+        assert_eq!(list.vertices(), vec![b, c, d]);
+        assert_eq!(list.total_vertex_count(), 4);
+    }
+
+    #[test]
+    fn memory_usage() {
+        // Given: Directed and undirected lists with two vertices and an edge between them.
+        let mut di_list = DiList::<usize>::init();
+        let (a, b) = (di_list.add_vertex(), di_list.add_vertex());
+        di_list.add_edge_unchecked(a, b, 1.into());
+
+        let mut list = List::<usize>::init();
+        let (a, b) = (list.add_vertex(), list.add_vertex());
+        list.add_edge_unchecked(a, b, 1.into());
+
+        // When: Computing memory usage.
+        let di_usage = di_list.memory_usage();
+        let usage = list.memory_usage();
+
+        // Then: Undirected list stores the edge once for each end point.
+        let edge_size = size_of::<DefaultEdge<usize>>();
+        assert_eq!(di_usage.payloads(), edge_size);
+        assert_eq!(usage.payloads(), 2 * edge_size);
+        assert!(di_usage.edge_structure() >= 2 * size_of::<Vec<(usize, DefaultEdge<usize>)>>());
+        assert_eq!(di_usage.id_sets(), 0);
+
+        // When: Removing a vertex.
+        list.remove_vertex_unchecked(a);
+
+        // Then: Released ids of the vertex and its edge are counted as part of the id sets.
+        assert_eq!(list.memory_usage().id_sets(), 2 * size_of::<usize>());
+        assert_eq!(list.memory_usage().payloads(), 0);
+    }
 }
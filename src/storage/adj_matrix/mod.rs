@@ -1,11 +1,14 @@
-mod utils;
+mod transpose;
+pub(super) mod utils;
 
 use std::any::Any;
-use std::collections::HashSet;
 use std::marker::PhantomData;
+use std::mem::size_of;
 
-use crate::graph::{DefaultEdge, DirectedEdge, Edge, EdgeDir, FlowEdge, UndirectedEdge};
-use crate::storage::GraphStorage;
+use crate::graph::{impl_pretty, DefaultEdge, DirectedEdge, Edge, EdgeDir, FlowEdge, UndirectedEdge};
+use crate::storage::{storage_eq, vec_bytes, GraphStorage, IdAllocator, MemoryUsage};
+
+pub use transpose::TransposeView;
 
 pub type Mat<W, Dir = UndirectedEdge> = AdjMatrix<W, DefaultEdge<W>, Dir>;
 pub type DiMat<W> = AdjMatrix<W, DefaultEdge<W>, DirectedEdge>;
@@ -27,7 +30,7 @@ pub type DiFlowMat<W> = AdjMatrix<W, FlowEdge<W>, DirectedEdge>;
 /// * |E<sup>*</sup>|: Means number of edges exiting a vertex(out degree of the vertex).
 ///
 /// ## Space complexity
-/// Space complexity of `AdjMatrix` depends on wether `Dir` is [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge). \
+/// Space complexity of `AdjMatrix` depends on whether `Dir` is [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge). \
 /// * **Directed**: For directed graphs `AdjMatrix` stores matrix with |V|<sup>2</sup> + |E| elements.
 /// * **Undirected**: For undirected graphs `AdjMatrix` stores a lower triangle matrix with (|V|<sup>2</sup> + |V|)/2 + |E| elements.
 ///
@@ -38,12 +41,8 @@ pub type DiFlowMat<W> = AdjMatrix<W, FlowEdge<W>, DirectedEdge>;
 pub struct AdjMatrix<W, E: Edge<W>, Dir: EdgeDir = UndirectedEdge> {
     vec: Vec<Vec<E>>,
 
-    reusable_vertex_ids: HashSet<usize>,
-
-    max_edge_id: usize,
-    reusable_edge_ids: HashSet<usize>,
-
-    vertex_count: usize,
+    vertex_ids: IdAllocator,
+    edge_ids: IdAllocator,
 
     phantom_w: PhantomData<W>,
     phantom_dir: PhantomData<Dir>,
@@ -77,45 +76,288 @@ impl<W, E: Edge<W>, Dir: EdgeDir> AdjMatrix<W, E, Dir> {
     pub fn init() -> Self {
         AdjMatrix {
             vec: vec![],
-            reusable_vertex_ids: HashSet::new(),
 
-            max_edge_id: 0,
-            reusable_edge_ids: HashSet::new(),
-
-            vertex_count: 0,
+            vertex_ids: IdAllocator::init(),
+            edge_ids: IdAllocator::init(),
 
             phantom_w: PhantomData,
             phantom_dir: PhantomData,
         }
     }
 
-    fn next_reusable_vertex_id(&mut self) -> Option<usize> {
-        if let Some(id) = self.reusable_vertex_ids.iter().take(1).next().copied() {
-            self.reusable_vertex_ids.remove(&id);
+    /// Initializes an empty adjacency matrix with enough preallocated memory to store `vertex_count` vertices.
+    ///
+    /// Adding vertices to an `AdjMatrix` grows the underlying matrix by a whole row and column each time.
+    /// So if you know the number of vertices beforehand, using this function prevents the matrix from being reallocated repeatedly.
+    ///
+    /// # Arguments
+    /// `vertex_count`: Number of vertices to allocate memory for.
+    ///
+    /// # Returns
+    /// An empty `AdjMatrix` that can store `vertex_count` vertices without reallocating.
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::storage::Mat;
+    ///
+    /// let mut mat = Mat::<usize>::with_capacity(3);
+    ///
+    /// let a = mat.add_vertex();
+    /// let b = mat.add_vertex();
+    /// let c = mat.add_vertex();
+    ///
+    /// assert_eq!(mat.vertex_count(), 3);
+    /// ```
+    pub fn with_capacity(vertex_count: usize) -> Self {
+        let mut mat = AdjMatrix::init();
+
+        mat.reserve_vertices(vertex_count);
+
+        mat
+    }
+
+    /// Reserves memory for at least `additional` more vertices to be added to the storage.
+    ///
+    /// # Arguments
+    /// `additional`: Number of vertices to reserve memory for.
+    ///
+    /// # Complexity
+    /// O(|V|<sup>2</sup>): If reallocation happens.
+    pub fn reserve_vertices(&mut self, additional: usize) {
+        let required_slots = utils::slots_for(self.total_vertex_count() + additional, Dir::is_directed());
+
+        self.vec.reserve(required_slots.saturating_sub(self.vec.len()));
+    }
+
+    /// # Returns
+    /// Number of vertices that storage can hold without reallocating.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn vertex_capacity(&self) -> usize {
+        let slots = self.vec.capacity();
+
+        // Largest n such that slots_for(n) <= slots.
+        let mut vertex_count = (slots as f64).sqrt() as usize;
+        while utils::slots_for(vertex_count + 1, Dir::is_directed()) <= slots {
+            vertex_count += 1;
+        }
+        while utils::slots_for(vertex_count, Dir::is_directed()) > slots {
+            vertex_count -= 1;
+        }
+
+        vertex_count
+    }
+
+    /// Disables reusing ids of removed vertices and edges, so each id is used at most once during the lifetime of the storage.
+    ///
+    /// Note that the matrix keeps growing with each vertex that is added, because memory of removed vertices is not reused either.
+    ///
+    /// # Returns
+    /// The storage that never reuses ids.
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::storage::Mat;
+    ///
+    /// let mut mat = Mat::<usize>::init().without_id_reuse();
+    /// let a = mat.add_vertex();
+    /// mat.remove_vertex(a).unwrap();
+    ///
+    /// assert_ne!(mat.add_vertex(), a);
+    /// ```
+    pub fn without_id_reuse(mut self) -> Self {
+        self.vertex_ids.set_reuse(false);
+        self.edge_ids.set_reuse(false);
+
+        self
+    }
+
+    /// # Returns
+    /// Total number of vertices in the storage(|V|).
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn total_vertex_count(&self) -> usize {
+        self.vertex_ids.total_count()
+    }
+
+    fn has_vertex(&self, vertex_id: usize) -> bool {
+        self.vertex_ids.is_allocated(vertex_id)
+    }
+
+    /// Non-panicking version of indexing the matrix with (`src_id`, `dst_id`).
+    ///
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing edges from vertex with id: `src_id` to vertex with id: `dst_id`.
+    /// * `None`: If vertex with either id: `src_id` or `dst_id` does not exist.
+    ///
+    /// # Complexity
+    /// O(1)
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::storage::DiMat;
+    ///
+    /// let mut mat = DiMat::<usize>::init();
+    /// let a = mat.add_vertex();
+    /// let b = mat.add_vertex();
+    /// mat.add_edge_unchecked(a, b, 1.into());
+    ///
+    /// assert_eq!(mat.get(a, b).unwrap().len(), 1);
+    /// assert!(mat.get(b, a).unwrap().is_empty());
+    /// assert!(mat.get(a, b + 1).is_none());
+    /// ```
+    pub fn get(&self, src_id: usize, dst_id: usize) -> Option<&Vec<E>> {
+        if self.has_vertex(src_id) && self.has_vertex(dst_id) {
+            let index = utils::from_ij(src_id, dst_id, Dir::is_directed());
 
-            Some(id)
+            self.vec.get(index)
         } else {
             None
         }
     }
 
-    fn next_reusable_edge_id(&mut self) -> Option<usize> {
-        if let Some(id) = self.reusable_edge_ids.iter().take(1).next().copied() {
-            self.reusable_edge_ids.remove(&id);
+    /// Non-panicking version of mutably indexing the matrix with (`src_id`, `dst_id`).
+    ///
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing edges from vertex with id: `src_id` to vertex with id: `dst_id`.
+    /// * `None`: If vertex with either id: `src_id` or `dst_id` does not exist.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn get_mut(&mut self, src_id: usize, dst_id: usize) -> Option<&mut Vec<E>> {
+        if self.has_vertex(src_id) && self.has_vertex(dst_id) {
+            let index = utils::from_ij(src_id, dst_id, Dir::is_directed());
 
-            Some(id)
+            self.vec.get_mut(index)
         } else {
             None
         }
     }
 
+    /// # Arguments
+    /// `src_id`: Id of the source vertex.
+    ///
     /// # Returns
-    /// Total number of vertices in the storage(|V|).
+    /// Iterator over the row of `src_id` in the format of: (`dst_id`, edges from `src_id` to `dst_id`), in ascending order of `dst_id`.
+    /// Cells of every vertex are visited, including the ones without any edge.
+    ///
+    /// # Complexity
+    /// O(|V|) to iterate over the whole row.
+    ///
+    /// # Panics
+    /// If there is no vertex with id: `src_id`.
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::storage::DiMat;
+    ///
+    /// let mut mat = DiMat::<usize>::init();
+    /// let a = mat.add_vertex();
+    /// let b = mat.add_vertex();
+    /// let c = mat.add_vertex();
+    /// mat.add_edge_unchecked(a, c, 2.into());
+    ///
+    /// let row_sizes: Vec<(usize, usize)> = mat.row(a).map(|(dst_id, edges)| (dst_id, edges.len())).collect();
+    /// assert_eq!(row_sizes, vec![(a, 0), (b, 0), (c, 1)]);
+    /// ```
+    pub fn row(&self, src_id: usize) -> impl Iterator<Item = (usize, &Vec<E>)> + '_ {
+        if !self.has_vertex(src_id) {
+            panic!("Vertex with id: {} does not exist", src_id);
+        }
+
+        self.vertex_ids.iter().map(move |dst_id| {
+            let index = utils::from_ij(src_id, dst_id, Dir::is_directed());
+
+            (dst_id, &self.vec[index])
+        })
+    }
+
+    /// # Arguments
+    /// `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// Iterator over the column of `dst_id` in the format of: (`src_id`, edges from `src_id` to `dst_id`), in ascending order of `src_id`.
+    /// Cells of every vertex are visited, including the ones without any edge.
+    /// For undirected matrices the column of a vertex is the same as its row.
+    ///
+    /// # Complexity
+    /// O(|V|) to iterate over the whole column.
+    ///
+    /// # Panics
+    /// If there is no vertex with id: `dst_id`.
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::storage::DiMat;
+    ///
+    /// let mut mat = DiMat::<usize>::init();
+    /// let a = mat.add_vertex();
+    /// let b = mat.add_vertex();
+    /// let c = mat.add_vertex();
+    /// mat.add_edge_unchecked(a, c, 2.into());
+    /// mat.add_edge_unchecked(b, c, 3.into());
+    ///
+    /// // Sum of the weights of edges entering c.
+    /// let in_weight: usize = mat
+    ///     .column(c)
+    ///     .flat_map(|(_, edges)| edges.iter())
+    ///     .map(|edge| edge.get_weight().unwrap())
+    ///     .sum();
+    /// assert_eq!(in_weight, 5);
+    /// ```
+    pub fn column(&self, dst_id: usize) -> impl Iterator<Item = (usize, &Vec<E>)> + '_ {
+        if !self.has_vertex(dst_id) {
+            panic!("Vertex with id: {} does not exist", dst_id);
+        }
+
+        self.vertex_ids.iter().map(move |src_id| {
+            let index = utils::from_ij(src_id, dst_id, Dir::is_directed());
+
+            (src_id, &self.vec[index])
+        })
+    }
+}
+
+impl<W, E: Edge<W>> AdjMatrix<W, E, DirectedEdge> {
+    /// Transposing an undirected matrix results in the same matrix, so this function is only provided for directed matrices.
+    ///
+    /// # Returns
+    /// A view of the matrix in which the direction of every edge is reversed. No edge is copied.
     ///
     /// # Complexity
     /// O(1)
-    pub fn total_vertex_count(&self) -> usize {
-        self.vertex_count + self.reusable_vertex_ids.len()
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::storage::DiMat;
+    ///
+    /// let mut mat = DiMat::<usize>::init();
+    /// let a = mat.add_vertex();
+    /// let b = mat.add_vertex();
+    /// mat.add_edge_unchecked(a, b, 1.into());
+    ///
+    /// let transposed = mat.transpose_view();
+    /// assert_eq!(transposed[(b, a)].len(), 1);
+    /// assert!(transposed[(a, b)].is_empty());
+    /// ```
+    pub fn transpose_view(&self) -> TransposeView<'_, W, E> {
+        TransposeView::init(self)
     }
 }
 
@@ -128,23 +370,14 @@ impl<W: Any, E: Edge<W>, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjMatrix<W,
     /// # Complexity
     /// O(|V|)
     fn add_vertex(&mut self) -> usize {
-        if let Some(reusable_id) = self.next_reusable_vertex_id() {
-            self.vertex_count += 1;
-
-            reusable_id
-        } else {
-            let new_size = if self.is_directed() {
-                self.vec.len() + 2 * self.total_vertex_count() + 1
-            } else {
-                self.vec.len() + self.total_vertex_count() + 1
-            };
+        let vertex_id = self.vertex_ids.allocate();
 
+        let new_size = utils::slots_for(self.total_vertex_count(), Dir::is_directed());
+        if self.vec.len() < new_size {
             self.vec.resize_with(new_size, || vec![]);
-
-            self.vertex_count += 1;
-
-            self.vertex_count - 1
         }
+
+        vertex_id
     }
 
     /// Removes the vertex with id: `vertex_id` from graph.
@@ -155,14 +388,26 @@ impl<W: Any, E: Edge<W>, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjMatrix<W,
     /// # Complexity
     /// O(|V|)
     fn remove_vertex_unchecked(&mut self, vertex_id: usize) {
+        if !self.has_vertex(vertex_id) {
+            return;
+        }
+
+        let mut removed_edge_ids = vec![];
+
         for other_id in 0..self.total_vertex_count() {
-            self[(vertex_id, other_id)].clear();
-            self[(other_id, vertex_id)].clear();
+            if let Some(edges) = self.get_mut(vertex_id, other_id) {
+                removed_edge_ids.extend(edges.drain(..).map(|edge| edge.get_id()));
+            }
+            if let Some(edges) = self.get_mut(other_id, vertex_id) {
+                removed_edge_ids.extend(edges.drain(..).map(|edge| edge.get_id()));
+            }
         }
 
-        self.reusable_vertex_ids.insert(vertex_id);
+        for edge_id in removed_edge_ids {
+            self.edge_ids.release(edge_id);
+        }
 
-        self.vertex_count -= 1;
+        self.vertex_ids.release(vertex_id);
     }
 
     /// Adds `edge` from vertex with id `src_id`: to vertex with id: `dst_id`.
@@ -175,16 +420,13 @@ impl<W: Any, E: Edge<W>, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjMatrix<W,
     /// # Returns
     /// Unique id of the newly added edge.
     ///
+    /// # Panics
+    /// If vertex with either id: `src_id` or `dst_id` does not exist. Use `add_edge` to get an `Err` instead.
+    ///
     /// # Complexity
     /// O(1)
     fn add_edge_unchecked(&mut self, src_id: usize, dst_id: usize, mut edge: E) -> usize {
-        let edge_id = if let Some(id) = self.next_reusable_edge_id() {
-            id
-        } else {
-            self.max_edge_id += 1;
-
-            self.max_edge_id - 1
-        };
+        let edge_id = self.edge_ids.allocate();
 
         edge.set_id(edge_id);
 
@@ -204,12 +446,11 @@ impl<W: Any, E: Edge<W>, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjMatrix<W,
     /// # Complexity
     /// O(E<sup>*</sup>)
     fn update_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize, mut edge: E) {
-        if let Some(index) = self[(src_id, dst_id)]
-            .iter()
-            .position(|edge| edge.get_id() == edge_id)
-        {
-            edge.set_id(edge_id);
-            self[(src_id, dst_id)][index] = edge;
+        if let Some(edges) = self.get_mut(src_id, dst_id) {
+            if let Some(index) = edges.iter().position(|edge| edge.get_id() == edge_id) {
+                edge.set_id(edge_id);
+                edges[index] = edge;
+            }
         }
     }
 
@@ -227,16 +468,13 @@ impl<W: Any, E: Edge<W>, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjMatrix<W,
     /// # Complexity
     /// O(E<sup>*</sup>)
     fn remove_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> Option<E> {
-        if let Some(index) = self[(src_id, dst_id)]
-            .iter()
-            .position(|edge| edge.get_id() == edge_id)
-        {
-            self.reusable_edge_ids.insert(edge_id);
+        let edges = self.get_mut(src_id, dst_id)?;
+        let index = edges.iter().position(|edge| edge.get_id() == edge_id)?;
+        let edge = edges.swap_remove(index);
 
-            Some(self[(src_id, dst_id)].swap_remove(index))
-        } else {
-            None
-        }
+        self.edge_ids.release(edge_id);
+
+        Some(edge)
     }
 
     /// # Returns
@@ -245,11 +483,11 @@ impl<W: Any, E: Edge<W>, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjMatrix<W,
     /// # Complexity
     /// O(1)
     fn vertex_count(&self) -> usize {
-        self.vertex_count
+        self.vertex_ids.count()
     }
 
     fn edge_count(&self) -> usize {
-        self.max_edge_id - self.reusable_edge_ids.len()
+        self.edge_ids.count()
     }
 
     /// # Returns
@@ -258,10 +496,7 @@ impl<W: Any, E: Edge<W>, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjMatrix<W,
     /// # Complexity
     /// O(|V|)
     fn vertices(&self) -> Vec<usize> {
-        (0..self.total_vertex_count())
-            .into_iter()
-            .filter(|v_id| !self.reusable_vertex_ids.contains(v_id))
-            .collect()
+        self.vertex_ids.iter().collect()
     }
 
     /// # Arguments
@@ -295,7 +530,10 @@ impl<W: Any, E: Edge<W>, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjMatrix<W,
     fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
         (0..self.total_vertex_count())
             .into_iter()
-            .filter(|dst_id| !self[(src_id, *dst_id)].is_empty())
+            .filter(|dst_id| {
+                self.get(src_id, *dst_id)
+                    .map_or(false, |edges| !edges.is_empty())
+            })
             .collect()
     }
 
@@ -309,18 +547,41 @@ impl<W: Any, E: Edge<W>, Dir: EdgeDir> GraphStorage<W, E, Dir> for AdjMatrix<W,
     /// # Complexity
     /// O(|E<sup>*</sup>|)
     fn edges_between_unchecked(&self, src_id: usize, dst_id: usize) -> Vec<&E> {
-        self[(src_id, dst_id)].iter().collect()
+        self.get(src_id, dst_id)
+            .map(|edges| edges.iter().collect())
+            .unwrap_or_default()
     }
 
     fn contains_vertex(&self, vertex_id: usize) -> bool {
-        vertex_id < self.total_vertex_count() && !self.reusable_vertex_ids.contains(&vertex_id)
+        self.has_vertex(vertex_id)
     }
 
     fn contains_edge(&self, edge_id: usize) -> bool {
-        edge_id < self.max_edge_id && !self.reusable_edge_ids.contains(&edge_id)
+        self.edge_ids.is_allocated(edge_id)
+    }
+
+    /// Every cell of the matrix is part of the edge structure, even if there is no edge between its end points.
+    ///
+    /// # Complexity
+    /// O(|V|<sup>2</sup>)
+    fn memory_usage(&self) -> MemoryUsage {
+        let payloads: usize = self.vec.iter().map(|edges| edges.len() * size_of::<E>()).sum();
+        let cells: usize = self.vec.iter().map(vec_bytes).sum();
+
+        MemoryUsage::init(
+            vec_bytes(&self.vec) + cells - payloads,
+            self.vertex_ids.memory_usage() + self.edge_ids.memory_usage(),
+            payloads,
+        )
     }
 }
 
+impl_pretty!(
+    "AdjMatrix",
+    [W, E, Dir] AdjMatrix<W, E, Dir>,
+    where W: Any, E: Edge<W>, Dir: EdgeDir
+);
+
 use std::ops::{Index, IndexMut};
 impl<W: Any, E: Edge<W>, Dir: EdgeDir> Index<(usize, usize)> for AdjMatrix<W, E, Dir> {
     type Output = Vec<E>;
@@ -330,6 +591,9 @@ impl<W: Any, E: Edge<W>, Dir: EdgeDir> Index<(usize, usize)> for AdjMatrix<W, E,
     ///
     /// # Returns
     /// Edges from vertex with id: `src_id` to vertex with id: `dst_id`.
+    ///
+    /// # Panics
+    /// If (`src_id`, `dst_id`) is out of bounds. Use [`get`](crate::storage::AdjMatrix::get) for a non-panicking version.
     fn index(&self, (src_id, dst_id): (usize, usize)) -> &Self::Output {
         let index = utils::from_ij(src_id, dst_id, self.is_directed());
 
@@ -343,6 +607,9 @@ impl<W: Any, E: Edge<W>, Dir: EdgeDir> IndexMut<(usize, usize)> for AdjMatrix<W,
     ///
     /// # Returns
     /// Edges from vertex with id: `src_id` to vertex with id: `dst_id`.
+    ///
+    /// # Panics
+    /// If (`src_id`, `dst_id`) is out of bounds. Use [`get_mut`](crate::storage::AdjMatrix::get_mut) for a non-panicking version.
     fn index_mut(&mut self, (src_id, dst_id): (usize, usize)) -> &mut Self::Output {
         let index = utils::from_ij(src_id, dst_id, self.is_directed());
 
@@ -350,6 +617,13 @@ impl<W: Any, E: Edge<W>, Dir: EdgeDir> IndexMut<(usize, usize)> for AdjMatrix<W,
     }
 }
 
+/// Two matrices are equal if they contain vertices with the same ids, and edges with the same ids, end points and values.
+impl<W: Any, E: Edge<W> + PartialEq, Dir: EdgeDir> PartialEq for AdjMatrix<W, E, Dir> {
+    fn eq(&self, other: &Self) -> bool {
+        storage_eq(self, other)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -366,7 +640,7 @@ mod tests {
         assert_eq!(matrix.vertex_count(), 0);
         assert_eq!(matrix.total_vertex_count(), 0);
         assert_eq!(matrix.vec.len(), 0);
-        assert_eq!(matrix.reusable_vertex_ids.len(), 0);
+        assert_eq!(matrix.vertex_ids.released_count(), 0);
         assert_eq!(matrix.is_directed(), true);
     }
 
@@ -382,7 +656,7 @@ mod tests {
         assert_eq!(matrix.vertex_count(), 0);
         assert_eq!(matrix.total_vertex_count(), 0);
         assert_eq!(matrix.vec.len(), 0);
-        assert_eq!(matrix.reusable_vertex_ids.len(), 0);
+        assert_eq!(matrix.vertex_ids.released_count(), 0);
         assert_eq!(matrix.is_directed(), false);
     }
 
@@ -401,7 +675,7 @@ mod tests {
         assert_eq!(matrix.vertex_count(), 3);
         assert_eq!(matrix.total_vertex_count(), 3);
         assert_eq!(matrix.vec.len(), 9);
-        assert_eq!(matrix.reusable_vertex_ids.len(), 0);
+        assert_eq!(matrix.vertex_ids.released_count(), 0);
 
         assert_eq!(matrix.vertices().len(), 3);
         assert!(vec![a, b, c]
@@ -431,7 +705,7 @@ mod tests {
         assert_eq!(matrix.vertex_count(), 3);
         assert_eq!(matrix.total_vertex_count(), 3);
         assert_eq!(matrix.vec.len(), 6);
-        assert_eq!(matrix.reusable_vertex_ids.len(), 0);
+        assert_eq!(matrix.vertex_ids.released_count(), 0);
 
         assert_eq!(matrix.vertices().len(), 3);
         assert!(vec![a, b, c]
@@ -468,10 +742,10 @@ mod tests {
         assert_eq!(matrix.vec.len(), 9);
 
         // Vertices a and b must be reusable.
-        assert_eq!(matrix.reusable_vertex_ids.len(), 2);
+        assert_eq!(matrix.vertex_ids.released_count(), 2);
         assert!(vec![a, b]
             .iter()
-            .all(|vertex_id| matrix.reusable_vertex_ids.contains(vertex_id)));
+            .all(|vertex_id| !matrix.vertex_ids.is_allocated(*vertex_id)));
 
         // Matrix must only contain c.
         assert_eq!(matrix.vertices().len(), 1);
@@ -502,10 +776,10 @@ mod tests {
         assert_eq!(matrix.vec.len(), 6);
 
         // Vertices a and b must be reusable.
-        assert_eq!(matrix.reusable_vertex_ids.len(), 2);
+        assert_eq!(matrix.vertex_ids.released_count(), 2);
         assert!(vec![a, b]
             .iter()
-            .all(|vertex_id| matrix.reusable_vertex_ids.contains(vertex_id)));
+            .all(|vertex_id| !matrix.vertex_ids.is_allocated(*vertex_id)));
 
         // Matrix must only contain c.
         assert_eq!(matrix.vertices().len(), 1);
@@ -538,7 +812,7 @@ mod tests {
         assert_eq!(matrix.vec.len(), 9);
 
         // There must be no reusable id.
-        assert_eq!(matrix.reusable_vertex_ids.len(), 0);
+        assert_eq!(matrix.vertex_ids.released_count(), 0);
 
         // Vertex ids a and b must be reused.
         assert_eq!(matrix.vertices().len(), 3);
@@ -578,7 +852,7 @@ mod tests {
         assert_eq!(matrix.vec.len(), 6);
 
         // There must be no reusable id.
-        assert_eq!(matrix.reusable_vertex_ids.len(), 0);
+        assert_eq!(matrix.vertex_ids.released_count(), 0);
 
         // Vertex ids a and b must be reused.
         assert_eq!(matrix.vertices().len(), 3);
@@ -934,4 +1208,191 @@ mod tests {
             .iter()
             .all(|vertex_id| matrix.neighbors_unchecked(c).contains(vertex_id)));
     }
+
+    #[test]
+    fn directed_with_capacity() {
+        // Given: A directed matrix with capacity for 3 vertices.
+        let mut matrix = DiMat::<usize>::with_capacity(3);
+        let capacity = matrix.vec.capacity();
+
+        // When: Adding 3 vertices.
+        for _ in 0..3 {
+            matrix.add_vertex();
+        }
+
+        // Then: Matrix must not get reallocated.
+        assert!(capacity >= 9);
+        assert_eq!(matrix.vec.capacity(), capacity);
+        assert!(matrix.vertex_capacity() >= 3);
+        assert_eq!(matrix.vertex_count(), 3);
+    }
+
+    #[test]
+    fn undirected_with_capacity() {
+        // Given: An undirected matrix with capacity for 3 vertices.
+        let mut matrix = Mat::<usize>::with_capacity(3);
+        let capacity = matrix.vec.capacity();
+
+        // When: Adding 3 vertices.
+        for _ in 0..3 {
+            matrix.add_vertex();
+        }
+
+        // Then: Matrix must not get reallocated.
+        assert!(capacity >= 6);
+        assert_eq!(matrix.vec.capacity(), capacity);
+        assert!(matrix.vertex_capacity() >= 3);
+        assert_eq!(matrix.vertex_count(), 3);
+    }
+
+    #[test]
+    fn reserve_vertices() {
+        // Given: Directed matrix
+        //
+        //      a   b
+        //
+        let mut matrix = DiMat::<usize>::init();
+        matrix.add_vertex();
+        matrix.add_vertex();
+
+        // When: Reserving memory for 2 more vertices.
+        matrix.reserve_vertices(2);
+        let capacity = matrix.vec.capacity();
+        matrix.add_vertex();
+        matrix.add_vertex();
+
+        // Then: Matrix must not get reallocated.
+        assert!(capacity >= 16);
+        assert_eq!(matrix.vec.capacity(), capacity);
+        assert_eq!(matrix.vec.len(), 16);
+    }
+
+    #[test]
+    fn get_with_missing_vertices() {
+        // Given: Directed matrix
+        //
+        //      a  -->  b   c
+        //
+        let mut matrix = DiMat::<usize>::init();
+        let a = matrix.add_vertex();
+        let b = matrix.add_vertex();
+        let c = matrix.add_vertex();
+        matrix.add_edge_unchecked(a, b, 1.into());
+
+        // When: Removing vertex c.
+        matrix.remove_vertex_unchecked(c);
+
+        // Then:
+        assert_eq!(matrix.get(a, b).unwrap().len(), 1);
+        assert!(matrix.get_mut(b, a).unwrap().is_empty());
+        assert!(matrix.get(a, c).is_none());
+        assert!(matrix.get_mut(c, a).is_none());
+        assert!(matrix.get(a, c + 1).is_none());
+    }
+
+    #[test]
+    fn unchecked_functions_with_missing_vertices() {
+        // Given: Undirected matrix
+        //
+        //      a  ---  b
+        //
+        let mut matrix = Mat::<usize>::init();
+        let a = matrix.add_vertex();
+        let b = matrix.add_vertex();
+        let ab = matrix.add_edge_unchecked(a, b, 1.into());
+
+        // When: Using ids that do not exist.
+        let missing_id = b + 1;
+
+        // Then: Nothing must panic.
+        assert!(matrix.remove_edge_unchecked(a, missing_id, ab).is_none());
+        matrix.update_edge_unchecked(missing_id, a, ab, 2.into());
+        assert!(matrix.edges_between_unchecked(a, missing_id).is_empty());
+        matrix.remove_vertex_unchecked(missing_id);
+        assert_eq!(matrix.vertex_count(), 2);
+        assert_eq!(matrix.edges_between_unchecked(a, b)[0].get_weight().unwrap(), 1);
+    }
+
+    #[test]
+    fn remove_vertex_collect() {
+        // Given: Undirected matrix
+        //
+        //      a  --(1)--  b  --(2)--  c
+        //
+        let mut matrix = Mat::<usize>::init();
+        let a = matrix.add_vertex();
+        let b = matrix.add_vertex();
+        let c = matrix.add_vertex();
+        let ab = matrix.add_edge_unchecked(a, b, 1.into());
+        let bc = matrix.add_edge_unchecked(b, c, 2.into());
+
+        // When: Removing b.
+        let removed_edges = matrix.remove_vertex_collect(b).unwrap();
+
+        // Then: Each edge must be returned once.
+        let mut removed_edges = removed_edges
+            .into_iter()
+            .map(|(src_id, dst_id, edge)| (src_id.min(dst_id), src_id.max(dst_id), edge.get_id()))
+            .collect::<Vec<(usize, usize, usize)>>();
+        removed_edges.sort_unstable();
+        assert_eq!(removed_edges, vec![(a, b, ab), (b, c, bc)]);
+        assert_eq!(matrix.vertex_count(), 2);
+        assert_eq!(matrix.edge_count(), 0);
+        assert!(matrix.remove_vertex_collect(b).is_err());
+    }
+
+    #[test]
+    fn memory_usage() {
+        // Given: Directed matrix with ten vertices and an edge.
+        let mut matrix = DiMat::<usize>::init();
+        for _ in 0..10 {
+            matrix.add_vertex();
+        }
+        matrix.add_edge_unchecked(0, 1, 1.into());
+
+        // When: Computing memory usage.
+        let usage = matrix.memory_usage();
+
+        // Then: There is a cell for each pair of vertices, but only one edge.
+        assert_eq!(usage.payloads(), size_of::<DefaultEdge<usize>>());
+        assert!(usage.edge_structure() >= 100 * size_of::<Vec<DefaultEdge<usize>>>());
+        assert_eq!(usage.id_sets(), 0);
+        assert_eq!(usage.total(), usage.edge_structure() + usage.payloads());
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        // Given: Undirected matrix with a removed vertex.
+        //
+        //      a  ---  b      d
+        //      |              |
+        //      '--------------'
+        //
+        let mut matrix = Mat::<usize>::init();
+        let a = matrix.add_vertex();
+        let b = matrix.add_vertex();
+        let c = matrix.add_vertex();
+        let d = matrix.add_vertex();
+        matrix.remove_vertex_unchecked(c);
+        matrix.add_edge_unchecked(a, b, 1.into());
+        matrix.add_edge_unchecked(a, d, 2.into());
+
+        // When: Iterating over the row and column of a.
+        let row: Vec<(usize, usize)> = matrix.row(a).map(|(id, edges)| (id, edges.len())).collect();
+        let column: Vec<(usize, usize)> = matrix.column(a).map(|(id, edges)| (id, edges.len())).collect();
+
+        // Then: Both contain the cells of present vertices only, and are the same.
+        assert_eq!(row, vec![(a, 0), (b, 1), (d, 1)]);
+        assert_eq!(row, column);
+    }
+
+    #[test]
+    #[should_panic]
+    fn row_of_removed_vertex() {
+        let mut matrix = DiMat::<usize>::init();
+        let a = matrix.add_vertex();
+        matrix.remove_vertex_unchecked(a);
+
+        let _ = matrix.row(a).count();
+    }
 }
@@ -0,0 +1,145 @@
+use std::ops::Index;
+
+use crate::graph::{DirectedEdge, Edge};
+use crate::storage::AdjMatrix;
+
+/// Read-only view of a directed [`AdjMatrix`](crate::storage::AdjMatrix) in which the direction of every edge is reversed.
+///
+/// Cell (`src_id`, `dst_id`) of the view is cell (`dst_id`, `src_id`) of the matrix, so rows of the view are columns of the matrix and vice versa.
+/// The view only borrows the matrix and does not copy any edge. It can be created using [`transpose_view`](crate::storage::AdjMatrix::transpose_view).
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that matrix uses.
+pub struct TransposeView<'a, W, E: Edge<W>> {
+    matrix: &'a AdjMatrix<W, E, DirectedEdge>,
+}
+
+impl<'a, W, E: Edge<W>> TransposeView<'a, W, E> {
+    pub(super) fn init(matrix: &'a AdjMatrix<W, E, DirectedEdge>) -> Self {
+        TransposeView { matrix }
+    }
+
+    /// # Returns
+    /// The matrix that is being viewed.
+    pub fn matrix(&self) -> &'a AdjMatrix<W, E, DirectedEdge> {
+        self.matrix
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex in the view.
+    /// * `dst_id`: Id of the destination vertex in the view.
+    ///
+    /// # Returns
+    /// * `Some`: Containing edges from vertex with id: `dst_id` to vertex with id: `src_id` in the matrix.
+    /// * `None`: If vertex with either id: `src_id` or `dst_id` does not exist.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn get(&self, src_id: usize, dst_id: usize) -> Option<&'a Vec<E>> {
+        self.matrix.get(dst_id, src_id)
+    }
+
+    /// # Arguments
+    /// `src_id`: Id of the source vertex in the view.
+    ///
+    /// # Returns
+    /// Iterator over the row of `src_id` in the view, which is the column of `src_id` in the matrix.
+    /// Checkout [`column`](crate::storage::AdjMatrix::column).
+    ///
+    /// # Panics
+    /// If there is no vertex with id: `src_id`.
+    pub fn row(&self, src_id: usize) -> impl Iterator<Item = (usize, &'a Vec<E>)> + 'a {
+        self.matrix.column(src_id)
+    }
+
+    /// # Arguments
+    /// `dst_id`: Id of the destination vertex in the view.
+    ///
+    /// # Returns
+    /// Iterator over the column of `dst_id` in the view, which is the row of `dst_id` in the matrix.
+    /// Checkout [`row`](crate::storage::AdjMatrix::row).
+    ///
+    /// # Panics
+    /// If there is no vertex with id: `dst_id`.
+    pub fn column(&self, dst_id: usize) -> impl Iterator<Item = (usize, &'a Vec<E>)> + 'a {
+        self.matrix.row(dst_id)
+    }
+}
+
+impl<'a, W, E: Edge<W>> Index<(usize, usize)> for TransposeView<'a, W, E> {
+    type Output = Vec<E>;
+
+    /// # Arguments
+    /// * (`src_id`, `dst_id`): (Id of the source vertex in the view, Id of the destination vertex in the view).
+    ///
+    /// # Returns
+    /// Edges from vertex with id: `dst_id` to vertex with id: `src_id` in the matrix.
+    ///
+    /// # Panics
+    /// If there is no vertex with id: `src_id` or `dst_id`. Use [`get`](crate::storage::TransposeView::get) for a non-panicking version.
+    fn index(&self, (src_id, dst_id): (usize, usize)) -> &Self::Output {
+        match self.get(src_id, dst_id) {
+            Some(edges) => edges,
+            None => panic!("Vertex with id: {} or {} does not exist", src_id, dst_id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::Edge;
+    use crate::storage::{DiMat, GraphStorage};
+
+    #[test]
+    fn rows_and_columns_are_swapped() {
+        // Given: Matrix with a removed vertex.
+        //
+        //      a  -->  b  -->  d
+        //      ^               |
+        //      '---------------'
+        //
+        let mut mat = DiMat::<usize>::init();
+        let a = mat.add_vertex();
+        let b = mat.add_vertex();
+        let c = mat.add_vertex();
+        let d = mat.add_vertex();
+        mat.remove_vertex_unchecked(c);
+        mat.add_edge_unchecked(a, b, 1.into());
+        mat.add_edge_unchecked(b, d, 2.into());
+        mat.add_edge_unchecked(d, a, 3.into());
+
+        // When: Transposing the matrix.
+        let transposed = mat.transpose_view();
+
+        // Then:
+        let row_of = |view_row: Vec<(usize, &Vec<_>)>| -> Vec<(usize, usize)> {
+            view_row
+                .into_iter()
+                .map(|(id, edges)| (id, edges.len()))
+                .collect()
+        };
+        assert_eq!(
+            row_of(transposed.row(a).collect()),
+            vec![(a, 0), (b, 0), (d, 1)]
+        );
+        assert_eq!(
+            row_of(transposed.column(a).collect()),
+            vec![(a, 0), (b, 1), (d, 0)]
+        );
+        assert_eq!(transposed[(a, d)][0].get_weight().unwrap(), 3);
+        assert!(transposed.get(a, c).is_none());
+        assert_eq!(transposed.matrix().vertex_count(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_of_removed_vertex() {
+        let mut mat = DiMat::<usize>::init();
+        let a = mat.add_vertex();
+        let b = mat.add_vertex();
+        mat.remove_vertex_unchecked(b);
+
+        let _ = &mat.transpose_view()[(a, b)];
+    }
+}
@@ -60,3 +60,18 @@ pub fn from_ij(mut i: usize, mut j: usize, is_directed: bool) -> usize {
         (i * (i + 1) >> 1) + j
     }
 }
+
+// Number of slots in the flat vector that are needed to store a matrix with `vertex_count` vertices.
+//
+// Directed edges:
+//      A full square matrix is stored so |V|^2 slots are needed.
+//
+// Undirected edges:
+//      Only the lower triangle(including the diagonal) is stored so (|V|^2 + |V|)/2 slots are needed.
+pub fn slots_for(vertex_count: usize, is_directed: bool) -> usize {
+    if is_directed {
+        vertex_count * vertex_count
+    } else {
+        (vertex_count * (vertex_count + 1)) >> 1
+    }
+}
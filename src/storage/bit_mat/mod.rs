@@ -0,0 +1,671 @@
+use std::marker::PhantomData;
+
+use anyhow::Result;
+
+use crate::graph::{DirectedEdge, EdgeDir, UndirectedEdge};
+use crate::provide::{Neighbors, Vertices};
+use crate::storage::adj_matrix::utils;
+use crate::storage::{vec_bytes, Error, IdAllocator, MemoryUsage};
+
+const BITS_PER_BLOCK: usize = 64;
+
+pub type BitMat = BitMatrix<UndirectedEdge>;
+pub type DiBitMat = BitMatrix<DirectedEdge>;
+
+/// Is an adjacency matrix for unweighted graphs that uses a single bit to store each pair of vertices.
+///
+/// Unlike other storages, `BitMatrix` does not store any edge. It only stores whether two vertices are adjacent or not.
+/// Therefore it does not implement [`GraphStorage`](crate::storage::GraphStorage) and can not be used as storage of a graph.
+/// Instead it implements [`Vertices`](crate::provide::Vertices) and [`Neighbors`](crate::provide::Neighbors),
+/// So it can directly be passed to algorithms that only depend on these traits, like [`Bfs`](crate::algo::Bfs) and [`Dfs`](crate::algo::Dfs).
+///
+/// ## Note
+/// From now on
+/// * |V|: Means total number of vertices that are stored in the storage.
+/// Just like [`AdjMatrix`](crate::storage::AdjMatrix), memory allocated for a removed vertex will be reused when adding a new vertex.
+/// You can retrieve the amount of |V| using `total_vertex_count` function.
+///
+/// ## Space complexity
+/// * **Directed**: |V|<sup>2</sup> bits.
+/// * **Undirected**: (|V|<sup>2</sup> + |V|)/2 bits.
+///
+/// ## Generic Parameters
+/// * `Dir`: **Dir**ection of edges: [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge).
+pub struct BitMatrix<Dir: EdgeDir = UndirectedEdge> {
+    blocks: Vec<u64>,
+
+    vertex_ids: IdAllocator,
+
+    edge_count: usize,
+
+    phantom_dir: PhantomData<Dir>,
+}
+
+impl<Dir: EdgeDir> BitMatrix<Dir> {
+    /// Initializes an empty bit matrix.
+    ///
+    /// `BitMatrix` defines multiple types with different combination of values for generic parameters.
+    /// These types are:
+    /// * [`BitMat`](crate::storage::BitMat): A bit matrix that stores [`undirected`](crate::graph::UndirectedEdge) edges.
+    /// * [`DiBitMat`](crate::storage::DiBitMat): A bit matrix that stores [`directed`](crate::graph::DirectedEdge) edges.
+    ///
+    /// # Returns
+    /// An empty `BitMatrix`.
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::storage::{BitMat, DiBitMat};
+    ///
+    /// // To store an undirected unweighted graph
+    /// let mat = BitMat::init();
+    ///
+    /// // To store a directed unweighted graph
+    /// let di_mat = DiBitMat::init();
+    /// ```
+    pub fn init() -> Self {
+        BitMatrix {
+            blocks: vec![],
+
+            vertex_ids: IdAllocator::init(),
+
+            edge_count: 0,
+
+            phantom_dir: PhantomData,
+        }
+    }
+
+    /// Disables reusing ids of removed vertices, so each id is used at most once during the lifetime of the storage.
+    ///
+    /// Note that the matrix keeps growing with each vertex that is added, because memory of removed vertices is not reused either.
+    ///
+    /// # Returns
+    /// The storage that never reuses ids.
+    pub fn without_id_reuse(mut self) -> Self {
+        self.vertex_ids.set_reuse(false);
+
+        self
+    }
+
+    fn bit(&self, src_id: usize, dst_id: usize) -> bool {
+        let index = utils::from_ij(src_id, dst_id, Dir::is_directed());
+
+        self.blocks[index / BITS_PER_BLOCK] & (1 << (index % BITS_PER_BLOCK)) != 0
+    }
+
+    // Sets the bit corresponding to (`src_id`, `dst_id`) to `value` and returns the previous value of the bit.
+    fn set_bit(&mut self, src_id: usize, dst_id: usize, value: bool) -> bool {
+        let index = utils::from_ij(src_id, dst_id, Dir::is_directed());
+        let mask = 1 << (index % BITS_PER_BLOCK);
+        let block = &mut self.blocks[index / BITS_PER_BLOCK];
+
+        let prev_value = *block & mask != 0;
+
+        if value {
+            *block |= mask;
+        } else {
+            *block &= !mask;
+        }
+
+        prev_value
+    }
+
+    /// # Returns
+    /// Total number of vertices in the storage(|V|).
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn total_vertex_count(&self) -> usize {
+        self.vertex_ids.total_count()
+    }
+
+    /// Edges are single bits without any payload, so only the edge structure and id sets take memory.
+    ///
+    /// # Returns
+    /// Number of bytes allocated by the matrix. Checkout [`MemoryUsage`](crate::storage::MemoryUsage) for what is and is not counted.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage::init(vec_bytes(&self.blocks), self.vertex_ids.memory_usage(), 0)
+    }
+
+    /// # Returns
+    /// * `true`: If edges are directed.
+    /// * `false`: Otherwise.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn is_directed(&self) -> bool {
+        Dir::is_directed()
+    }
+
+    /// # Returns
+    /// Number of edges in the storage.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Adds a vertex to the storage.
+    ///
+    /// # Returns
+    /// Unique id of the newly added vertex.
+    ///
+    /// # Complexity
+    /// O(|V|)
+    pub fn add_vertex(&mut self) -> usize {
+        let vertex_id = self.vertex_ids.allocate();
+
+        let slots = utils::slots_for(self.total_vertex_count(), Dir::is_directed());
+        let required_blocks = slots.div_ceil(BITS_PER_BLOCK);
+        if self.blocks.len() < required_blocks {
+            self.blocks.resize(required_blocks, 0);
+        }
+
+        vertex_id
+    }
+
+    /// Removes the vertex with id: `vertex_id` from storage.
+    ///
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex to be removed.
+    ///
+    /// # Returns
+    /// * `Ok`: If vertex removed successfully.
+    /// * `Err`: [`VertexNotFound`](crate::storage::ErrorKind::VertexNotFound) if vertex with specified id does not exist.
+    ///
+    /// # Complexity
+    /// O(|V|)
+    pub fn remove_vertex(&mut self, vertex_id: usize) -> Result<()> {
+        if !self.contains_vertex(vertex_id) {
+            Err(Error::new_vnf(vertex_id))?
+        } else {
+            Ok(self.remove_vertex_unchecked(vertex_id))
+        }
+    }
+
+    /// Removes the vertex with id: `vertex_id` from storage.
+    ///
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex to be removed.
+    ///
+    /// # Panics
+    /// If `vertex_id` is out of bounds.
+    ///
+    /// # Complexity
+    /// O(|V|)
+    pub fn remove_vertex_unchecked(&mut self, vertex_id: usize) {
+        for other_id in 0..self.total_vertex_count() {
+            if self.set_bit(vertex_id, other_id, false) {
+                self.edge_count -= 1;
+            }
+
+            if Dir::is_directed() && other_id != vertex_id && self.set_bit(other_id, vertex_id, false) {
+                self.edge_count -= 1;
+            }
+        }
+
+        self.vertex_ids.release(vertex_id);
+    }
+
+    /// Adds an edge from vertex with id: `src_id` to vertex with id: `dst_id`.
+    ///
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing `true` if edge did not already exist and `false` otherwise.
+    /// * `Err`: [`VertexNotFound`](crate::storage::ErrorKind::VertexNotFound) if vertex with either id: `src_id` or `dst_id` does not exist.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn add_edge(&mut self, src_id: usize, dst_id: usize) -> Result<bool> {
+        if !self.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else if !self.contains_vertex(dst_id) {
+            Err(Error::new_vnf(dst_id))?
+        } else {
+            Ok(self.add_edge_unchecked(src_id, dst_id))
+        }
+    }
+
+    /// Adds an edge from vertex with id: `src_id` to vertex with id: `dst_id`.
+    ///
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `true`: If edge did not already exist.
+    /// * `false`: Otherwise.
+    ///
+    /// # Panics
+    /// If `src_id` or `dst_id` is out of bounds.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn add_edge_unchecked(&mut self, src_id: usize, dst_id: usize) -> bool {
+        let is_new = !self.set_bit(src_id, dst_id, true);
+
+        if is_new {
+            self.edge_count += 1;
+        }
+
+        is_new
+    }
+
+    /// Removes the edge from vertex with id: `src_id` to vertex with id: `dst_id`.
+    ///
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing `true` if edge existed and `false` otherwise.
+    /// * `Err`: [`VertexNotFound`](crate::storage::ErrorKind::VertexNotFound) if vertex with either id: `src_id` or `dst_id` does not exist.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn remove_edge(&mut self, src_id: usize, dst_id: usize) -> Result<bool> {
+        if !self.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else if !self.contains_vertex(dst_id) {
+            Err(Error::new_vnf(dst_id))?
+        } else {
+            Ok(self.remove_edge_unchecked(src_id, dst_id))
+        }
+    }
+
+    /// Removes the edge from vertex with id: `src_id` to vertex with id: `dst_id`.
+    ///
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `true`: If edge existed.
+    /// * `false`: Otherwise.
+    ///
+    /// # Panics
+    /// If `src_id` or `dst_id` is out of bounds.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn remove_edge_unchecked(&mut self, src_id: usize, dst_id: usize) -> bool {
+        let existed = self.set_bit(src_id, dst_id, false);
+
+        if existed {
+            self.edge_count -= 1;
+        }
+
+        existed
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing `true` if there is an edge from source to destination and `false` otherwise.
+    /// * `Err`: [`VertexNotFound`](crate::storage::ErrorKind::VertexNotFound) if vertex with either id: `src_id` or `dst_id` does not exist.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn has_edge(&self, src_id: usize, dst_id: usize) -> Result<bool> {
+        if !self.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else if !self.contains_vertex(dst_id) {
+            Err(Error::new_vnf(dst_id))?
+        } else {
+            Ok(self.has_edge_unchecked(src_id, dst_id))
+        }
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// * `true`: If there is an edge from source to destination.
+    /// * `false`: Otherwise.
+    ///
+    /// # Panics
+    /// If `src_id` or `dst_id` is out of bounds.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn has_edge_unchecked(&self, src_id: usize, dst_id: usize) -> bool {
+        self.bit(src_id, dst_id)
+    }
+
+    /// # Returns
+    /// All edges in the storage in the format: (`src_id`, `dst_id`).
+    ///
+    /// # Complexity
+    /// O(|V|<sup>2</sup>)
+    pub fn edges(&self) -> Vec<(usize, usize)> {
+        let vertices = self.vertices();
+
+        let mut edges = vec![];
+        for (index, &src_id) in vertices.iter().enumerate() {
+            // For undirected edges only visit the lower triangle, so each edge is reported once.
+            let dst_ids = if Dir::is_directed() {
+                &vertices[..]
+            } else {
+                &vertices[..=index]
+            };
+
+            for &dst_id in dst_ids {
+                if self.bit(src_id, dst_id) {
+                    edges.push((src_id, dst_id));
+                }
+            }
+        }
+
+        edges
+    }
+}
+
+/// For documentation about each function checkout [`Vertices`](crate::provide::Vertices) trait.
+impl<Dir: EdgeDir> Vertices for BitMatrix<Dir> {
+    /// # Complexity
+    /// O(|V|)
+    fn vertices(&self) -> Vec<usize> {
+        self.vertex_ids.iter().collect()
+    }
+
+    /// # Complexity
+    /// O(1)
+    fn vertex_count(&self) -> usize {
+        self.vertex_ids.count()
+    }
+
+    /// # Complexity
+    /// O(log(number of removed vertices))
+    fn contains_vertex(&self, vertex_id: usize) -> bool {
+        self.vertex_ids.is_allocated(vertex_id)
+    }
+}
+
+/// For documentation about each function checkout [`Neighbors`](crate::provide::Neighbors) trait.
+impl<Dir: EdgeDir> Neighbors for BitMatrix<Dir> {
+    /// # Returns
+    /// * `Ok`: Containing id of vertices accessible from source vertex using one edge.
+    /// * `Err`: [`VertexNotFound`](crate::storage::ErrorKind::VertexNotFound) if vertex with id: `src_id` does not exist.
+    ///
+    /// # Complexity
+    /// O(|V|)
+    fn neighbors(&self, src_id: usize) -> Result<Vec<usize>> {
+        if !self.contains_vertex(src_id) {
+            Err(Error::new_vnf(src_id))?
+        } else {
+            Ok(self.neighbors_unchecked(src_id))
+        }
+    }
+
+    /// # Complexity
+    /// O(|V|)
+    fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
+        (0..self.total_vertex_count())
+            .filter(|dst_id| self.vertex_ids.is_allocated(*dst_id) && self.bit(src_id, *dst_id))
+            .collect()
+    }
+}
+
+/// Two bit matrices are equal if they contain vertices with the same ids, and the same pairs of adjacent vertices.
+impl<Dir: EdgeDir> PartialEq for BitMatrix<Dir> {
+    /// # Complexity
+    /// O(|V|<sup>2</sup>)
+    fn eq(&self, other: &Self) -> bool {
+        self.vertices() == other.vertices() && self.edges() == other.edges()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directed_empty_matrix() {
+        // Given: An empty directed matrix.
+        let matrix = DiBitMat::init();
+
+        // When: Doing nothing.
+
+        // Then:
+        assert_eq!(matrix.vertex_count(), 0);
+        assert_eq!(matrix.edge_count(), 0);
+        assert_eq!(matrix.blocks.len(), 0);
+        assert_eq!(matrix.is_directed(), true);
+    }
+
+    #[test]
+    fn undirected_empty_matrix() {
+        // Given: An empty undirected matrix.
+        let matrix = BitMat::init();
+
+        // When: Doing nothing.
+
+        // Then:
+        assert_eq!(matrix.vertex_count(), 0);
+        assert_eq!(matrix.edge_count(), 0);
+        assert_eq!(matrix.blocks.len(), 0);
+        assert_eq!(matrix.is_directed(), false);
+    }
+
+    #[test]
+    fn directed_add_vertex() {
+        // Given: An empty directed matrix.
+        let mut matrix = DiBitMat::init();
+
+        // When: Adding 10 vertices.
+        for _ in 0..10 {
+            matrix.add_vertex();
+        }
+
+        // Then: 100 bits must fit in 2 blocks.
+        assert_eq!(matrix.vertex_count(), 10);
+        assert_eq!(matrix.blocks.len(), 2);
+        assert_eq!(matrix.vertices(), (0..10).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn undirected_add_vertex() {
+        // Given: An empty undirected matrix.
+        let mut matrix = BitMat::init();
+
+        // When: Adding 10 vertices.
+        for _ in 0..10 {
+            matrix.add_vertex();
+        }
+
+        // Then: 55 bits must fit in 1 block.
+        assert_eq!(matrix.vertex_count(), 10);
+        assert_eq!(matrix.blocks.len(), 1);
+        assert_eq!(matrix.vertices(), (0..10).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn directed_add_edge() {
+        // Given: Directed matrix
+        //
+        //      a   b   c
+        //
+        let mut matrix = DiBitMat::init();
+        let a = matrix.add_vertex();
+        let b = matrix.add_vertex();
+        let c = matrix.add_vertex();
+
+        // When: Adding edges
+        //
+        //      a  -->  b  -->  c
+        //      ^               |
+        //      '----------------
+        //
+        assert!(matrix.add_edge_unchecked(a, b));
+        assert!(matrix.add_edge_unchecked(b, c));
+        assert!(matrix.add_edge_unchecked(c, a));
+        assert!(!matrix.add_edge_unchecked(a, b));
+
+        // Then:
+        assert_eq!(matrix.edge_count(), 3);
+        assert_eq!(matrix.edges().len(), 3);
+        assert!(matrix.has_edge_unchecked(a, b));
+        assert!(!matrix.has_edge_unchecked(b, a));
+        assert_eq!(matrix.neighbors_unchecked(a), vec![b]);
+        assert_eq!(matrix.neighbors_unchecked(b), vec![c]);
+        assert_eq!(matrix.neighbors_unchecked(c), vec![a]);
+    }
+
+    #[test]
+    fn undirected_add_edge() {
+        // Given: Undirected matrix
+        //
+        //      a   b   c
+        //
+        let mut matrix = BitMat::init();
+        let a = matrix.add_vertex();
+        let b = matrix.add_vertex();
+        let c = matrix.add_vertex();
+
+        // When: Adding edges
+        //
+        //      a  ---  b  ---  c
+        //
+        assert!(matrix.add_edge_unchecked(a, b));
+        assert!(matrix.add_edge_unchecked(b, c));
+        assert!(!matrix.add_edge_unchecked(b, a));
+
+        // Then:
+        assert_eq!(matrix.edge_count(), 2);
+        assert_eq!(matrix.edges().len(), 2);
+        assert!(matrix.has_edge_unchecked(a, b));
+        assert!(matrix.has_edge_unchecked(b, a));
+        assert_eq!(matrix.neighbors_unchecked(a), vec![b]);
+        assert_eq!(matrix.neighbors_unchecked(b), vec![a, c]);
+        assert_eq!(matrix.neighbors_unchecked(c), vec![b]);
+    }
+
+    #[test]
+    fn undirected_remove_edge() {
+        // Given: Undirected matrix
+        //
+        //      a  ---  b  ---  c
+        //
+        let mut matrix = BitMat::init();
+        let a = matrix.add_vertex();
+        let b = matrix.add_vertex();
+        let c = matrix.add_vertex();
+        matrix.add_edge_unchecked(a, b);
+        matrix.add_edge_unchecked(b, c);
+
+        // When: Removing edge c --- b.
+        assert!(matrix.remove_edge_unchecked(c, b));
+        assert!(!matrix.remove_edge_unchecked(c, b));
+
+        // Then:
+        assert_eq!(matrix.edge_count(), 1);
+        assert_eq!(matrix.edges(), vec![(b, a)]);
+        assert!(matrix.neighbors_unchecked(c).is_empty());
+    }
+
+    #[test]
+    fn directed_remove_vertex() {
+        // Given: Directed matrix
+        //
+        //      a  -->  b  -->  c
+        //      ^               |
+        //      '----------------
+        //
+        let mut matrix = DiBitMat::init();
+        let a = matrix.add_vertex();
+        let b = matrix.add_vertex();
+        let c = matrix.add_vertex();
+        matrix.add_edge_unchecked(a, b);
+        matrix.add_edge_unchecked(b, c);
+        matrix.add_edge_unchecked(c, a);
+
+        // When: Removing vertex b and adding a new vertex.
+        matrix.remove_vertex_unchecked(b);
+        assert_eq!(matrix.edge_count(), 1);
+        assert!(!matrix.contains_vertex(b));
+        let d = matrix.add_vertex();
+
+        // Then: Id of b must be reused and new vertex must have no edges.
+        assert_eq!(d, b);
+        assert_eq!(matrix.vertex_count(), 3);
+        assert_eq!(matrix.edges(), vec![(c, a)]);
+        assert!(matrix.neighbors_unchecked(a).is_empty());
+        assert!(matrix.neighbors_unchecked(d).is_empty());
+    }
+
+    #[test]
+    fn checked_functions_with_invalid_vertex() {
+        // Given: Undirected matrix
+        //
+        //      a
+        //
+        let mut matrix = BitMat::init();
+        let a = matrix.add_vertex();
+
+        // When: Using an id that does not exist.
+        let invalid_id = a + 1;
+
+        // Then:
+        assert!(matrix.add_edge(a, invalid_id).is_err());
+        assert!(matrix.remove_edge(invalid_id, a).is_err());
+        assert!(matrix.has_edge(a, invalid_id).is_err());
+        assert!(matrix.neighbors(invalid_id).is_err());
+        assert!(matrix.remove_vertex(invalid_id).is_err());
+    }
+
+    #[test]
+    fn equality() {
+        // Given: Undirected matrices
+        //
+        //      a  ---  b  ---  c
+        //
+        let mut matrix = BitMat::init();
+        let a = matrix.add_vertex();
+        let b = matrix.add_vertex();
+        let c = matrix.add_vertex();
+        matrix.add_edge_unchecked(a, b);
+        matrix.add_edge_unchecked(b, c);
+
+        let mut other_matrix = BitMat::init();
+        other_matrix.add_vertex();
+        other_matrix.add_vertex();
+        other_matrix.add_vertex();
+        other_matrix.add_edge_unchecked(c, b);
+        other_matrix.add_edge_unchecked(b, a);
+
+        // Then:
+        assert!(matrix == other_matrix);
+
+        // When: Removing an edge.
+        other_matrix.remove_edge_unchecked(a, b);
+
+        // Then:
+        assert!(matrix != other_matrix);
+    }
+
+    #[test]
+    fn memory_usage() {
+        // Given: Directed matrix with 16 vertices.
+        let mut matrix = DiBitMat::init();
+        for _ in 0..16 {
+            matrix.add_vertex();
+        }
+
+        // When: Computing memory usage.
+        let usage = matrix.memory_usage();
+
+        // Then: 256 bits are stored in at least four blocks.
+        assert!(usage.edge_structure() >= 4 * 8);
+        assert_eq!(usage.payloads(), 0);
+    }
+}
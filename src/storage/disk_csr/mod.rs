@@ -0,0 +1,529 @@
+use std::convert::TryInto;
+use std::fs::File;
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::marker::PhantomData;
+use std::path::Path;
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+
+use crate::graph::{EdgeDir, UndirectedEdge};
+use crate::provide::{Neighbors, Vertices};
+use crate::storage::{vec_bytes, Error, MemoryUsage};
+
+const MAGIC: &[u8; 8] = b"PRPNCSR1";
+
+// Magic, flags, vertex count and number of neighbor entries.
+const HEADER_SIZE: u64 = 32;
+
+// Number of neighbor entries that are read at once while validating a snapshot.
+const ENTRY_CHUNK_SIZE: u64 = 1 << 16;
+
+// Where neighbor entries are read from.
+enum Entries {
+    // Entries are read by seeking in the file, one reader at a time.
+    #[cfg_attr(feature = "mmap", allow(dead_code))]
+    File(Mutex<File>),
+
+    // Entries are read from a memory map of the file, so readers do not block each other.
+    #[cfg(feature = "mmap")]
+    Mmap(memmap2::Mmap),
+}
+
+/// Read-only compressed sparse row(CSR) storage that is backed by a binary snapshot file, for analyzing graphs that do not fit in memory.
+///
+/// Only id of the vertices and offsets of their neighbor lists are loaded into memory, which takes 16 bytes per vertex.
+/// Neighbors of a vertex are read from the file each time they are requested, so the operating system pages in the parts of the file
+/// that are accessed and can evict them under memory pressure.
+///
+/// With the `mmap` feature the file is memory mapped using [memmap2](https://docs.rs/memmap2), so threads read neighbors concurrently
+/// without any system call. The file must not be modified while it's open, because changing a memory mapped file is undefined behavior.
+/// Without the feature, each read seeks in the file while holding a lock, so concurrent readers wait for each other.
+/// It's named after where the graph lives rather than how it's read, because memory mapping is optional.
+/// With the feature it's also exported as `MmapCsr`.
+///
+/// Opening a snapshot only reads its vertices and offsets, so it does not scan the neighbor entries.
+/// Instead, neighbors are checked each time they are read. Use [`open_validated`](crate::storage::DiskCsr::open_validated)
+/// to check every neighbor entry once when the snapshot is opened.
+///
+/// Like [`BitMatrix`](crate::storage::BitMatrix), it does not store any edge and does not implement [`GraphStorage`](crate::storage::GraphStorage).
+/// Instead it implements [`Vertices`](crate::provide::Vertices) and [`Neighbors`](crate::provide::Neighbors),
+/// So it can directly be passed to algorithms that only depend on these traits, like [`Bfs`](crate::algo::Bfs) and [`Dfs`](crate::algo::Dfs).
+///
+/// ## Snapshot format
+/// All numbers are little endian `u64`s.
+/// * Header: magic bytes `PRPNCSR1`, flags(first bit is set if graph is directed), number of vertices(n) and number of neighbor entries(m).
+/// * Id of the vertices in ascending order: n numbers.
+/// * Offset of the neighbor list of each vertex in the neighbor entries, followed by m: n + 1 numbers.
+/// * Neighbor entries, which are id of the neighbors of each vertex in order: m numbers.
+///
+/// ## Note
+/// From now on
+/// * |V|: Means number of vertices in the snapshot.
+/// * d: Means number of neighbors of a vertex.
+///
+/// ## Generic Parameters
+/// * `Dir`: **Dir**ection of edges: [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge).
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::{DiskCsr, Mat};
+/// use prepona::graph::MatGraph;
+///
+/// // Given: Graph
+/// //
+/// //      a  ---  b  ---  c
+/// //
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+///
+/// let path = std::env::temp_dir().join("prepona_disk_csr_doc.csr");
+/// DiskCsr::<UndirectedEdge>::write_file(&graph, &path).unwrap();
+///
+/// let csr = DiskCsr::<UndirectedEdge>::open(&path).unwrap();
+/// assert_eq!(csr.vertex_count(), 3);
+/// assert_eq!(csr.neighbors_unchecked(b), vec![a, c]);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub struct DiskCsr<Dir: EdgeDir = UndirectedEdge> {
+    entries: Entries,
+
+    vertex_ids: Vec<usize>,
+    offsets: Vec<u64>,
+
+    phantom_dir: PhantomData<Dir>,
+}
+
+/// [`DiskCsr`] that memory maps its snapshot file, which is what it does whenever the `mmap` feature is enabled.
+#[cfg(feature = "mmap")]
+pub type MmapCsr<Dir = UndirectedEdge> = DiskCsr<Dir>;
+
+impl<Dir: EdgeDir> DiskCsr<Dir> {
+    /// Writes a snapshot of the graph.
+    ///
+    /// # Arguments
+    /// * `graph`: Graph to take snapshot of. Its direction must be `Dir`.
+    /// * `writer`: Writer of the snapshot.
+    ///
+    /// # Returns
+    /// * `Ok`: If snapshot is written successfully.
+    /// * `Err`: Any error that writing to `writer` returns.
+    ///
+    /// # Complexity
+    /// O(|V| * log(|V|) + |E|)
+    pub fn write<G, Wr>(graph: &G, writer: Wr) -> Result<()>
+    where
+        G: Vertices + Neighbors,
+        Wr: Write,
+    {
+        let mut writer = BufWriter::new(writer);
+
+        let mut vertex_ids = graph.vertices();
+        vertex_ids.sort_unstable();
+
+        let neighbors_of = vertex_ids
+            .iter()
+            .map(|vertex_id| {
+                let mut neighbors = graph.neighbors_unchecked(*vertex_id);
+                neighbors.sort_unstable();
+                neighbors
+            })
+            .collect::<Vec<Vec<usize>>>();
+        let entry_count = neighbors_of
+            .iter()
+            .map(|neighbors| neighbors.len())
+            .sum::<usize>();
+
+        writer.write_all(MAGIC)?;
+
+        let mut write_u64 = |number: u64| writer.write_all(&number.to_le_bytes());
+        write_u64(Dir::is_directed() as u64)?;
+        write_u64(vertex_ids.len() as u64)?;
+        write_u64(entry_count as u64)?;
+
+        for vertex_id in &vertex_ids {
+            write_u64(*vertex_id as u64)?;
+        }
+
+        let mut offset = 0;
+        for neighbors in &neighbors_of {
+            write_u64(offset)?;
+            offset += neighbors.len() as u64;
+        }
+        write_u64(offset)?;
+
+        for n_id in neighbors_of.iter().flatten() {
+            write_u64(*n_id as u64)?;
+        }
+
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Writes a snapshot of the graph to a file. Checkout [`write`](crate::storage::DiskCsr::write).
+    pub fn write_file<G, P>(graph: &G, path: P) -> Result<()>
+    where
+        G: Vertices + Neighbors,
+        P: AsRef<Path>,
+    {
+        let file = File::create(path.as_ref())
+            .with_context(|| format!("Failed to create {}", path.as_ref().display()))?;
+
+        Self::write(graph, file)
+    }
+
+    /// Opens a snapshot file and loads its vertices and offsets. Neighbor entries are not read until neighbors of a vertex are requested.
+    ///
+    /// # Arguments
+    /// `path`: Path of the snapshot file.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing the storage.
+    /// * `Err`:
+    ///     * [`InvalidSnapshot`](crate::storage::ErrorKind::InvalidSnapshot) if file is not a snapshot, its direction is not `Dir` or its size does not match its header.
+    ///     * Any error that opening or reading the file returns.
+    ///
+    /// # Complexity
+    /// O(|V|)
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mut file = File::open(path.as_ref())
+            .with_context(|| format!("Failed to open {}", path.as_ref().display()))?;
+        let file_size = file.metadata()?.len();
+
+        if file_size < HEADER_SIZE {
+            Err(Error::new_is("file is smaller than the header"))?
+        }
+
+        let header = Self::read_u64s(&mut file, 0, 4)?;
+        if header[0].to_le_bytes() != *MAGIC {
+            Err(Error::new_is("file does not start with the magic bytes"))?
+        }
+
+        if (header[1] & 1 == 1) != Dir::is_directed() {
+            Err(Error::new_is("direction of the snapshot does not match"))?
+        }
+
+        let (vertex_count, entry_count) = (header[2], header[3]);
+        let expected_size = vertex_count
+            .checked_mul(2)
+            .and_then(|count| count.checked_add(entry_count)?.checked_add(1))
+            .and_then(|count| count.checked_mul(8)?.checked_add(HEADER_SIZE));
+        if expected_size != Some(file_size) {
+            Err(Error::new_is("size of the file does not match its header"))?
+        }
+
+        let vertex_ids = Self::read_u64s(&mut file, HEADER_SIZE, vertex_count as usize)?
+            .into_iter()
+            .map(|vertex_id| vertex_id as usize)
+            .collect::<Vec<usize>>();
+        let offsets = Self::read_u64s(
+            &mut file,
+            HEADER_SIZE + 8 * vertex_count,
+            vertex_count as usize + 1,
+        )?;
+
+        let is_valid = vertex_ids.windows(2).all(|ids| ids[0] < ids[1])
+            && offsets.windows(2).all(|offsets| offsets[0] <= offsets[1])
+            && offsets[0] == 0
+            && offsets[vertex_count as usize] == entry_count;
+        if !is_valid {
+            Err(Error::new_is("vertices or offsets are not sorted"))?
+        }
+
+        // Mapping is safe as long as the file is not modified while it's open, as documented on `DiskCsr`.
+        #[cfg(feature = "mmap")]
+        let entries = Entries::Mmap(unsafe { memmap2::Mmap::map(&file)? });
+        #[cfg(not(feature = "mmap"))]
+        let entries = Entries::File(Mutex::new(file));
+
+        Ok(DiskCsr {
+            entries,
+
+            vertex_ids,
+            offsets,
+
+            phantom_dir: PhantomData,
+        })
+    }
+
+    /// Same as `open`, but also reads every neighbor entry once to check that it's id of a vertex.
+    ///
+    /// # Arguments
+    /// `path`: Path of the snapshot file.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing the storage.
+    /// * `Err`:
+    ///     * [`InvalidSnapshot`](crate::storage::ErrorKind::InvalidSnapshot) if file is not a snapshot, its direction is not `Dir`, its size does not match its header
+    ///       or one of its neighbor entries is not id of a vertex.
+    ///     * Any error that opening or reading the file returns.
+    ///
+    /// # Complexity
+    /// O(|V| + |E| * log(|V|))
+    pub fn open_validated<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let csr = Self::open(path)?;
+        let entry_count = csr.offsets[csr.vertex_ids.len()];
+
+        let mut start = 0;
+        while start < entry_count {
+            let count = ENTRY_CHUNK_SIZE.min(entry_count - start);
+
+            csr.check_entries(&csr.read_entries(start, count as usize)?)?;
+
+            start += count;
+        }
+
+        Ok(csr)
+    }
+
+    fn read_u64s(file: &mut File, position: u64, count: usize) -> Result<Vec<u64>> {
+        let mut bytes = vec![0; count * 8];
+        file.seek(SeekFrom::Start(position))?;
+        file.read_exact(&mut bytes)?;
+
+        Ok(Self::decode_u64s(&bytes))
+    }
+
+    fn decode_u64s(bytes: &[u8]) -> Vec<u64> {
+        bytes
+            .chunks_exact(8)
+            .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+            .collect()
+    }
+
+    // Reads `count` neighbor entries, starting from the entry at index `start`.
+    fn read_entries(&self, start: u64, count: usize) -> Result<Vec<u64>> {
+        let position = HEADER_SIZE + 8 * (2 * self.vertex_ids.len() as u64 + 1 + start);
+
+        match &self.entries {
+            Entries::File(file) => Self::read_u64s(&mut file.lock().unwrap(), position, count),
+            #[cfg(feature = "mmap")]
+            Entries::Mmap(mmap) => {
+                let position = position as usize;
+
+                Ok(Self::decode_u64s(&mmap[position..position + 8 * count]))
+            }
+        }
+    }
+
+    fn check_entries(&self, entries: &[u64]) -> Result<()> {
+        if entries
+            .iter()
+            .any(|n_id| !self.contains_vertex(*n_id as usize))
+        {
+            Err(Error::new_is("a neighbor entry is not id of a vertex"))?
+        }
+
+        Ok(())
+    }
+
+    fn read_neighbors(&self, src_id: usize) -> Result<Vec<usize>> {
+        let index = match self.vertex_ids.binary_search(&src_id) {
+            Ok(index) => index,
+            Err(_) => Err(Error::new_vnf(src_id))?,
+        };
+
+        let (start, end) = (self.offsets[index], self.offsets[index + 1]);
+        let neighbors = self.read_entries(start, (end - start) as usize)?;
+        self.check_entries(&neighbors)?;
+
+        Ok(neighbors.into_iter().map(|n_id| n_id as usize).collect())
+    }
+
+    /// # Returns
+    /// * `true`: If snapshot is of a directed graph.
+    /// * `false`: Otherwise.
+    pub fn is_directed(&self) -> bool {
+        Dir::is_directed()
+    }
+
+    /// Neighbors are read from the file on demand, so only the offsets and ids kept in memory are counted.
+    /// Pages of a memory mapped file are managed by the operating system and are not counted either.
+    ///
+    /// # Returns
+    /// Number of bytes allocated in memory. Checkout [`MemoryUsage`](crate::storage::MemoryUsage) for what is and is not counted.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage::init(vec_bytes(&self.offsets), vec_bytes(&self.vertex_ids), 0)
+    }
+}
+
+/// For documentation about each function checkout [`Vertices`](crate::provide::Vertices) trait.
+impl<Dir: EdgeDir> Vertices for DiskCsr<Dir> {
+    /// # Complexity
+    /// O(|V|)
+    fn vertices(&self) -> Vec<usize> {
+        self.vertex_ids.clone()
+    }
+
+    /// # Complexity
+    /// O(1)
+    fn vertex_count(&self) -> usize {
+        self.vertex_ids.len()
+    }
+
+    /// # Complexity
+    /// O(log(|V|))
+    fn contains_vertex(&self, vertex_id: usize) -> bool {
+        self.vertex_ids.binary_search(&vertex_id).is_ok()
+    }
+}
+
+/// For documentation about each function checkout [`Neighbors`](crate::provide::Neighbors) trait.
+impl<Dir: EdgeDir> Neighbors for DiskCsr<Dir> {
+    /// # Returns
+    /// * `Ok`: Containing id of vertices accessible from source vertex using one edge, in ascending order.
+    /// * `Err`:
+    ///     * [`VertexNotFound`](crate::storage::ErrorKind::VertexNotFound) if vertex with id: `src_id` does not exist.
+    ///     * [`InvalidSnapshot`](crate::storage::ErrorKind::InvalidSnapshot) if one of the neighbor entries of the vertex is not id of a vertex.
+    ///     * Any error that reading the file returns.
+    ///
+    /// # Complexity
+    /// O((d + 1) * log(|V|))
+    fn neighbors(&self, src_id: usize) -> Result<Vec<usize>> {
+        self.read_neighbors(src_id)
+    }
+
+    /// # Panics
+    /// If vertex with id: `src_id` does not exist, one of its neighbor entries is not id of a vertex, or reading the file fails.
+    ///
+    /// # Complexity
+    /// O((d + 1) * log(|V|))
+    fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
+        self.read_neighbors(src_id).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::Bfs;
+    use crate::graph::{DirectedEdge, MatGraph};
+    use crate::provide::*;
+    use crate::storage::{DiMat, ErrorKind};
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("prepona_disk_csr_{}.csr", name))
+    }
+
+    #[test]
+    fn empty_graph() {
+        let path = temp_path("empty");
+        let graph = MatGraph::init(DiMat::<usize>::init());
+        DiskCsr::<DirectedEdge>::write_file(&graph, &path).unwrap();
+
+        let csr = DiskCsr::<DirectedEdge>::open(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(csr.vertex_count(), 0);
+        assert!(csr.vertices().is_empty());
+    }
+
+    #[test]
+    fn directed_graph() {
+        // Given: Graph with ids 0, 2 and 3.
+        //
+        //      a  -->  c  -->  d
+        //      ^               |
+        //      '---------------'
+        //
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        let c = graph.add_vertex();
+        let d = graph.add_vertex();
+        graph.add_edge_unchecked(a, c, 1.into());
+        graph.add_edge_unchecked(c, d, 1.into());
+        graph.add_edge_unchecked(d, a, 1.into());
+        graph.remove_vertex_unchecked(b);
+
+        // When:
+        let path = temp_path("directed");
+        DiskCsr::<DirectedEdge>::write_file(&graph, &path).unwrap();
+        let csr = DiskCsr::<DirectedEdge>::open(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        // Then:
+        assert!(csr.is_directed());
+        assert_eq!(csr.vertices(), vec![a, c, d]);
+        assert!(!csr.contains_vertex(b));
+        assert_eq!(csr.neighbors_unchecked(a), vec![c]);
+        assert_eq!(csr.neighbors_unchecked(d), vec![a]);
+        assert!(csr.neighbors(b).is_err());
+
+        // Then: Algorithms can run on the snapshot.
+        let visited = Bfs::init_with_starts(&csr, vec![c]).execute(&csr);
+        assert_eq!(visited, vec![c, d, a]);
+    }
+
+    #[test]
+    fn invalid_snapshots() {
+        let graph = MatGraph::init(DiMat::<usize>::init());
+        let path = temp_path("invalid");
+
+        let is_invalid = |bytes: &[u8]| {
+            std::fs::write(&path, bytes).unwrap();
+            let result = DiskCsr::<DirectedEdge>::open(&path);
+
+            matches!(
+                result
+                    .err()
+                    .unwrap()
+                    .downcast_ref::<Error>()
+                    .unwrap()
+                    .kind(),
+                ErrorKind::InvalidSnapshot
+            )
+        };
+
+        let mut snapshot = vec![];
+        DiskCsr::<DirectedEdge>::write(&graph, &mut snapshot).unwrap();
+
+        // Then: Too small, wrong magic, wrong direction and wrong size.
+        assert!(is_invalid(&snapshot[..16]));
+
+        let mut wrong_magic = snapshot.clone();
+        wrong_magic[0] = b'X';
+        assert!(is_invalid(&wrong_magic));
+
+        let mut undirected = snapshot.clone();
+        undirected[8] = 0;
+        assert!(is_invalid(&undirected));
+
+        let mut too_long = snapshot.clone();
+        too_long.extend_from_slice(&[0; 8]);
+        assert!(is_invalid(&too_long));
+
+        // Then: Neighbor entry that is not id of a vertex.
+        let mut graph = MatGraph::init(DiMat::<usize>::init());
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge_unchecked(a, b, 1.into());
+
+        let mut unknown_neighbor = vec![];
+        DiskCsr::<DirectedEdge>::write(&graph, &mut unknown_neighbor).unwrap();
+        let last_entry = unknown_neighbor.len() - 8;
+        unknown_neighbor[last_entry..].copy_from_slice(&7u64.to_le_bytes());
+        std::fs::write(&path, &unknown_neighbor).unwrap();
+
+        // Then: It's only found when validated or when the entry is read.
+        assert!(DiskCsr::<DirectedEdge>::open_validated(&path).is_err());
+        let csr = DiskCsr::<DirectedEdge>::open(&path).unwrap();
+        let error = csr.neighbors(a).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<Error>().unwrap().kind(),
+            ErrorKind::InvalidSnapshot
+        ));
+        assert!(csr.neighbors(b).unwrap().is_empty());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+
+use crate::graph::{Edge, EdgeDir};
+use crate::storage::GraphStorage;
+
+// Maps id of each edge to (`src_id`, `dst_id`, `edge`). End points of undirected edges are ordered so `src_id` <= `dst_id`.
+fn edges_by_id<W, E, Dir, S>(storage: &S) -> HashMap<usize, (usize, usize, &E)>
+where
+    E: Edge<W>,
+    Dir: EdgeDir,
+    S: GraphStorage<W, E, Dir>,
+{
+    storage
+        .edges()
+        .into_iter()
+        .map(|(src_id, dst_id, edge)| {
+            if Dir::is_undirected() && dst_id < src_id {
+                (edge.get_id(), (dst_id, src_id, edge))
+            } else {
+                (edge.get_id(), (src_id, dst_id, edge))
+            }
+        })
+        .collect()
+}
+
+/// Two storages are equal if they contain vertices with the same ids, and edges with the same ids, end points and values.
+///
+/// # Complexity
+/// O(|V|.log(|V|) + |E|)
+pub(crate) fn storage_eq<W, E, Dir, S>(lhs: &S, rhs: &S) -> bool
+where
+    E: Edge<W> + PartialEq,
+    Dir: EdgeDir,
+    S: GraphStorage<W, E, Dir>,
+{
+    let mut lhs_vertices = lhs.vertices();
+    let mut rhs_vertices = rhs.vertices();
+    lhs_vertices.sort_unstable();
+    rhs_vertices.sort_unstable();
+
+    lhs_vertices == rhs_vertices && edges_by_id(lhs) == edges_by_id(rhs)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::{DiList, DiMat, DiSparseMat, GraphStorage, List, Mat, SparseMat};
+
+    #[test]
+    fn equal_storages() {
+        // Given: Two matrices with the same vertices and edges.
+        //
+        //      a  --(1)--  b  --(2)--  c
+        //
+        let mut mat = Mat::<usize>::init();
+        let a = mat.add_vertex();
+        let b = mat.add_vertex();
+        let c = mat.add_vertex();
+        mat.add_edge_unchecked(a, b, 1.into());
+        let bc = mat.add_edge_unchecked(b, c, 2.into());
+
+        let mut other_mat = Mat::<usize>::init();
+        other_mat.add_vertex();
+        other_mat.add_vertex();
+        other_mat.add_vertex();
+        other_mat.add_edge_unchecked(a, b, 1.into());
+        other_mat.add_edge_unchecked(c, b, 2.into());
+
+        // Then:
+        assert!(mat == other_mat);
+
+        // When: Changing weight of an edge.
+        other_mat.update_edge_unchecked(b, c, bc, 3.into());
+
+        // Then:
+        assert!(mat != other_mat);
+    }
+
+    #[test]
+    fn edge_ids() {
+        // Given: Two lists with the same edges, added in different orders.
+        let mut list = List::<usize>::init();
+        let a = list.add_vertex();
+        let b = list.add_vertex();
+        let c = list.add_vertex();
+        list.add_edge_unchecked(a, b, 1.into());
+        list.add_edge_unchecked(b, c, 1.into());
+
+        let mut other_list = List::<usize>::init();
+        other_list.add_vertex();
+        other_list.add_vertex();
+        other_list.add_vertex();
+        other_list.add_edge_unchecked(b, c, 1.into());
+        other_list.add_edge_unchecked(a, b, 1.into());
+
+        // Then: Edges have different ids.
+        assert!(list != other_list);
+    }
+
+    #[test]
+    fn directed_storages() {
+        // Given: Storages with an edge from a to b, and storages with an edge from b to a.
+        let mut mat = DiMat::<usize>::init();
+        let mut reversed_mat = DiMat::<usize>::init();
+        let mut list = DiList::<usize>::init();
+        let mut reversed_list = DiList::<usize>::init();
+        let mut sparse_mat = DiSparseMat::<usize>::init();
+        let mut reversed_sparse_mat = DiSparseMat::<usize>::init();
+
+        let a = mat.add_vertex();
+        let b = mat.add_vertex();
+        mat.add_edge_unchecked(a, b, 1.into());
+        reversed_mat.add_vertex();
+        reversed_mat.add_vertex();
+        reversed_mat.add_edge_unchecked(b, a, 1.into());
+
+        list.add_vertex();
+        list.add_vertex();
+        list.add_edge_unchecked(a, b, 1.into());
+        reversed_list.add_vertex();
+        reversed_list.add_vertex();
+        reversed_list.add_edge_unchecked(b, a, 1.into());
+
+        sparse_mat.add_vertex();
+        sparse_mat.add_vertex();
+        sparse_mat.add_edge_unchecked(a, b, 1.into());
+        reversed_sparse_mat.add_vertex();
+        reversed_sparse_mat.add_vertex();
+        reversed_sparse_mat.add_edge_unchecked(b, a, 1.into());
+
+        // Then:
+        assert!(mat != reversed_mat);
+        assert!(list != reversed_list);
+        assert!(sparse_mat != reversed_sparse_mat);
+    }
+
+    #[test]
+    fn vertex_ids() {
+        // Given: Two sparse matrices with the same number of vertices but different vertex ids.
+        let mut sparse_mat = SparseMat::<usize>::init();
+        sparse_mat.add_vertex();
+        sparse_mat.add_vertex();
+
+        let mut other_sparse_mat = SparseMat::<usize>::init();
+        let a = other_sparse_mat.add_vertex();
+        other_sparse_mat.add_vertex();
+        other_sparse_mat.add_vertex();
+        other_sparse_mat.remove_vertex_unchecked(a);
+
+        // Then:
+        assert_eq!(sparse_mat.vertex_count(), other_sparse_mat.vertex_count());
+        assert!(sparse_mat != other_sparse_mat);
+    }
+}
@@ -1,6 +1,7 @@
 pub enum ErrorKind {
     VertexNotFound,
     EdgeNotFound,
+    InvalidSnapshot,
 }
 
 pub struct Error {
@@ -27,6 +28,13 @@ impl Error {
         }
     }
 
+    pub fn new_is(reason: &str) -> Self {
+        Error {
+            kind: ErrorKind::InvalidSnapshot,
+            msg: format!("Invalid snapshot: {}", reason),
+        }
+    }
+
     pub fn msg(&self) -> &str {
         self.msg.as_str()
     }
@@ -0,0 +1,220 @@
+use std::collections::BTreeSet;
+use std::mem::size_of;
+
+/// Allocates unique ids for vertices or edges of a storage.
+///
+/// Ids are allocated from a continuous range that starts at 0. By default, released ids are reused before the range is extended, smallest id first.
+/// So the allocated ids only depend on the order of allocations and releases.
+/// Reusing ids can be disabled, so that while it's disabled only ids that have never been allocated before are allocated.
+///
+/// # Examples
+/// ```
+/// use prepona::storage::IdAllocator;
+///
+/// let mut ids = IdAllocator::init();
+/// assert_eq!(ids.allocate_many(3), vec![0, 1, 2]);
+///
+/// ids.release(2);
+/// ids.release(0);
+/// assert_eq!(ids.allocate(), 0);
+/// assert_eq!(ids.allocate(), 2);
+/// assert_eq!(ids.allocate(), 3);
+///
+/// // Never reuse released ids.
+/// let mut ids = IdAllocator::init().without_reuse();
+/// let id = ids.allocate();
+/// ids.release(id);
+/// assert_eq!(ids.allocate(), 1);
+/// assert!(!ids.is_allocated(id));
+/// ```
+#[derive(Debug, Clone)]
+pub struct IdAllocator {
+    next_id: usize,
+    released_ids: BTreeSet<usize>,
+
+    reuse: bool,
+}
+
+impl IdAllocator {
+    /// # Returns
+    /// An allocator that has not allocated any id and reuses released ids.
+    pub fn init() -> Self {
+        IdAllocator {
+            next_id: 0,
+            released_ids: BTreeSet::new(),
+
+            reuse: true,
+        }
+    }
+
+    /// # Returns
+    /// The allocator that never reuses released ids.
+    pub fn without_reuse(mut self) -> Self {
+        self.set_reuse(false);
+
+        self
+    }
+
+    /// While reuse is disabled no released id is allocated, including ids that were released before disabling it.
+    /// Released ids are still kept, so they are allocated again if reuse is enabled later.
+    ///
+    /// # Arguments
+    /// `reuse`: Whether released ids should be allocated again.
+    pub fn set_reuse(&mut self, reuse: bool) {
+        self.reuse = reuse;
+    }
+
+    /// # Returns
+    /// `true` if released ids are allocated again, `false` otherwise.
+    pub fn reuses_ids(&self) -> bool {
+        self.reuse
+    }
+
+    /// # Returns
+    /// Smallest released id if reusing ids is enabled and there is any. Otherwise the first id that has never been allocated.
+    ///
+    /// # Complexity
+    /// O(log(number of released ids))
+    pub fn allocate(&mut self) -> usize {
+        if self.reuse {
+            if let Some(id) = self.released_ids.iter().next().copied() {
+                self.released_ids.remove(&id);
+
+                return id;
+            }
+        }
+
+        self.next_id += 1;
+
+        self.next_id - 1
+    }
+
+    /// # Arguments
+    /// `count`: Number of ids to allocate.
+    ///
+    /// # Returns
+    /// Allocated ids in the order that `allocate` would have returned them.
+    pub fn allocate_many(&mut self, count: usize) -> Vec<usize> {
+        (0..count).map(|_| self.allocate()).collect()
+    }
+
+    /// Releases `id` so it can be allocated again. Does nothing if `id` is not allocated.
+    ///
+    /// # Arguments
+    /// `id`: Id to be released.
+    ///
+    /// # Returns
+    /// `true` if `id` was allocated, `false` otherwise.
+    ///
+    /// # Complexity
+    /// O(log(number of released ids))
+    pub fn release(&mut self, id: usize) -> bool {
+        id < self.next_id && self.released_ids.insert(id)
+    }
+
+    /// # Arguments
+    /// `id`: Id to check.
+    ///
+    /// # Returns
+    /// `true` if `id` is allocated and not released, `false` otherwise.
+    ///
+    /// # Complexity
+    /// O(log(number of released ids))
+    pub fn is_allocated(&self, id: usize) -> bool {
+        id < self.next_id && !self.released_ids.contains(&id)
+    }
+
+    /// # Returns
+    /// Number of allocated ids that are not released.
+    pub fn count(&self) -> usize {
+        self.next_id - self.released_ids.len()
+    }
+
+    /// # Returns
+    /// Number of ids that have ever been allocated. All allocated ids are smaller than this number.
+    pub fn total_count(&self) -> usize {
+        self.next_id
+    }
+
+    /// # Returns
+    /// Number of released ids.
+    pub fn released_count(&self) -> usize {
+        self.released_ids.len()
+    }
+
+    /// # Returns
+    /// Allocated ids that are not released, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.next_id).filter(move |id| !self.released_ids.contains(id))
+    }
+
+    /// Allocated ids are not stored, so only released ids take memory.
+    ///
+    /// # Returns
+    /// Estimated number of bytes allocated to keep track of released ids.
+    pub fn memory_usage(&self) -> usize {
+        self.released_ids.len() * size_of::<usize>()
+    }
+}
+
+impl Default for IdAllocator {
+    fn default() -> Self {
+        IdAllocator::init()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn init() {
+        let ids = IdAllocator::init();
+
+        assert_eq!(ids.count(), 0);
+        assert_eq!(ids.total_count(), 0);
+        assert!(ids.reuses_ids());
+        assert!(!ids.is_allocated(0));
+    }
+
+    #[test]
+    fn reuse_smallest_released_id() {
+        // Given: Allocator with ids 0 to 4.
+        let mut ids = IdAllocator::init();
+        ids.allocate_many(5);
+
+        // When: Releasing 3, 1 and 3 again.
+        assert!(ids.release(3));
+        assert!(ids.release(1));
+        assert!(!ids.release(3));
+        assert!(!ids.release(5));
+
+        // Then:
+        assert_eq!(ids.count(), 3);
+        assert_eq!(ids.released_count(), 2);
+        assert_eq!(ids.iter().collect::<Vec<usize>>(), vec![0, 2, 4]);
+        assert_eq!(ids.allocate_many(3), vec![1, 3, 5]);
+        assert_eq!(ids.total_count(), 6);
+    }
+
+    #[test]
+    fn without_reuse() {
+        // Given: Allocator that never reuses ids, with ids 0 to 2.
+        let mut ids = IdAllocator::init().without_reuse();
+        ids.allocate_many(3);
+
+        // When: Releasing 0.
+        ids.release(0);
+
+        // Then:
+        assert_eq!(ids.allocate(), 3);
+        assert!(!ids.is_allocated(0));
+        assert_eq!(ids.count(), 3);
+
+        // When: Enabling reuse.
+        ids.set_reuse(true);
+
+        // Then:
+        assert_eq!(ids.allocate(), 0);
+    }
+}
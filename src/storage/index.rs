@@ -0,0 +1,81 @@
+use std::convert::TryFrom;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// Defines the integer type that is used to store vertex ids internally.
+///
+/// Storages and id maps use `usize` by default. But on 64-bit platforms using a smaller type like `u32` halves the memory
+/// needed for each stored id, as long as the number of vertices does not exceed the maximum value of the type.
+/// Note that regardless of the index type, ids are always exposed as `usize` in the public api.
+pub trait IndexType: Copy + Default + Eq + Ord + Hash + Debug + 'static {
+    /// # Arguments
+    /// `id`: Id to be converted.
+    ///
+    /// # Returns
+    /// `id` converted to the index type.
+    ///
+    /// # Panics
+    /// If `id` is greater than [`max_index`](crate::storage::IndexType::max_index).
+    fn new(id: usize) -> Self;
+
+    /// # Returns
+    /// Index converted back to `usize`.
+    fn index(&self) -> usize;
+
+    /// # Returns
+    /// Maximum id that can be represented by the index type.
+    fn max_index() -> usize;
+}
+
+macro_rules! impl_index_type {
+    ($($ty:ty),*) => {
+        $(
+            impl IndexType for $ty {
+                #[inline(always)]
+                fn new(id: usize) -> Self {
+                    <$ty>::try_from(id).unwrap_or_else(|_| {
+                        panic!("Id: {} does not fit in the index type: {}", id, stringify!($ty))
+                    })
+                }
+
+                #[inline(always)]
+                fn index(&self) -> usize {
+                    *self as usize
+                }
+
+                #[inline(always)]
+                fn max_index() -> usize {
+                    <$ty>::MAX as usize
+                }
+            }
+        )*
+    };
+}
+
+impl_index_type!(u8, u16, u32, u64, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        assert_eq!(<u8 as IndexType>::new(200).index(), 200);
+        assert_eq!(<u16 as IndexType>::new(60_000).index(), 60_000);
+        assert_eq!(<u32 as IndexType>::new(1 << 31).index(), 1 << 31);
+        assert_eq!(<usize as IndexType>::new(usize::MAX).index(), usize::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit")]
+    fn id_out_of_range() {
+        <u16 as IndexType>::new(1 << 16);
+    }
+
+    #[test]
+    fn max_index() {
+        assert_eq!(<u8 as IndexType>::max_index(), 255);
+        assert_eq!(<u16 as IndexType>::max_index(), 65_535);
+        assert_eq!(<u32 as IndexType>::max_index(), u32::MAX as usize);
+    }
+}
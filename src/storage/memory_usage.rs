@@ -0,0 +1,133 @@
+use std::collections::{BTreeSet, HashMap};
+use std::mem::size_of;
+use std::ops::Add;
+
+/// Number of bytes a storage has allocated, broken down by what they are used for.
+///
+/// Only heap allocations owned by the storage are counted, including the unused capacity of its collections.
+/// Memory that edges allocate themselves(for example when `W` is a `String`) is not counted, and allocator and hash table bookkeeping is only estimated.
+/// So the reported numbers are meant to compare storages and capacities with each other, not to be matched against the allocator.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::storage::{DiList, DiMat};
+///
+/// let mut mat = DiMat::<usize>::init();
+/// let mut list = DiList::<usize>::init();
+/// for _ in 0..100 {
+///     mat.add_vertex();
+///     list.add_vertex();
+/// }
+/// mat.add_edge_unchecked(0, 1, 1.into());
+/// list.add_edge_unchecked(0, 1, 1.into());
+///
+/// // Both store the same edge, but the matrix also stores a cell for every pair of vertices.
+/// assert_eq!(mat.memory_usage().payloads(), list.memory_usage().payloads());
+/// assert!(mat.memory_usage().total() > list.memory_usage().total());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MemoryUsage {
+    edge_structure: usize,
+    id_sets: usize,
+    payloads: usize,
+}
+
+impl MemoryUsage {
+    /// # Arguments
+    /// * `edge_structure`: Bytes used to store which vertices are connected, without the edges themselves.
+    /// * `id_sets`: Bytes used to keep track of vertex and edge ids.
+    /// * `payloads`: Bytes used by the stored edges.
+    ///
+    /// # Returns
+    /// The memory usage made of the given parts.
+    pub fn init(edge_structure: usize, id_sets: usize, payloads: usize) -> Self {
+        MemoryUsage {
+            edge_structure,
+            id_sets,
+            payloads,
+        }
+    }
+
+    /// # Returns
+    /// Bytes used to store which vertices are connected, including empty cells and unused capacity.
+    pub fn edge_structure(&self) -> usize {
+        self.edge_structure
+    }
+
+    /// # Returns
+    /// Bytes used to keep track of vertex and edge ids.
+    pub fn id_sets(&self) -> usize {
+        self.id_sets
+    }
+
+    /// # Returns
+    /// Bytes used by the stored edges.
+    pub fn payloads(&self) -> usize {
+        self.payloads
+    }
+
+    /// # Returns
+    /// Sum of all parts.
+    pub fn total(&self) -> usize {
+        self.edge_structure + self.id_sets + self.payloads
+    }
+}
+
+impl Add for MemoryUsage {
+    type Output = MemoryUsage;
+
+    fn add(self, other: MemoryUsage) -> MemoryUsage {
+        MemoryUsage {
+            edge_structure: self.edge_structure + other.edge_structure,
+            id_sets: self.id_sets + other.id_sets,
+            payloads: self.payloads + other.payloads,
+        }
+    }
+}
+
+/// # Returns
+/// Bytes allocated by `vec`, excluding what its elements allocate.
+pub(crate) fn vec_bytes<T>(vec: &Vec<T>) -> usize {
+    vec.capacity() * size_of::<T>()
+}
+
+/// # Returns
+/// Estimated bytes allocated by `map`: one entry and one control byte per bucket.
+pub(crate) fn map_bytes<K, V>(map: &HashMap<K, V>) -> usize {
+    map.capacity() * (size_of::<(K, V)>() + 1)
+}
+
+/// # Returns
+/// Estimated bytes allocated by `set`: one element per element, ignoring the overhead of the nodes of the tree.
+pub(crate) fn set_bytes<T>(set: &BTreeSet<T>) -> usize {
+    set.len() * size_of::<T>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn total_and_add() {
+        // Given: Two memory usages.
+        let first = MemoryUsage::init(1, 2, 3);
+        let second = MemoryUsage::init(10, 20, 30);
+
+        // When: Adding them.
+        let sum = first + second;
+
+        // Then:
+        assert_eq!(sum, MemoryUsage::init(11, 22, 33));
+        assert_eq!(first.total(), 6);
+        assert_eq!(sum.total(), 66);
+        assert_eq!(MemoryUsage::default().total(), 0);
+    }
+
+    #[test]
+    fn vec_bytes_counts_capacity() {
+        let vec = Vec::<u64>::with_capacity(10);
+
+        assert_eq!(vec_bytes(&vec), 80);
+    }
+}
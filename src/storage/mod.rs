@@ -1,10 +1,30 @@
 mod adj_list;
 mod adj_matrix;
+mod bit_mat;
+mod disk_csr;
+mod equality;
 mod error;
+mod id_allocator;
+mod index;
+mod memory_usage;
+mod sparse_mat;
 
 pub use adj_list::{AdjList, DiFlowList, DiList, FlowList, List};
-pub use adj_matrix::{AdjMatrix, DiFlowMat, DiMat, FlowMat, Mat};
+pub use adj_matrix::{AdjMatrix, DiFlowMat, DiMat, FlowMat, Mat, TransposeView};
+pub use bit_mat::{BitMat, BitMatrix, DiBitMat};
+pub use disk_csr::DiskCsr;
+#[cfg(feature = "mmap")]
+pub use disk_csr::MmapCsr;
 pub use error::{Error, ErrorKind};
+pub use id_allocator::IdAllocator;
+pub use index::IndexType;
+pub use memory_usage::MemoryUsage;
+pub use sparse_mat::{DiFlowSparseMat, DiSparseMat, FlowSparseMat, SparseMat, SparseMatrix};
+
+pub(crate) use equality::storage_eq;
+pub(crate) use memory_usage::{map_bytes, set_bytes, vec_bytes};
+
+use std::mem::size_of;
 
 use crate::graph::{Edge, EdgeDir};
 
@@ -28,9 +48,18 @@ use anyhow::Result;
 /// In checked versions some checkings will occur before computing the end result. So these functions will return `Result` and will not panic.
 /// But checking on every call will have some overheads so if you want to skip these checkings(For example you are sure some invariants are never violated),
 /// You can use unchecked version of the function. Regarding that what situations will cause a function to `panic` or return `Err`,
-/// It depends on wether storage uses the default implmenetation provided by `GraphStorage` or not. Causes of returning `Err` is specified for default implementations.
+/// It depends on whether storage uses the default implmenetation provided by `GraphStorage` or not. Causes of returning `Err` is specified for default implementations.
 /// But if storage does use the default implementatino, refer to the documentation of the storage to find out about causes of `panic` or `Err`.
 ///
+/// ## Iteration order
+/// Storages of prepona return vertices, edges and neighbors in an order that only depends on the operations applied to the storage.
+/// So running the same program twice gives the same results, even for algorithms that depend on the order of iteration.
+/// * `vertices` returns ids in ascending order.
+/// * [`AdjMatrix`](crate::storage::AdjMatrix) and [`SparseMatrix`](crate::storage::SparseMatrix) return neighbors and edges of a vertex in ascending order of the other end point.
+/// * [`AdjList`](crate::storage::AdjList) returns edges of a vertex in the order they were added.
+///
+/// Custom storages should provide the same guarantee, so they can be compared using golden tests.
+///
 /// ## Generic Parameters
 /// * `W`: **W**eight type associated with edges.
 /// * `E`: **E**dge type that graph uses.
@@ -64,6 +93,46 @@ pub trait GraphStorage<W, E: Edge<W>, Dir: EdgeDir> {
     /// `vertex_id`: Id of the vertex to be removed.
     fn remove_vertex_unchecked(&mut self, vertex_id: usize);
 
+    /// Removes the vertex with id: `vertex_id` from storage, and returns the edges that got removed along with it.
+    ///
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex to be removed.
+    ///
+    /// # Returns
+    /// * `Ok`: Containing the removed edges in the format of: (`src_id`, `dst_id`, `edge`). Checkout [`remove_vertex_collect_unchecked`](crate::storage::GraphStorage::remove_vertex_collect_unchecked).
+    /// * `Err`: [`VertexNotFound`](crate::storage::ErrorKind::VertexNotFound) if vertex with specified id does not exist.
+    fn remove_vertex_collect(&mut self, vertex_id: usize) -> Result<Vec<(usize, usize, E)>> {
+        if !self.contains_vertex(vertex_id) {
+            Err(Error::new_vnf(vertex_id))?
+        } else {
+            Ok(self.remove_vertex_collect_unchecked(vertex_id))
+        }
+    }
+
+    /// Removes the vertex with id: `vertex_id` from storage, and returns the edges that got removed along with it.
+    ///
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex to be removed.
+    ///
+    /// # Returns
+    /// Edges that were connected to the vertex in the format of: (`src_id`, `dst_id`, `edge`). Like `edges`, each edge is returned once
+    /// and end points of undirected edges are ordered so `src_id` <= `dst_id`. Removed edges can be added back using `add_edges`, but they get new ids.
+    ///
+    /// # Complexity
+    /// O(|E|) for directed graphs, because edges that end at the vertex are found by going through edges of the other vertices.
+    /// For undirected graphs, only edges of the vertex itself are visited.
+    fn remove_vertex_collect_unchecked(&mut self, vertex_id: usize) -> Vec<(usize, usize, E)> {
+        crate::provide::remove_vertex_collect(
+            self,
+            vertex_id,
+            Dir::is_directed(),
+            Self::vertices,
+            Self::edges_from_unchecked,
+            Self::remove_edge_unchecked,
+            Self::remove_vertex_unchecked,
+        )
+    }
+
     /// # Arguments
     /// `vertex_id`: Id of the vertex.
     ///
@@ -103,6 +172,52 @@ pub trait GraphStorage<W, E: Edge<W>, Dir: EdgeDir> {
     /// Unique id of the newly added edge.
     fn add_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge: E) -> usize;
 
+    /// Adds all `edges` to the storage in one pass.
+    ///
+    /// All edges are checked before any of them gets added. So either all edges are added or none of them.
+    ///
+    /// # Arguments
+    /// `edges`: Edges to be added in the format of: (`src_id`, `dst_id`, `edge`).
+    ///
+    /// # Returns
+    /// * `Ok`: Containing unique ids of the newly added edges, in the same order as `edges`.
+    /// * `Err`: [`VertexNotFound`](crate::storage::ErrorKind::VertexNotFound) if source or destination of any of the edges does not exist.
+    fn add_edges<I>(&mut self, edges: I) -> Result<Vec<usize>>
+    where
+        I: IntoIterator<Item = (usize, usize, E)>,
+        Self: Sized,
+    {
+        let edges = edges.into_iter().collect::<Vec<(usize, usize, E)>>();
+
+        for (src_id, dst_id, _) in &edges {
+            if !self.contains_vertex(*src_id) {
+                Err(Error::new_vnf(*src_id))?
+            } else if !self.contains_vertex(*dst_id) {
+                Err(Error::new_vnf(*dst_id))?
+            }
+        }
+
+        Ok(self.add_edges_unchecked(edges))
+    }
+
+    /// Adds all `edges` to the storage in one pass.
+    ///
+    /// # Arguments
+    /// `edges`: Edges to be added in the format of: (`src_id`, `dst_id`, `edge`).
+    ///
+    /// # Returns
+    /// Unique ids of the newly added edges, in the same order as `edges`.
+    fn add_edges_unchecked<I>(&mut self, edges: I) -> Vec<usize>
+    where
+        I: IntoIterator<Item = (usize, usize, E)>,
+        Self: Sized,
+    {
+        edges
+            .into_iter()
+            .map(|(src_id, dst_id, edge)| self.add_edge_unchecked(src_id, dst_id, edge))
+            .collect()
+    }
+
     /// Replaces the edge with id: `edge_id` with `edge`.
     ///
     /// # Arguments
@@ -185,7 +300,7 @@ pub trait GraphStorage<W, E: Edge<W>, Dir: EdgeDir> {
     }
 
     /// # Returns
-    /// Id of vertices that are present in the storage.
+    /// Id of vertices that are present in the storage, in ascending order.
     fn vertices(&self) -> Vec<usize>;
 
     /// # Arguments
@@ -321,14 +436,12 @@ pub trait GraphStorage<W, E: Edge<W>, Dir: EdgeDir> {
 
     /// # Returns
     /// All edges in the storage in the format: (`src_id`, `dst_id`, `edge`).
+    /// Each edge is returned once, and end points of undirected edges are ordered so `src_id` <= `dst_id`.
     fn edges(&self) -> Vec<(usize, usize, &E)> {
         if Dir::is_directed() {
             self.as_directed_edges()
         } else {
-            self.as_directed_edges()
-                .into_iter()
-                .filter(|(src_id, dst_id, _)| src_id <= dst_id)
-                .collect()
+            crate::provide::undirected_edges(self.as_directed_edges())
         }
     }
 
@@ -419,4 +532,23 @@ pub trait GraphStorage<W, E: Edge<W>, Dir: EdgeDir> {
     fn is_undirected(&self) -> bool {
         Dir::is_undirected()
     }
+
+    /// Checkout [`MemoryUsage`](crate::storage::MemoryUsage) for what is and is not counted.
+    ///
+    /// The default implementation is only an estimate: It assumes one id per edge for the edge structure, one id per vertex and edge for the id sets and one `E` per edge.
+    /// Unused capacity and empty cells are not taken into account.
+    /// Storages that know their layout, like [`AdjList`](crate::storage::AdjList), [`AdjMatrix`](crate::storage::AdjMatrix) and [`SparseMatrix`](crate::storage::SparseMatrix), override it with the exact numbers.
+    ///
+    /// # Returns
+    /// Number of bytes the storage has allocated for its edge structure, id sets and edges.
+    fn memory_usage(&self) -> MemoryUsage {
+        let vertex_count = self.vertex_count();
+        let edge_count = self.edge_count();
+
+        MemoryUsage::init(
+            edge_count * size_of::<usize>(),
+            (vertex_count + edge_count) * size_of::<usize>(),
+            edge_count * size_of::<E>(),
+        )
+    }
 }
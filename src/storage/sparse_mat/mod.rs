@@ -0,0 +1,751 @@
+use std::collections::{BTreeSet, HashMap};
+use std::marker::PhantomData;
+use std::mem::size_of;
+
+use crate::graph::{DefaultEdge, DirectedEdge, Edge, EdgeDir, FlowEdge, UndirectedEdge};
+use crate::storage::{
+    map_bytes, set_bytes, storage_eq, vec_bytes, GraphStorage, IdAllocator, MemoryUsage,
+};
+
+pub type SparseMat<W, Dir = UndirectedEdge> = SparseMatrix<W, DefaultEdge<W>, Dir>;
+pub type DiSparseMat<W> = SparseMatrix<W, DefaultEdge<W>, DirectedEdge>;
+
+pub type FlowSparseMat<W, Dir = UndirectedEdge> = SparseMatrix<W, FlowEdge<W>, Dir>;
+pub type DiFlowSparseMat<W> = SparseMatrix<W, FlowEdge<W>, DirectedEdge>;
+
+/// Is a sparse matrix used to represent a finite graph.
+/// Only the non-empty cells of the matrix are stored, using a hash map keyed by (`src_id`, `dst_id`).
+///
+/// Unlike [`AdjMatrix`](crate::storage::AdjMatrix), adding a vertex does not allocate a whole row and column.
+/// And unlike [`AdjList`](crate::storage::AdjList), finding and removing the edges between two vertices does not require iterating over the edges of the source.
+/// So it's suitable for very sparse graphs that are mutated frequently.
+///
+/// Vertices adjacent to each vertex are kept sorted, so neighbors and edges of a vertex are returned in ascending order of the id of the other end point,
+/// regardless of the order edges were added in.
+///
+/// ## Note
+/// From now on
+/// * |V|: Means number of vertices present in the graph.
+/// * |E|: Means number of edges present in the graph.
+/// * |E<sup>*</sup>|: Means number of edges between two vertices.
+/// * |N<sup>*</sup>|: Means number of vertices adjacent to a vertex(either by an incoming or an outgoing edge).
+///
+/// ## Space complexity
+/// `SparseMatrix` stores |V| + 3 * |E| elements, regardless of whether `Dir` is [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge).
+///
+/// ## Generic Parameters
+/// * `W`: **W**eight type associated with edges.
+/// * `E`: **E**dge type that graph uses.
+/// * `Dir`: **Dir**ection of edges: [`Directed`](crate::graph::DirectedEdge) or [`Undirected`](crate::graph::UndirectedEdge).
+pub struct SparseMatrix<W, E: Edge<W>, Dir: EdgeDir = UndirectedEdge> {
+    cells: HashMap<(usize, usize), Vec<E>>,
+    adjacent_of: HashMap<usize, BTreeSet<usize>>,
+
+    vertex_ids: IdAllocator,
+    edge_ids: IdAllocator,
+
+    phantom_w: PhantomData<W>,
+    phantom_dir: PhantomData<Dir>,
+}
+
+impl<W, E: Edge<W>, Dir: EdgeDir> SparseMatrix<W, E, Dir> {
+    /// Initializes an empty sparse matrix.
+    ///
+    /// `SparseMatrix` defines multiple types with different combination of values for generic parameters.
+    /// These types are:
+    /// * [`SparseMat`](crate::storage::SparseMat): A sparse matrix that uses [`undirected`](crate::graph::UndirectedEdge) [`default edges`](crate::graph::DefaultEdge).
+    /// * [`DiSparseMat`](crate::storage::DiSparseMat): A sparse matrix that uses [`directed`](crate::graph::DirectedEdge) [`default edges`](crate::graph::DefaultEdge).
+    /// * [`FlowSparseMat`](crate::storage::FlowSparseMat): A sparse matrix that uses [`undirected`](crate::graph::UndirectedEdge) [`flow edges`](crate::graph::FlowEdge).
+    /// * [`DiFlowSparseMat`](crate::storage::DiFlowSparseMat): A sparse matrix that uses [`directed`](crate::graph::DirectedEdge) [`flow edges`](crate::graph::FlowEdge).
+    ///
+    /// # Returns
+    /// An empty `SparseMatrix`.
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::storage::{SparseMat, DiSparseMat};
+    ///
+    /// // To store an undirected graph with usize weights
+    /// let mat = SparseMat::<usize>::init();
+    ///
+    /// // To store a directed graph with usize weights
+    /// let di_mat = DiSparseMat::<usize>::init();
+    /// ```
+    pub fn init() -> Self {
+        SparseMatrix {
+            cells: HashMap::new(),
+            adjacent_of: HashMap::new(),
+
+            vertex_ids: IdAllocator::init(),
+            edge_ids: IdAllocator::init(),
+
+            phantom_w: PhantomData,
+            phantom_dir: PhantomData,
+        }
+    }
+
+    /// Initializes an empty sparse matrix with enough preallocated memory to store `vertex_count` vertices.
+    ///
+    /// # Arguments
+    /// `vertex_count`: Number of vertices to allocate memory for.
+    ///
+    /// # Returns
+    /// An empty `SparseMatrix` that can store `vertex_count` vertices without reallocating.
+    ///
+    /// # Examples
+    /// ```
+    /// use prepona::prelude::*;
+    /// use prepona::storage::DiSparseMat;
+    ///
+    /// let mut mat = DiSparseMat::<usize>::with_capacity(2);
+    ///
+    /// let a = mat.add_vertex();
+    /// let b = mat.add_vertex();
+    ///
+    /// assert_eq!(mat.vertex_count(), 2);
+    /// ```
+    pub fn with_capacity(vertex_count: usize) -> Self {
+        let mut mat = SparseMatrix::init();
+
+        mat.reserve_vertices(vertex_count);
+
+        mat
+    }
+
+    /// Reserves memory for at least `additional` more vertices to be added to the storage.
+    /// Memory of the edges is not reserved, because it's allocated per cell.
+    ///
+    /// # Arguments
+    /// `additional`: Number of vertices to reserve memory for.
+    ///
+    /// # Complexity
+    /// O(|V|): If reallocation happens.
+    pub fn reserve_vertices(&mut self, additional: usize) {
+        self.adjacent_of.reserve(additional);
+    }
+
+    /// # Returns
+    /// Number of vertices that storage can hold without reallocating.
+    ///
+    /// # Complexity
+    /// O(1)
+    pub fn vertex_capacity(&self) -> usize {
+        self.adjacent_of.capacity()
+    }
+
+    /// Disables reusing ids of removed vertices and edges, so each id is used at most once during the lifetime of the storage.
+    ///
+    /// # Returns
+    /// The storage that never reuses ids.
+    pub fn without_id_reuse(mut self) -> Self {
+        self.vertex_ids.set_reuse(false);
+        self.edge_ids.set_reuse(false);
+
+        self
+    }
+
+    // Undirected edges are stored only once, so (src_id, dst_id) and (dst_id, src_id) must map to the same cell.
+    fn key_of(src_id: usize, dst_id: usize) -> (usize, usize) {
+        if Dir::is_undirected() && dst_id > src_id {
+            (dst_id, src_id)
+        } else {
+            (src_id, dst_id)
+        }
+    }
+
+    fn cell(&self, src_id: usize, dst_id: usize) -> Option<&Vec<E>> {
+        self.cells.get(&Self::key_of(src_id, dst_id))
+    }
+
+    // Removes the cell between source and destination and frees the id of edges in it.
+    fn clear_cell(&mut self, src_id: usize, dst_id: usize) {
+        if let Some(edges) = self.cells.remove(&Self::key_of(src_id, dst_id)) {
+            for edge in edges {
+                self.edge_ids.release(edge.get_id());
+            }
+        }
+    }
+
+    // Two vertices remain adjacent as long as there is an edge between them in either direction.
+    fn update_adjacency(&mut self, src_id: usize, dst_id: usize) {
+        let is_adjacent = self.cell(src_id, dst_id).is_some() || self.cell(dst_id, src_id).is_some();
+
+        if !is_adjacent {
+            if let Some(adjacents) = self.adjacent_of.get_mut(&src_id) {
+                adjacents.remove(&dst_id);
+            }
+            if let Some(adjacents) = self.adjacent_of.get_mut(&dst_id) {
+                adjacents.remove(&src_id);
+            }
+        }
+    }
+}
+
+impl<W, E: Edge<W>, Dir: EdgeDir> GraphStorage<W, E, Dir> for SparseMatrix<W, E, Dir> {
+    /// Adds a vertex to the graph.
+    ///
+    /// # Returns
+    /// Unique id of the newly added vertex.
+    ///
+    /// # Complexity
+    /// O(1)
+    fn add_vertex(&mut self) -> usize {
+        let vertex_id = self.vertex_ids.allocate();
+
+        self.adjacent_of.insert(vertex_id, BTreeSet::new());
+
+        vertex_id
+    }
+
+    /// Removes the vertex with id: `vertex_id` from graph.
+    ///
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex to be removed.
+    ///
+    /// # Complexity
+    /// O(|N<sup>*</sup>| + |E<sup>*</sup>|)
+    fn remove_vertex_unchecked(&mut self, vertex_id: usize) {
+        if let Some(adjacents) = self.adjacent_of.remove(&vertex_id) {
+            for other_id in adjacents {
+                self.clear_cell(vertex_id, other_id);
+                self.clear_cell(other_id, vertex_id);
+
+                if let Some(other_adjacents) = self.adjacent_of.get_mut(&other_id) {
+                    other_adjacents.remove(&vertex_id);
+                }
+            }
+        }
+
+        self.vertex_ids.release(vertex_id);
+    }
+
+    /// Adds `edge` from vertex with id `src_id`: to vertex with id: `dst_id`.
+    ///
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    /// * `edge`: Edge to be added from source to destination.
+    ///
+    /// # Returns
+    /// Unique id of the newly added edge.
+    ///
+    /// # Complexity
+    /// O(1)
+    fn add_edge_unchecked(&mut self, src_id: usize, dst_id: usize, mut edge: E) -> usize {
+        let edge_id = self.edge_ids.allocate();
+
+        edge.set_id(edge_id);
+
+        self.cells
+            .entry(Self::key_of(src_id, dst_id))
+            .or_insert_with(Vec::new)
+            .push(edge);
+
+        self.adjacent_of.get_mut(&src_id).unwrap().insert(dst_id);
+        self.adjacent_of.get_mut(&dst_id).unwrap().insert(src_id);
+
+        edge_id
+    }
+
+    /// Replaces the edge with id: `edge_id` with `edge`.
+    ///
+    /// # Arguments
+    /// * `src_id`: Id of source vertex.
+    /// * `dst_id`: Id of destination vertex.
+    /// * `edge_id`: Id of the to be updated edge.
+    /// * `edge`: New edge to replace the old one.
+    ///
+    /// # Complexity
+    /// O(|E<sup>*</sup>|)
+    fn update_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize, mut edge: E) {
+        if let Some(edges) = self.cells.get_mut(&Self::key_of(src_id, dst_id)) {
+            if let Some(index) = edges.iter().position(|edge| edge.get_id() == edge_id) {
+                edge.set_id(edge_id);
+                edges[index] = edge;
+            }
+        }
+    }
+
+    /// Removes the edge with id: `edge_id`.
+    ///
+    /// # Arguments
+    /// * `src_id`: Id of source vertex.
+    /// * `dst_id`: Id of destination vertex.
+    /// * `edge_id`: Id of edge to be removed.
+    ///
+    /// # Returns
+    /// * `Some`: Containing the removed edge.
+    /// * `None`: If edge with `edge_id` does not exist in the graph.
+    ///
+    /// # Complexity
+    /// O(|E<sup>*</sup>|)
+    fn remove_edge_unchecked(&mut self, src_id: usize, dst_id: usize, edge_id: usize) -> Option<E> {
+        let key = Self::key_of(src_id, dst_id);
+
+        let edges = self.cells.get_mut(&key)?;
+        let index = edges.iter().position(|edge| edge.get_id() == edge_id)?;
+        let edge = edges.swap_remove(index);
+
+        if edges.is_empty() {
+            self.cells.remove(&key);
+            self.update_adjacency(src_id, dst_id);
+        }
+
+        self.edge_ids.release(edge_id);
+
+        Some(edge)
+    }
+
+    /// # Returns
+    /// Number of vertices in the graph.
+    ///
+    /// # Complexity
+    /// O(1)
+    fn vertex_count(&self) -> usize {
+        self.adjacent_of.len()
+    }
+
+    /// # Returns
+    /// Number of edges in the graph.
+    ///
+    /// # Complexity
+    /// O(1)
+    fn edge_count(&self) -> usize {
+        self.edge_ids.count()
+    }
+
+    /// # Returns
+    /// Id of vertices that are present in the graph, in ascending order.
+    ///
+    /// # Complexity
+    /// O(|V| * log(|V|))
+    fn vertices(&self) -> Vec<usize> {
+        let mut vertices = self.adjacent_of.keys().copied().collect::<Vec<usize>>();
+
+        vertices.sort_unstable();
+
+        vertices
+    }
+
+    /// # Arguments
+    /// `src_id`: Id of the source vertex.
+    ///
+    /// # Returns
+    /// * All edges from the source vertex in the format of: (`dst_id`, `edge`), in ascending order of `dst_id`.
+    ///
+    /// # Complexity
+    /// O(|N<sup>*</sup>| * |E<sup>*</sup>|)
+    fn edges_from_unchecked(&self, src_id: usize) -> Vec<(usize, &E)> {
+        self.adjacent_of[&src_id]
+            .iter()
+            .flat_map(|dst_id| {
+                self.edges_between_unchecked(src_id, *dst_id)
+                    .into_iter()
+                    .map(move |edge| (*dst_id, edge))
+            })
+            .collect()
+    }
+
+    /// # Arguments:
+    /// `src_id`: Id of the source vertex.
+    ///
+    /// # Returns
+    /// Id of vertices accessible from source vertex using one edge, in ascending order.
+    ///
+    /// Complexity
+    /// O(|N<sup>*</sup>|)
+    fn neighbors_unchecked(&self, src_id: usize) -> Vec<usize> {
+        self.adjacent_of[&src_id]
+            .iter()
+            .filter(|dst_id| self.cell(src_id, **dst_id).is_some())
+            .copied()
+            .collect()
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of source vertex.
+    /// * `dst_id`: Id of destination vertex.
+    ///
+    /// # Returns
+    /// Edges from source vertex to destination vertex.
+    ///
+    /// # Complexity
+    /// O(|E<sup>*</sup>|)
+    fn edges_between_unchecked(&self, src_id: usize, dst_id: usize) -> Vec<&E> {
+        self.cell(src_id, dst_id)
+            .map(|edges| edges.iter().collect())
+            .unwrap_or_default()
+    }
+
+    /// # Complexity
+    /// O(1)
+    fn has_any_edge_unchecked(&self, src_id: usize, dst_id: usize) -> bool {
+        self.cell(src_id, dst_id).is_some()
+    }
+
+    fn contains_vertex(&self, vertex_id: usize) -> bool {
+        self.adjacent_of.contains_key(&vertex_id)
+    }
+
+    fn contains_edge(&self, edge_id: usize) -> bool {
+        self.edge_ids.is_allocated(edge_id)
+    }
+
+    /// Hash tables of the cells and adjacent vertices are part of the edge structure.
+    ///
+    /// # Complexity
+    /// O(|V| + |E|)
+    fn memory_usage(&self) -> MemoryUsage {
+        let payloads: usize = self.cells.values().map(|edges| edges.len() * size_of::<E>()).sum();
+        let cells: usize = self.cells.values().map(vec_bytes).sum();
+        let adjacents: usize = self.adjacent_of.values().map(set_bytes).sum();
+
+        MemoryUsage::init(
+            map_bytes(&self.cells) + cells - payloads + map_bytes(&self.adjacent_of) + adjacents,
+            self.vertex_ids.memory_usage() + self.edge_ids.memory_usage(),
+            payloads,
+        )
+    }
+}
+
+/// Two matrices are equal if they contain vertices with the same ids, and edges with the same ids, end points and values.
+impl<W, E: Edge<W> + PartialEq, Dir: EdgeDir> PartialEq for SparseMatrix<W, E, Dir> {
+    fn eq(&self, other: &Self) -> bool {
+        storage_eq(self, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directed_empty_matrix() {
+        // Given: An empty directed matrix.
+        let matrix = DiSparseMat::<usize>::init();
+
+        // When: Doing nothing.
+
+        // Then:
+        assert_eq!(matrix.edges().len(), 0);
+        assert_eq!(matrix.vertex_count(), 0);
+        assert_eq!(matrix.cells.len(), 0);
+        assert_eq!(matrix.is_directed(), true);
+    }
+
+    #[test]
+    fn undirected_empty_matrix() {
+        // Given: An empty undirected matrix.
+        let matrix = SparseMat::<usize>::init();
+
+        // When: Doing nothing.
+
+        // Then:
+        assert_eq!(matrix.edges().len(), 0);
+        assert_eq!(matrix.vertex_count(), 0);
+        assert_eq!(matrix.cells.len(), 0);
+        assert_eq!(matrix.is_directed(), false);
+    }
+
+    #[test]
+    fn with_capacity() {
+        // Given: A directed matrix with capacity for 3 vertices.
+        let mut matrix = DiSparseMat::<usize>::with_capacity(3);
+        let capacity = matrix.vertex_capacity();
+
+        // When: Adding 3 vertices.
+        for _ in 0..3 {
+            matrix.add_vertex();
+        }
+
+        // Then: Matrix must not get reallocated.
+        assert!(capacity >= 3);
+        assert_eq!(matrix.vertex_capacity(), capacity);
+        assert_eq!(matrix.vertex_count(), 3);
+    }
+
+    #[test]
+    fn reserve_vertices() {
+        // Given: Undirected matrix
+        //
+        //      a   b
+        //
+        let mut matrix = SparseMat::<usize>::init();
+        matrix.add_vertex();
+        matrix.add_vertex();
+
+        // When: Reserving memory for 2 more vertices.
+        matrix.reserve_vertices(2);
+
+        // Then:
+        assert!(matrix.vertex_capacity() >= 4);
+    }
+
+    #[test]
+    fn add_vertex_after_vertex_deletion() {
+        // Given: Directed graph
+        //
+        //      a   b   c
+        //
+        let mut matrix = DiSparseMat::<usize>::init();
+        let a = matrix.add_vertex();
+        let b = matrix.add_vertex();
+        let c = matrix.add_vertex();
+
+        // When: Removing vertices a and b and afterwards adding two new vertices.
+        matrix.remove_vertex_unchecked(a);
+        matrix.remove_vertex_unchecked(b);
+        assert_eq!(matrix.vertices(), vec![c]);
+        let _ = matrix.add_vertex();
+        let _ = matrix.add_vertex();
+
+        // Then: Vertex ids a and b must be reused.
+        assert_eq!(matrix.vertex_count(), 3);
+        assert_eq!(matrix.vertex_ids.released_count(), 0);
+        assert_eq!(matrix.vertices(), vec![a, b, c]);
+    }
+
+    #[test]
+    fn directed_add_edge() {
+        // Given: Directed matrix
+        //
+        //      a   b   c
+        //
+        let mut matrix = DiSparseMat::<usize>::init();
+        let a = matrix.add_vertex();
+        let b = matrix.add_vertex();
+        let c = matrix.add_vertex();
+
+        // When: Adding edges
+        //
+        //      a  -->  b  -->  c
+        //      ^               |
+        //      '----------------
+        //
+        matrix.add_edge_unchecked(a, b, 1.into());
+        matrix.add_edge_unchecked(b, c, 2.into());
+        matrix.add_edge_unchecked(c, a, 3.into());
+
+        // Then:
+        assert_eq!(matrix.edges().len(), 3);
+        assert_eq!(matrix.edge_count(), 3);
+        for (src_id, dst_id, edge) in matrix.edges() {
+            match (src_id, dst_id) {
+                (0, 1) => assert_eq!(edge.get_weight().unwrap(), 1),
+                (1, 2) => assert_eq!(edge.get_weight().unwrap(), 2),
+                (2, 0) => assert_eq!(edge.get_weight().unwrap(), 3),
+                _ => panic!("Unknown vertex id"),
+            }
+        }
+        assert!(matrix.has_any_edge_unchecked(a, b));
+        assert!(!matrix.has_any_edge_unchecked(b, a));
+    }
+
+    #[test]
+    fn undirected_add_edge() {
+        // Given: Undirected matrix
+        //
+        //      a   b   c
+        //
+        let mut matrix = SparseMat::<usize>::init();
+        let a = matrix.add_vertex();
+        let b = matrix.add_vertex();
+        let c = matrix.add_vertex();
+
+        // When: Adding edges
+        //
+        //      a  ---  b  ---  c
+        //      |               |
+        //      '----------------
+        //
+        matrix.add_edge_unchecked(a, b, 1.into());
+        matrix.add_edge_unchecked(b, c, 2.into());
+        matrix.add_edge_unchecked(c, a, 3.into());
+
+        // Then:
+        assert_eq!(matrix.edges().len(), 3);
+        assert_eq!(matrix.cells.len(), 3);
+        for (src_id, dst_id, edge) in matrix.edges() {
+            match (src_id, dst_id) {
+                (0, 1) | (1, 0) => assert_eq!(edge.get_weight().unwrap(), 1),
+                (1, 2) | (2, 1) => assert_eq!(edge.get_weight().unwrap(), 2),
+                (2, 0) | (0, 2) => assert_eq!(edge.get_weight().unwrap(), 3),
+                _ => panic!("Unknown vertex id"),
+            }
+        }
+        assert!(matrix.has_any_edge_unchecked(a, b));
+        assert!(matrix.has_any_edge_unchecked(b, a));
+    }
+
+    #[test]
+    fn directed_update_edge() {
+        // Given: Directed matrix
+        //
+        //      a  -->  b
+        //
+        let mut matrix = DiSparseMat::<usize>::init();
+        let a = matrix.add_vertex();
+        let b = matrix.add_vertex();
+        let ab = matrix.add_edge_unchecked(a, b, 1.into());
+
+        // When: Incrementing weight of the edge by 1.
+        matrix.update_edge_unchecked(a, b, ab, 2.into());
+
+        // Then:
+        assert_eq!(matrix.edges_between_unchecked(a, b)[0].get_weight().unwrap(), 2);
+        assert_eq!(matrix.edges_between_unchecked(a, b)[0].get_id(), ab);
+    }
+
+    #[test]
+    fn undirected_remove_edge() {
+        // Given: Undirected matrix
+        //
+        //      a  ---  b  ---  c
+        //      |               |
+        //      '----------------
+        //
+        let mut matrix = SparseMat::<usize>::init();
+        let a = matrix.add_vertex();
+        let b = matrix.add_vertex();
+        let c = matrix.add_vertex();
+        let ab = matrix.add_edge_unchecked(a, b, 1.into());
+        let bc = matrix.add_edge_unchecked(b, c, 2.into());
+        matrix.add_edge_unchecked(c, a, 3.into());
+
+        // When: Removing edges a --- b and b --- c
+        //
+        //      a   b   c
+        //      |       |
+        //      '--------
+        //
+        assert!(matrix.remove_edge_unchecked(b, a, ab).is_some());
+        assert!(matrix.remove_edge_unchecked(b, c, bc).is_some());
+
+        // Then:
+        assert_eq!(matrix.edges().len(), 1);
+        assert_eq!(matrix.edge_count(), 1);
+        assert!(matrix.neighbors_unchecked(b).is_empty());
+        assert_eq!(matrix.edges_between_unchecked(a, c)[0].get_weight().unwrap(), 3);
+    }
+
+    #[test]
+    fn directed_remove_vertex_with_edges() {
+        // Given: Directed matrix
+        //
+        //      a  -->  b  -->  c
+        //      ^               |
+        //      '----------------
+        //
+        let mut matrix = DiSparseMat::<usize>::init();
+        let a = matrix.add_vertex();
+        let b = matrix.add_vertex();
+        let c = matrix.add_vertex();
+        matrix.add_edge_unchecked(a, b, 1.into());
+        matrix.add_edge_unchecked(b, c, 2.into());
+        let ca = matrix.add_edge_unchecked(c, a, 3.into());
+
+        // When: Removing vertex b.
+        matrix.remove_vertex_unchecked(b);
+
+        // Then: Only c --> a must remain.
+        assert_eq!(matrix.vertices(), vec![a, c]);
+        assert_eq!(matrix.edge_count(), 1);
+        assert_eq!(matrix.edges().len(), 1);
+        assert!(matrix.contains_edge(ca));
+        assert!(matrix.neighbors_unchecked(a).is_empty());
+        assert_eq!(matrix.neighbors_unchecked(c), vec![a]);
+    }
+
+    #[test]
+    fn directed_neighbors() {
+        // Given: Directed matrix
+        //
+        //      a  -->  b  -->  c
+        //      ^               |
+        //      '----------------
+        //
+        let mut matrix = DiSparseMat::<usize>::init();
+        let a = matrix.add_vertex();
+        let b = matrix.add_vertex();
+        let c = matrix.add_vertex();
+        matrix.add_edge_unchecked(a, b, 1.into());
+        matrix.add_edge_unchecked(b, c, 2.into());
+        matrix.add_edge_unchecked(c, a, 3.into());
+
+        // When: Doing nothing.
+
+        // Then:
+        assert_eq!(matrix.neighbors_unchecked(a), vec![b]);
+        assert_eq!(matrix.neighbors_unchecked(b), vec![c]);
+        assert_eq!(matrix.neighbors_unchecked(c), vec![a]);
+    }
+
+    #[test]
+    fn undirected_neighbors() {
+        // Given: Undirected matrix
+        //
+        //      a  ---  b  ---  c
+        //
+        let mut matrix = SparseMat::<usize>::init();
+        let a = matrix.add_vertex();
+        let b = matrix.add_vertex();
+        let c = matrix.add_vertex();
+        matrix.add_edge_unchecked(a, b, 1.into());
+        matrix.add_edge_unchecked(b, c, 2.into());
+
+        // When: Doing nothing.
+
+        // Then:
+        assert_eq!(matrix.neighbors_unchecked(a), vec![b]);
+        assert_eq!(matrix.neighbors_unchecked(b).len(), 2);
+        assert!(vec![a, c]
+            .iter()
+            .all(|vertex_id| matrix.neighbors_unchecked(b).contains(vertex_id)));
+        assert_eq!(matrix.neighbors_unchecked(c), vec![b]);
+    }
+
+    #[test]
+    fn neighbors_in_ascending_order() {
+        // Given: Directed matrix with edges added in descending order of destination.
+        let mut matrix = DiSparseMat::<usize>::init();
+        let vertices: Vec<usize> = (0..20).map(|_| matrix.add_vertex()).collect();
+        for dst_id in vertices[1..].iter().rev() {
+            matrix.add_edge_unchecked(vertices[0], *dst_id, (*dst_id).into());
+        }
+
+        // When: Getting neighbors and edges of the source.
+        let neighbors = matrix.neighbors_unchecked(vertices[0]);
+        let edges_from = matrix.edges_from_unchecked(vertices[0]);
+
+        // Then:
+        assert_eq!(neighbors, vertices[1..].to_vec());
+        assert_eq!(
+            edges_from
+                .into_iter()
+                .map(|(dst_id, _)| dst_id)
+                .collect::<Vec<usize>>(),
+            vertices[1..].to_vec()
+        );
+    }
+
+    #[test]
+    fn memory_usage() {
+        // Given: Matrix with ten vertices and an edge.
+        let mut matrix = SparseMat::<usize>::init();
+        for _ in 0..10 {
+            matrix.add_vertex();
+        }
+        let empty_usage = matrix.memory_usage();
+        matrix.add_edge_unchecked(0, 1, 1.into());
+
+        // When: Computing memory usage.
+        let usage = matrix.memory_usage();
+
+        // Then: Only the cell of the edge is stored.
+        assert_eq!(empty_usage.payloads(), 0);
+        assert_eq!(usage.payloads(), size_of::<DefaultEdge<usize>>());
+        assert!(usage.edge_structure() > empty_usage.edge_structure());
+        assert_eq!(usage.id_sets(), 0);
+    }
+}
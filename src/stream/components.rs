@@ -0,0 +1,193 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use crate::stream::EdgeStream;
+
+/// Keeps track of connected components of an edge stream using a union-find with union by rank and path halving.
+///
+/// Direction of the edges is ignored. Memory usage is O(|V|), regardless of the number of edges.
+///
+/// # Examples
+/// ```
+/// use prepona::stream::{EdgeStream, StreamComponents};
+///
+/// let mut components = StreamComponents::init();
+/// components.process_all(vec![(0, 1), (2, 3), (1, 4)]);
+///
+/// assert_eq!(components.component_count(), 2);
+/// assert!(components.are_connected(0, 4));
+/// assert!(!components.are_connected(0, 3));
+/// ```
+#[derive(Debug, Default)]
+pub struct StreamComponents {
+    parent: HashMap<usize, usize>,
+    rank: HashMap<usize, usize>,
+
+    component_count: usize,
+}
+
+impl StreamComponents {
+    /// # Returns
+    /// Structure that has not seen any edge.
+    pub fn init() -> Self {
+        StreamComponents {
+            parent: HashMap::new(),
+            rank: HashMap::new(),
+
+            component_count: 0,
+        }
+    }
+
+    /// Starts tracking a vertex that may have no edges, as a new component.
+    ///
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    pub fn add_vertex(&mut self, vertex_id: usize) {
+        if let Entry::Vacant(entry) = self.parent.entry(vertex_id) {
+            entry.insert(vertex_id);
+            self.rank.insert(vertex_id, 0);
+
+            self.component_count += 1;
+        }
+    }
+
+    fn find(&mut self, mut vertex_id: usize) -> usize {
+        while self.parent[&vertex_id] != vertex_id {
+            let grandparent_id = self.parent[&self.parent[&vertex_id]];
+            self.parent.insert(vertex_id, grandparent_id);
+
+            vertex_id = grandparent_id;
+        }
+
+        vertex_id
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// * `Some`: Containing id of the representative vertex of the component that vertex belongs to.
+    /// * `None`: If vertex has not been seen.
+    pub fn component_of(&mut self, vertex_id: usize) -> Option<usize> {
+        if self.parent.contains_key(&vertex_id) {
+            Some(self.find(vertex_id))
+        } else {
+            None
+        }
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// `true` if both vertices have been seen and they are in the same component, `false` otherwise.
+    pub fn are_connected(&mut self, src_id: usize, dst_id: usize) -> bool {
+        match (self.component_of(src_id), self.component_of(dst_id)) {
+            (Some(src_root), Some(dst_root)) => src_root == dst_root,
+            _ => false,
+        }
+    }
+
+    /// # Returns
+    /// Number of components.
+    pub fn component_count(&self) -> usize {
+        self.component_count
+    }
+
+    /// # Returns
+    /// Number of vertices that have been seen.
+    pub fn vertex_count(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// # Returns
+    /// Vertices of each component. Vertices of each component, and components by their smallest vertex, are in ascending order.
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut vertices = self.parent.keys().copied().collect::<Vec<usize>>();
+        vertices.sort_unstable();
+
+        let mut index_of = HashMap::new();
+        let mut components: Vec<Vec<usize>> = vec![];
+        for vertex_id in vertices {
+            let root_id = self.find(vertex_id);
+
+            let index = *index_of.entry(root_id).or_insert_with(|| {
+                components.push(vec![]);
+                components.len() - 1
+            });
+            components[index].push(vertex_id);
+        }
+
+        components
+    }
+}
+
+impl EdgeStream for StreamComponents {
+    fn process(&mut self, src_id: usize, dst_id: usize) {
+        self.add_vertex(src_id);
+        self.add_vertex(dst_id);
+
+        let src_root = self.find(src_id);
+        let dst_root = self.find(dst_id);
+        if src_root == dst_root {
+            return;
+        }
+
+        let src_rank = self.rank[&src_root];
+        let dst_rank = self.rank[&dst_root];
+        if src_rank < dst_rank {
+            self.parent.insert(src_root, dst_root);
+        } else {
+            self.parent.insert(dst_root, src_root);
+
+            if src_rank == dst_rank {
+                self.rank.insert(src_root, src_rank + 1);
+            }
+        }
+
+        self.component_count -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_stream() {
+        let mut components = StreamComponents::init();
+
+        assert_eq!(components.component_count(), 0);
+        assert!(components.components().is_empty());
+        assert!(components.component_of(0).is_none());
+    }
+
+    #[test]
+    fn merging_components() {
+        // Given: Stream that first builds two paths, then connects them.
+        //
+        //      0  ---  1  ---  2       3  ---  4       5
+        //
+        let mut components = StreamComponents::init();
+        components.add_vertex(5);
+        components.process_all(vec![(0, 1), (1, 2), (4, 3), (2, 0)]);
+
+        // Then:
+        assert_eq!(components.component_count(), 3);
+        assert_eq!(
+            components.components(),
+            vec![vec![0, 1, 2], vec![3, 4], vec![5]]
+        );
+
+        // When:
+        components.process(4, 1);
+
+        // Then:
+        assert_eq!(components.component_count(), 2);
+        assert_eq!(components.vertex_count(), 6);
+        assert!(components.are_connected(0, 3));
+        assert!(!components.are_connected(0, 5));
+        assert!(!components.are_connected(0, 6));
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use crate::stream::EdgeStream;
+
+/// Counts degree of each vertex of an edge stream.
+///
+/// Memory usage is O(|V|), regardless of the number of edges.
+///
+/// # Examples
+/// ```
+/// use prepona::stream::{DegreeCounter, EdgeStream};
+///
+/// let mut degrees = DegreeCounter::init().directed(true);
+/// degrees.process_all(vec![(0, 1), (0, 2), (2, 1)]);
+///
+/// assert_eq!(degrees.out_degree_of(0), 2);
+/// assert_eq!(degrees.in_degree_of(1), 2);
+/// assert_eq!(degrees.degree_of(2), 2);
+/// assert_eq!(degrees.edge_count(), 3);
+/// ```
+#[derive(Debug, Default)]
+pub struct DegreeCounter {
+    // Number of edges going out of and coming into each vertex. Both are the same for undirected streams.
+    degrees_of: HashMap<usize, (usize, usize)>,
+
+    edge_count: usize,
+    is_directed: bool,
+}
+
+impl DegreeCounter {
+    /// # Returns
+    /// Counter for an undirected stream that has not seen any edge.
+    pub fn init() -> Self {
+        DegreeCounter {
+            degrees_of: HashMap::new(),
+
+            edge_count: 0,
+            is_directed: false,
+        }
+    }
+
+    /// # Arguments
+    /// `is_directed`: Whether edges of the stream are directed.
+    ///
+    /// # Returns
+    /// The counter for a directed or undirected stream.
+    pub fn directed(mut self, is_directed: bool) -> Self {
+        self.is_directed = is_directed;
+
+        self
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Number of edges that are connected to the vertex. Loops are counted twice in undirected streams. Zero if vertex has not been seen.
+    pub fn degree_of(&self, vertex_id: usize) -> usize {
+        match self.degrees_of.get(&vertex_id) {
+            Some((out_degree, in_degree)) if self.is_directed => out_degree + in_degree,
+            Some((degree, _)) => *degree,
+            None => 0,
+        }
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Number of edges that go out of the vertex. Same as [`degree_of`](crate::stream::DegreeCounter::degree_of) in undirected streams.
+    pub fn out_degree_of(&self, vertex_id: usize) -> usize {
+        match self.degrees_of.get(&vertex_id) {
+            Some((out_degree, _)) => *out_degree,
+            None => 0,
+        }
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// Number of edges that come into the vertex. Same as [`degree_of`](crate::stream::DegreeCounter::degree_of) in undirected streams.
+    pub fn in_degree_of(&self, vertex_id: usize) -> usize {
+        match self.degrees_of.get(&vertex_id) {
+            Some((_, in_degree)) => *in_degree,
+            None => 0,
+        }
+    }
+
+    /// # Returns
+    /// Degree of each vertex that has been seen, keyed by id of the vertex.
+    pub fn degrees(&self) -> HashMap<usize, usize> {
+        self.degrees_of
+            .keys()
+            .map(|vertex_id| (*vertex_id, self.degree_of(*vertex_id)))
+            .collect()
+    }
+
+    /// # Returns
+    /// * `Some`: Containing id and degree of the vertex with the largest degree. Ties are broken by the smaller id.
+    /// * `None`: If no edge has been seen.
+    pub fn max_degree(&self) -> Option<(usize, usize)> {
+        self.degrees_of
+            .keys()
+            .map(|vertex_id| (*vertex_id, self.degree_of(*vertex_id)))
+            .max_by(|(lhs_id, lhs), (rhs_id, rhs)| lhs.cmp(rhs).then(rhs_id.cmp(lhs_id)))
+    }
+
+    /// # Returns
+    /// Number of vertices that have been seen.
+    pub fn vertex_count(&self) -> usize {
+        self.degrees_of.len()
+    }
+
+    /// # Returns
+    /// Number of edges that have been seen.
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+}
+
+impl EdgeStream for DegreeCounter {
+    fn process(&mut self, src_id: usize, dst_id: usize) {
+        self.edge_count += 1;
+
+        if self.is_directed {
+            self.degrees_of.entry(src_id).or_default().0 += 1;
+            self.degrees_of.entry(dst_id).or_default().1 += 1;
+        } else {
+            for vertex_id in [src_id, dst_id].iter() {
+                let degrees = self.degrees_of.entry(*vertex_id).or_default();
+                degrees.0 += 1;
+                degrees.1 += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_stream() {
+        let degrees = DegreeCounter::init();
+
+        assert_eq!(degrees.vertex_count(), 0);
+        assert_eq!(degrees.degree_of(0), 0);
+        assert!(degrees.max_degree().is_none());
+    }
+
+    #[test]
+    fn undirected_stream() {
+        // Given: Stream of a star with center 5, and a loop on vertex 1.
+        let edges = vec![(5, 1), (2, 5), (5, 3), (1, 1)];
+
+        // When:
+        let mut degrees = DegreeCounter::init();
+        degrees.process_all(edges);
+
+        // Then:
+        assert_eq!(degrees.vertex_count(), 4);
+        assert_eq!(degrees.edge_count(), 4);
+        assert_eq!(degrees.degree_of(5), 3);
+        assert_eq!(degrees.degree_of(1), 3);
+        assert_eq!(degrees.in_degree_of(2), 1);
+        assert_eq!(degrees.max_degree(), Some((1, 3)));
+        assert_eq!(degrees.degrees().values().sum::<usize>(), 8);
+    }
+
+    #[test]
+    fn directed_stream() {
+        // Given: Stream of a directed cycle 0 -> 1 -> 2 -> 0 and an extra edge 0 -> 2.
+        let edges = vec![(0, 1), (1, 2), (2, 0), (0, 2)];
+
+        // When:
+        let mut degrees = DegreeCounter::init().directed(true);
+        degrees.process_all(edges);
+
+        // Then:
+        assert_eq!(degrees.out_degree_of(0), 2);
+        assert_eq!(degrees.in_degree_of(0), 1);
+        assert_eq!(degrees.in_degree_of(2), 2);
+        assert_eq!(degrees.degree_of(1), 2);
+        assert_eq!(degrees.max_degree(), Some((0, 3)));
+    }
+}
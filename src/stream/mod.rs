@@ -0,0 +1,68 @@
+mod components;
+mod degrees;
+mod triangles;
+
+pub use components::StreamComponents;
+pub use degrees::DegreeCounter;
+pub use triangles::TriangleEstimator;
+
+use std::io::BufRead;
+
+use anyhow::Result;
+
+use crate::io::parse_line;
+
+/// Algorithm that sees the edges of a graph one at a time, and keeps a summary of them instead of the whole graph.
+pub trait EdgeStream {
+    /// Updates the summary with an edge.
+    ///
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    fn process(&mut self, src_id: usize, dst_id: usize);
+
+    /// Updates the summary with each edge of `edges` in order.
+    ///
+    /// # Arguments
+    /// `edges`: Edges in the format of: (`src_id`, `dst_id`).
+    fn process_all<I: IntoIterator<Item = (usize, usize)>>(&mut self, edges: I)
+    where
+        Self: Sized,
+    {
+        for (src_id, dst_id) in edges {
+            self.process(src_id, dst_id);
+        }
+    }
+}
+
+/// Reads edges of an edge list lazily, in the same format as [`EdgeListLoader`](crate::io::EdgeListLoader). Weights and extra columns are ignored.
+///
+/// # Arguments
+/// `reader`: Reader of the edge list.
+///
+/// # Returns
+/// Iterator over edges in the format of: `Ok((src_id, dst_id))`. Ids are the ones written in the file.
+/// Yields an `Err` for each line that is not a valid edge, with kind of [`InvalidFormat`](crate::graph::ErrorKind::InvalidFormat), and for each error that reading from `reader` returns.
+///
+/// # Examples
+/// ```
+/// use prepona::stream::{read_edges, DegreeCounter, EdgeStream};
+///
+/// let file = "# FromNodeId\tToNodeId\n10\t20\n20\t30\n";
+///
+/// let mut degrees = DegreeCounter::init();
+/// degrees.process_all(read_edges(file.as_bytes()).map(|edge| edge.unwrap()));
+///
+/// assert_eq!(degrees.degree_of(20), 2);
+/// ```
+pub fn read_edges<R: BufRead>(reader: R) -> impl Iterator<Item = Result<(usize, usize)>> {
+    reader
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| match line {
+            Ok(line) => parse_line::<f64>(index + 1, &line)
+                .map(|edge| edge.map(|(src_id, dst_id, _)| (src_id, dst_id)))
+                .transpose(),
+            Err(error) => Some(Err(error.into())),
+        })
+}
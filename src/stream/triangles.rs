@@ -0,0 +1,202 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+
+use crate::stream::EdgeStream;
+
+/// Estimates number of triangles of an undirected edge stream using a fixed size sample of edges(TRIÈST-base).
+///
+/// Each edge is kept in the sample with probability of `capacity / t`, where t is the number of edges seen so far, by evicting a random edge of the sample.
+/// Triangles that are closed inside the sample are counted, and the count is scaled up by the inverse of the probability of a triangle being in the sample.
+/// The estimate is unbiased, and it's exact while the number of edges is not more than `capacity`.
+///
+/// Stream must not contain the same edge twice. Loops are ignored. Memory usage is O(`capacity`).
+///
+/// # Examples
+/// ```
+/// use prepona::stream::{EdgeStream, TriangleEstimator};
+/// use prepona::util::seeded;
+///
+/// // Two triangles sharing the edge (1, 2).
+/// let mut triangles = TriangleEstimator::init(100, seeded(7));
+/// triangles.process_all(vec![(0, 1), (1, 2), (2, 0), (1, 3), (3, 2)]);
+///
+/// assert_eq!(triangles.estimate(), 2.0);
+/// ```
+pub struct TriangleEstimator<R> {
+    capacity: usize,
+    rng: R,
+
+    sample: Vec<(usize, usize)>,
+    neighbors_of: HashMap<usize, HashSet<usize>>,
+
+    // Number of edges seen so far, and number of triangles in the sample.
+    edge_count: usize,
+    triangle_count: usize,
+}
+
+impl<R: FnMut(usize) -> usize> TriangleEstimator<R> {
+    /// # Arguments
+    /// * `capacity`: Maximum number of edges kept in the sample. Estimates get more accurate as it grows. It's at least three.
+    /// * `rng`: Source of randomness: when called with `n`, returns a uniformly random number in range of 0 to `n` - 1.
+    ///   Use [`seeded`](crate::util::seeded) for reproducible results, or `util::from_rng` with the `rand` feature.
+    ///
+    /// # Returns
+    /// Estimator that has not seen any edge.
+    pub fn init(capacity: usize, rng: R) -> Self {
+        TriangleEstimator {
+            capacity: capacity.max(3),
+            rng,
+
+            sample: vec![],
+            neighbors_of: HashMap::new(),
+
+            edge_count: 0,
+            triangle_count: 0,
+        }
+    }
+
+    // Number of triangles that edge closes with the edges of the sample.
+    fn closed_count(&self, src_id: usize, dst_id: usize) -> usize {
+        match (
+            self.neighbors_of.get(&src_id),
+            self.neighbors_of.get(&dst_id),
+        ) {
+            (Some(src_neighbors), Some(dst_neighbors)) => {
+                src_neighbors.intersection(dst_neighbors).count()
+            }
+            _ => 0,
+        }
+    }
+
+    fn remove_from_sample(&mut self, index: usize) {
+        let (src_id, dst_id) = self.sample.swap_remove(index);
+
+        self.remove_neighbor(src_id, dst_id);
+        self.remove_neighbor(dst_id, src_id);
+        self.triangle_count -= self.closed_count(src_id, dst_id);
+    }
+
+    // Vertices without any edge in the sample are dropped, so memory usage does not grow with the number of vertices of the stream.
+    fn remove_neighbor(&mut self, src_id: usize, dst_id: usize) {
+        if let Entry::Occupied(mut entry) = self.neighbors_of.entry(src_id) {
+            entry.get_mut().remove(&dst_id);
+
+            if entry.get().is_empty() {
+                entry.remove();
+            }
+        }
+    }
+
+    /// # Returns
+    /// Estimated number of triangles in the edges seen so far.
+    pub fn estimate(&self) -> f64 {
+        let t = self.edge_count as f64;
+        let m = self.capacity as f64;
+
+        let scale = (t * (t - 1.0) * (t - 2.0)) / (m * (m - 1.0) * (m - 2.0));
+
+        self.triangle_count as f64 * scale.max(1.0)
+    }
+
+    /// # Returns
+    /// Number of edges seen so far, without the loops.
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+}
+
+impl<R: FnMut(usize) -> usize> EdgeStream for TriangleEstimator<R> {
+    fn process(&mut self, src_id: usize, dst_id: usize) {
+        if src_id == dst_id {
+            return;
+        }
+
+        self.edge_count += 1;
+
+        if self.edge_count > self.capacity {
+            if (self.rng)(self.edge_count) >= self.capacity {
+                return;
+            }
+
+            let index = (self.rng)(self.capacity);
+            self.remove_from_sample(index);
+        }
+
+        self.triangle_count += self.closed_count(src_id, dst_id);
+        self.sample.push((src_id, dst_id));
+        self.neighbors_of.entry(src_id).or_default().insert(dst_id);
+        self.neighbors_of.entry(dst_id).or_default().insert(src_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::seeded;
+
+    // Edges of the complete graph with `n` vertices, which has n * (n - 1) * (n - 2) / 6 triangles.
+    fn complete_graph(n: usize) -> Vec<(usize, usize)> {
+        (0..n)
+            .flat_map(|src_id| (src_id + 1..n).map(move |dst_id| (src_id, dst_id)))
+            .collect()
+    }
+
+    #[test]
+    fn empty_stream() {
+        let triangles = TriangleEstimator::init(10, seeded(1));
+
+        assert_eq!(triangles.estimate(), 0.0);
+    }
+
+    #[test]
+    fn exact_while_stream_fits() {
+        // Given: Complete graph with 6 vertices, which has 15 edges and 20 triangles, and a loop.
+        let mut edges = complete_graph(6);
+        edges.push((2, 2));
+
+        // When:
+        let mut triangles = TriangleEstimator::init(15, seeded(7));
+        triangles.process_all(edges);
+
+        // Then:
+        assert_eq!(triangles.edge_count(), 15);
+        assert_eq!(triangles.estimate(), 20.0);
+    }
+
+    #[test]
+    fn estimate_of_larger_stream() {
+        // Given: Complete graph with 20 vertices, which has 190 edges and 1140 triangles.
+        let edges = complete_graph(20);
+
+        // When: Averaging estimates of independent runs that keep about half of the edges.
+        let run_count = 50;
+        let average = (0..run_count)
+            .map(|seed| {
+                let mut triangles = TriangleEstimator::init(100, seeded(seed));
+                triangles.process_all(edges.clone());
+                triangles.estimate()
+            })
+            .sum::<f64>()
+            / run_count as f64;
+
+        // Then:
+        assert!((average - 1140.0).abs() / 1140.0 < 0.15);
+    }
+
+    #[test]
+    fn memory_is_bounded_by_capacity() {
+        // Given: Stream of edges that do not share any vertex.
+        let edges = (0..1000)
+            .map(|i| (2 * i, 2 * i + 1))
+            .collect::<Vec<(usize, usize)>>();
+
+        // When:
+        let mut triangles = TriangleEstimator::init(10, seeded(3));
+        triangles.process_all(edges);
+
+        // Then: Only end points of the sampled edges are kept.
+        assert_eq!(triangles.sample.len(), 10);
+        assert_eq!(triangles.neighbors_of.len(), 20);
+        assert_eq!(triangles.estimate(), 0.0);
+    }
+}
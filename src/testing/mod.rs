@@ -0,0 +1,323 @@
+mod model;
+mod mutator;
+
+pub use model::Model;
+pub use mutator::{Op, RandomMutator};
+
+use std::fmt::Debug;
+
+use crate::graph::{Edge, EdgeDir};
+use crate::storage::GraphStorage;
+
+/// Applies `n_ops` random operations on `storage` and on a [`Model`](crate::testing::Model) of it, and checks that they behave the same after each operation.
+///
+/// After each operation vertices, edges, edges from each vertex, neighbors of each vertex and edges between each pair of vertices are compared.
+/// Ids returned by the storage must be unique among the present vertices and edges, but they can be any number.
+///
+/// # Arguments
+/// * `storage`: Storage under test. It can contain vertices and edges before the test.
+/// * `n_ops`: Number of operations to apply.
+/// * `rng`: Source of randomness: when called with `n`, returns a uniformly random number in range of 0 to `n` - 1.
+///   Use [`seeded`](crate::util::seeded) for reproducible results, or `util::from_rng` with the `rand` feature.
+///
+/// # Panics
+/// If storage does not behave like the model. The panic message contains the index of the operation and the operation itself.
+///
+/// # Examples
+/// ```
+/// use prepona::storage::DiList;
+/// use prepona::testing::check_storage;
+/// use prepona::util::seeded;
+///
+/// check_storage(&mut DiList::<usize>::init(), 200, seeded(7));
+/// ```
+pub fn check_storage<W, E, Dir, S, R>(storage: &mut S, n_ops: usize, rng: R)
+where
+    W: From<u8> + Clone + PartialEq + Debug,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    S: GraphStorage<W, E, Dir>,
+    R: FnMut(usize) -> usize,
+{
+    let mut model = Model::from_storage(storage);
+    let mut mutator = RandomMutator::init(rng);
+
+    for op_index in 0..n_ops {
+        let op = mutator.next_op(&model);
+        let context = format!("after op #{}: {:?}", op_index, op);
+
+        apply_op(storage, &mut model, &op, &context);
+        assert_same(storage, &model, &context);
+    }
+}
+
+fn apply_op<W, E, Dir, S>(storage: &mut S, model: &mut Model<W>, op: &Op<W>, context: &str)
+where
+    W: Clone + PartialEq + Debug,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    S: GraphStorage<W, E, Dir>,
+{
+    match op {
+        Op::AddVertex => {
+            let vertex_id = storage.add_vertex();
+            assert!(
+                !model.contains_vertex(vertex_id),
+                "id of an existing vertex is returned {}",
+                context
+            );
+
+            model.add_vertex(vertex_id);
+        }
+        Op::RemoveVertex(vertex_id) => {
+            let result = storage.remove_vertex(*vertex_id);
+
+            if model.contains_vertex(*vertex_id) {
+                assert!(result.is_ok(), "vertex is not removed {}", context);
+                model.remove_vertex(*vertex_id);
+            } else {
+                assert!(result.is_err(), "missing vertex is removed {}", context);
+            }
+        }
+        Op::AddEdge(src_id, dst_id, weight) => {
+            let result = storage.add_edge(*src_id, *dst_id, E::init(weight.clone()));
+
+            if model.contains_vertex(*src_id) && model.contains_vertex(*dst_id) {
+                let edge_id = result.unwrap_or_else(|_| panic!("edge is not added {}", context));
+                assert!(
+                    !model.contains_edge(edge_id),
+                    "id of an existing edge is returned {}",
+                    context
+                );
+
+                model.add_edge(*src_id, *dst_id, edge_id, weight.clone());
+            } else {
+                assert!(
+                    result.is_err(),
+                    "edge to missing vertex is added {}",
+                    context
+                );
+            }
+        }
+        Op::UpdateEdge(src_id, dst_id, edge_id, weight) => {
+            let result = storage.update_edge(*src_id, *dst_id, *edge_id, E::init(weight.clone()));
+            assert!(result.is_ok(), "edge is not updated {}", context);
+
+            model.update_edge(*edge_id, weight.clone());
+        }
+        Op::RemoveEdge(src_id, dst_id, edge_id) => {
+            let edge = storage
+                .remove_edge(*src_id, *dst_id, *edge_id)
+                .ok()
+                .flatten()
+                .unwrap_or_else(|| panic!("edge is not removed {}", context));
+
+            assert_eq!(edge.get_id(), *edge_id, "wrong edge is removed {}", context);
+            assert_eq!(
+                Some(edge.get_weight()),
+                model.edge(*edge_id).map(|(_, _, weight)| weight),
+                "removed edge has wrong weight {}",
+                context
+            );
+
+            model.remove_edge(*edge_id);
+        }
+    }
+}
+
+fn assert_same<W, E, Dir, S>(storage: &S, model: &Model<W>, context: &str)
+where
+    W: Clone + PartialEq + Debug,
+    E: Edge<W>,
+    Dir: EdgeDir,
+    S: GraphStorage<W, E, Dir>,
+{
+    let vertices = model.vertices();
+
+    let mut storage_vertices = storage.vertices();
+    storage_vertices.sort_unstable();
+    assert_eq!(storage_vertices, vertices, "vertices differ {}", context);
+    assert_eq!(
+        storage.vertex_count(),
+        vertices.len(),
+        "vertex count differs {}",
+        context
+    );
+
+    let mut storage_edges = storage
+        .edges()
+        .into_iter()
+        .map(|(src_id, dst_id, edge)| (src_id, dst_id, edge.get_id(), edge.get_weight().clone()))
+        .collect::<Vec<_>>();
+    storage_edges.sort_unstable_by_key(|(_, _, edge_id, _)| *edge_id);
+    assert_eq!(storage_edges, model.edges(), "edges differ {}", context);
+    assert_eq!(
+        storage.edge_count(),
+        storage_edges.len(),
+        "edge count differs {}",
+        context
+    );
+
+    for (_, _, edge_id, weight) in model.edges() {
+        assert!(
+            storage.contains_edge(edge_id),
+            "edge {} is missing {}",
+            edge_id,
+            context
+        );
+
+        let edge = storage.edge_unchecked(edge_id);
+        assert_eq!(
+            edge.map(|edge| (edge.get_id(), edge.get_weight())),
+            Some((edge_id, &weight)),
+            "edge {} differs {}",
+            edge_id,
+            context
+        );
+    }
+
+    for src_id in &vertices {
+        let mut edges_from = storage
+            .edges_from_unchecked(*src_id)
+            .into_iter()
+            .map(|(dst_id, edge)| (dst_id, edge.get_id()))
+            .collect::<Vec<(usize, usize)>>();
+        edges_from.sort_unstable();
+        assert_eq!(
+            edges_from,
+            model.edges_from(*src_id),
+            "edges from {} differ {}",
+            src_id,
+            context
+        );
+
+        let mut neighbors = storage.neighbors_unchecked(*src_id);
+        neighbors.sort_unstable();
+        neighbors.dedup();
+        assert_eq!(
+            neighbors,
+            model.neighbors(*src_id),
+            "neighbors of {} differ {}",
+            src_id,
+            context
+        );
+
+        for dst_id in &vertices {
+            let mut edges_between = storage
+                .edges_between_unchecked(*src_id, *dst_id)
+                .into_iter()
+                .map(|edge| edge.get_id())
+                .collect::<Vec<usize>>();
+            edges_between.sort_unstable();
+            let expected = model.edges_between(*src_id, *dst_id);
+
+            assert_eq!(
+                storage.has_any_edge_unchecked(*src_id, *dst_id),
+                !expected.is_empty(),
+                "existence of edge from {} to {} differs {}",
+                src_id,
+                dst_id,
+                context
+            );
+            assert_eq!(
+                edges_between, expected,
+                "edges from {} to {} differ {}",
+                src_id, dst_id, context
+            );
+        }
+    }
+
+    let missing_id = vertices.last().map_or(0, |vertex_id| vertex_id + 1);
+    assert!(
+        !storage.contains_vertex(missing_id),
+        "vertex {} is present {}",
+        missing_id,
+        context
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{DiList, DiMat, DiSparseMat, List, Mat, SparseMat};
+    use crate::util::seeded;
+
+    const N_OPS: usize = 200;
+
+    #[test]
+    fn adj_matrix() {
+        for seed in 0..2 {
+            check_storage(&mut Mat::<usize>::init(), N_OPS, seeded(seed));
+            check_storage(&mut DiMat::<usize>::init(), N_OPS, seeded(seed));
+        }
+    }
+
+    #[test]
+    fn adj_list() {
+        for seed in 0..2 {
+            check_storage(&mut List::<usize>::init(), N_OPS, seeded(seed));
+            check_storage(&mut DiList::<usize>::init(), N_OPS, seeded(seed));
+        }
+    }
+
+    #[test]
+    fn sparse_matrix() {
+        for seed in 0..2 {
+            check_storage(&mut SparseMat::<usize>::init(), N_OPS, seeded(seed));
+            check_storage(&mut DiSparseMat::<usize>::init(), N_OPS, seeded(seed));
+        }
+    }
+
+    #[test]
+    fn without_id_reuse() {
+        check_storage(
+            &mut DiMat::<usize>::init().without_id_reuse(),
+            N_OPS,
+            seeded(11),
+        );
+        check_storage(
+            &mut List::<usize>::init().without_id_reuse(),
+            N_OPS,
+            seeded(11),
+        );
+    }
+
+    #[test]
+    fn non_empty_storage() {
+        // Given: Storage with two vertices and an edge between them.
+        let mut storage = Mat::<usize>::init();
+        let a = storage.add_vertex();
+        let b = storage.add_vertex();
+        storage.add_edge_unchecked(a, b, 1.into());
+
+        // Then: Model starts from the state of the storage.
+        let model = Model::from_storage(&storage);
+        assert_eq!(model.vertices(), vec![a, b]);
+        assert_eq!(model.edges_between(b, a).len(), 1);
+
+        check_storage(&mut storage, N_OPS, seeded(5));
+    }
+
+    #[test]
+    fn mutator_respects_max_vertex_count() {
+        // Given: Model and a mutator that allows at most 3 vertices.
+        let mut model = Model::<usize>::init(true);
+        let mut mutator = RandomMutator::init(seeded(3)).with_max_vertex_count(3);
+
+        // When: Applying only vertex additions and removals.
+        let mut next_id = 0;
+        for _ in 0..200 {
+            match mutator.next_op(&model) {
+                Op::AddVertex => {
+                    model.add_vertex(next_id);
+                    next_id += 1;
+                }
+                Op::RemoveVertex(vertex_id) => model.remove_vertex(vertex_id),
+                _ => {}
+            }
+
+            // Then:
+            assert!(model.vertices().len() <= 3);
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use magnitude::Magnitude;
+
+use crate::graph::{Edge, EdgeDir};
+use crate::storage::GraphStorage;
+
+/// Naive reference model of a storage, which keeps vertices in a set and edges in a map from their ids to their end points and weights.
+///
+/// The model does not allocate ids. Instead ids that are returned by the storage under test are passed to it, so the two can be compared.
+/// End points of undirected edges are stored so that `src_id` <= `dst_id`, matching the order that [`edges`](crate::storage::GraphStorage::edges) returns them.
+///
+/// ## Generic Parameters
+/// `W`: **W**eight type associated with edges.
+#[derive(Debug, Clone)]
+pub struct Model<W> {
+    directed: bool,
+
+    vertices: BTreeSet<usize>,
+    edges: BTreeMap<usize, (usize, usize, Magnitude<W>)>,
+}
+
+impl<W: Clone> Model<W> {
+    /// # Arguments
+    /// `directed`: Whether edges of the model are directed.
+    ///
+    /// # Returns
+    /// Empty model.
+    pub fn init(directed: bool) -> Self {
+        Model {
+            directed,
+
+            vertices: BTreeSet::new(),
+            edges: BTreeMap::new(),
+        }
+    }
+
+    /// # Arguments
+    /// `storage`: Storage to copy vertices and edges from.
+    ///
+    /// # Returns
+    /// Model with the same vertices and edges as `storage`.
+    pub fn from_storage<E, Dir, S>(storage: &S) -> Self
+    where
+        E: Edge<W>,
+        Dir: EdgeDir,
+        S: GraphStorage<W, E, Dir>,
+    {
+        let mut model = Model::init(Dir::is_directed());
+
+        for vertex_id in storage.vertices() {
+            model.add_vertex(vertex_id);
+        }
+
+        for (src_id, dst_id, edge) in storage.edges() {
+            model.add_edge(src_id, dst_id, edge.get_id(), edge.get_weight().clone());
+        }
+
+        model
+    }
+
+    /// # Returns
+    /// `true` if edges of the model are directed, `false` otherwise.
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// # Returns
+    /// Id of vertices in ascending order.
+    pub fn vertices(&self) -> Vec<usize> {
+        self.vertices.iter().copied().collect()
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex.
+    ///
+    /// # Returns
+    /// `true` if model contains the vertex, `false` otherwise.
+    pub fn contains_vertex(&self, vertex_id: usize) -> bool {
+        self.vertices.contains(&vertex_id)
+    }
+
+    /// # Arguments
+    /// `edge_id`: Id of the edge.
+    ///
+    /// # Returns
+    /// `true` if model contains the edge, `false` otherwise.
+    pub fn contains_edge(&self, edge_id: usize) -> bool {
+        self.edges.contains_key(&edge_id)
+    }
+
+    /// # Returns
+    /// All edges in the format of: (`src_id`, `dst_id`, `edge_id`, `weight`), sorted by their ids.
+    pub fn edges(&self) -> Vec<(usize, usize, usize, Magnitude<W>)> {
+        self.edges
+            .iter()
+            .map(|(edge_id, (src_id, dst_id, weight))| (*src_id, *dst_id, *edge_id, weight.clone()))
+            .collect()
+    }
+
+    /// # Arguments
+    /// `edge_id`: Id of the edge.
+    ///
+    /// # Returns
+    /// * `Some`: Containing end points and weight of the edge in the format of: (`src_id`, `dst_id`, `weight`).
+    /// * `None`: If edge does not exist.
+    pub fn edge(&self, edge_id: usize) -> Option<(usize, usize, &Magnitude<W>)> {
+        self.edges
+            .get(&edge_id)
+            .map(|(src_id, dst_id, weight)| (*src_id, *dst_id, weight))
+    }
+
+    /// # Arguments
+    /// `src_id`: Id of the source vertex.
+    ///
+    /// # Returns
+    /// Edges from the source vertex in the format of: (`dst_id`, `edge_id`), sorted.
+    /// In undirected models edges from any end point are included.
+    pub fn edges_from(&self, src_id: usize) -> Vec<(usize, usize)> {
+        let mut edges = self
+            .edges
+            .iter()
+            .filter_map(|(edge_id, (s_id, d_id, _))| {
+                if *s_id == src_id {
+                    Some((*d_id, *edge_id))
+                } else if !self.directed && *d_id == src_id {
+                    Some((*s_id, *edge_id))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<(usize, usize)>>();
+
+        edges.sort_unstable();
+
+        edges
+    }
+
+    /// # Arguments
+    /// `src_id`: Id of the source vertex.
+    ///
+    /// # Returns
+    /// Id of vertices accessible from the source vertex using one edge, in ascending order and without duplicates.
+    pub fn neighbors(&self, src_id: usize) -> Vec<usize> {
+        let mut neighbors = self
+            .edges_from(src_id)
+            .into_iter()
+            .map(|(dst_id, _)| dst_id)
+            .collect::<Vec<usize>>();
+
+        neighbors.dedup();
+
+        neighbors
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    ///
+    /// # Returns
+    /// Id of edges from source to destination in ascending order.
+    pub fn edges_between(&self, src_id: usize, dst_id: usize) -> Vec<usize> {
+        let (src_id, dst_id) = self.end_points(src_id, dst_id);
+
+        self.edges
+            .iter()
+            .filter(|(_, (s_id, d_id, _))| *s_id == src_id && *d_id == dst_id)
+            .map(|(edge_id, _)| *edge_id)
+            .collect()
+    }
+
+    /// # Arguments
+    /// `vertex_id`: Id of the added vertex.
+    pub fn add_vertex(&mut self, vertex_id: usize) {
+        self.vertices.insert(vertex_id);
+    }
+
+    /// Removes the vertex and all edges connected to it.
+    ///
+    /// # Arguments
+    /// `vertex_id`: Id of the vertex to be removed.
+    pub fn remove_vertex(&mut self, vertex_id: usize) {
+        self.vertices.remove(&vertex_id);
+        self.edges
+            .retain(|_, (src_id, dst_id, _)| *src_id != vertex_id && *dst_id != vertex_id);
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    /// * `edge_id`: Id of the added edge.
+    /// * `weight`: Weight of the added edge.
+    pub fn add_edge(&mut self, src_id: usize, dst_id: usize, edge_id: usize, weight: Magnitude<W>) {
+        let (src_id, dst_id) = self.end_points(src_id, dst_id);
+
+        self.edges.insert(edge_id, (src_id, dst_id, weight));
+    }
+
+    /// # Arguments
+    /// * `edge_id`: Id of the edge to be updated.
+    /// * `weight`: New weight of the edge.
+    pub fn update_edge(&mut self, edge_id: usize, weight: Magnitude<W>) {
+        if let Some((_, _, old_weight)) = self.edges.get_mut(&edge_id) {
+            *old_weight = weight;
+        }
+    }
+
+    /// # Arguments
+    /// `edge_id`: Id of the edge to be removed.
+    pub fn remove_edge(&mut self, edge_id: usize) {
+        self.edges.remove(&edge_id);
+    }
+
+    fn end_points(&self, src_id: usize, dst_id: usize) -> (usize, usize) {
+        if self.directed || src_id <= dst_id {
+            (src_id, dst_id)
+        } else {
+            (dst_id, src_id)
+        }
+    }
+}
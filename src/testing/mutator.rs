@@ -0,0 +1,153 @@
+use magnitude::Magnitude;
+
+use crate::testing::Model;
+
+// Default maximum number of vertices, so random graphs stay small and dense.
+const DEFAULT_MAX_VERTEX_COUNT: usize = 16;
+
+/// An operation that mutates a storage.
+///
+/// ## Generic Parameters
+/// `W`: **W**eight type associated with edges.
+#[derive(Debug, Clone)]
+pub enum Op<W> {
+    AddVertex,
+    RemoveVertex(usize),
+    /// In the format of: (`src_id`, `dst_id`, `weight`).
+    AddEdge(usize, usize, Magnitude<W>),
+    /// In the format of: (`src_id`, `dst_id`, `edge_id`, `weight`).
+    UpdateEdge(usize, usize, usize, Magnitude<W>),
+    /// In the format of: (`src_id`, `dst_id`, `edge_id`).
+    RemoveEdge(usize, usize, usize),
+}
+
+/// Generates random operations based on the current state of a [`Model`](crate::testing::Model).
+///
+/// Most operations are valid for the model. But some of them intentionally refer to vertices that do not exist, so storages are expected to reject them.
+/// Generated edges are never loops, but there may be multiple edges between two vertices.
+///
+/// ## Generic Parameters
+/// `R`: **R**ng that when called with `n`, returns a uniformly random number in range of 0 to `n` - 1.
+///
+/// # Examples
+/// ```
+/// use prepona::testing::{Model, Op, RandomMutator};
+/// use prepona::util::seeded;
+///
+/// let mut mutator = RandomMutator::init(seeded(7));
+///
+/// let model = Model::<usize>::init(true);
+/// assert!(matches!(mutator.next_op(&model), Op::AddVertex));
+/// ```
+pub struct RandomMutator<R> {
+    rng: R,
+    max_vertex_count: usize,
+}
+
+impl<R: FnMut(usize) -> usize> RandomMutator<R> {
+    /// # Arguments
+    /// `rng`: Source of randomness: when called with `n`, returns a uniformly random number in range of 0 to `n` - 1.
+    ///
+    /// # Returns
+    /// Mutator that keeps the number of vertices at most 16.
+    pub fn init(rng: R) -> Self {
+        RandomMutator {
+            rng,
+            max_vertex_count: DEFAULT_MAX_VERTEX_COUNT,
+        }
+    }
+
+    /// # Arguments
+    /// `max_vertex_count`: Maximum number of vertices. It's at least 2 so edges can be added.
+    ///
+    /// # Returns
+    /// The mutator that stops adding vertices when model has `max_vertex_count` vertices.
+    pub fn with_max_vertex_count(mut self, max_vertex_count: usize) -> Self {
+        self.max_vertex_count = max_vertex_count.max(2);
+
+        self
+    }
+
+    /// # Arguments
+    /// `model`: Current state of the storage.
+    ///
+    /// # Returns
+    /// Random operation to be applied on the storage.
+    pub fn next_op<W: From<u8> + Clone>(&mut self, model: &Model<W>) -> Op<W> {
+        let vertices = model.vertices();
+        let edges = model.edges();
+
+        if vertices.len() < 2 {
+            return Op::AddVertex;
+        }
+
+        match (self.rng)(12) {
+            0 | 1 if vertices.len() < self.max_vertex_count => Op::AddVertex,
+            2 => Op::RemoveVertex(self.pick(&vertices)),
+            3 | 4 if !edges.is_empty() => {
+                let (src_id, dst_id, edge_id, _) = edges[(self.rng)(edges.len())];
+                let (src_id, dst_id) = self.maybe_swap(model, src_id, dst_id);
+
+                Op::UpdateEdge(src_id, dst_id, edge_id, self.weight())
+            }
+            5 | 6 if !edges.is_empty() => {
+                let (src_id, dst_id, edge_id, _) = edges[(self.rng)(edges.len())];
+                let (src_id, dst_id) = self.maybe_swap(model, src_id, dst_id);
+
+                Op::RemoveEdge(src_id, dst_id, edge_id)
+            }
+            7 => {
+                let missing_id = self.missing_vertex(model, &vertices);
+
+                if (self.rng)(2) == 0 {
+                    Op::RemoveVertex(missing_id)
+                } else {
+                    Op::AddEdge(self.pick(&vertices), missing_id, self.weight())
+                }
+            }
+            _ => {
+                let src_index = (self.rng)(vertices.len());
+                let mut dst_index = (self.rng)(vertices.len() - 1);
+                if dst_index >= src_index {
+                    dst_index += 1;
+                }
+
+                Op::AddEdge(vertices[src_index], vertices[dst_index], self.weight())
+            }
+        }
+    }
+
+    fn pick(&mut self, ids: &[usize]) -> usize {
+        ids[(self.rng)(ids.len())]
+    }
+
+    fn weight<W: From<u8>>(&mut self) -> Magnitude<W> {
+        W::from((self.rng)(100) as u8).into()
+    }
+
+    // Undirected edges can be referred to from either of their end points.
+    fn maybe_swap<W: Clone>(
+        &mut self,
+        model: &Model<W>,
+        src_id: usize,
+        dst_id: usize,
+    ) -> (usize, usize) {
+        if !model.is_directed() && (self.rng)(2) == 0 {
+            (dst_id, src_id)
+        } else {
+            (src_id, dst_id)
+        }
+    }
+
+    // Picks an id in range of 0 to maximum id + 1 which is not in the model. Ids of removed vertices are more likely to be picked this way.
+    fn missing_vertex<W: Clone>(&mut self, model: &Model<W>, vertices: &[usize]) -> usize {
+        let max_id = vertices.last().copied().unwrap_or(0);
+        let vertex_id = (self.rng)(max_id + 2);
+
+        if model.contains_vertex(vertex_id) {
+            max_id + 1
+        } else {
+            vertex_id
+        }
+    }
+}
@@ -0,0 +1,379 @@
+/// Binary min-heap of ids, where each id has a key and keys can be decreased in place.
+///
+/// Each id is in the heap at most once. Ids are used as indexes to find their position in the heap,
+/// so they should be small and continuous, like virtual ids of an [`IdMap`](crate::provide::IdMap).
+/// Ids with equal keys are popped in increasing order of ids, so the order of pops only depends on the keys.
+///
+/// # Examples
+/// ```
+/// use prepona::util::IndexedHeap;
+///
+/// let mut heap = IndexedHeap::init();
+/// heap.push(0, 7);
+/// heap.push(1, 5);
+/// heap.push(2, 9);
+///
+/// // Key of 2 is decreased, instead of pushing it again.
+/// assert!(heap.push_or_decrease(2, 3));
+/// assert!(!heap.push_or_decrease(0, 8));
+///
+/// assert_eq!(heap.pop(), Some((2, 3)));
+/// assert_eq!(heap.pop(), Some((1, 5)));
+/// assert_eq!(heap.pop(), Some((0, 7)));
+/// assert_eq!(heap.pop(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct IndexedHeap<K> {
+    entries: Vec<(K, usize)>,
+
+    // Position of each id in `entries`, or `ABSENT` if id is not in the heap.
+    positions: Vec<usize>,
+}
+
+const ABSENT: usize = usize::MAX;
+
+impl<K: Ord> IndexedHeap<K> {
+    /// # Returns
+    /// An empty heap.
+    pub fn init() -> Self {
+        IndexedHeap {
+            entries: vec![],
+            positions: vec![],
+        }
+    }
+
+    /// # Arguments
+    /// `id_count`: Ids that are going to be pushed are less than `id_count`.
+    ///
+    /// # Returns
+    /// An empty heap that does not need to allocate when ids less than `id_count` are pushed.
+    pub fn with_capacity(id_count: usize) -> Self {
+        IndexedHeap {
+            entries: Vec::with_capacity(id_count),
+            positions: vec![ABSENT; id_count],
+        }
+    }
+
+    /// # Returns
+    /// Number of ids in the heap.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// # Returns
+    /// `true` if there is no id in the heap, `false` otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// # Arguments
+    /// `id`: Id to look for.
+    ///
+    /// # Returns
+    /// `true` if `id` is in the heap, `false` otherwise.
+    pub fn contains(&self, id: usize) -> bool {
+        self.position_of(id).is_some()
+    }
+
+    /// # Arguments
+    /// `id`: Id to get its key.
+    ///
+    /// # Returns
+    /// * `Some`: Containing key of `id`.
+    /// * `None`: If `id` is not in the heap.
+    pub fn key_of(&self, id: usize) -> Option<&K> {
+        self.position_of(id).map(|position| &self.entries[position].0)
+    }
+
+    /// # Returns
+    /// * `Some`: Containing id with the smallest key and its key.
+    /// * `None`: If heap is empty.
+    pub fn peek(&self) -> Option<(usize, &K)> {
+        self.entries.first().map(|(key, id)| (*id, key))
+    }
+
+    /// # Arguments
+    /// * `id`: Id to be pushed.
+    /// * `key`: Key of `id`.
+    ///
+    /// # Panics
+    /// If `id` is already in the heap.
+    ///
+    /// # Complexity
+    /// O(log(n)), where n is the number of ids in the heap.
+    pub fn push(&mut self, id: usize, key: K) {
+        assert!(!self.contains(id), "Id {} is already in the heap", id);
+
+        if id >= self.positions.len() {
+            self.positions.resize(id + 1, ABSENT);
+        }
+
+        let position = self.entries.len();
+        self.entries.push((key, id));
+        self.positions[id] = position;
+
+        self.sift_up(position);
+    }
+
+    /// # Arguments
+    /// * `id`: Id to decrease its key.
+    /// * `key`: New key of `id`.
+    ///
+    /// # Panics
+    /// * If `id` is not in the heap.
+    /// * If `key` is greater than the current key of `id`.
+    ///
+    /// # Complexity
+    /// O(log(n)), where n is the number of ids in the heap.
+    pub fn decrease_key(&mut self, id: usize, key: K) {
+        let position = self
+            .position_of(id)
+            .unwrap_or_else(|| panic!("Id {} is not in the heap", id));
+
+        assert!(
+            key <= self.entries[position].0,
+            "New key of id {} is greater than its current key",
+            id
+        );
+
+        self.entries[position].0 = key;
+
+        self.sift_up(position);
+    }
+
+    /// Pushes `id` if it's not in the heap, or decreases its key if `key` is less than its current key.
+    ///
+    /// # Arguments
+    /// * `id`: Id to be pushed or updated.
+    /// * `key`: Key of `id`.
+    ///
+    /// # Returns
+    /// `true` if `id` is pushed or its key is decreased, `false` otherwise.
+    ///
+    /// # Complexity
+    /// O(log(n)), where n is the number of ids in the heap.
+    pub fn push_or_decrease(&mut self, id: usize, key: K) -> bool {
+        match self.position_of(id) {
+            None => self.push(id, key),
+            Some(position) if key < self.entries[position].0 => self.decrease_key(id, key),
+            Some(_) => return false,
+        }
+
+        true
+    }
+
+    /// Removes id with the smallest key from the heap.
+    ///
+    /// # Returns
+    /// * `Some`: Containing removed id and its key.
+    /// * `None`: If heap is empty.
+    ///
+    /// # Complexity
+    /// O(log(n)), where n is the number of ids in the heap.
+    pub fn pop(&mut self) -> Option<(usize, K)> {
+        if self.entries.is_empty() {
+            return None;
+        }
+
+        let last = self.entries.len() - 1;
+        self.swap(0, last);
+
+        let (key, id) = self.entries.pop().unwrap();
+        self.positions[id] = ABSENT;
+
+        if !self.entries.is_empty() {
+            self.sift_down(0);
+        }
+
+        Some((id, key))
+    }
+
+    /// Removes all ids from the heap.
+    pub fn clear(&mut self) {
+        for (_, id) in self.entries.drain(..) {
+            self.positions[id] = ABSENT;
+        }
+    }
+
+    fn position_of(&self, id: usize) -> Option<usize> {
+        self.positions
+            .get(id)
+            .copied()
+            .filter(|position| *position != ABSENT)
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        self.entries.swap(i, j);
+
+        self.positions[self.entries[i].1] = i;
+        self.positions[self.entries[j].1] = j;
+    }
+
+    fn sift_up(&mut self, mut position: usize) {
+        while position > 0 {
+            let parent = (position - 1) / 2;
+            if self.entries[position] >= self.entries[parent] {
+                break;
+            }
+
+            self.swap(position, parent);
+            position = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut position: usize) {
+        loop {
+            let left = 2 * position + 1;
+            let right = left + 1;
+
+            let mut smallest = position;
+            if left < self.entries.len() && self.entries[left] < self.entries[smallest] {
+                smallest = left;
+            }
+            if right < self.entries.len() && self.entries[right] < self.entries[smallest] {
+                smallest = right;
+            }
+
+            if smallest == position {
+                break;
+            }
+
+            self.swap(position, smallest);
+            position = smallest;
+        }
+    }
+}
+
+impl<K: Ord> Default for IndexedHeap<K> {
+    fn default() -> Self {
+        IndexedHeap::init()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_heap() {
+        let mut heap = IndexedHeap::<usize>::init();
+
+        assert!(heap.is_empty());
+        assert_eq!(heap.len(), 0);
+        assert_eq!(heap.peek(), None);
+        assert_eq!(heap.pop(), None);
+        assert!(!heap.contains(0));
+    }
+
+    #[test]
+    fn pops_in_order_of_keys_then_ids() {
+        // Given: Heap with repeated keys.
+        let mut heap = IndexedHeap::with_capacity(4);
+        heap.push(10, 2);
+        heap.push(3, 1);
+        heap.push(7, 2);
+        heap.push(0, 2);
+        heap.push(5, 0);
+
+        // When: Popping all ids.
+        let popped: Vec<(usize, usize)> = std::iter::from_fn(|| heap.pop()).collect();
+
+        // Then:
+        assert_eq!(popped, vec![(5, 0), (3, 1), (0, 2), (7, 2), (10, 2)]);
+        assert!(!heap.contains(10));
+    }
+
+    #[test]
+    fn decrease_key() {
+        // Given: Heap with three ids.
+        let mut heap = IndexedHeap::init();
+        heap.push(0, 5);
+        heap.push(1, 6);
+        heap.push(2, 7);
+
+        // When: Decreasing key of the last id.
+        heap.decrease_key(2, 1);
+
+        // Then:
+        assert_eq!(heap.key_of(2), Some(&1));
+        assert_eq!(heap.peek(), Some((2, &1)));
+        assert_eq!(heap.len(), 3);
+    }
+
+    #[test]
+    fn push_or_decrease() {
+        let mut heap = IndexedHeap::init();
+
+        assert!(heap.push_or_decrease(4, 3));
+        assert!(!heap.push_or_decrease(4, 3));
+        assert!(!heap.push_or_decrease(4, 8));
+        assert!(heap.push_or_decrease(4, 2));
+
+        assert_eq!(heap.pop(), Some((4, 2)));
+        assert!(heap.push_or_decrease(4, 9));
+        assert_eq!(heap.key_of(4), Some(&9));
+    }
+
+    #[test]
+    fn matches_sorting_on_random_operations() {
+        // Given: Random pushes and decreases, tracked in a plain vector.
+        let mut seed = 3_u64;
+        let mut rng = |bound: usize| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+
+            (seed >> 33) as usize % bound
+        };
+        let mut heap = IndexedHeap::init();
+        let mut expected: Vec<Option<usize>> = vec![None; 100];
+        for _ in 0..1000 {
+            let id = rng(100);
+            let key = rng(1000);
+            heap.push_or_decrease(id, key);
+            expected[id] = Some(expected[id].map_or(key, |current| current.min(key)));
+        }
+
+        // When: Popping all ids.
+        let popped: Vec<(usize, usize)> = std::iter::from_fn(|| heap.pop()).collect();
+
+        // Then:
+        let mut expected: Vec<(usize, usize)> = expected
+            .into_iter()
+            .enumerate()
+            .filter_map(|(id, key)| key.map(|key| (id, key)))
+            .collect();
+        expected.sort_by_key(|(id, key)| (*key, *id));
+        assert_eq!(popped, expected);
+    }
+
+    #[test]
+    fn clear() {
+        let mut heap = IndexedHeap::init();
+        heap.push(1, 'b');
+        heap.push(0, 'a');
+
+        heap.clear();
+
+        assert!(heap.is_empty());
+        assert!(!heap.contains(0));
+        heap.push(0, 'c');
+        assert_eq!(heap.pop(), Some((0, 'c')));
+    }
+
+    #[test]
+    #[should_panic]
+    fn push_twice() {
+        let mut heap = IndexedHeap::init();
+        heap.push(0, 1);
+        heap.push(0, 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn increase_key() {
+        let mut heap = IndexedHeap::init();
+        heap.push(0, 1);
+        heap.decrease_key(0, 2);
+    }
+}
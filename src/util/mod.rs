@@ -0,0 +1,9 @@
+mod indexed_heap;
+mod random;
+
+pub use indexed_heap::IndexedHeap;
+pub(crate) use random::unit;
+pub use random::{seeded, SplitMix64};
+
+#[cfg(feature = "rand")]
+pub use random::from_rng;
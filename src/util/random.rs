@@ -0,0 +1,209 @@
+// Number of distinct values that `unit` draws from. It fits in `usize` of 32-bit targets.
+const UNIT_RESOLUTION: usize = 1 << 30;
+
+/// Small and fast pseudo random generator(SplitMix64), that produces the same sequence of numbers for the same seed on every platform.
+///
+/// Randomized algorithms of prepona take their randomness as a closure of type `FnMut(usize) -> usize`, which returns a number in range of `0..bound` given `bound`.
+/// [`seeded`](crate::util::seeded) creates such a closure from a seed, for reproducible experiments.
+/// With the `rand` feature, `from_rng` creates one from any `rand::Rng`, and this generator implements `rand::RngCore`.
+///
+/// # Examples
+/// ```
+/// use prepona::util::SplitMix64;
+///
+/// let mut first = SplitMix64::init(42);
+/// let mut second = SplitMix64::init(42);
+///
+/// assert_eq!(first.next_u64(), second.next_u64());
+/// assert!(first.next_below(10) < 10);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// # Arguments
+    /// `seed`: Seed of the generator.
+    ///
+    /// # Returns
+    /// Generator whose sequence of numbers is determined by `seed`.
+    pub fn init(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+
+    /// # Returns
+    /// Next number of the sequence.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+        z ^ (z >> 31)
+    }
+
+    /// # Arguments
+    /// `bound`: Exclusive upper bound of the number.
+    ///
+    /// # Returns
+    /// Next number of the sequence, mapped to range of `0..bound`.
+    ///
+    /// # Panics
+    /// If `bound` is zero.
+    pub fn next_below(&mut self, bound: usize) -> usize {
+        assert!(bound > 0, "Bound must be positive");
+
+        // Takes the high bits of the product, which are uniform enough without the bias of taking the remainder.
+        ((self.next_u64() as u128 * bound as u128) >> 64) as usize
+    }
+
+    /// # Returns
+    /// Next number of the sequence, mapped to range of [0, 1) using its 53 high bits.
+    pub fn next_unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// # Arguments
+/// `seed`: Seed of the generator.
+///
+/// # Returns
+/// Closure that returns a pseudo random number in range of `0..bound` given `bound`, as accepted by randomized algorithms.
+/// Closures with the same seed return the same numbers.
+///
+/// # Examples
+/// ```
+/// use prepona::prelude::*;
+/// use prepona::algo::walk::Node2Vec;
+/// use prepona::storage::Mat;
+/// use prepona::graph::MatGraph;
+/// use prepona::util::seeded;
+///
+/// let mut graph = MatGraph::init(Mat::<usize>::init());
+/// let a = graph.add_vertex();
+/// let b = graph.add_vertex();
+/// let c = graph.add_vertex();
+/// graph.add_edge_unchecked(a, b, 1.into());
+/// graph.add_edge_unchecked(b, c, 1.into());
+///
+/// let node2vec = Node2Vec::init(&graph);
+///
+/// assert_eq!(
+///     node2vec.execute(&graph, &mut seeded(7)),
+///     node2vec.execute(&graph, &mut seeded(7))
+/// );
+/// ```
+pub fn seeded(seed: u64) -> impl FnMut(usize) -> usize {
+    let mut generator = SplitMix64::init(seed);
+
+    move |bound| generator.next_below(bound)
+}
+
+/// # Arguments
+/// `rng`: Any generator of the rand ecosystem. Pass `&mut rng` to keep using it afterwards.
+///
+/// # Returns
+/// Closure that returns a random number in range of `0..bound` given `bound`, as accepted by randomized algorithms.
+#[cfg(feature = "rand")]
+pub fn from_rng<R: rand::Rng>(mut rng: R) -> impl FnMut(usize) -> usize {
+    move |bound| rng.gen_range(0..bound)
+}
+
+#[cfg(feature = "rand")]
+impl rand::RngCore for SplitMix64 {
+    fn next_u32(&mut self) -> u32 {
+        (SplitMix64::next_u64(self) >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        SplitMix64::next_u64(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = SplitMix64::next_u64(self).to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+
+        Ok(())
+    }
+}
+
+// Draws a number in range of [0, 1) using `rng`.
+pub(crate) fn unit<R: FnMut(usize) -> usize>(rng: &mut R) -> f64 {
+    rng(UNIT_RESOLUTION) as f64 / UNIT_RESOLUTION as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let mut first = seeded(3);
+        let mut second = seeded(3);
+        let mut other = seeded(4);
+
+        let first = (0..20).map(|_| first(1000)).collect::<Vec<usize>>();
+        let second = (0..20).map(|_| second(1000)).collect::<Vec<usize>>();
+        let other = (0..20).map(|_| other(1000)).collect::<Vec<usize>>();
+
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+        assert!(first.iter().all(|number| *number < 1000));
+    }
+
+    #[test]
+    fn numbers_cover_the_range() {
+        // Given:
+        let mut rng = seeded(0);
+        let mut counts = [0; 4];
+
+        // When:
+        for _ in 0..4000 {
+            counts[rng(4)] += 1;
+        }
+
+        // Then:
+        assert!(counts.iter().all(|count| (800..1200).contains(count)));
+    }
+
+    #[test]
+    fn unit_is_in_range() {
+        let mut rng = seeded(1);
+        let mut generator = SplitMix64::init(1);
+
+        assert!((0..100)
+            .map(|_| unit(&mut rng))
+            .all(|number| (0.0..1.0).contains(&number)));
+        assert!((0..100)
+            .map(|_| generator.next_unit())
+            .all(|number| (0.0..1.0).contains(&number)));
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn rand_generators() {
+        use rand::{Rng, SeedableRng};
+
+        // Given:
+        let mut rng = rand::rngs::StdRng::seed_from_u64(5);
+        let mut split_mix = SplitMix64::init(5);
+
+        // When:
+        let numbers = (0..20)
+            .map(|_| from_rng(&mut rng)(10))
+            .collect::<Vec<usize>>();
+        let number: f64 = split_mix.gen();
+
+        // Then:
+        assert!(numbers.iter().all(|number| *number < 10));
+        assert!((0.0..1.0).contains(&number));
+    }
+}
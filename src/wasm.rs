@@ -0,0 +1,319 @@
+use anyhow::Result;
+use magnitude::Magnitude;
+use wasm_bindgen::prelude::*;
+
+use crate::algo::{AStar, Dijkstra};
+use crate::bindings::{with_graph, Inner};
+use crate::graph::{Edge, Error};
+use crate::provide::{Edges, Graph, Vertices};
+
+/// Graph with `u32` weights that can be built and analyzed from JavaScript.
+///
+/// Results of the algorithms are returned as JSON strings, so they can be passed to `JSON.parse` and fed to visualization libraries.
+/// Ids of the vertices are the ones returned by `addVertex`, and errors are thrown as strings describing what went wrong.
+///
+/// # Examples
+/// ```js
+/// const graph = new JsGraph(false);
+/// const a = graph.addVertex();
+/// const b = graph.addVertex();
+/// graph.addEdge(a, b, 3);
+///
+/// JSON.parse(graph.dijkstra(a)); // { "0": 0, "1": 3 }
+/// ```
+#[wasm_bindgen]
+pub struct JsGraph {
+    inner: Inner<u32>,
+}
+
+#[wasm_bindgen]
+impl JsGraph {
+    /// # Arguments
+    /// `directed`: If `true` edges are directed, otherwise they are undirected.
+    ///
+    /// # Returns
+    /// An empty graph.
+    #[wasm_bindgen(constructor)]
+    pub fn init(directed: bool) -> Self {
+        let inner = Inner::init(directed);
+
+        JsGraph { inner }
+    }
+
+    /// # Returns
+    /// `true` if edges of the graph are directed, `false` otherwise.
+    #[wasm_bindgen(js_name = isDirected)]
+    pub fn is_directed(&self) -> bool {
+        self.inner.is_directed()
+    }
+
+    /// # Returns
+    /// Id of the new vertex.
+    #[wasm_bindgen(js_name = addVertex)]
+    pub fn add_vertex(&mut self) -> usize {
+        with_graph!(&mut self.inner, graph => graph.add_vertex())
+    }
+
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    /// * `weight`: Weight of the edge.
+    ///
+    /// # Returns
+    /// Id of the new edge.
+    /// Throws if any of the vertices is not in the graph, or the edge is not allowed, like a loop or a second edge between two vertices.
+    #[wasm_bindgen(js_name = addEdge)]
+    pub fn add_edge(
+        &mut self,
+        src_id: usize,
+        dst_id: usize,
+        weight: u32,
+    ) -> Result<usize, JsValue> {
+        with_graph!(&mut self.inner, graph => graph.add_edge(src_id, dst_id, weight.into()))
+            .map_err(to_js_error)
+    }
+
+    /// # Returns
+    /// Number of vertices in the graph.
+    #[wasm_bindgen(js_name = vertexCount)]
+    pub fn vertex_count(&self) -> usize {
+        with_graph!(&self.inner, graph => graph.vertex_count())
+    }
+
+    /// # Returns
+    /// Number of edges in the graph.
+    #[wasm_bindgen(js_name = edgeCount)]
+    pub fn edge_count(&self) -> usize {
+        with_graph!(&self.inner, graph => graph.edges_count())
+    }
+
+    /// # Arguments
+    /// `src_id`: Id of the source vertex.
+    ///
+    /// # Returns
+    /// JSON object mapping id of each vertex to its distance from `src_id`, which is `null` for vertices that are not reachable.
+    /// Throws if `src_id` is not in the graph or a distance does not fit in `u32`.
+    pub fn dijkstra(&self, src_id: usize) -> Result<String, JsValue> {
+        self.dijkstra_json(src_id).map_err(to_js_error)
+    }
+
+    /// Finds shortest path between two vertices, using positions of the vertices to guide the search.
+    ///
+    /// Straight line distance between positions of a vertex and `dst_id` is used as the heuristic, so it's only correct if weight of each edge is at least the distance between positions of its end points.
+    /// Pass empty arrays to search without positions.
+    ///
+    /// # Arguments
+    /// * `src_id`: Id of the source vertex.
+    /// * `dst_id`: Id of the destination vertex.
+    /// * `xs`: X coordinate of each vertex, indexed by its id.
+    /// * `ys`: Y coordinate of each vertex, indexed by its id.
+    ///
+    /// # Returns
+    /// JSON object with `path` containing ids of the vertices on the shortest path(including `src_id` and `dst_id`), and its `distance`.
+    /// It's `null` if `dst_id` is not reachable from `src_id`.
+    /// Throws if any of the vertices is not in the graph or length of the path does not fit in `u32`.
+    #[wasm_bindgen(js_name = aStar)]
+    pub fn a_star(
+        &self,
+        src_id: usize,
+        dst_id: usize,
+        xs: &[f64],
+        ys: &[f64],
+    ) -> Result<String, JsValue> {
+        self.a_star_json(src_id, dst_id, xs, ys)
+            .map_err(to_js_error)
+    }
+
+    /// Connected components of undirected graphs, and weakly connected components of directed graphs.
+    ///
+    /// # Returns
+    /// JSON array where each element is an array of ids of the vertices in a component.
+    #[wasm_bindgen(js_name = connectedComponents)]
+    pub fn connected_components(&self) -> String {
+        let components = self
+            .inner
+            .components()
+            .iter()
+            .map(|component| ids_to_json(component))
+            .collect::<Vec<String>>();
+
+        format!("[{}]", components.join(","))
+    }
+
+    /// # Returns
+    /// JSON object describing the graph, in the form of:
+    /// `{"directed": bool, "vertices": [id], "edges": [{"id": id, "src": id, "dst": id, "weight": number}]}`
+    #[wasm_bindgen(js_name = toJson)]
+    pub fn to_json(&self) -> String {
+        let (vertices, edges) = with_graph!(&self.inner, graph => {
+            let edges = graph
+                .edges()
+                .into_iter()
+                .map(|(src_id, dst_id, edge)| {
+                    format!(
+                        "{{\"id\":{},\"src\":{},\"dst\":{},\"weight\":{}}}",
+                        edge.get_id(),
+                        src_id,
+                        dst_id,
+                        magnitude_to_json(*edge.get_weight())
+                    )
+                })
+                .collect::<Vec<String>>();
+
+            (graph.vertices(), edges)
+        });
+
+        format!(
+            "{{\"directed\":{},\"vertices\":{},\"edges\":[{}]}}",
+            self.is_directed(),
+            ids_to_json(&vertices),
+            edges.join(",")
+        )
+    }
+}
+
+impl JsGraph {
+    fn dijkstra_json(&self, src_id: usize) -> Result<String> {
+        let distances = with_graph!(&self.inner, graph => {
+            if !graph.contains_vertex(src_id) {
+                Err(Error::new_vnf(src_id))?
+            }
+
+            let sp_subgraph = Dijkstra::init(graph).execute_checked(graph, src_id)?;
+
+            graph
+                .vertices()
+                .into_iter()
+                .map(|vertex_id| {
+                    let distance = sp_subgraph
+                        .distance_to(vertex_id)
+                        .unwrap_or(Magnitude::PosInfinite);
+
+                    format!("\"{}\":{}", vertex_id, magnitude_to_json(distance))
+                })
+                .collect::<Vec<String>>()
+        });
+
+        Ok(format!("{{{}}}", distances.join(",")))
+    }
+
+    fn a_star_json(&self, src_id: usize, dst_id: usize, xs: &[f64], ys: &[f64]) -> Result<String> {
+        let position_of = |vertex_id: usize| match (xs.get(vertex_id), ys.get(vertex_id)) {
+            (Some(x), Some(y)) => Some((*x, *y)),
+            _ => None,
+        };
+        let dst_position = position_of(dst_id);
+
+        // Rounding down keeps the estimate from exceeding the straight line distance.
+        let heuristic = |vertex_id: usize| match (position_of(vertex_id), dst_position) {
+            (Some((x, y)), Some((dst_x, dst_y))) => (x - dst_x).hypot(y - dst_y).floor() as u32,
+            _ => 0,
+        };
+
+        let shortest_path = with_graph!(&self.inner, graph => {
+            AStar::init(graph).execute(graph, src_id, dst_id, heuristic)?
+        });
+
+        Ok(match shortest_path {
+            Some((path, distance)) => format!(
+                "{{\"path\":{},\"distance\":{}}}",
+                ids_to_json(&path),
+                magnitude_to_json(distance)
+            ),
+            None => "null".to_string(),
+        })
+    }
+}
+
+fn to_js_error(error: anyhow::Error) -> JsValue {
+    JsValue::from_str(&error.to_string())
+}
+
+fn ids_to_json(ids: &[usize]) -> String {
+    let ids = ids.iter().map(|id| id.to_string()).collect::<Vec<String>>();
+
+    format!("[{}]", ids.join(","))
+}
+
+// Infinite values have no representation in JSON, so they are written as `null`.
+fn magnitude_to_json(value: Magnitude<u32>) -> String {
+    match value {
+        Magnitude::Finite(value) => value.to_string(),
+        _ => "null".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Path a --(1)--> b --(2)--> c and an isolated vertex d.
+    fn path_graph(directed: bool) -> (JsGraph, Vec<usize>) {
+        let mut graph = JsGraph::init(directed);
+        let ids = (0..4).map(|_| graph.add_vertex()).collect::<Vec<usize>>();
+        graph.add_edge(ids[0], ids[1], 1).unwrap();
+        graph.add_edge(ids[1], ids[2], 2).unwrap();
+
+        (graph, ids)
+    }
+
+    #[test]
+    fn build_graph() {
+        let (graph, _) = path_graph(true);
+
+        assert!(graph.is_directed());
+        assert_eq!(graph.vertex_count(), 4);
+        assert_eq!(graph.edge_count(), 2);
+    }
+
+    #[test]
+    fn dijkstra_marks_unreachable_vertices_with_null() {
+        let (graph, ids) = path_graph(true);
+
+        let distances = graph.dijkstra(ids[1]).unwrap();
+
+        assert_eq!(distances, "{\"0\":null,\"1\":0,\"2\":2,\"3\":null}");
+    }
+
+    #[test]
+    fn a_star_with_and_without_positions() {
+        // Given:
+        let (graph, ids) = path_graph(false);
+        let xs = [0.0, 1.0, 3.0, 10.0];
+        let ys = [0.0; 4];
+
+        // When:
+        let with_positions = graph.a_star(ids[0], ids[2], &xs, &ys).unwrap();
+        let without_positions = graph.a_star(ids[2], ids[0], &[], &[]).unwrap();
+        let unreachable = graph.a_star(ids[0], ids[3], &xs, &ys).unwrap();
+
+        // Then:
+        assert_eq!(with_positions, "{\"path\":[0,1,2],\"distance\":3}");
+        assert_eq!(without_positions, "{\"path\":[2,1,0],\"distance\":3}");
+        assert_eq!(unreachable, "null");
+    }
+
+    #[test]
+    fn connected_components_ignore_direction() {
+        let (graph, _) = path_graph(true);
+
+        let mut components = graph.inner.components();
+        components
+            .iter_mut()
+            .for_each(|component| component.sort_unstable());
+        components.sort_unstable();
+
+        assert_eq!(components, vec![vec![0, 1, 2], vec![3]]);
+        assert_eq!(graph.connected_components().matches('[').count(), 3);
+    }
+
+    #[test]
+    fn to_json() {
+        let (graph, _) = path_graph(false);
+
+        assert_eq!(
+            graph.to_json(),
+            "{\"directed\":false,\"vertices\":[0,1,2,3],\"edges\":[{\"id\":0,\"src\":0,\"dst\":1,\"weight\":1},{\"id\":1,\"src\":1,\"dst\":2,\"weight\":2}]}"
+        );
+    }
+}